@@ -0,0 +1,130 @@
+-- Base schema for the integration test database (docker-compose.it.yml).
+-- Mirrors the tables the station node owns; scripts/migrations/* run on top of it.
+
+CREATE TABLE IF NOT EXISTS staff (
+    id TEXT PRIMARY KEY,
+    cin TEXT NOT NULL UNIQUE,
+    phone_number TEXT NOT NULL DEFAULT '',
+    first_name TEXT NOT NULL,
+    last_name TEXT NOT NULL,
+    role TEXT NOT NULL DEFAULT 'WORKER',
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS vehicles (
+    id TEXT PRIMARY KEY,
+    license_plate TEXT NOT NULL UNIQUE,
+    capacity INTEGER NOT NULL DEFAULT 8,
+    phone_number TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    is_available BOOLEAN NOT NULL DEFAULT true,
+    is_banned BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS routes (
+    id TEXT PRIMARY KEY,
+    station_id TEXT NOT NULL UNIQUE,
+    station_name TEXT NOT NULL,
+    base_price DOUBLE PRECISION NOT NULL,
+    governorate TEXT,
+    governorate_ar TEXT,
+    delegation TEXT,
+    delegation_ar TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS vehicle_authorized_stations (
+    id TEXT PRIMARY KEY,
+    vehicle_id TEXT NOT NULL REFERENCES vehicles(id) ON DELETE CASCADE,
+    station_id TEXT NOT NULL,
+    station_name TEXT,
+    priority INTEGER NOT NULL DEFAULT 1,
+    is_default BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (vehicle_id, station_id)
+);
+
+CREATE TABLE IF NOT EXISTS vehicle_queue (
+    id TEXT PRIMARY KEY,
+    vehicle_id TEXT NOT NULL REFERENCES vehicles(id),
+    destination_id TEXT NOT NULL,
+    destination_name TEXT NOT NULL,
+    sub_route TEXT,
+    sub_route_name TEXT,
+    queue_type TEXT NOT NULL DEFAULT 'REGULAR',
+    queue_position INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'WAITING',
+    entered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    available_seats INTEGER NOT NULL,
+    total_seats INTEGER NOT NULL,
+    base_price DOUBLE PRECISION NOT NULL,
+    estimated_departure TIMESTAMPTZ,
+    actual_departure TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS vehicle_queue_history (
+    id SERIAL PRIMARY KEY,
+    license_plate TEXT NOT NULL,
+    destination_id TEXT NOT NULL,
+    destination_name TEXT NOT NULL,
+    sub_route TEXT,
+    sub_route_name TEXT,
+    total_seats INTEGER NOT NULL,
+    base_price DOUBLE PRECISION NOT NULL,
+    entered_at TIMESTAMPTZ,
+    exit_time TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    status TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS bookings (
+    id TEXT PRIMARY KEY,
+    queue_id TEXT NOT NULL,
+    seats_booked INTEGER NOT NULL,
+    total_amount DOUBLE PRECISION NOT NULL,
+    refund_amount DOUBLE PRECISION,
+    sub_route TEXT,
+    sub_route_name TEXT,
+    booking_source TEXT NOT NULL,
+    booking_type TEXT NOT NULL,
+    payment_status TEXT NOT NULL,
+    payment_method TEXT NOT NULL,
+    verification_code TEXT NOT NULL UNIQUE,
+    is_verified BOOLEAN NOT NULL DEFAULT false,
+    created_offline BOOLEAN NOT NULL DEFAULT false,
+    created_by TEXT REFERENCES staff(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS exit_passes (
+    id TEXT PRIMARY KEY,
+    queue_id TEXT,
+    vehicle_id TEXT NOT NULL REFERENCES vehicles(id),
+    license_plate TEXT NOT NULL,
+    destination_id TEXT NOT NULL,
+    destination_name TEXT NOT NULL,
+    current_exit_time TIMESTAMPTZ NOT NULL,
+    created_by TEXT REFERENCES staff(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS day_passes (
+    id TEXT PRIMARY KEY,
+    vehicle_id TEXT NOT NULL REFERENCES vehicles(id),
+    license_plate TEXT NOT NULL,
+    price DOUBLE PRECISION NOT NULL,
+    purchase_date TIMESTAMPTZ NOT NULL,
+    valid_from TIMESTAMPTZ NOT NULL,
+    valid_until TIMESTAMPTZ NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    is_expired BOOLEAN NOT NULL DEFAULT false,
+    created_by TEXT REFERENCES staff(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,41 @@
+-- One active day pass per vehicle per Tunisian calendar day.
+-- Two terminals entering the same plate at the same second could both insert a
+-- pass; the partial unique index lets inserts use ON CONFLICT instead.
+
+ALTER TABLE day_passes ADD COLUMN IF NOT EXISTS tunis_date DATE;
+
+UPDATE day_passes
+SET tunis_date = (purchase_date AT TIME ZONE 'Africa/Tunis')::date
+WHERE tunis_date IS NULL;
+
+-- Keep the oldest active pass of each day, deactivate the duplicates
+UPDATE day_passes dp
+SET is_active = false, updated_at = NOW()
+WHERE dp.is_active = true
+  AND EXISTS (
+      SELECT 1 FROM day_passes older
+      WHERE older.license_plate = dp.license_plate
+        AND older.tunis_date = dp.tunis_date
+        AND older.is_active = true
+        AND (older.created_at, older.id) < (dp.created_at, dp.id)
+  );
+
+-- Writers that do not set tunis_date (older app versions) still get it filled in
+CREATE OR REPLACE FUNCTION day_passes_set_tunis_date()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.tunis_date IS NULL THEN
+        NEW.tunis_date := (NEW.purchase_date AT TIME ZONE 'Africa/Tunis')::date;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS day_passes_set_tunis_date_trigger ON day_passes;
+CREATE TRIGGER day_passes_set_tunis_date_trigger
+    BEFORE INSERT ON day_passes
+    FOR EACH ROW EXECUTE FUNCTION day_passes_set_tunis_date();
+
+CREATE UNIQUE INDEX IF NOT EXISTS day_passes_plate_tunis_date_active_key
+    ON day_passes (license_plate, tunis_date)
+    WHERE is_active = true;
@@ -0,0 +1,32 @@
+-- Weekly and monthly passes alongside the day pass.
+-- A pass covers every Tunisian day from valid_from to valid_until.
+
+ALTER TABLE day_passes ADD COLUMN IF NOT EXISTS pass_type TEXT NOT NULL DEFAULT 'DAY';
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint WHERE conname = 'day_passes_pass_type_check'
+    ) THEN
+        ALTER TABLE day_passes
+            ADD CONSTRAINT day_passes_pass_type_check CHECK (pass_type IN ('DAY', 'WEEK', 'MONTH'));
+    END IF;
+END;
+$$;
+
+-- Price per pass type, editable from the app
+CREATE TABLE IF NOT EXISTS pass_prices (
+    pass_type TEXT PRIMARY KEY CHECK (pass_type IN ('DAY', 'WEEK', 'MONTH')),
+    price DOUBLE PRECISION NOT NULL CHECK (price >= 0),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO pass_prices (pass_type, price) VALUES
+    ('DAY', 2.0),
+    ('WEEK', 12.0),
+    ('MONTH', 45.0)
+ON CONFLICT (pass_type) DO NOTHING;
+
+CREATE INDEX IF NOT EXISTS day_passes_plate_validity_idx
+    ON day_passes (license_plate, valid_from, valid_until)
+    WHERE is_active = true;
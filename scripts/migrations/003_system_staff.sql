@@ -0,0 +1,6 @@
+-- Staff row used as created_by for writes made without a logged-in staff member
+-- (automatic day passes, background jobs). It is inactive so nobody can log in with it.
+
+INSERT INTO staff (id, cin, phone_number, first_name, last_name, role, is_active, created_at, updated_at)
+VALUES ('SYSTEM', 'SYSTEM', '', 'Système', 'Nqlix', 'WORKER', false, NOW(), NOW())
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,15 @@
+-- Per-staff PIN for switching cashiers on a shared terminal, and the audit log
+-- that records who switched to whom.
+
+ALTER TABLE staff ADD COLUMN IF NOT EXISTS pin_hash TEXT;
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id TEXT PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    actor_id TEXT,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_created_at_idx ON audit_log (created_at DESC);
+CREATE INDEX IF NOT EXISTS audit_log_event_type_idx ON audit_log (event_type, created_at DESC);
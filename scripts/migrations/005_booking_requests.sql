@@ -0,0 +1,11 @@
+-- Client-supplied request ids for booking calls, so a retried request returns
+-- the booking it already made instead of charging the customer twice.
+
+CREATE TABLE IF NOT EXISTS booking_requests (
+    request_id TEXT PRIMARY KEY,
+    result JSONB NOT NULL,
+    created_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS booking_requests_created_at_idx ON booking_requests (created_at DESC);
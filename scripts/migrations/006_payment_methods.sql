@@ -0,0 +1,29 @@
+-- Payment methods beyond cash: which ones the station accepts, and how each booking was
+-- paid. A split payment (cash + card) has one booking_payments row per method and
+-- bookings.payment_method = 'SPLIT'.
+
+CREATE TABLE IF NOT EXISTS station_payment_methods (
+    method TEXT PRIMARY KEY CHECK (method IN ('CASH', 'CARD', 'E_WALLET', 'ON_ACCOUNT')),
+    enabled BOOLEAN NOT NULL DEFAULT false,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO station_payment_methods (method, enabled) VALUES
+    ('CASH', true),
+    ('CARD', false),
+    ('E_WALLET', false),
+    ('ON_ACCOUNT', false)
+ON CONFLICT (method) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS booking_payments (
+    id TEXT PRIMARY KEY,
+    booking_id TEXT NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    method TEXT NOT NULL CHECK (method IN ('CASH', 'CARD', 'E_WALLET', 'ON_ACCOUNT')),
+    amount DOUBLE PRECISION NOT NULL CHECK (amount >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS booking_payments_booking_idx ON booking_payments (booking_id);
+
+-- Passes are sold at the same counter
+ALTER TABLE day_passes ADD COLUMN IF NOT EXISTS payment_method TEXT NOT NULL DEFAULT 'CASH';
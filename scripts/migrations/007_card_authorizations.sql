@@ -0,0 +1,5 @@
+-- Card payments approved on the LAN terminal keep its authorization code and transaction id,
+-- so the till can be reconciled against the acquirer's statement.
+
+ALTER TABLE booking_payments ADD COLUMN IF NOT EXISTS authorization_code TEXT;
+ALTER TABLE booking_payments ADD COLUMN IF NOT EXISTS terminal_transaction_id TEXT;
@@ -0,0 +1,29 @@
+-- Companies that book seats on account and settle monthly. What a customer owes is their
+-- ON_ACCOUNT booking payments minus what they have paid in customer_payments.
+
+CREATE TABLE IF NOT EXISTS customers (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    phone_number TEXT,
+    tax_id TEXT,
+    credit_limit DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (credit_limit >= 0),
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS customers_name_idx ON customers (lower(name));
+
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS customer_id TEXT REFERENCES customers(id);
+CREATE INDEX IF NOT EXISTS bookings_customer_idx ON bookings (customer_id) WHERE customer_id IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS customer_payments (
+    id TEXT PRIMARY KEY,
+    customer_id TEXT NOT NULL REFERENCES customers(id),
+    amount DOUBLE PRECISION NOT NULL CHECK (amount > 0),
+    reference TEXT,
+    created_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS customer_payments_customer_idx ON customer_payments (customer_id, created_at);
@@ -0,0 +1,33 @@
+-- Recurring bookings (a school taking 4 seats to Tunis every weekday). Each morning the
+-- active templates due that day are materialized into one HELD occurrence, which the
+-- cashier then books, skips or cancels.
+
+CREATE TABLE IF NOT EXISTS standing_reservations (
+    id TEXT PRIMARY KEY,
+    label TEXT NOT NULL,
+    destination_id TEXT NOT NULL,
+    destination_name TEXT NOT NULL,
+    seats INTEGER NOT NULL CHECK (seats > 0),
+    weekdays INTEGER[] NOT NULL, -- ISO weekdays, 1 = Monday .. 7 = Sunday
+    customer_id TEXT REFERENCES customers(id),
+    starts_on DATE NOT NULL,
+    ends_on DATE,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS reservation_occurrences (
+    id TEXT PRIMARY KEY,
+    reservation_id TEXT NOT NULL REFERENCES standing_reservations(id) ON DELETE CASCADE,
+    service_date DATE NOT NULL,
+    seats INTEGER NOT NULL CHECK (seats > 0),
+    status TEXT NOT NULL DEFAULT 'HELD' CHECK (status IN ('HELD', 'BOOKED', 'SKIPPED', 'CANCELLED')),
+    updated_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (reservation_id, service_date)
+);
+
+CREATE INDEX IF NOT EXISTS reservation_occurrences_date_idx ON reservation_occurrences (service_date, status);
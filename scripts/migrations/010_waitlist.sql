@@ -0,0 +1,18 @@
+-- Customers turned away for lack of seats. An entry is NOTIFIED when enough seats open up
+-- for it (a vehicle enters the queue or a booking is cancelled) and keeps that claim for a
+-- few minutes; after that it lapses as EXPIRED and the seats go to the next in line.
+
+CREATE TABLE IF NOT EXISTS waitlist_entries (
+    id TEXT PRIMARY KEY,
+    destination_id TEXT NOT NULL,
+    destination_name TEXT NOT NULL,
+    seats INTEGER NOT NULL CHECK (seats > 0),
+    phone_number TEXT,
+    status TEXT NOT NULL DEFAULT 'WAITING' CHECK (status IN ('WAITING', 'NOTIFIED', 'BOOKED', 'CANCELLED', 'EXPIRED')),
+    created_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    notified_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS waitlist_entries_destination_idx ON waitlist_entries (destination_id, status, created_at);
@@ -0,0 +1,14 @@
+-- Station-wide settings shared by every terminal of the station, one row per key.
+-- language: 'fr' or 'ar', used for command messages and tickets
+-- bilingual_tickets: 'true' to print every ticket label in French and Arabic
+
+CREATE TABLE IF NOT EXISTS station_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO station_settings (key, value) VALUES
+    ('language', 'fr'),
+    ('bilingual_tickets', 'false')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,31 @@
+-- Fare modifiers set by supervisors: reduced fares for children and luggage supplements.
+-- A rule is a fixed amount or a percent of the route base price, per child seat or per bag;
+-- destination_id NULL applies it to every destination. booking_fare_modifiers keeps what
+-- each booking was charged under each rule, with the label as it was printed.
+
+CREATE TABLE IF NOT EXISTS pricing_rules (
+    id TEXT PRIMARY KEY,
+    rule_type TEXT NOT NULL CHECK (rule_type IN ('CHILD_FARE', 'LUGGAGE')),
+    label TEXT NOT NULL,
+    amount DOUBLE PRECISION CHECK (amount > 0),
+    percent DOUBLE PRECISION CHECK (percent > 0 AND percent <= 100),
+    destination_id TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    updated_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((amount IS NULL) <> (percent IS NULL))
+);
+
+CREATE TABLE IF NOT EXISTS booking_fare_modifiers (
+    id TEXT PRIMARY KEY,
+    booking_id TEXT NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    rule_id TEXT NOT NULL,
+    rule_type TEXT NOT NULL,
+    label TEXT NOT NULL,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    amount DOUBLE PRECISION NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS booking_fare_modifiers_booking_idx ON booking_fare_modifiers (booking_id);
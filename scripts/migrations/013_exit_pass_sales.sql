@@ -0,0 +1,7 @@
+-- What a vehicle actually sold on the queue entry it left from, recorded on its exit pass:
+-- paid seats, the amount collected for them, and what is due to the driver after the station's
+-- service fee and the first-exit day pass discount. NULL on passes recorded before this.
+
+ALTER TABLE exit_passes ADD COLUMN IF NOT EXISTS seats_sold INTEGER;
+ALTER TABLE exit_passes ADD COLUMN IF NOT EXISTS amount_collected DOUBLE PRECISION;
+ALTER TABLE exit_passes ADD COLUMN IF NOT EXISTS amount_due DOUBLE PRECISION;
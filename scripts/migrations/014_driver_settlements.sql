@@ -0,0 +1,20 @@
+-- One settlement per exit pass: what was collected on the queue entry the vehicle left from
+-- (gross), the service fees the station keeps (commission), the day pass held back on the
+-- first exit of the day, and what is paid to the driver (net_to_driver).
+
+CREATE TABLE IF NOT EXISTS driver_settlements (
+    id TEXT PRIMARY KEY,
+    exit_pass_id TEXT NOT NULL UNIQUE REFERENCES exit_passes(id) ON DELETE CASCADE,
+    vehicle_id TEXT NOT NULL,
+    license_plate TEXT NOT NULL,
+    destination_name TEXT NOT NULL,
+    seats_sold INTEGER NOT NULL CHECK (seats_sold >= 0),
+    gross DOUBLE PRECISION NOT NULL,
+    commission DOUBLE PRECISION NOT NULL,
+    day_pass_fee DOUBLE PRECISION NOT NULL DEFAULT 0,
+    net_to_driver DOUBLE PRECISION NOT NULL,
+    created_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS driver_settlements_vehicle_idx ON driver_settlements (vehicle_id, created_at);
@@ -0,0 +1,13 @@
+-- Petty cash paid out of a till (cleaning, receipt rolls...). Each expense comes out of the
+-- drawer of the staff member who recorded it and is deducted from their expected cash.
+
+CREATE TABLE IF NOT EXISTS expenses (
+    id TEXT PRIMARY KEY,
+    amount DOUBLE PRECISION NOT NULL CHECK (amount > 0),
+    category TEXT NOT NULL,
+    note TEXT,
+    created_by TEXT REFERENCES staff(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS expenses_created_at_idx ON expenses (created_at);
@@ -0,0 +1,22 @@
+-- Drawer counts at shift close: the notes and coins counted by a cashier, the expected cash of
+-- their shift report at that moment and the difference. The threshold in force is kept with
+-- the count so a later change does not re-flag old counts.
+-- cash_variance_threshold: dinars of difference tolerated before a variance slip prints
+
+CREATE TABLE IF NOT EXISTS cash_counts (
+    id TEXT PRIMARY KEY,
+    staff_id TEXT NOT NULL REFERENCES staff(id),
+    tunis_date DATE NOT NULL,
+    denominations JSONB NOT NULL DEFAULT '[]'::jsonb,
+    counted DOUBLE PRECISION NOT NULL,
+    expected DOUBLE PRECISION NOT NULL,
+    variance DOUBLE PRECISION NOT NULL,
+    threshold DOUBLE PRECISION NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS cash_counts_tunis_date_idx ON cash_counts (tunis_date);
+
+INSERT INTO station_settings (key, value) VALUES
+    ('cash_variance_threshold', '2.000')
+ON CONFLICT (key) DO NOTHING;
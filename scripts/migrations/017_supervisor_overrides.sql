@@ -0,0 +1,18 @@
+-- Actions a supervisor authorized with their PIN on behalf of a cashier (refunds, price
+-- changes), with a reason code and a note, for the head office audit.
+-- action: REFUND | PRICE_OVERRIDE
+-- reason_code: CUSTOMER_REQUEST | VEHICLE_BREAKDOWN | CASHIER_ERROR | TARIFF_CHANGE | OTHER
+-- target: what was overridden (license plate, pass type); details: amounts before/after
+
+CREATE TABLE IF NOT EXISTS supervisor_overrides (
+    id TEXT PRIMARY KEY,
+    action TEXT NOT NULL,
+    reason_code TEXT NOT NULL,
+    note TEXT NOT NULL,
+    supervisor_id TEXT NOT NULL REFERENCES staff(id),
+    target TEXT NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS supervisor_overrides_created_at_idx ON supervisor_overrides (created_at);
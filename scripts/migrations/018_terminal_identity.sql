@@ -0,0 +1,17 @@
+-- Cashier PCs, each with the id it generated on first run (terminal.json in the app config
+-- dir), registered at every startup. Bookings, passes and audit rows carry the id of the
+-- terminal that wrote them; rows written before this migration or by older versions have none.
+
+CREATE TABLE IF NOT EXISTS terminals (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    hostname TEXT NOT NULL DEFAULT '',
+    app_version TEXT NOT NULL DEFAULT '',
+    provisioned_at TIMESTAMPTZ NOT NULL,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS terminal_id TEXT;
+ALTER TABLE day_passes ADD COLUMN IF NOT EXISTS terminal_id TEXT;
+ALTER TABLE exit_passes ADD COLUMN IF NOT EXISTS terminal_id TEXT;
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS terminal_id TEXT;
@@ -0,0 +1,24 @@
+-- Tells every terminal which plate's day pass changed, so their day-pass caches drop it.
+-- The payload is only the plate; terminals read the pass again when they next need it.
+
+CREATE OR REPLACE FUNCTION day_passes_notify_cache()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        PERFORM pg_notify('day_pass_cache', NEW.license_plate);
+    ELSIF TG_OP = 'UPDATE' THEN
+        PERFORM pg_notify('day_pass_cache', OLD.license_plate);
+        IF NEW.license_plate IS DISTINCT FROM OLD.license_plate THEN
+            PERFORM pg_notify('day_pass_cache', NEW.license_plate);
+        END IF;
+    ELSE
+        PERFORM pg_notify('day_pass_cache', OLD.license_plate);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS day_passes_notify_cache_trigger ON day_passes;
+CREATE TRIGGER day_passes_notify_cache_trigger
+    AFTER INSERT OR UPDATE OR DELETE ON day_passes
+    FOR EACH ROW EXECUTE FUNCTION day_passes_notify_cache();
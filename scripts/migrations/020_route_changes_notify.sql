@@ -0,0 +1,15 @@
+-- Tells every terminal that the routes changed, so their location trees are read again.
+-- One notification per statement; a sync rewriting every route sends a single one.
+
+CREATE OR REPLACE FUNCTION routes_notify_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('route_changes', TG_OP);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS routes_notify_changes_trigger ON routes;
+CREATE TRIGGER routes_notify_changes_trigger
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON routes
+    FOR EACH STATEMENT EXECUTE FUNCTION routes_notify_changes();
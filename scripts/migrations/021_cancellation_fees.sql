@@ -0,0 +1,19 @@
+-- Fees kept on late cancellations under the station's cancellation policy (the
+-- cancellation_policy setting). One row per cancellation that was charged; `refunded` is the
+-- share of the booking given up, and the customer gets `refunded - fee` back.
+
+CREATE TABLE IF NOT EXISTS cancellation_fees (
+    id TEXT PRIMARY KEY,
+    booking_id TEXT NOT NULL,
+    verification_code TEXT NOT NULL,
+    destination_name TEXT NOT NULL DEFAULT '',
+    license_plate TEXT NOT NULL DEFAULT '',
+    seats INTEGER NOT NULL CHECK (seats > 0),
+    refunded DOUBLE PRECISION NOT NULL,
+    fee DOUBLE PRECISION NOT NULL CHECK (fee > 0),
+    created_by TEXT REFERENCES staff(id),
+    terminal_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS cancellation_fees_created_at_idx ON cancellation_fees (created_at);
@@ -0,0 +1,25 @@
+-- Closing of the queue at the end of each service day (queue_closing.rs). The first terminal
+-- to close a day inserts its queue_closings row, so the closing runs once per station; the
+-- vehicles still WAITING at closing time are kept in queue_closing_vehicles, whether they were
+-- purged from the queue or carried over to the next day.
+
+CREATE TABLE IF NOT EXISTS queue_closings (
+    service_date DATE PRIMARY KEY,
+    mode TEXT NOT NULL CHECK (mode IN ('PURGE', 'CARRY_OVER')),
+    terminal_id TEXT,
+    closed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS queue_closing_vehicles (
+    id TEXT PRIMARY KEY,
+    service_date DATE NOT NULL REFERENCES queue_closings(service_date) ON DELETE CASCADE,
+    vehicle_id TEXT NOT NULL,
+    license_plate TEXT NOT NULL,
+    destination_id TEXT NOT NULL,
+    destination_name TEXT NOT NULL DEFAULT '',
+    sub_route_name TEXT,
+    queue_position INTEGER NOT NULL,
+    entered_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS queue_closing_vehicles_date_idx ON queue_closing_vehicles (service_date);
@@ -0,0 +1,70 @@
+-- Journal of every change to vehicle_queue, whoever makes it (this app, the remote api, a
+-- manual fix), so the queue of a destination can be replayed as of any moment
+-- (db_replay_queue_state). Each event carries the entry as it was after the change, or as it
+-- was when deleted; `id` orders the events.
+--
+--   ENTERED    row inserted
+--   BOOKED     fewer seats available
+--   CANCELLED  seats given back
+--   MOVED      position, destination or sub-route changed
+--   UPDATED    anything else (status, price)
+--   DEPARTED   row deleted after its exit pass was recorded
+--   REMOVED    row deleted without an exit pass
+
+CREATE TABLE IF NOT EXISTS queue_events (
+    id BIGSERIAL PRIMARY KEY,
+    queue_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    vehicle_id TEXT NOT NULL,
+    destination_id TEXT NOT NULL,
+    destination_name TEXT,
+    sub_route TEXT,
+    queue_position INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    available_seats INTEGER NOT NULL,
+    total_seats INTEGER NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+
+CREATE INDEX IF NOT EXISTS queue_events_destination_idx ON queue_events (destination_id, occurred_at);
+CREATE INDEX IF NOT EXISTS queue_events_queue_id_idx ON queue_events (queue_id, id);
+
+CREATE OR REPLACE FUNCTION vehicle_queue_journal()
+RETURNS TRIGGER AS $$
+DECLARE
+    event_kind TEXT;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        event_kind := 'ENTERED';
+    ELSIF TG_OP = 'DELETE' THEN
+        IF EXISTS (SELECT 1 FROM exit_passes WHERE queue_id = OLD.id) THEN
+            event_kind := 'DEPARTED';
+        ELSE
+            event_kind := 'REMOVED';
+        END IF;
+        INSERT INTO queue_events (queue_id, kind, vehicle_id, destination_id, destination_name, sub_route, queue_position, status, available_seats, total_seats)
+        VALUES (OLD.id, event_kind, OLD.vehicle_id, OLD.destination_id, OLD.destination_name, OLD.sub_route, OLD.queue_position, OLD.status::text, OLD.available_seats, OLD.total_seats);
+        RETURN NULL;
+    ELSIF NEW.available_seats < OLD.available_seats THEN
+        event_kind := 'BOOKED';
+    ELSIF NEW.available_seats > OLD.available_seats THEN
+        event_kind := 'CANCELLED';
+    ELSIF NEW.queue_position IS DISTINCT FROM OLD.queue_position
+       OR NEW.destination_id IS DISTINCT FROM OLD.destination_id
+       OR NEW.sub_route IS DISTINCT FROM OLD.sub_route THEN
+        event_kind := 'MOVED';
+    ELSIF ROW(NEW.*) IS NOT DISTINCT FROM ROW(OLD.*) THEN
+        RETURN NULL;
+    ELSE
+        event_kind := 'UPDATED';
+    END IF;
+    INSERT INTO queue_events (queue_id, kind, vehicle_id, destination_id, destination_name, sub_route, queue_position, status, available_seats, total_seats)
+    VALUES (NEW.id, event_kind, NEW.vehicle_id, NEW.destination_id, NEW.destination_name, NEW.sub_route, NEW.queue_position, NEW.status::text, NEW.available_seats, NEW.total_seats);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS vehicle_queue_journal_trigger ON vehicle_queue;
+CREATE TRIGGER vehicle_queue_journal_trigger
+    AFTER INSERT OR UPDATE OR DELETE ON vehicle_queue
+    FOR EACH ROW EXECUTE FUNCTION vehicle_queue_journal();
@@ -0,0 +1,20 @@
+-- Optimistic concurrency token for queue entries: `version` goes up on every change to the
+-- row, whoever makes it, so a reorder computed from an older read can be refused
+-- (db_update_queue_positions with expected versions) instead of overwriting newer changes.
+
+ALTER TABLE vehicle_queue ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 0;
+
+CREATE OR REPLACE FUNCTION vehicle_queue_bump_version()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF ROW(NEW.*) IS DISTINCT FROM ROW(OLD.*) THEN
+        NEW.version := OLD.version + 1;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS vehicle_queue_bump_version_trigger ON vehicle_queue;
+CREATE TRIGGER vehicle_queue_bump_version_trigger
+    BEFORE UPDATE ON vehicle_queue
+    FOR EACH ROW EXECUTE FUNCTION vehicle_queue_bump_version();
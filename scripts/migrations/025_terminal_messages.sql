@@ -0,0 +1,37 @@
+-- Short messages from one terminal to another ("stop selling Sfax, last vehicle is leaving"),
+-- acknowledged by the staff member at the receiving terminal. Every insert and acknowledgment
+-- is sent on the terminal_messages channel with both terminal ids, so only the two terminals
+-- concerned read the message.
+
+CREATE TABLE IF NOT EXISTS terminal_messages (
+    id TEXT PRIMARY KEY,
+    from_terminal_id TEXT NOT NULL,
+    target_terminal_id TEXT NOT NULL,
+    sent_by TEXT,
+    text TEXT NOT NULL,
+    priority TEXT NOT NULL DEFAULT 'NORMAL',
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    acknowledged_at TIMESTAMPTZ,
+    acknowledged_by TEXT
+);
+
+CREATE INDEX IF NOT EXISTS terminal_messages_target_idx ON terminal_messages (target_terminal_id, acknowledged_at);
+CREATE INDEX IF NOT EXISTS terminal_messages_from_idx ON terminal_messages (from_terminal_id, sent_at);
+
+CREATE OR REPLACE FUNCTION terminal_messages_notify()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('terminal_messages', json_build_object(
+        'id', NEW.id,
+        'fromTerminalId', NEW.from_terminal_id,
+        'targetTerminalId', NEW.target_terminal_id,
+        'acknowledged', NEW.acknowledged_at IS NOT NULL
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS terminal_messages_notify_trigger ON terminal_messages;
+CREATE TRIGGER terminal_messages_notify_trigger
+    AFTER INSERT OR UPDATE ON terminal_messages
+    FOR EACH ROW EXECUTE FUNCTION terminal_messages_notify();
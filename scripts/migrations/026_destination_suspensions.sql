@@ -0,0 +1,18 @@
+-- Routes suspended for weather or roadworks. A suspension blocks new queue entries and bookings
+-- for the destination while it is active: not lifted, and `until` (when set) still ahead. It
+-- lifts by itself at `until`; lifted_at records an early lift by a supervisor.
+
+CREATE TABLE IF NOT EXISTS destination_suspensions (
+    id TEXT PRIMARY KEY,
+    destination_id TEXT NOT NULL,
+    destination_name TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    suspended_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    suspended_by TEXT,
+    until TIMESTAMPTZ,
+    lifted_at TIMESTAMPTZ,
+    lifted_by TEXT
+);
+
+CREATE INDEX IF NOT EXISTS destination_suspensions_open_idx
+    ON destination_suspensions (destination_id) WHERE lifted_at IS NULL;
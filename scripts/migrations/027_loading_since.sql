@@ -0,0 +1,27 @@
+-- When a queue entry started LOADING, so a vehicle that stays half full for too long can be
+-- flagged to a supervisor (loading_timeout.rs). Set when the status becomes LOADING, whoever
+-- changes it, and cleared when it leaves LOADING.
+
+ALTER TABLE vehicle_queue ADD COLUMN IF NOT EXISTS loading_since TIMESTAMPTZ;
+
+-- Entries already loading start their clock now
+UPDATE vehicle_queue SET loading_since = NOW() WHERE status = 'LOADING' AND loading_since IS NULL;
+
+CREATE OR REPLACE FUNCTION vehicle_queue_loading_since()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status = 'LOADING' THEN
+        IF TG_OP = 'INSERT' OR OLD.status IS DISTINCT FROM 'LOADING' THEN
+            NEW.loading_since := NOW();
+        END IF;
+    ELSE
+        NEW.loading_since := NULL;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS vehicle_queue_loading_since_trigger ON vehicle_queue;
+CREATE TRIGGER vehicle_queue_loading_since_trigger
+    BEFORE INSERT OR UPDATE ON vehicle_queue
+    FOR EACH ROW EXECUTE FUNCTION vehicle_queue_loading_since();
@@ -0,0 +1,11 @@
+-- Raw ESC/POS sequences saved by technicians (print density, cutter position, code page tests),
+-- run by name on any printer of the station.
+
+CREATE TABLE IF NOT EXISTS escpos_macros (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    bytes BYTEA NOT NULL,
+    updated_by TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
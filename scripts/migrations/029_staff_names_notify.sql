@@ -0,0 +1,19 @@
+-- Tells every terminal whose name changed, so their staff-name caches drop it.
+-- The payload is only the staff id; terminals read the name again when they next need it.
+
+CREATE OR REPLACE FUNCTION staff_notify_names()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM pg_notify('staff_changes', OLD.id);
+    ELSE
+        PERFORM pg_notify('staff_changes', NEW.id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS staff_notify_names_trigger ON staff;
+CREATE TRIGGER staff_notify_names_trigger
+    AFTER INSERT OR UPDATE OF first_name, last_name OR DELETE ON staff
+    FOR EACH ROW EXECUTE FUNCTION staff_notify_names();
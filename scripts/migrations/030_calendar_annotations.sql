@@ -0,0 +1,12 @@
+-- Notes on service days (public holidays, strikes, storms) so a day with unusual revenue can be
+-- explained in the reports covering it. A day may carry several notes.
+
+CREATE TABLE IF NOT EXISTS calendar_annotations (
+    id TEXT PRIMARY KEY,
+    day DATE NOT NULL,
+    label TEXT NOT NULL,
+    created_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS calendar_annotations_day_idx ON calendar_annotations (day);
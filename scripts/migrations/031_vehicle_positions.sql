@@ -0,0 +1,17 @@
+-- Last known position of each vehicle fitted with a GPS tracker, as posted through the ingestion
+-- listener or db_record_vehicle_position. One row per vehicle: a report older than the stored
+-- one is ignored, so trackers replaying a backlog never move a vehicle back in time.
+
+CREATE TABLE IF NOT EXISTS vehicle_positions (
+    vehicle_id TEXT PRIMARY KEY REFERENCES vehicles(id),
+    latitude DOUBLE PRECISION NOT NULL,
+    longitude DOUBLE PRECISION NOT NULL,
+    speed_kmh DOUBLE PRECISION,
+    heading DOUBLE PRECISION,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    source TEXT
+);
+
+-- Round trips are measured from a vehicle's exit pass to its next queue entry
+CREATE INDEX IF NOT EXISTS queue_events_vehicle_entered_idx ON queue_events (vehicle_id, occurred_at) WHERE kind = 'ENTERED';
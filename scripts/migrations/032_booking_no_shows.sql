@@ -0,0 +1,21 @@
+-- Passengers who paid and never boarded, marked with db_mark_no_show. The booking itself is set
+-- CANCELLED so its seats stop counting as sold; this row tags it as a no-show and keeps what was
+-- refunded under the cancellation policy's no-show rule. `seat_freed` is false when the vehicle
+-- had already left and the seats could not go back on sale.
+
+CREATE TABLE IF NOT EXISTS booking_no_shows (
+    booking_id TEXT PRIMARY KEY,
+    verification_code TEXT NOT NULL,
+    destination_id TEXT,
+    destination_name TEXT NOT NULL DEFAULT '',
+    license_plate TEXT NOT NULL DEFAULT '',
+    seats INTEGER NOT NULL CHECK (seats > 0),
+    amount DOUBLE PRECISION NOT NULL,
+    refunded DOUBLE PRECISION NOT NULL CHECK (refunded >= 0),
+    seat_freed BOOLEAN NOT NULL,
+    created_by TEXT REFERENCES staff(id),
+    terminal_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS booking_no_shows_created_at_idx ON booking_no_shows (created_at);
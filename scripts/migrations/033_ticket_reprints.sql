@@ -0,0 +1,17 @@
+-- Every reprint of a ticket from the printer's reprint cache, numbered per ticket. The
+-- reprint_policy setting caps the number a ticket gets; `fee` is what the cashier charged for the
+-- reprint under that policy (0 when free), so the rows double as the ledger of reprint fees.
+
+CREATE TABLE IF NOT EXISTS ticket_reprints (
+    id TEXT PRIMARY KEY,
+    ticket_kind TEXT NOT NULL,
+    ticket_ref TEXT NOT NULL,
+    reprint_number INTEGER NOT NULL CHECK (reprint_number > 0),
+    fee DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (fee >= 0),
+    created_by TEXT REFERENCES staff(id),
+    terminal_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (ticket_kind, ticket_ref, reprint_number)
+);
+
+CREATE INDEX IF NOT EXISTS ticket_reprints_created_at_idx ON ticket_reprints (created_at);
@@ -0,0 +1,17 @@
+-- Ticket numbers voided because the printed ticket was spoiled (paper jam, misprint). A voided
+-- number is never printed again: the next reprint of that ticket takes a fresh number, kept in
+-- `replaced_by`. The cashier's shift report lists the numbers they voided.
+
+CREATE TABLE IF NOT EXISTS voided_ticket_numbers (
+    id TEXT PRIMARY KEY,
+    ticket_kind TEXT NOT NULL,
+    ticket_number TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    replaced_by TEXT,
+    created_by TEXT REFERENCES staff(id),
+    terminal_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (ticket_kind, ticket_number)
+);
+
+CREATE INDEX IF NOT EXISTS voided_ticket_numbers_created_at_idx ON voided_ticket_numbers (created_at);
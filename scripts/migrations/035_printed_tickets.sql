@@ -0,0 +1,22 @@
+-- Every ticket the print queue printed, as it came out of the printer: the rendered text with
+-- the payload it was built from. Kept for customer disputes; an archived ticket can be searched
+-- and exported to PDF.
+
+CREATE TABLE IF NOT EXISTS printed_tickets (
+    id TEXT PRIMARY KEY,
+    job_id TEXT NOT NULL,
+    ticket_type TEXT NOT NULL,
+    ticket_number TEXT,
+    staff_name TEXT,
+    printer_id TEXT NOT NULL,
+    printer_name TEXT NOT NULL,
+    duplicate_number INTEGER,
+    payload TEXT NOT NULL,
+    rendered_text TEXT NOT NULL,
+    width SMALLINT NOT NULL,
+    terminal_id TEXT,
+    printed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS printed_tickets_printed_at_idx ON printed_tickets (printed_at);
+CREATE INDEX IF NOT EXISTS printed_tickets_ticket_number_idx ON printed_tickets (ticket_number);
@@ -0,0 +1,4 @@
+-- Driver PIN for the kiosk self check-in: the driver types the plate and this PIN instead of
+-- queueing at the counter. Argon2 hash, like staff.pin_hash.
+
+ALTER TABLE vehicles ADD COLUMN IF NOT EXISTS driver_pin_hash TEXT;
@@ -0,0 +1,41 @@
+-- Supervisor approvals asked for from a cashier's terminal instead of with the supervisor's PIN
+-- at the counter (see approvals.rs). A request is PENDING until a supervisor approves or
+-- rejects it; an approved one is CONSUMED by the action it was asked for. Every insert and
+-- decision is sent on the approval_requests channel, so supervisor screens see new requests and
+-- the requesting terminal sees the decision.
+
+CREATE TABLE IF NOT EXISTS approval_requests (
+    id TEXT PRIMARY KEY,
+    action TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    reason_code TEXT NOT NULL,
+    note TEXT NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    requested_by TEXT,
+    terminal_id TEXT,
+    status TEXT NOT NULL DEFAULT 'PENDING',
+    decided_by TEXT,
+    decision_note TEXT,
+    decided_at TIMESTAMPTZ,
+    consumed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS approval_requests_status_idx ON approval_requests (status, created_at);
+
+CREATE OR REPLACE FUNCTION approval_requests_notify()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('approval_requests', json_build_object(
+        'id', NEW.id,
+        'status', NEW.status,
+        'terminalId', NEW.terminal_id
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS approval_requests_notify_trigger ON approval_requests;
+CREATE TRIGGER approval_requests_notify_trigger
+    AFTER INSERT OR UPDATE OF status ON approval_requests
+    FOR EACH ROW EXECUTE FUNCTION approval_requests_notify();
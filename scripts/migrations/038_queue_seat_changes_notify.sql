@@ -0,0 +1,32 @@
+-- Tells every terminal which destination's seats changed, so their booking destination
+-- summaries read it again. The payload is the destination id; '*' after a TRUNCATE.
+
+CREATE OR REPLACE FUNCTION vehicle_queue_notify_seats()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'TRUNCATE' THEN
+        PERFORM pg_notify('queue_seat_changes', '*');
+    ELSIF TG_OP = 'INSERT' THEN
+        PERFORM pg_notify('queue_seat_changes', NEW.destination_id);
+    ELSIF TG_OP = 'UPDATE' THEN
+        PERFORM pg_notify('queue_seat_changes', OLD.destination_id);
+        IF NEW.destination_id IS DISTINCT FROM OLD.destination_id THEN
+            PERFORM pg_notify('queue_seat_changes', NEW.destination_id);
+        END IF;
+    ELSE
+        PERFORM pg_notify('queue_seat_changes', OLD.destination_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS vehicle_queue_notify_seats_trigger ON vehicle_queue;
+CREATE TRIGGER vehicle_queue_notify_seats_trigger
+    AFTER INSERT OR DELETE OR UPDATE OF destination_id, destination_name, sub_route, sub_route_name, available_seats
+    ON vehicle_queue
+    FOR EACH ROW EXECUTE FUNCTION vehicle_queue_notify_seats();
+
+DROP TRIGGER IF EXISTS vehicle_queue_notify_seats_truncate ON vehicle_queue;
+CREATE TRIGGER vehicle_queue_notify_seats_truncate
+    AFTER TRUNCATE ON vehicle_queue
+    FOR EACH STATEMENT EXECUTE FUNCTION vehicle_queue_notify_seats();
@@ -0,0 +1,12 @@
+-- Other names of a route for the destination search, and its Arabic name where the route sync
+-- does not provide one.
+
+ALTER TABLE routes ADD COLUMN IF NOT EXISTS station_name_ar TEXT;
+
+CREATE TABLE IF NOT EXISTS route_aliases (
+    station_id TEXT NOT NULL,
+    alias TEXT NOT NULL,
+    created_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (station_id, alias)
+);
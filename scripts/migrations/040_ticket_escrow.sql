@@ -0,0 +1,9 @@
+-- Tickets issued while the station runs without a printer (ticket_escrow.rs) are archived
+-- unprinted as ESCROWED, until they are printed in bulk or sent by SMS.
+
+ALTER TABLE printed_tickets ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'PRINTED';
+ALTER TABLE printed_tickets ADD COLUMN IF NOT EXISTS delivered_at TIMESTAMPTZ;
+-- The phone number an escrowed ticket was texted to
+ALTER TABLE printed_tickets ADD COLUMN IF NOT EXISTS delivered_to TEXT;
+
+CREATE INDEX IF NOT EXISTS printed_tickets_escrowed_idx ON printed_tickets (printed_at) WHERE status = 'ESCROWED';
@@ -0,0 +1,25 @@
+-- Refunds handed back at the counter with db_record_refund, each against the booking or day pass
+-- it refunds, signed off by a supervisor. The refunds given by cancellations and no-shows stay on
+-- bookings.refund_amount; together with these they never exceed what was paid (refunds.rs).
+-- A booking deleted by a later cancellation keeps its refunds, with booking_id cleared and the
+-- verification code still in `reference`.
+
+CREATE TABLE IF NOT EXISTS refunds (
+    id TEXT PRIMARY KEY,
+    booking_id TEXT REFERENCES bookings(id) ON DELETE SET NULL,
+    day_pass_id TEXT REFERENCES day_passes(id) ON DELETE SET NULL,
+    -- Verification code of the booking, id of the day pass
+    reference TEXT NOT NULL,
+    amount DOUBLE PRECISION NOT NULL CHECK (amount > 0),
+    reason_code TEXT NOT NULL,
+    note TEXT NOT NULL,
+    supervisor_id TEXT REFERENCES staff(id),
+    created_by TEXT REFERENCES staff(id),
+    terminal_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (booking_id IS NULL OR day_pass_id IS NULL)
+);
+
+CREATE INDEX IF NOT EXISTS refunds_booking_id_idx ON refunds (booking_id) WHERE booking_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS refunds_day_pass_id_idx ON refunds (day_pass_id) WHERE day_pass_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS refunds_created_at_idx ON refunds (created_at);
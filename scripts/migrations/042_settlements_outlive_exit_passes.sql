@@ -0,0 +1,9 @@
+-- A settlement is the driver's pay record and carries everything it shows, so it outlives the
+-- exit pass it was written with: purging old exit passes (retention.rs) clears exit_pass_id
+-- instead of deleting the settlement.
+
+ALTER TABLE driver_settlements ALTER COLUMN exit_pass_id DROP NOT NULL;
+ALTER TABLE driver_settlements DROP CONSTRAINT IF EXISTS driver_settlements_exit_pass_id_fkey;
+ALTER TABLE driver_settlements
+    ADD CONSTRAINT driver_settlements_exit_pass_id_fkey
+    FOREIGN KEY (exit_pass_id) REFERENCES exit_passes(id) ON DELETE SET NULL;
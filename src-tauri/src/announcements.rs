@@ -0,0 +1,217 @@
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::i18n;
+use crate::time;
+
+// Departure calls on the hall's public address. When an exit pass is printed on this terminal,
+// "Départ pour Sousse, véhicule 123 Tunis 4567" is spoken for the destinations chosen in
+// announcements.json: POSTed as {"text", "language"} to a local PA endpoint that does the
+// text-to-speech, or passed to a playback command (e.g. ["espeak", "-v", "fr", "{text}"]) run
+// without a shell. The last call can be replayed for a driver who missed it.
+
+const SETTINGS_FILE: &str = "announcements.json";
+const PLAY_TIMEOUT_SECS: u64 = 30;
+
+static SETTINGS_PATH: OnceCell<PathBuf> = OnceCell::new();
+static LAST: Lazy<Mutex<Option<Announcement>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AnnouncementSettings {
+    pub enabled: bool,
+    pub destination_ids: Vec<String>,
+    pub template: String, // {destination} and {plate}
+    pub endpoint_url: Option<String>,
+    pub play_command: Vec<String>, // program then arguments, "{text}" replaced by the call
+}
+
+impl Default for AnnouncementSettings {
+    fn default() -> Self {
+        AnnouncementSettings {
+            enabled: false,
+            destination_ids: Vec::new(),
+            template: "Départ pour {destination}, véhicule {plate}".to_string(),
+            endpoint_url: None,
+            play_command: Vec::new(),
+        }
+    }
+}
+
+impl AnnouncementSettings {
+    fn endpoint(&self) -> Option<&str> {
+        self.endpoint_url.as_deref().map(str::trim).filter(|url| !url.is_empty())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.template.trim().is_empty() || (self.enabled && self.endpoint().is_none() && self.play_command.is_empty()) {
+            return Err(i18n::t("error.invalid_announcement_settings"));
+        }
+        Ok(())
+    }
+
+    /// What to say for the departure, None when it is not announced
+    pub fn text_for(&self, departure: &Departure) -> Option<String> {
+        if !self.enabled || !self.destination_ids.contains(&departure.destination_id) {
+            return None;
+        }
+        Some(
+            self.template
+                .replace("{destination}", &departure.destination_name)
+                .replace("{plate}", &spoken_plate(&departure.license_plate)),
+        )
+    }
+}
+
+// A vehicle leaving with its exit pass
+#[derive(Debug, Clone)]
+pub struct Departure {
+    pub destination_id: String,
+    pub destination_name: String,
+    pub license_plate: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Announcement {
+    pub text: String,
+    pub license_plate: String,
+    pub destination_name: String,
+    pub announced_at: String,
+}
+
+/// The plate as it is read out: digits and letters apart, the series "TU"/"TUN"/"تونس" as Tunis
+pub fn spoken_plate(plate: &str) -> String {
+    let mut groups: Vec<String> = Vec::new();
+    let mut previous_digit: Option<bool> = None;
+    for c in plate.chars().filter(|c| c.is_alphanumeric()) {
+        let digit = c.is_ascii_digit();
+        if previous_digit != Some(digit) {
+            groups.push(String::new());
+        }
+        groups.last_mut().unwrap().push(c);
+        previous_digit = Some(digit);
+    }
+    groups
+        .into_iter()
+        .map(|group| match group.to_uppercase().as_str() {
+            "TU" | "TUN" | "تونس" => "Tunis".to_string(),
+            _ => group,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Where this terminal keeps its settings; set once at startup
+pub fn init(app_handle: &tauri::AppHandle) {
+    let dir = app_handle.path_resolver().app_config_dir().unwrap_or_else(|| PathBuf::from("."));
+    let _ = SETTINGS_PATH.set(dir.join(SETTINGS_FILE));
+}
+
+fn settings_path() -> PathBuf {
+    SETTINGS_PATH.get().cloned().unwrap_or_else(|| PathBuf::from(SETTINGS_FILE))
+}
+
+pub fn load() -> AnnouncementSettings {
+    fs::read_to_string(settings_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(settings: &AnnouncementSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Impossible d'enregistrer {:?}: {}", path, e))
+}
+
+async fn play(settings: &AnnouncementSettings, text: &str) -> Result<(), String> {
+    if let Some(url) = settings.endpoint() {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(PLAY_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| e.to_string())?;
+        let response = client
+            .post(url)
+            .json(&serde_json::json!({ "text": text, "language": "fr" }))
+            .send()
+            .await
+            .map_err(|e| format!("Sonorisation injoignable: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Sonorisation: HTTP {}", response.status()));
+        }
+        return Ok(());
+    }
+    let Some((program, args)) = settings.play_command.split_first() else {
+        return Err(i18n::t("error.invalid_announcement_settings"));
+    };
+    let mut command = tokio::process::Command::new(program);
+    command.args(args.iter().map(|arg| arg.replace("{text}", text))).kill_on_drop(true);
+    let status = tokio::time::timeout(Duration::from_secs(PLAY_TIMEOUT_SECS), command.status())
+        .await
+        .map_err(|_| format!("{} n'a pas terminé", program))?
+        .map_err(|e| format!("{}: {}", program, e))?;
+    if !status.success() {
+        return Err(format!("{}: {}", program, status));
+    }
+    Ok(())
+}
+
+/// Call the departure when its destination is announced; failures are only logged, the exit
+/// pass is already out
+pub async fn announce_departure(departure: &Departure) {
+    let settings = load();
+    let Some(text) = settings.text_for(departure) else {
+        return;
+    };
+    let announcement = Announcement {
+        text,
+        license_plate: departure.license_plate.clone(),
+        destination_name: departure.destination_name.clone(),
+        announced_at: time::tunis_now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+    *LAST.lock().unwrap() = Some(announcement.clone());
+    match play(&settings, &announcement.text).await {
+        Ok(()) => println!("📢 [ANNOUNCE] {}", announcement.text),
+        Err(e) => println!("⚠️ [ANNOUNCE] \"{}\" not played: {}", announcement.text, e),
+    }
+}
+
+/// Play the last departure call again
+pub async fn replay_last() -> Result<Announcement, String> {
+    let last = LAST.lock().unwrap().clone();
+    let announcement = last.ok_or_else(|| i18n::t("error.no_announcement"))?;
+    play(&load(), &announcement.text).await?;
+    Ok(announcement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn departures_of_chosen_destinations_are_called_with_the_plate_read_out() {
+        assert_eq!(spoken_plate("123 TUN 4567"), "123 Tunis 4567");
+        assert_eq!(spoken_plate("123TU456"), "123 Tunis 456");
+        assert_eq!(spoken_plate("45 RS 789"), "45 RS 789");
+
+        let departure = Departure { destination_id: "sousse".into(), destination_name: "Sousse".into(), license_plate: "123 TU 456".into() };
+        let mut settings = AnnouncementSettings { destination_ids: vec!["sousse".into()], ..Default::default() };
+        assert_eq!(settings.text_for(&departure), None);
+        settings.enabled = true;
+        assert_eq!(settings.text_for(&departure).unwrap(), "Départ pour Sousse, véhicule 123 Tunis 456");
+        settings.destination_ids.clear();
+        assert_eq!(settings.text_for(&departure), None);
+
+        assert!(settings.validate().is_err());
+        settings.play_command = vec!["espeak".into(), "{text}".into()];
+        assert!(settings.validate().is_ok());
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::db;
+use crate::i18n;
+use crate::time::{self, TunisTime};
+
+// Rules-based watch for patterns worth a supervisor's look, computed from what the station
+// already records:
+// - more than MAX_CANCELLATIONS_PER_HOUR cancellations by the same staff member within an hour
+//   (cancel commands in the audit log);
+// - bookings removed after the exit pass was issued (fewer paid seats on the queue entry than
+//   the exit pass counted);
+// - more than MAX_FREE_ENTRIES_PER_HOUR 0-TND entry tickets for the same plate within an hour
+//   (FREE_ENTRY_EVENT in the audit log, written when a valid day pass makes the entry free).
+// Nothing is stored: db_get_anomalies evaluates the rules for a day, and the watch evaluates
+// today's every few minutes and tells the UI about the ones it has not reported yet.
+
+const CHECK_INTERVAL_SECS: u64 = 5 * 60;
+const MAX_CANCELLATIONS_PER_HOUR: usize = 3;
+const MAX_FREE_ENTRIES_PER_HOUR: usize = 2;
+pub const FREE_ENTRY_EVENT: &str = "ENTRY_TICKET_FREE";
+pub const ANOMALY_EVENT: &str = "anomaly-detected";
+
+// Anomalies already sent to the UI since the app started
+static REPORTED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AnomalyKind {
+    RepeatedCancellations,
+    BookingsRemovedAfterExit,
+    RepeatedFreeEntries,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnomalyDto {
+    pub kind: AnomalyKind,
+    /// Staff name or license plate
+    pub subject: String,
+    pub count: i64,
+    pub first_at: String,
+    pub last_at: String,
+    pub message: String,
+}
+
+impl AnomalyDto {
+    fn key(&self) -> String {
+        format!("{:?}|{}|{}", self.kind, self.subject, self.first_at)
+    }
+}
+
+/// Runs of more than `more_than` events within `window` of the first one, without overlap.
+/// `times` must be sorted; each run is returned as (first index, last index).
+pub fn bursts(times: &[DateTime<Utc>], window: Duration, more_than: usize) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < times.len() {
+        let end = start + times[start..].iter().take_while(|t| **t - times[start] <= window).count() - 1;
+        if end - start + 1 > more_than {
+            runs.push((start, end));
+            start = end + 1;
+        } else {
+            start += 1;
+        }
+    }
+    runs
+}
+
+// Events grouped by subject (kept in first-seen order), then checked for bursts
+fn burst_anomalies(kind: AnomalyKind, events: &[(String, DateTime<Utc>)], more_than: usize, message_key: &str) -> Vec<AnomalyDto> {
+    let mut subjects: Vec<&str> = Vec::new();
+    for (subject, _) in events {
+        if !subjects.contains(&subject.as_str()) {
+            subjects.push(subject);
+        }
+    }
+    let mut anomalies = Vec::new();
+    for subject in subjects {
+        let mut times: Vec<DateTime<Utc>> = events.iter().filter(|(s, _)| s == subject).map(|(_, t)| *t).collect();
+        times.sort();
+        for (first, last) in bursts(&times, Duration::hours(1), more_than) {
+            let count = (last - first + 1) as i64;
+            anomalies.push(AnomalyDto {
+                kind,
+                subject: subject.to_string(),
+                count,
+                first_at: TunisTime(times[first].with_timezone(&time::TZ)).fmt_dto(),
+                last_at: TunisTime(times[last].with_timezone(&time::TZ)).fmt_dto(),
+                message: i18n::tf(message_key, &[("subject", &subject), ("count", &count)]),
+            });
+        }
+    }
+    anomalies
+}
+
+pub async fn for_day(client: &impl db::GenericClient, day: chrono::NaiveDate) -> Result<Vec<AnomalyDto>, String> {
+    let (start, end) = time::tunis_day_bounds(day);
+    let cancellations = db::anomalies::cancellations(client, start, end).await?;
+    let mut anomalies = burst_anomalies(AnomalyKind::RepeatedCancellations, &cancellations, MAX_CANCELLATIONS_PER_HOUR, "anomaly.repeated_cancellations");
+
+    for removed in db::anomalies::bookings_removed_after_exit(client, start, end).await? {
+        anomalies.push(AnomalyDto {
+            kind: AnomalyKind::BookingsRemovedAfterExit,
+            subject: removed.license_plate.clone(),
+            count: (removed.seats_on_pass - removed.seats_now) as i64,
+            first_at: removed.exit_at.clone(),
+            last_at: removed.exit_at.clone(),
+            message: i18n::tf(
+                "anomaly.bookings_removed_after_exit",
+                &[("subject", &removed.license_plate), ("before", &removed.seats_on_pass), ("after", &removed.seats_now)],
+            ),
+        });
+    }
+
+    let free_entries = db::anomalies::audit_events_by_plate(client, FREE_ENTRY_EVENT, start, end).await?;
+    anomalies.extend(burst_anomalies(AnomalyKind::RepeatedFreeEntries, &free_entries, MAX_FREE_ENTRIES_PER_HOUR, "anomaly.repeated_free_entries"));
+    Ok(anomalies)
+}
+
+/// Today's anomalies not reported yet, remembered as reported
+async fn check(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let client = db::pool::background_read().await?;
+    let anomalies = for_day(&client, time::tunis_today()).await?;
+    let fresh: Vec<AnomalyDto> = match REPORTED.lock() {
+        Ok(mut reported) => anomalies.into_iter().filter(|a| reported.insert(a.key())).collect(),
+        Err(e) => return Err(e.to_string()),
+    };
+    for anomaly in &fresh {
+        println!("🚩 [ANOMALY] {}", anomaly.message);
+        let _ = app_handle.emit_all(ANOMALY_EVENT, anomaly);
+    }
+    Ok(())
+}
+
+/// Every CHECK_INTERVAL_SECS, starting one interval after launch
+pub async fn run_anomaly_watch(app_handle: tauri::AppHandle) {
+    let period = std::time::Duration::from_secs(CHECK_INTERVAL_SECS);
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    loop {
+        interval.tick().await;
+        if let Err(e) = check(&app_handle).await {
+            println!("⚠️ [ANOMALY] Check failed: {}", e);
+        }
+    }
+}
+
+/// YYYY-MM-DD, today in Tunis when not given
+#[tauri::command]
+pub async fn db_get_anomalies(date: Option<String>) -> Result<Vec<AnomalyDto>, String> {
+    let day = match date {
+        Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|_| i18n::tf("error.invalid_date", &[("date", &date)]))?,
+        None => time::tunis_today(),
+    };
+    let client = db::pool::read().await?;
+    for_day(&client, day).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp(1_760_000_000, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn bursts_need_more_than_the_limit_within_the_window() {
+        let hour = Duration::hours(1);
+        assert!(bursts(&[at(0), at(10), at(20)], hour, 3).is_empty());
+        assert_eq!(bursts(&[at(0), at(10), at(20), at(60)], hour, 3), vec![(0, 3)]);
+        // Spread over more than an hour: no window holds four
+        assert!(bursts(&[at(0), at(30), at(61), at(95)], hour, 3).is_empty());
+        // Two separate runs
+        let times = [at(0), at(1), at(2), at(3), at(200), at(201), at(202), at(203), at(204)];
+        assert_eq!(bursts(&times, hour, 3), vec![(0, 3), (4, 8)]);
+    }
+
+    #[test]
+    fn bursts_are_counted_per_subject() {
+        let events: Vec<(String, DateTime<Utc>)> = vec![
+            ("Sami".to_string(), at(0)),
+            ("Amel".to_string(), at(1)),
+            ("Sami".to_string(), at(5)),
+            ("Amel".to_string(), at(90)),
+            ("Sami".to_string(), at(30)),
+        ];
+        let anomalies = burst_anomalies(AnomalyKind::RepeatedFreeEntries, &events, 2, "anomaly.repeated_free_entries");
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!((anomalies[0].subject.as_str(), anomalies[0].count), ("Sami", 3));
+    }
+}
@@ -0,0 +1,192 @@
+use serde::Deserialize;
+use serde_json::json;
+use tauri::Manager;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+
+use crate::audit;
+use crate::db;
+use crate::db::anpr::PendingAnprEntryDto;
+use crate::gps::{http_response, read_post};
+use crate::i18n;
+
+// Plate-recognition camera at the gate. The camera (or the local node relaying it) posts the
+// plates it reads to POST /plates on ANPR_INGEST_PORT, with `Authorization: Bearer
+// <ANPR_INGEST_TOKEN>`; the listener stays off unless both are set. A plate read with enough
+// confidence, of an active vehicle not queued yet, puts it in the queue of its default
+// authorized destination as PENDING_CONFIRM: it sells no seats and prints nothing yet. The
+// cashier screens get ANPR_PENDING_EVENT and confirm the entry (db_confirm_anpr_entry, which
+// prints the entry ticket or day pass as at the counter) or reject it (db_reject_anpr_entry).
+// Reads that can't be used are answered in the response and logged.
+
+const MIN_CONFIDENCE: f64 = 0.8;
+pub const ANPR_PENDING_EVENT: &str = "anpr-entry-pending";
+pub const ANPR_ENTRY_EVENT: &str = "ANPR_ENTRY";
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlateRead {
+    pub plate: String,
+    /// 0 to 1, as the camera rates its read; trusted when missing
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    #[serde(default)]
+    pub camera_id: Option<String>,
+}
+
+// One read or a batch of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ReadBody {
+    Many(Vec<PlateRead>),
+    One(PlateRead),
+}
+
+/// The letters and digits of a plate, upper case: how reads and stored plates are compared
+pub fn normalize_plate(plate: &str) -> String {
+    plate.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_uppercase()).collect()
+}
+
+impl PlateRead {
+    /// The normalized plate once the read is checked
+    pub fn check(&self) -> Result<String, String> {
+        if let Some(confidence) = self.confidence.filter(|c| *c < MIN_CONFIDENCE) {
+            return Err(i18n::tf("error.anpr_low_confidence", &[("plate", &self.plate), ("confidence", &confidence), ("min", &MIN_CONFIDENCE)]));
+        }
+        let normalized = normalize_plate(&self.plate);
+        if normalized.is_empty() {
+            return Err(i18n::t("error.anpr_plate_unreadable"));
+        }
+        Ok(normalized)
+    }
+}
+
+/// Queue the vehicle read at the gate for the cashier to confirm
+pub async fn enqueue(client: &deadpool_postgres::Client, read: &PlateRead) -> Result<PendingAnprEntryDto, String> {
+    let normalized = read.check()?;
+    let plate = db::anpr::match_plate(client, &normalized)
+        .await?
+        .ok_or_else(|| i18n::tf("error.anpr_plate_unknown", &[("plate", &read.plate)]))?;
+    let vehicle = db::vehicles::find_by_plate(client, &plate)
+        .await?
+        .ok_or_else(|| i18n::tf("error.anpr_plate_unknown", &[("plate", &read.plate)]))?;
+    if db::queue::entry_for_vehicle(client, &vehicle.id).await?.is_some() {
+        return Err(i18n::tf("error.anpr_already_queued", &[("plate", &plate)]));
+    }
+    // Listed default first, then by priority
+    let destination = db::vehicles::authorized_destinations(client, &plate)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| i18n::tf("error.anpr_no_destination", &[("plate", &plate)]))?;
+
+    let entered = crate::enter_queue(&plate, &destination.station_id, Some(destination.station_name), None, None, db::queue::PENDING_CONFIRM).await?;
+    audit::record(
+        client,
+        ANPR_ENTRY_EVENT,
+        None,
+        json!({ "licensePlate": plate, "read": read.plate, "confidence": read.confidence, "cameraId": read.camera_id, "queueId": entered.queue_id }),
+    )
+    .await?;
+    Ok(PendingAnprEntryDto {
+        queue_id: entered.queue_id,
+        license_plate: plate,
+        destination_id: destination.station_id,
+        destination_name: entered.destination_name,
+        queue_position: entered.position,
+        entered_at: crate::time::TunisTime(crate::time::tunis_now()).fmt_dto(),
+    })
+}
+
+async fn handle(stream: &mut tokio::net::TcpStream, token: &str, app_handle: &tauri::AppHandle) -> std::io::Result<String> {
+    let body = match read_post(stream, "/plates", token).await? {
+        Ok(body) => body,
+        Err(response) => return Ok(response),
+    };
+    let reads = match serde_json::from_slice::<ReadBody>(&body) {
+        Ok(ReadBody::Many(reads)) => reads,
+        Ok(ReadBody::One(read)) => vec![read],
+        Err(e) => return Ok(http_response("400 Bad Request", &json!({ "error": e.to_string() }).to_string())),
+    };
+    let client = match db::pool::background().await {
+        Ok(client) => client,
+        Err(e) => return Ok(http_response("503 Service Unavailable", &json!({ "error": e }).to_string())),
+    };
+    let (mut pending, mut rejected) = (Vec::new(), Vec::new());
+    for read in &reads {
+        match enqueue(&client, read).await {
+            Ok(entry) => {
+                println!("📷 [ANPR] {} queued for {} pending confirmation", entry.license_plate, entry.destination_name);
+                let _ = app_handle.emit_all(ANPR_PENDING_EVENT, &entry);
+                pending.push(entry.queue_id);
+            }
+            Err(e) => {
+                println!("📷 [ANPR] Read {} not queued: {}", read.plate, e);
+                rejected.push(e);
+            }
+        }
+    }
+    Ok(http_response("200 OK", &json!({ "pending": pending, "rejected": rejected }).to_string()))
+}
+
+/// Serve POST /plates on ANPR_INGEST_PORT; does nothing unless the port and ANPR_INGEST_TOKEN are set
+pub async fn run_ingest(app_handle: tauri::AppHandle) {
+    let port = match std::env::var("ANPR_INGEST_PORT").ok().and_then(|v| v.trim().parse::<u16>().ok()) {
+        Some(port) => port,
+        None => return,
+    };
+    let token = match std::env::var("ANPR_INGEST_TOKEN").ok().map(|t| t.trim().to_string()).filter(|t| !t.is_empty()) {
+        Some(token) => token,
+        None => {
+            println!("⚠️ [ANPR] ANPR_INGEST_PORT is set without ANPR_INGEST_TOKEN; the camera stays off");
+            return;
+        }
+    };
+    let listener = match TcpListener::bind(("0.0.0.0", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("❌ [ANPR] Cannot listen on port {}: {}", port, e);
+            return;
+        }
+    };
+    println!("📷 [ANPR] Accepting plate reads on port {}", port);
+    loop {
+        match listener.accept().await {
+            Ok((mut stream, _)) => {
+                let token = token.clone();
+                let app_handle = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    let result = match handle(&mut stream, &token, &app_handle).await {
+                        Ok(response) => stream.write_all(response.as_bytes()).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        println!("⚠️ [ANPR] {}", e);
+                    }
+                    let _ = stream.shutdown().await;
+                });
+            }
+            Err(e) => println!("⚠️ [ANPR] Accept failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_are_normalized_and_unsure_ones_refused() {
+        assert_eq!(normalize_plate("123 tu-4567"), "123TU4567");
+        assert_eq!(normalize_plate("123 تونس 4567"), "1234567");
+
+        let read = |plate: &str, confidence: Option<f64>| PlateRead { plate: plate.to_string(), confidence, camera_id: None };
+        assert_eq!(read("123 TU 4567", Some(0.93)).check(), Ok("123TU4567".to_string()));
+        assert_eq!(read("123 TU 4567", None).check(), Ok("123TU4567".to_string()));
+        assert!(read("123 TU 4567", Some(0.4)).check().is_err());
+        assert!(read(" - ", None).check().is_err());
+
+        let body: ReadBody = serde_json::from_str(r#"[{"plate":"123TU4567","confidence":0.9,"cameraId":"gate-1"}]"#).unwrap();
+        assert!(matches!(body, ReadBody::Many(ref reads) if reads[0].camera_id.as_deref() == Some("gate-1")));
+    }
+}
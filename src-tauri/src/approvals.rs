@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::db;
+use crate::db::approvals::{ApprovalRequestDto, NewApprovalRequest};
+use crate::i18n;
+use crate::overrides::{self, AuthorizedOverride, OverrideAction, SupervisorOverride};
+use crate::terminal;
+use crate::DB_POOL;
+
+// Supervisor sign-off from a distance. Instead of calling the supervisor over to type their
+// PIN, the cashier asks from their terminal: the request (approval_requests, migration 037) is
+// notified on the approval_requests channel, which cache_listener hands to `notified`, so every
+// supervisor screen shows it in its inbox. The supervisor approves or rejects it there and the
+// requesting terminal hears the decision. An approved request is good once, for the action and
+// subject it was asked for, within APPROVAL_VALID_MINUTES: the command consumes it in its own
+// transaction and records the override as the PIN would have (overrides.rs).
+//
+// The approval_policy setting holds the thresholds that call for a sign-off on top of the
+// actions that always need one.
+
+pub const APPROVAL_VALID_MINUTES: i32 = 30;
+pub const REQUESTED_EVENT: &str = "approval-requested";
+pub const DECIDED_EVENT: &str = "approval-decided";
+pub const CLOSED_EVENT: &str = "approval-closed";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ApprovalPolicy {
+    /// Ending a trip with less of its seats sold needs a sign-off; 0 to never ask
+    pub dispatch_min_percent: i64,
+}
+
+impl ApprovalPolicy {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0..=100).contains(&self.dispatch_min_percent) {
+            return Err(i18n::t("error.invalid_approval_policy"));
+        }
+        Ok(())
+    }
+
+    /// Whether a trip leaving with `sold` of `total` seats needs a sign-off
+    pub fn dispatch_needs_sign_off(&self, sold: i32, total: i32) -> bool {
+        total > 0 && (sold as i64) * 100 < self.dispatch_min_percent * total as i64
+    }
+}
+
+/// Sent by the frontend to ask for a sign-off
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequest {
+    pub action: OverrideAction,
+    /// What the action applies to, as the command checks it: plate, pass type, booking code
+    pub subject: String,
+    pub reason_code: String,
+    pub note: String,
+    #[serde(default)]
+    pub details: serde_json::Value,
+}
+
+/// The sign-off sent with a command: a supervisor PIN, or the id of an approved request
+#[derive(Debug, Clone)]
+pub enum SignOff {
+    Pin(AuthorizedOverride),
+    Approval(String),
+}
+
+impl SignOff {
+    /// The PIN is checked here, before the action's transaction; None when neither was sent
+    pub async fn from_request(supervisor_override: Option<SupervisorOverride>, approval_id: Option<String>) -> Result<Option<SignOff>, String> {
+        if let Some(supervisor_override) = supervisor_override {
+            return Ok(Some(SignOff::Pin(supervisor_override.authorize().await?)));
+        }
+        Ok(approval_id.filter(|id| !id.trim().is_empty()).map(SignOff::Approval))
+    }
+
+    /// For the actions that always need a sign-off
+    pub async fn required(supervisor_override: Option<SupervisorOverride>, approval_id: Option<String>) -> Result<SignOff, String> {
+        SignOff::from_request(supervisor_override, approval_id)
+            .await?
+            .ok_or_else(|| i18n::t("error.sign_off_required"))
+    }
+
+    /// The override to record for `action` on `subject`; an approval is used up, so call it in
+    /// the action's transaction
+    pub async fn authorize(self, client: &impl db::GenericClient, action: OverrideAction, subject: &str) -> Result<AuthorizedOverride, String> {
+        match self {
+            SignOff::Pin(authorized) => Ok(authorized),
+            SignOff::Approval(id) => db::approvals::consume(client, &id, action, subject, APPROVAL_VALID_MINUTES)
+                .await?
+                .ok_or_else(|| i18n::tf("error.approval_not_usable", &[("minutes", &APPROVAL_VALID_MINUTES)])),
+        }
+    }
+}
+
+/// Ask the supervisors for a sign-off from this terminal
+pub async fn request(request: &ApprovalRequest, requested_by: &Option<String>) -> Result<ApprovalRequestDto, String> {
+    let (reason, note) = overrides::check_reason(&request.reason_code, &request.note)?;
+    let subject = request.subject.trim();
+    if subject.is_empty() {
+        return Err(i18n::t("error.approval_subject_required"));
+    }
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let id = db::approvals::insert(
+        &client,
+        &NewApprovalRequest {
+            action: request.action,
+            subject,
+            reason,
+            note: &note,
+            details: &request.details,
+            requested_by,
+            terminal_id: terminal::id(),
+        },
+    )
+    .await?;
+    db::approvals::find(&client, &id).await?.ok_or_else(|| i18n::t("error.approval_not_found"))
+}
+
+/// Approve or reject a pending request, with the supervisor's note if any
+pub async fn decide(client: &impl db::GenericClient, id: &str, approve: bool, note: Option<String>, supervisor_id: &str) -> Result<ApprovalRequestDto, String> {
+    let status = if approve { db::approvals::APPROVED } else { db::approvals::REJECTED };
+    let note = note.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    if !db::approvals::decide(client, id, status, supervisor_id, note).await? {
+        return Err(i18n::t("error.approval_not_pending"));
+    }
+    db::approvals::find(client, id).await?.ok_or_else(|| i18n::t("error.approval_not_found"))
+}
+
+// Payload of the approval_requests notification
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApprovalNotice {
+    id: String,
+    status: String,
+    terminal_id: Option<String>,
+}
+
+// The UI event a notice means for `terminal_id`: a new request for every inbox, the decision
+// for the terminal that asked and the end of it for the other inboxes
+fn event_for(notice: &ApprovalNotice, terminal_id: &str) -> Option<&'static str> {
+    match notice.status.as_str() {
+        db::approvals::PENDING => Some(REQUESTED_EVENT),
+        db::approvals::APPROVED | db::approvals::REJECTED if notice.terminal_id.as_deref() == Some(terminal_id) => Some(DECIDED_EVENT),
+        db::approvals::APPROVED | db::approvals::REJECTED => Some(CLOSED_EVENT),
+        _ => None,
+    }
+}
+
+/// Tell the UI about a request made or decided on any terminal
+pub fn notified(app_handle: &tauri::AppHandle, payload: &str) {
+    let Ok(notice) = serde_json::from_str::<ApprovalNotice>(payload) else {
+        return;
+    };
+    let Some(event) = event_for(&notice, terminal::id().unwrap_or_default()) else {
+        return;
+    };
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        // From the primary: a replica may not have the row yet
+        let request = match DB_POOL.get().await {
+            Ok(client) => db::approvals::find(&client, &notice.id).await,
+            Err(e) => Err(e.to_string()),
+        };
+        match request {
+            Ok(Some(request)) => {
+                let _ = app_handle.emit_all(event, &request);
+            }
+            Ok(None) => {}
+            Err(e) => println!("⚠️ [APPROVALS] Request {} not read: {}", notice.id, e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_reach_every_inbox_and_decisions_the_asking_terminal() {
+        let notice = |status: &str| ApprovalNotice { id: "a".into(), status: status.into(), terminal_id: Some("booth-2".into()) };
+        assert_eq!(event_for(&notice("PENDING"), "office"), Some(REQUESTED_EVENT));
+        assert_eq!(event_for(&notice("APPROVED"), "booth-2"), Some(DECIDED_EVENT));
+        assert_eq!(event_for(&notice("REJECTED"), "booth-2"), Some(DECIDED_EVENT));
+        assert_eq!(event_for(&notice("APPROVED"), "office"), Some(CLOSED_EVENT));
+        assert_eq!(event_for(&notice("CONSUMED"), "booth-2"), None);
+
+        let policy = ApprovalPolicy::parse(Some(r#"{"dispatchMinPercent":50}"#));
+        assert!(policy.dispatch_needs_sign_off(3, 8));
+        assert!(!policy.dispatch_needs_sign_off(4, 8));
+        assert!(!ApprovalPolicy::default().dispatch_needs_sign_off(0, 8));
+        assert!(ApprovalPolicy { dispatch_min_percent: 101 }.validate().is_err());
+
+        let request: ApprovalRequest =
+            serde_json::from_str(r#"{"action":"VOID_AFTER_WINDOW","subject":"AB12","reasonCode":"CASHIER_ERROR","note":"x"}"#).unwrap();
+        assert_eq!((request.action, request.details), (OverrideAction::VoidAfterWindow, serde_json::Value::Null));
+    }
+}
@@ -0,0 +1,21 @@
+// Append-only audit trail stored in the audit_log table (migration 004)
+
+/// Record an event; `actor_id` is the staff member who performed it, on this terminal. Pass the
+/// command's transaction to have the entry commit or roll back with the change.
+pub async fn record(
+    client: &impl crate::db::GenericClient,
+    event_type: &str,
+    actor_id: Option<&str>,
+    details: serde_json::Value,
+) -> Result<(), String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO audit_log (id, event_type, actor_id, details, terminal_id) VALUES ($1, $2, $3, $4, $5)",
+            &[&id, &event_type, &actor_id, &details, &crate::terminal::id()],
+        )
+        .await
+        .map_err(|e| format!("Failed to write audit log: {}", e))?;
+    println!("📝 [AUDIT] {} by {}", event_type, actor_id.unwrap_or("-"));
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::audit;
+use crate::i18n;
+use crate::printer::StaffInfo;
+use crate::DB_POOL;
+
+// PIN quick-switch: two cashiers sharing a terminal hand over with a PIN instead of
+// a full logout. PINs are stored as Argon2 hashes in staff.pin_hash.
+
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+const LOCKOUT: Duration = Duration::from_secs(30);
+
+// Cashier currently working on this terminal
+static ACTIVE_STAFF: Lazy<Mutex<Option<StaffInfo>>> = Lazy::new(|| Mutex::new(None));
+
+// Failed attempts since the last success, and when the terminal is unlocked again
+static FAILED_ATTEMPTS: Lazy<Mutex<(u32, Option<Instant>)>> = Lazy::new(|| Mutex::new((0, None)));
+
+pub fn validate_pin(pin: &str) -> Result<(), String> {
+    if pin.len() < 4 || pin.len() > 6 || !pin.chars().all(|c| c.is_ascii_digit()) {
+        return Err("Le code PIN doit contenir 4 à 6 chiffres".to_string());
+    }
+    Ok(())
+}
+
+pub fn hash_pin(pin: &str) -> Result<String, String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(pin.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash PIN: {}", e))
+}
+
+pub fn verify_pin(pin: &str, pin_hash: &str) -> bool {
+    match PasswordHash::new(pin_hash) {
+        Ok(parsed) => Argon2::default().verify_password(pin.as_bytes(), &parsed).is_ok(),
+        Err(_) => false,
+    }
+}
+
+fn check_lockout() -> Result<(), String> {
+    let mut state = FAILED_ATTEMPTS.lock().map_err(|e| e.to_string())?;
+    if let Some(until) = state.1 {
+        if Instant::now() < until {
+            let remaining = until.saturating_duration_since(Instant::now()).as_secs().max(1);
+            return Err(i18n::tf("error.pin_locked", &[("seconds", &remaining)]));
+        }
+        *state = (0, None);
+    }
+    Ok(())
+}
+
+fn register_failure() {
+    if let Ok(mut state) = FAILED_ATTEMPTS.lock() {
+        state.0 += 1;
+        if state.0 >= MAX_FAILED_ATTEMPTS {
+            state.1 = Some(Instant::now() + LOCKOUT);
+        }
+    }
+}
+
+struct StaffPin {
+    staff: StaffInfo,
+    pin_hash: String,
+}
+
+async fn load_staff_pins(client: &tokio_postgres::Client) -> Result<Vec<StaffPin>, String> {
+    let rows = client
+        .query(
+            "SELECT id, cin, first_name, last_name, role::text AS role, phone_number, pin_hash
+             FROM staff
+             WHERE is_active = true AND pin_hash IS NOT NULL",
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .map(|r| StaffPin {
+            staff: StaffInfo {
+                id: r.get("id"),
+                cin: r.get("cin"),
+                firstName: r.get("first_name"),
+                lastName: r.get("last_name"),
+                role: r.get("role"),
+                phoneNumber: r.get("phone_number"),
+            },
+            pin_hash: r.get("pin_hash"),
+        })
+        .collect())
+}
+
+// Argon2 is deliberately slow: keep verification off the async runtime
+async fn find_staff_by_pin(candidates: Vec<StaffPin>, pin: String) -> Result<Option<StaffInfo>, String> {
+    tokio::task::spawn_blocking(move || {
+        candidates
+            .into_iter()
+            .find(|candidate| verify_pin(&pin, &candidate.pin_hash))
+            .map(|candidate| candidate.staff)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Set or replace a staff member's PIN. The caller proves it may: with the staff member's
+/// current PIN, with a supervisor's or admin's PIN, or, for a first PIN only, by being the
+/// staff member logged in on this terminal. PINs stay unique so a PIN identifies one person,
+/// but a clash is refused like a wrong PIN so it doesn't tell whose PIN it is.
+#[tauri::command]
+pub async fn auth_set_pin(staff_id: String, pin: String, current_pin: Option<String>, supervisor_pin: Option<String>) -> Result<(), String> {
+    check_lockout()?;
+    validate_pin(&pin)?;
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+
+    let current_hash: Option<String> = client
+        .query_opt("SELECT pin_hash FROM staff WHERE id = $1 AND is_active = true", &[&staff_id])
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| i18n::tf("error.staff_not_found", &[("id", &staff_id)]))?
+        .get("pin_hash");
+    let authorized_by = match (supervisor_pin, current_pin, &current_hash) {
+        (Some(supervisor_pin), _, _) => verify_supervisor_pin(&supervisor_pin).await?.id,
+        (None, Some(current_pin), Some(hash)) => {
+            let hash = hash.clone();
+            if !tokio::task::spawn_blocking(move || verify_pin(&current_pin, &hash)).await.map_err(|e| e.to_string())? {
+                register_failure();
+                return Err(i18n::t("error.pin_incorrect"));
+            }
+            staff_id.clone()
+        }
+        (None, None, None) if active_staff_id().as_deref() == Some(staff_id.as_str()) => staff_id.clone(),
+        _ => return Err(i18n::t("error.pin_change_not_allowed")),
+    };
+
+    let others: Vec<StaffPin> = load_staff_pins(&client)
+        .await?
+        .into_iter()
+        .filter(|candidate| candidate.staff.id != staff_id)
+        .collect();
+    if find_staff_by_pin(others, pin.clone()).await?.is_some() {
+        register_failure();
+        return Err(i18n::t("error.pin_rejected"));
+    }
+
+    let pin_hash = tokio::task::spawn_blocking(move || hash_pin(&pin))
+        .await
+        .map_err(|e| e.to_string())??;
+    client
+        .execute(
+            "UPDATE staff SET pin_hash = $1, updated_at = NOW() WHERE id = $2",
+            &[&pin_hash, &staff_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    audit::record(&client, "STAFF_PIN_SET", Some(&authorized_by), serde_json::json!({ "staffId": staff_id })).await?;
+    Ok(())
+}
+
+/// Switch the terminal to the cashier owning `pin` and record the handover
+#[tauri::command]
+pub async fn auth_switch_user(pin: String) -> Result<StaffInfo, String> {
+    check_lockout()?;
+    validate_pin(&pin)?;
+
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let candidates = load_staff_pins(&client).await?;
+    let staff = match find_staff_by_pin(candidates, pin).await? {
+        Some(staff) => staff,
+        None => {
+            register_failure();
+            return Err(i18n::t("error.pin_incorrect"));
+        }
+    };
+    if let Ok(mut state) = FAILED_ATTEMPTS.lock() {
+        *state = (0, None);
+    }
+
+    let previous = {
+        let mut active = ACTIVE_STAFF.lock().map_err(|e| e.to_string())?;
+        active.replace(staff.clone())
+    };
+    let previous_id = previous.as_ref().map(|s| s.id.clone());
+
+    audit::record(
+        &client,
+        "STAFF_SWITCH",
+        Some(&staff.id),
+        serde_json::json!({
+            "fromStaffId": previous_id,
+            "fromStaffName": previous.as_ref().map(|s| format!("{} {}", s.firstName, s.lastName)),
+            "toStaffId": staff.id,
+            "toStaffName": format!("{} {}", staff.firstName, staff.lastName),
+        }),
+    )
+    .await?;
+
+    println!("🔁 [AUTH] Terminal switched to {} {} ({})", staff.firstName, staff.lastName, staff.id);
+    Ok(staff)
+}
+
+/// The supervisor or admin owning `pin`, for actions a cashier may not take alone
+pub async fn verify_supervisor_pin(pin: &str) -> Result<StaffInfo, String> {
+    check_lockout()?;
+    validate_pin(pin)?;
+
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let candidates = load_staff_pins(&client)
+        .await?
+        .into_iter()
+        .filter(|candidate| candidate.staff.role == "SUPERVISOR" || candidate.staff.role == "ADMIN")
+        .collect();
+    match find_staff_by_pin(candidates, pin.to_string()).await? {
+        Some(staff) => {
+            if let Ok(mut state) = FAILED_ATTEMPTS.lock() {
+                *state = (0, None);
+            }
+            Ok(staff)
+        }
+        None => {
+            register_failure();
+            Err("Code PIN superviseur incorrect".to_string())
+        }
+    }
+}
+
+/// The staff member a local node session token belongs to, as the database has them
+async fn staff_for_token(token: &str) -> Result<StaffInfo, String> {
+    let headers = std::collections::HashMap::from([("Authorization".to_string(), format!("Bearer {}", token))]);
+    let response = crate::proxy::request("GET", "/api/auth/verify-token", None, None, Some(headers), Default::default()).await?;
+    let body: serde_json::Value = serde_json::from_str(&response.body).unwrap_or_default();
+    let staff_id = [&body["staff"]["id"], &body["data"]["staff"]["id"]].into_iter().find_map(|id| id.as_str().map(str::to_string));
+    let staff_id = match (response.ok && body["success"].as_bool() == Some(true), staff_id) {
+        (true, Some(staff_id)) => staff_id,
+        _ => return Err(i18n::t("error.session_invalid")),
+    };
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let row = client
+        .query_opt(
+            "SELECT id, cin, first_name, last_name, role::text AS role, phone_number FROM staff WHERE id = $1 AND is_active = true",
+            &[&staff_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| i18n::t("error.session_invalid"))?;
+    Ok(StaffInfo {
+        id: row.get("id"),
+        cin: row.get("cin"),
+        firstName: row.get("first_name"),
+        lastName: row.get("last_name"),
+        role: row.get("role"),
+        phoneNumber: row.get("phone_number"),
+    })
+}
+
+/// Register the cashier who logged in normally, so the next switch knows who handed over. The
+/// staff member comes from the session token, checked with the local node, never from the UI;
+/// None logs out.
+#[tauri::command]
+pub async fn auth_set_active_staff(token: Option<String>) -> Result<Option<StaffInfo>, String> {
+    let staff = match token {
+        Some(token) => Some(staff_for_token(&token).await?),
+        None => None,
+    };
+    *ACTIVE_STAFF.lock().map_err(|e| e.to_string())? = staff.clone();
+    Ok(staff)
+}
+
+pub fn active_staff_id() -> Option<String> {
+    ACTIVE_STAFF.lock().ok()?.as_ref().map(|staff| staff.id.clone())
+}
+
+#[tauri::command]
+pub async fn auth_get_active_staff() -> Result<Option<StaffInfo>, String> {
+    Ok(ACTIVE_STAFF.lock().map_err(|e| e.to_string())?.clone())
+}
@@ -0,0 +1,210 @@
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::db;
+use crate::i18n;
+use crate::terminal;
+use crate::time;
+
+// Opening hours of the station. Outside them the booking and queue-entry commands are turned
+// away by the middleware (`check`), unless a supervisor signs off this terminal for
+// AFTER_HOURS_UNLOCK_MINUTES, with a PIN or from the approval inbox (approvals.rs). While the
+// hours are on, the queue closing (queue_closing.rs) runs at their closing time. The hours are
+// the business_hours setting (JSON in station_settings); each terminal reads it every minute,
+// since the middleware can't wait on the database, and shows OPEN/CLOSED in its tray.
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+pub const AFTER_HOURS_UNLOCK_MINUTES: i64 = 60;
+pub const BUSINESS_HOURS_EVENT: &str = "business-hours-changed";
+pub const TRAY_STATUS_ITEM: &str = "business_hours";
+
+// Commands that sell seats or put vehicles in the queue
+const GATED_COMMANDS: &[&str] = &[
+    "db_create_queue_booking",
+    "db_create_vehicle_specific_booking",
+    "db_enter_queue",
+    "db_quick_enter_queue",
+    "db_confirm_anpr_entry",
+    "kiosk_self_check_in",
+    "db_book_reservation_occurrence",
+    "db_book_waitlist_entry",
+];
+
+#[derive(Default)]
+struct State {
+    hours: BusinessHours,
+    unlocked_until: Option<DateTime<Utc>>,
+    // Last status shown in the tray, to tell the UI only of changes
+    shown: Option<(bool, BusinessHours)>,
+}
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(|| Mutex::new(State::default()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BusinessHours {
+    pub enabled: bool,
+    pub opens_at: NaiveTime,
+    pub closes_at: NaiveTime,
+}
+
+impl Default for BusinessHours {
+    fn default() -> Self {
+        BusinessHours { enabled: false, opens_at: NaiveTime::from_hms_opt(5, 0, 0).unwrap(), closes_at: NaiveTime::from_hms_opt(23, 0, 0).unwrap() }
+    }
+}
+
+impl BusinessHours {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    /// Opening before closing, on the same day
+    pub fn validate(&self) -> Result<(), String> {
+        if self.opens_at >= self.closes_at {
+            return Err(i18n::t("error.business_hours_invalid"));
+        }
+        Ok(())
+    }
+
+    /// Whether the station is open at Tunis wall-clock `time`; always when the hours are off
+    pub fn is_open(&self, time: NaiveTime) -> bool {
+        !self.enabled || (self.opens_at <= time && time < self.closes_at)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BusinessHoursStatusDto {
+    pub hours: BusinessHours,
+    pub open: bool,
+    /// Set while a supervisor's sign-off lets this terminal sell after hours
+    pub unlocked_until: Option<String>,
+    /// What an after-hours approval request names as its subject
+    pub terminal_id: Option<String>,
+}
+
+fn status_at(state: &State, now: DateTime<Utc>) -> BusinessHoursStatusDto {
+    BusinessHoursStatusDto {
+        hours: state.hours,
+        open: state.hours.is_open(now.with_timezone(&time::TZ).time()),
+        unlocked_until: state.unlocked_until.filter(|until| *until > now).map(|until| time::TunisTime(until.with_timezone(&time::TZ)).fmt_dto()),
+        terminal_id: terminal::id().map(str::to_string),
+    }
+}
+
+pub fn status() -> BusinessHoursStatusDto {
+    let state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    status_at(&state, crate::clock::now())
+}
+
+fn check_at(state: &State, command: &str, now: DateTime<Utc>) -> Result<(), String> {
+    if !GATED_COMMANDS.contains(&command) || state.unlocked_until.is_some_and(|until| until > now) {
+        return Ok(());
+    }
+    if state.hours.is_open(now.with_timezone(&time::TZ).time()) {
+        return Ok(());
+    }
+    Err(i18n::tf(
+        "error.station_closed",
+        &[("opens", &state.hours.opens_at.format("%H:%M")), ("closes", &state.hours.closes_at.format("%H:%M"))],
+    ))
+}
+
+/// Turn `command` away outside business hours, unless this terminal was signed off
+pub fn check(command: &str) -> Result<(), String> {
+    let state = STATE.lock().map_err(|e| e.to_string())?;
+    check_at(&state, command, crate::clock::now())
+}
+
+/// Let this terminal sell after hours for AFTER_HOURS_UNLOCK_MINUTES
+pub fn unlock() -> BusinessHoursStatusDto {
+    let now = crate::clock::now();
+    let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    state.unlocked_until = Some(now + Duration::minutes(AFTER_HOURS_UNLOCK_MINUTES));
+    status_at(&state, now)
+}
+
+/// Use new hours on this terminal right away; the others pick them up within a minute
+pub fn apply(app_handle: &tauri::AppHandle, hours: BusinessHours) {
+    let status = {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        state.hours = hours;
+        let status = status_at(&state, crate::clock::now());
+        if state.shown == Some((status.open, hours)) {
+            return;
+        }
+        state.shown = Some((status.open, hours));
+        status
+    };
+    show(app_handle, &status);
+}
+
+fn tray_label(status: &BusinessHoursStatusDto) -> String {
+    let (opens, closes) = (status.hours.opens_at.format("%H:%M"), status.hours.closes_at.format("%H:%M"));
+    if status.open {
+        i18n::tf("message.station_open", &[("closes", &closes)])
+    } else {
+        i18n::tf("message.station_closed", &[("opens", &opens)])
+    }
+}
+
+/// The status line at the top of the tray menu
+pub fn tray_title() -> String {
+    tray_label(&status())
+}
+
+fn show(app_handle: &tauri::AppHandle, status: &BusinessHoursStatusDto) {
+    let label = tray_label(status);
+    let tray = app_handle.tray_handle();
+    let _ = tray.set_tooltip(&format!("Wasla · {}", label));
+    let _ = tray.get_item(TRAY_STATUS_ITEM).set_title(label);
+    let _ = app_handle.emit_all(BUSINESS_HOURS_EVENT, status);
+    println!("🕘 [HOURS] Station {}", if status.open { "open" } else { "closed" });
+}
+
+/// Keep the hours up to date and the tray on OPEN/CLOSED
+pub async fn run(app_handle: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let hours = match db::pool::background_read().await {
+            Ok(client) => db::business_hours::hours(&client).await,
+            Err(e) => Err(e),
+        };
+        match hours {
+            Ok(hours) => apply(&app_handle, hours),
+            Err(e) => println!("⚠️ [HOURS] Business hours not read: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gated_commands_wait_for_opening_unless_signed_off() {
+        let hours = BusinessHours::parse(Some(r#"{"enabled":true,"opensAt":"05:30:00","closesAt":"22:00:00"}"#));
+        let at = |h: u32, m: u32| time::from_tunis_local(chrono::NaiveDate::from_ymd_opt(2026, 10, 15).unwrap().and_hms_opt(h, m, 0).unwrap()).with_timezone(&Utc);
+        let mut state = State { hours, ..Default::default() };
+
+        assert!(check_at(&state, "db_create_queue_booking", at(12, 0)).is_ok());
+        assert!(check_at(&state, "db_create_queue_booking", at(22, 0)).is_err());
+        assert!(check_at(&state, "db_enter_queue", at(5, 29)).is_err());
+        assert!(check_at(&state, "db_confirm_anpr_entry", at(23, 0)).is_err());
+        assert!(check_at(&state, "db_get_queue", at(3, 0)).is_ok());
+        assert!(!status_at(&state, at(23, 0)).open);
+
+        state.unlocked_until = Some(at(23, 0));
+        assert!(check_at(&state, "db_create_queue_booking", at(22, 30)).is_ok());
+        assert!(check_at(&state, "db_create_queue_booking", at(23, 0)).is_err());
+
+        assert!(BusinessHours::default().is_open(NaiveTime::from_hms_opt(3, 0, 0).unwrap()));
+        assert!(BusinessHours { closes_at: hours.opens_at, ..hours }.validate().is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_postgres::{AsyncMessage, NoTls};
+
+use crate::{approvals, day_pass_cache, destination_summary, location_tree, staff_names, terminal_messages};
+
+// One LISTEN connection per terminal for the caches that other terminals can make stale: day
+// passes (migration 019), routes (migration 020), staff names (migration 029) and the seats
+// left per destination (migration 038). Caches are emptied and bypassed whenever
+// the connection is down, since a change made meanwhile would go unseen. Messages between
+// terminals (migration 025) and approval requests (migration 037) come in on the same connection.
+
+const RECONNECT_SECS: u64 = 5;
+const DAY_PASS_CHANNEL: &str = "day_pass_cache";
+const ROUTES_CHANNEL: &str = "route_changes";
+const MESSAGES_CHANNEL: &str = "terminal_messages";
+const STAFF_CHANNEL: &str = "staff_changes";
+const APPROVALS_CHANNEL: &str = "approval_requests";
+const SEATS_CHANNEL: &str = "queue_seat_changes";
+
+fn set_listening(on: bool) {
+    day_pass_cache::set_listening(on);
+    location_tree::set_listening(on);
+    staff_names::set_listening(on);
+    destination_summary::set_listening(on);
+}
+
+fn dispatch(app_handle: &tauri::AppHandle, channel: &str, payload: &str) {
+    match channel {
+        DAY_PASS_CHANNEL => day_pass_cache::invalidate(payload),
+        ROUTES_CHANNEL => {
+            location_tree::invalidate();
+            destination_summary::clear();
+        }
+        MESSAGES_CHANNEL => terminal_messages::notified(app_handle, payload),
+        STAFF_CHANNEL => staff_names::invalidate(payload),
+        APPROVALS_CHANNEL => approvals::notified(app_handle, payload),
+        SEATS_CHANNEL => destination_summary::invalidate(payload),
+        _ => {}
+    }
+}
+
+/// Keep the LISTEN connection open and hand each notification to its cache; reconnects when it is lost
+pub async fn run(app_handle: tauri::AppHandle) {
+    loop {
+        if let Err(e) = listen(&app_handle).await {
+            println!("⚠️ [CACHE] Listener stopped: {}", e);
+        }
+        set_listening(false);
+        tokio::time::sleep(Duration::from_secs(RECONNECT_SECS)).await;
+    }
+}
+
+async fn listen(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let (client, mut connection) = tokio_postgres::connect(&crate::secrets::database_url(), NoTls)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (tx, mut rx) = mpsc::unbounded_channel::<(String, String)>();
+    let mut messages = futures::stream::poll_fn(move |cx| connection.poll_message(cx));
+    tokio::spawn(async move {
+        while let Some(message) = messages.next().await {
+            match message {
+                Ok(AsyncMessage::Notification(n)) => {
+                    if tx.send((n.channel().to_string(), n.payload().to_string())).is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    println!("⚠️ [CACHE] Connection error: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    client
+        .batch_execute(&format!(
+            "LISTEN {}; LISTEN {}; LISTEN {}; LISTEN {}; LISTEN {}; LISTEN {}",
+            DAY_PASS_CHANNEL, ROUTES_CHANNEL, MESSAGES_CHANNEL, STAFF_CHANNEL, APPROVALS_CHANNEL, SEATS_CHANNEL
+        ))
+        .await
+        .map_err(|e| e.to_string())?;
+    set_listening(true);
+    println!("✅ [CACHE] Listening for day pass, route, staff and seat changes, terminal messages and approval requests");
+    while let Some((channel, payload)) = rx.recv().await {
+        dispatch(app_handle, &channel, &payload);
+    }
+    Err("connection closed".to_string())
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::approvals::SignOff;
+use crate::db;
+use crate::db::booking::BookingDetails;
+use crate::i18n;
+use crate::money::Money;
+use crate::overrides::OverrideAction;
+use crate::tickets::CancellationSlip;
+
+// When a booking may be cancelled and what it costs, checked by every cancellation command.
+// Cancelling is free within `free_minutes` of the booking; after that `fee_per_seat` is kept
+// on each seat given up, never more than the seat refunds. Once the vehicle's exit pass is
+// printed the seats have left with it and cannot be cancelled without a supervisor's sign-off
+// (approvals.rs). The policy is the cancellation_policy setting (JSON in station_settings);
+// fees go to cancellation_fees.
+// A passenger who never boards (no_show.rs) gets back `no_show_refund_percent` of the fare.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CancellationPolicy {
+    pub free_minutes: i64,
+    pub fee_per_seat: Money,
+    pub forbid_after_exit: bool,
+    pub no_show_refund_percent: i64,
+}
+
+impl Default for CancellationPolicy {
+    fn default() -> Self {
+        CancellationPolicy { free_minutes: 15, fee_per_seat: Money::ZERO, forbid_after_exit: true, no_show_refund_percent: 0 }
+    }
+}
+
+impl CancellationPolicy {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.free_minutes < 0 || self.fee_per_seat < Money::ZERO || !(0..=100).contains(&self.no_show_refund_percent) {
+            return Err(i18n::t("error.invalid_cancellation_policy"));
+        }
+        Ok(())
+    }
+
+    /// Fee kept when `seats` seats refunding `refund` are cancelled at `now`; an error when the
+    /// cancellation is not allowed at all
+    pub fn assess(&self, booked_at: DateTime<Utc>, now: DateTime<Utc>, seats: i32, refund: Money, after_exit: bool) -> Result<Money, String> {
+        if after_exit && self.forbid_after_exit {
+            return Err(i18n::t("error.cancel_after_exit"));
+        }
+        if now - booked_at <= chrono::Duration::minutes(self.free_minutes) {
+            return Ok(Money::ZERO);
+        }
+        Ok((self.fee_per_seat * seats).min(refund))
+    }
+
+    /// What a no-show passenger gets back of the `paid` fare
+    pub fn no_show_refund(&self, paid: Money) -> Money {
+        paid.portion(self.no_show_refund_percent, 100)
+    }
+}
+
+/// Check the policy for giving up `seats` seats of `details` refunding `refund`, and record the
+/// fee it charges. Runs in the cancelling transaction; returns the slip to print after commit.
+/// A supervisor's sign-off lets seats go after the exit pass, as a VOID_AFTER_WINDOW override.
+pub async fn enforce(
+    client: &impl db::GenericClient,
+    details: &BookingDetails,
+    seats: i32,
+    refund: Money,
+    created_by: &Option<String>,
+    sign_off: Option<SignOff>,
+) -> Result<CancellationSlip, String> {
+    let policy = db::cancellations::policy(client).await?;
+    let mut after_exit = db::passes::exit_pass_printed(client, &details.booking.queue_id).await?;
+    if let (true, true, Some(sign_off)) = (after_exit, policy.forbid_after_exit, sign_off) {
+        let authorized = sign_off.authorize(client, OverrideAction::VoidAfterWindow, &details.verification_code).await?;
+        db::overrides::record_override(
+            client,
+            OverrideAction::VoidAfterWindow,
+            &authorized,
+            &details.verification_code,
+            serde_json::json!({ "licensePlate": details.license_plate, "destinationName": details.destination_name, "seats": seats, "refund": refund }),
+        )
+        .await?;
+        after_exit = false;
+    }
+    let fee = policy.assess(details.booking.created_at, crate::clock::now(), seats, refund, after_exit)?;
+    if fee.is_positive() {
+        db::cancellations::record_fee(client, details, seats, refund, fee, created_by).await?;
+    }
+    Ok(CancellationSlip::new(details, seats, refund, fee))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_at_first_then_a_fee_per_seat_and_never_after_exit() {
+        let policy = CancellationPolicy { free_minutes: 10, fee_per_seat: Money::from_tnd(0.5), forbid_after_exit: true, no_show_refund_percent: 0 };
+        let booked_at = Utc::now();
+        let later = |minutes| booked_at + chrono::Duration::minutes(minutes);
+        assert_eq!(policy.assess(booked_at, later(10), 2, Money::from_tnd(8.4), false), Ok(Money::ZERO));
+        assert_eq!(policy.assess(booked_at, later(11), 2, Money::from_tnd(8.4), false), Ok(Money::from_tnd(1.0)));
+        assert_eq!(policy.assess(booked_at, later(11), 1, Money::from_tnd(0.3), false), Ok(Money::from_tnd(0.3)));
+        assert!(policy.assess(booked_at, later(1), 1, Money::from_tnd(4.2), true).is_err());
+        let lenient = CancellationPolicy { forbid_after_exit: false, ..policy };
+        assert_eq!(lenient.assess(booked_at, later(1), 1, Money::from_tnd(4.2), true), Ok(Money::ZERO));
+
+        assert_eq!(CancellationPolicy::parse(Some(r#"{"freeMinutes":5}"#)).free_minutes, 5);
+        assert_eq!(CancellationPolicy::parse(Some("n/a")), CancellationPolicy::default());
+        assert!(CancellationPolicy { free_minutes: -1, ..policy }.validate().is_err());
+    }
+
+    #[test]
+    fn no_shows_get_back_their_share_of_the_fare() {
+        let policy = CancellationPolicy::default();
+        assert_eq!(policy.no_show_refund(Money::from_tnd(8.4)), Money::ZERO);
+        let half = CancellationPolicy { no_show_refund_percent: 50, ..policy };
+        assert_eq!(half.no_show_refund(Money::from_tnd(8.4)), Money::from_tnd(4.2));
+        assert_eq!(CancellationPolicy::parse(Some(r#"{"noShowRefundPercent":100}"#)).no_show_refund(Money::from_tnd(4.2)), Money::from_tnd(4.2));
+        assert!(CancellationPolicy { no_show_refund_percent: 101, ..policy }.validate().is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+use crate::money::Money;
+
+// Counting the drawer at shift close. The cashier enters how many of each note and coin the
+// till holds; the total is compared with the expected cash of their shift report and the
+// difference is kept with the count. A difference beyond the station threshold
+// (cash_variance_threshold in station_settings) prints a variance slip for the supervisor.
+
+/// Tunisian notes and coins, largest first
+pub const DENOMINATIONS: [Money; 12] = [
+    Money::from_millimes(50_000),
+    Money::from_millimes(20_000),
+    Money::from_millimes(10_000),
+    Money::from_millimes(5_000),
+    Money::from_millimes(2_000),
+    Money::from_millimes(1_000),
+    Money::from_millimes(500),
+    Money::from_millimes(200),
+    Money::from_millimes(100),
+    Money::from_millimes(50),
+    Money::from_millimes(20),
+    Money::from_millimes(10),
+];
+
+/// Used until a supervisor sets cash_variance_threshold
+pub const DEFAULT_VARIANCE_THRESHOLD: Money = Money::from_millimes(2_000);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DenominationCount {
+    pub denomination: Money,
+    pub count: i32,
+}
+
+/// One line per denomination, largest first, without empty lines; unknown notes and negative
+/// counts are refused
+pub fn normalize(lines: &[DenominationCount]) -> Result<Vec<DenominationCount>, String> {
+    let mut counts = [0i32; DENOMINATIONS.len()];
+    for line in lines {
+        let index = DENOMINATIONS
+            .iter()
+            .position(|d| *d == line.denomination)
+            .ok_or_else(|| i18n::tf("error.unknown_denomination", &[("amount", &line.denomination)]))?;
+        if line.count < 0 {
+            return Err(i18n::t("error.cash_count_negative"));
+        }
+        counts[index] += line.count;
+    }
+    Ok(DENOMINATIONS
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(denomination, count)| DenominationCount { denomination: *denomination, count })
+        .collect())
+}
+
+pub fn counted_total(lines: &[DenominationCount]) -> Money {
+    lines.iter().map(|l| l.denomination * l.count).sum()
+}
+
+/// Short or over by more than the threshold; a difference equal to it is tolerated
+pub fn exceeds_threshold(variance: Money, threshold: Money) -> bool {
+    variance.millimes().abs() > threshold.millimes()
+}
+
+/// The stored setting in dinars, or the default when missing or unreadable
+pub fn parse_threshold(value: Option<&str>) -> Money {
+    value
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .map(Money::from_tnd)
+        .filter(|t| t.millimes() >= 0)
+        .unwrap_or(DEFAULT_VARIANCE_THRESHOLD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(tnd: f64, count: i32) -> DenominationCount {
+        DenominationCount { denomination: Money::from_tnd(tnd), count }
+    }
+
+    #[test]
+    fn lines_are_merged_and_totalled() {
+        let lines = normalize(&[line(0.5, 3), line(20.0, 2), line(0.5, 1), line(10.0, 0)]).unwrap();
+        assert_eq!(lines, vec![line(20.0, 2), line(0.5, 4)]);
+        assert_eq!(counted_total(&lines), Money::from_tnd(42.0));
+        assert_eq!(counted_total(&normalize(&[]).unwrap()), Money::ZERO);
+    }
+
+    #[test]
+    fn unknown_notes_and_negative_counts_are_refused() {
+        assert!(normalize(&[line(0.3, 1)]).is_err());
+        assert!(normalize(&[line(5.0, -1)]).is_err());
+    }
+
+    #[test]
+    fn threshold_applies_both_ways() {
+        let threshold = parse_threshold(Some("2.5"));
+        assert_eq!(threshold, Money::from_tnd(2.5));
+        assert!(!exceeds_threshold(Money::from_tnd(-2.5), threshold));
+        assert!(exceeds_threshold(Money::from_tnd(-2.51), threshold));
+        assert!(exceeds_threshold(Money::from_tnd(3.0), threshold));
+        assert_eq!(parse_threshold(None), DEFAULT_VARIANCE_THRESHOLD);
+        assert_eq!(parse_threshold(Some("beaucoup")), DEFAULT_VARIANCE_THRESHOLD);
+    }
+}
@@ -0,0 +1,129 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::DB_POOL;
+
+// Guard against the station PC clock drifting away from PostgreSQL. Day-pass validity and exit
+// times are compared with NOW() in SQL, while tickets used to be stamped from the PC clock, so
+// a PC a few minutes off prints times the database disagrees with. Drift is measured at startup
+// and every few minutes; past the threshold the UI is blocked with a warning and every ticket
+// is stamped with database time until the clock is fixed.
+
+const CHECK_INTERVAL_SECS: u64 = 5 * 60;
+const DEFAULT_THRESHOLD_SECS: i64 = 60;
+pub const CLOCK_DRIFT_EVENT: &str = "clock-drift";
+
+// Database clock minus local clock, in milliseconds
+static OFFSET_MS: AtomicI64 = AtomicI64::new(0);
+static DRIFTING: AtomicBool = AtomicBool::new(false);
+static LAST_STATUS: Lazy<Mutex<Option<ClockStatusDto>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockStatusDto {
+    pub drift_seconds: f64, // positive when the PC is behind the database
+    pub threshold_seconds: i64,
+    pub drifting: bool,
+    pub checked_at: String,
+}
+
+/// CLOCK_DRIFT_THRESHOLD_SECS, 60 seconds when unset
+fn threshold_secs() -> i64 {
+    std::env::var("CLOCK_DRIFT_THRESHOLD_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_THRESHOLD_SECS)
+}
+
+pub fn exceeds(offset_ms: i64, threshold_secs: i64) -> bool {
+    offset_ms.abs() > threshold_secs * 1000
+}
+
+/// Current time: the local clock, or database time while the local clock is drifting
+pub fn now() -> chrono::DateTime<chrono::Utc> {
+    let local = chrono::Utc::now();
+    if DRIFTING.load(Ordering::Relaxed) {
+        local + chrono::Duration::milliseconds(OFFSET_MS.load(Ordering::Relaxed))
+    } else {
+        local
+    }
+}
+
+async fn measure_offset_ms() -> Result<i64, String> {
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let sent = chrono::Utc::now().timestamp_millis();
+    let row = client
+        .query_one("SELECT (EXTRACT(EPOCH FROM NOW()) * 1000)::float8 AS now_ms", &[])
+        .await
+        .map_err(|e| e.to_string())?;
+    let received = chrono::Utc::now().timestamp_millis();
+    let db_ms: f64 = row.get("now_ms");
+    // The server read its clock somewhere during the round trip; take the middle
+    Ok(db_ms as i64 - (sent + received) / 2)
+}
+
+/// Measure the drift once, switch ticket stamping accordingly and tell the UI
+pub async fn check(app_handle: &tauri::AppHandle) -> Result<ClockStatusDto, String> {
+    let offset_ms = measure_offset_ms().await?;
+    let threshold = threshold_secs();
+    let drifting = exceeds(offset_ms, threshold);
+    OFFSET_MS.store(offset_ms, Ordering::Relaxed);
+    let was_drifting = DRIFTING.swap(drifting, Ordering::Relaxed);
+
+    let status = ClockStatusDto {
+        drift_seconds: offset_ms as f64 / 1000.0,
+        threshold_seconds: threshold,
+        drifting,
+        checked_at: crate::time::tunis_now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+    };
+    if drifting {
+        println!("⏰ [CLOCK] Local clock is {:.1}s off the database; stamping tickets with database time", status.drift_seconds);
+    } else if was_drifting {
+        println!("✅ [CLOCK] Local clock back within {}s of the database", threshold);
+    }
+    if let Ok(mut last) = LAST_STATUS.lock() {
+        *last = Some(status.clone());
+    }
+    let _ = app_handle.emit_all(CLOCK_DRIFT_EVENT, &status);
+    Ok(status)
+}
+
+/// At startup, then every CHECK_INTERVAL_SECS
+pub async fn run_clock_guard(app_handle: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if let Err(e) = check(&app_handle).await {
+            println!("⚠️ [CLOCK] Drift check failed: {}", e);
+        }
+    }
+}
+
+/// Last measured drift, None before the first check completed
+#[tauri::command]
+pub async fn get_clock_status() -> Result<Option<ClockStatusDto>, String> {
+    Ok(LAST_STATUS.lock().map_err(|e| e.to_string())?.clone())
+}
+
+/// Measure again now, e.g. once the Windows clock has been corrected
+#[tauri::command]
+pub async fn check_clock_drift(app_handle: tauri::AppHandle) -> Result<ClockStatusDto, String> {
+    check(&app_handle).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift_counts_in_both_directions() {
+        assert!(!exceeds(0, 60));
+        assert!(!exceeds(60_000, 60));
+        assert!(exceeds(60_001, 60));
+        assert!(exceeds(-5 * 60_000, 60));
+    }
+}
@@ -0,0 +1,226 @@
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Crash reports for panics anywhere in the app, including inside spawned tasks where tokio
+// swallows them. Release builds have no console, so every println! also goes to a small
+// in-memory tail (see the println! override in main.rs) that is saved with the report.
+
+pub const LOG_TAIL_LINES: usize = 200;
+const REPORTS_DIR: &str = "crash_reports";
+
+static LOG_TAIL: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::with_capacity(LOG_TAIL_LINES)));
+static REPORTS_PATH: OnceCell<PathBuf> = OnceCell::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub id: String,
+    pub created_at: String,
+    pub app_version: String,
+    pub os: String,
+    pub thread: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    pub log_tail: Vec<String>,
+    #[serde(default)]
+    pub uploaded_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportSummaryDto {
+    pub id: String,
+    pub created_at: String,
+    pub app_version: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub uploaded_at: Option<String>,
+}
+
+impl From<CrashReport> for CrashReportSummaryDto {
+    fn from(r: CrashReport) -> Self {
+        CrashReportSummaryDto {
+            id: r.id,
+            created_at: r.created_at,
+            app_version: r.app_version,
+            message: r.message,
+            location: r.location,
+            uploaded_at: r.uploaded_at,
+        }
+    }
+}
+
+pub fn record_log_line(line: String) {
+    if let Ok(mut tail) = LOG_TAIL.lock() {
+        if tail.len() == LOG_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+}
+
+fn log_tail() -> Vec<String> {
+    LOG_TAIL.lock().map(|tail| tail.iter().cloned().collect()).unwrap_or_default()
+}
+
+fn reports_dir() -> PathBuf {
+    REPORTS_PATH.get().cloned().unwrap_or_else(|| {
+        std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|dir| dir.join(REPORTS_DIR)))
+            .unwrap_or_else(|| PathBuf::from(REPORTS_DIR))
+    })
+}
+
+// Ids are generated here; anything else could point outside the reports folder
+fn report_path(dir: &std::path::Path, id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Rapport invalide: {}", id));
+    }
+    Ok(dir.join(format!("{}.json", id)))
+}
+
+fn write_report(dir: &std::path::Path, report: &CrashReport) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let path = report_path(dir, &report.id)?;
+    let content = serde_json::to_string_pretty(report).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+fn read_report(dir: &std::path::Path, id: &str) -> Result<CrashReport, String> {
+    let content = fs::read_to_string(report_path(dir, id)?).map_err(|_| format!("Rapport introuvable: {}", id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Rapport illisible {}: {}", id, e))
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panic".to_string()
+    }
+}
+
+/// Reports go to `dir` (the app data folder) once Tauri has resolved it, next to the
+/// executable before that
+pub fn set_reports_dir(dir: PathBuf) {
+    let _ = REPORTS_PATH.set(dir);
+}
+
+/// Write a report for every panic, then let the default hook print it as before
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let now = crate::time::tunis_now();
+        let report = CrashReport {
+            id: format!("{}-{}", now.format("%Y%m%d-%H%M%S"), &uuid::Uuid::new_v4().simple().to_string()[..8]),
+            created_at: now.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            thread: std::thread::current().name().unwrap_or("unnamed").to_string(),
+            message: panic_message(info.payload()),
+            location: info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            log_tail: log_tail(),
+            uploaded_at: None,
+        };
+        match write_report(&reports_dir(), &report) {
+            Ok(path) => eprintln!("💥 [CRASH] Report written to {:?}", path),
+            Err(e) => eprintln!("💥 [CRASH] Failed to write crash report: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+#[tauri::command]
+pub async fn list_crash_reports() -> Result<Vec<CrashReportSummaryDto>, String> {
+    let dir = reports_dir();
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+    let mut reports: Vec<CrashReportSummaryDto> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.path().file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .filter_map(|id| read_report(&dir, &id).ok())
+        .map(CrashReportSummaryDto::from)
+        .collect();
+    reports.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(reports)
+}
+
+#[tauri::command]
+pub async fn get_crash_report(id: String) -> Result<CrashReport, String> {
+    read_report(&reports_dir(), &id)
+}
+
+/// Send one report to CRASH_REPORT_ENDPOINT. Nothing leaves the station unless a supervisor asks.
+#[tauri::command]
+pub async fn upload_crash_report(id: String) -> Result<CrashReportSummaryDto, String> {
+    let endpoint = std::env::var("CRASH_REPORT_ENDPOINT")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| "Aucun serveur de rapports configuré (CRASH_REPORT_ENDPOINT)".to_string())?;
+    let dir = reports_dir();
+    let mut report = read_report(&dir, &id)?;
+
+    let response = reqwest::Client::new()
+        .post(endpoint.trim())
+        .timeout(std::time::Duration::from_secs(30))
+        .json(&report)
+        .send()
+        .await
+        .map_err(|e| format!("Envoi du rapport impossible: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Le serveur a refusé le rapport: {}", response.status()));
+    }
+
+    report.uploaded_at = Some(crate::time::tunis_now().format("%Y-%m-%dT%H:%M:%S").to_string());
+    write_report(&dir, &report)?;
+    println!("📤 [CRASH] Report {} uploaded", report.id);
+    Ok(report.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_tail_keeps_the_last_lines() {
+        for i in 0..LOG_TAIL_LINES + 5 {
+            record_log_line(format!("line {}", i));
+        }
+        let tail = log_tail();
+        assert_eq!(tail.len(), LOG_TAIL_LINES);
+        // Other tests print too; the newest of ours is still in
+        assert!(tail.contains(&format!("line {}", LOG_TAIL_LINES + 4)));
+    }
+
+    #[test]
+    fn reports_round_trip_and_ids_stay_inside_the_folder() {
+        let dir = std::env::temp_dir().join(format!("nqlix-crash-{}", uuid::Uuid::new_v4()));
+        let report = CrashReport {
+            id: "20250310-101500-abcd1234".to_string(),
+            created_at: "2025-03-10T10:15:00".to_string(),
+            app_version: "1.0.0".to_string(),
+            os: "windows x86_64".to_string(),
+            thread: "tokio-runtime-worker".to_string(),
+            message: "index out of bounds".to_string(),
+            location: Some("src/main.rs:1:1".to_string()),
+            backtrace: String::new(),
+            log_tail: vec!["🚀 started".to_string()],
+            uploaded_at: None,
+        };
+        write_report(&dir, &report).unwrap();
+        assert_eq!(read_report(&dir, &report.id).unwrap(), report);
+        assert!(read_report(&dir, "../printer_config").is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::db;
+use crate::DB_POOL;
+
+// Whether a plate has a day pass today, cached per (plate, Tunis date) for the queue screens
+// that ask for the same plates every few seconds. The day_pass_cache NOTIFY (migration 019)
+// drops a plate whenever one of its passes changes on any terminal, and the whole cache goes
+// at midnight. While the listener (cache_listener.rs) is not connected nothing is cached, since
+// changes made on other terminals would go unseen.
+
+static CACHE: Lazy<Mutex<DayPassCache>> = Lazy::new(|| Mutex::new(DayPassCache::default()));
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default)]
+pub struct DayPassCache {
+    date: Option<NaiveDate>,
+    passes: HashMap<String, bool>,
+    // Bumped by every invalidation, so a read that raced with a change is not stored
+    generation: u64,
+}
+
+impl DayPassCache {
+    fn roll(&mut self, today: NaiveDate) {
+        if self.date != Some(today) {
+            self.passes.clear();
+            self.date = Some(today);
+            self.generation += 1;
+        }
+    }
+
+    /// Cached answers, the plates to read from the database and the generation to store them with
+    pub fn lookup(&mut self, today: NaiveDate, plates: &[String]) -> (HashMap<String, bool>, Vec<String>, u64) {
+        self.roll(today);
+        let mut hits = HashMap::new();
+        let mut misses = Vec::new();
+        for plate in plates {
+            match self.passes.get(plate) {
+                Some(has_pass) => {
+                    hits.insert(plate.clone(), *has_pass);
+                }
+                None => misses.push(plate.clone()),
+            }
+        }
+        (hits, misses, self.generation)
+    }
+
+    pub fn store(&mut self, today: NaiveDate, generation: u64, results: &HashMap<String, bool>) {
+        if self.date == Some(today) && self.generation == generation {
+            self.passes.extend(results.iter().map(|(plate, has_pass)| (plate.clone(), *has_pass)));
+        }
+    }
+
+    pub fn invalidate(&mut self, plate: &str) {
+        self.passes.remove(plate);
+        self.generation += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.passes.clear();
+        self.generation += 1;
+    }
+}
+
+fn cache() -> std::sync::MutexGuard<'static, DayPassCache> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Day pass of each plate today, from the cache where possible
+pub async fn has_pass_today_batch(license_plates: &[String]) -> Result<HashMap<String, bool>, String> {
+    if !LISTENING.load(Ordering::SeqCst) {
+        let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+        return db::passes::has_pass_today_batch(&client, license_plates).await;
+    }
+    let today = crate::time::tunis_today();
+    let (mut hits, misses, generation) = cache().lookup(today, license_plates);
+    if !misses.is_empty() {
+        let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+        let read = db::passes::has_pass_today_batch(&client, &misses).await?;
+        cache().store(today, generation, &read);
+        hits.extend(read);
+    }
+    Ok(hits)
+}
+
+/// Called by the cache listener as its connection comes and goes; the cache starts empty either way
+pub fn set_listening(on: bool) {
+    cache().clear();
+    LISTENING.store(on, Ordering::SeqCst);
+}
+
+/// A pass of `plate` changed on some terminal
+pub fn invalidate(plate: &str) {
+    cache().invalidate(plate);
+}
+
+/// Forget every plate, as when the terminal switches to or from training data
+pub fn clear() {
+    cache().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plates(list: &[&str]) -> Vec<String> {
+        list.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn cached_plates_are_dropped_on_change_and_at_midnight() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let mut cache = DayPassCache::default();
+        let (hits, misses, generation) = cache.lookup(today, &plates(&["A", "B"]));
+        assert!(hits.is_empty());
+        cache.store(today, generation, &HashMap::from([("A".to_string(), true), ("B".to_string(), false)]));
+
+        let (hits, misses_after, _) = cache.lookup(today, &plates(&["A", "B", "C"]));
+        assert_eq!(misses, plates(&["A", "B"]));
+        assert_eq!(hits, HashMap::from([("A".to_string(), true), ("B".to_string(), false)]));
+        assert_eq!(misses_after, plates(&["C"]));
+
+        cache.invalidate("B");
+        assert_eq!(cache.lookup(today, &plates(&["B"])).1, plates(&["B"]));
+        assert_eq!(cache.lookup(today.succ_opt().unwrap(), &plates(&["A"])).1, plates(&["A"]));
+    }
+
+    #[test]
+    fn a_read_that_raced_with_a_change_is_not_stored() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let mut cache = DayPassCache::default();
+        let (_, _, generation) = cache.lookup(today, &plates(&["A"]));
+        cache.invalidate("A");
+        cache.store(today, generation, &HashMap::from([("A".to_string(), false)]));
+        assert_eq!(cache.lookup(today, &plates(&["A"])).1, plates(&["A"]));
+    }
+}
@@ -0,0 +1,112 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::i18n;
+use crate::time::TunisTime;
+
+// Notes on service days (migration 030): holidays, strikes, storms. Reports over a range of days
+// carry the notes of those days so an odd day explains itself in the export.
+
+const MAX_LABEL_CHARS: usize = 120;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarAnnotationDto {
+    pub id: String,
+    pub date: NaiveDate,
+    pub label: String,
+    pub created_by_name: Option<String>,
+    pub created_at: String,
+}
+
+fn map_annotation_row(r: &Row) -> CalendarAnnotationDto {
+    CalendarAnnotationDto {
+        id: r.get("id"),
+        date: r.get("day"),
+        label: r.get("label"),
+        created_by_name: r.get("created_by_name"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+/// The label trimmed, refused when empty or too long
+pub fn clean_label(label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(i18n::t("error.annotation_label_required"));
+    }
+    if label.chars().count() > MAX_LABEL_CHARS {
+        return Err(i18n::tf("error.annotation_label_too_long", &[("max", &MAX_LABEL_CHARS)]));
+    }
+    Ok(label.to_string())
+}
+
+/// One `Note,date,label` CSV line per annotation, for the report exports
+pub fn csv_lines(annotations: &[CalendarAnnotationDto]) -> String {
+    annotations
+        .iter()
+        .map(|a| format!("Note,{},{}\n", a.date.format("%Y-%m-%d"), super::customers::csv_field(&a.label)))
+        .collect()
+}
+
+pub async fn insert(client: &impl GenericClient, day: NaiveDate, label: &str, created_by: &Option<String>) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO calendar_annotations (id, day, label, created_by) VALUES ($1, $2, $3, $4)",
+            &[&id, &day, &label, created_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+/// Notes of the days `from` to `to`, both included, by day then in the order they were added
+pub async fn between(client: &impl GenericClient, from: NaiveDate, to: NaiveDate) -> Result<Vec<CalendarAnnotationDto>, String> {
+    let rows = client
+        .query(
+            r#"SELECT a.id, a.day, a.label, a.created_at, st.first_name || ' ' || st.last_name AS created_by_name
+               FROM calendar_annotations a
+               LEFT JOIN staff st ON st.id = a.created_by
+               WHERE a.day BETWEEN $1 AND $2
+               ORDER BY a.day, a.created_at"#,
+            &[&from, &to],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_annotation_row).collect())
+}
+
+pub async fn delete(client: &impl GenericClient, id: &str) -> Result<u64, String> {
+    client
+        .execute("DELETE FROM calendar_annotations WHERE id = $1", &[&id])
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_are_bounded_and_exported_one_note_per_line() {
+        assert_eq!(clean_label("  Aïd el-Fitr "), Ok("Aïd el-Fitr".to_string()));
+        assert!(clean_label("   ").is_err());
+        assert!(clean_label(&"é".repeat(MAX_LABEL_CHARS)).is_ok());
+        assert!(clean_label(&"é".repeat(MAX_LABEL_CHARS + 1)).is_err());
+
+        let note = |day: u32, label: &str| CalendarAnnotationDto {
+            id: format!("n{}", day),
+            date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            label: label.to_string(),
+            created_by_name: None,
+            created_at: String::new(),
+        };
+        assert_eq!(
+            csv_lines(&[note(20, "Fête de l'indépendance"), note(21, "Grève, transport")]),
+            "Note,2026-03-20,Fête de l'indépendance\nNote,2026-03-21,\"Grève, transport\"\n"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+
+use super::GenericClient;
+use crate::time::TunisTime;
+
+// Raw material for the anomaly rules (see anomalies.rs)
+
+const CANCEL_EVENTS: &[&str] = &["COMMAND:db_cancel_queue_booking", "COMMAND:db_cancel_seat_from_destination"];
+
+/// Cancel commands in the audit log as (staff name, time), oldest first
+pub async fn cancellations(client: &impl GenericClient, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<(String, DateTime<Utc>)>, String> {
+    let rows = client
+        .query(
+            r#"SELECT COALESCE(st.first_name || ' ' || st.last_name, a.actor_id) AS staff_name, a.created_at
+               FROM audit_log a
+               LEFT JOIN staff st ON st.id = a.actor_id
+               WHERE a.event_type = ANY($1) AND a.actor_id IS NOT NULL AND a.created_at >= $2 AND a.created_at < $3
+               ORDER BY a.created_at"#,
+            &[&CANCEL_EVENTS, &from, &to],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| (r.get("staff_name"), r.get("created_at"))).collect())
+}
+
+/// `event_type` entries of the audit log carrying a licensePlate, as (plate, time), oldest first
+pub async fn audit_events_by_plate(client: &impl GenericClient, event_type: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<(String, DateTime<Utc>)>, String> {
+    let rows = client
+        .query(
+            r#"SELECT details->>'licensePlate' AS license_plate, created_at
+               FROM audit_log
+               WHERE event_type = $1 AND details ? 'licensePlate' AND created_at >= $2 AND created_at < $3
+               ORDER BY created_at"#,
+            &[&event_type, &from, &to],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| (r.get("license_plate"), r.get("created_at"))).collect())
+}
+
+pub struct RemovedAfterExit {
+    pub license_plate: String,
+    pub exit_at: String,
+    pub seats_on_pass: i32,
+    pub seats_now: i32,
+}
+
+/// Exit passes whose queue entry now holds fewer paid seats than the pass counted
+pub async fn bookings_removed_after_exit(client: &impl GenericClient, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<RemovedAfterExit>, String> {
+    let rows = client
+        .query(
+            r#"SELECT license_plate, created_at, seats_sold, seats_now
+               FROM (
+                   SELECT e.license_plate, e.created_at, e.seats_sold,
+                          (SELECT COALESCE(SUM(b.seats_booked), 0)::int FROM bookings b
+                           WHERE b.queue_id = e.queue_id AND b.payment_status = 'PAID') AS seats_now
+                   FROM exit_passes e
+                   WHERE e.seats_sold IS NOT NULL AND e.created_at >= $1 AND e.created_at < $2
+               ) passes
+               WHERE seats_now < seats_sold
+               ORDER BY created_at"#,
+            &[&from, &to],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| RemovedAfterExit {
+            license_plate: r.get("license_plate"),
+            exit_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+            seats_on_pass: r.get("seats_sold"),
+            seats_now: r.get("seats_now"),
+        })
+        .collect())
+}
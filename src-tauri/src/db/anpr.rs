@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::time::TunisTime;
+
+// Queue entries the gate camera made, waiting for a cashier (see anpr.rs)
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingAnprEntryDto {
+    pub queue_id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub queue_position: i32,
+    pub entered_at: String,
+}
+
+fn map_pending_row(r: &Row) -> PendingAnprEntryDto {
+    PendingAnprEntryDto {
+        queue_id: r.get("id"),
+        license_plate: r.get("license_plate"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        queue_position: r.get("queue_position"),
+        entered_at: r.get::<_, TunisTime>("entered_at").fmt_dto(),
+    }
+}
+
+/// The stored plate a camera read stands for; `normalized` is the read's letters and digits
+pub async fn match_plate(client: &impl GenericClient, normalized: &str) -> Result<Option<String>, String> {
+    let row = client
+        .query_opt(
+            "SELECT license_plate FROM vehicles WHERE regexp_replace(upper(license_plate), '[^0-9A-Z]', '', 'g') = $1 LIMIT 1",
+            &[&normalized],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("license_plate")))
+}
+
+/// Entries waiting for confirmation, oldest first
+pub async fn pending(client: &impl GenericClient) -> Result<Vec<PendingAnprEntryDto>, String> {
+    let rows = client
+        .query(
+            r#"SELECT q.id, v.license_plate, q.destination_id, q.destination_name, q.queue_position, q.entered_at
+               FROM vehicle_queue q JOIN vehicles v ON v.id = q.vehicle_id
+               WHERE q.status = 'PENDING_CONFIRM'
+               ORDER BY q.entered_at"#,
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_pending_row).collect())
+}
+
+/// Confirm a pending entry: it waits like any other and its seats go on sale. None when it is
+/// not pending (already confirmed, rejected or gone).
+pub async fn confirm(client: &impl GenericClient, queue_id: &str) -> Result<Option<PendingAnprEntryDto>, String> {
+    let row = client
+        .query_opt(
+            r#"UPDATE vehicle_queue q SET status = 'WAITING', available_seats = q.total_seats
+               FROM vehicles v
+               WHERE q.id = $1 AND q.status = 'PENDING_CONFIRM' AND v.id = q.vehicle_id
+               RETURNING q.id, v.license_plate, q.destination_id, q.destination_name, q.queue_position, q.entered_at"#,
+            &[&queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_pending_row))
+}
+
+/// Take a pending entry out of the queue; its destination id, None when it is not pending
+pub async fn reject(client: &impl GenericClient, queue_id: &str) -> Result<Option<String>, String> {
+    let row = client
+        .query_opt(
+            "DELETE FROM vehicle_queue WHERE id = $1 AND status = 'PENDING_CONFIRM' RETURNING destination_id",
+            &[&queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("destination_id")))
+}
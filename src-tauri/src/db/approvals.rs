@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::{settings, GenericClient};
+use crate::approvals::ApprovalPolicy;
+use crate::overrides::{AuthorizedOverride, OverrideAction, OverrideReason};
+use crate::printer::StaffInfo;
+use crate::time::TunisTime;
+
+// Approval requests (migration 037, see approvals.rs) and the approval policy setting
+
+const POLICY_KEY: &str = "approval_policy";
+pub const PENDING: &str = "PENDING";
+pub const APPROVED: &str = "APPROVED";
+pub const REJECTED: &str = "REJECTED";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequestDto {
+    pub id: String,
+    pub action: String,
+    pub subject: String,
+    pub reason_code: String,
+    pub note: String,
+    pub details: serde_json::Value,
+    pub requested_by: Option<String>,
+    pub requested_by_name: Option<String>,
+    pub terminal_id: Option<String>,
+    pub terminal_name: Option<String>,
+    /// PENDING, APPROVED, REJECTED or CONSUMED
+    pub status: String,
+    pub decided_by_name: Option<String>,
+    pub decision_note: Option<String>,
+    pub decided_at: Option<String>,
+    pub created_at: String,
+}
+
+const SELECT_SQL: &str = r#"
+    SELECT a.id, a.action, a.subject, a.reason_code, a.note, a.details, a.requested_by,
+           rb.first_name || ' ' || rb.last_name AS requested_by_name, a.terminal_id,
+           t.name AS terminal_name, a.status, db.first_name || ' ' || db.last_name AS decided_by_name,
+           a.decision_note, a.decided_at, a.created_at
+    FROM approval_requests a
+    LEFT JOIN staff rb ON rb.id = a.requested_by
+    LEFT JOIN staff db ON db.id = a.decided_by
+    LEFT JOIN terminals t ON t.id = a.terminal_id"#;
+
+fn map_request_row(r: &Row) -> ApprovalRequestDto {
+    ApprovalRequestDto {
+        id: r.get("id"),
+        action: r.get("action"),
+        subject: r.get("subject"),
+        reason_code: r.get("reason_code"),
+        note: r.get("note"),
+        details: r.get("details"),
+        requested_by: r.get("requested_by"),
+        requested_by_name: r.get("requested_by_name"),
+        terminal_id: r.get("terminal_id"),
+        terminal_name: r.get("terminal_name"),
+        status: r.get("status"),
+        decided_by_name: r.get("decided_by_name"),
+        decision_note: r.get("decision_note"),
+        decided_at: r.get::<_, Option<TunisTime>>("decided_at").map(|t| t.fmt_dto()),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+pub async fn policy(client: &impl GenericClient) -> Result<ApprovalPolicy, String> {
+    Ok(ApprovalPolicy::parse(settings::get(client, POLICY_KEY).await?.as_deref()))
+}
+
+pub async fn set_policy(client: &impl GenericClient, policy: &ApprovalPolicy) -> Result<(), String> {
+    settings::set(client, POLICY_KEY, &serde_json::to_string(policy).map_err(|e| e.to_string())?).await
+}
+
+pub struct NewApprovalRequest<'a> {
+    pub action: OverrideAction,
+    pub subject: &'a str,
+    pub reason: OverrideReason,
+    pub note: &'a str,
+    pub details: &'a serde_json::Value,
+    pub requested_by: &'a Option<String>,
+    pub terminal_id: Option<&'a str>,
+}
+
+pub async fn insert(client: &impl GenericClient, request: &NewApprovalRequest<'_>) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO approval_requests (id, action, subject, reason_code, note, details, requested_by, terminal_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            &[
+                &id,
+                &request.action.as_str(),
+                &request.subject,
+                &request.reason.as_str(),
+                &request.note,
+                request.details,
+                request.requested_by,
+                &request.terminal_id,
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+pub async fn find(client: &impl GenericClient, id: &str) -> Result<Option<ApprovalRequestDto>, String> {
+    let sql = format!("{} WHERE a.id = $1", SELECT_SQL);
+    let row = client.query_opt(sql.as_str(), &[&id]).await.map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_request_row))
+}
+
+/// Requests waiting for a supervisor, oldest first
+pub async fn pending(client: &impl GenericClient) -> Result<Vec<ApprovalRequestDto>, String> {
+    let sql = format!("{} WHERE a.status = '{}' ORDER BY a.created_at", SELECT_SQL, PENDING);
+    let rows = client.query(sql.as_str(), &[]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_request_row).collect())
+}
+
+/// Approve or reject a pending request; false when it is not pending (any more)
+pub async fn decide(client: &impl GenericClient, id: &str, status: &str, decided_by: &str, note: Option<&str>) -> Result<bool, String> {
+    let updated = client
+        .execute(
+            "UPDATE approval_requests SET status = $2, decided_by = $3, decision_note = $4, decided_at = NOW()
+             WHERE id = $1 AND status = 'PENDING'",
+            &[&id, &status, &decided_by, &note],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(updated == 1)
+}
+
+/// Use up a request approved for `action` on `subject` within the last `valid_minutes`, as the
+/// override of the supervisor who approved it; None when there is no such request. Call it in
+/// the transaction of the action.
+pub async fn consume(client: &impl GenericClient, id: &str, action: OverrideAction, subject: &str, valid_minutes: i32) -> Result<Option<AuthorizedOverride>, String> {
+    let row = client
+        .query_opt(
+            "WITH used AS (
+                 UPDATE approval_requests SET status = 'CONSUMED', consumed_at = NOW()
+                 WHERE id = $1 AND status = 'APPROVED' AND action = $2 AND subject = $3
+                   AND decided_at > NOW() - make_interval(mins => $4::int)
+                 RETURNING decided_by, reason_code, note, decision_note
+             )
+             SELECT s.id, s.cin, s.first_name, s.last_name, s.role::text AS role, s.phone_number,
+                    used.reason_code, used.note, used.decision_note
+             FROM used JOIN staff s ON s.id = used.decided_by",
+            &[&id, &action.as_str(), &subject, &valid_minutes],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| {
+        let note: String = r.get("note");
+        // The supervisor's own note, when given, is kept after the cashier's
+        let decision_note: Option<String> = r.get("decision_note");
+        AuthorizedOverride {
+            supervisor: StaffInfo {
+                id: r.get("id"),
+                cin: r.get("cin"),
+                firstName: r.get("first_name"),
+                lastName: r.get("last_name"),
+                role: r.get("role"),
+                phoneNumber: r.get("phone_number"),
+            },
+            reason: OverrideReason::from_name(&r.get::<_, String>("reason_code")).unwrap_or(OverrideReason::Other),
+            note: match decision_note.filter(|n| !n.trim().is_empty()) {
+                Some(decision) => format!("{} / {}", note, decision.trim()),
+                None => note,
+            },
+        }
+    }))
+}
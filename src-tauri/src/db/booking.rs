@@ -0,0 +1,290 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio_postgres::Row;
+
+use super::{pricing, queue, GenericClient};
+use crate::money::{Money, SERVICE_FEE_PER_SEAT};
+use crate::tickets::BookingTicket;
+
+pub struct BookingRef {
+    pub id: String,
+    pub queue_id: String,
+    pub seats_booked: i32,
+    pub total_amount: Money,
+    pub created_at: DateTime<Utc>,
+}
+
+// A booking with what the cashier needs to confirm a cancel and print its slip
+pub struct BookingDetails {
+    pub booking: BookingRef,
+    pub verification_code: String,
+    pub destination_name: String,
+    pub license_plate: String,
+}
+
+fn map_details_row(r: &Row) -> BookingDetails {
+    BookingDetails {
+        booking: BookingRef {
+            id: r.get("id"),
+            queue_id: r.get("queue_id"),
+            seats_booked: r.get("seats_booked"),
+            total_amount: r.get("total_amount"),
+            created_at: r.get("created_at"),
+        },
+        verification_code: r.get("verification_code"),
+        destination_name: r.get("destination_name"),
+        license_plate: r.get("license_plate"),
+    }
+}
+
+// The vehicle may have left the queue already; its exit pass then names the destination and plate
+pub async fn find(client: &impl GenericClient, booking_id: &str) -> Result<Option<BookingDetails>, String> {
+    let row = client
+        .query_opt(
+            r#"
+            SELECT b.id, b.queue_id, b.seats_booked, b.total_amount, b.created_at, b.verification_code,
+                   COALESCE(vq.destination_name, ep.destination_name, '') AS destination_name,
+                   COALESCE(v.license_plate, ep.license_plate, '') AS license_plate
+            FROM bookings b
+            LEFT JOIN vehicle_queue vq ON vq.id = b.queue_id
+            LEFT JOIN vehicles v ON v.id = vq.vehicle_id
+            LEFT JOIN LATERAL (
+                SELECT destination_name, license_plate FROM exit_passes WHERE queue_id = b.queue_id LIMIT 1
+            ) ep ON true
+            WHERE b.id = $1
+            "#,
+            &[&booking_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_details_row))
+}
+
+// Latest booking on a vehicle still queued for `destination_id`, optionally limited to one cashier
+pub async fn latest_for_destination(client: &impl GenericClient, destination_id: &str, created_by: Option<&str>) -> Result<Option<BookingDetails>, String> {
+    let row = client
+        .query_opt(
+            r#"
+            SELECT b.id, b.queue_id, b.seats_booked, b.total_amount, b.created_at, b.verification_code, vq.destination_name, v.license_plate
+            FROM bookings b
+            JOIN vehicle_queue vq ON b.queue_id = vq.id
+            JOIN vehicles v ON vq.vehicle_id = v.id
+            WHERE vq.destination_id = $1 AND ($2::text IS NULL OR b.created_by = $2)
+            ORDER BY b.created_at DESC
+            LIMIT 1
+            "#,
+            &[&destination_id, &created_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_details_row))
+}
+
+// Why a booking was refused. NoSeats is what the UI offers the waiting list on; everything else
+// is shown as is.
+#[derive(Debug, Serialize)]
+#[serde(tag = "code", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BookingError {
+    NoSeats { message: String },
+    Failed { message: String },
+}
+
+impl From<String> for BookingError {
+    fn from(message: String) -> Self {
+        BookingError::Failed { message }
+    }
+}
+
+impl From<BookingError> for String {
+    fn from(error: BookingError) -> Self {
+        match error {
+            BookingError::NoSeats { message } | BookingError::Failed { message } => message,
+        }
+    }
+}
+
+// Paid seats on one queue entry and what the cashiers collected for them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoldSeats {
+    pub seats: i32,
+    pub collected: Money,
+}
+
+impl SoldSeats {
+    /// What the driver is owed: the collected fares less the station's fee on each seat
+    pub fn due_to_driver(&self) -> Money {
+        self.collected - SERVICE_FEE_PER_SEAT * self.seats
+    }
+}
+
+pub async fn sold_for_queue(client: &impl GenericClient, queue_id: &str) -> Result<SoldSeats, String> {
+    let row = client
+        .query_one(
+            "SELECT COALESCE(SUM(seats_booked), 0)::int AS seats, COALESCE(SUM(total_amount), 0)::float8 AS collected
+             FROM bookings WHERE queue_id = $1 AND payment_status = 'PAID'",
+            &[&queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(SoldSeats { seats: row.get("seats"), collected: row.get("collected") })
+}
+
+pub async fn count_queued_for_destination(client: &impl GenericClient, destination_id: &str) -> Result<i64, String> {
+    let row = client
+        .query_one("SELECT COUNT(*) AS count FROM vehicle_queue WHERE destination_id = $1", &[&destination_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get("count"))
+}
+
+// Delete a booking and give its seats back to the vehicle
+pub async fn cancel(client: &impl GenericClient, booking: &BookingRef) -> Result<(), String> {
+    client
+        .execute("DELETE FROM bookings WHERE id = $1", &[&booking.id])
+        .await
+        .map_err(|e| e.to_string())?;
+    queue::release_seats(client, &booking.queue_id, booking.seats_booked).await
+}
+
+/// What one seat of the booking gives back: what the remaining seats no longer cost, so the
+/// refund and the new total add up to the old total
+pub fn seat_refund(booking: &BookingRef) -> Money {
+    if booking.seats_booked <= 1 {
+        return booking.total_amount;
+    }
+    booking.total_amount - booking.total_amount.portion((booking.seats_booked - 1) as i64, booking.seats_booked as i64)
+}
+
+// Take one seat off a booking, refunding its share of the amount; a one-seat booking is deleted
+pub async fn cancel_one_seat(client: &impl GenericClient, booking: &BookingRef) -> Result<(), String> {
+    if booking.seats_booked <= 1 {
+        client
+            .execute("DELETE FROM bookings WHERE id = $1", &[&booking.id])
+            .await
+            .map_err(|e| e.to_string())?;
+    } else {
+        let refund = seat_refund(booking);
+        let new_seats = booking.seats_booked - 1;
+        let new_total = booking.total_amount - refund;
+        client
+            .execute(
+                "UPDATE bookings SET seats_booked = $1, total_amount = $2, refund_amount = COALESCE(refund_amount, 0) + $3 WHERE id = $4",
+                &[&new_seats, &new_total.to_tnd(), &refund.to_tnd(), &booking.id],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    queue::release_seats(client, &booking.queue_id, 1).await
+}
+
+// Keep a booking on record but stop counting its seats as sold, noting what was given back;
+// the caller decides whether the seats go back to the vehicle
+pub async fn void(client: &impl GenericClient, booking: &BookingRef, refund: Money) -> Result<(), String> {
+    client
+        .execute(
+            "UPDATE bookings SET payment_status = 'CANCELLED', refund_amount = COALESCE(refund_amount, 0) + $1, updated_at = NOW() WHERE id = $2",
+            &[&refund.to_tnd(), &booking.id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Paid bookings on a queue entry as (id, seats), the latest first
+pub async fn on_queue(client: &impl GenericClient, queue_id: &str) -> Result<Vec<(String, i32)>, String> {
+    let rows = client
+        .query("SELECT id, seats_booked FROM bookings WHERE queue_id = $1 AND payment_status = 'PAID' ORDER BY created_at DESC, id", &[&queue_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| (r.get("id"), r.get("seats_booked"))).collect())
+}
+
+// Put bookings on another queue entry, taking its sub-route; seat counts are the caller's to adjust
+pub async fn move_to_queue(client: &impl GenericClient, booking_ids: &[String], queue_id: &str) -> Result<u64, String> {
+    client
+        .execute(
+            "UPDATE bookings b SET queue_id = q.id, sub_route = q.sub_route, sub_route_name = q.sub_route_name, updated_at = NOW()
+             FROM vehicle_queue q WHERE q.id = $2 AND b.id = ANY($1)",
+            &[&booking_ids, &queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Customer tickets of bookings as they stand, for a reprint
+pub async fn tickets(client: &impl GenericClient, booking_ids: &[String]) -> Result<Vec<BookingTicket>, String> {
+    let rows = client
+        .query(
+            r#"
+            SELECT b.id, b.verification_code, b.queue_id, b.seats_booked, b.total_amount, b.payment_method, b.created_by,
+                   v.license_plate, vq.destination_name, vq.queue_position,
+                   st.first_name || ' ' || st.last_name AS staff_name
+            FROM bookings b
+            JOIN vehicle_queue vq ON vq.id = b.queue_id
+            JOIN vehicles v ON v.id = vq.vehicle_id
+            LEFT JOIN staff st ON st.id = b.created_by
+            WHERE b.id = ANY($1)
+            ORDER BY b.created_at
+            "#,
+            &[&booking_ids],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut tickets = Vec::new();
+    for r in &rows {
+        tickets.push(BookingTicket {
+            verification_code: r.get("verification_code"),
+            queue_id: r.get("queue_id"),
+            license_plate: r.get("license_plate"),
+            destination_name: r.get("destination_name"),
+            queue_position: r.get("queue_position"),
+            seats_booked: r.get("seats_booked"),
+            total_amount: r.get("total_amount"),
+            fare_modifiers: pricing::for_booking(client, r.get::<_, &str>("id")).await?,
+            payment_method: r.get("payment_method"),
+            staff_name: r.get("staff_name"),
+            staff_id: r.get("created_by"),
+            ..Default::default()
+        });
+    }
+    Ok(tickets)
+}
+
+// Result already recorded for a client request id. Holds a transaction-scoped lock on the id
+// so a retry racing the original waits for it to commit instead of booking a second time.
+pub async fn replayed_request(client: &impl GenericClient, request_id: &str) -> Result<Option<serde_json::Value>, String> {
+    client
+        .execute("SELECT pg_advisory_xact_lock(hashtext($1))", &[&request_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    let row = client
+        .query_opt("SELECT result FROM booking_requests WHERE request_id = $1", &[&request_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("result")))
+}
+
+pub async fn record_request(client: &impl GenericClient, request_id: &str, result: &serde_json::Value, created_by: &str) -> Result<(), String> {
+    client
+        .execute(
+            "INSERT INTO booking_requests (request_id, result, created_by, created_at) VALUES ($1, $2, $3, NOW())",
+            &[&request_id, result, &created_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn driver_is_owed_the_fares_without_the_service_fee() {
+        // 3 seats at 4.000 + 0.200, one of them a half-price child seat
+        let sold = SoldSeats { seats: 3, collected: Money::from_tnd(10.6) };
+        assert_eq!(sold.due_to_driver(), Money::from_tnd(10.0));
+        let empty = SoldSeats { seats: 0, collected: Money::ZERO };
+        assert_eq!(empty.due_to_driver(), Money::ZERO);
+    }
+}
@@ -0,0 +1,14 @@
+use super::{settings, GenericClient};
+use crate::business_hours::BusinessHours;
+
+// The station's opening hours (see business_hours.rs)
+
+const HOURS_KEY: &str = "business_hours";
+
+pub async fn hours(client: &impl GenericClient) -> Result<BusinessHours, String> {
+    Ok(BusinessHours::parse(settings::get(client, HOURS_KEY).await?.as_deref()))
+}
+
+pub async fn set_hours(client: &impl GenericClient, hours: &BusinessHours) -> Result<(), String> {
+    settings::set(client, HOURS_KEY, &serde_json::to_string(hours).map_err(|e| e.to_string())?).await
+}
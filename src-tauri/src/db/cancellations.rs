@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::booking::BookingDetails;
+use super::{settings, GenericClient};
+use crate::cancellation::CancellationPolicy;
+use crate::money::Money;
+use crate::time::{self, TunisTime};
+
+const POLICY_KEY: &str = "cancellation_policy";
+
+// A fee kept on a late cancellation (see cancellation.rs)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancellationFeeDto {
+    pub id: String,
+    pub booking_id: String,
+    pub verification_code: String,
+    pub destination_name: String,
+    pub license_plate: String,
+    pub seats: i32,
+    pub refunded: Money,
+    pub fee: Money,
+    pub created_by: Option<String>,
+    pub staff_name: String,
+    pub created_at: String,
+}
+
+fn map_fee_row(r: &Row) -> CancellationFeeDto {
+    CancellationFeeDto {
+        id: r.get("id"),
+        booking_id: r.get("booking_id"),
+        verification_code: r.get("verification_code"),
+        destination_name: r.get("destination_name"),
+        license_plate: r.get("license_plate"),
+        seats: r.get("seats"),
+        refunded: r.get("refunded"),
+        fee: r.get("fee"),
+        created_by: r.get("created_by"),
+        staff_name: r.get("staff_name"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+pub async fn policy(client: &impl GenericClient) -> Result<CancellationPolicy, String> {
+    Ok(CancellationPolicy::parse(settings::get(client, POLICY_KEY).await?.as_deref()))
+}
+
+pub async fn set_policy(client: &impl GenericClient, policy: &CancellationPolicy) -> Result<(), String> {
+    settings::set(client, POLICY_KEY, &serde_json::to_string(policy).map_err(|e| e.to_string())?).await
+}
+
+pub async fn record_fee(client: &impl GenericClient, details: &BookingDetails, seats: i32, refunded: Money, fee: Money, created_by: &Option<String>) -> Result<(), String> {
+    client
+        .execute(
+            r#"INSERT INTO cancellation_fees (id, booking_id, verification_code, destination_name, license_plate, seats, refunded, fee, created_by, terminal_id, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW())"#,
+            &[
+                &uuid::Uuid::new_v4().to_string(), &details.booking.id, &details.verification_code, &details.destination_name, &details.license_plate,
+                &seats, &refunded.to_tnd(), &fee.to_tnd(), created_by, &crate::terminal::id(),
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Fees of one Tunis day, oldest first
+pub async fn for_day(client: &impl GenericClient, day: chrono::NaiveDate) -> Result<Vec<CancellationFeeDto>, String> {
+    let (day_start, day_end) = time::tunis_day_bounds(day);
+    let rows = client
+        .query(
+            r#"SELECT f.id, f.booking_id, f.verification_code, f.destination_name, f.license_plate, f.seats, f.refunded, f.fee,
+                      f.created_by, f.created_at, COALESCE(st.first_name || ' ' || st.last_name, f.created_by, 'Staff') AS staff_name
+               FROM cancellation_fees f
+               LEFT JOIN staff st ON st.id = f.created_by
+               WHERE f.created_at >= $1 AND f.created_at < $2
+               ORDER BY f.created_at"#,
+            &[&day_start, &day_end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_fee_row).collect())
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::{settings, GenericClient};
+use crate::cash_count::{self, DenominationCount};
+use crate::money::Money;
+use crate::time::TunisTime;
+
+const THRESHOLD_KEY: &str = "cash_variance_threshold";
+
+// A drawer count at shift close against the expected cash of the cashier's shift report.
+// variance is counted - expected: negative when the drawer is short.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CashCountDto {
+    pub id: String,
+    pub staff_id: String,
+    pub staff_name: String,
+    pub date: String,
+    pub denominations: Vec<DenominationCount>,
+    pub counted: Money,
+    pub expected: Money,
+    pub variance: Money,
+    pub threshold: Money,
+    /// Beyond the threshold: a variance slip was printed
+    pub flagged: bool,
+    pub created_at: String,
+}
+
+const CASH_COUNT_SELECT: &str = r#"
+    SELECT c.id, c.staff_id, c.tunis_date, c.denominations, c.counted, c.expected, c.variance, c.threshold, c.created_at,
+           COALESCE(st.first_name || ' ' || st.last_name, c.staff_id) AS staff_name
+    FROM cash_counts c
+    LEFT JOIN staff st ON st.id = c.staff_id"#;
+
+fn map_cash_count_row(r: &Row) -> CashCountDto {
+    let variance: Money = r.get("variance");
+    let threshold: Money = r.get("threshold");
+    CashCountDto {
+        id: r.get("id"),
+        staff_id: r.get("staff_id"),
+        staff_name: r.get("staff_name"),
+        date: r.get::<_, chrono::NaiveDate>("tunis_date").format("%Y-%m-%d").to_string(),
+        denominations: serde_json::from_value(r.get("denominations")).unwrap_or_default(),
+        counted: r.get("counted"),
+        expected: r.get("expected"),
+        variance,
+        threshold,
+        flagged: cash_count::exceeds_threshold(variance, threshold),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+pub async fn variance_threshold(client: &impl GenericClient) -> Result<Money, String> {
+    Ok(cash_count::parse_threshold(settings::get(client, THRESHOLD_KEY).await?.as_deref()))
+}
+
+pub async fn set_variance_threshold(client: &impl GenericClient, threshold: Money) -> Result<(), String> {
+    settings::set(client, THRESHOLD_KEY, &threshold.to_string()).await
+}
+
+/// Records a count of `denominations` (already normalized) against `expected`
+pub async fn insert(
+    client: &impl GenericClient,
+    staff_id: &str,
+    day: chrono::NaiveDate,
+    denominations: &[DenominationCount],
+    expected: Money,
+    threshold: Money,
+) -> Result<CashCountDto, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let counted = cash_count::counted_total(denominations);
+    let lines = serde_json::to_value(denominations).map_err(|e| e.to_string())?;
+    client
+        .execute(
+            "INSERT INTO cash_counts (id, staff_id, tunis_date, denominations, counted, expected, variance, threshold, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())",
+            &[&id, &staff_id, &day, &lines, &counted.to_tnd(), &expected.to_tnd(), &(counted - expected).to_tnd(), &threshold.to_tnd()],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let sql = format!("{} WHERE c.id = $1", CASH_COUNT_SELECT);
+    let row = client.query_one(sql.as_str(), &[&id]).await.map_err(|e| e.to_string())?;
+    Ok(map_cash_count_row(&row))
+}
+
+// Counts of one Tunis day, oldest first
+pub async fn for_day(client: &impl GenericClient, day: chrono::NaiveDate) -> Result<Vec<CashCountDto>, String> {
+    let sql = format!("{} WHERE c.tunis_date = $1 ORDER BY c.created_at", CASH_COUNT_SELECT);
+    let rows = client.query(sql.as_str(), &[&day]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_cash_count_row).collect())
+}
@@ -0,0 +1,324 @@
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::annotations::CalendarAnnotationDto;
+use super::GenericClient;
+use crate::money::Money;
+use crate::time::{self, TunisTime};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerDto {
+    pub id: String,
+    pub name: String,
+    pub phone_number: Option<String>,
+    pub tax_id: Option<String>,
+    pub credit_limit: Money,
+    pub balance: Money, // owed: on-account bookings minus payments received
+    pub is_active: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementLineDto {
+    pub date: String,
+    pub kind: String, // BOOKING or PAYMENT
+    pub reference: String,
+    pub destination_name: Option<String>,
+    pub license_plate: Option<String>,
+    pub seats: Option<i32>,
+    pub charge: Money,
+    pub payment: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerStatementDto {
+    pub customer: CustomerDto,
+    pub month: String, // YYYY-MM
+    pub opening_balance: Money,
+    pub total_charges: Money,
+    pub total_payments: Money,
+    pub closing_balance: Money,
+    pub lines: Vec<StatementLineDto>,
+    pub annotations: Vec<CalendarAnnotationDto>, // notes on the days of the month
+}
+
+const CUSTOMER_COLUMNS: &str = r#"
+    c.id, c.name, c.phone_number, c.tax_id, c.credit_limit, c.is_active,
+    c.created_at,
+    (COALESCE((SELECT SUM(bp.amount) FROM booking_payments bp JOIN bookings b ON b.id = bp.booking_id
+               WHERE b.customer_id = c.id AND bp.method = 'ON_ACCOUNT'), 0)
+     - COALESCE((SELECT SUM(cp.amount) FROM customer_payments cp WHERE cp.customer_id = c.id), 0))::float8 AS balance
+"#;
+
+fn map_customer_row(r: &Row) -> CustomerDto {
+    CustomerDto {
+        id: r.get("id"),
+        name: r.get("name"),
+        phone_number: r.get("phone_number"),
+        tax_id: r.get("tax_id"),
+        credit_limit: r.get("credit_limit"),
+        balance: r.get("balance"),
+        is_active: r.get("is_active"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_iso(),
+    }
+}
+
+pub async fn insert(client: &impl GenericClient, name: &str, phone_number: &Option<String>, tax_id: &Option<String>, credit_limit: Money) -> Result<String, String> {
+    let customer_id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO customers (id, name, phone_number, tax_id, credit_limit, is_active, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, true, NOW(), NOW())",
+            &[&customer_id, &name, phone_number, tax_id, &credit_limit.to_tnd()],
+        )
+        .await
+        .map_err(|e| format!("Erreur lors de la création du client: {}", e))?;
+    Ok(customer_id)
+}
+
+pub async fn find(client: &impl GenericClient, customer_id: &str) -> Result<Option<CustomerDto>, String> {
+    let sql = format!("SELECT {} FROM customers c WHERE c.id = $1", CUSTOMER_COLUMNS);
+    let row = client.query_opt(sql.as_str(), &[&customer_id]).await.map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_customer_row))
+}
+
+pub async fn list(client: &impl GenericClient) -> Result<Vec<CustomerDto>, String> {
+    let sql = format!("SELECT {} FROM customers c ORDER BY c.is_active DESC, lower(c.name)", CUSTOMER_COLUMNS);
+    let rows = client.query(sql.as_str(), &[]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_customer_row).collect())
+}
+
+pub async fn update_credit_limit(client: &impl GenericClient, customer_id: &str, credit_limit: Money) -> Result<u64, String> {
+    client
+        .execute(
+            "UPDATE customers SET credit_limit = $1, updated_at = NOW() WHERE id = $2",
+            &[&credit_limit.to_tnd(), &customer_id],
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Check `amount` more on account fits under the customer's credit limit. Locks the customer
+/// row so two terminals cannot both spend the last of the limit.
+pub async fn reserve_credit(client: &impl GenericClient, customer_id: &str, amount: Money) -> Result<(), String> {
+    client
+        .execute("SELECT id FROM customers WHERE id = $1 FOR UPDATE", &[&customer_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    let customer = find(client, customer_id).await?.ok_or_else(|| "Client introuvable".to_string())?;
+    if !customer.is_active {
+        return Err(format!("Le compte client {} est désactivé", customer.name));
+    }
+    let available = customer.credit_limit - customer.balance;
+    if amount > available {
+        return Err(format!(
+            "Plafond de crédit dépassé pour {}: disponible {} TND, demandé {} TND",
+            customer.name, available.max(Money::ZERO), amount
+        ));
+    }
+    Ok(())
+}
+
+pub async fn link_booking(client: &impl GenericClient, booking_id: &str, customer_id: &str) -> Result<(), String> {
+    client
+        .execute("UPDATE bookings SET customer_id = $1 WHERE id = $2", &[&customer_id, &booking_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn record_payment(client: &impl GenericClient, customer_id: &str, amount: Money, reference: &Option<String>, created_by: &Option<String>) -> Result<String, String> {
+    let payment_id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO customer_payments (id, customer_id, amount, reference, created_by, created_at) VALUES ($1, $2, $3, $4, $5, NOW())",
+            &[&payment_id, &customer_id, &amount.to_tnd(), reference, created_by],
+        )
+        .await
+        .map_err(|e| format!("Erreur lors de l'enregistrement du règlement: {}", e))?;
+    Ok(payment_id)
+}
+
+/// Everything charged to and paid by a customer over one Tunis calendar month (`YYYY-MM`)
+pub async fn monthly_statement(client: &impl GenericClient, customer_id: &str, month: &str) -> Result<CustomerStatementDto, String> {
+    let start = chrono::NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d").map_err(|_| format!("Mois invalide: {}", month))?;
+    let end = if start.month0() == 11 {
+        chrono::NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+    } else {
+        chrono::NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+    }
+    .ok_or_else(|| format!("Mois invalide: {}", month))?;
+    let customer = find(client, customer_id).await?.ok_or_else(|| "Client introuvable".to_string())?;
+    let (from, to) = (time::tunis_day_bounds(start).0, time::tunis_day_bounds(end).0);
+
+    let opening = client
+        .query_one(
+            r#"
+            SELECT (COALESCE((SELECT SUM(bp.amount) FROM booking_payments bp JOIN bookings b ON b.id = bp.booking_id
+                              WHERE b.customer_id = $1 AND bp.method = 'ON_ACCOUNT'
+                                AND b.created_at < $2), 0)
+                    - COALESCE((SELECT SUM(amount) FROM customer_payments
+                                WHERE customer_id = $1 AND created_at < $2), 0))::float8 AS balance
+            "#,
+            &[&customer_id, &from],
+        )
+        .await
+        .map_err(|e| e.to_string())?
+        .get::<_, Money>("balance");
+
+    let rows = client
+        .query(
+            r#"
+            SELECT * FROM (
+                SELECT b.created_at AS happened_at, 'BOOKING' AS kind, b.verification_code AS reference,
+                       q.destination_name, v.license_plate, b.seats_booked AS seats, SUM(bp.amount)::float8 AS charge, 0::float8 AS payment
+                FROM bookings b
+                JOIN booking_payments bp ON bp.booking_id = b.id AND bp.method = 'ON_ACCOUNT'
+                LEFT JOIN vehicle_queue q ON q.id = b.queue_id
+                LEFT JOIN vehicles v ON v.id = q.vehicle_id
+                WHERE b.customer_id = $1
+                  AND b.created_at >= $2 AND b.created_at < $3
+                GROUP BY b.id, b.created_at, b.verification_code, q.destination_name, v.license_plate, b.seats_booked
+                UNION ALL
+                SELECT cp.created_at AS happened_at, 'PAYMENT' AS kind, COALESCE(cp.reference, '') AS reference,
+                       NULL, NULL, NULL, 0::float8, cp.amount
+                FROM customer_payments cp
+                WHERE cp.customer_id = $1
+                  AND cp.created_at >= $2 AND cp.created_at < $3
+            ) lines
+            ORDER BY happened_at
+            "#,
+            &[&customer_id, &from, &to],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let lines: Vec<StatementLineDto> = rows
+        .iter()
+        .map(|r| StatementLineDto {
+            date: r.get::<_, TunisTime>("happened_at").local().format("%Y-%m-%d %H:%M").to_string(),
+            kind: r.get("kind"),
+            reference: r.get("reference"),
+            destination_name: r.get("destination_name"),
+            license_plate: r.get("license_plate"),
+            seats: r.get("seats"),
+            charge: r.get("charge"),
+            payment: r.get("payment"),
+        })
+        .collect();
+    let total_charges: Money = lines.iter().map(|l| l.charge).sum();
+    let total_payments: Money = lines.iter().map(|l| l.payment).sum();
+    let annotations = super::annotations::between(client, start, end.pred_opt().unwrap_or(end)).await?;
+
+    Ok(CustomerStatementDto {
+        customer,
+        month: start.format("%Y-%m").to_string(),
+        opening_balance: opening,
+        total_charges,
+        total_payments,
+        closing_balance: opening + total_charges - total_payments,
+        lines,
+        annotations,
+    })
+}
+
+/// A CSV field, quoted when it holds a separator, a quote or a line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', ';']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// The statement as CSV for the customer's accountant
+pub fn statement_csv(statement: &CustomerStatementDto) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("Client,{}\n", csv_field(&statement.customer.name)));
+    out.push_str(&format!("Matricule fiscal,{}\n", csv_field(statement.customer.tax_id.as_deref().unwrap_or(""))));
+    out.push_str(&format!("Mois,{}\n", statement.month));
+    out.push_str(&format!("Solde initial,{}\n", statement.opening_balance));
+    out.push_str("Date,Type,Référence,Destination,Véhicule,Places,Débit,Crédit\n");
+    for line in &statement.lines {
+        let kind = if line.kind == "PAYMENT" { "Règlement" } else { "Réservation" };
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            line.date,
+            kind,
+            csv_field(&line.reference),
+            csv_field(line.destination_name.as_deref().unwrap_or("")),
+            csv_field(line.license_plate.as_deref().unwrap_or("")),
+            line.seats.map(|s| s.to_string()).unwrap_or_default(),
+            line.charge,
+            line.payment,
+        ));
+    }
+    out.push_str(&format!("Total,,,,,,{},{}\n", statement.total_charges, statement.total_payments));
+    out.push_str(&format!("Solde final,{}\n", statement.closing_balance));
+    out.push_str(&super::annotations::csv_lines(&statement.annotations));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statement_csv_lists_charges_and_payments_with_balances() {
+        let statement = CustomerStatementDto {
+            customer: CustomerDto {
+                id: "c1".into(),
+                name: "Sotupa, SARL".into(),
+                phone_number: None,
+                tax_id: Some("1234567A".into()),
+                credit_limit: Money::from_tnd(500.0),
+                balance: Money::from_tnd(12.6),
+                is_active: true,
+                created_at: "2026-01-05T09:00:00".into(),
+            },
+            month: "2026-09".into(),
+            opening_balance: Money::from_tnd(20.0),
+            total_charges: Money::from_tnd(12.6),
+            total_payments: Money::from_tnd(20.0),
+            closing_balance: Money::from_tnd(12.6),
+            lines: vec![
+                StatementLineDto {
+                    date: "2026-09-02 07:15".into(),
+                    kind: "BOOKING".into(),
+                    reference: "A1B2C3".into(),
+                    destination_name: Some("Jemmal".into()),
+                    license_plate: Some("123 TU 4567".into()),
+                    seats: Some(3),
+                    charge: Money::from_tnd(12.6),
+                    payment: Money::from_tnd(0.0),
+                },
+                StatementLineDto {
+                    date: "2026-09-30 17:00".into(),
+                    kind: "PAYMENT".into(),
+                    reference: "CHQ 0042".into(),
+                    destination_name: None,
+                    license_plate: None,
+                    seats: None,
+                    charge: Money::from_tnd(0.0),
+                    payment: Money::from_tnd(20.0),
+                },
+            ],
+            annotations: vec![],
+        };
+        let expected = concat!(
+            "Client,\"Sotupa, SARL\"\n",
+            "Matricule fiscal,1234567A\n",
+            "Mois,2026-09\n",
+            "Solde initial,20.000\n",
+            "Date,Type,Référence,Destination,Véhicule,Places,Débit,Crédit\n",
+            "2026-09-02 07:15,Réservation,A1B2C3,Jemmal,123 TU 4567,3,12.600,0.000\n",
+            "2026-09-30 17:00,Règlement,CHQ 0042,,,,0.000,20.000\n",
+            "Total,,,,,,12.600,20.000\n",
+            "Solde final,12.600\n",
+        );
+        assert_eq!(statement_csv(&statement), expected);
+    }
+}
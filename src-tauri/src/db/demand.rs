@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+
+use super::GenericClient;
+
+// Paid bookings of a destination for the demand profile (see demand.rs). A departed vehicle's
+// queue entry is gone, so the destination is read from its exit pass instead.
+
+/// Paid bookings for the destination made in [from, to), as (booked at, seats), oldest first
+pub async fn paid_bookings(client: &impl GenericClient, destination_id: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<(DateTime<Utc>, i32)>, String> {
+    let rows = client
+        .query(
+            r#"SELECT b.created_at, b.seats_booked
+               FROM bookings b
+               LEFT JOIN vehicle_queue q ON q.id = b.queue_id
+               LEFT JOIN exit_passes e ON e.queue_id = b.queue_id
+               WHERE b.payment_status = 'PAID' AND COALESCE(q.destination_id, e.destination_id) = $1
+                 AND b.created_at >= $2 AND b.created_at < $3
+               ORDER BY b.created_at"#,
+            &[&destination_id, &from, &to],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| (r.get("created_at"), r.get("seats_booked"))).collect())
+}
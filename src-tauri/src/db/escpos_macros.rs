@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::escpos;
+use crate::time::TunisTime;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EscposMacroDto {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub base64_bytes: String,
+    pub size: usize,
+    pub updated_by_name: Option<String>,
+    pub updated_at: String,
+}
+
+const SELECT_SQL: &str = r#"
+    SELECT m.id, m.name, m.description, m.bytes, s.first_name || ' ' || s.last_name AS updated_by_name, m.updated_at
+    FROM escpos_macros m
+    LEFT JOIN staff s ON s.id = m.updated_by"#;
+
+fn map_macro_row(r: &Row) -> EscposMacroDto {
+    let bytes: Vec<u8> = r.get("bytes");
+    EscposMacroDto {
+        id: r.get("id"),
+        name: r.get("name"),
+        description: r.get("description"),
+        base64_bytes: escpos::encode(&bytes),
+        size: bytes.len(),
+        updated_by_name: r.get("updated_by_name"),
+        updated_at: r.get::<_, TunisTime>("updated_at").fmt_dto(),
+    }
+}
+
+pub async fn list(client: &impl GenericClient) -> Result<Vec<EscposMacroDto>, String> {
+    let sql = format!("{} ORDER BY m.name", SELECT_SQL);
+    let rows = client.query(sql.as_str(), &[]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_macro_row).collect())
+}
+
+/// The macro's bytes, by name
+pub async fn bytes(client: &impl GenericClient, name: &str) -> Result<Option<Vec<u8>>, String> {
+    let row = client
+        .query_opt("SELECT bytes FROM escpos_macros WHERE name = $1", &[&name])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("bytes")))
+}
+
+/// Save under `name`, replacing a macro of the same name
+pub async fn save(client: &impl GenericClient, name: &str, description: &Option<String>, bytes: &[u8], staff_id: &Option<String>) -> Result<EscposMacroDto, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO escpos_macros (id, name, description, bytes, updated_by) VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (name) DO UPDATE SET description = EXCLUDED.description, bytes = EXCLUDED.bytes,
+                 updated_by = EXCLUDED.updated_by, updated_at = NOW()",
+            &[&id, &name, description, &bytes, staff_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let sql = format!("{} WHERE m.name = $1", SELECT_SQL);
+    let row = client.query_one(sql.as_str(), &[&name]).await.map_err(|e| e.to_string())?;
+    Ok(map_macro_row(&row))
+}
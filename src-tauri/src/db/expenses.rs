@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::money::Money;
+use crate::time::{self, TunisTime};
+
+// Petty cash paid out of the till, recorded by the staff member whose drawer it came from
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpenseDto {
+    pub id: String,
+    pub amount: Money,
+    pub category: String,
+    pub note: Option<String>,
+    pub created_by: Option<String>,
+    pub staff_name: String,
+    pub created_at: String,
+}
+
+const EXPENSE_SELECT: &str = r#"
+    SELECT e.id, e.amount, e.category, e.note, e.created_by, e.created_at,
+           COALESCE(st.first_name || ' ' || st.last_name, e.created_by, 'Staff') AS staff_name
+    FROM expenses e
+    LEFT JOIN staff st ON st.id = e.created_by"#;
+
+fn map_expense_row(r: &Row) -> ExpenseDto {
+    ExpenseDto {
+        id: r.get("id"),
+        amount: r.get("amount"),
+        category: r.get("category"),
+        note: r.get("note"),
+        created_by: r.get("created_by"),
+        staff_name: r.get("staff_name"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+pub async fn insert(client: &impl GenericClient, amount: Money, category: &str, note: &Option<String>, created_by: &Option<String>) -> Result<ExpenseDto, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO expenses (id, amount, category, note, created_by, created_at) VALUES ($1, $2, $3, $4, $5, NOW())",
+            &[&id, &amount.to_tnd(), &category, note, created_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let sql = format!("{} WHERE e.id = $1", EXPENSE_SELECT);
+    let row = client.query_one(sql.as_str(), &[&id]).await.map_err(|e| e.to_string())?;
+    Ok(map_expense_row(&row))
+}
+
+// Expenses of one Tunis day, optionally from one staff member's till, oldest first
+pub async fn for_day(client: &impl GenericClient, day: chrono::NaiveDate, created_by: Option<&str>) -> Result<Vec<ExpenseDto>, String> {
+    let (day_start, day_end) = time::tunis_day_bounds(day);
+    let sql = format!(
+        "{} WHERE e.created_at >= $1 AND e.created_at < $2 AND ($3::text IS NULL OR e.created_by = $3) ORDER BY e.created_at",
+        EXPENSE_SELECT
+    );
+    let rows = client
+        .query(sql.as_str(), &[&day_start, &day_end, &created_by])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_expense_row).collect())
+}
+
+pub fn total(expenses: &[ExpenseDto]) -> Money {
+    expenses.iter().map(|e| e.amount).sum()
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use tokio_postgres::Row;
+
+use super::{settings, GenericClient};
+use crate::loading_timeout::LoadingTimeoutPolicy;
+
+const POLICY_KEY: &str = "loading_timeout";
+
+// Entries LOADING with the time they started (loading_since, migration 027)
+const LOADING_SQL: &str = r#"
+    SELECT q.id, v.license_plate, q.destination_id, q.destination_name, q.sub_route_name, q.queue_position,
+           q.total_seats - q.available_seats AS booked_seats, q.total_seats, q.loading_since
+    FROM vehicle_queue q
+    JOIN vehicles v ON v.id = q.vehicle_id
+    WHERE q.status = 'LOADING' AND q.loading_since IS NOT NULL
+    ORDER BY q.loading_since"#;
+
+/// A queue entry LOADING, with the time it started
+pub struct LoadingEntry {
+    pub queue_id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub sub_route_name: Option<String>,
+    pub queue_position: i32,
+    pub booked_seats: i32,
+    pub total_seats: i32,
+    pub loading_since: DateTime<Utc>,
+}
+
+fn map_entry_row(r: &Row) -> LoadingEntry {
+    LoadingEntry {
+        queue_id: r.get("id"),
+        license_plate: r.get("license_plate"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        sub_route_name: r.get("sub_route_name"),
+        queue_position: r.get("queue_position"),
+        booked_seats: r.get("booked_seats"),
+        total_seats: r.get("total_seats"),
+        loading_since: r.get("loading_since"),
+    }
+}
+
+pub async fn policy(client: &impl GenericClient) -> Result<LoadingTimeoutPolicy, String> {
+    Ok(LoadingTimeoutPolicy::parse(settings::get(client, POLICY_KEY).await?.as_deref()))
+}
+
+pub async fn set_policy(client: &impl GenericClient, policy: &LoadingTimeoutPolicy) -> Result<(), String> {
+    settings::set(client, POLICY_KEY, &serde_json::to_string(policy).map_err(|e| e.to_string())?).await
+}
+
+/// Every entry loading, the longest first
+pub async fn loading(client: &impl GenericClient) -> Result<Vec<LoadingEntry>, String> {
+    let rows = client.query(LOADING_SQL, &[]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_entry_row).collect())
+}
+
+/// Plate and destination of a LOADING entry, locked until the transaction ends
+pub async fn lock_loading(client: &impl GenericClient, queue_id: &str) -> Result<Option<(String, String)>, String> {
+    let row = client
+        .query_opt(
+            "SELECT v.license_plate, q.destination_id FROM vehicle_queue q JOIN vehicles v ON v.id = q.vehicle_id
+             WHERE q.id = $1 AND q.status = 'LOADING' FOR UPDATE OF q",
+            &[&queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| (r.get("license_plate"), r.get("destination_id"))))
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use super::GenericClient;
+use crate::money::Money;
+
+// Governorate → delegation → station, as the booking screen filters them. Routes without a
+// governorate or a delegation cannot be reached by those filters and are left out.
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StationNode {
+    pub station_id: String,
+    pub station_name: String,
+    pub base_price: Money,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationNode {
+    pub name: String,
+    pub name_ar: Option<String>,
+    pub stations: Vec<StationNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GovernorateNode {
+    pub name: String,
+    pub name_ar: Option<String>,
+    pub delegations: Vec<DelegationNode>,
+}
+
+pub struct LocationRow {
+    pub governorate: String,
+    pub governorate_ar: Option<String>,
+    pub delegation: String,
+    pub delegation_ar: Option<String>,
+    pub station: StationNode,
+}
+
+/// Active routes with a governorate and a delegation, sorted by governorate, delegation, station
+pub async fn active_routes(client: &impl GenericClient) -> Result<Vec<LocationRow>, String> {
+    let rows = client
+        .query(
+            r#"SELECT station_id, station_name, base_price, governorate, governorate_ar, delegation, delegation_ar
+               FROM routes
+               WHERE is_active = true AND governorate IS NOT NULL AND delegation IS NOT NULL
+               ORDER BY governorate, delegation, station_name"#,
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| LocationRow {
+            governorate: r.get("governorate"),
+            governorate_ar: r.get("governorate_ar"),
+            delegation: r.get("delegation"),
+            delegation_ar: r.get("delegation_ar"),
+            station: StationNode { station_id: r.get("station_id"), station_name: r.get("station_name"), base_price: r.get("base_price") },
+        })
+        .collect())
+}
+
+/// Nest sorted rows; the first Arabic name found for a governorate or delegation is kept
+pub fn build_tree(rows: Vec<LocationRow>) -> Vec<GovernorateNode> {
+    let mut tree: Vec<GovernorateNode> = Vec::new();
+    for row in rows {
+        if tree.last().map(|g| g.name != row.governorate).unwrap_or(true) {
+            tree.push(GovernorateNode { name: row.governorate.clone(), name_ar: None, delegations: Vec::new() });
+        }
+        let governorate = tree.last_mut().unwrap();
+        governorate.name_ar = governorate.name_ar.take().or(row.governorate_ar);
+        if governorate.delegations.last().map(|d| d.name != row.delegation).unwrap_or(true) {
+            governorate.delegations.push(DelegationNode { name: row.delegation.clone(), name_ar: None, stations: Vec::new() });
+        }
+        let delegation = governorate.delegations.last_mut().unwrap();
+        delegation.name_ar = delegation.name_ar.take().or(row.delegation_ar);
+        delegation.stations.push(row.station);
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(governorate: &str, delegation: &str, delegation_ar: Option<&str>, station: &str) -> LocationRow {
+        LocationRow {
+            governorate: governorate.to_string(),
+            governorate_ar: (governorate == "Monastir").then(|| "المنستير".to_string()),
+            delegation: delegation.to_string(),
+            delegation_ar: delegation_ar.map(str::to_string),
+            station: StationNode { station_id: station.to_lowercase(), station_name: station.to_string(), base_price: Money::from_millimes(2_500) },
+        }
+    }
+
+    #[test]
+    fn rows_nest_under_their_governorate_and_delegation() {
+        let tree = build_tree(vec![
+            row("Monastir", "Jemmal", None, "JEMMAL"),
+            row("Monastir", "Jemmal", Some("جمال"), "ZERAMDINE"),
+            row("Monastir", "Ksar Hellal", Some("قصر هلال"), "KSAR HELLAL"),
+            row("Sousse", "Msaken", None, "MSAKEN"),
+        ]);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].name_ar.as_deref(), Some("المنستير"));
+        assert_eq!(tree[0].delegations.len(), 2);
+        assert_eq!(tree[0].delegations[0].name_ar.as_deref(), Some("جمال"));
+        assert_eq!(tree[0].delegations[0].stations.iter().map(|s| s.station_name.as_str()).collect::<Vec<_>>(), ["JEMMAL", "ZERAMDINE"]);
+        assert_eq!(tree[1].name_ar, None);
+        assert_eq!(tree[1].delegations[0].stations[0].station_id, "msaken");
+    }
+}
@@ -0,0 +1,55 @@
+// Repository layer over the station database. Each function takes any GenericClient,
+// a pooled connection or an open transaction, so a command can compose several calls in
+// one transaction and tests can run them inside a transaction that is rolled back.
+// Tauri commands in main.rs stay thin wrappers: get a client, call in, shape the reply.
+// Commands that only read take their client from pool::read(), which may be a replica.
+
+pub mod annotations;
+pub mod anomalies;
+pub mod anpr;
+pub mod approvals;
+pub mod booking;
+pub mod business_hours;
+pub mod cancellations;
+pub mod cash_counts;
+pub mod customers;
+pub mod demand;
+pub mod escpos_macros;
+pub mod expenses;
+pub mod loading_timeouts;
+pub mod locations;
+pub mod no_shows;
+pub mod overrides;
+pub mod passes;
+pub mod payments;
+pub mod pool;
+pub mod positions;
+pub mod printed_tickets;
+pub mod pricing;
+pub mod queue;
+pub mod queue_closings;
+pub mod queue_events;
+pub mod quick_entry;
+pub mod refunds;
+pub mod reprints;
+pub mod reservations;
+pub mod retention;
+pub mod route_aliases;
+pub mod search;
+pub mod self_check_in;
+pub mod settings;
+pub mod settlements;
+pub mod shifts;
+pub mod staff;
+pub mod suspensions;
+pub mod terminal_messages;
+pub mod terminals;
+pub mod ticket_escrow;
+pub mod training;
+pub mod trips;
+pub mod vehicle_profile;
+pub mod vehicles;
+pub mod voided_tickets;
+pub mod waitlist;
+
+pub use deadpool_postgres::GenericClient;
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use super::booking::BookingDetails;
+use super::GenericClient;
+use crate::money::Money;
+use crate::time;
+
+// Paid bookings whose passengers never boarded (migration 032, see no_show.rs)
+
+/// Where a booking stands before it is marked
+pub struct BookingState {
+    pub payment_status: String,
+    /// From the queue entry, or from the exit pass once the vehicle has left
+    pub destination_id: Option<String>,
+    pub no_show: bool,
+}
+
+// No-shows of one destination over a day, for the daily report
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NoShowTotalDto {
+    pub destination_id: Option<String>,
+    pub destination_name: String,
+    pub bookings: i64,
+    pub seats: i64,
+    pub amount: Money,
+    pub refunded: Money,
+}
+
+pub async fn state(client: &impl GenericClient, booking_id: &str) -> Result<Option<BookingState>, String> {
+    let row = client
+        .query_opt(
+            r#"SELECT b.payment_status::text AS payment_status, COALESCE(vq.destination_id, ep.destination_id) AS destination_id,
+                      EXISTS(SELECT 1 FROM booking_no_shows ns WHERE ns.booking_id = b.id) AS no_show
+               FROM bookings b
+               LEFT JOIN vehicle_queue vq ON vq.id = b.queue_id
+               LEFT JOIN LATERAL (SELECT destination_id FROM exit_passes WHERE queue_id = b.queue_id LIMIT 1) ep ON true
+               WHERE b.id = $1"#,
+            &[&booking_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| BookingState { payment_status: r.get("payment_status"), destination_id: r.get("destination_id"), no_show: r.get("no_show") }))
+}
+
+pub async fn record(
+    client: &impl GenericClient,
+    details: &BookingDetails,
+    destination_id: &Option<String>,
+    refunded: Money,
+    seat_freed: bool,
+    created_by: &Option<String>,
+) -> Result<(), String> {
+    let booking = &details.booking;
+    client
+        .execute(
+            r#"INSERT INTO booking_no_shows (booking_id, verification_code, destination_id, destination_name, license_plate, seats, amount, refunded, seat_freed, created_by, terminal_id, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())"#,
+            &[
+                &booking.id, &details.verification_code, destination_id, &details.destination_name, &details.license_plate,
+                &booking.seats_booked, &booking.total_amount.to_tnd(), &refunded.to_tnd(), &seat_freed, created_by, &crate::terminal::id(),
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// No-shows marked on one Tunis day, by destination
+pub async fn totals_for_day(client: &impl GenericClient, day: chrono::NaiveDate) -> Result<Vec<NoShowTotalDto>, String> {
+    let (day_start, day_end) = time::tunis_day_bounds(day);
+    let rows = client
+        .query(
+            r#"SELECT destination_id, destination_name, COUNT(*) AS bookings, SUM(seats)::bigint AS seats,
+                      SUM(amount) AS amount, SUM(refunded) AS refunded
+               FROM booking_no_shows
+               WHERE created_at >= $1 AND created_at < $2
+               GROUP BY destination_id, destination_name
+               ORDER BY destination_name"#,
+            &[&day_start, &day_end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| NoShowTotalDto {
+            destination_id: r.get("destination_id"),
+            destination_name: r.get("destination_name"),
+            bookings: r.get("bookings"),
+            seats: r.get("seats"),
+            amount: r.get("amount"),
+            refunded: r.get("refunded"),
+        })
+        .collect())
+}
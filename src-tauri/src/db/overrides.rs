@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::overrides::{AuthorizedOverride, OverrideAction};
+use crate::time::TunisTime;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideDto {
+    pub id: String,
+    pub action: String,
+    pub reason_code: String,
+    pub note: String,
+    pub supervisor_id: String,
+    pub supervisor_name: String,
+    pub target: String,
+    pub details: serde_json::Value,
+    pub created_at: String,
+}
+
+fn map_override_row(r: &Row) -> OverrideDto {
+    OverrideDto {
+        id: r.get("id"),
+        action: r.get("action"),
+        reason_code: r.get("reason_code"),
+        note: r.get("note"),
+        supervisor_id: r.get("supervisor_id"),
+        supervisor_name: r.get("supervisor_name"),
+        target: r.get("target"),
+        details: r.get("details"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+/// Keeps an authorized override with what it was applied to; call it in the transaction of
+/// the action so one is never recorded without the other
+pub async fn record_override(
+    client: &impl GenericClient,
+    action: OverrideAction,
+    authorized: &AuthorizedOverride,
+    target: &str,
+    details: serde_json::Value,
+) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO supervisor_overrides (id, action, reason_code, note, supervisor_id, target, details, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())",
+            &[&id, &action.as_str(), &authorized.reason.as_str(), &authorized.note, &authorized.supervisor.id, &target, &details],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    println!(
+        "🛡️ [OVERRIDE] {} on {} by {} {} ({}: {})",
+        action.as_str(),
+        target,
+        authorized.supervisor.firstName,
+        authorized.supervisor.lastName,
+        authorized.reason.as_str(),
+        authorized.note
+    );
+    Ok(id)
+}
+
+pub async fn between(client: &impl GenericClient, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<OverrideDto>, String> {
+    let rows = client
+        .query(
+            r#"SELECT o.id, o.action, o.reason_code, o.note, o.supervisor_id, o.target, o.details, o.created_at,
+                      COALESCE(st.first_name || ' ' || st.last_name, o.supervisor_id) AS supervisor_name
+               FROM supervisor_overrides o
+               LEFT JOIN staff st ON st.id = o.supervisor_id
+               WHERE o.created_at >= $1 AND o.created_at < $2
+               ORDER BY o.created_at"#,
+            &[&from, &to],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_override_row).collect())
+}
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::money::Money;
+use crate::settlement::Settlement;
+use crate::tickets::PreviousVehicle;
+use crate::time::{self, TunisTime};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayPassDto {
+    pub id: String,
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub pass_type: String,
+    pub price: Money,
+    pub purchase_date: String,
+    pub valid_from: String,
+    pub valid_until: String,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitPassDto {
+    pub id: String,
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub current_exit_time: String,
+    pub created_at: String,
+}
+
+// An exit pass as recorded when a vehicle leaves, with how that queue entry settles with the driver
+pub struct NewExitPass<'a> {
+    pub queue_id: &'a str,
+    pub vehicle_id: &'a str,
+    pub license_plate: &'a str,
+    pub destination_id: &'a str,
+    pub destination_name: &'a str,
+    pub settlement: &'a Settlement,
+    pub created_by: &'a str,
+}
+
+// Any active pass (day, week or month) overlapping today in Africa/Tunis, with $2 and $3
+// bound to time::today_bounds()
+const COVERS_TODAY: &str = "valid_from < $3 AND valid_until >= $2";
+
+fn map_day_pass_row(r: &Row) -> DayPassDto {
+    DayPassDto {
+        id: r.get("id"),
+        vehicle_id: r.get("vehicle_id"),
+        license_plate: r.get("license_plate"),
+        pass_type: r.get("pass_type"),
+        price: r.get::<_, Money>("price"),
+        purchase_date: r.get::<_, TunisTime>("purchase_date").fmt_dto(),
+        valid_from: r.get::<_, TunisTime>("valid_from").fmt_dto(),
+        valid_until: r.get::<_, TunisTime>("valid_until").fmt_dto(),
+        is_active: r.get("is_active"),
+    }
+}
+
+fn map_exit_pass_row(r: &Row) -> ExitPassDto {
+    ExitPassDto {
+        id: r.get("id"),
+        vehicle_id: r.get("vehicle_id"),
+        license_plate: r.get("license_plate"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        current_exit_time: r.get::<_, TunisTime>("current_exit_time").fmt_dto(),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+pub async fn has_pass_today(client: &impl GenericClient, license_plate: &str) -> Result<bool, String> {
+    let sql = format!(
+        "SELECT id FROM day_passes WHERE license_plate = $1 AND is_active = true AND {}",
+        COVERS_TODAY
+    );
+    let (day_start, day_end) = time::today_bounds();
+    let row = client
+        .query_opt(sql.as_str(), &[&license_plate, &day_start, &day_end])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.is_some())
+}
+
+pub async fn has_pass_today_batch(client: &impl GenericClient, license_plates: &[String]) -> Result<HashMap<String, bool>, String> {
+    let mut map: HashMap<String, bool> = license_plates.iter().map(|lp| (lp.clone(), false)).collect();
+    if license_plates.is_empty() {
+        return Ok(map);
+    }
+    // Query all plates at once using ANY($1)
+    let sql = format!(
+        "SELECT license_plate FROM day_passes WHERE is_active = true AND {} AND license_plate = ANY($1)",
+        COVERS_TODAY
+    );
+    let (day_start, day_end) = time::today_bounds();
+    let rows = client
+        .query(sql.as_str(), &[&license_plates, &day_start, &day_end])
+        .await
+        .map_err(|e| e.to_string())?;
+    for r in rows {
+        map.insert(r.get("license_plate"), true);
+    }
+    Ok(map)
+}
+
+pub async fn today_day_passes(client: &impl GenericClient) -> Result<Vec<DayPassDto>, String> {
+    let (day_start, day_end) = time::today_bounds();
+    let rows = client
+        .query(
+            r#"SELECT id, vehicle_id, license_plate, pass_type, price, purchase_date, valid_from, valid_until, is_active
+               FROM day_passes
+               WHERE is_active = true
+                 AND purchase_date >= $1 AND purchase_date < $2
+               ORDER BY purchase_date DESC"#,
+            &[&day_start, &day_end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_day_pass_row).collect())
+}
+
+/// The active pass covering today for the plate, the latest bought if several
+pub async fn today_pass_for_plate(client: &impl GenericClient, license_plate: &str) -> Result<Option<DayPassDto>, String> {
+    let sql = format!(
+        r#"SELECT id, vehicle_id, license_plate, pass_type, price, purchase_date, valid_from, valid_until, is_active
+           FROM day_passes
+           WHERE license_plate = $1 AND is_active = true AND {}
+           ORDER BY purchase_date DESC LIMIT 1"#,
+        COVERS_TODAY
+    );
+    let (day_start, day_end) = time::today_bounds();
+    let row = client
+        .query_opt(sql.as_str(), &[&license_plate, &day_start, &day_end])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_day_pass_row))
+}
+
+pub async fn insert_exit_pass(client: &impl GenericClient, pass: &NewExitPass<'_>) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            r#"INSERT INTO exit_passes (
+                    id, queue_id, vehicle_id, license_plate, destination_id, destination_name, current_exit_time,
+                    seats_sold, amount_collected, amount_due, created_by, created_at, terminal_id
+                ) VALUES ($1,$2,$3,$4,$5,$6,NOW(),$7,$8,$9,$10,NOW(),$11)"#,
+            &[
+                &id, &pass.queue_id, &pass.vehicle_id, &pass.license_plate, &pass.destination_id, &pass.destination_name,
+                &pass.settlement.seats_sold, &pass.settlement.gross.to_tnd(), &pass.settlement.net_to_driver.to_tnd(), &pass.created_by,
+                &crate::terminal::id(),
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    super::settlements::record(client, &id, pass).await?;
+    Ok(id)
+}
+
+/// Whether the queue entry already left with an exit pass
+pub async fn exit_pass_printed(client: &impl GenericClient, queue_id: &str) -> Result<bool, String> {
+    let row = client
+        .query_one("SELECT EXISTS(SELECT 1 FROM exit_passes WHERE queue_id = $1) AS printed", &[&queue_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get("printed"))
+}
+
+pub async fn today_exit_passes(client: &impl GenericClient) -> Result<Vec<ExitPassDto>, String> {
+    let (day_start, day_end) = time::today_bounds();
+    let rows = client
+        .query(
+            r#"SELECT id, vehicle_id, license_plate, destination_id, destination_name, current_exit_time, created_at
+               FROM exit_passes
+               WHERE current_exit_time >= $1 AND current_exit_time < $2
+               ORDER BY current_exit_time DESC"#,
+            &[&day_start, &day_end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_exit_pass_row).collect())
+}
+
+/// The last vehicle to leave for the destination today, for the "previous vehicle" line of a pass
+pub async fn previous_departure(client: &impl GenericClient, destination_id: &str) -> Result<Option<PreviousVehicle>, String> {
+    let (day_start, day_end) = time::today_bounds();
+    let row = client
+        .query_opt(
+            r#"SELECT license_plate, current_exit_time
+               FROM exit_passes
+               WHERE destination_id = $1 AND current_exit_time >= $2 AND current_exit_time < $3
+               ORDER BY current_exit_time DESC
+               LIMIT 1"#,
+            &[&destination_id, &day_start, &day_end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(PreviousVehicle::from_exit_pass_row))
+}
+
+pub async fn recent_exit_passes(client: &impl GenericClient) -> Result<Vec<ExitPassDto>, String> {
+    let rows = client
+        .query(
+            r#"SELECT id, vehicle_id, license_plate, destination_id, destination_name, current_exit_time, created_at
+               FROM exit_passes
+               WHERE current_exit_time >= NOW() - INTERVAL '10 minutes'
+               ORDER BY current_exit_time DESC
+               LIMIT 20"#,
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_exit_pass_row).collect())
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use super::GenericClient;
+use crate::money::Money;
+use crate::payments::{PaymentMethod, PaymentPart};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaymentMethodSettingDto {
+    pub method: String,
+    pub label: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaymentTotalDto {
+    pub method: String,
+    pub label: String,
+    pub count: i64,
+    pub amount: Money,
+}
+
+// Methods this station takes; cash when nothing is configured so a fresh database can still sell
+pub async fn accepted_methods(client: &impl GenericClient) -> Result<Vec<PaymentMethod>, String> {
+    let rows = client
+        .query("SELECT method FROM station_payment_methods WHERE enabled = true", &[])
+        .await
+        .map_err(|e| e.to_string())?;
+    let methods: Vec<PaymentMethod> = rows
+        .iter()
+        .filter_map(|r| PaymentMethod::from_name(&r.get::<_, String>("method")))
+        .collect();
+    if methods.is_empty() {
+        return Ok(vec![PaymentMethod::Cash]);
+    }
+    Ok(methods)
+}
+
+pub async fn settings(client: &impl GenericClient) -> Result<Vec<PaymentMethodSettingDto>, String> {
+    let accepted = accepted_methods(client).await?;
+    Ok(PaymentMethod::ALL
+        .iter()
+        .map(|method| PaymentMethodSettingDto {
+            method: method.as_str().to_string(),
+            label: method.label().to_string(),
+            enabled: accepted.contains(method),
+        })
+        .collect())
+}
+
+pub async fn set_enabled(client: &impl GenericClient, method: PaymentMethod, enabled: bool) -> Result<(), String> {
+    client
+        .execute(
+            "INSERT INTO station_payment_methods (method, enabled, updated_at) VALUES ($1, $2, NOW())
+             ON CONFLICT (method) DO UPDATE SET enabled = EXCLUDED.enabled, updated_at = NOW()",
+            &[&method.as_str(), &enabled],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// `card` is the terminal's (authorization code, transaction id), stored on the CARD rows
+pub async fn record(client: &impl GenericClient, booking_id: &str, parts: &[PaymentPart], card: Option<(&str, &str)>) -> Result<(), String> {
+    for part in parts {
+        let (authorization_code, transaction_id) = match card {
+            Some((code, txn)) if part.method == PaymentMethod::Card => (Some(code), Some(txn)),
+            _ => (None, None),
+        };
+        client
+            .execute(
+                "INSERT INTO booking_payments (id, booking_id, method, amount, authorization_code, terminal_transaction_id, created_at) VALUES ($1, $2, $3, $4, $5, $6, NOW())",
+                &[&uuid::Uuid::new_v4().to_string(), &booking_id, &part.method.as_str(), &part.amount.to_tnd(), &authorization_code, &transaction_id],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Booking takings per method for one Tunis day, optionally for a single vehicle or a single
+// cashier. Bookings made before payments were itemised count in full under their
+// bookings.payment_method.
+pub async fn booking_totals_for_day(client: &impl GenericClient, day: chrono::NaiveDate, vehicle_id: Option<&str>, created_by: Option<&str>) -> Result<Vec<PaymentTotalDto>, String> {
+    let (day_start, day_end) = crate::time::tunis_day_bounds(day);
+    let rows = client
+        .query(
+            r#"
+            WITH day_bookings AS (
+                SELECT b.id, b.payment_method, b.total_amount
+                FROM bookings b
+                LEFT JOIN vehicle_queue q ON q.id = b.queue_id
+                WHERE b.created_at >= $1 AND b.created_at < $2
+                  AND ($3::text IS NULL OR q.vehicle_id = $3)
+                  AND ($4::text IS NULL OR b.created_by = $4)
+            )
+            SELECT method, COUNT(DISTINCT booking_id) AS count, COALESCE(SUM(amount), 0)::float8 AS amount
+            FROM (
+                SELECT bp.method, bp.booking_id, bp.amount
+                FROM booking_payments bp
+                JOIN day_bookings d ON d.id = bp.booking_id
+                UNION ALL
+                SELECT d.payment_method, d.id, d.total_amount
+                FROM day_bookings d
+                WHERE NOT EXISTS (SELECT 1 FROM booking_payments bp WHERE bp.booking_id = d.id)
+            ) p
+            GROUP BY method
+            ORDER BY method
+            "#,
+            &[&day_start, &day_end, &vehicle_id, &created_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let method: String = r.get("method");
+            PaymentTotalDto {
+                label: crate::payments::method_label(&method),
+                method,
+                count: r.get("count"),
+                amount: r.get("amount"),
+            }
+        })
+        .collect())
+}
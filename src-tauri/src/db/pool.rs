@@ -0,0 +1,247 @@
+use deadpool_postgres::{Hook, HookError, Object, Pool, Runtime};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio_postgres::NoTls;
+
+use crate::secrets;
+use crate::training;
+
+// Database nodes. The primary (DATABASE_URL) takes every write; read replicas
+// (DATABASE_REPLICA_URLS, comma separated) serve the commands tagged read-only, which get their
+// connection from read() instead of DB_POOL. Passwords come from the credential store. A node that fails a connection or a health check
+// is skipped until it answers again: reads go to the primary while every replica is down and
+// stay on the replicas while the primary is being maintained.
+//
+// In training mode (training.rs) new connections are pointed at the training schema and those
+// opened before the switch are dropped instead of reused; reads all go to the primary. Background
+// jobs (nightly cleanup, queue closing, reservations, device ingest) work on the station's real
+// data whatever the cashier is doing, so they take background() or background_read(), which
+// stay on the public schema.
+
+const PRIMARY_POOL_SIZE: usize = 16;
+const REPLICA_POOL_SIZE: usize = 8;
+const BACKGROUND_POOL_SIZE: usize = 4;
+const CONNECT_TIMEOUT_SECS: u64 = 5;
+const HEALTH_CHECK_INTERVAL_SECS: u64 = 15;
+
+pub struct Node {
+    address: String,
+    pool: Pool,
+    healthy: AtomicBool,
+}
+
+impl Node {
+    // `follows_training`: connections take the training schema while training mode is on
+    fn new(url: &str, max_size: usize, follows_training: bool) -> Result<Self, String> {
+        let mut cfg = deadpool_postgres::Config::new();
+        cfg.url = Some(url.to_string());
+        cfg.connect_timeout = Some(Duration::from_secs(CONNECT_TIMEOUT_SECS));
+        cfg.pool = Some(deadpool_postgres::PoolConfig::new(max_size));
+        let mut builder = cfg.builder(NoTls).map_err(|e| e.to_string())?.runtime(Runtime::Tokio1);
+        if follows_training {
+            builder = builder
+                .post_create(Hook::async_fn(|client, _| {
+                    Box::pin(async move {
+                        if let Some(setup) = training::session_setup() {
+                            client.batch_execute(&setup).await.map_err(HookError::Backend)?;
+                        }
+                        Ok(())
+                    })
+                }))
+                .post_recycle(Hook::sync_fn(|_, metrics| {
+                    if training::is_current(metrics.created) {
+                        Ok(())
+                    } else {
+                        Err(HookError::message("opened before the training mode switch"))
+                    }
+                }));
+        }
+        let pool = builder.build().map_err(|e| e.to_string())?;
+        Ok(Node { address: address(url), pool, healthy: AtomicBool::new(true) })
+    }
+
+    async fn get(&self) -> Result<Object, String> {
+        let client = self.pool.get().await.map_err(|e| e.to_string());
+        self.mark(client.is_ok(), client.as_ref().err());
+        client
+    }
+
+    fn mark(&self, healthy: bool, error: Option<&String>) {
+        let was_healthy = self.healthy.swap(healthy, Ordering::Relaxed);
+        if was_healthy && !healthy {
+            println!("⚠️ Database node {} is not answering: {}", self.address, error.map(String::as_str).unwrap_or(""));
+        } else if !was_healthy && healthy {
+            println!("✅ Database node {} is answering again", self.address);
+        }
+    }
+
+    async fn check(&self) {
+        let answer = tokio::time::timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS), async {
+            let client = self.pool.get().await.map_err(|e| e.to_string())?;
+            client.simple_query("SELECT 1").await.map_err(|e| e.to_string())
+        })
+        .await
+        .unwrap_or_else(|_| Err("timed out".to_string()));
+        self.mark(answer.is_ok(), answer.as_ref().err());
+    }
+}
+
+pub struct Nodes {
+    pub primary: Node,
+    // The primary again, always on the public schema
+    background: Node,
+    replicas: Vec<Node>,
+    next_replica: AtomicUsize,
+}
+
+pub static NODES: Lazy<Nodes> = Lazy::new(|| {
+    let primary_url = secrets::database_url();
+    let replicas = replica_urls(std::env::var("DATABASE_REPLICA_URLS").ok().as_deref())
+        .iter()
+        // Never read from in training mode by read(), so they can stay on the public schema
+        .filter_map(|url| match Node::new(&secrets::database_url_with_password(url), REPLICA_POOL_SIZE, false) {
+            Ok(node) => Some(node),
+            Err(e) => {
+                println!("⚠️ Ignoring read replica {}: {}", address(url), e);
+                None
+            }
+        })
+        .collect();
+    Nodes {
+        primary: Node::new(&primary_url, PRIMARY_POOL_SIZE, true).expect("Failed to create DB pool"),
+        background: Node::new(&primary_url, BACKGROUND_POOL_SIZE, false).expect("Failed to create DB pool"),
+        replicas,
+        next_replica: AtomicUsize::new(0),
+    }
+});
+
+impl Nodes {
+    pub fn primary_pool(&self) -> Pool {
+        self.primary.pool.clone()
+    }
+}
+
+pub fn replica_urls(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Host, port and database of a DSN, without the credentials, for logs and the status screen
+pub fn address(url: &str) -> String {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    rest.rsplit_once('@').map(|(_, host)| host).unwrap_or(rest).to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    Primary,
+    Replica(usize),
+}
+
+/// Where a read is tried, in turn: the healthy replicas, round robin from `start`, then the
+/// primary, then the replicas that failed their last check
+pub fn read_order(replicas_healthy: &[bool], start: usize) -> Vec<Target> {
+    let count = replicas_healthy.len();
+    let rotated: Vec<usize> = (0..count).map(|i| (start + i) % count).collect();
+    let healthy = rotated.iter().filter(|i| replicas_healthy[**i]).map(|i| Target::Replica(*i));
+    let failing = rotated.iter().filter(|i| !replicas_healthy[**i]).map(|i| Target::Replica(*i));
+    healthy.chain(std::iter::once(Target::Primary)).chain(failing).collect()
+}
+
+/// A connection for a read-only command
+pub async fn read() -> Result<Object, String> {
+    if training::is_active() {
+        return NODES.primary.get().await;
+    }
+    read_from(&NODES.primary).await
+}
+
+/// A connection to the primary on the public schema, for a background job that writes
+pub async fn background() -> Result<Object, String> {
+    NODES.background.get().await
+}
+
+/// A connection on the public schema for a background job that only reads
+pub async fn background_read() -> Result<Object, String> {
+    read_from(&NODES.background).await
+}
+
+// The replicas in read_order, with `primary` in the primary's place
+async fn read_from(primary: &Node) -> Result<Object, String> {
+    let nodes = &*NODES;
+    let healthy: Vec<bool> = nodes.replicas.iter().map(|r| r.healthy.load(Ordering::Relaxed)).collect();
+    let start = nodes.next_replica.fetch_add(1, Ordering::Relaxed);
+    let mut last_error = String::new();
+    for target in read_order(&healthy, start) {
+        let node = match target {
+            Target::Primary => primary,
+            Target::Replica(i) => &nodes.replicas[i],
+        };
+        match node.get().await {
+            Ok(client) => return Ok(client),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+/// Check every node now and then, so a node that came back is used again
+pub async fn run_health_checks() {
+    let mut interval = tokio::time::interval(Duration::from_secs(HEALTH_CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        NODES.primary.check().await;
+        for replica in &NODES.replicas {
+            replica.check().await;
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseNodeDto {
+    pub role: String,
+    pub address: String,
+    pub healthy: bool,
+}
+
+pub fn status() -> Vec<DatabaseNodeDto> {
+    let node = |role: &str, node: &Node| DatabaseNodeDto {
+        role: role.to_string(),
+        address: node.address.clone(),
+        healthy: node.healthy.load(Ordering::Relaxed),
+    };
+    std::iter::once(node("PRIMARY", &NODES.primary))
+        .chain(NODES.replicas.iter().map(|r| node("REPLICA", r)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replica_dsns_are_listed_without_credentials() {
+        let urls = replica_urls(Some(" postgresql://u:p@10.0.0.2:5432/louaj_node, ,postgresql://10.0.0.3/louaj_node"));
+        assert_eq!(urls.len(), 2);
+        assert_eq!(address(&urls[0]), "10.0.0.2:5432/louaj_node");
+        assert_eq!(address(&urls[1]), "10.0.0.3/louaj_node");
+        assert!(replica_urls(None).is_empty());
+    }
+
+    #[test]
+    fn reads_prefer_healthy_replicas_then_the_primary() {
+        use Target::*;
+        assert_eq!(read_order(&[], 7), vec![Primary]);
+        assert_eq!(read_order(&[true, true], 1), vec![Replica(1), Replica(0), Primary]);
+        assert_eq!(read_order(&[false, true, true], 0), vec![Replica(1), Replica(2), Primary, Replica(0)]);
+        assert_eq!(read_order(&[false, false], 0), vec![Primary, Replica(0), Replica(1)]);
+    }
+}
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+
+use super::GenericClient;
+use crate::time::TunisTime;
+
+// Last known GPS positions (migration 031)
+
+/// A position to store, already checked
+pub struct NewPosition<'a> {
+    pub vehicle_id: &'a str,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub speed_kmh: Option<f64>,
+    pub heading: Option<f64>,
+    pub recorded_at: DateTime<Utc>,
+    pub source: &'a str,
+}
+
+/// A vehicle's stored position with its last departure and whether it is queued now
+pub struct PositionRow {
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub speed_kmh: Option<f64>,
+    pub heading: Option<f64>,
+    pub recorded_at: DateTime<Utc>,
+    pub last_exit_at: Option<DateTime<Utc>>,
+    pub last_destination_id: Option<String>,
+    pub last_destination_name: Option<String>,
+    pub in_queue: bool,
+}
+
+/// Store the position unless a later one is already stored; whether it was stored
+pub async fn upsert(client: &impl GenericClient, position: &NewPosition<'_>) -> Result<bool, String> {
+    let stored = client
+        .execute(
+            r#"INSERT INTO vehicle_positions (vehicle_id, latitude, longitude, speed_kmh, heading, recorded_at, received_at, source)
+               VALUES ($1, $2, $3, $4, $5, $6, NOW(), $7)
+               ON CONFLICT (vehicle_id) DO UPDATE SET
+                   latitude = EXCLUDED.latitude, longitude = EXCLUDED.longitude, speed_kmh = EXCLUDED.speed_kmh,
+                   heading = EXCLUDED.heading, recorded_at = EXCLUDED.recorded_at, received_at = NOW(), source = EXCLUDED.source
+               WHERE vehicle_positions.recorded_at <= EXCLUDED.recorded_at"#,
+            &[
+                &position.vehicle_id, &position.latitude, &position.longitude, &position.speed_kmh, &position.heading,
+                &position.recorded_at, &position.source,
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(stored > 0)
+}
+
+/// Every stored position, by plate
+pub async fn all(client: &impl GenericClient) -> Result<Vec<PositionRow>, String> {
+    let rows = client
+        .query(
+            r#"SELECT p.vehicle_id, v.license_plate, p.latitude, p.longitude, p.speed_kmh, p.heading, p.recorded_at,
+                      e.current_exit_time, e.destination_id, e.destination_name,
+                      EXISTS(SELECT 1 FROM vehicle_queue q WHERE q.vehicle_id = p.vehicle_id) AS in_queue
+               FROM vehicle_positions p
+               JOIN vehicles v ON v.id = p.vehicle_id
+               LEFT JOIN LATERAL (
+                   SELECT current_exit_time, destination_id, destination_name FROM exit_passes
+                   WHERE vehicle_id = p.vehicle_id ORDER BY current_exit_time DESC LIMIT 1
+               ) e ON true
+               ORDER BY v.license_plate"#,
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| PositionRow {
+            vehicle_id: r.get("vehicle_id"),
+            license_plate: r.get("license_plate"),
+            latitude: r.get("latitude"),
+            longitude: r.get("longitude"),
+            speed_kmh: r.get("speed_kmh"),
+            heading: r.get("heading"),
+            recorded_at: r.get("recorded_at"),
+            last_exit_at: r.get::<_, Option<TunisTime>>("current_exit_time").map(|t| t.0.with_timezone(&Utc)),
+            last_destination_id: r.get("destination_id"),
+            last_destination_name: r.get("destination_name"),
+            in_queue: r.get("in_queue"),
+        })
+        .collect())
+}
+
+/// The id of the vehicle named by id or by plate, when it exists
+pub async fn resolve_vehicle(client: &impl GenericClient, vehicle_id: Option<&str>, license_plate: Option<&str>) -> Result<Option<String>, String> {
+    let row = client
+        .query_opt("SELECT id FROM vehicles WHERE id = $1 OR license_plate = $2 LIMIT 1", &[&vehicle_id, &license_plate])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("id")))
+}
@@ -0,0 +1,85 @@
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::pricing::{FareModifier, PricingRule, PricingRuleType};
+
+const RULE_COLUMNS: &str = "id, rule_type, label, amount, percent, destination_id, is_active";
+
+fn map_rule_row(r: &Row) -> Option<PricingRule> {
+    Some(PricingRule {
+        id: r.get("id"),
+        rule_type: PricingRuleType::from_name(&r.get::<_, String>("rule_type"))?,
+        label: r.get("label"),
+        amount: r.get("amount"),
+        percent: r.get("percent"),
+        destination_id: r.get("destination_id"),
+        is_active: r.get("is_active"),
+    })
+}
+
+// Every rule, station-wide ones first
+pub async fn list(client: &impl GenericClient) -> Result<Vec<PricingRule>, String> {
+    let sql = format!(
+        "SELECT {} FROM pricing_rules ORDER BY destination_id NULLS FIRST, rule_type, label",
+        RULE_COLUMNS
+    );
+    let rows = client.query(sql.as_str(), &[]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().filter_map(map_rule_row).collect())
+}
+
+// Insert, or replace the rule with the same id; returns the rule as stored
+pub async fn upsert(client: &impl GenericClient, rule: &PricingRule, updated_by: &Option<String>) -> Result<PricingRule, String> {
+    let sql = format!(
+        "INSERT INTO pricing_rules (id, rule_type, label, amount, percent, destination_id, is_active, updated_by, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW(), NOW())
+         ON CONFLICT (id) DO UPDATE SET rule_type = EXCLUDED.rule_type, label = EXCLUDED.label, amount = EXCLUDED.amount,
+             percent = EXCLUDED.percent, destination_id = EXCLUDED.destination_id, is_active = EXCLUDED.is_active,
+             updated_by = EXCLUDED.updated_by, updated_at = NOW()
+         RETURNING {}",
+        RULE_COLUMNS
+    );
+    let row = client
+        .query_one(
+            sql.as_str(),
+            &[&rule.id, &rule.rule_type.as_str(), &rule.label.trim(), &rule.amount.map(|a| a.to_tnd()), &rule.percent, &rule.destination_id, &rule.is_active, updated_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    map_rule_row(&row).ok_or_else(|| "Type de règle inconnu".to_string())
+}
+
+pub async fn record(client: &impl GenericClient, booking_id: &str, modifiers: &[FareModifier]) -> Result<(), String> {
+    for modifier in modifiers {
+        client
+            .execute(
+                "INSERT INTO booking_fare_modifiers (id, booking_id, rule_id, rule_type, label, quantity, amount, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())",
+                &[&uuid::Uuid::new_v4().to_string(), &booking_id, &modifier.rule_id, &modifier.rule_type.as_str(), &modifier.label, &modifier.quantity, &modifier.amount.to_tnd()],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// What a booking was charged under each rule, as printed on its ticket
+pub async fn for_booking(client: &impl GenericClient, booking_id: &str) -> Result<Vec<FareModifier>, String> {
+    let rows = client
+        .query(
+            "SELECT rule_id, rule_type, label, quantity, amount FROM booking_fare_modifiers WHERE booking_id = $1 ORDER BY created_at, id",
+            &[&booking_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .filter_map(|r| {
+            Some(FareModifier {
+                rule_id: r.get("rule_id"),
+                rule_type: PricingRuleType::from_name(&r.get::<_, String>("rule_type"))?,
+                label: r.get("label"),
+                quantity: r.get("quantity"),
+                amount: r.get("amount"),
+            })
+        })
+        .collect())
+}
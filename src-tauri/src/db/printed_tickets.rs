@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::search::escape_like;
+use super::GenericClient;
+use crate::time::TunisTime;
+
+// The print history (migration 035, see ticket_archive.rs), with the tickets kept unprinted
+// while the station runs without a printer (migration 040, see ticket_escrow.rs)
+
+pub const PRINTED: &str = "PRINTED";
+/// Issued without a printer, waiting to be printed or texted
+pub const ESCROWED: &str = "ESCROWED";
+/// An escrowed ticket texted to the customer instead
+pub const SENT: &str = "SENT";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintedTicketDto {
+    pub id: String,
+    pub job_id: String,
+    /// Print job type, as in PrintJobType ("EntryTicket")
+    pub ticket_type: String,
+    pub ticket_number: Option<String>,
+    pub staff_name: Option<String>,
+    pub printer_id: String,
+    pub printer_name: String,
+    /// Reprint number when the ticket printed as a duplicate
+    pub duplicate_number: Option<i32>,
+    pub payload: String,
+    /// The ticket as printed, one line per printed line
+    pub rendered_text: String,
+    /// Characters per line of the printer it came out of
+    pub width: i16,
+    /// PRINTED, ESCROWED or SENT
+    pub status: String,
+    /// When an escrowed ticket was printed or texted, and the number it was texted to
+    pub delivered_at: Option<String>,
+    pub delivered_to: Option<String>,
+    /// When it printed, or was issued for an escrowed ticket
+    pub printed_at: String,
+}
+
+/// A printed ticket to archive
+pub struct NewPrintedTicket<'a> {
+    pub job_id: &'a str,
+    pub ticket_type: &'a str,
+    pub ticket_number: Option<&'a str>,
+    pub staff_name: Option<&'a str>,
+    pub printer_id: &'a str,
+    pub printer_name: &'a str,
+    pub duplicate_number: Option<i32>,
+    pub payload: &'a str,
+    pub rendered_text: &'a str,
+    pub width: i16,
+    pub status: &'a str,
+}
+
+/// Search criteria, already checked; text criteria match anywhere, ignoring case
+pub struct PrintedTicketSearch {
+    pub ticket_type: Option<String>,
+    pub ticket_number: Option<String>,
+    pub staff_name: Option<String>,
+    pub printer: Option<String>,
+    pub text: Option<String>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub limit: i64,
+}
+
+fn map_printed_row(r: &Row) -> PrintedTicketDto {
+    PrintedTicketDto {
+        id: r.get("id"),
+        job_id: r.get("job_id"),
+        ticket_type: r.get("ticket_type"),
+        ticket_number: r.get("ticket_number"),
+        staff_name: r.get("staff_name"),
+        printer_id: r.get("printer_id"),
+        printer_name: r.get("printer_name"),
+        duplicate_number: r.get("duplicate_number"),
+        payload: r.get("payload"),
+        rendered_text: r.get("rendered_text"),
+        width: r.get("width"),
+        status: r.get("status"),
+        delivered_at: r.get::<_, Option<TunisTime>>("delivered_at").map(|t| t.fmt_dto()),
+        delivered_to: r.get("delivered_to"),
+        printed_at: r.get::<_, TunisTime>("printed_at").fmt_dto(),
+    }
+}
+
+const PRINTED_SQL: &str = r#"
+    SELECT id, job_id, ticket_type, ticket_number, staff_name, printer_id, printer_name, duplicate_number,
+           payload, rendered_text, width, status, delivered_at, delivered_to, printed_at
+    FROM printed_tickets"#;
+
+pub async fn insert(client: &impl GenericClient, ticket: &NewPrintedTicket<'_>) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            r#"INSERT INTO printed_tickets (id, job_id, ticket_type, ticket_number, staff_name, printer_id, printer_name,
+                                           duplicate_number, payload, rendered_text, width, terminal_id, status, printed_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, NOW())"#,
+            &[
+                &id, &ticket.job_id, &ticket.ticket_type, &ticket.ticket_number, &ticket.staff_name, &ticket.printer_id,
+                &ticket.printer_name, &ticket.duplicate_number, &ticket.payload, &ticket.rendered_text, &ticket.width,
+                &crate::terminal::id(), &ticket.status,
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+fn contains(text: &Option<String>) -> Option<String> {
+    text.as_ref().map(|t| format!("%{}%", escape_like(t)))
+}
+
+/// Tickets printed between `from` and `to` matching every given criterion, latest first
+pub async fn search(client: &impl GenericClient, search: &PrintedTicketSearch) -> Result<Vec<PrintedTicketDto>, String> {
+    let rows = client
+        .query(
+            &format!(
+                r#"{} WHERE printed_at >= $1 AND printed_at < $2
+                      AND ($3::text IS NULL OR ticket_type = $3)
+                      AND ($4::text IS NULL OR ticket_number ILIKE $4)
+                      AND ($5::text IS NULL OR staff_name ILIKE $5)
+                      AND ($6::text IS NULL OR printer_name ILIKE $6 OR printer_id ILIKE $6)
+                      AND ($7::text IS NULL OR rendered_text ILIKE $7)
+                   ORDER BY printed_at DESC
+                   LIMIT $8"#,
+                PRINTED_SQL
+            ),
+            &[
+                &search.from, &search.to, &search.ticket_type, &contains(&search.ticket_number), &contains(&search.staff_name),
+                &contains(&search.printer), &contains(&search.text), &search.limit,
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_printed_row).collect())
+}
+
+pub async fn get(client: &impl GenericClient, id: &str) -> Result<Option<PrintedTicketDto>, String> {
+    let row = client
+        .query_opt(&format!("{} WHERE id = $1", PRINTED_SQL), &[&id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_printed_row))
+}
+
+/// Escrowed tickets, oldest first; only those among `ids` when given
+pub async fn escrowed(client: &impl GenericClient, ids: Option<&[String]>, limit: i64) -> Result<Vec<PrintedTicketDto>, String> {
+    let rows = client
+        .query(
+            &format!("{} WHERE status = $1 AND ($2::text[] IS NULL OR id = ANY($2)) ORDER BY printed_at LIMIT $3", PRINTED_SQL),
+            &[&ESCROWED, &ids, &limit],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_printed_row).collect())
+}
+
+/// An escrowed ticket has come out of `printer_id`; 0 when it was no longer escrowed
+pub async fn mark_printed(client: &impl GenericClient, id: &str, printer_id: &str, printer_name: &str) -> Result<u64, String> {
+    client
+        .execute(
+            "UPDATE printed_tickets SET status = $1, printer_id = $2, printer_name = $3, delivered_at = NOW()
+             WHERE id = $4 AND status = $5",
+            &[&PRINTED, &printer_id, &printer_name, &id, &ESCROWED],
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// An escrowed ticket was texted to `phone_number`; 0 when it was no longer escrowed
+pub async fn mark_sent(client: &impl GenericClient, id: &str, phone_number: &str) -> Result<u64, String> {
+    client
+        .execute(
+            "UPDATE printed_tickets SET status = $1, delivered_to = $2, delivered_at = NOW() WHERE id = $3 AND status = $4",
+            &[&SENT, &phone_number, &id, &ESCROWED],
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,524 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::money::Money;
+
+// Re-derive a queue entry's status from its seats after seats are given back:
+// no booking = WAITING, partly booked = LOADING, full = READY
+const SYNC_STATUS_SQL: &str = "UPDATE vehicle_queue SET status = CASE \
+        WHEN available_seats = 0 THEN 'READY' \
+        WHEN available_seats < total_seats THEN 'LOADING' \
+        ELSE 'WAITING' END \
+     WHERE id = $1 AND status IN ('WAITING', 'LOADING', 'READY')";
+
+pub const WAITING: &str = "WAITING";
+// Put in the queue by the gate camera (anpr.rs) and not yet confirmed by a cashier: it sells no
+// seats and does not depart until then
+pub const PENDING_CONFIRM: &str = "PENDING_CONFIRM";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueItemDto {
+    pub id: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub sub_route: Option<String>,
+    pub sub_route_name: Option<String>,
+    pub queue_position: i32,
+    pub status: String,
+    pub available_seats: i32,
+    pub total_seats: i32,
+    pub base_price: Money,
+    pub license_plate: String,
+    pub version: i64, // concurrency token, bumped on every change to the entry (migration 024)
+}
+
+// Why a reorder was refused. StaleState carries the queue as it is now, for the UI to merge its
+// move into and send again.
+#[derive(Debug, Serialize)]
+#[serde(tag = "code", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReorderError {
+    #[serde(rename_all = "camelCase")]
+    StaleState { message: String, changed: Vec<String>, queue: Vec<QueueItemDto> },
+    Failed { message: String },
+}
+
+impl From<String> for ReorderError {
+    fn from(message: String) -> Self {
+        ReorderError::Failed { message }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleQueueStatusDto {
+    pub id: String,
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub queue_position: i32,
+    pub status: String,
+    pub available_seats: i32,
+    pub total_seats: i32,
+    pub base_price: Money,
+    pub entered_at: String,
+}
+
+// Route row backing a destination: price per seat and display name
+pub struct DestinationRoute {
+    pub base_price: Money,
+    pub station_name: String,
+}
+
+pub fn map_queue_row(row: &Row) -> QueueItemDto {
+    QueueItemDto {
+        id: row.get::<_, String>("id"),
+        destination_id: row.get::<_, String>("destination_id"),
+        destination_name: row.get::<_, String>("destination_name"),
+        sub_route: row.get::<_, Option<String>>("sub_route"),
+        sub_route_name: row.get::<_, Option<String>>("sub_route_name"),
+        queue_position: row.get::<_, i32>("queue_position"),
+        status: row.get::<_, String>("status"),
+        available_seats: row.get::<_, i32>("available_seats"),
+        total_seats: row.get::<_, i32>("total_seats"),
+        base_price: row.get::<_, Money>("base_price"),
+        license_plate: row.get::<_, String>("license_plate"),
+        version: row.get::<_, i64>("version"),
+    }
+}
+
+pub async fn by_destination(client: &impl GenericClient, destination_id: &str) -> Result<Vec<QueueItemDto>, String> {
+    let sql = r#"
+        SELECT q.id,
+               q.destination_id,
+               q.destination_name,
+               q.sub_route,
+               q.sub_route_name,
+               q.queue_position,
+               q.status,
+               q.available_seats,
+               q.total_seats,
+               q.base_price,
+               v.license_plate,
+               q.version
+        FROM vehicle_queue q
+        JOIN vehicles v ON v.id = q.vehicle_id
+        WHERE q.destination_id = $1
+        ORDER BY q.queue_position ASC
+    "#;
+    let rows = client.query(sql, &[&destination_id]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_queue_row).collect())
+}
+
+// Seats left per destination and sub-route, over the vehicles still selling
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DestinationSeats {
+    pub destination_id: String,
+    pub destination_name: String,
+    pub sub_route: Option<String>,
+    pub sub_route_name: Option<String>,
+    pub available_seats: i64,
+    pub vehicle_count: i64,
+    pub governorate: Option<String>,
+    pub governorate_ar: Option<String>,
+    pub delegation: Option<String>,
+    pub delegation_ar: Option<String>,
+}
+
+/// Every destination with seats left, or only `destinations` when given
+pub async fn destination_seats(client: &impl GenericClient, destinations: Option<&[String]>) -> Result<Vec<DestinationSeats>, String> {
+    let sql = r#"
+        SELECT q.destination_id,
+               MAX(q.destination_name) AS destination_name,
+               COALESCE(q.sub_route, '') AS sub_route,
+               COALESCE(q.sub_route_name, '') AS sub_route_name,
+               SUM(q.available_seats)::bigint AS available_seats,
+               COUNT(*)::bigint AS vehicle_count,
+               MAX(r.governorate) AS governorate,
+               MAX(r.governorate_ar) AS governorate_ar,
+               MAX(r.delegation) AS delegation,
+               MAX(r.delegation_ar) AS delegation_ar
+        FROM vehicle_queue q
+        LEFT JOIN routes r ON r.station_id = q.destination_id
+        WHERE q.available_seats > 0
+          AND ($1::text[] IS NULL OR q.destination_id = ANY($1))
+        GROUP BY q.destination_id, q.sub_route, q.sub_route_name
+    "#;
+    let rows = client.query(sql, &[&destinations]).await.map_err(|e| e.to_string())?;
+    let non_empty = |v: String| if v.is_empty() { None } else { Some(v) };
+    Ok(rows
+        .iter()
+        .map(|r| DestinationSeats {
+            destination_id: r.get("destination_id"),
+            destination_name: r.get("destination_name"),
+            sub_route: non_empty(r.get("sub_route")),
+            sub_route_name: non_empty(r.get("sub_route_name")),
+            available_seats: r.get("available_seats"),
+            vehicle_count: r.get("vehicle_count"),
+            governorate: r.get("governorate"),
+            governorate_ar: r.get("governorate_ar"),
+            delegation: r.get("delegation"),
+            delegation_ar: r.get("delegation_ar"),
+        })
+        .collect())
+}
+
+pub async fn status_for_plate(client: &impl GenericClient, license_plate: &str) -> Result<Option<VehicleQueueStatusDto>, String> {
+    let sql = r#"
+        SELECT q.id, q.vehicle_id, v.license_plate, q.destination_id, q.destination_name,
+               q.queue_position, q.status, q.available_seats, q.total_seats, q.base_price,
+               q.entered_at
+        FROM vehicle_queue q
+        JOIN vehicles v ON q.vehicle_id = v.id
+        WHERE v.license_plate = $1
+    "#;
+    let row = client.query_opt(sql, &[&license_plate]).await.map_err(|e| e.to_string())?;
+    Ok(row.map(|row| VehicleQueueStatusDto {
+        id: row.get("id"),
+        vehicle_id: row.get("vehicle_id"),
+        license_plate: row.get("license_plate"),
+        destination_id: row.get("destination_id"),
+        destination_name: row.get("destination_name"),
+        queue_position: row.get("queue_position"),
+        status: row.get("status"),
+        available_seats: row.get("available_seats"),
+        total_seats: row.get("total_seats"),
+        base_price: row.get("base_price"),
+        entered_at: row.get::<_, crate::time::TunisTime>("entered_at").local().to_string(),
+    }))
+}
+
+pub async fn destination_route(client: &impl GenericClient, destination_id: &str) -> Result<Option<DestinationRoute>, String> {
+    let row = client
+        .query_opt("SELECT base_price, station_name FROM routes WHERE station_id = $1", &[&destination_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| DestinationRoute {
+        base_price: r.get("base_price"),
+        station_name: r.get("station_name"),
+    }))
+}
+
+pub async fn destination_of(client: &impl GenericClient, queue_id: &str) -> Result<Option<String>, String> {
+    let row = client
+        .query_opt("SELECT destination_id FROM vehicle_queue WHERE id = $1", &[&queue_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("destination_id")))
+}
+
+// Seats still free on the vehicles queued for a destination
+pub async fn free_seats(client: &impl GenericClient, destination_id: &str) -> Result<i64, String> {
+    let row = client
+        .query_one(
+            "SELECT COALESCE(SUM(available_seats), 0)::bigint AS seats FROM vehicle_queue WHERE destination_id = $1",
+            &[&destination_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get("seats"))
+}
+
+// Next position within destination + sub-route
+pub async fn next_position(client: &impl GenericClient, destination_id: &str, sub_route: &Option<String>) -> Result<i32, String> {
+    let row = client
+        .query_one(
+            "SELECT COALESCE(MAX(queue_position), 0)+1 AS next_pos \
+             FROM vehicle_queue \
+             WHERE destination_id = $1 \
+               AND COALESCE(sub_route,'') = COALESCE($2,'')",
+            &[&destination_id, sub_route],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get("next_pos"))
+}
+
+pub async fn entry_for_vehicle(client: &impl GenericClient, vehicle_id: &str) -> Result<Option<String>, String> {
+    let row = client
+        .query_opt("SELECT id FROM vehicle_queue WHERE vehicle_id = $1", &[&vehicle_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("id")))
+}
+
+pub struct NewQueueEntry<'a> {
+    pub vehicle_id: &'a str,
+    pub destination_id: &'a str,
+    pub destination_name: &'a str,
+    pub sub_route: &'a Option<String>,
+    pub sub_route_name: &'a Option<String>,
+    pub position: i32,
+    pub seats: i32,
+    pub base_price: Money,
+    /// WAITING, or PENDING_CONFIRM for the gate camera's entries
+    pub status: &'a str,
+}
+
+pub async fn insert(client: &impl GenericClient, entry: &NewQueueEntry<'_>) -> Result<String, String> {
+    let qid = uuid::Uuid::new_v4().to_string();
+    // Seats of an unconfirmed entry go on sale when it is confirmed
+    let available = if entry.status == PENDING_CONFIRM { 0 } else { entry.seats };
+    client
+        .execute(
+            "INSERT INTO vehicle_queue (id, vehicle_id, destination_id, destination_name, sub_route, sub_route_name, queue_position, status, entered_at, available_seats, total_seats, base_price) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,NOW(),$9,$10,$11)",
+            &[&qid, &entry.vehicle_id, &entry.destination_id, &entry.destination_name, entry.sub_route, entry.sub_route_name, &entry.position, &entry.status, &available, &entry.seats, &entry.base_price.to_tnd()],
+        )
+        .await
+        .map_err(|e| format!("Insertion dans la file échouée: {}", e))?;
+    Ok(qid)
+}
+
+// Point an existing entry at another destination; seats and bookings are left as they are
+pub async fn move_to_destination(client: &impl GenericClient, queue_id: &str, entry: &NewQueueEntry<'_>) -> Result<(), String> {
+    client
+        .execute(
+            "UPDATE vehicle_queue SET destination_id = $1, destination_name = $2, sub_route = $3, sub_route_name = $4, queue_position = $5, base_price = $6 WHERE id = $7",
+            &[&entry.destination_id, &entry.destination_name, entry.sub_route, entry.sub_route_name, &entry.position, &entry.base_price.to_tnd(), &queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn update_subroute(client: &impl GenericClient, queue_id: &str, sub_route: &Option<String>, sub_route_name: &Option<String>) -> Result<u64, String> {
+    client
+        .execute(
+            "UPDATE vehicle_queue SET sub_route = $1, sub_route_name = $2 WHERE id = $3",
+            &[sub_route, sub_route_name, &queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn bulk_update_subroute(client: &impl GenericClient, destination_id: &str, sub_route: &str, sub_route_name: &str, only_empty: bool) -> Result<u64, String> {
+    let sql = if only_empty {
+        "UPDATE vehicle_queue SET sub_route = $1, sub_route_name = $2 WHERE destination_id = $3 AND (sub_route IS NULL OR sub_route = '')"
+    } else {
+        "UPDATE vehicle_queue SET sub_route = $1, sub_route_name = $2 WHERE destination_id = $3"
+    };
+    client
+        .execute(sql, &[&sub_route, &sub_route_name, &destination_id])
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Version of every entry queued for the destination, locked until the transaction ends
+pub async fn lock_versions(client: &impl GenericClient, destination_id: &str) -> Result<HashMap<String, i64>, String> {
+    let rows = client
+        .query("SELECT id, version FROM vehicle_queue WHERE destination_id = $1 FOR UPDATE", &[&destination_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| (r.get("id"), r.get("version"))).collect())
+}
+
+/// Entries changed since the UI read `expected`: edited, gone, or new in the queue
+pub fn changed_entries(expected: &HashMap<String, i64>, current: &HashMap<String, i64>) -> Vec<String> {
+    let mut changed: Vec<String> = expected
+        .iter()
+        .filter(|(id, version)| current.get(*id) != Some(version))
+        .map(|(id, _)| id.clone())
+        .chain(current.keys().filter(|id| !expected.contains_key(*id)).cloned())
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// A queue entry still in the queue, locked until the transaction ends
+pub struct LockedEntry {
+    pub id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub sub_route: Option<String>,
+    pub available_seats: i32,
+    pub total_seats: i32,
+}
+
+/// The entries among `ids` still WAITING, LOADING or READY, locked in id order so two calls on
+/// the same pair cannot deadlock
+pub async fn lock_entries(client: &impl GenericClient, ids: &[&str]) -> Result<Vec<LockedEntry>, String> {
+    let rows = client
+        .query(
+            "SELECT q.id, v.license_plate, q.destination_id, q.sub_route, q.available_seats, q.total_seats
+             FROM vehicle_queue q JOIN vehicles v ON v.id = q.vehicle_id
+             WHERE q.id = ANY($1) AND q.status IN ('WAITING', 'LOADING', 'READY')
+             ORDER BY q.id FOR UPDATE OF q",
+            &[&ids],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| LockedEntry {
+            id: r.get("id"),
+            license_plate: r.get("license_plate"),
+            destination_id: r.get("destination_id"),
+            sub_route: r.get("sub_route"),
+            available_seats: r.get("available_seats"),
+            total_seats: r.get("total_seats"),
+        })
+        .collect())
+}
+
+/// The vehicle's queue entry, whatever its status, locked until the transaction ends
+pub async fn lock_vehicle_entry(client: &impl GenericClient, vehicle_id: &str) -> Result<Option<LockedEntry>, String> {
+    let row = client
+        .query_opt(
+            "SELECT q.id, v.license_plate, q.destination_id, q.sub_route, q.available_seats, q.total_seats
+             FROM vehicle_queue q JOIN vehicles v ON v.id = q.vehicle_id
+             WHERE q.vehicle_id = $1 FOR UPDATE OF q",
+            &[&vehicle_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| LockedEntry {
+        id: r.get("id"),
+        license_plate: r.get("license_plate"),
+        destination_id: r.get("destination_id"),
+        sub_route: r.get("sub_route"),
+        available_seats: r.get("available_seats"),
+        total_seats: r.get("total_seats"),
+    }))
+}
+
+// Give a queue entry a new seat count and bring its status in line
+pub async fn resize(client: &impl GenericClient, queue_id: &str, total_seats: i32, available_seats: i32) -> Result<(), String> {
+    client
+        .execute(
+            "UPDATE vehicle_queue SET total_seats = $1, available_seats = $2 WHERE id = $3",
+            &[&total_seats, &available_seats, &queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    sync_status(client, queue_id).await
+}
+
+/// A queue entry of a destination being dispatched, with what its exit pass needs
+pub struct DepartingEntry {
+    pub id: String,
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub destination_name: String,
+    pub available_seats: i32,
+    pub total_seats: i32,
+    pub base_price: Money,
+}
+
+/// Every confirmed entry queued for the destination, in queue order, locked until the transaction ends
+pub async fn lock_destination(client: &impl GenericClient, destination_id: &str) -> Result<Vec<DepartingEntry>, String> {
+    let rows = client
+        .query(
+            "SELECT q.id, q.vehicle_id, v.license_plate, q.destination_name, q.available_seats, q.total_seats, q.base_price
+             FROM vehicle_queue q JOIN vehicles v ON v.id = q.vehicle_id
+             WHERE q.destination_id = $1 AND q.status <> 'PENDING_CONFIRM'
+             ORDER BY COALESCE(q.sub_route, ''), q.queue_position FOR UPDATE OF q",
+            &[&destination_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| DepartingEntry {
+            id: r.get("id"),
+            vehicle_id: r.get("vehicle_id"),
+            license_plate: r.get("license_plate"),
+            destination_name: r.get("destination_name"),
+            available_seats: r.get("available_seats"),
+            total_seats: r.get("total_seats"),
+            base_price: r.get("base_price"),
+        })
+        .collect())
+}
+
+/// Number the destination's remaining entries 1.. again, per sub-route, keeping their order
+pub async fn renumber_destination(client: &impl GenericClient, destination_id: &str) -> Result<(), String> {
+    client
+        .execute(
+            r#"UPDATE vehicle_queue q SET queue_position = r.position
+               FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY COALESCE(sub_route, '') ORDER BY queue_position)::int AS position
+                     FROM vehicle_queue WHERE destination_id = $1) r
+               WHERE q.id = r.id AND q.queue_position <> r.position"#,
+            &[&destination_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn update_position(client: &impl GenericClient, queue_id: &str, position: i32) -> Result<u64, String> {
+    client
+        .execute("UPDATE vehicle_queue SET queue_position = $1 WHERE id = $2", &[&position, &queue_id])
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn remove_vehicle(client: &impl GenericClient, license_plate: &str) -> Result<u64, String> {
+    client
+        .execute(
+            "DELETE FROM vehicle_queue WHERE vehicle_id = (SELECT id FROM vehicles WHERE license_plate = $1)",
+            &[&license_plate],
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn sync_status(client: &impl GenericClient, queue_id: &str) -> Result<(), String> {
+    client.execute(SYNC_STATUS_SQL, &[&queue_id]).await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Put an entry last in its destination and sub-route and close the gap it leaves
+pub async fn send_to_back(client: &impl GenericClient, queue_id: &str) -> Result<(), String> {
+    client
+        .execute(
+            r#"UPDATE vehicle_queue q SET queue_position = r.position
+               FROM (SELECT o.id, ROW_NUMBER() OVER (ORDER BY o.id = $1, o.queue_position)::int AS position
+                     FROM vehicle_queue o JOIN vehicle_queue me ON me.id = $1
+                     WHERE o.destination_id = me.destination_id AND COALESCE(o.sub_route, '') = COALESCE(me.sub_route, '')) r
+               WHERE q.id = r.id AND q.queue_position <> r.position"#,
+            &[&queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Take `seats` of a queue entry for bookings moved onto it and bring its status in line
+pub async fn hold_seats(client: &impl GenericClient, queue_id: &str, seats: i32) -> Result<(), String> {
+    client
+        .execute(
+            "UPDATE vehicle_queue SET available_seats = available_seats - $1 WHERE id = $2",
+            &[&seats, &queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    sync_status(client, queue_id).await
+}
+
+// Give `seats` back to a queue entry and bring its status in line
+pub async fn release_seats(client: &impl GenericClient, queue_id: &str, seats: i32) -> Result<(), String> {
+    client
+        .execute(
+            "UPDATE vehicle_queue SET available_seats = available_seats + $1 WHERE id = $2",
+            &[&seats, &queue_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    sync_status(client, queue_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_edited_gone_or_new_since_the_read_are_changed() {
+        let read = HashMap::from([("a".to_string(), 3), ("b".to_string(), 1), ("c".to_string(), 7)]);
+        assert!(changed_entries(&read, &read).is_empty());
+        let now = HashMap::from([("a".to_string(), 4), ("c".to_string(), 7), ("d".to_string(), 0)]);
+        assert_eq!(changed_entries(&read, &now), vec!["a", "b", "d"]);
+    }
+}
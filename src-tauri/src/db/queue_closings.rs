@@ -0,0 +1,174 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio_postgres::Row;
+
+use super::{settings, GenericClient};
+use crate::maintenance::{ChangeAction, RowChange};
+use crate::queue_closing::{ClosingMode, QueueClosingPolicy};
+use crate::time::TunisTime;
+
+const POLICY_KEY: &str = "queue_closing";
+
+// Vehicles left WAITING at closing time: no seat booked, so nobody is left behind either way
+const WAITING_SQL: &str = r#"
+    SELECT q.id, q.vehicle_id, v.license_plate, q.destination_id, q.destination_name, q.sub_route_name,
+           q.queue_position, q.entered_at
+    FROM vehicle_queue q
+    JOIN vehicles v ON v.id = q.vehicle_id
+    WHERE q.status = 'WAITING' AND q.available_seats = q.total_seats AND q.entered_at < $1
+    ORDER BY q.destination_name, q.queue_position"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosingVehicleDto {
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub sub_route_name: Option<String>,
+    pub queue_position: i32,
+    pub entered_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueClosingDto {
+    pub service_date: NaiveDate,
+    pub mode: ClosingMode,
+    pub closed_at: String,
+    pub vehicles: Vec<ClosingVehicleDto>,
+}
+
+/// A waiting vehicle with the queue entry it holds
+pub struct WaitingEntry {
+    pub queue_id: String,
+    pub vehicle: ClosingVehicleDto,
+}
+
+fn map_vehicle_row(r: &Row) -> ClosingVehicleDto {
+    ClosingVehicleDto {
+        vehicle_id: r.get("vehicle_id"),
+        license_plate: r.get("license_plate"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        sub_route_name: r.get("sub_route_name"),
+        queue_position: r.get("queue_position"),
+        entered_at: r.get::<_, Option<TunisTime>>("entered_at").map(|t| t.fmt_dto()),
+    }
+}
+
+pub async fn policy(client: &impl GenericClient) -> Result<QueueClosingPolicy, String> {
+    Ok(QueueClosingPolicy::parse(settings::get(client, POLICY_KEY).await?.as_deref()))
+}
+
+pub async fn set_policy(client: &impl GenericClient, policy: &QueueClosingPolicy) -> Result<(), String> {
+    settings::set(client, POLICY_KEY, &serde_json::to_string(policy).map_err(|e| e.to_string())?).await
+}
+
+/// Vehicles that entered before `closes_at` and are still waiting, by destination and position
+pub async fn waiting_before(client: &impl GenericClient, closes_at: DateTime<Utc>) -> Result<Vec<WaitingEntry>, String> {
+    let rows = client.query(WAITING_SQL, &[&closes_at]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| WaitingEntry { queue_id: r.get("id"), vehicle: map_vehicle_row(r) }).collect())
+}
+
+pub async fn is_closed(client: &impl GenericClient, day: NaiveDate) -> Result<bool, String> {
+    let row = client
+        .query_opt("SELECT 1 FROM queue_closings WHERE service_date = $1", &[&day])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.is_some())
+}
+
+/// Take the closing of `day` for this terminal; false when another terminal already has it
+pub async fn claim(client: &impl GenericClient, day: NaiveDate, mode: ClosingMode) -> Result<bool, String> {
+    let inserted = client
+        .execute(
+            "INSERT INTO queue_closings (service_date, mode, terminal_id, closed_at) VALUES ($1, $2, $3, NOW())
+             ON CONFLICT (service_date) DO NOTHING",
+            &[&day, &mode.as_str(), &crate::terminal::id()],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(inserted == 1)
+}
+
+pub async fn record_vehicles(client: &impl GenericClient, day: NaiveDate, entries: &[WaitingEntry]) -> Result<Vec<RowChange>, String> {
+    let mut changes = Vec::new();
+    for entry in entries {
+        let v = &entry.vehicle;
+        let id = uuid::Uuid::new_v4().to_string();
+        client
+            .execute(
+                r#"INSERT INTO queue_closing_vehicles (id, service_date, vehicle_id, license_plate, destination_id, destination_name, sub_route_name, queue_position, entered_at)
+                   SELECT $1, $2, $3, $4, $5, $6, $7, $8, entered_at FROM vehicle_queue WHERE id = $9"#,
+                &[&id, &day, &v.vehicle_id, &v.license_plate, &v.destination_id, &v.destination_name, &v.sub_route_name, &v.queue_position, &entry.queue_id],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        changes.push(RowChange::new("queue_closing_vehicles", &id, &v.license_plate, ChangeAction::Insert, json!(null), json!(v)));
+    }
+    Ok(changes)
+}
+
+/// Take the entries out of the queue and close the gaps they leave, per destination and sub-route
+pub async fn purge(client: &impl GenericClient, entries: &[WaitingEntry]) -> Result<Vec<RowChange>, String> {
+    let ids: Vec<&str> = entries.iter().map(|e| e.queue_id.as_str()).collect();
+    client
+        .execute("DELETE FROM vehicle_queue WHERE id = ANY($1)", &[&ids])
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut changes: Vec<RowChange> = entries
+        .iter()
+        .map(|e| RowChange::new("vehicle_queue", &e.queue_id, &e.vehicle.license_plate, ChangeAction::Delete, json!(e.vehicle), json!(null)))
+        .collect();
+    let moved = client
+        .query(
+            r#"UPDATE vehicle_queue q SET queue_position = r.position
+               FROM (SELECT vq.id, vq.queue_position AS previous, v.license_plate,
+                            ROW_NUMBER() OVER (PARTITION BY vq.destination_id, COALESCE(vq.sub_route, '') ORDER BY vq.queue_position)::int AS position
+                     FROM vehicle_queue vq JOIN vehicles v ON v.id = vq.vehicle_id) r
+               WHERE q.id = r.id AND q.queue_position <> r.position
+               RETURNING q.id, r.license_plate, r.previous, q.queue_position"#,
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    changes.extend(moved.iter().map(|r| {
+        RowChange::new(
+            "vehicle_queue",
+            r.get("id"),
+            r.get("license_plate"),
+            ChangeAction::Update,
+            json!({ "queuePosition": r.get::<_, i32>("previous") }),
+            json!({ "queuePosition": r.get::<_, i32>("queue_position") }),
+        )
+    }));
+    Ok(changes)
+}
+
+/// The closing of `day` with the vehicles it purged or carried over
+pub async fn for_day(client: &impl GenericClient, day: NaiveDate) -> Result<Option<QueueClosingDto>, String> {
+    let Some(closing) = client
+        .query_opt("SELECT mode, closed_at FROM queue_closings WHERE service_date = $1", &[&day])
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    let rows = client
+        .query(
+            r#"SELECT vehicle_id, license_plate, destination_id, destination_name, sub_route_name, queue_position, entered_at
+               FROM queue_closing_vehicles WHERE service_date = $1
+               ORDER BY destination_name, queue_position"#,
+            &[&day],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(Some(QueueClosingDto {
+        service_date: day,
+        mode: ClosingMode::parse(&closing.get::<_, String>("mode")),
+        closed_at: closing.get::<_, TunisTime>("closed_at").fmt_dto(),
+        vehicles: rows.iter().map(map_vehicle_row).collect(),
+    }))
+}
@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::time::TunisTime;
+
+// The vehicle_queue journal kept by the trigger of migration 023, and the replay of a
+// destination's queue from it. Each event holds the whole entry after the change, so replaying
+// is keeping the last event of every entry; an entry whose last event deleted it is gone.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum QueueEventKind {
+    Entered,
+    Booked,
+    Cancelled,
+    Moved,
+    Updated,
+    Departed,
+    Removed,
+}
+
+impl QueueEventKind {
+    pub fn parse(kind: &str) -> Self {
+        match kind {
+            "ENTERED" => QueueEventKind::Entered,
+            "BOOKED" => QueueEventKind::Booked,
+            "CANCELLED" => QueueEventKind::Cancelled,
+            "MOVED" => QueueEventKind::Moved,
+            "DEPARTED" => QueueEventKind::Departed,
+            "REMOVED" => QueueEventKind::Removed,
+            _ => QueueEventKind::Updated,
+        }
+    }
+
+    pub fn leaves_queue(&self) -> bool {
+        matches!(self, QueueEventKind::Departed | QueueEventKind::Removed)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueEventDto {
+    pub seq: i64,
+    pub queue_id: String,
+    pub kind: QueueEventKind,
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub sub_route: Option<String>,
+    pub queue_position: i32,
+    pub status: String,
+    pub available_seats: i32,
+    pub total_seats: i32,
+    pub occurred_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueReplayDto {
+    pub destination_id: String,
+    pub at: String,
+    pub entries: Vec<QueueEventDto>, // the last event of each entry still queued, by position
+    pub events: Vec<QueueEventDto>,  // every event replayed, oldest first
+}
+
+fn map_event_row(r: &Row) -> QueueEventDto {
+    QueueEventDto {
+        seq: r.get("id"),
+        queue_id: r.get("queue_id"),
+        kind: QueueEventKind::parse(&r.get::<_, String>("kind")),
+        vehicle_id: r.get("vehicle_id"),
+        license_plate: r.get("license_plate"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        sub_route: r.get("sub_route"),
+        queue_position: r.get("queue_position"),
+        status: r.get("status"),
+        available_seats: r.get("available_seats"),
+        total_seats: r.get("total_seats"),
+        occurred_at: r.get::<_, TunisTime>("occurred_at").fmt_dto(),
+    }
+}
+
+/// Events up to `at` of every entry that was queued for the destination by then, oldest first.
+/// An entry moved away keeps its later events, so the replay knows it left.
+pub async fn up_to(client: &impl GenericClient, destination_id: &str, at: DateTime<Utc>) -> Result<Vec<QueueEventDto>, String> {
+    let rows = client
+        .query(
+            r#"SELECT e.id, e.queue_id, e.kind, e.vehicle_id, COALESCE(v.license_plate, '') AS license_plate,
+                      e.destination_id, COALESCE(e.destination_name, '') AS destination_name, e.sub_route,
+                      e.queue_position, e.status, e.available_seats, e.total_seats, e.occurred_at
+               FROM queue_events e
+               LEFT JOIN vehicles v ON v.id = e.vehicle_id
+               WHERE e.occurred_at <= $2
+                 AND e.queue_id IN (SELECT queue_id FROM queue_events WHERE destination_id = $1 AND occurred_at <= $2)
+               ORDER BY e.id"#,
+            &[&destination_id, &at],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_event_row).collect())
+}
+
+/// The queue of `destination_id` once `events` (oldest first) have all happened, by position
+pub fn replay(events: &[QueueEventDto], destination_id: &str) -> Vec<QueueEventDto> {
+    let mut latest: HashMap<&str, &QueueEventDto> = HashMap::new();
+    for event in events {
+        latest.insert(&event.queue_id, event);
+    }
+    let mut entries: Vec<QueueEventDto> = latest
+        .into_values()
+        .filter(|e| !e.kind.leaves_queue() && e.destination_id == destination_id)
+        .cloned()
+        .collect();
+    entries.sort_by(|a, b| (&a.sub_route, a.queue_position, a.seq).cmp(&(&b.sub_route, b.queue_position, b.seq)));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(seq: i64, queue_id: &str, kind: QueueEventKind, destination_id: &str, position: i32) -> QueueEventDto {
+        QueueEventDto {
+            seq,
+            queue_id: queue_id.to_string(),
+            kind,
+            vehicle_id: format!("v-{}", queue_id),
+            license_plate: String::new(),
+            destination_id: destination_id.to_string(),
+            destination_name: String::new(),
+            sub_route: None,
+            queue_position: position,
+            status: "WAITING".to_string(),
+            available_seats: 8,
+            total_seats: 8,
+            occurred_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn replay_keeps_the_last_state_of_each_entry_still_queued() {
+        use QueueEventKind::*;
+        let events = vec![
+            event(1, "a", Entered, "sousse", 1),
+            event(2, "b", Entered, "sousse", 2),
+            event(3, "c", Entered, "sousse", 3),
+            event(4, "a", Departed, "sousse", 1),
+            event(5, "b", Moved, "sousse", 1),
+            event(6, "c", Moved, "sousse", 2),
+            event(7, "d", Entered, "sousse", 3),
+            event(8, "d", Moved, "monastir", 1),
+        ];
+        let queue = replay(&events, "sousse");
+        assert_eq!(queue.iter().map(|e| (e.queue_id.as_str(), e.queue_position)).collect::<Vec<_>>(), vec![("b", 1), ("c", 2)]);
+        // Up to the departure, `a` still leads
+        assert_eq!(replay(&events[..3], "sousse")[0].queue_id, "a");
+        assert_eq!(replay(&events, "monastir")[0].queue_id, "d");
+    }
+}
@@ -0,0 +1,14 @@
+use super::{settings, GenericClient};
+use crate::quick_entry::QuickEntryPolicy;
+
+// The quick_entry setting (see quick_entry.rs)
+
+const POLICY_KEY: &str = "quick_entry";
+
+pub async fn policy(client: &impl GenericClient) -> Result<QuickEntryPolicy, String> {
+    Ok(QuickEntryPolicy::parse(settings::get(client, POLICY_KEY).await?.as_deref()))
+}
+
+pub async fn set_policy(client: &impl GenericClient, policy: &QuickEntryPolicy) -> Result<(), String> {
+    settings::set(client, POLICY_KEY, &serde_json::to_string(policy).map_err(|e| e.to_string())?).await
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::money::Money;
+use crate::overrides::AuthorizedOverride;
+use crate::refunds::{Refundable, RefundTarget};
+use crate::time::TunisTime;
+
+// The refund ledger (migration 041, see refunds.rs)
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundDto {
+    pub id: String,
+    pub booking_id: Option<String>,
+    pub day_pass_id: Option<String>,
+    pub reference: String,
+    pub amount: Money,
+    pub reason_code: String,
+    pub note: String,
+    pub supervisor_id: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: String,
+}
+
+fn map_refund_row(r: &Row) -> RefundDto {
+    RefundDto {
+        id: r.get("id"),
+        booking_id: r.get("booking_id"),
+        day_pass_id: r.get("day_pass_id"),
+        reference: r.get("reference"),
+        amount: r.get("amount"),
+        reason_code: r.get("reason_code"),
+        note: r.get("note"),
+        supervisor_id: r.get("supervisor_id"),
+        created_by: r.get("created_by"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+fn map_refundable_row(r: &Row) -> Refundable {
+    Refundable { reference: r.get("reference"), original: r.get("original"), refunded: r.get("refunded") }
+}
+
+/// What a booking was paid and gave back. A paid booking's cancelled seats are off its total and
+/// on refund_amount; a voided one keeps its total, so seats cancelled before the void are not
+/// counted as paid, which errs on refusing. `lock` holds the booking until the transaction ends.
+pub async fn booking(client: &impl GenericClient, booking_id: &str, lock: bool) -> Result<Option<Refundable>, String> {
+    let sql = format!(
+        r#"SELECT b.verification_code AS reference,
+                  b.total_amount + CASE WHEN b.payment_status::text = 'PAID' THEN COALESCE(b.refund_amount, 0) ELSE 0 END AS original,
+                  COALESCE(b.refund_amount, 0) + COALESCE(r.amount, 0) AS refunded
+           FROM bookings b
+           LEFT JOIN LATERAL (SELECT SUM(amount) AS amount FROM refunds WHERE booking_id = b.id) r ON true
+           WHERE b.id = $1{}"#,
+        if lock { " FOR UPDATE OF b" } else { "" }
+    );
+    let row = client.query_opt(sql.as_str(), &[&booking_id]).await.map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_refundable_row))
+}
+
+/// What a day pass was paid and gave back; a pass deactivated without a refund recorded was
+/// refunded in full before the ledger. `lock` holds the pass until the transaction ends.
+pub async fn day_pass(client: &impl GenericClient, day_pass_id: &str, lock: bool) -> Result<Option<Refundable>, String> {
+    let sql = format!(
+        r#"SELECT dp.id AS reference, dp.price AS original,
+                  CASE WHEN dp.is_active THEN COALESCE(r.amount, 0) ELSE GREATEST(dp.price, COALESCE(r.amount, 0)) END AS refunded
+           FROM day_passes dp
+           LEFT JOIN LATERAL (SELECT SUM(amount) AS amount FROM refunds WHERE day_pass_id = dp.id) r ON true
+           WHERE dp.id = $1{}"#,
+        if lock { " FOR UPDATE OF dp" } else { "" }
+    );
+    let row = client.query_opt(sql.as_str(), &[&day_pass_id]).await.map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_refundable_row))
+}
+
+/// Refunds recorded against a booking
+pub async fn recorded_for_booking(client: &impl GenericClient, booking_id: &str) -> Result<Money, String> {
+    let row = client
+        .query_one("SELECT COALESCE(SUM(amount), 0)::float8 AS amount FROM refunds WHERE booking_id = $1", &[&booking_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get("amount"))
+}
+
+pub async fn for_target(client: &impl GenericClient, target: RefundTarget, id: &str) -> Result<Vec<RefundDto>, String> {
+    let column = match target {
+        RefundTarget::Booking => "booking_id",
+        RefundTarget::DayPass => "day_pass_id",
+    };
+    let rows = client
+        .query(format!("SELECT * FROM refunds WHERE {} = $1 ORDER BY created_at", column).as_str(), &[&id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_refund_row).collect())
+}
+
+pub async fn insert(
+    client: &impl GenericClient,
+    target: RefundTarget,
+    id: &str,
+    reference: &str,
+    amount: Money,
+    authorized: &AuthorizedOverride,
+    created_by: &Option<String>,
+) -> Result<String, String> {
+    let refund_id = uuid::Uuid::new_v4().to_string();
+    let (booking_id, day_pass_id) = match target {
+        RefundTarget::Booking => (Some(id), None),
+        RefundTarget::DayPass => (None, Some(id)),
+    };
+    client
+        .execute(
+            r#"INSERT INTO refunds (id, booking_id, day_pass_id, reference, amount, reason_code, note, supervisor_id, created_by, terminal_id, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW())"#,
+            &[
+                &refund_id, &booking_id, &day_pass_id, &reference, &amount.to_tnd(), &authorized.reason.as_str(), &authorized.note,
+                &authorized.supervisor.id, created_by, &crate::terminal::id(),
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(refund_id)
+}
+
+pub async fn deactivate_day_pass(client: &impl GenericClient, day_pass_id: &str) -> Result<(), String> {
+    client
+        .execute("UPDATE day_passes SET is_active = false, updated_at = NOW() WHERE id = $1", &[&day_pass_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
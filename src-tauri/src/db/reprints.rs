@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::{settings, GenericClient};
+use crate::money::Money;
+use crate::reprints::ReprintPolicy;
+use crate::time::{self, TunisTime};
+
+const POLICY_KEY: &str = "reprint_policy";
+
+// A reprint of a ticket and the fee charged for it (see reprints.rs)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TicketReprintDto {
+    pub id: String,
+    pub ticket_kind: String,
+    pub ticket_ref: String,
+    pub reprint_number: i32,
+    pub fee: Money,
+    pub created_by: Option<String>,
+    pub staff_name: String,
+    pub created_at: String,
+}
+
+fn map_reprint_row(r: &Row) -> TicketReprintDto {
+    TicketReprintDto {
+        id: r.get("id"),
+        ticket_kind: r.get("ticket_kind"),
+        ticket_ref: r.get("ticket_ref"),
+        reprint_number: r.get("reprint_number"),
+        fee: r.get("fee"),
+        created_by: r.get("created_by"),
+        staff_name: r.get("staff_name"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+pub async fn policy(client: &impl GenericClient) -> Result<ReprintPolicy, String> {
+    Ok(ReprintPolicy::parse(settings::get(client, POLICY_KEY).await?.as_deref()))
+}
+
+pub async fn set_policy(client: &impl GenericClient, policy: &ReprintPolicy) -> Result<(), String> {
+    settings::set(client, POLICY_KEY, &serde_json::to_string(policy).map_err(|e| e.to_string())?).await
+}
+
+/// Reprints of one ticket so far; holds a lock on the ticket until the transaction ends, so two
+/// tills reprinting it at once get consecutive numbers
+pub async fn count_locked(client: &impl GenericClient, ticket_kind: &str, ticket_ref: &str) -> Result<u32, String> {
+    client
+        .execute("SELECT pg_advisory_xact_lock(hashtext($1 || ':' || $2))", &[&ticket_kind, &ticket_ref])
+        .await
+        .map_err(|e| e.to_string())?;
+    let row = client
+        .query_one(
+            "SELECT COUNT(*) AS count FROM ticket_reprints WHERE ticket_kind = $1 AND ticket_ref = $2",
+            &[&ticket_kind, &ticket_ref],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get::<_, i64>("count") as u32)
+}
+
+pub async fn record(client: &impl GenericClient, ticket_kind: &str, ticket_ref: &str, reprint_number: u32, fee: Money, created_by: &Option<String>) -> Result<(), String> {
+    client
+        .execute(
+            r#"INSERT INTO ticket_reprints (id, ticket_kind, ticket_ref, reprint_number, fee, created_by, terminal_id, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())"#,
+            &[
+                &uuid::Uuid::new_v4().to_string(), &ticket_kind, &ticket_ref, &(reprint_number as i32), &fee.to_tnd(), created_by, &crate::terminal::id(),
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Reprints of one Tunis day, oldest first
+pub async fn for_day(client: &impl GenericClient, day: chrono::NaiveDate) -> Result<Vec<TicketReprintDto>, String> {
+    let (day_start, day_end) = time::tunis_day_bounds(day);
+    let rows = client
+        .query(
+            r#"SELECT r.id, r.ticket_kind, r.ticket_ref, r.reprint_number, r.fee, r.created_by, r.created_at,
+                      COALESCE(st.first_name || ' ' || st.last_name, r.created_by, 'Staff') AS staff_name
+               FROM ticket_reprints r
+               LEFT JOIN staff st ON st.id = r.created_by
+               WHERE r.created_at >= $1 AND r.created_at < $2
+               ORDER BY r.created_at"#,
+            &[&day_start, &day_end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_reprint_row).collect())
+}
@@ -0,0 +1,259 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingReservationDto {
+    pub id: String,
+    pub label: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub seats: i32,
+    pub weekdays: Vec<i32>,
+    pub customer_id: Option<String>,
+    pub starts_on: String,
+    pub ends_on: Option<String>,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReservationOccurrenceDto {
+    pub id: String,
+    pub reservation_id: String,
+    pub label: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub customer_id: Option<String>,
+    pub service_date: String,
+    pub seats: i32,
+    pub status: String, // HELD, BOOKED, SKIPPED or CANCELLED
+}
+
+/// Sent by the frontend to create a standing reservation; dates are YYYY-MM-DD
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingReservationRequest {
+    pub label: String,
+    pub destination_id: String,
+    pub seats: i32,
+    pub weekdays: Vec<i32>,
+    pub customer_id: Option<String>,
+    pub starts_on: Option<String>,
+    pub ends_on: Option<String>,
+}
+
+pub struct NewReservation<'a> {
+    pub label: &'a str,
+    pub destination_id: &'a str,
+    pub seats: i32,
+    pub weekdays: &'a [i32],
+    pub customer_id: Option<&'a str>,
+    pub starts_on: NaiveDate,
+    pub ends_on: Option<NaiveDate>,
+    pub created_by: Option<&'a str>,
+}
+
+/// ISO weekdays (1 = Monday .. 7 = Sunday), sorted and without duplicates
+pub fn normalize_weekdays(weekdays: &[i32]) -> Result<Vec<i32>, String> {
+    if let Some(day) = weekdays.iter().find(|d| !(1..=7).contains(*d)) {
+        return Err(format!("Jour de semaine invalide: {} (1 = lundi .. 7 = dimanche)", day));
+    }
+    let mut days = weekdays.to_vec();
+    days.sort_unstable();
+    days.dedup();
+    if days.is_empty() {
+        return Err("Choisissez au moins un jour de la semaine".to_string());
+    }
+    Ok(days)
+}
+
+fn map_reservation_row(r: &Row) -> StandingReservationDto {
+    StandingReservationDto {
+        id: r.get("id"),
+        label: r.get("label"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        seats: r.get("seats"),
+        weekdays: r.get("weekdays"),
+        customer_id: r.get("customer_id"),
+        starts_on: r.get::<_, NaiveDate>("starts_on").to_string(),
+        ends_on: r.get::<_, Option<NaiveDate>>("ends_on").map(|d| d.to_string()),
+        is_active: r.get("is_active"),
+    }
+}
+
+fn map_occurrence_row(r: &Row) -> ReservationOccurrenceDto {
+    ReservationOccurrenceDto {
+        id: r.get("id"),
+        reservation_id: r.get("reservation_id"),
+        label: r.get("label"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        customer_id: r.get("customer_id"),
+        service_date: r.get::<_, NaiveDate>("service_date").to_string(),
+        seats: r.get("seats"),
+        status: r.get("status"),
+    }
+}
+
+const OCCURRENCE_COLUMNS: &str = "o.id, o.reservation_id, s.label, s.destination_id, s.destination_name, s.customer_id, o.service_date, o.seats, o.status";
+
+pub async fn insert(client: &impl GenericClient, new: &NewReservation<'_>) -> Result<StandingReservationDto, String> {
+    let weekdays = normalize_weekdays(new.weekdays)?;
+    let destination_name: String = client
+        .query_opt("SELECT station_name FROM routes WHERE station_id = $1", &[&new.destination_id])
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|r| r.get("station_name"))
+        .ok_or_else(|| "Destination introuvable".to_string())?;
+    let row = client
+        .query_one(
+            r#"INSERT INTO standing_reservations
+                   (id, label, destination_id, destination_name, seats, weekdays, customer_id, starts_on, ends_on, is_active, created_by, created_at, updated_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, true, $10, NOW(), NOW())
+               RETURNING *"#,
+            &[
+                &uuid::Uuid::new_v4().to_string(),
+                &new.label,
+                &new.destination_id,
+                &destination_name,
+                &new.seats,
+                &weekdays,
+                &new.customer_id,
+                &new.starts_on,
+                &new.ends_on,
+                &new.created_by,
+            ],
+        )
+        .await
+        .map_err(|e| format!("Erreur lors de la création de la réservation: {}", e))?;
+    Ok(map_reservation_row(&row))
+}
+
+pub async fn list(client: &impl GenericClient) -> Result<Vec<StandingReservationDto>, String> {
+    let rows = client
+        .query("SELECT * FROM standing_reservations ORDER BY is_active DESC, destination_name, label", &[])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_reservation_row).collect())
+}
+
+/// Stop the series; today's and later holds that were not booked yet are cancelled with it
+pub async fn deactivate(client: &impl GenericClient, reservation_id: &str, day: NaiveDate, updated_by: Option<&str>) -> Result<u64, String> {
+    let updated = client
+        .execute("UPDATE standing_reservations SET is_active = false, updated_at = NOW() WHERE id = $1", &[&reservation_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    client
+        .execute(
+            "UPDATE reservation_occurrences SET status = 'CANCELLED', updated_by = $3, updated_at = NOW()
+             WHERE reservation_id = $1 AND service_date >= $2 AND status = 'HELD'",
+            &[&reservation_id, &day, &updated_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(updated)
+}
+
+/// Create the HELD occurrences due on `day`. Safe to call repeatedly: an occurrence that
+/// already exists, including one skipped ahead of time, is left alone.
+pub async fn materialize(client: &impl GenericClient, day: NaiveDate) -> Result<u64, String> {
+    let due = client
+        .query(
+            r#"SELECT id, seats FROM standing_reservations
+               WHERE is_active = true
+                 AND starts_on <= $1 AND (ends_on IS NULL OR ends_on >= $1)
+                 AND EXTRACT(ISODOW FROM $1::date)::int = ANY(weekdays)"#,
+            &[&day],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut created = 0;
+    for r in due {
+        let reservation_id: String = r.get("id");
+        let seats: i32 = r.get("seats");
+        created += client
+            .execute(
+                "INSERT INTO reservation_occurrences (id, reservation_id, service_date, seats, status, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, 'HELD', NOW(), NOW())
+                 ON CONFLICT (reservation_id, service_date) DO NOTHING",
+                &[&uuid::Uuid::new_v4().to_string(), &reservation_id, &day, &seats],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(created)
+}
+
+pub async fn occurrences_for_day(client: &impl GenericClient, day: NaiveDate) -> Result<Vec<ReservationOccurrenceDto>, String> {
+    let sql = format!(
+        "SELECT {} FROM reservation_occurrences o JOIN standing_reservations s ON s.id = o.reservation_id
+         WHERE o.service_date = $1 ORDER BY s.destination_name, s.label",
+        OCCURRENCE_COLUMNS
+    );
+    let rows = client.query(sql.as_str(), &[&day]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_occurrence_row).collect())
+}
+
+pub async fn find_occurrence(client: &impl GenericClient, occurrence_id: &str) -> Result<Option<ReservationOccurrenceDto>, String> {
+    let sql = format!(
+        "SELECT {} FROM reservation_occurrences o JOIN standing_reservations s ON s.id = o.reservation_id WHERE o.id = $1",
+        OCCURRENCE_COLUMNS
+    );
+    let row = client.query_opt(sql.as_str(), &[&occurrence_id]).await.map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_occurrence_row))
+}
+
+/// Skip one day of the series, ahead of time or on the day while it is still held
+pub async fn skip(client: &impl GenericClient, reservation_id: &str, day: NaiveDate, updated_by: Option<&str>) -> Result<(), String> {
+    let seats: i32 = client
+        .query_opt("SELECT seats FROM standing_reservations WHERE id = $1", &[&reservation_id])
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|r| r.get("seats"))
+        .ok_or_else(|| "Réservation introuvable".to_string())?;
+    let skipped = client
+        .execute(
+            r#"INSERT INTO reservation_occurrences (id, reservation_id, service_date, seats, status, updated_by, created_at, updated_at)
+               VALUES ($1, $2, $3, $4, 'SKIPPED', $5, NOW(), NOW())
+               ON CONFLICT (reservation_id, service_date) DO UPDATE
+                   SET status = 'SKIPPED', updated_by = EXCLUDED.updated_by, updated_at = NOW()
+                   WHERE reservation_occurrences.status = 'HELD'"#,
+            &[&uuid::Uuid::new_v4().to_string(), &reservation_id, &day, &seats, &updated_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    if skipped == 0 {
+        return Err("Cette date est déjà réservée, sautée ou annulée".to_string());
+    }
+    Ok(())
+}
+
+/// Move a HELD occurrence to `status`; false when it was not held any more
+pub async fn set_status(client: &impl GenericClient, occurrence_id: &str, status: &str, updated_by: Option<&str>) -> Result<bool, String> {
+    let updated = client
+        .execute(
+            "UPDATE reservation_occurrences SET status = $2, updated_by = $3, updated_at = NOW() WHERE id = $1 AND status = 'HELD'",
+            &[&occurrence_id, &status, &updated_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(updated > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weekdays_are_iso_sorted_and_deduplicated() {
+        assert_eq!(normalize_weekdays(&[5, 1, 3, 1]), Ok(vec![1, 3, 5]));
+        assert!(normalize_weekdays(&[]).is_err());
+        assert!(normalize_weekdays(&[0, 2]).is_err());
+        assert!(normalize_weekdays(&[8]).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use super::{settings, GenericClient};
+use crate::retention::{RetentionPolicy, RetentionTable};
+
+const POLICY_KEY: &str = "retention_policy";
+const LAST_RUN_KEY: &str = "retention_last_run";
+// Serializes the nightly cleanup of terminals waking at the same time
+const RETENTION_LOCK_KEY: i64 = 0x52455445; // "RETE"
+
+pub async fn policy(client: &impl GenericClient) -> Result<RetentionPolicy, String> {
+    Ok(RetentionPolicy::parse(settings::get(client, POLICY_KEY).await?.as_deref()))
+}
+
+pub async fn set_policy(client: &impl GenericClient, policy: &RetentionPolicy) -> Result<(), String> {
+    settings::set(client, POLICY_KEY, &serde_json::to_string(policy).map_err(|e| e.to_string())?).await
+}
+
+/// Claim `day`'s nightly cleanup; false when a terminal already ran it. Holds a lock until the
+/// transaction ends, so call it in the cleanup's transaction.
+pub async fn claim_day(client: &impl GenericClient, day: NaiveDate) -> Result<bool, String> {
+    client
+        .execute("SELECT pg_advisory_xact_lock($1)", &[&RETENTION_LOCK_KEY])
+        .await
+        .map_err(|e| e.to_string())?;
+    let last = settings::get(client, LAST_RUN_KEY).await?;
+    if last.as_deref() == Some(day.to_string().as_str()) {
+        return Ok(false);
+    }
+    settings::set(client, LAST_RUN_KEY, &day.to_string()).await?;
+    Ok(true)
+}
+
+/// Rows of the table older than `cutoff`, less the ones it always keeps
+pub async fn count_before(client: &impl GenericClient, table: RetentionTable, cutoff: DateTime<Utc>) -> Result<u64, String> {
+    let row = client
+        .query_one(&format!("SELECT COUNT(*) AS rows FROM {} WHERE {} < $1 AND NOT ({})", table.as_str(), table.column(), table.kept()), &[&cutoff])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get::<_, i64>("rows") as u64)
+}
+
+/// Delete the rows of the table older than `cutoff`, less the ones it always keeps; how many went
+pub async fn delete_before(client: &impl GenericClient, table: RetentionTable, cutoff: DateTime<Utc>) -> Result<u64, String> {
+    client
+        .execute(&format!("DELETE FROM {} WHERE {} < $1 AND NOT ({})", table.as_str(), table.column(), table.kept()), &[&cutoff])
+        .await
+        .map_err(|e| e.to_string())
+}
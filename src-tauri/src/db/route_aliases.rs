@@ -0,0 +1,77 @@
+use super::GenericClient;
+use crate::money::Money;
+
+// Other names cashiers type for a route ("sfax" for "Station Sfax Ville"), searched along with
+// its French and Arabic names by destination_search.rs (migration 039).
+
+pub struct RouteNames {
+    pub station_id: String,
+    pub station_name: String,
+    pub station_name_ar: Option<String>,
+    pub governorate: Option<String>,
+    pub delegation: Option<String>,
+    pub base_price: Money,
+    pub aliases: Vec<String>,
+}
+
+/// Every active route with its aliases
+pub async fn active_routes(client: &impl GenericClient) -> Result<Vec<RouteNames>, String> {
+    let rows = client
+        .query(
+            r#"SELECT r.station_id, r.station_name, r.station_name_ar, r.governorate, r.delegation, r.base_price,
+                      COALESCE(ARRAY_AGG(a.alias ORDER BY a.alias) FILTER (WHERE a.alias IS NOT NULL), '{}') AS aliases
+               FROM routes r
+               LEFT JOIN route_aliases a ON a.station_id = r.station_id
+               WHERE r.is_active = true
+               GROUP BY r.station_id, r.station_name, r.station_name_ar, r.governorate, r.delegation, r.base_price"#,
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| RouteNames {
+            station_id: r.get("station_id"),
+            station_name: r.get("station_name"),
+            station_name_ar: r.get("station_name_ar"),
+            governorate: r.get("governorate"),
+            delegation: r.get("delegation"),
+            base_price: r.get("base_price"),
+            aliases: r.get("aliases"),
+        })
+        .collect())
+}
+
+pub async fn aliases(client: &impl GenericClient, station_id: &str) -> Result<Vec<String>, String> {
+    let rows = client
+        .query("SELECT alias FROM route_aliases WHERE station_id = $1 ORDER BY alias", &[&station_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| r.get(0)).collect())
+}
+
+pub async fn route_exists(client: &impl GenericClient, station_id: &str) -> Result<bool, String> {
+    let row = client
+        .query_one("SELECT EXISTS (SELECT 1 FROM routes WHERE station_id = $1)", &[&station_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get(0))
+}
+
+/// Replace the aliases of a route; run it in a transaction
+pub async fn set_aliases(client: &impl GenericClient, station_id: &str, aliases: &[String], created_by: &Option<String>) -> Result<(), String> {
+    client
+        .execute("DELETE FROM route_aliases WHERE station_id = $1", &[&station_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    for alias in aliases {
+        client
+            .execute(
+                "INSERT INTO route_aliases (station_id, alias, created_by) VALUES ($1, $2, $3)",
+                &[&station_id, alias, created_by],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::money::Money;
+use crate::time::TunisTime;
+
+// One search box for phone inquiries ("a customer lost ticket ABC-123"): bookings, exit passes
+// and day passes matched by verification code, plate, pass id or the cashier's name. Codes,
+// plates and ids are compared on their letters and digits only, so "abc 123" finds ABC-123.
+
+pub const MIN_QUERY_CHARS: usize = 2;
+const MAX_HITS_PER_KIND: i64 = 50;
+
+// Letters and digits of an identifier, upper-cased; norm_sql does the same in the database
+pub fn normalize(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_uppercase()).collect()
+}
+
+fn norm_sql(column: &str) -> String {
+    format!("UPPER(regexp_replace({}, '[^[:alnum:]]', '', 'g'))", column)
+}
+
+pub fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SearchKind {
+    Booking,
+    ExitPass,
+    DayPass,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchedField {
+    VerificationCode,
+    LicensePlate,
+    Id,
+    StaffName,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHitDto {
+    pub kind: SearchKind,
+    pub id: String,
+    pub reference: String, // verification code of a booking, id of a pass
+    pub license_plate: String,
+    pub destination_name: String,
+    pub seats: Option<i32>,
+    pub amount: Money,
+    /// Given back by cancellations, no-shows and refunds (refunds.rs)
+    pub refunded: Money,
+    pub status: String,
+    pub staff_name: String,
+    pub at: String,
+    pub matched: MatchedField,
+}
+
+/// Which field of the hit the query matched, the most specific first
+pub fn matched_field(query: &str, reference: &str, license_plate: &str, id: &str) -> MatchedField {
+    let needle = normalize(query);
+    if normalize(reference).contains(&needle) {
+        MatchedField::VerificationCode
+    } else if normalize(license_plate).contains(&needle) {
+        MatchedField::LicensePlate
+    } else if normalize(id).contains(&needle) {
+        MatchedField::Id
+    } else {
+        MatchedField::StaffName
+    }
+}
+
+fn map_hit(kind: SearchKind, query: &str, r: &Row) -> SearchHitDto {
+    let id: String = r.get("id");
+    let reference: String = r.get("reference");
+    let license_plate: String = r.get("license_plate");
+    let matched = match kind {
+        SearchKind::Booking => matched_field(query, &reference, &license_plate, &id),
+        // A pass's reference is its id
+        _ => matched_field(query, "", &license_plate, &id),
+    };
+    SearchHitDto {
+        kind,
+        id,
+        reference,
+        license_plate,
+        destination_name: r.get("destination_name"),
+        seats: r.get("seats"),
+        amount: r.get("amount"),
+        refunded: r.get("refunded"),
+        status: r.get("status"),
+        staff_name: r.get("staff_name"),
+        at: r.get::<_, TunisTime>("at").fmt_dto(),
+        matched,
+    }
+}
+
+fn booking_sql() -> String {
+    format!(
+        r#"SELECT * FROM (
+               SELECT b.id, b.verification_code AS reference,
+                      COALESCE(v.license_plate, ep.license_plate, '') AS license_plate,
+                      COALESCE(vq.destination_name, ep.destination_name, '') AS destination_name,
+                      b.seats_booked AS seats, b.total_amount AS amount,
+                      COALESCE(b.refund_amount, 0) + COALESCE(rf.amount, 0) AS refunded,
+                      CASE WHEN ns.booking_id IS NULL THEN b.payment_status::text ELSE 'NO_SHOW' END AS status,
+                      COALESCE(st.first_name || ' ' || st.last_name, b.created_by, '') AS staff_name,
+                      b.created_at AS at
+               FROM bookings b
+               LEFT JOIN vehicle_queue vq ON vq.id = b.queue_id
+               LEFT JOIN vehicles v ON v.id = vq.vehicle_id
+               LEFT JOIN LATERAL (
+                   SELECT destination_name, license_plate FROM exit_passes WHERE queue_id = b.queue_id LIMIT 1
+               ) ep ON true
+               LEFT JOIN booking_no_shows ns ON ns.booking_id = b.id
+               LEFT JOIN LATERAL (SELECT SUM(amount) AS amount FROM refunds WHERE booking_id = b.id) rf ON true
+               LEFT JOIN staff st ON st.id = b.created_by
+               WHERE ($3::timestamptz IS NULL OR b.created_at >= $3) AND b.created_at < $4
+           ) hit
+           WHERE {} LIKE $1 OR {} LIKE $1 OR {} LIKE $1 OR staff_name ILIKE $2
+           ORDER BY at DESC LIMIT $5"#,
+        norm_sql("reference"),
+        norm_sql("license_plate"),
+        norm_sql("id"),
+    )
+}
+
+fn exit_pass_sql() -> String {
+    format!(
+        r#"SELECT ep.id, ep.id AS reference, ep.license_plate, ep.destination_name, ep.seats_sold AS seats,
+                  ep.amount_collected AS amount, 0::float8 AS refunded, 'EXITED' AS status,
+                  COALESCE(st.first_name || ' ' || st.last_name, ep.created_by, '') AS staff_name,
+                  ep.current_exit_time AS at
+           FROM exit_passes ep
+           LEFT JOIN staff st ON st.id = ep.created_by
+           WHERE ($3::timestamptz IS NULL OR ep.current_exit_time >= $3) AND ep.current_exit_time < $4
+             AND ({} LIKE $1 OR {} LIKE $1 OR (st.first_name || ' ' || st.last_name) ILIKE $2)
+           ORDER BY at DESC LIMIT $5"#,
+        norm_sql("ep.license_plate"),
+        norm_sql("ep.id"),
+    )
+}
+
+fn day_pass_sql() -> String {
+    format!(
+        r#"SELECT dp.id, dp.id AS reference, dp.license_plate, '' AS destination_name, NULL::int AS seats,
+                  dp.price AS amount,
+                  CASE WHEN dp.is_active THEN COALESCE(rf.amount, 0) ELSE GREATEST(dp.price, COALESCE(rf.amount, 0)) END AS refunded,
+                  CASE WHEN dp.is_active THEN COALESCE(dp.pass_type::text, 'DAY') ELSE 'INACTIVE' END AS status,
+                  COALESCE(st.first_name || ' ' || st.last_name, dp.created_by, '') AS staff_name,
+                  dp.purchase_date AS at
+           FROM day_passes dp
+           LEFT JOIN LATERAL (SELECT SUM(amount) AS amount FROM refunds WHERE day_pass_id = dp.id) rf ON true
+           LEFT JOIN staff st ON st.id = dp.created_by
+           WHERE ($3::timestamptz IS NULL OR dp.purchase_date >= $3) AND dp.purchase_date < $4
+             AND ({} LIKE $1 OR {} LIKE $1 OR (st.first_name || ' ' || st.last_name) ILIKE $2)
+           ORDER BY at DESC LIMIT $5"#,
+        norm_sql("dp.license_plate"),
+        norm_sql("dp.id"),
+    )
+}
+
+/// Everything matching `query` created from `since` (all history when None) up to `until`,
+/// newest first, at most MAX_HITS_PER_KIND of each kind
+pub async fn search(client: &impl GenericClient, query: &str, since: Option<DateTime<Utc>>, until: DateTime<Utc>) -> Result<Vec<SearchHitDto>, String> {
+    let identifier = format!("%{}%", escape_like(&normalize(query)));
+    let name = format!("%{}%", escape_like(query.trim()));
+    let mut hits = Vec::new();
+    for (kind, sql) in [(SearchKind::Booking, booking_sql()), (SearchKind::ExitPass, exit_pass_sql()), (SearchKind::DayPass, day_pass_sql())] {
+        let rows = client
+            .query(sql.as_str(), &[&identifier, &name, &since, &until, &MAX_HITS_PER_KIND])
+            .await
+            .map_err(|e| e.to_string())?;
+        hits.extend(rows.iter().map(|r| map_hit(kind, query, r)));
+    }
+    // fmt_dto strings sort like the times they carry
+    hits.sort_by(|a, b| b.at.cmp(&a.at));
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_match_on_letters_and_digits_only() {
+        assert_eq!(normalize(" abc-123 "), "ABC123");
+        assert_eq!(normalize("123 تونس 4567"), "123تونس4567");
+        assert_eq!(matched_field("abc 123", "ABC-123", "123 TUN 4567", "b-1"), MatchedField::VerificationCode);
+        assert_eq!(matched_field("tun4567", "ABC-123", "123 TUN 4567", "b-1"), MatchedField::LicensePlate);
+        assert_eq!(matched_field("Sami", "ABC-123", "123 TUN 4567", "b-1"), MatchedField::StaffName);
+        assert_eq!(escape_like("50%_off"), "50\\%\\_off");
+    }
+}
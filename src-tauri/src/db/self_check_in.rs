@@ -0,0 +1,37 @@
+use super::{settings, GenericClient};
+use crate::self_check_in::SelfCheckInPolicy;
+
+// Kiosk self check-in: its setting and the drivers' PINs (migration 036, see self_check_in.rs)
+
+const POLICY_KEY: &str = "self_check_in_policy";
+
+pub async fn policy(client: &impl GenericClient) -> Result<SelfCheckInPolicy, String> {
+    Ok(SelfCheckInPolicy::parse(settings::get(client, POLICY_KEY).await?.as_deref()))
+}
+
+pub async fn set_policy(client: &impl GenericClient, policy: &SelfCheckInPolicy) -> Result<(), String> {
+    settings::set(client, POLICY_KEY, &serde_json::to_string(policy).map_err(|e| e.to_string())?).await
+}
+
+/// The driver PIN hash of an active vehicle, None when the plate is unknown or has no PIN
+pub async fn driver_pin_hash(client: &impl GenericClient, license_plate: &str) -> Result<Option<String>, String> {
+    let row = client
+        .query_opt(
+            "SELECT driver_pin_hash FROM vehicles WHERE license_plate = $1 AND is_active = true",
+            &[&license_plate],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.and_then(|r| r.get("driver_pin_hash")))
+}
+
+/// Set or clear (None) the vehicle's driver PIN hash; the number of vehicles updated
+pub async fn set_driver_pin_hash(client: &impl GenericClient, license_plate: &str, pin_hash: Option<&str>) -> Result<u64, String> {
+    client
+        .execute(
+            "UPDATE vehicles SET driver_pin_hash = $1, updated_at = NOW() WHERE license_plate = $2",
+            &[&pin_hash, &license_plate],
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,23 @@
+use super::GenericClient;
+
+// Key/value settings in station_settings, shared by every terminal of the station
+
+pub async fn get(client: &impl GenericClient, key: &str) -> Result<Option<String>, String> {
+    let row = client
+        .query_opt("SELECT value FROM station_settings WHERE key = $1", &[&key])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("value")))
+}
+
+pub async fn set(client: &impl GenericClient, key: &str, value: &str) -> Result<(), String> {
+    client
+        .execute(
+            "INSERT INTO station_settings (key, value, updated_at) VALUES ($1, $2, NOW())
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()",
+            &[&key, &value],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::passes::NewExitPass;
+use super::GenericClient;
+use crate::settlement::Settlement;
+use crate::time::TunisTime;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DriverSettlementDto {
+    pub id: String,
+    /// None once the exit pass was purged (retention.rs)
+    pub exit_pass_id: Option<String>,
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub destination_name: String,
+    #[serde(flatten)]
+    pub settlement: Settlement,
+    pub created_by: Option<String>,
+    pub created_at: String,
+}
+
+fn map_settlement_row(r: &Row) -> DriverSettlementDto {
+    DriverSettlementDto {
+        id: r.get("id"),
+        exit_pass_id: r.get("exit_pass_id"),
+        vehicle_id: r.get("vehicle_id"),
+        license_plate: r.get("license_plate"),
+        destination_name: r.get("destination_name"),
+        settlement: Settlement {
+            seats_sold: r.get("seats_sold"),
+            gross: r.get("gross"),
+            commission: r.get("commission"),
+            day_pass_fee: r.get("day_pass_fee"),
+            net_to_driver: r.get("net_to_driver"),
+        },
+        created_by: r.get("created_by"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+// Settlement row of the exit pass `exit_pass_id`, written with the pass
+pub async fn record(client: &impl GenericClient, exit_pass_id: &str, pass: &NewExitPass<'_>) -> Result<(), String> {
+    let s = pass.settlement;
+    client
+        .execute(
+            r#"INSERT INTO driver_settlements (
+                    id, exit_pass_id, vehicle_id, license_plate, destination_name,
+                    seats_sold, gross, commission, day_pass_fee, net_to_driver, created_by, created_at
+                ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,NOW())"#,
+            &[
+                &uuid::Uuid::new_v4().to_string(), &exit_pass_id, &pass.vehicle_id, &pass.license_plate, &pass.destination_name,
+                &s.seats_sold, &s.gross.to_tnd(), &s.commission.to_tnd(), &s.day_pass_fee.to_tnd(), &s.net_to_driver.to_tnd(), &pass.created_by,
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Settlements of one vehicle between `from` (inclusive) and `to` (exclusive), oldest first
+pub async fn for_vehicle(client: &impl GenericClient, vehicle_id: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<DriverSettlementDto>, String> {
+    let rows = client
+        .query(
+            r#"SELECT id, exit_pass_id, vehicle_id, license_plate, destination_name,
+                      seats_sold, gross, commission, day_pass_fee, net_to_driver, created_by, created_at
+               FROM driver_settlements
+               WHERE vehicle_id = $1 AND created_at >= $2 AND created_at < $3
+               ORDER BY created_at"#,
+            &[&vehicle_id, &from, &to],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_settlement_row).collect())
+}
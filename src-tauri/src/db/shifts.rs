@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use super::expenses::{self, ExpenseDto};
+use super::payments::{self, PaymentTotalDto};
+use super::voided_tickets::{self, VoidedTicketDto};
+use super::GenericClient;
+use crate::money::Money;
+use crate::payments::PaymentMethod;
+use crate::time;
+
+// End-of-shift reconciliation for one cashier and one Tunis day: what their drawer should
+// hold. Cash comes in from bookings, day passes and customer account settlements; expenses
+// paid out of the till and the refunds handed back at the counter (the refunds ledger) come
+// off it. Card, e-wallet and on-account takings are listed but not
+// part of the expected cash.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShiftReportDto {
+    pub staff_id: String,
+    pub date: String,
+    pub payments: Vec<PaymentTotalDto>,
+    pub cash_bookings: Money,
+    pub cash_day_passes: Money,
+    pub customer_payments: Money,
+    /// Refunds the cashier handed back that day, partial ones included
+    pub cash_refunds: Money,
+    pub expenses: Vec<ExpenseDto>,
+    pub total_expenses: Money,
+    pub expected_cash: Money,
+    /// Ticket numbers the cashier voided that day, for the fiscal audit
+    pub voided_tickets: Vec<VoidedTicketDto>,
+}
+
+pub async fn report(client: &impl GenericClient, staff_id: &str, day: chrono::NaiveDate) -> Result<ShiftReportDto, String> {
+    let (day_start, day_end) = time::tunis_day_bounds(day);
+    let payments = payments::booking_totals_for_day(client, day, None, Some(staff_id)).await?;
+    let cash_bookings: Money = payments
+        .iter()
+        .filter(|p| p.method == PaymentMethod::Cash.as_str())
+        .map(|p| p.amount)
+        .sum();
+
+    // A pass refunded through the ledger counts as sold, its refund comes off below; a pass
+    // deactivated with no refund recorded was given back in full before the ledger
+    let row = client
+        .query_one(
+            r#"SELECT
+                   (SELECT COALESCE(SUM(price), 0)::float8 FROM day_passes dp
+                    WHERE created_by = $1 AND tunis_date = $2 AND payment_method = 'CASH'
+                      AND (is_active OR EXISTS (SELECT 1 FROM refunds r WHERE r.day_pass_id = dp.id))) AS cash_day_passes,
+                   (SELECT COALESCE(SUM(amount), 0)::float8 FROM customer_payments
+                    WHERE created_by = $1 AND created_at >= $3 AND created_at < $4) AS customer_payments,
+                   (SELECT COALESCE(SUM(amount), 0)::float8 FROM refunds
+                    WHERE created_by = $1 AND created_at >= $3 AND created_at < $4) AS cash_refunds"#,
+            &[&staff_id, &day, &day_start, &day_end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let cash_day_passes: Money = row.get("cash_day_passes");
+    let customer_payments: Money = row.get("customer_payments");
+    let cash_refunds: Money = row.get("cash_refunds");
+
+    let expenses = expenses::for_day(client, day, Some(staff_id)).await?;
+    let total_expenses = expenses::total(&expenses);
+    let voided_tickets = voided_tickets::for_staff_day(client, staff_id, day).await?;
+
+    Ok(ShiftReportDto {
+        staff_id: staff_id.to_string(),
+        date: day.format("%Y-%m-%d").to_string(),
+        payments,
+        cash_bookings,
+        cash_day_passes,
+        customer_payments,
+        cash_refunds,
+        expenses,
+        total_expenses,
+        expected_cash: cash_bookings + cash_day_passes + customer_payments - cash_refunds - total_expenses,
+        voided_tickets,
+    })
+}
@@ -0,0 +1,23 @@
+use super::GenericClient;
+
+/// First and last name of a staff member
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaffName {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+impl StaffName {
+    /// "First Last", as printed on tickets
+    pub fn full(&self) -> String {
+        format!("{} {}", self.first_name, self.last_name)
+    }
+}
+
+pub async fn name(client: &impl GenericClient, staff_id: &str) -> Result<Option<StaffName>, String> {
+    let row = client
+        .query_opt("SELECT first_name, last_name FROM staff WHERE id = $1", &[&staff_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| StaffName { first_name: r.get("first_name"), last_name: r.get("last_name") }))
+}
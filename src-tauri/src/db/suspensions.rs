@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::i18n;
+use crate::time::TunisTime;
+
+// Suspended routes (migration 026). A suspension is active until it is lifted or its `until`
+// passes, so lifting at the given time needs nothing to run.
+
+const ACTIVE: &str = "s.lifted_at IS NULL AND (s.until IS NULL OR s.until > NOW())";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SuspensionDto {
+    pub id: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub reason: String,
+    pub suspended_at: String,
+    pub until: Option<String>, // None: until a supervisor lifts it
+    pub suspended_by_name: Option<String>,
+}
+
+fn map_suspension_row(r: &Row) -> SuspensionDto {
+    SuspensionDto {
+        id: r.get("id"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        reason: r.get("reason"),
+        suspended_at: r.get::<_, TunisTime>("suspended_at").fmt_dto(),
+        until: r.get::<_, Option<TunisTime>>("until").map(|t| t.fmt_dto()),
+        suspended_by_name: r.get("suspended_by_name"),
+    }
+}
+
+fn select_sql(filter: &str) -> String {
+    format!(
+        r#"SELECT s.id, s.destination_id, s.destination_name, s.reason, s.suspended_at, s.until,
+                  st.first_name || ' ' || st.last_name AS suspended_by_name
+           FROM destination_suspensions s
+           LEFT JOIN staff st ON st.id = s.suspended_by
+           WHERE {} {}
+           ORDER BY s.destination_name"#,
+        ACTIVE, filter
+    )
+}
+
+/// The end of a suspension must be ahead of `now`
+pub fn validate_until(until: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Result<(), String> {
+    match until {
+        Some(until) if until <= now => Err(i18n::t("error.suspension_until_past")),
+        _ => Ok(()),
+    }
+}
+
+pub async fn active(client: &impl GenericClient) -> Result<Vec<SuspensionDto>, String> {
+    let rows = client.query(select_sql("").as_str(), &[]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_suspension_row).collect())
+}
+
+/// Active suspensions by destination id
+pub async fn active_by_destination(client: &impl GenericClient) -> Result<HashMap<String, SuspensionDto>, String> {
+    Ok(active(client).await?.into_iter().map(|s| (s.destination_id.clone(), s)).collect())
+}
+
+pub async fn active_for(client: &impl GenericClient, destination_id: &str) -> Result<Option<SuspensionDto>, String> {
+    let row = client
+        .query_opt(select_sql("AND s.destination_id = $1").as_str(), &[&destination_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_suspension_row))
+}
+
+/// Refuse with the reason when the destination is suspended; for queue entries and bookings
+pub async fn ensure_open(client: &impl GenericClient, destination_id: &str) -> Result<(), String> {
+    let Some(suspension) = active_for(client, destination_id).await? else {
+        return Ok(());
+    };
+    Err(match &suspension.until {
+        Some(until) => i18n::tf(
+            "error.destination_suspended_until",
+            &[("destination", &suspension.destination_name), ("reason", &suspension.reason), ("until", &until.get(11..16).unwrap_or(until))],
+        ),
+        None => i18n::tf("error.destination_suspended", &[("destination", &suspension.destination_name), ("reason", &suspension.reason)]),
+    })
+}
+
+/// Suspend the destination, replacing a suspension already active
+pub async fn suspend(client: &impl GenericClient, destination_id: &str, destination_name: &str, reason: &str, until: Option<DateTime<Utc>>, staff_id: &Option<String>) -> Result<String, String> {
+    lift(client, destination_id, staff_id).await?;
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO destination_suspensions (id, destination_id, destination_name, reason, suspended_by, until) VALUES ($1, $2, $3, $4, $5, $6)",
+            &[&id, &destination_id, &destination_name, &reason, staff_id, &until],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+/// Lift the active suspension of the destination; the number lifted (0 or 1)
+pub async fn lift(client: &impl GenericClient, destination_id: &str, staff_id: &Option<String>) -> Result<u64, String> {
+    let sql = format!(
+        "UPDATE destination_suspensions s SET lifted_at = NOW(), lifted_by = $2 WHERE s.destination_id = $1 AND {}",
+        ACTIVE
+    );
+    client.execute(sql.as_str(), &[&destination_id, staff_id]).await.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn a_suspension_cannot_end_in_the_past() {
+        let now = Utc::now();
+        assert!(validate_until(None, now).is_ok());
+        assert!(validate_until(Some(now + Duration::hours(2)), now).is_ok());
+        assert!(validate_until(Some(now), now).is_err());
+        assert!(validate_until(Some(now - Duration::minutes(5)), now).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::terminal_messages::MessagePriority;
+use crate::time::{self, TunisTime};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalMessageDto {
+    pub id: String,
+    pub from_terminal_id: String,
+    pub from_terminal_name: String,
+    pub target_terminal_id: String,
+    pub target_terminal_name: String,
+    pub sent_by_name: Option<String>,
+    pub text: String,
+    pub priority: MessagePriority,
+    pub sent_at: String,
+    pub acknowledged_at: Option<String>,
+    pub acknowledged_by_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalMessagesDto {
+    pub inbox: Vec<TerminalMessageDto>, // sent to this terminal and not acknowledged yet
+    pub sent: Vec<TerminalMessageDto>,  // sent from this terminal today
+}
+
+const SELECT_SQL: &str = r#"
+    SELECT m.id, m.from_terminal_id, COALESCE(tf.name, m.from_terminal_id) AS from_terminal_name,
+           m.target_terminal_id, COALESCE(tt.name, m.target_terminal_id) AS target_terminal_name,
+           sb.first_name || ' ' || sb.last_name AS sent_by_name, m.text, m.priority, m.sent_at,
+           m.acknowledged_at, ab.first_name || ' ' || ab.last_name AS acknowledged_by_name
+    FROM terminal_messages m
+    LEFT JOIN terminals tf ON tf.id = m.from_terminal_id
+    LEFT JOIN terminals tt ON tt.id = m.target_terminal_id
+    LEFT JOIN staff sb ON sb.id = m.sent_by
+    LEFT JOIN staff ab ON ab.id = m.acknowledged_by"#;
+
+fn map_message_row(r: &Row) -> TerminalMessageDto {
+    TerminalMessageDto {
+        id: r.get("id"),
+        from_terminal_id: r.get("from_terminal_id"),
+        from_terminal_name: r.get("from_terminal_name"),
+        target_terminal_id: r.get("target_terminal_id"),
+        target_terminal_name: r.get("target_terminal_name"),
+        sent_by_name: r.get("sent_by_name"),
+        text: r.get("text"),
+        priority: MessagePriority::parse(&r.get::<_, String>("priority")),
+        sent_at: r.get::<_, TunisTime>("sent_at").fmt_dto(),
+        acknowledged_at: r.get::<_, Option<TunisTime>>("acknowledged_at").map(|t| t.fmt_dto()),
+        acknowledged_by_name: r.get("acknowledged_by_name"),
+    }
+}
+
+pub async fn terminal_exists(client: &impl GenericClient, terminal_id: &str) -> Result<bool, String> {
+    let row = client
+        .query_opt("SELECT 1 FROM terminals WHERE id = $1", &[&terminal_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.is_some())
+}
+
+pub async fn insert(client: &impl GenericClient, from_terminal_id: &str, target_terminal_id: &str, sent_by: &Option<String>, text: &str, priority: MessagePriority) -> Result<String, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO terminal_messages (id, from_terminal_id, target_terminal_id, sent_by, text, priority) VALUES ($1, $2, $3, $4, $5, $6)",
+            &[&id, &from_terminal_id, &target_terminal_id, sent_by, &text, &priority.as_str()],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+/// Acknowledge a message sent to `terminal_id`; false when there is none or it was already acknowledged
+pub async fn acknowledge(client: &impl GenericClient, message_id: &str, terminal_id: &str, staff_id: &Option<String>) -> Result<bool, String> {
+    let updated = client
+        .execute(
+            "UPDATE terminal_messages SET acknowledged_at = NOW(), acknowledged_by = $3
+             WHERE id = $1 AND target_terminal_id = $2 AND acknowledged_at IS NULL",
+            &[&message_id, &terminal_id, staff_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(updated == 1)
+}
+
+pub async fn find(client: &impl GenericClient, message_id: &str) -> Result<Option<TerminalMessageDto>, String> {
+    let sql = format!("{} WHERE m.id = $1", SELECT_SQL);
+    let row = client.query_opt(sql.as_str(), &[&message_id]).await.map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_message_row))
+}
+
+/// Unacknowledged messages to the terminal, oldest first, and what it sent today, newest first
+pub async fn for_terminal(client: &impl GenericClient, terminal_id: &str) -> Result<TerminalMessagesDto, String> {
+    let inbox_sql = format!("{} WHERE m.target_terminal_id = $1 AND m.acknowledged_at IS NULL ORDER BY m.sent_at", SELECT_SQL);
+    let inbox = client.query(inbox_sql.as_str(), &[&terminal_id]).await.map_err(|e| e.to_string())?;
+    let (day_start, _) = time::today_bounds();
+    let sent_sql = format!("{} WHERE m.from_terminal_id = $1 AND m.sent_at >= $2 ORDER BY m.sent_at DESC", SELECT_SQL);
+    let sent = client.query(sent_sql.as_str(), &[&terminal_id, &day_start]).await.map_err(|e| e.to_string())?;
+    Ok(TerminalMessagesDto {
+        inbox: inbox.iter().map(map_message_row).collect(),
+        sent: sent.iter().map(map_message_row).collect(),
+    })
+}
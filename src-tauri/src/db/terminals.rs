@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use super::GenericClient;
+use crate::terminal::TerminalInfo;
+use crate::time::TunisTime;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalDto {
+    pub id: String,
+    pub name: String,
+    pub hostname: String,
+    pub app_version: String,
+    pub provisioned_at: String,
+    pub last_seen_at: String,
+}
+
+/// Insert or refresh this terminal; the name in terminals is kept once set, so head office
+/// may rename a terminal there
+pub async fn register(client: &impl GenericClient, info: &TerminalInfo, app_version: &str) -> Result<(), String> {
+    client
+        .execute(
+            r#"INSERT INTO terminals (id, name, hostname, app_version, provisioned_at, last_seen_at)
+               VALUES ($1, $2, $3, $4, $5, NOW())
+               ON CONFLICT (id) DO UPDATE
+               SET hostname = EXCLUDED.hostname, app_version = EXCLUDED.app_version, last_seen_at = NOW()"#,
+            &[&info.terminal_id, &info.name, &info.hostname, &app_version, &info.provisioned_at],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn list(client: &impl GenericClient) -> Result<Vec<TerminalDto>, String> {
+    let rows = client
+        .query(
+            "SELECT id, name, hostname, app_version, provisioned_at, last_seen_at FROM terminals ORDER BY name",
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| TerminalDto {
+            id: r.get("id"),
+            name: r.get("name"),
+            hostname: r.get("hostname"),
+            app_version: r.get("app_version"),
+            provisioned_at: r.get::<_, TunisTime>("provisioned_at").fmt_dto(),
+            last_seen_at: r.get::<_, TunisTime>("last_seen_at").fmt_dto(),
+        })
+        .collect())
+}
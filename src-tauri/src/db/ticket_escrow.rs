@@ -0,0 +1,14 @@
+use super::{settings, GenericClient};
+use crate::ticket_escrow::NoPrinterMode;
+
+// The no_printer_mode setting (see ticket_escrow.rs); the tickets are in printed_tickets
+
+const MODE_KEY: &str = "no_printer_mode";
+
+pub async fn mode(client: &impl GenericClient) -> Result<NoPrinterMode, String> {
+    Ok(NoPrinterMode::parse(settings::get(client, MODE_KEY).await?.as_deref()))
+}
+
+pub async fn set_mode(client: &impl GenericClient, mode: &NoPrinterMode) -> Result<(), String> {
+    settings::set(client, MODE_KEY, &serde_json::to_string(mode).map_err(|e| e.to_string())?).await
+}
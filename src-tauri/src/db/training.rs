@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use super::GenericClient;
+use crate::training::SCHEMA;
+
+// The training schema (see training.rs): rebuilt from the public one, every table empty but
+// those the cashier needs to sell: staff, routes, vehicles, prices and settings.
+
+const SEEDED_TABLES: &[&str] = &[
+    "staff",
+    "routes",
+    "vehicles",
+    "vehicle_authorized_stations",
+    "terminals",
+    "station_settings",
+    "station_payment_methods",
+    "pass_prices",
+    "pricing_rules",
+    "escpos_macros",
+    "route_aliases",
+];
+
+// Bookkeeping of the public schema, not station data
+const SKIPPED_TABLES: &[&str] = &["app_schema_migrations"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrainingSeedDto {
+    pub tables: usize,
+    pub rows: u64,
+}
+
+pub async fn seeded(client: &impl GenericClient) -> Result<bool, String> {
+    let row = client
+        .query_one("SELECT EXISTS (SELECT 1 FROM information_schema.schemata WHERE schema_name = $1)", &[&SCHEMA])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get(0))
+}
+
+/// Drop and rebuild the training schema; run it in a transaction
+pub async fn seed(client: &impl GenericClient) -> Result<TrainingSeedDto, String> {
+    // Trigger definitions come back unqualified, so they can be replayed on the copies
+    client.batch_execute("SET LOCAL search_path TO public").await.map_err(|e| e.to_string())?;
+    let tables: Vec<String> = client
+        .query(
+            "SELECT table_name::text FROM information_schema.tables \
+             WHERE table_schema = 'public' AND table_type = 'BASE TABLE' ORDER BY table_name",
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|row| row.get::<_, String>(0))
+        .filter(|table| !SKIPPED_TABLES.contains(&table.as_str()))
+        .collect();
+    let triggers: Vec<String> = client
+        .query(
+            "SELECT pg_get_triggerdef(t.oid) FROM pg_trigger t \
+             JOIN pg_class c ON c.oid = t.tgrelid JOIN pg_namespace n ON n.oid = c.relnamespace \
+             WHERE n.nspname = 'public' AND NOT t.tgisinternal",
+            &[],
+        )
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    client
+        .batch_execute(&format!("DROP SCHEMA IF EXISTS {0} CASCADE; CREATE SCHEMA {0}", SCHEMA))
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut rows = 0;
+    for table in &tables {
+        client
+            .batch_execute(&format!("CREATE TABLE {0}.\"{1}\" (LIKE public.\"{1}\" INCLUDING ALL)", SCHEMA, table))
+            .await
+            .map_err(|e| e.to_string())?;
+        if SEEDED_TABLES.contains(&table.as_str()) {
+            rows += client
+                .execute(&format!("INSERT INTO {0}.\"{1}\" SELECT * FROM public.\"{1}\"", SCHEMA, table), &[])
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    client
+        .batch_execute(&format!("SET LOCAL search_path TO {}, public", SCHEMA))
+        .await
+        .map_err(|e| e.to_string())?;
+    for trigger in &triggers {
+        client.batch_execute(trigger).await.map_err(|e| e.to_string())?;
+    }
+    Ok(TrainingSeedDto { tables: tables.len(), rows })
+}
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::GenericClient;
+use crate::time;
+
+// Trips of a vehicle, counted from its exit passes: one pass, one departure
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TripPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl TripPeriod {
+    pub fn parse(period: &str) -> Result<Self, String> {
+        match period.to_ascii_lowercase().as_str() {
+            "day" | "jour" => Ok(TripPeriod::Day),
+            "week" | "semaine" => Ok(TripPeriod::Week),
+            "month" | "mois" => Ok(TripPeriod::Month),
+            _ => Err(crate::i18n::tf("error.invalid_trip_period", &[("period", &period)])),
+        }
+    }
+
+    /// First and last Tunis day of the period holding `day`; weeks start on Monday
+    pub fn days(&self, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            TripPeriod::Day => (day, day),
+            TripPeriod::Week => {
+                let monday = day - chrono::Duration::days(day.weekday().num_days_from_monday() as i64);
+                (monday, monday + chrono::Duration::days(6))
+            }
+            TripPeriod::Month => {
+                let first = day.with_day(1).unwrap();
+                let next = if first.month() == 12 {
+                    NaiveDate::from_ymd_opt(first.year() + 1, 1, 1).unwrap()
+                } else {
+                    NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1).unwrap()
+                };
+                (first, next.pred_opt().unwrap())
+            }
+        }
+    }
+
+    pub fn bounds(&self, day: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let (first, last) = self.days(day);
+        (time::tunis_day_bounds(first).0, time::tunis_day_bounds(last).1)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DestinationTrips {
+    pub destination_id: String,
+    pub destination_name: String,
+    pub trips: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleTripCounts {
+    pub vehicle_id: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub trips: i64,
+    pub by_destination: Vec<DestinationTrips>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TripLeaderboardEntry {
+    pub rank: i64,
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub trips: i64,
+}
+
+/// Departures of the vehicle today, the one just recorded included
+pub async fn trip_number_today(client: &impl GenericClient, vehicle_id: &str) -> Result<i64, String> {
+    let (start, end) = time::today_bounds();
+    let row = client
+        .query_one(
+            "SELECT COUNT(*) AS trips FROM exit_passes WHERE vehicle_id = $1 AND current_exit_time >= $2 AND current_exit_time < $3",
+            &[&vehicle_id, &start, &end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get("trips"))
+}
+
+/// Trips of the vehicle in the period holding `day`, per destination, busiest first
+pub async fn vehicle_trip_counts(client: &impl GenericClient, vehicle_id: &str, period: TripPeriod, day: NaiveDate) -> Result<VehicleTripCounts, String> {
+    let (start, end) = period.bounds(day);
+    let rows = client
+        .query(
+            r#"SELECT destination_id, MAX(destination_name) AS destination_name, COUNT(*) AS trips
+               FROM exit_passes
+               WHERE vehicle_id = $1 AND current_exit_time >= $2 AND current_exit_time < $3
+               GROUP BY destination_id
+               ORDER BY trips DESC, destination_name"#,
+            &[&vehicle_id, &start, &end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let by_destination: Vec<DestinationTrips> = rows
+        .iter()
+        .map(|r| DestinationTrips { destination_id: r.get("destination_id"), destination_name: r.get("destination_name"), trips: r.get("trips") })
+        .collect();
+    let (from, to) = period.days(day);
+    Ok(VehicleTripCounts {
+        vehicle_id: vehicle_id.to_string(),
+        from,
+        to,
+        trips: by_destination.iter().map(|d| d.trips).sum(),
+        by_destination,
+    })
+}
+
+/// Vehicles with the most departures to a destination in the period holding `day`; ties share a rank
+pub async fn leaderboard(client: &impl GenericClient, destination_id: &str, period: TripPeriod, day: NaiveDate, limit: i64) -> Result<Vec<TripLeaderboardEntry>, String> {
+    let (start, end) = period.bounds(day);
+    let rows = client
+        .query(
+            r#"SELECT RANK() OVER (ORDER BY COUNT(*) DESC) AS rank, vehicle_id, MAX(license_plate) AS license_plate, COUNT(*) AS trips
+               FROM exit_passes
+               WHERE destination_id = $1 AND current_exit_time >= $2 AND current_exit_time < $3
+               GROUP BY vehicle_id
+               ORDER BY trips DESC, license_plate
+               LIMIT $4"#,
+            &[&destination_id, &start, &end, &limit],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| TripLeaderboardEntry { rank: r.get("rank"), vehicle_id: r.get("vehicle_id"), license_plate: r.get("license_plate"), trips: r.get("trips") })
+        .collect())
+}
+
+/// A vehicle's time from an exit pass to its next queue entry
+pub struct RoundTrip {
+    pub destination_id: String,
+    pub minutes: f64,
+}
+
+/// Round trips that started since `since` and took under `max_hours`, for one destination or all.
+/// The next entry comes from the queue journal (migration 023), which outlives the queue rows.
+pub async fn round_trips(client: &impl GenericClient, destination_id: Option<&str>, since: DateTime<Utc>, max_hours: i32) -> Result<Vec<RoundTrip>, String> {
+    let rows = client
+        .query(
+            r#"SELECT e.destination_id,
+                      (EXTRACT(EPOCH FROM (n.occurred_at - e.current_exit_time)) / 60)::float8 AS minutes
+               FROM exit_passes e
+               JOIN LATERAL (
+                   SELECT occurred_at FROM queue_events
+                   WHERE vehicle_id = e.vehicle_id AND kind = 'ENTERED' AND occurred_at > e.current_exit_time
+                   ORDER BY occurred_at LIMIT 1
+               ) n ON true
+               WHERE ($1::text IS NULL OR e.destination_id = $1) AND e.current_exit_time >= $2
+                 AND n.occurred_at - e.current_exit_time < make_interval(hours => $3)"#,
+            &[&destination_id, &since, &max_hours],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| RoundTrip { destination_id: r.get("destination_id"), minutes: r.get("minutes") })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn periods_are_calendar_days_weeks_from_monday_and_months() {
+        let thursday = date(2026, 10, 15);
+        assert_eq!(TripPeriod::parse("day").unwrap().days(thursday), (thursday, thursday));
+        assert_eq!(TripPeriod::parse("Semaine").unwrap().days(thursday), (date(2026, 10, 12), date(2026, 10, 18)));
+        assert_eq!(TripPeriod::Month.days(thursday), (date(2026, 10, 1), date(2026, 10, 31)));
+        assert_eq!(TripPeriod::Month.days(date(2026, 12, 31)), (date(2026, 12, 1), date(2026, 12, 31)));
+        assert!(TripPeriod::parse("year").is_err());
+    }
+}
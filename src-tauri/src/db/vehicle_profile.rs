@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use super::passes::DayPassDto;
+use super::queue::VehicleQueueStatusDto;
+use super::vehicles::{AuthorizedDestinationDto, VehicleActivityItem, VehicleDto};
+use super::{passes, queue, vehicles, GenericClient};
+use crate::money::Money;
+use crate::time::TunisTime;
+
+// Everything the vehicle window (open_vehicle_window) shows about one plate, read in one command
+// instead of one per panel. The parts reuse the queries behind the single-purpose commands, so
+// the window and those commands cannot disagree.
+
+const RECENT_BOOKINGS: i64 = 20;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarriedBookingDto {
+    pub id: String,
+    pub verification_code: String,
+    pub destination_name: String,
+    pub seats_booked: i32,
+    pub total_amount: Money,
+    pub payment_status: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleProfileDto {
+    pub vehicle: VehicleDto,
+    pub authorizations: Vec<AuthorizedDestinationDto>,
+    pub queue_status: Option<VehicleQueueStatusDto>,
+    pub activity_72h: Vec<VehicleActivityItem>,
+    pub today_day_pass: Option<DayPassDto>,
+    pub recent_bookings: Vec<CarriedBookingDto>,
+}
+
+/// Latest bookings on the vehicle's queue entries, the current one or those it left with
+async fn recent_bookings(client: &impl GenericClient, vehicle_id: &str) -> Result<Vec<CarriedBookingDto>, String> {
+    let rows = client
+        .query(
+            r#"SELECT b.id, b.verification_code, COALESCE(vq.destination_name, ep.destination_name, '') AS destination_name,
+                      b.seats_booked, b.total_amount, b.payment_status::text AS payment_status, b.created_at
+               FROM bookings b
+               LEFT JOIN vehicle_queue vq ON vq.id = b.queue_id
+               LEFT JOIN LATERAL (
+                   SELECT vehicle_id, destination_name FROM exit_passes WHERE queue_id = b.queue_id LIMIT 1
+               ) ep ON true
+               WHERE COALESCE(vq.vehicle_id, ep.vehicle_id) = $1
+               ORDER BY b.created_at DESC LIMIT $2"#,
+            &[&vehicle_id, &RECENT_BOOKINGS],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .iter()
+        .map(|r| CarriedBookingDto {
+            id: r.get("id"),
+            verification_code: r.get("verification_code"),
+            destination_name: r.get("destination_name"),
+            seats_booked: r.get("seats_booked"),
+            total_amount: r.get("total_amount"),
+            payment_status: r.get("payment_status"),
+            created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+        })
+        .collect())
+}
+
+/// The profile of the plate, None when no vehicle has it
+pub async fn load(client: &impl GenericClient, license_plate: &str) -> Result<Option<VehicleProfileDto>, String> {
+    let Some(vehicle) = vehicles::dto_by_plate(client, license_plate).await? else {
+        return Ok(None);
+    };
+    Ok(Some(VehicleProfileDto {
+        authorizations: vehicles::authorized_destinations(client, license_plate).await?,
+        queue_status: queue::status_for_plate(client, license_plate).await?,
+        activity_72h: vehicles::activity_72h(client, license_plate).await?,
+        today_day_pass: passes::today_pass_for_plate(client, license_plate).await?,
+        recent_bookings: recent_bookings(client, &vehicle.id).await?,
+        vehicle,
+    }))
+}
@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::money::Money;
+use crate::time::TunisTime;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleDto {
+    pub id: String,
+    pub license_plate: String,
+    pub capacity: i32,
+    pub is_active: bool,
+    pub is_available: bool,
+    pub is_banned: bool,
+    pub phone_number: Option<String>,
+    pub default_destination_id: Option<String>,
+    pub default_destination_name: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizedDestinationDto {
+    pub station_id: String,
+    pub station_name: String,
+    pub base_price: Money,
+    pub is_default: bool,
+    pub priority: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverflowVehicleDto {
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub capacity: i32,
+    pub phone_number: Option<String>,
+    pub trips_today: i64,
+    pub last_trip_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleActivityItem {
+    pub event_type: String, // ENTRY or EXIT
+    pub timestamp: String,
+    pub destination_name: Option<String>,
+}
+
+fn map_vehicle_row(r: &Row) -> VehicleDto {
+    VehicleDto {
+        id: r.get("id"),
+        license_plate: r.get("license_plate"),
+        capacity: r.get("capacity"),
+        is_active: r.get("is_active"),
+        is_available: r.get("is_available"),
+        is_banned: r.get("is_banned"),
+        phone_number: r.get("phone_number"),
+        default_destination_id: r.get("default_destination_id"),
+        default_destination_name: r.get("default_destination_name"),
+        created_at: r.get::<_, Option<TunisTime>>("created_at").map(|t| t.fmt_dto()),
+    }
+}
+
+// The columns queue entry needs to know about a vehicle
+pub struct VehicleRef {
+    pub id: String,
+    pub capacity: i32,
+    pub is_active: bool,
+}
+
+pub async fn find_by_plate(client: &impl GenericClient, license_plate: &str) -> Result<Option<VehicleRef>, String> {
+    let row = client
+        .query_opt("SELECT id, capacity, is_active FROM vehicles WHERE license_plate = $1", &[&license_plate])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| VehicleRef {
+        id: r.get("id"),
+        capacity: r.get("capacity"),
+        is_active: r.get("is_active"),
+    }))
+}
+
+pub async fn list_active(client: &impl GenericClient) -> Result<Vec<VehicleDto>, String> {
+    let sql = r#"
+        SELECT id, license_plate, capacity, is_active, is_available, is_banned, phone_number,
+               default_destination_id, default_destination_name, created_at
+        FROM vehicles
+        WHERE is_active = true
+        ORDER BY created_at DESC
+    "#;
+    let rows = client.query(sql, &[]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_vehicle_row).collect())
+}
+
+pub async fn dto_by_plate(client: &impl GenericClient, license_plate: &str) -> Result<Option<VehicleDto>, String> {
+    let sql = r#"
+        SELECT id, license_plate, capacity, is_active, is_available, is_banned, phone_number,
+               default_destination_id, default_destination_name, created_at
+        FROM vehicles
+        WHERE license_plate = $1
+    "#;
+    let row = client.query_opt(sql, &[&license_plate]).await.map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_vehicle_row))
+}
+
+/// Day passes bought (ENTRY) and exit passes printed (EXIT) for the plate in the last 72 hours, newest first
+pub async fn activity_72h(client: &impl GenericClient, license_plate: &str) -> Result<Vec<VehicleActivityItem>, String> {
+    let rows = client.query(
+        r#"
+        SELECT 'ENTRY' AS event_type,
+               dp.purchase_date AS ts,
+               COALESCE(
+                   (SELECT vq.destination_name FROM vehicle_queue vq 
+                    JOIN vehicles v ON vq.vehicle_id = v.id
+                    WHERE v.license_plate = dp.license_plate 
+                    ORDER BY vq.entered_at DESC LIMIT 1),
+                   'Destination inconnue'
+               ) AS destination_name
+        FROM day_passes dp
+        WHERE dp.license_plate = $1
+          AND dp.purchase_date >= NOW() - INTERVAL '72 hours'
+        UNION ALL
+        SELECT 'EXIT' AS event_type,
+               current_exit_time AS ts,
+               destination_name
+        FROM exit_passes
+        WHERE license_plate = $1
+          AND current_exit_time >= NOW() - INTERVAL '72 hours'
+        ORDER BY ts DESC
+        "#,
+        &[&license_plate]
+    ).await.map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .iter()
+        .map(|r| VehicleActivityItem {
+            event_type: r.get::<_, String>("event_type"),
+            timestamp: r.get::<_, TunisTime>("ts").fmt_dto(),
+            destination_name: r.get::<_, Option<String>>("destination_name"),
+        })
+        .collect())
+}
+
+pub async fn insert(client: &impl GenericClient, license_plate: &str, capacity: i32, phone_number: &Option<String>) -> Result<String, String> {
+    let vehicle_id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO vehicles (id, license_plate, capacity, phone_number, is_active, is_available, is_banned, created_at, updated_at) VALUES ($1, $2, $3, $4, true, true, false, NOW(), NOW())",
+            &[&vehicle_id, &license_plate, &capacity, phone_number],
+        )
+        .await
+        .map_err(|e| format!("Erreur lors de la création du véhicule: {}", e))?;
+    Ok(vehicle_id)
+}
+
+pub async fn update_phone(client: &impl GenericClient, vehicle_id: &str, phone_number: &Option<String>) -> Result<u64, String> {
+    client
+        .execute(
+            "UPDATE vehicles SET phone_number = $1, updated_at = NOW() WHERE id = $2",
+            &[phone_number, &vehicle_id],
+        )
+        .await
+        .map_err(|e| format!("Failed to update phone number: {}", e))
+}
+
+/// A vehicle whose capacity is being changed
+pub struct LockedVehicle {
+    pub license_plate: String,
+    pub capacity: i32,
+}
+
+/// The vehicle, locked until the transaction ends
+pub async fn lock(client: &impl GenericClient, vehicle_id: &str) -> Result<Option<LockedVehicle>, String> {
+    let row = client
+        .query_opt("SELECT license_plate, capacity FROM vehicles WHERE id = $1 FOR UPDATE", &[&vehicle_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| LockedVehicle { license_plate: r.get("license_plate"), capacity: r.get("capacity") }))
+}
+
+pub async fn update_capacity(client: &impl GenericClient, vehicle_id: &str, capacity: i32) -> Result<u64, String> {
+    client
+        .execute("UPDATE vehicles SET capacity = $1, updated_at = NOW() WHERE id = $2", &[&capacity, &vehicle_id])
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Plates of the other vehicles registered with `phone_number`
+pub async fn plates_with_phone(client: &impl GenericClient, phone_number: &str, except_vehicle_id: Option<&str>) -> Result<Vec<String>, String> {
+    let rows = client
+        .query(
+            "SELECT license_plate FROM vehicles WHERE phone_number = $1 AND id IS DISTINCT FROM $2 ORDER BY license_plate",
+            &[&phone_number, &except_vehicle_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(|r| r.get("license_plate")).collect())
+}
+
+pub async fn ban(client: &impl GenericClient, vehicle_id: &str) -> Result<u64, String> {
+    client
+        .execute("UPDATE vehicles SET is_banned = true, updated_at = NOW() WHERE id = $1", &[&vehicle_id])
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub async fn authorized_destinations(client: &impl GenericClient, license_plate: &str) -> Result<Vec<AuthorizedDestinationDto>, String> {
+    let sql = r#"
+        SELECT vas.station_id,
+               COALESCE(vas.station_name, r.station_name) AS station_name,
+               COALESCE(r.base_price, 0)::float8 AS base_price,
+               vas.is_default,
+               vas.priority
+        FROM vehicle_authorized_stations vas
+        JOIN vehicles v ON v.id = vas.vehicle_id
+        LEFT JOIN routes r ON r.station_id = vas.station_id
+        WHERE v.license_plate = $1
+        ORDER BY vas.is_default DESC, vas.priority ASC
+    "#;
+    let rows = client.query(sql, &[&license_plate]).await.map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .map(|r| AuthorizedDestinationDto {
+            station_id: r.get("station_id"),
+            station_name: r.get("station_name"),
+            base_price: r.get("base_price"),
+            is_default: r.get("is_default"),
+            priority: r.get("priority"),
+        })
+        .collect())
+}
+
+// Station name recorded on the authorization ("" when none), None when not authorized
+pub async fn authorization(client: &impl GenericClient, vehicle_id: &str, station_id: &str) -> Result<Option<String>, String> {
+    let row = client
+        .query_opt(
+            "SELECT COALESCE(station_name, '') AS name FROM vehicle_authorized_stations WHERE vehicle_id = $1 AND station_id = $2",
+            &[&vehicle_id, &station_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.map(|r| r.get("name")))
+}
+
+pub async fn authorize_station(client: &impl GenericClient, vehicle_id: &str, station_id: &str, station_name: &str) -> Result<String, String> {
+    let auth_id = uuid::Uuid::new_v4().to_string();
+    client
+        .execute(
+            "INSERT INTO vehicle_authorized_stations (id, vehicle_id, station_id, station_name, priority, is_default, created_at) VALUES ($1, $2, $3, $4, 1, false, NOW())",
+            &[&auth_id, &vehicle_id, &station_id, &station_name],
+        )
+        .await
+        .map_err(|e| format!("Erreur lors de l'autorisation: {}", e))?;
+    Ok(auth_id)
+}
+
+/// Vehicles that could be called in for `station_id`: authorized, active, not banned and not in
+/// any queue. Fairest first: fewest trips today, then longest since their last departure.
+pub async fn overflow_candidates(client: &impl GenericClient, station_id: &str, limit: i64) -> Result<Vec<OverflowVehicleDto>, String> {
+    let sql = r#"
+        SELECT v.id, v.license_plate, v.capacity, v.phone_number,
+               COALESCE(h.trips_today, 0) AS trips_today,
+               h.last_exit
+        FROM vehicle_authorized_stations vas
+        JOIN vehicles v ON v.id = vas.vehicle_id
+        LEFT JOIN LATERAL (
+            SELECT MAX(exit_time) AS last_exit,
+                   COUNT(*) FILTER (WHERE exit_time >= $3 AND exit_time < $4) AS trips_today
+            FROM vehicle_queue_history
+            WHERE license_plate = v.license_plate
+        ) h ON true
+        WHERE vas.station_id = $1
+          AND v.is_active = true AND v.is_banned = false
+          AND NOT EXISTS (SELECT 1 FROM vehicle_queue q WHERE q.vehicle_id = v.id)
+        ORDER BY trips_today ASC, h.last_exit ASC NULLS FIRST, vas.priority ASC, v.license_plate
+        LIMIT $2
+    "#;
+    let (day_start, day_end) = crate::time::today_bounds();
+    let rows = client
+        .query(sql, &[&station_id, &limit, &day_start, &day_end])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .map(|r| OverflowVehicleDto {
+            vehicle_id: r.get("id"),
+            license_plate: r.get("license_plate"),
+            capacity: r.get("capacity"),
+            phone_number: r.get("phone_number"),
+            trips_today: r.get("trips_today"),
+            last_trip_at: r.get::<_, Option<TunisTime>>("last_exit").map(|t| t.fmt_iso()),
+        })
+        .collect())
+}
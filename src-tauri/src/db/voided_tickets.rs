@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::time::{self, TunisTime};
+
+// Spoiled ticket numbers that must not be printed again (migration 034, see reprints.rs)
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VoidedTicketDto {
+    pub id: String,
+    pub ticket_kind: String,
+    pub ticket_number: String,
+    pub reason: String,
+    /// The number the ticket was reprinted under, once it was
+    pub replaced_by: Option<String>,
+    pub created_by: Option<String>,
+    pub staff_name: String,
+    pub created_at: String,
+}
+
+fn map_voided_row(r: &Row) -> VoidedTicketDto {
+    VoidedTicketDto {
+        id: r.get("id"),
+        ticket_kind: r.get("ticket_kind"),
+        ticket_number: r.get("ticket_number"),
+        reason: r.get("reason"),
+        replaced_by: r.get("replaced_by"),
+        created_by: r.get("created_by"),
+        staff_name: r.get("staff_name"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_dto(),
+    }
+}
+
+const VOIDED_SQL: &str = r#"
+    SELECT v.id, v.ticket_kind, v.ticket_number, v.reason, v.replaced_by, v.created_by, v.created_at,
+           COALESCE(st.first_name || ' ' || st.last_name, v.created_by, 'Staff') AS staff_name
+    FROM voided_ticket_numbers v
+    LEFT JOIN staff st ON st.id = v.created_by"#;
+
+/// Void the number; None when it was voided already
+pub async fn insert(client: &impl GenericClient, ticket_kind: &str, ticket_number: &str, reason: &str, created_by: &Option<String>) -> Result<Option<VoidedTicketDto>, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let inserted = client
+        .execute(
+            r#"INSERT INTO voided_ticket_numbers (id, ticket_kind, ticket_number, reason, created_by, terminal_id, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, NOW())
+               ON CONFLICT (ticket_kind, ticket_number) DO NOTHING"#,
+            &[&id, &ticket_kind, &ticket_number, &reason, created_by, &crate::terminal::id()],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    if inserted == 0 {
+        return Ok(None);
+    }
+    let row = client
+        .query_one(&format!("{} WHERE v.id = $1", VOIDED_SQL), &[&id])
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(Some(map_voided_row(&row)))
+}
+
+/// Whether the number is voided and not yet replaced, locking its row until the transaction ends
+pub async fn awaiting_replacement(client: &impl GenericClient, ticket_kind: &str, ticket_number: &str) -> Result<bool, String> {
+    let row = client
+        .query_opt(
+            "SELECT 1 FROM voided_ticket_numbers WHERE ticket_kind = $1 AND ticket_number = $2 AND replaced_by IS NULL FOR UPDATE",
+            &[&ticket_kind, &ticket_number],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.is_some())
+}
+
+pub async fn set_replacement(client: &impl GenericClient, ticket_kind: &str, ticket_number: &str, replaced_by: &str) -> Result<(), String> {
+    client
+        .execute(
+            "UPDATE voided_ticket_numbers SET replaced_by = $3 WHERE ticket_kind = $1 AND ticket_number = $2",
+            &[&ticket_kind, &ticket_number, &replaced_by],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Numbers voided by one staff member on one Tunis day, oldest first
+pub async fn for_staff_day(client: &impl GenericClient, staff_id: &str, day: chrono::NaiveDate) -> Result<Vec<VoidedTicketDto>, String> {
+    let (day_start, day_end) = time::tunis_day_bounds(day);
+    let rows = client
+        .query(
+            &format!("{} WHERE v.created_by = $1 AND v.created_at >= $2 AND v.created_at < $3 ORDER BY v.created_at", VOIDED_SQL),
+            &[&staff_id, &day_start, &day_end],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_voided_row).collect())
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
+
+use super::GenericClient;
+use crate::time::TunisTime;
+
+// How long a notified customer keeps their claim on the seats before the next one is called
+pub const CLAIM_MINUTES: i32 = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitlistEntryDto {
+    pub id: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub seats: i32,
+    pub phone_number: Option<String>,
+    pub status: String, // WAITING, NOTIFIED, BOOKED, CANCELLED or EXPIRED
+    pub created_at: String,
+    pub notified_at: Option<String>,
+}
+
+const ENTRY_COLUMNS: &str = r#"id, destination_id, destination_name, seats, phone_number, status,
+    created_at, notified_at"#;
+
+fn map_entry_row(r: &Row) -> WaitlistEntryDto {
+    WaitlistEntryDto {
+        id: r.get("id"),
+        destination_id: r.get("destination_id"),
+        destination_name: r.get("destination_name"),
+        seats: r.get("seats"),
+        phone_number: r.get("phone_number"),
+        status: r.get("status"),
+        created_at: r.get::<_, TunisTime>("created_at").fmt_iso(),
+        notified_at: r.get::<_, Option<TunisTime>>("notified_at").map(|t| t.fmt_iso()),
+    }
+}
+
+pub async fn insert(client: &impl GenericClient, destination_id: &str, destination_name: &str, seats: i32, phone_number: &Option<String>, created_by: &Option<String>) -> Result<WaitlistEntryDto, String> {
+    let sql = format!(
+        "INSERT INTO waitlist_entries (id, destination_id, destination_name, seats, phone_number, status, created_by, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, 'WAITING', $6, NOW(), NOW()) RETURNING {}",
+        ENTRY_COLUMNS
+    );
+    let row = client
+        .query_one(sql.as_str(), &[&uuid::Uuid::new_v4().to_string(), &destination_id, &destination_name, &seats, phone_number, created_by])
+        .await
+        .map_err(|e| format!("Erreur lors de l'inscription en liste d'attente: {}", e))?;
+    Ok(map_entry_row(&row))
+}
+
+pub async fn find(client: &impl GenericClient, entry_id: &str) -> Result<Option<WaitlistEntryDto>, String> {
+    let sql = format!("SELECT {} FROM waitlist_entries WHERE id = $1", ENTRY_COLUMNS);
+    let row = client.query_opt(sql.as_str(), &[&entry_id]).await.map_err(|e| e.to_string())?;
+    Ok(row.as_ref().map(map_entry_row))
+}
+
+// Entries still in line for a destination, oldest first
+pub async fn open_for_destination(client: &impl GenericClient, destination_id: &str) -> Result<Vec<WaitlistEntryDto>, String> {
+    let sql = format!(
+        "SELECT {} FROM waitlist_entries WHERE destination_id = $1 AND status IN ('WAITING', 'NOTIFIED') ORDER BY created_at",
+        ENTRY_COLUMNS
+    );
+    let rows = client.query(sql.as_str(), &[&destination_id]).await.map_err(|e| e.to_string())?;
+    Ok(rows.iter().map(map_entry_row).collect())
+}
+
+/// Seats asked for by customers still in line for a destination
+pub async fn waiting_seats(client: &impl GenericClient, destination_id: &str) -> Result<i64, String> {
+    let row = client
+        .query_one(
+            "SELECT COALESCE(SUM(seats), 0)::bigint AS seats FROM waitlist_entries WHERE destination_id = $1 AND status IN ('WAITING', 'NOTIFIED')",
+            &[&destination_id],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(row.get("seats"))
+}
+
+/// Move an open entry to `status`; false when it was already booked, cancelled or expired
+pub async fn close(client: &impl GenericClient, entry_id: &str, status: &str) -> Result<bool, String> {
+    let updated = client
+        .execute(
+            "UPDATE waitlist_entries SET status = $2, updated_at = NOW() WHERE id = $1 AND status IN ('WAITING', 'NOTIFIED')",
+            &[&entry_id, &status],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(updated > 0)
+}
+
+/// Notify the entries that now fit in the seats free for `destination_id`, oldest first. A
+/// later, smaller group may go ahead of an earlier one that does not fit yet. Seats claimed by
+/// entries already notified are kept for them until the claim runs out.
+pub async fn promote(client: &impl GenericClient, destination_id: &str) -> Result<Vec<WaitlistEntryDto>, String> {
+    // Serialize promotions for a destination so two terminals do not promise the same seats
+    client
+        .execute("SELECT pg_advisory_xact_lock(hashtext('waitlist:' || $1))", &[&destination_id])
+        .await
+        .map_err(|e| e.to_string())?;
+    client
+        .execute(
+            "UPDATE waitlist_entries SET status = 'EXPIRED', updated_at = NOW()
+             WHERE destination_id = $1 AND status = 'NOTIFIED' AND notified_at < NOW() - make_interval(mins => $2)",
+            &[&destination_id, &CLAIM_MINUTES],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let free = super::queue::free_seats(client, destination_id).await?;
+    let open = open_for_destination(client, destination_id).await?;
+    let claimed: i64 = open.iter().filter(|e| e.status == "NOTIFIED").map(|e| e.seats as i64).sum();
+    let mut remaining = free - claimed;
+
+    let mut promoted = Vec::new();
+    for entry in open.into_iter().filter(|e| e.status == "WAITING") {
+        if remaining <= 0 {
+            break;
+        }
+        if entry.seats as i64 > remaining {
+            continue;
+        }
+        let sql = format!(
+            "UPDATE waitlist_entries SET status = 'NOTIFIED', notified_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = 'WAITING' RETURNING {}",
+            ENTRY_COLUMNS
+        );
+        if let Some(row) = client.query_opt(sql.as_str(), &[&entry.id]).await.map_err(|e| e.to_string())? {
+            remaining -= entry.seats as i64;
+            promoted.push(map_entry_row(&row));
+        }
+    }
+    Ok(promoted)
+}
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::db;
+use crate::db::annotations::CalendarAnnotationDto;
+use crate::i18n;
+use crate::time;
+
+// When a destination's passengers come, for scheduling vehicles: the paid seats booked on one
+// weekday over the last few weeks, in 30-minute slots of Tunis time. Each slot gives the seats of
+// all those days together, the average per day and the busiest single day. Today is left out
+// since it is not over yet.
+
+pub const SLOT_MINUTES: u32 = 30;
+const SLOTS_PER_DAY: usize = (24 * 60 / SLOT_MINUTES) as usize;
+pub const DEFAULT_WEEKS: u32 = 8;
+const MAX_WEEKS: u32 = 52;
+const WEEKDAYS: [Weekday; 7] = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DemandSlotDto {
+    /// Start of the slot, HH:MM
+    pub slot: String,
+    pub seats_sold: i64,
+    /// Seats per day over the days of the profile, to one decimal
+    pub average_seats: f64,
+    /// Most seats sold in the slot on a single day
+    pub peak_seats: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DemandProfileDto {
+    pub destination_id: String,
+    pub destination_name: String,
+    /// ISO weekday: 1 Monday to 7 Sunday
+    pub weekday: u32,
+    pub weeks: u32,
+    pub days: Vec<NaiveDate>,
+    pub slots: Vec<DemandSlotDto>,
+    pub seats_sold: i64,
+    /// The slot with the most seats, the earliest on a tie; None when nothing was sold
+    pub busiest_slot: Option<String>,
+    /// Notes on the days of the profile (holidays, strikes) that explain an odd one
+    pub annotations: Vec<CalendarAnnotationDto>,
+}
+
+pub fn parse_weekday(weekday: u32) -> Result<Weekday, String> {
+    match weekday {
+        1..=7 => Ok(WEEKDAYS[weekday as usize - 1]),
+        _ => Err(i18n::tf("error.invalid_weekday", &[("weekday", &weekday)])),
+    }
+}
+
+pub fn check_weeks(weeks: Option<u32>) -> Result<u32, String> {
+    match weeks.unwrap_or(DEFAULT_WEEKS) {
+        w @ 1..=MAX_WEEKS => Ok(w),
+        _ => Err(i18n::tf("error.demand_weeks_invalid", &[("max", &MAX_WEEKS)])),
+    }
+}
+
+/// The last `weeks` days falling on `weekday` before `today`, oldest first
+pub fn days_before(today: NaiveDate, weekday: Weekday, weeks: u32) -> Vec<NaiveDate> {
+    let back = (today.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+    let last = today - Duration::days(if back == 0 { 7 } else { back as i64 });
+    (0..weeks as i64).rev().map(|w| last - Duration::weeks(w)).collect()
+}
+
+/// Every slot of the day for the bookings (booked at, seats) made on one of `days`
+pub fn slots(days: &[NaiveDate], bookings: &[(DateTime<Utc>, i32)]) -> Vec<DemandSlotDto> {
+    let mut by_day: HashMap<(NaiveDate, usize), i64> = HashMap::new();
+    for (at, seats) in bookings {
+        let local = at.with_timezone(&time::TZ);
+        let day = local.date_naive();
+        if !days.contains(&day) {
+            continue;
+        }
+        let slot = (local.hour() * 60 + local.minute()) / SLOT_MINUTES;
+        *by_day.entry((day, slot as usize)).or_default() += *seats as i64;
+    }
+    (0..SLOTS_PER_DAY)
+        .map(|slot| {
+            let per_day: Vec<i64> = days.iter().map(|day| by_day.get(&(*day, slot)).copied().unwrap_or(0)).collect();
+            let seats_sold: i64 = per_day.iter().sum();
+            let average = if days.is_empty() { 0.0 } else { seats_sold as f64 / days.len() as f64 };
+            let start = slot as u32 * SLOT_MINUTES;
+            DemandSlotDto {
+                slot: format!("{:02}:{:02}", start / 60, start % 60),
+                seats_sold,
+                average_seats: (average * 10.0).round() / 10.0,
+                peak_seats: per_day.into_iter().max().unwrap_or(0),
+            }
+        })
+        .collect()
+}
+
+pub async fn profile(client: &impl db::GenericClient, destination_id: &str, weekday: u32, weeks: Option<u32>) -> Result<DemandProfileDto, String> {
+    let day = parse_weekday(weekday)?;
+    let weeks = check_weeks(weeks)?;
+    let destination_name = db::queue::destination_route(client, destination_id)
+        .await?
+        .map(|r| r.station_name)
+        .ok_or_else(|| i18n::t("error.destination_not_found"))?;
+    let days = days_before(time::tunis_today(), day, weeks);
+    let from = time::tunis_day_bounds(days[0]).0;
+    let to = time::tunis_day_bounds(days[days.len() - 1]).1;
+    let bookings = db::demand::paid_bookings(client, destination_id, from, to).await?;
+    let annotations = db::annotations::between(client, days[0], days[days.len() - 1])
+        .await?
+        .into_iter()
+        .filter(|a| days.contains(&a.date))
+        .collect();
+    let slots = slots(&days, &bookings);
+    let busiest_slot = slots
+        .iter()
+        .filter(|s| s.seats_sold > 0)
+        .fold(None::<&DemandSlotDto>, |best, s| match best {
+            Some(b) if b.seats_sold >= s.seats_sold => Some(b),
+            _ => Some(s),
+        })
+        .map(|s| s.slot.clone());
+    Ok(DemandProfileDto {
+        destination_id: destination_id.to_string(),
+        destination_name,
+        weekday,
+        weeks,
+        seats_sold: slots.iter().map(|s| s.seats_sold).sum(),
+        days,
+        slots,
+        busiest_slot,
+        annotations,
+    })
+}
+
+/// The profile as CSV, one line per slot
+pub fn profile_csv(profile: &DemandProfileDto) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("Destination,{}\n", db::customers::csv_field(&profile.destination_name)));
+    out.push_str(&format!("Jour,{}\n", profile.weekday));
+    out.push_str(&format!("Semaines,{}\n", profile.weeks));
+    out.push_str("Créneau,Places vendues,Moyenne par jour,Pic\n");
+    for slot in &profile.slots {
+        out.push_str(&format!("{},{},{:.1},{}\n", slot.slot, slot.seats_sold, slot.average_seats, slot.peak_seats));
+    }
+    out.push_str(&format!("Total,{}\n", profile.seats_sold));
+    out.push_str(&db::annotations::csv_lines(&profile.annotations));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn at(local: &str) -> DateTime<Utc> {
+        let local = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap();
+        time::from_tunis_local(local).with_timezone(&Utc)
+    }
+
+    #[test]
+    fn seats_fall_in_half_hour_slots_of_the_chosen_weekday() {
+        // 2026-10-15 is a Thursday: the last two Mondays before it
+        let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let days = days_before(today, parse_weekday(1).unwrap(), 2);
+        assert_eq!(days, vec![NaiveDate::from_ymd_opt(2026, 10, 5).unwrap(), NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()]);
+        // Today's own weekday starts a week back
+        assert_eq!(days_before(today, Weekday::Thu, 1), vec![NaiveDate::from_ymd_opt(2026, 10, 8).unwrap()]);
+
+        let bookings = vec![
+            (at("2026-10-05 07:00"), 3),
+            (at("2026-10-05 07:29"), 1),
+            (at("2026-10-12 07:10"), 2),
+            (at("2026-10-12 07:30"), 5),
+            // A Tuesday, not in the profile
+            (at("2026-10-13 07:00"), 8),
+        ];
+        let slots = slots(&days, &bookings);
+        assert_eq!(slots.len(), 48);
+        assert_eq!(slots[14], DemandSlotDto { slot: "07:00".to_string(), seats_sold: 6, average_seats: 3.0, peak_seats: 4 });
+        assert_eq!(slots[15], DemandSlotDto { slot: "07:30".to_string(), seats_sold: 5, average_seats: 2.5, peak_seats: 5 });
+        assert_eq!(slots.iter().map(|s| s.seats_sold).sum::<i64>(), 11);
+
+        assert!(parse_weekday(0).is_err());
+        assert!(check_weeks(Some(53)).is_err());
+        assert_eq!(check_weeks(None), Ok(DEFAULT_WEEKS));
+    }
+}
@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::route_aliases::RouteNames;
+use crate::i18n;
+use crate::money::Money;
+
+// Destination search for the booking screen: "sfax" finds "Station Sfax Ville", "gabès" finds
+// "GABES" and "صفاقس" the Arabic name. Names and aliases are folded (case, Latin accents,
+// Arabic diacritics and letter variants) and scored, best first: the whole name, its start, the
+// start of one of its words, anywhere in it, then words one or two typos away.
+
+pub const MAX_RESULTS: usize = 20;
+const MAX_ALIASES: usize = 20;
+const MAX_ALIAS_CHARS: usize = 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DestinationMatchDto {
+    pub station_id: String,
+    pub station_name: String,
+    pub station_name_ar: Option<String>,
+    pub governorate: Option<String>,
+    pub delegation: Option<String>,
+    pub base_price: Money,
+    /// The name or alias the query matched
+    pub matched: String,
+    pub score: u32,
+}
+
+fn fold_char(c: char) -> Option<char> {
+    let folded = match c {
+        'à' | 'â' | 'ä' | 'á' | 'ã' => 'a',
+        'ç' => 'c',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'î' | 'ï' | 'í' | 'ì' => 'i',
+        'ô' | 'ö' | 'ó' | 'ò' => 'o',
+        'ù' | 'û' | 'ü' | 'ú' => 'u',
+        'ÿ' => 'y',
+        // Hamza and madda on alef, alef wasla
+        'أ' | 'إ' | 'آ' | 'ٱ' => 'ا',
+        'ى' | 'ئ' => 'ي',
+        'ؤ' => 'و',
+        'ة' => 'ه',
+        // Harakat, superscript alef and tatweel are dropped
+        '\u{064B}'..='\u{065F}' | '\u{0670}' | '\u{0640}' => return None,
+        c if c.is_alphanumeric() => c,
+        _ => ' ',
+    };
+    Some(folded)
+}
+
+/// Lower case, without accents or Arabic diacritics, words separated by single spaces
+pub fn fold(text: &str) -> String {
+    let folded: String = text.to_lowercase().chars().filter_map(fold_char).collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            current.push((previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// Typos allowed in a word of that many letters
+fn typos_allowed(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    }
+}
+
+/// How well `name` answers `query`, both folded; None when it doesn't
+pub fn score(query: &str, name: &str) -> Option<u32> {
+    if query.is_empty() || name.is_empty() {
+        return None;
+    }
+    if name == query {
+        return Some(100);
+    }
+    if name.starts_with(query) {
+        return Some(90);
+    }
+    if name.split(' ').any(|word| word.starts_with(query)) {
+        return Some(80);
+    }
+    if name.contains(query) {
+        return Some(70);
+    }
+    // Every word of the query near a word of the name, or near the start of a longer one
+    let words: Vec<Vec<char>> = name.split(' ').map(|w| w.chars().collect()).collect();
+    let mut typos = 0;
+    for query_word in query.split(' ') {
+        let query_word: Vec<char> = query_word.chars().collect();
+        let allowed = typos_allowed(query_word.len());
+        let best = words
+            .iter()
+            .map(|word| {
+                let whole = edit_distance(&query_word, word);
+                let prefix = edit_distance(&query_word, &word[..word.len().min(query_word.len())]);
+                whole.min(prefix)
+            })
+            .min()?;
+        if best > allowed {
+            return None;
+        }
+        typos += best;
+    }
+    Some(60u32.saturating_sub(10 * typos as u32))
+}
+
+/// Trimmed aliases, one per folded spelling
+pub fn clean_aliases(aliases: Vec<String>) -> Result<Vec<String>, String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for alias in aliases {
+        let alias = alias.split_whitespace().collect::<Vec<_>>().join(" ");
+        if fold(&alias).is_empty() || cleaned.iter().any(|a| fold(a) == fold(&alias)) {
+            continue;
+        }
+        if alias.chars().count() > MAX_ALIAS_CHARS {
+            return Err(i18n::tf("error.route_alias_too_long", &[("max", &MAX_ALIAS_CHARS)]));
+        }
+        cleaned.push(alias);
+    }
+    if cleaned.len() > MAX_ALIASES {
+        return Err(i18n::tf("error.too_many_route_aliases", &[("max", &MAX_ALIASES)]));
+    }
+    Ok(cleaned)
+}
+
+/// Routes matching `query` by name, Arabic name or alias, best first
+pub fn search(query: &str, routes: Vec<RouteNames>) -> Vec<DestinationMatchDto> {
+    let query = fold(query);
+    let mut matches: Vec<DestinationMatchDto> = routes
+        .into_iter()
+        .filter_map(|route| {
+            let names = std::iter::once(&route.station_name).chain(route.station_name_ar.iter()).chain(route.aliases.iter());
+            let (score, matched) = names.filter_map(|name| score(&query, &fold(name)).map(|s| (s, name.clone()))).max_by_key(|(s, _)| *s)?;
+            Some(DestinationMatchDto {
+                station_id: route.station_id,
+                station_name: route.station_name,
+                station_name_ar: route.station_name_ar,
+                governorate: route.governorate,
+                delegation: route.delegation,
+                base_price: route.base_price,
+                matched,
+                score,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.station_name.cmp(&b.station_name)));
+    matches.truncate(MAX_RESULTS);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(id: &str, name: &str, name_ar: Option<&str>, aliases: &[&str]) -> RouteNames {
+        RouteNames {
+            station_id: id.into(),
+            station_name: name.into(),
+            station_name_ar: name_ar.map(str::to_string),
+            governorate: None,
+            delegation: None,
+            base_price: Money::default(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn partial_accented_arabic_and_alias_queries_find_the_route() {
+        assert_eq!(fold("  Station  SFAX-Ville "), "station sfax ville");
+        assert_eq!(fold("Gabès"), "gabes");
+        assert_eq!(fold("إِسْتَاسْيُون"), fold("استاسيون"));
+
+        let routes = || {
+            vec![
+                route("sfx", "Station Sfax Ville", Some("صفاقس المدينة"), &[]),
+                route("gab", "GABES", Some("قابس"), &["gabès centre"]),
+                route("msk", "Monastir", None, &["mestir"]),
+            ]
+        };
+        let ids = |query: &str| search(query, routes()).into_iter().map(|m| m.station_id).collect::<Vec<_>>();
+        assert_eq!(ids("sfax"), ["sfx"]);
+        assert_eq!(ids("Gabès"), ["gab"]);
+        assert_eq!(ids("صفاقس"), ["sfx"]);
+        assert_eq!(ids("mestir"), ["msk"]);
+        assert_eq!(ids("monastr"), ["msk"]);
+        assert!(ids("tunis").is_empty());
+
+        let hit = &search("centre", routes())[0];
+        assert_eq!((hit.station_id.as_str(), hit.matched.as_str()), ("gab", "gabès centre"));
+
+        let aliases = clean_aliases(vec![" Sfax  ville ".into(), "SFAX VILLE".into(), "-".into(), "صفاقس".into()]).unwrap();
+        assert_eq!(aliases, ["Sfax ville", "صفاقس"]);
+        assert!(clean_aliases(vec!["x".repeat(61)]).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::db;
+use crate::db::queue::DestinationSeats;
+use crate::DB_POOL;
+
+// Seats left per destination for the booking destination picker, which asks on every
+// keystroke. The summary is read from the queue once, then only the destinations named by the
+// queue_seat_changes NOTIFY (migration 038) are read again, when next asked for; a route change
+// (migration 020) drops it all. Like the other caches, it is not kept while the cache listener
+// is not connected, and every call then reads the queue.
+
+static CACHE: Lazy<Mutex<Summary>> = Lazy::new(|| Mutex::new(Summary::default()));
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+// Payload of queue_seat_changes after a TRUNCATE
+const ALL_DESTINATIONS: &str = "*";
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    rows: Option<Vec<DestinationSeats>>,
+    // Destinations whose seats changed since they were read
+    stale: HashSet<String>,
+    // Bumped by every invalidation, so rows read while the queue changed are not kept
+    generation: u64,
+}
+
+/// What a read of the summary needs from the database
+#[derive(Debug, PartialEq)]
+pub enum Lookup {
+    Fresh(Vec<DestinationSeats>),
+    Stale { rows: Vec<DestinationSeats>, destinations: Vec<String>, generation: u64 },
+    Missing { generation: u64 },
+}
+
+fn sorted(mut rows: Vec<DestinationSeats>) -> Vec<DestinationSeats> {
+    rows.sort_by(|a, b| (&a.destination_name, &a.sub_route_name).cmp(&(&b.destination_name, &b.sub_route_name)));
+    rows
+}
+
+impl Summary {
+    pub fn lookup(&self) -> Lookup {
+        match &self.rows {
+            None => Lookup::Missing { generation: self.generation },
+            Some(rows) if self.stale.is_empty() => Lookup::Fresh(rows.clone()),
+            Some(rows) => Lookup::Stale { rows: rows.clone(), destinations: self.stale.iter().cloned().collect(), generation: self.generation },
+        }
+    }
+
+    /// `rows` with the re-read `destinations` swapped for `fresh`, kept unless the queue changed meanwhile
+    pub fn refresh(&mut self, generation: u64, rows: Vec<DestinationSeats>, destinations: &[String], fresh: Vec<DestinationSeats>) -> Vec<DestinationSeats> {
+        let mut rows: Vec<DestinationSeats> = rows.into_iter().filter(|r| !destinations.contains(&r.destination_id)).collect();
+        rows.extend(fresh);
+        self.store(generation, rows)
+    }
+
+    pub fn store(&mut self, generation: u64, rows: Vec<DestinationSeats>) -> Vec<DestinationSeats> {
+        let rows = sorted(rows);
+        if self.generation == generation {
+            self.rows = Some(rows.clone());
+            self.stale.clear();
+        }
+        rows
+    }
+
+    pub fn invalidate(&mut self, destination_id: &str) {
+        if destination_id == ALL_DESTINATIONS {
+            self.clear();
+            return;
+        }
+        if self.rows.is_some() {
+            self.stale.insert(destination_id.to_string());
+        }
+        self.generation += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.rows = None;
+        self.stale.clear();
+        self.generation += 1;
+    }
+}
+
+fn cache() -> std::sync::MutexGuard<'static, Summary> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Every destination with seats left, by name then sub-route name
+pub async fn get() -> Result<Vec<DestinationSeats>, String> {
+    if !LISTENING.load(Ordering::SeqCst) {
+        let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+        return Ok(sorted(db::queue::destination_seats(&client, None).await?));
+    }
+    let lookup = cache().lookup();
+    match lookup {
+        Lookup::Fresh(rows) => Ok(rows),
+        Lookup::Stale { rows, destinations, generation } => {
+            // From the primary: a replica may not have the change yet
+            let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+            let fresh = db::queue::destination_seats(&client, Some(&destinations)).await?;
+            Ok(cache().refresh(generation, rows, &destinations, fresh))
+        }
+        Lookup::Missing { generation } => {
+            let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+            let rows = db::queue::destination_seats(&client, None).await?;
+            Ok(cache().store(generation, rows))
+        }
+    }
+}
+
+/// Whether a destination passes the picker's filters; `route_filter` is one of the route groups
+/// or part of a destination name, "ALL" for every destination
+pub fn matches(row: &DestinationSeats, governorate: Option<&str>, delegation: Option<&str>, route_filter: Option<&str>) -> bool {
+    if governorate.is_some_and(|g| row.governorate.as_deref() != Some(g)) {
+        return false;
+    }
+    if delegation.is_some_and(|d| row.delegation.as_deref() != Some(d)) {
+        return false;
+    }
+    let patterns: Vec<String> = match route_filter {
+        None | Some("ALL") => return true,
+        Some("JEMMAL") => vec!["JEMMAL".to_string()],
+        Some("MOKNIN_TEBOULBA") => vec!["MOKNIN".to_string(), "TEBOULBA".to_string()],
+        Some("KSAR_HLEL") => vec!["KSAR HLEL".to_string()],
+        Some(route) => vec![route.to_uppercase()],
+    };
+    let name = row.destination_name.to_uppercase();
+    patterns.iter().any(|pattern| name.contains(pattern.as_str()))
+}
+
+/// Seats changed for a destination on some terminal, or everywhere with "*"
+pub fn invalidate(destination_id: &str) {
+    cache().invalidate(destination_id);
+}
+
+/// The routes changed, or this terminal switched to or from training data
+pub fn clear() {
+    cache().clear();
+}
+
+/// Called by the cache listener as its connection comes and goes
+pub fn set_listening(on: bool) {
+    clear();
+    LISTENING.store(on, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seats(destination_id: &str, name: &str, available_seats: i64) -> DestinationSeats {
+        DestinationSeats { destination_id: destination_id.into(), destination_name: name.into(), available_seats, vehicle_count: 1, ..Default::default() }
+    }
+
+    #[test]
+    fn only_changed_destinations_are_read_again() {
+        let mut summary = Summary::default();
+        let Lookup::Missing { generation } = summary.lookup() else { panic!("empty summary is a miss") };
+        summary.store(generation, vec![seats("s2", "SOUSSE", 8), seats("s1", "MONASTIR", 4)]);
+        let Lookup::Fresh(rows) = summary.lookup() else { panic!("stored summary is fresh") };
+        assert_eq!(rows.iter().map(|r| r.destination_id.as_str()).collect::<Vec<_>>(), ["s1", "s2"]);
+
+        summary.invalidate("s2");
+        let Lookup::Stale { rows, destinations, generation } = summary.lookup() else { panic!("s2 changed") };
+        assert_eq!(destinations, ["s2"]);
+        // Sold out: no row comes back for s2
+        let rows = summary.refresh(generation, rows, &destinations, vec![]);
+        assert_eq!(rows, vec![seats("s1", "MONASTIR", 4)]);
+        assert_eq!(summary.lookup(), Lookup::Fresh(rows.clone()));
+
+        // A change while reading: the rows are served but not kept
+        summary.invalidate("s1");
+        let Lookup::Stale { rows, destinations, generation } = summary.lookup() else { panic!("s1 changed") };
+        summary.invalidate("s1");
+        summary.refresh(generation, rows, &destinations, vec![seats("s1", "MONASTIR", 3)]);
+        assert!(matches!(summary.lookup(), Lookup::Stale { .. }));
+
+        summary.invalidate(ALL_DESTINATIONS);
+        assert!(matches!(summary.lookup(), Lookup::Missing { .. }));
+
+        let ksar = DestinationSeats { governorate: Some("Monastir".into()), ..seats("s3", "Ksar Hlel", 2) };
+        assert!(matches(&ksar, Some("Monastir"), None, Some("KSAR_HLEL")));
+        assert!(matches(&ksar, None, None, Some("ALL")));
+        assert!(!matches(&ksar, Some("Sousse"), None, None));
+        assert!(!matches(&ksar, None, None, Some("JEMMAL")));
+    }
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+
+use crate::announcements::{self, Departure};
+use crate::db;
+use crate::i18n;
+use crate::money::Money;
+use crate::settlement::Settlement;
+use crate::tickets::{ExitPassTicket, PreviousVehicle, SettlementSlip};
+use crate::time::{self, TunisTime};
+
+// End of service on a line: every vehicle still queued for a destination with paid seats leaves
+// in one action, each with the exit pass and settlement slip a trip ended by hand would get
+// (real booking totals, no day pass on top). Vehicles with nothing sold stay in the queue for the
+// closing to carry over. The passes print as one batch after the commit, so a printer failure
+// leaves the departures recorded and is reported in the summary.
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatchedVehicleDto {
+    pub queue_id: String,
+    pub exit_pass_id: String,
+    pub license_plate: String,
+    pub seats_sold: i32,
+    pub amount_collected: Money,
+    pub net_to_driver: Money,
+    pub trip_number: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatchSummaryDto {
+    pub destination_id: String,
+    pub destination_name: String,
+    pub vehicles: Vec<DispatchedVehicleDto>,
+    pub seats_sold: i32,
+    pub amount_collected: Money,
+    pub net_to_driver: Money,
+    /// Plates left in the queue because nothing was sold on them
+    pub left_in_queue: Vec<String>,
+    pub print_batch_id: Option<String>,
+    pub print_error: Option<String>,
+    pub message: String,
+}
+
+impl DispatchSummaryDto {
+    pub fn new(destination_id: &str, destination_name: &str, vehicles: Vec<DispatchedVehicleDto>, left_in_queue: Vec<String>) -> Self {
+        let seats_sold = vehicles.iter().map(|v| v.seats_sold).sum();
+        let amount_collected = vehicles.iter().map(|v| v.amount_collected).sum();
+        let net_to_driver = vehicles.iter().map(|v| v.net_to_driver).sum();
+        let message = i18n::tf(
+            "message.destination_dispatched",
+            &[("count", &vehicles.len()), ("destination", &destination_name), ("seats", &seats_sold), ("amount", &amount_collected), ("left", &left_in_queue.len())],
+        );
+        DispatchSummaryDto {
+            destination_id: destination_id.to_string(),
+            destination_name: destination_name.to_string(),
+            vehicles,
+            seats_sold,
+            amount_collected,
+            net_to_driver,
+            left_in_queue,
+            print_batch_id: None,
+            print_error: None,
+            message,
+        }
+    }
+}
+
+/// Dispatch the destination's queue: exit passes in one transaction, then one print batch and
+/// the announcements
+pub async fn dispatch_all(client: &mut deadpool_postgres::Client, destination_id: &str, staff_id: &str, staff_name: Option<String>) -> Result<DispatchSummaryDto, String> {
+    let tx = client.build_transaction().start().await.map_err(|e| e.to_string())?;
+    let entries = db::queue::lock_destination(&tx, destination_id).await?;
+    let destination_name = match entries.first() {
+        Some(entry) => entry.destination_name.clone(),
+        None => return Err(i18n::t("error.dispatch_queue_empty")),
+    };
+    // Every pass of the batch is stamped with the same transaction time: the previous vehicle
+    // is followed in memory rather than read back
+    let mut previous = db::passes::previous_departure(&tx, destination_id).await?;
+    let mut vehicles = Vec::new();
+    let mut left_in_queue = Vec::new();
+    let mut departures = Vec::new();
+    for entry in entries {
+        let sold = db::booking::sold_for_queue(&tx, &entry.id).await?;
+        if sold.seats <= 0 {
+            left_in_queue.push(entry.license_plate);
+            continue;
+        }
+        let settlement = Settlement::for_departure(&sold, Money::ZERO);
+        let exit_pass_id = db::passes::insert_exit_pass(&tx, &db::passes::NewExitPass {
+            queue_id: &entry.id,
+            vehicle_id: &entry.vehicle_id,
+            license_plate: &entry.license_plate,
+            destination_id,
+            destination_name: &destination_name,
+            settlement: &settlement,
+            created_by: staff_id,
+        })
+        .await?;
+        let trip_number = db::trips::trip_number_today(&tx, &entry.vehicle_id).await?;
+        tx.execute("DELETE FROM vehicle_queue WHERE id = $1", &[&entry.id]).await.map_err(|e| e.to_string())?;
+
+        let seats_used = entry.total_seats - entry.available_seats;
+        let mut ticket = ExitPassTicket::new(&entry.license_plate, &destination_name, seats_used as i64, entry.base_price, sold.due_to_driver())
+            .with_sales(sold.seats as i64, sold.collected)
+            .with_previous(previous.take())
+            .with_trip_number(trip_number);
+        // Passes built in the same millisecond would share a number
+        ticket.ticket_number = format!("{}-{}", ticket.ticket_number, vehicles.len() + 1);
+        let slip = SettlementSlip::new(&ticket, settlement);
+        previous = Some(PreviousVehicle {
+            license_plate: entry.license_plate.clone(),
+            exit_time: TunisTime(crate::clock::now().with_timezone(&time::TZ)).fmt_ticket(),
+        });
+        departures.push((ticket, slip));
+        vehicles.push(DispatchedVehicleDto {
+            queue_id: entry.id,
+            exit_pass_id,
+            license_plate: entry.license_plate,
+            seats_sold: sold.seats,
+            amount_collected: sold.collected,
+            net_to_driver: settlement.net_to_driver,
+            trip_number,
+        });
+    }
+    if vehicles.is_empty() {
+        return Err(i18n::t("error.dispatch_nothing_sold"));
+    }
+    db::queue::renumber_destination(&tx, destination_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    let mut summary = DispatchSummaryDto::new(destination_id, &destination_name, vehicles, left_in_queue);
+    println!("🚌 [DISPATCH] {}", summary.message);
+
+    let announced: Vec<Departure> = summary
+        .vehicles
+        .iter()
+        .map(|v| Departure { destination_id: destination_id.to_string(), destination_name: destination_name.clone(), license_plate: v.license_plate.clone() })
+        .collect();
+    tauri::async_runtime::spawn(async move {
+        for departure in &announced {
+            announcements::announce_departure(departure).await;
+        }
+    });
+
+    match crate::PRINTER_SERVICE.print_exit_passes_with_settlements(&departures, staff_name).await {
+        Ok(batch_id) => summary.print_batch_id = Some(batch_id),
+        Err(e) => {
+            println!("❌ [DISPATCH] Failed to print the exit passes: {}", e);
+            summary.print_error = Some(e);
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vehicle(plate: &str, seats: i32, collected: f64, net: f64) -> DispatchedVehicleDto {
+        DispatchedVehicleDto {
+            queue_id: format!("q-{}", plate),
+            exit_pass_id: format!("x-{}", plate),
+            license_plate: plate.to_string(),
+            seats_sold: seats,
+            amount_collected: Money::from_tnd(collected),
+            net_to_driver: Money::from_tnd(net),
+            trip_number: 1,
+        }
+    }
+
+    #[test]
+    fn summary_adds_up_the_departures() {
+        let vehicles = vec![vehicle("123 TUN 1", 3, 12.6, 12.0), vehicle("456 TUN 2", 1, 4.2, 4.0)];
+        let summary = DispatchSummaryDto::new("sousse", "Sousse", vehicles, vec!["789 TUN 3".to_string()]);
+        assert_eq!(summary.seats_sold, 4);
+        assert_eq!(summary.amount_collected, Money::from_tnd(16.8));
+        assert_eq!(summary.net_to_driver, Money::from_tnd(16.0));
+        assert_eq!(summary.left_in_queue, vec!["789 TUN 3"]);
+        assert!(summary.print_batch_id.is_none());
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["vehicles"][0]["licensePlate"], "123 TUN 1");
+        assert_eq!(json["amountCollected"], 16.8);
+    }
+}
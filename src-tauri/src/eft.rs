@@ -0,0 +1,326 @@
+// Card terminal (EFT) on the station LAN. When EFT_ENDPOINT is set, the card share of a booking
+// is sent to the terminal and must be approved before the booking commits; without it card
+// payments are confirmed on the terminal by hand and only recorded here, as before.
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::money::Money;
+use crate::payments::{PaymentMethod, PaymentPart};
+
+// Long enough for the customer to insert the card and type a PIN
+const DEFAULT_TIMEOUT_SECS: u64 = 90;
+
+/// Wire format spoken by the terminal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EftProtocol {
+    Http, // JSON POST to the endpoint URL
+    Tcp,  // one JSON object per line over a raw socket (host:port)
+}
+
+impl EftProtocol {
+    fn from_env_value(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "http" | "https" | "rest" => Some(EftProtocol::Http),
+            "tcp" | "socket" | "raw" => Some(EftProtocol::Tcp),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EftConfig {
+    pub protocol: EftProtocol,
+    pub endpoint: String,
+    pub timeout: Duration,
+}
+
+impl EftConfig {
+    /// EFT_ENDPOINT, EFT_PROTOCOL (inferred from the endpoint when unset) and EFT_TIMEOUT in seconds
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("EFT_ENDPOINT").ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())?;
+        let protocol = std::env::var("EFT_PROTOCOL")
+            .ok()
+            .and_then(|v| EftProtocol::from_env_value(&v))
+            .unwrap_or(if endpoint.starts_with("http://") || endpoint.starts_with("https://") { EftProtocol::Http } else { EftProtocol::Tcp });
+        let timeout = std::env::var("EFT_TIMEOUT")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TIMEOUT_SECS);
+        Some(Self { protocol, endpoint, timeout: Duration::from_secs(timeout) })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TerminalRequest<'a> {
+    #[serde(rename = "type")]
+    kind: &'a str, // SALE or VOID
+    reference: &'a str,
+    amount: i64, // millimes
+    currency: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_id: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TerminalResponse {
+    status: String,
+    #[serde(default)]
+    authorization_code: Option<String>,
+    #[serde(default)]
+    transaction_id: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// An approved sale on the terminal
+#[derive(Debug, Clone, PartialEq)]
+pub struct Authorization {
+    pub reference: String,
+    pub transaction_id: String,
+    pub authorization_code: String,
+    pub amount: Money,
+}
+
+fn parse_response(raw: &str) -> Result<TerminalResponse, String> {
+    serde_json::from_str(raw.trim()).map_err(|e| format!("Réponse du terminal de paiement illisible: {}", e))
+}
+
+// Why an exchange with the terminal failed. Only a request that never reached the terminal is
+// known not to have charged anything; after a timeout or a garbled reply the sale may have gone
+// through, so it has to be voided.
+enum ExchangeError {
+    NotSent(String),
+    NoAnswer(String),
+}
+
+impl ExchangeError {
+    fn message(self) -> String {
+        match self {
+            ExchangeError::NotSent(message) | ExchangeError::NoAnswer(message) => message,
+        }
+    }
+}
+
+async fn exchange(config: &EftConfig, request: &TerminalRequest<'_>) -> Result<TerminalResponse, ExchangeError> {
+    match config.protocol {
+        EftProtocol::Http => {
+            let client = reqwest::Client::builder().timeout(config.timeout).build().map_err(|e| ExchangeError::NotSent(e.to_string()))?;
+            let response = client.post(&config.endpoint).json(request).send().await.map_err(|e| {
+                let message = format!("Terminal de paiement injoignable: {}", e);
+                if e.is_connect() || e.is_builder() { ExchangeError::NotSent(message) } else { ExchangeError::NoAnswer(message) }
+            })?;
+            let body = response.text().await.map_err(|e| ExchangeError::NoAnswer(e.to_string()))?;
+            parse_response(&body).map_err(ExchangeError::NoAnswer)
+        }
+        EftProtocol::Tcp => {
+            let mut line = serde_json::to_string(request).map_err(|e| ExchangeError::NotSent(e.to_string()))?;
+            line.push('\n');
+            let mut stream = tokio::time::timeout(config.timeout, TcpStream::connect(&config.endpoint))
+                .await
+                .map_err(|_| ExchangeError::NotSent("Délai dépassé en attendant le terminal de paiement".to_string()))?
+                .map_err(|e| ExchangeError::NotSent(format!("Terminal de paiement injoignable: {}", e)))?;
+            let reply = tokio::time::timeout(config.timeout, async {
+                stream.write_all(line.as_bytes()).await?;
+                let mut reply = String::new();
+                BufReader::new(stream).read_line(&mut reply).await?;
+                Ok::<String, std::io::Error>(reply)
+            })
+            .await
+            .map_err(|_| ExchangeError::NoAnswer("Délai dépassé en attendant le terminal de paiement".to_string()))?
+            .map_err(|e| ExchangeError::NoAnswer(format!("Terminal de paiement injoignable: {}", e)))?;
+            parse_response(&reply).map_err(ExchangeError::NoAnswer)
+        }
+    }
+}
+
+// The sale's outcome is unknown (or it was approved without a code we can record): cancel
+// whatever the terminal did under `reference` so the customer is not left charged
+fn void_unconfirmed_sale(config: &EftConfig, amount: Money, reference: &str) {
+    let config = config.clone();
+    let reference = reference.to_string();
+    println!("↩️ [EFT] No usable answer for {}, voiding by reference", reference);
+    tauri::async_runtime::spawn(async move {
+        let request = TerminalRequest { kind: "VOID", reference: &reference, amount: amount.millimes(), currency: "TND", transaction_id: None };
+        match exchange(&config, &request).await {
+            Ok(_) => println!("✅ [EFT] Voided {}", reference),
+            Err(e) => println!("❌ [EFT] Void of {} failed, reverse it on the terminal: {}", reference, e.message()),
+        }
+    });
+}
+
+/// Ask the terminal to charge `amount` and wait for the customer; only an approval is Ok
+pub async fn authorize(config: &EftConfig, amount: Money, reference: &str) -> Result<Authorization, String> {
+    let request = TerminalRequest { kind: "SALE", reference, amount: amount.millimes(), currency: "TND", transaction_id: None };
+    let response = match exchange(config, &request).await {
+        Ok(response) => response,
+        Err(ExchangeError::NotSent(message)) => return Err(message),
+        Err(ExchangeError::NoAnswer(message)) => {
+            void_unconfirmed_sale(config, amount, reference);
+            return Err(message);
+        }
+    };
+    if !response.status.eq_ignore_ascii_case("APPROVED") {
+        return Err(format!("Paiement par carte refusé: {}", response.message.unwrap_or(response.status)));
+    }
+    let Some(authorization_code) = response.authorization_code.filter(|code| !code.trim().is_empty()) else {
+        void_unconfirmed_sale(config, amount, reference);
+        return Err("Le terminal a approuvé sans code d'autorisation".to_string());
+    };
+    Ok(Authorization {
+        reference: reference.to_string(),
+        transaction_id: response.transaction_id.unwrap_or_else(|| reference.to_string()),
+        authorization_code,
+        amount,
+    })
+}
+
+pub async fn void(config: &EftConfig, authorization: &Authorization) -> Result<(), String> {
+    let request = TerminalRequest {
+        kind: "VOID",
+        reference: &authorization.reference,
+        amount: authorization.amount.millimes(),
+        currency: "TND",
+        transaction_id: Some(&authorization.transaction_id),
+    };
+    let response = exchange(config, &request).await.map_err(ExchangeError::message)?;
+    if response.status.eq_ignore_ascii_case("APPROVED") || response.status.eq_ignore_ascii_case("VOIDED") {
+        Ok(())
+    } else {
+        Err(format!("Annulation refusée par le terminal: {}", response.message.unwrap_or(response.status)))
+    }
+}
+
+/// Charge the card share of `parts` on the terminal, if there is one and a terminal is configured
+pub async fn authorize_card_parts(parts: &[PaymentPart], reference: &str) -> Result<CardAuthorization, String> {
+    let card_amount: Money = parts.iter().filter(|p| p.method == PaymentMethod::Card).map(|p| p.amount).sum();
+    let config = match EftConfig::from_env() {
+        Some(config) if card_amount.is_positive() => config,
+        _ => return Ok(CardAuthorization { pending: None }),
+    };
+    println!("💳 [EFT] Sending {} TND to terminal ({})", card_amount, reference);
+    let authorization = authorize(&config, card_amount, reference).await?;
+    println!("✅ [EFT] Approved {} (auth {})", authorization.transaction_id, authorization.authorization_code);
+    Ok(CardAuthorization { pending: Some((config, authorization)) })
+}
+
+/// An approval the booking still has to be committed against. Dropping it before `confirm`
+/// voids the sale, so any error after the customer paid gives the money back.
+pub struct CardAuthorization {
+    pending: Option<(EftConfig, Authorization)>,
+}
+
+impl CardAuthorization {
+    pub fn authorization_code(&self) -> Option<&str> {
+        self.pending.as_ref().map(|(_, auth)| auth.authorization_code.as_str())
+    }
+
+    pub fn transaction_id(&self) -> Option<&str> {
+        self.pending.as_ref().map(|(_, auth)| auth.transaction_id.as_str())
+    }
+
+    /// The booking is committed; keep the sale
+    pub fn confirm(mut self) {
+        self.pending = None;
+    }
+}
+
+impl Drop for CardAuthorization {
+    fn drop(&mut self) {
+        if let Some((config, authorization)) = self.pending.take() {
+            println!("↩️ [EFT] Booking not saved, voiding {}", authorization.transaction_id);
+            tauri::async_runtime::spawn(async move {
+                match void(&config, &authorization).await {
+                    Ok(()) => println!("✅ [EFT] Voided {}", authorization.transaction_id),
+                    Err(e) => println!("❌ [EFT] Void of {} failed, reverse it on the terminal: {}", authorization.transaction_id, e),
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn amounts_are_sent_in_millimes() {
+        assert_eq!(Money::from_tnd(2.5).millimes(), 2500);
+        assert_eq!(Money::from_tnd(10.4).millimes(), 10400);
+        assert_eq!(Money::from_tnd(0.0004).millimes(), 0);
+    }
+
+    #[tokio::test]
+    async fn tcp_sale_returns_the_authorization_code_and_declines_are_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = EftConfig {
+            protocol: EftProtocol::Tcp,
+            endpoint: listener.local_addr().unwrap().to_string(),
+            timeout: Duration::from_secs(5),
+        };
+        let terminal = tokio::spawn(async move {
+            let replies = [
+                r#"{"status":"APPROVED","authorizationCode":"A1B2C3","transactionId":"T-9"}"#,
+                r#"{"status":"DECLINED","message":"Solde insuffisant"}"#,
+            ];
+            let mut requests = Vec::new();
+            for reply in replies {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                requests.push(line);
+                reader.get_mut().write_all(format!("{}\n", reply).as_bytes()).await.unwrap();
+            }
+            requests
+        });
+
+        let approved = authorize(&config, Money::from_tnd(5.4), "req-1").await.unwrap();
+        assert_eq!(approved.authorization_code, "A1B2C3");
+        assert_eq!(approved.transaction_id, "T-9");
+        let declined = authorize(&config, Money::from_tnd(5.4), "req-2").await.unwrap_err();
+        assert!(declined.contains("Solde insuffisant"));
+
+        let requests = terminal.await.unwrap();
+        let sale: serde_json::Value = serde_json::from_str(&requests[0]).unwrap();
+        assert_eq!(sale["type"], "SALE");
+        assert_eq!(sale["amount"], 5400);
+        assert_eq!(sale["reference"], "req-1");
+    }
+
+    #[tokio::test]
+    async fn an_unreadable_sale_reply_is_voided_by_reference() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = EftConfig {
+            protocol: EftProtocol::Tcp,
+            endpoint: listener.local_addr().unwrap().to_string(),
+            timeout: Duration::from_secs(5),
+        };
+        let terminal = tokio::spawn(async move {
+            let replies = ["<html>busy</html>", r#"{"status":"VOIDED"}"#];
+            let mut requests = Vec::new();
+            for reply in replies {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                requests.push(line);
+                reader.get_mut().write_all(format!("{}\n", reply).as_bytes()).await.unwrap();
+            }
+            requests
+        });
+
+        let error = authorize(&config, Money::from_tnd(3.0), "req-7").await.unwrap_err();
+        assert!(error.contains("illisible"));
+
+        let requests = terminal.await.unwrap();
+        let void: serde_json::Value = serde_json::from_str(&requests[1]).unwrap();
+        assert_eq!(void["type"], "VOID");
+        assert_eq!(void["reference"], "req-7");
+        assert_eq!(void["amount"], 3000);
+    }
+}
@@ -0,0 +1,447 @@
+use serde::{Deserialize, Serialize};
+
+// Thermal printers do not understand UTF-8: text must be sent in the code page
+// selected with ESC t, otherwise "Émis par" comes out as garbage.
+
+/// Printer character tables supported by the ticket pipeline
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CodePage {
+    Cp437,
+    Cp850,
+    #[default]
+    Cp858, // CP850 with the Euro sign at 0xD5
+}
+
+// Unicode characters for bytes 0x80..=0xFF
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
+];
+
+const CP850_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', 'ø', '£', 'Ø', '×', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '®', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', 'Á', 'Â', 'À', '©', '╣', '║', '╗', '╝', '¢', '¥', '┐',
+    '└', '┴', '┬', '├', '─', '┼', 'ã', 'Ã', '╚', '╔', '╩', '╦', '╠', '═', '╬', '¤',
+    'ð', 'Ð', 'Ê', 'Ë', 'È', 'ı', 'Í', 'Î', 'Ï', '┘', '┌', '█', '▄', '¦', 'Ì', '▀',
+    'Ó', 'ß', 'Ô', 'Ò', 'õ', 'Õ', 'µ', 'þ', 'Þ', 'Ú', 'Û', 'Ù', 'ý', 'Ý', '¯', '´',
+    '\u{00AD}', '±', '‗', '¾', '¶', '§', '÷', '¸', '°', '¨', '·', '¹', '³', '²', '■', '\u{00A0}',
+];
+
+impl CodePage {
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "cp437" | "437" | "pc437" => Some(CodePage::Cp437),
+            "cp850" | "850" | "pc850" => Some(CodePage::Cp850),
+            "cp858" | "858" | "pc858" => Some(CodePage::Cp858),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CodePage::Cp437 => "CP437",
+            CodePage::Cp850 => "CP850",
+            CodePage::Cp858 => "CP858",
+        }
+    }
+
+    /// ESC t n: select the character code table (Epson numbering)
+    pub fn select_command(&self) -> [u8; 3] {
+        let n = match self {
+            CodePage::Cp437 => 0,
+            CodePage::Cp850 => 2,
+            CodePage::Cp858 => 19,
+        };
+        [0x1B, 0x74, n]
+    }
+
+    fn byte_for(&self, c: char) -> Option<u8> {
+        if c.is_ascii() {
+            return Some(c as u8);
+        }
+        if *self == CodePage::Cp858 && c == '€' {
+            return Some(0xD5);
+        }
+        let table = match self {
+            CodePage::Cp437 => &CP437_HIGH,
+            CodePage::Cp850 | CodePage::Cp858 => &CP850_HIGH,
+        };
+        table.iter().position(|t| *t == c).map(|pos| 0x80 + pos as u8)
+    }
+}
+
+// Closest ASCII spelling for characters missing from the code page
+fn fallback(c: char) -> &'static str {
+    match c {
+        '’' | '‘' | '`' => "'",
+        '“' | '”' | '«' | '»' => "\"",
+        '–' | '—' | '‐' => "-",
+        '…' => "...",
+        'œ' => "oe",
+        'Œ' => "OE",
+        '€' => "EUR",
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => "A",
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a",
+        'Ç' => "C",
+        'ç' => "c",
+        'È' | 'É' | 'Ê' | 'Ë' => "E",
+        'è' | 'é' | 'ê' | 'ë' => "e",
+        'Ì' | 'Í' | 'Î' | 'Ï' => "I",
+        'ì' | 'í' | 'î' | 'ï' => "i",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' => "O",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => "o",
+        'Ù' | 'Ú' | 'Û' | 'Ü' => "U",
+        'ù' | 'ú' | 'û' | 'ü' => "u",
+        '°' => "o",
+        '\u{00A0}' => " ",
+        '٠' => "0",
+        '١' => "1",
+        '٢' => "2",
+        '٣' => "3",
+        '٤' => "4",
+        '٥' => "5",
+        '٦' => "6",
+        '٧' => "7",
+        '٨' => "8",
+        '٩' => "9",
+        _ => "?",
+    }
+}
+
+fn push_char(out: &mut Vec<u8>, c: char, code_page: CodePage) {
+    match code_page.byte_for(c) {
+        Some(b) => out.push(b),
+        None => out.extend_from_slice(fallback(c).as_bytes()),
+    }
+}
+
+// Arabic is printed from the PC864 table, which holds the contextual shapes of each letter
+// rather than the letters themselves. Runs of Arabic are shaped here and reversed into
+// visual order, since the printer only lays text out left to right.
+const ARABIC_SELECT: [u8; 3] = [0x1B, 0x74, 37];
+
+struct ArabicLetter {
+    letter: char,
+    dual: bool, // also joins the following letter
+    forms: [u8; 4], // isolated, final, initial, medial
+}
+
+// PC864 lacks most final and medial glyphs; its isolated and initial ones are drawn to connect
+const ARABIC_LETTERS: &[ArabicLetter] = &[
+    ArabicLetter { letter: 'ء', dual: false, forms: [0xC1, 0xC1, 0xC1, 0xC1] },
+    ArabicLetter { letter: 'آ', dual: false, forms: [0xC2, 0xA2, 0xC2, 0xA2] },
+    ArabicLetter { letter: 'أ', dual: false, forms: [0xC3, 0xA5, 0xC3, 0xA5] },
+    ArabicLetter { letter: 'ؤ', dual: false, forms: [0xC4, 0xC4, 0xC4, 0xC4] },
+    ArabicLetter { letter: 'إ', dual: false, forms: [0xC7, 0xA8, 0xC7, 0xA8] },
+    ArabicLetter { letter: 'ئ', dual: true, forms: [0xC6, 0xC6, 0xC6, 0xC6] },
+    ArabicLetter { letter: 'ا', dual: false, forms: [0xC7, 0xA8, 0xC7, 0xA8] },
+    ArabicLetter { letter: 'ب', dual: true, forms: [0xA9, 0xA9, 0xC8, 0xC8] },
+    ArabicLetter { letter: 'ة', dual: false, forms: [0xC9, 0xC9, 0xC9, 0xC9] },
+    ArabicLetter { letter: 'ت', dual: true, forms: [0xAA, 0xAA, 0xCA, 0xCA] },
+    ArabicLetter { letter: 'ث', dual: true, forms: [0xAB, 0xAB, 0xCB, 0xCB] },
+    ArabicLetter { letter: 'ج', dual: true, forms: [0xAD, 0xAD, 0xCC, 0xCC] },
+    ArabicLetter { letter: 'ح', dual: true, forms: [0xAE, 0xAE, 0xCD, 0xCD] },
+    ArabicLetter { letter: 'خ', dual: true, forms: [0xAF, 0xAF, 0xCE, 0xCE] },
+    ArabicLetter { letter: 'د', dual: false, forms: [0xCF, 0xCF, 0xCF, 0xCF] },
+    ArabicLetter { letter: 'ذ', dual: false, forms: [0xD0, 0xD0, 0xD0, 0xD0] },
+    ArabicLetter { letter: 'ر', dual: false, forms: [0xD1, 0xD1, 0xD1, 0xD1] },
+    ArabicLetter { letter: 'ز', dual: false, forms: [0xD2, 0xD2, 0xD2, 0xD2] },
+    ArabicLetter { letter: 'س', dual: true, forms: [0xBC, 0xBC, 0xD3, 0xD3] },
+    ArabicLetter { letter: 'ش', dual: true, forms: [0xBD, 0xBD, 0xD4, 0xD4] },
+    ArabicLetter { letter: 'ص', dual: true, forms: [0xBE, 0xBE, 0xD5, 0xD5] },
+    ArabicLetter { letter: 'ض', dual: true, forms: [0xEB, 0xEB, 0xD6, 0xD6] },
+    ArabicLetter { letter: 'ط', dual: true, forms: [0xD7, 0xD7, 0xD7, 0xD7] },
+    ArabicLetter { letter: 'ظ', dual: true, forms: [0xD8, 0xD8, 0xD8, 0xD8] },
+    ArabicLetter { letter: 'ع', dual: true, forms: [0xDF, 0xC5, 0xD9, 0xEC] },
+    ArabicLetter { letter: 'غ', dual: true, forms: [0xEE, 0xED, 0xDA, 0xF7] },
+    ArabicLetter { letter: 'ـ', dual: true, forms: [0xE0, 0xE0, 0xE0, 0xE0] },
+    ArabicLetter { letter: 'ف', dual: true, forms: [0xBA, 0xBA, 0xE1, 0xE1] },
+    ArabicLetter { letter: 'ق', dual: true, forms: [0xF8, 0xF8, 0xE2, 0xE2] },
+    ArabicLetter { letter: 'ك', dual: true, forms: [0xFC, 0xFC, 0xE3, 0xE3] },
+    ArabicLetter { letter: 'ل', dual: true, forms: [0xFB, 0xFB, 0xE4, 0xE4] },
+    ArabicLetter { letter: 'م', dual: true, forms: [0xEF, 0xEF, 0xE5, 0xE5] },
+    ArabicLetter { letter: 'ن', dual: true, forms: [0xF2, 0xF2, 0xE6, 0xE6] },
+    ArabicLetter { letter: 'ه', dual: true, forms: [0xF3, 0xF3, 0xE7, 0xF4] },
+    ArabicLetter { letter: 'و', dual: false, forms: [0xE8, 0xE8, 0xE8, 0xE8] },
+    ArabicLetter { letter: 'ى', dual: true, forms: [0xE9, 0xF5, 0xEA, 0xEA] },
+    ArabicLetter { letter: 'ي', dual: true, forms: [0xFD, 0xF6, 0xEA, 0xEA] },
+];
+
+fn arabic_letter(c: char) -> Option<&'static ArabicLetter> {
+    ARABIC_LETTERS.iter().find(|l| l.letter == c)
+}
+
+// Lam followed by any alef is a single glyph: isolated, final
+fn lam_alef(alef: char) -> Option<[u8; 2]> {
+    match alef {
+        'ا' | 'إ' => Some([0x9D, 0x9E]),
+        'أ' => Some([0x99, 0x9A]),
+        'آ' => Some([0xF9, 0xFA]),
+        _ => None,
+    }
+}
+
+/// Letters, harakat and Arabic punctuation; spaces only count between two of these
+pub fn is_arabic(c: char) -> bool {
+    matches!(c as u32, 0x060C | 0x061B | 0x061F | 0x0621..=0x063A | 0x0640..=0x0652)
+}
+
+/// PC864 bytes for a run of Arabic in logical order, returned in visual (left to right) order
+pub fn shape_arabic(run: &str) -> Vec<u8> {
+    // Harakat have no glyph of their own in PC864
+    let chars: Vec<char> = run.chars().filter(|c| !matches!(*c as u32, 0x064B..=0x0652)).collect();
+    let joins_next = |i: usize| chars.get(i + 1).is_some_and(|n| *n != 'ء' && arabic_letter(*n).is_some());
+
+    let mut out = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let joins_prev = i > 0 && arabic_letter(chars[i - 1]).is_some_and(|l| l.dual);
+        if c == 'ل' {
+            if let Some(ligature) = chars.get(i + 1).and_then(|n| lam_alef(*n)) {
+                out.push(ligature[joins_prev as usize]);
+                i += 2;
+                continue;
+            }
+        }
+        match arabic_letter(c) {
+            Some(letter) => {
+                let form = match (joins_prev, letter.dual && joins_next(i)) {
+                    (false, false) => 0,
+                    (true, false) => 1,
+                    (false, true) => 2,
+                    (true, true) => 3,
+                };
+                out.push(letter.forms[form]);
+            }
+            None => out.push(match c {
+                '،' => 0xAC,
+                '؛' => 0xBB,
+                '؟' => 0xBF,
+                ' ' => b' ',
+                _ => b'?',
+            }),
+        }
+        i += 1;
+    }
+    out.reverse();
+    out
+}
+
+// Next UTF-8 character at `i` and its length in bytes
+fn decode_char(bytes: &[u8], i: usize) -> Option<(char, usize)> {
+    let len = match bytes[i] {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => return None,
+    };
+    let s = std::str::from_utf8(bytes.get(i..i + len)?).ok()?;
+    s.chars().next().map(|c| (c, len))
+}
+
+// End of the Arabic run starting at `start`: trailing spaces stay outside it
+fn arabic_run_end(bytes: &[u8], start: usize) -> usize {
+    let mut end = start;
+    let mut i = start;
+    while i < bytes.len() {
+        match decode_char(bytes, i) {
+            Some((c, len)) if is_arabic(c) => {
+                i += len;
+                end = i;
+            }
+            Some((' ', len)) => i += len,
+            _ => break,
+        }
+    }
+    end
+}
+
+// Little-endian length stored in `n` bytes at `at`; missing bytes count as zero
+fn length_at(bytes: &[u8], at: usize, n: usize) -> usize {
+    (0..n).map(|k| (*bytes.get(at + k).unwrap_or(&0) as usize) << (8 * k)).sum()
+}
+
+// Length of the ESC or GS command at `i`, parameters and data included, so that QR
+// payloads, barcodes and raster images are never mistaken for text. Commands missing
+// from this table only keep their first byte after the prefix.
+fn command_len(bytes: &[u8], i: usize) -> usize {
+    let at = |k: usize| bytes.get(i + k).copied().unwrap_or(0);
+    let len = match (bytes[i], at(1)) {
+        // ESC * m nL nH: bit image, 24-dot modes take three bytes per column
+        (0x1B, b'*') => 5 + length_at(bytes, i + 3, 2) * if at(2) >= 32 { 3 } else { 1 },
+        (0x1B, b'p') => 5,
+        (0x1B, b'$') | (0x1B, b'\\') => 4,
+        (0x1B, b'2') => 2,
+        (0x1B, _) => 3,
+        // GS ( fn pL pH: QR codes and other two-dimensional symbols
+        (0x1D, b'(') => 5 + length_at(bytes, i + 3, 2),
+        // GS v 0 m xL xH yL yH: raster image of xL+xH*256 bytes by yL+yH*256 rows
+        (0x1D, b'v') => 8 + length_at(bytes, i + 4, 2) * length_at(bytes, i + 6, 2),
+        // GS 8 L p1..p4: raster data with a 32-bit length
+        (0x1D, b'8') => 7 + length_at(bytes, i + 3, 4),
+        (0x1D, b'V') => if matches!(at(2), 65 | 66) { 4 } else { 3 },
+        // GS k m: barcodes 0..=6 are NUL terminated, the others carry their length
+        (0x1D, b'k') if at(2) <= 6 => {
+            let data = i + 3;
+            bytes[data.min(bytes.len())..].iter().position(|b| *b == 0).map_or(bytes.len() - i, |end| 4 + end)
+        }
+        (0x1D, b'k') => 4 + at(3) as usize,
+        (0x1D, b'L') | (0x1D, b'W') => 4,
+        (0x1D, _) => 3,
+        _ => 1,
+    };
+    len.min(bytes.len() - i)
+}
+
+/// Convert a job built with UTF-8 text into printer bytes: every ESC @ (which resets
+/// the code table) is followed by ESC t, and the job starts with one if it has no ESC @.
+/// Arabic runs switch to PC864 and back. ESC and GS commands are copied whole with their
+/// parameters, and bytes that are not valid UTF-8 are passed through untouched.
+pub fn encode_job(bytes: &[u8], code_page: CodePage) -> Vec<u8> {
+    let select = code_page.select_command();
+    let mut out = Vec::with_capacity(bytes.len() + select.len());
+    if !bytes.starts_with(&[0x1B, 0x40]) {
+        out.extend_from_slice(&select);
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(&[0x1B, 0x40]) {
+            out.extend_from_slice(&[0x1B, 0x40]);
+            out.extend_from_slice(&select);
+            i += 2;
+            continue;
+        }
+
+        let b = bytes[i];
+        if b == 0x1B || b == 0x1D {
+            let len = command_len(bytes, i);
+            out.extend_from_slice(&bytes[i..i + len]);
+            i += len;
+            continue;
+        }
+        if b < 0x80 {
+            out.push(b);
+            i += 1;
+            continue;
+        }
+
+        match decode_char(bytes, i) {
+            Some((c, _)) if is_arabic(c) => {
+                let end = arabic_run_end(bytes, i);
+                let run = std::str::from_utf8(&bytes[i..end]).unwrap_or_default();
+                out.extend_from_slice(&ARABIC_SELECT);
+                out.extend_from_slice(&shape_arabic(run));
+                out.extend_from_slice(&select);
+                i = end;
+            }
+            Some((c, len)) => {
+                push_char(&mut out, c, code_page);
+                i += len;
+            }
+            None => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Text alone, without the code page selection that starts every job
+    fn encode(text: &str, code_page: CodePage) -> Vec<u8> {
+        encode_job(text.as_bytes(), code_page)[3..].to_vec()
+    }
+
+    #[test]
+    fn encodes_french_accents() {
+        assert_eq!(encode("Émis par", CodePage::Cp850), vec![0x90, b'm', b'i', b's', b' ', b'p', b'a', b'r']);
+        assert_eq!(encode("é è à ç", CodePage::Cp437), vec![0x82, b' ', 0x8A, b' ', 0x85, b' ', 0x87]);
+        assert_eq!(encode("N° 5", CodePage::Cp858), vec![b'N', 0xF8, b' ', b'5']);
+    }
+
+    #[test]
+    fn euro_sign_only_in_cp858() {
+        assert_eq!(encode("€", CodePage::Cp858), vec![0xD5]);
+        assert_eq!(encode("€", CodePage::Cp850), b"EUR".to_vec());
+        assert_eq!(encode("œ", CodePage::Cp858), b"oe".to_vec());
+        assert_eq!(encode("✓", CodePage::Cp858), b"?".to_vec());
+    }
+
+    #[test]
+    fn job_gets_code_page_select_after_each_init() {
+        let job: Vec<u8> = [&[0x1B, 0x40][..], "é\n".as_bytes(), &[0x1B, 0x40], &[0x1D, 0x56, 0x00]].concat();
+        let expected: Vec<u8> = vec![
+            0x1B, 0x40, 0x1B, 0x74, 19, 0x82, b'\n',
+            0x1B, 0x40, 0x1B, 0x74, 19, 0x1D, 0x56, 0x00,
+        ];
+        assert_eq!(encode_job(&job, CodePage::Cp858), expected);
+        assert_eq!(&encode_job(b"A", CodePage::Cp850)[..], &[0x1B, 0x74, 2, b'A']);
+    }
+
+    #[test]
+    fn arabic_is_shaped_and_reversed() {
+        // باب: initial beh, final alef, isolated beh (alef does not join what follows)
+        assert_eq!(shape_arabic("باب"), vec![0xA9, 0xA8, 0xC8]);
+        // لا is one glyph; مرحبا keeps ر and ا unjoined to the left
+        assert_eq!(shape_arabic("لا"), vec![0x9D]);
+        assert_eq!(shape_arabic("مرحبا"), vec![0xA8, 0xC8, 0xCD, 0xD1, 0xE5]);
+        // Harakat are dropped
+        assert_eq!(shape_arabic("بَاب"), shape_arabic("باب"));
+    }
+
+    #[test]
+    fn arabic_runs_switch_code_table() {
+        let job = "Code الوجهة: 5".as_bytes();
+        let mut expected: Vec<u8> = vec![0x1B, 0x74, 19];
+        expected.extend_from_slice(b"Code ");
+        expected.extend_from_slice(&[0x1B, 0x74, 37]);
+        expected.extend_from_slice(&shape_arabic("الوجهة"));
+        expected.extend_from_slice(&[0x1B, 0x74, 19]);
+        expected.extend_from_slice(b": 5");
+        assert_eq!(encode_job(job, CodePage::Cp858), expected);
+        // Spaces between Arabic words stay inside the run, in visual order
+        let two_words = encode_job("رقم التذكرة".as_bytes(), CodePage::Cp850);
+        assert_eq!(two_words[3..6], [0x1B, 0x74, 37]);
+        assert!(two_words.ends_with(&[0x1B, 0x74, 2]));
+        assert_eq!(&two_words[6..two_words.len() - 3], &shape_arabic("رقم التذكرة")[..]);
+    }
+
+    #[test]
+    fn non_utf8_bytes_pass_through() {
+        assert_eq!(encode_job(&[0x1B, 0x40, 0xFF, 0x41], CodePage::Cp437), vec![0x1B, 0x40, 0x1B, 0x74, 0, 0xFF, 0x41]);
+    }
+
+    #[test]
+    fn command_parameters_are_not_transcoded() {
+        // "é" in UTF-8 is C3 A9; inside a QR payload or an image it must reach the printer as is
+        let qr: Vec<u8> = [&[0x1D, b'(', b'k', 6, 0, 49, 80, 48][..], "é\n".as_bytes()].concat();
+        let raster: Vec<u8> = vec![0x1D, b'v', b'0', 0, 2, 0, 1, 0, 0xC3, 0xA9];
+        let image: Vec<u8> = vec![0x1B, b'*', 0, 2, 0, 0xC3, 0xA9];
+        for command in [&qr, &raster, &image] {
+            let job: Vec<u8> = [&command[..], "é".as_bytes()].concat();
+            let mut expected = vec![0x1B, 0x74, 19];
+            expected.extend_from_slice(command);
+            expected.push(0x82);
+            assert_eq!(encode_job(&job, CodePage::Cp858), expected);
+        }
+        // A code 39 barcode ends at its NUL, and the text after it is still transcoded
+        let barcode: Vec<u8> = [&[0x1D, b'k', 4][..], b"AB12", &[0], "é".as_bytes()].concat();
+        assert!(encode_job(&barcode, CodePage::Cp858).ends_with(&[b'2', 0, 0x82]));
+    }
+}
@@ -0,0 +1,75 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::json;
+
+use crate::audit;
+use crate::i18n;
+
+// Raw ESC/POS for technicians calibrating a printer (print density, cutter position, code page
+// tests). The bytes go to the printer as given, without the queue or the code page conversion.
+// Admins only, at most MAX_RAW_BYTES a send, and every send is recorded in the audit log with
+// the bytes sent. Sequences worth keeping are saved by name in escpos_macros (migration 028).
+
+pub const MAX_RAW_BYTES: usize = 4096;
+const MAX_MACRO_NAME: usize = 64;
+pub const RAW_SENT_EVENT: &str = "ESCPOS_RAW_SENT";
+
+pub fn encode(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// The bytes of a base64 payload, within the size limit
+pub fn decode(base64_bytes: &str) -> Result<Vec<u8>, String> {
+    let bytes = STANDARD
+        .decode(base64_bytes.trim())
+        .map_err(|_| i18n::t("error.escpos_invalid_base64"))?;
+    if bytes.is_empty() {
+        return Err(i18n::t("error.escpos_empty"));
+    }
+    if bytes.len() > MAX_RAW_BYTES {
+        return Err(i18n::tf("error.escpos_too_large", &[("size", &bytes.len()), ("max", &MAX_RAW_BYTES)]));
+    }
+    Ok(bytes)
+}
+
+/// Trimmed macro name, checked
+pub fn macro_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_MACRO_NAME {
+        return Err(i18n::tf("error.escpos_macro_name", &[("max", &MAX_MACRO_NAME)]));
+    }
+    Ok(name.to_string())
+}
+
+/// Send to the printer and audit it; `macro_name` when the bytes come from a saved macro
+pub async fn send(client: &deadpool_postgres::Client, printer_id: &str, bytes: &[u8], macro_name: Option<&str>, actor: Option<&str>) -> Result<String, String> {
+    let result = crate::PRINTER_SERVICE.send_raw(printer_id, bytes).await;
+    let details = json!({
+        "printerId": printer_id,
+        "macro": macro_name,
+        "size": bytes.len(),
+        "base64Bytes": encode(bytes),
+        "error": result.as_ref().err(),
+    });
+    if let Err(e) = audit::record(client, RAW_SENT_EVENT, actor, details).await {
+        println!("⚠️ {}", e);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_payloads_are_decoded_within_the_limit() {
+        // ESC @, GS V 0
+        assert_eq!(decode("G0AdVgA=").unwrap(), vec![0x1B, 0x40, 0x1D, 0x56, 0x00]);
+        assert_eq!(encode(&[0x1B, 0x40, 0x1D, 0x56, 0x00]), "G0AdVgA=");
+        assert!(decode("not base64!").is_err());
+        assert!(decode("").is_err());
+        assert!(decode(&encode(&vec![0u8; MAX_RAW_BYTES])).is_ok());
+        assert!(decode(&encode(&vec![0u8; MAX_RAW_BYTES + 1])).is_err());
+        assert_eq!(macro_name("  density +2 ").unwrap(), "density +2");
+        assert!(macro_name("   ").is_err());
+    }
+}
@@ -0,0 +1,329 @@
+// Windows firewall rules letting the app reach the local node and the network printers.
+// Checking runs `netsh advfirewall firewall show rule` as the user. Adding and removing rules
+// needs an administrator, so netsh runs through ShellExecuteExW with the "runas" verb: Windows
+// shows its elevation prompt and the cashier can decline. Every call returns what exists, what
+// was done and why not, instead of a bare error.
+
+use serde::Serialize;
+
+use crate::i18n;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FirewallOutcome {
+    Applied,
+    Unchanged, // the rules were already as asked, no prompt shown
+    Declined,  // the elevation prompt was refused
+    Failed,
+    Unsupported,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FirewallRuleStatus {
+    pub app_name: String,
+    pub supported: bool,
+    pub inbound: bool,
+    pub outbound: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FirewallActionResult {
+    pub outcome: FirewallOutcome,
+    pub status: FirewallRuleStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    In,
+    Out,
+}
+
+impl Direction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Direction::In => "in",
+            Direction::Out => "out",
+        }
+    }
+}
+
+const DIRECTIONS: [Direction; 2] = [Direction::In, Direction::Out];
+
+// Names and paths end up inside a netsh command line: no quotes, no shell metacharacters
+fn check_name(app_name: &str) -> Result<&str, String> {
+    let name = app_name.trim();
+    let valid = !name.is_empty() && name.len() <= 64 && name.chars().all(|c| c.is_alphanumeric() || " -_.".contains(c));
+    if valid {
+        Ok(name)
+    } else {
+        Err(i18n::tf("error.firewall_invalid_name", &[("name", &app_name)]))
+    }
+}
+
+fn check_exe_path(exe_path: &str) -> Result<&str, String> {
+    let path = exe_path.trim();
+    let valid = std::path::Path::new(path).is_absolute()
+        && path.to_lowercase().ends_with(".exe")
+        && !path.chars().any(|c| c.is_control() || "\"&|<>^%".contains(c));
+    if valid {
+        Ok(path)
+    } else {
+        Err(i18n::tf("error.firewall_invalid_path", &[("path", &exe_path)]))
+    }
+}
+
+/// netsh arguments adding the rule for one direction
+fn add_args(app_name: &str, exe_path: &str, direction: Direction) -> String {
+    format!(
+        "advfirewall firewall add rule name=\"{}\" dir={} action=allow program=\"{}\" enable=yes",
+        app_name,
+        direction.as_str(),
+        exe_path
+    )
+}
+
+/// netsh arguments deleting the rules of one direction
+fn delete_args(app_name: &str, direction: Direction) -> String {
+    format!("advfirewall firewall delete rule name=\"{}\" dir={}", app_name, direction.as_str())
+}
+
+/// One cmd line running the netsh calls in turn, so a single elevation prompt covers them
+fn elevated_command_line(netsh_args: &[String]) -> String {
+    let calls: Vec<String> = netsh_args.iter().map(|args| format!("netsh {}", args)).collect();
+    format!("/C \"{}\"", calls.join(" && "))
+}
+
+fn result(outcome: FirewallOutcome, status: FirewallRuleStatus, key: &str) -> FirewallActionResult {
+    let message = i18n::tf(key, &[("name", &status.app_name)]);
+    FirewallActionResult { outcome, status, message }
+}
+
+pub fn check(app_name: &str) -> Result<FirewallRuleStatus, String> {
+    let app_name = check_name(app_name)?;
+    if !cfg!(windows) {
+        return Ok(FirewallRuleStatus { app_name: app_name.to_string(), supported: false, inbound: false, outbound: false });
+    }
+    Ok(FirewallRuleStatus {
+        app_name: app_name.to_string(),
+        supported: true,
+        inbound: rule_exists(app_name, Direction::In)?,
+        outbound: rule_exists(app_name, Direction::Out)?,
+    })
+}
+
+/// Add the missing inbound and outbound rules for the executable
+pub fn add(app_name: &str, exe_path: &str) -> Result<FirewallActionResult, String> {
+    let exe_path = check_exe_path(exe_path)?;
+    let status = check(app_name)?;
+    if !status.supported {
+        return Ok(result(FirewallOutcome::Unsupported, status, "error.firewall_unsupported"));
+    }
+    let missing: Vec<String> = DIRECTIONS
+        .iter()
+        .filter(|d| !status_has(&status, **d))
+        .map(|d| add_args(&status.app_name, exe_path, *d))
+        .collect();
+    if missing.is_empty() {
+        return Ok(result(FirewallOutcome::Unchanged, status, "message.firewall_rule_present"));
+    }
+    run_and_recheck(&status.app_name, &missing, |s| s.inbound && s.outbound, "message.firewall_rule_added")
+}
+
+/// Delete the app's inbound and outbound rules
+pub fn remove(app_name: &str) -> Result<FirewallActionResult, String> {
+    let status = check(app_name)?;
+    if !status.supported {
+        return Ok(result(FirewallOutcome::Unsupported, status, "error.firewall_unsupported"));
+    }
+    let present: Vec<String> = DIRECTIONS
+        .iter()
+        .filter(|d| status_has(&status, **d))
+        .map(|d| delete_args(&status.app_name, *d))
+        .collect();
+    if present.is_empty() {
+        return Ok(result(FirewallOutcome::Unchanged, status, "message.firewall_rule_absent"));
+    }
+    run_and_recheck(&status.app_name, &present, |s| !s.inbound && !s.outbound, "message.firewall_rule_removed")
+}
+
+fn status_has(status: &FirewallRuleStatus, direction: Direction) -> bool {
+    match direction {
+        Direction::In => status.inbound,
+        Direction::Out => status.outbound,
+    }
+}
+
+// Run elevated, then read the rules back: that is what decides success, not netsh's exit code
+fn run_and_recheck(app_name: &str, netsh_args: &[String], done: impl Fn(&FirewallRuleStatus) -> bool, applied_key: &str) -> Result<FirewallActionResult, String> {
+    let run = run_elevated(&elevated_command_line(netsh_args));
+    let status = check(app_name)?;
+    Ok(match run {
+        Err(Elevation::Declined) => result(FirewallOutcome::Declined, status, "error.firewall_declined"),
+        _ if done(&status) => result(FirewallOutcome::Applied, status, applied_key),
+        Err(Elevation::Failed(error)) => {
+            let message = i18n::tf("error.firewall_failed", &[("error", &error)]);
+            FirewallActionResult { outcome: FirewallOutcome::Failed, status, message }
+        }
+        Ok(code) => {
+            let message = i18n::tf("error.firewall_failed", &[("error", &format!("netsh exit code {}", code))]);
+            FirewallActionResult { outcome: FirewallOutcome::Failed, status, message }
+        }
+    })
+}
+
+#[allow(dead_code)]
+enum Elevation {
+    Declined,
+    Failed(String),
+}
+
+#[cfg(windows)]
+fn rule_exists(app_name: &str, direction: Direction) -> Result<bool, String> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    // netsh exits with 1 ("No rules match the specified criteria") when there is none
+    let status = std::process::Command::new("netsh")
+        .args(["advfirewall", "firewall", "show", "rule", &format!("name={}", app_name), &format!("dir={}", direction.as_str())])
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_err(|e| i18n::tf("error.firewall_failed", &[("error", &e)]))?;
+    Ok(status.success())
+}
+
+#[cfg(not(windows))]
+fn rule_exists(_app_name: &str, _direction: Direction) -> Result<bool, String> {
+    Ok(false)
+}
+
+#[cfg(windows)]
+#[allow(non_snake_case, non_camel_case_types, clippy::upper_case_acronyms)]
+mod ffi {
+    use std::ffi::c_void;
+
+    pub type HANDLE = *mut c_void;
+    pub type BOOL = i32;
+
+    #[repr(C)]
+    pub struct SHELLEXECUTEINFOW {
+        pub cbSize: u32,
+        pub fMask: u32,
+        pub hwnd: HANDLE,
+        pub lpVerb: *const u16,
+        pub lpFile: *const u16,
+        pub lpParameters: *const u16,
+        pub lpDirectory: *const u16,
+        pub nShow: i32,
+        pub hInstApp: HANDLE,
+        pub lpIDList: *mut c_void,
+        pub lpClass: *const u16,
+        pub hkeyClass: HANDLE,
+        pub dwHotKey: u32,
+        pub hIconOrMonitor: HANDLE,
+        pub hProcess: HANDLE,
+    }
+
+    pub const SEE_MASK_NOCLOSEPROCESS: u32 = 0x0000_0040;
+    pub const SW_HIDE: i32 = 0;
+    pub const WAIT_OBJECT_0: u32 = 0;
+    pub const ERROR_CANCELLED: i32 = 1223;
+
+    #[link(name = "shell32")]
+    extern "system" {
+        pub fn ShellExecuteExW(pExecInfo: *mut SHELLEXECUTEINFOW) -> BOOL;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn WaitForSingleObject(hHandle: HANDLE, dwMilliseconds: u32) -> u32;
+        pub fn GetExitCodeProcess(hProcess: HANDLE, lpExitCode: *mut u32) -> BOOL;
+        pub fn CloseHandle(hObject: HANDLE) -> BOOL;
+    }
+}
+
+#[cfg(windows)]
+fn to_wide(value: &str) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+    std::ffi::OsStr::new(value).encode_wide().chain(std::iter::once(0)).collect()
+}
+
+// cmd.exe with the "runas" verb and the exit code once it is done
+#[cfg(windows)]
+fn run_elevated(parameters: &str) -> Result<u32, Elevation> {
+    use ffi::*;
+    const WAIT_MS: u32 = 60_000;
+    let verb = to_wide("runas");
+    let file = to_wide("cmd.exe");
+    let parameters = to_wide(parameters);
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS,
+        hwnd: std::ptr::null_mut(),
+        lpVerb: verb.as_ptr(),
+        lpFile: file.as_ptr(),
+        lpParameters: parameters.as_ptr(),
+        lpDirectory: std::ptr::null(),
+        nShow: SW_HIDE,
+        hInstApp: std::ptr::null_mut(),
+        lpIDList: std::ptr::null_mut(),
+        lpClass: std::ptr::null(),
+        hkeyClass: std::ptr::null_mut(),
+        dwHotKey: 0,
+        hIconOrMonitor: std::ptr::null_mut(),
+        hProcess: std::ptr::null_mut(),
+    };
+    unsafe {
+        if ShellExecuteExW(&mut info) == 0 {
+            let err = std::io::Error::last_os_error();
+            if err.raw_os_error() == Some(ERROR_CANCELLED) {
+                return Err(Elevation::Declined);
+            }
+            return Err(Elevation::Failed(err.to_string()));
+        }
+        if info.hProcess.is_null() {
+            return Err(Elevation::Failed("no process handle".to_string()));
+        }
+        let waited = WaitForSingleObject(info.hProcess, WAIT_MS);
+        let mut code: u32 = 0;
+        let got_code = GetExitCodeProcess(info.hProcess, &mut code);
+        CloseHandle(info.hProcess);
+        if waited != WAIT_OBJECT_0 {
+            return Err(Elevation::Failed("netsh did not finish in time".to_string()));
+        }
+        if got_code == 0 {
+            return Err(Elevation::Failed(std::io::Error::last_os_error().to_string()));
+        }
+        Ok(code)
+    }
+}
+
+#[cfg(not(windows))]
+fn run_elevated(_parameters: &str) -> Result<u32, Elevation> {
+    Err(Elevation::Failed("Windows only".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_are_added_and_removed_with_one_elevated_command() {
+        let line = elevated_command_line(&[
+            add_args("Nqlix", r"C:\Program Files\Nqlix\Nqlix.exe", Direction::In),
+            delete_args("Nqlix", Direction::Out),
+        ]);
+        assert_eq!(
+            line,
+            r#"/C "netsh advfirewall firewall add rule name="Nqlix" dir=in action=allow program="C:\Program Files\Nqlix\Nqlix.exe" enable=yes && netsh advfirewall firewall delete rule name="Nqlix" dir=out""#
+        );
+        assert_eq!(check_name(" Nqlix "), Ok("Nqlix"));
+        assert!(check_name("Nqlix\" & del").is_err());
+        assert!(check_exe_path("Nqlix.exe").is_err());
+        assert!(check_exe_path("/opt/nqlix/Nqlix.exe & calc").is_err());
+        assert!(check_exe_path("/opt/nqlix/Nqlix.exe").is_ok());
+    }
+}
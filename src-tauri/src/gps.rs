@@ -0,0 +1,326 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::db;
+use crate::i18n;
+use crate::time::{self, TunisTime};
+
+// Last known positions of the vehicles fitted with GPS trackers. Trackers (or the local node
+// relaying them) post JSON reports to POST /positions on GPS_INGEST_PORT, with
+// `Authorization: Bearer <GPS_INGEST_TOKEN>`; the listener stays off unless both are set. The
+// UI can also record a report with db_record_vehicle_position.
+//
+// db_get_vehicle_positions lists the positions with an estimated return time for the vehicles
+// out on a trip: their last exit pass plus the median round trip to that destination
+// (trip_durations.rs).
+
+// Tracker clocks a little ahead are accepted; further ahead the report is refused
+const MAX_CLOCK_AHEAD_SECS: i64 = 5 * 60;
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+const MAX_BODY_BYTES: usize = 256 * 1024;
+pub const HTTP_SOURCE: &str = "http";
+pub const APP_SOURCE: &str = "app";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionReport {
+    /// The vehicle, by id or by plate
+    #[serde(default)]
+    pub vehicle_id: Option<String>,
+    #[serde(default)]
+    pub license_plate: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default)]
+    pub speed_kmh: Option<f64>,
+    #[serde(default)]
+    pub heading: Option<f64>,
+    /// When the tracker took the fix; the time it arrives when missing
+    #[serde(default)]
+    pub recorded_at: Option<String>,
+}
+
+// One report or a batch of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ReportBody {
+    Many(Vec<PositionReport>),
+    One(PositionReport),
+}
+
+impl PositionReport {
+    /// The time of the fix once the report is checked
+    pub fn check(&self, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+        let valid = (-90.0..=90.0).contains(&self.latitude) && (-180.0..=180.0).contains(&self.longitude);
+        // 0,0 is what trackers without a fix send
+        if !valid || (self.latitude == 0.0 && self.longitude == 0.0) {
+            return Err(i18n::tf("error.gps_position_invalid", &[("latitude", &self.latitude), ("longitude", &self.longitude)]));
+        }
+        if self.vehicle_id.as_deref().unwrap_or("").trim().is_empty() && self.license_plate.as_deref().unwrap_or("").trim().is_empty() {
+            return Err(i18n::t("error.gps_vehicle_required"));
+        }
+        let recorded_at = match self.recorded_at.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            Some(value) => time::parse_instant(value).ok_or_else(|| i18n::tf("error.invalid_timestamp", &[("value", &value)]))?,
+            None => now,
+        };
+        if recorded_at > now + Duration::seconds(MAX_CLOCK_AHEAD_SECS) {
+            return Err(i18n::t("error.gps_time_ahead"));
+        }
+        Ok(recorded_at)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VehiclePositionDto {
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub speed_kmh: Option<f64>,
+    pub heading: Option<f64>,
+    pub recorded_at: String,
+    pub minutes_since_report: i64,
+    pub in_queue: bool,
+    pub last_exit_at: Option<String>,
+    pub last_destination_name: Option<String>,
+    pub typical_trip_minutes: Option<i64>,
+    /// Last exit pass plus the usual round trip, for a vehicle not back in the queue yet
+    pub estimated_return_at: Option<String>,
+    /// Past its estimated return and still not queued
+    pub overdue: bool,
+}
+
+/// When a vehicle that left at `last_exit_at` should be back, given the usual round trip
+pub fn estimated_return(last_exit_at: Option<DateTime<Utc>>, in_queue: bool, typical_minutes: Option<f64>) -> Option<DateTime<Utc>> {
+    if in_queue {
+        return None;
+    }
+    Some(last_exit_at? + Duration::seconds((typical_minutes? * 60.0).round() as i64))
+}
+
+/// Store one report; false when a later position of the vehicle is already stored
+pub async fn record(client: &impl db::GenericClient, report: &PositionReport, source: &str) -> Result<bool, String> {
+    let recorded_at = report.check(crate::clock::now())?;
+    let vehicle_id = report.vehicle_id.as_deref().map(str::trim).filter(|v| !v.is_empty());
+    let license_plate = report.license_plate.as_deref().map(str::trim).filter(|v| !v.is_empty());
+    let vehicle_id = db::positions::resolve_vehicle(client, vehicle_id, license_plate)
+        .await?
+        .ok_or_else(|| i18n::tf("error.gps_vehicle_unknown", &[("vehicle", &license_plate.or(vehicle_id).unwrap_or_default())]))?;
+    db::positions::upsert(
+        client,
+        &db::positions::NewPosition {
+            vehicle_id: &vehicle_id,
+            latitude: report.latitude,
+            longitude: report.longitude,
+            speed_kmh: report.speed_kmh,
+            heading: report.heading,
+            recorded_at,
+            source,
+        },
+    )
+    .await
+}
+
+pub async fn positions(client: &impl db::GenericClient) -> Result<Vec<VehiclePositionDto>, String> {
+    let typical = crate::trip_durations::typical_minutes(client).await?;
+    let now = crate::clock::now();
+    let dto_time = |at: DateTime<Utc>| TunisTime(at.with_timezone(&time::TZ)).fmt_dto();
+    Ok(db::positions::all(client)
+        .await?
+        .into_iter()
+        .map(|p| {
+            let typical_minutes = p.last_destination_id.as_ref().and_then(|d| typical.get(d)).copied();
+            let estimated_return_at = estimated_return(p.last_exit_at, p.in_queue, typical_minutes);
+            VehiclePositionDto {
+                vehicle_id: p.vehicle_id,
+                license_plate: p.license_plate,
+                latitude: p.latitude,
+                longitude: p.longitude,
+                speed_kmh: p.speed_kmh,
+                heading: p.heading,
+                recorded_at: dto_time(p.recorded_at),
+                minutes_since_report: (now - p.recorded_at).num_minutes().max(0),
+                in_queue: p.in_queue,
+                last_exit_at: p.last_exit_at.map(dto_time),
+                last_destination_name: p.last_destination_name,
+                typical_trip_minutes: typical_minutes.map(|m| m.round() as i64),
+                estimated_return_at: estimated_return_at.map(dto_time),
+                overdue: estimated_return_at.is_some_and(|at| at < now),
+            }
+        })
+        .collect())
+}
+
+/// The request line, the Authorization header and the Content-Length of a request head
+fn parse_head(head: &str) -> (String, Option<String>, usize) {
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default().to_string();
+    let mut authorization = None;
+    let mut content_length = 0;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else { continue };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "authorization" => authorization = Some(value.trim().to_string()),
+            "content-length" => content_length = value.trim().parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    (request_line, authorization, content_length)
+}
+
+pub fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// The body of a `POST <path>` carrying the bearer `token`, else the error response to send.
+/// The ANPR camera endpoint (anpr.rs) reads its requests the same way.
+pub async fn read_post(stream: &mut tokio::net::TcpStream, path: &str, token: &str) -> std::io::Result<Result<Vec<u8>, String>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(Err(http_response("400 Bad Request", "{}")));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(at) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break at + 4;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Ok(Err(http_response("431 Request Header Fields Too Large", "{}")));
+        }
+    };
+    let (request_line, authorization, content_length) = parse_head(&String::from_utf8_lossy(&buffer[..head_end]));
+    if !request_line.starts_with(&format!("POST {} ", path)) {
+        return Ok(Err(http_response("404 Not Found", "{}")));
+    }
+    if authorization.as_deref() != Some(format!("Bearer {}", token).as_str()) {
+        return Ok(Err(http_response("401 Unauthorized", "{}")));
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Ok(Err(http_response("413 Payload Too Large", "{}")));
+    }
+    while buffer.len() < head_end + content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let end = buffer.len().min(head_end + content_length);
+    Ok(Ok(buffer[head_end..end].to_vec()))
+}
+
+async fn handle(stream: &mut tokio::net::TcpStream, token: &str) -> std::io::Result<String> {
+    let body = match read_post(stream, "/positions", token).await? {
+        Ok(body) => body,
+        Err(response) => return Ok(response),
+    };
+    let reports = match serde_json::from_slice::<ReportBody>(&body) {
+        Ok(ReportBody::Many(reports)) => reports,
+        Ok(ReportBody::One(report)) => vec![report],
+        Err(e) => return Ok(http_response("400 Bad Request", &json!({ "error": e.to_string() }).to_string())),
+    };
+    let client = match db::pool::background().await {
+        Ok(client) => client,
+        Err(e) => return Ok(http_response("503 Service Unavailable", &json!({ "error": e }).to_string())),
+    };
+    let (mut stored, mut ignored, mut rejected) = (0, 0, Vec::new());
+    for report in &reports {
+        match record(&client, report, HTTP_SOURCE).await {
+            Ok(true) => stored += 1,
+            Ok(false) => ignored += 1,
+            Err(e) => rejected.push(e),
+        }
+    }
+    Ok(http_response("200 OK", &json!({ "stored": stored, "ignored": ignored, "rejected": rejected }).to_string()))
+}
+
+/// Serve POST /positions on GPS_INGEST_PORT; does nothing unless the port and GPS_INGEST_TOKEN are set
+pub async fn run_ingest() {
+    let port = match std::env::var("GPS_INGEST_PORT").ok().and_then(|v| v.trim().parse::<u16>().ok()) {
+        Some(port) => port,
+        None => return,
+    };
+    let token = match std::env::var("GPS_INGEST_TOKEN").ok().map(|t| t.trim().to_string()).filter(|t| !t.is_empty()) {
+        Some(token) => token,
+        None => {
+            println!("⚠️ [GPS] GPS_INGEST_PORT is set without GPS_INGEST_TOKEN; ingestion stays off");
+            return;
+        }
+    };
+    let listener = match TcpListener::bind(("0.0.0.0", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("❌ [GPS] Cannot listen on port {}: {}", port, e);
+            return;
+        }
+    };
+    println!("🛰️ [GPS] Accepting positions on port {}", port);
+    loop {
+        match listener.accept().await {
+            Ok((mut stream, _)) => {
+                let token = token.clone();
+                tauri::async_runtime::spawn(async move {
+                    let result = match handle(&mut stream, &token).await {
+                        Ok(response) => stream.write_all(response.as_bytes()).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        println!("⚠️ [GPS] {}", e);
+                    }
+                    let _ = stream.shutdown().await;
+                });
+            }
+            Err(e) => println!("⚠️ [GPS] Accept failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(latitude: f64, longitude: f64, recorded_at: Option<&str>) -> PositionReport {
+        PositionReport {
+            vehicle_id: None,
+            license_plate: Some("123 TUN 4567".to_string()),
+            latitude,
+            longitude,
+            speed_kmh: None,
+            heading: None,
+            recorded_at: recorded_at.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn reports_are_checked_and_returns_estimated() {
+        let now = DateTime::parse_from_rfc3339("2026-10-15T08:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(report(35.77, 10.82, None).check(now), Ok(now));
+        assert!(report(35.77, 10.82, Some("2026-10-15T08:04:00Z")).check(now).is_ok());
+        assert!(report(35.77, 10.82, Some("2026-10-15T08:10:00Z")).check(now).is_err());
+        assert!(report(0.0, 0.0, None).check(now).is_err());
+        assert!(report(95.0, 10.0, None).check(now).is_err());
+        assert!(PositionReport { license_plate: None, ..report(35.77, 10.82, None) }.check(now).is_err());
+
+        let left = now - Duration::minutes(50);
+        assert_eq!(estimated_return(Some(left), false, Some(90.0)), Some(now + Duration::minutes(40)));
+        assert_eq!(estimated_return(Some(left), true, Some(90.0)), None);
+        assert_eq!(estimated_return(None, false, Some(90.0)), None);
+        assert_eq!(estimated_return(Some(left), false, None), None);
+
+        let body: ReportBody = serde_json::from_str(r#"{"licensePlate":"123 TUN 4567","latitude":35.77,"longitude":10.82}"#).unwrap();
+        assert!(matches!(body, ReportBody::One(_)));
+        let (line, authorization, length) = parse_head("POST /positions HTTP/1.1\r\nHost: x\r\nauthorization: Bearer s3cret\r\nContent-Length: 42\r\n\r\n");
+        assert_eq!((line.as_str(), authorization.as_deref(), length), ("POST /positions HTTP/1.1", Some("Bearer s3cret"), 42));
+    }
+}
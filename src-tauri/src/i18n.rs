@@ -0,0 +1,493 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::DB_POOL;
+
+// French and Arabic message catalog for everything a command returns to the operator and
+// everything printed on a ticket. The station language lives in station_settings and is
+// cached here at startup, so ticket builders and error paths read it without a query.
+// Messages take named arguments: tf("error.vehicle_inactive", &[("plate", &plate)]).
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    Fr,
+    Ar,
+}
+
+impl Lang {
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "fr" | "french" | "francais" | "français" => Some(Lang::Fr),
+            "ar" | "arabic" | "arabe" => Some(Lang::Ar),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Lang::Fr => "fr",
+            Lang::Ar => "ar",
+        }
+    }
+}
+
+/// How tickets are worded: the station language, or French and Arabic side by side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TicketLanguage {
+    pub lang: Lang,
+    pub bilingual: bool,
+}
+
+impl Default for TicketLanguage {
+    fn default() -> Self {
+        Self { lang: Lang::Fr, bilingual: false }
+    }
+}
+
+impl TicketLanguage {
+    /// Ticket wording for `key`, "FR / AR" when bilingual
+    pub fn text(&self, key: &str) -> String {
+        if self.bilingual {
+            format!("{} / {}", lookup(key, Lang::Fr), lookup(key, Lang::Ar))
+        } else {
+            lookup(key, self.lang).to_string()
+        }
+    }
+
+    pub fn text_with(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        if self.bilingual {
+            format!("{} / {}", format_args_into(lookup(key, Lang::Fr), args), format_args_into(lookup(key, Lang::Ar), args))
+        } else {
+            format_args_into(lookup(key, self.lang), args)
+        }
+    }
+
+    /// Field label followed by a colon, for TextLayout::row
+    pub fn label(&self, key: &str) -> String {
+        format!("{}:", self.text(key))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageSettingsDto {
+    pub language: String,
+    pub bilingual_tickets: bool,
+}
+
+static SETTINGS: Lazy<RwLock<TicketLanguage>> = Lazy::new(|| RwLock::new(TicketLanguage::default()));
+
+// (key, French, Arabic). French ticket wording is kept unaccented where tickets always were.
+const CATALOG: &[(&str, &str, &str)] = &[
+    // Ticket titles and sections
+    ("ticket.booking", "RESERVATION", "حجز"),
+    ("ticket.vehicle_change", "CHANGEMENT DE VÉHICULE", "تغيير السيارة"),
+    ("ticket.copy", "COPIE {copy}/{total}", "نسخة {copy}/{total}"),
+    ("ticket.duplicate", "DUPLICATA n°{number}", "نظير رقم {number}"),
+    ("ticket.printed_on", "Imprimé sur {printer}", "طبع على {printer}"),
+    ("ticket.training", "FORMATION – NON VALABLE", "تدريب – غير صالحة"),
+    ("ticket.entry", "TICKET D'ENTREE", "تذكرة دخول"),
+    ("ticket.exit", "TICKET DE SORTIE", "تذكرة خروج"),
+    ("ticket.exit_pass", "PASS DE SORTIE", "إذن خروج"),
+    ("ticket.day_pass", "PASS JOURNALIER", "تصريح يومي"),
+    ("ticket.week_pass", "PASS HEBDOMADAIRE", "تصريح أسبوعي"),
+    ("ticket.month_pass", "PASS MENSUEL", "تصريح شهري"),
+    ("ticket.day_pass_summary", "RESUME PASS JOURNALIERS", "ملخص التصاريح اليومية"),
+    ("ticket.settlement_slip", "DECOMPTE CHAUFFEUR", "كشف حساب السائق"),
+    ("ticket.expense_voucher", "BON DE DEPENSE", "وصل مصروف"),
+    ("ticket.cash_variance_slip", "ECART DE CAISSE", "فارق الصندوق"),
+    ("ticket.cancellation_slip", "ANNULATION", "إلغاء حجز"),
+    ("ticket.queue_board", "FILE D'ATTENTE", "صف الانتظار"),
+    ("ticket.section.vehicle", "VEHICULE", "السيارة"),
+    ("ticket.section.current_vehicle", "VEHICULE ACTUEL", "السيارة الحالية"),
+    ("ticket.section.previous_vehicle", "VEHICULE PRECEDENT", "السيارة السابقة"),
+    ("ticket.section.destination", "DESTINATION", "الوجهة"),
+    ("ticket.section.entry_time", "HEURE D'ENTREE", "وقت الدخول"),
+    ("ticket.section.pricing", "TARIFICATION", "التسعيرة"),
+    ("ticket.section.by_staff", "PAR AGENT", "حسب العون"),
+    ("ticket.section.by_tariff", "PAR TARIF", "حسب التعريفة"),
+    ("ticket.section.by_payment", "PAR PAIEMENT", "حسب الدفع"),
+    ("ticket.section.total", "TOTAL", "المجموع"),
+    ("ticket.section.settlement", "REGLEMENT", "التسوية"),
+    ("ticket.section.cash_count", "COMPTAGE", "عد النقود"),
+    // Ticket fields
+    ("ticket.code", "Code", "الرمز"),
+    ("ticket.plate", "Plaque", "اللوحة"),
+    ("ticket.previous_plate", "Ancien véhicule", "السيارة السابقة"),
+    ("ticket.destination", "Destination", "الوجهة"),
+    ("ticket.station", "Station", "المحطة"),
+    ("ticket.position", "Position", "الترتيب"),
+    ("ticket.seats", "Places", "المقاعد"),
+    ("ticket.amount", "Montant", "المبلغ"),
+    ("ticket.amount_due", "MONTANT", "المبلغ"),
+    ("ticket.payment", "Paiement", "الدفع"),
+    ("ticket.ticket_number", "N° Ticket", "رقم التذكرة"),
+    ("ticket.day_pass_field", "Pass journalier", "التصريح اليومي"),
+    ("ticket.week_pass_field", "Pass hebdomadaire", "التصريح الأسبوعي"),
+    ("ticket.month_pass_field", "Pass mensuel", "التصريح الشهري"),
+    ("ticket.purchased_on", "Achat le", "تاريخ الشراء"),
+    ("ticket.purchase_date", "Date d'achat", "تاريخ الشراء"),
+    ("ticket.valid_from", "Valide du", "صالح من"),
+    ("ticket.valid_until", "Valide au", "صالح إلى"),
+    ("ticket.valid_for", "Valide pour", "صالح ليوم"),
+    ("ticket.day", "Journee", "اليوم"),
+    ("ticket.passes_sold", "Pass vendus", "التصاريح المباعة"),
+    ("ticket.gross_amount", "Montant brut", "المبلغ الجملي"),
+    ("ticket.cancelled", "Annules", "الملغاة"),
+    ("ticket.net", "NET", "الصافي"),
+    ("ticket.capacity", "Capacite", "السعة"),
+    ("ticket.exit_time", "Heure de sortie", "وقت الخروج"),
+    ("ticket.trip_of_day", "Voyage n° {number} du jour", "الرحلة رقم {number} اليوم"),
+    ("ticket.seat_price", "Prix par place", "ثمن المقعد"),
+    ("ticket.vehicle_capacity", "Capacite vehicule", "سعة السيارة"),
+    ("ticket.total_due", "TOTAL A RECEVOIR", "المجموع المستحق"),
+    ("ticket.seats_sold", "Places vendues", "المقاعد المباعة"),
+    ("ticket.amount_collected", "Montant encaisse", "المبلغ المقبوض"),
+    ("ticket.driver_due", "DU AU CHAUFFEUR", "المستحق للسائق"),
+    ("ticket.commission", "Commission station", "عمولة المحطة"),
+    ("ticket.net_to_driver", "NET CHAUFFEUR", "صافي السائق"),
+    ("ticket.category", "Categorie", "الصنف"),
+    ("ticket.reason", "Motif", "السبب"),
+    ("ticket.received_by", "Recu par", "استلمه"),
+    ("ticket.cashier", "Caissier", "أمين الصندوق"),
+    ("ticket.supervisor", "Superviseur", "المشرف"),
+    ("ticket.counted", "Compte", "المعدود"),
+    ("ticket.expected", "Attendu", "المتوقع"),
+    ("ticket.variance", "ECART", "الفارق"),
+    ("ticket.threshold", "Seuil", "الحد المسموح"),
+    ("ticket.seats_cancelled", "Places annulees", "المقاعد الملغاة"),
+    ("ticket.cancellation_fee", "Frais d'annulation", "معلوم الإلغاء"),
+    ("ticket.to_refund", "A REMBOURSER", "المبلغ المسترجع"),
+    ("ticket.status", "Statut", "الحالة"),
+    ("ticket.issued_by", "Émis par", "أصدرها"),
+    ("ticket.date", "Date", "التاريخ"),
+    // Ticket values and closing lines
+    ("ticket.valid", "VALIDE", "صالح"),
+    ("ticket.purchased", "ACHETE", "مدفوع"),
+    ("ticket.not_valid", "NON VALIDE", "غير صالح"),
+    ("ticket.seat_count", "{count} places", "{count} مقاعد"),
+    ("ticket.no_pass_sold", "Aucun pass vendu", "لم يتم بيع أي تصريح"),
+    ("ticket.queue_empty", "Aucun vehicule en file", "لا توجد سيارات في الصف"),
+    ("ticket.queue_status.waiting", "En attente", "في الانتظار"),
+    ("ticket.queue_status.loading", "Chargement", "بصدد التحميل"),
+    ("ticket.queue_status.ready", "Pret", "جاهزة"),
+    ("ticket.no_previous_vehicle", "Aucun vehicule precedent aujourd'hui", "لا توجد سيارة سابقة اليوم"),
+    ("ticket.thanks", "Merci!", "شكرا"),
+    ("ticket.thanks_for_trust", "Merci de votre confiance!", "شكرا على ثقتكم"),
+    // Command errors
+    ("error.queue_entry_not_found", "Entrée de file introuvable", "لم يتم العثور على السيارة في الطابور"),
+    ("error.queue_entry_not_found_for_vehicle", "Aucune entrée de file trouvée pour le véhicule {plate}", "السيارة {plate} ليست في الطابور"),
+    ("error.vehicle_not_found", "Véhicule introuvable", "السيارة غير موجودة"),
+    ("error.vehicle_not_found_plate", "Véhicule introuvable: {plate}", "السيارة غير موجودة: {plate}"),
+    ("error.vehicle_not_found_id", "Véhicule introuvable avec l'ID: {id}", "لا توجد سيارة بالمعرف: {id}"),
+    ("error.vehicle_inactive", "Véhicule inactif: {plate}", "السيارة غير نشطة: {plate}"),
+    ("error.vehicle_not_authorized", "Véhicule {plate} non autorisé pour la destination {destination}", "السيارة {plate} غير مرخص لها في الوجهة {destination}"),
+    ("error.vehicle_already_queued", "Véhicule {plate} est déjà dans une file d'attente", "السيارة {plate} موجودة مسبقا في طابور"),
+    ("error.vehicle_exists", "Véhicule avec la plaque {plate} existe déjà", "السيارة ذات اللوحة {plate} موجودة مسبقا"),
+    ("error.vehicle_unavailable", "Véhicule sélectionné non disponible ou pas assez de places", "السيارة المختارة غير متاحة أو لا توجد مقاعد كافية"),
+    ("error.not_enough_seats", "Pas assez de places disponibles. Véhicule {plate} a seulement {available} places disponibles, mais {requested} demandées", "لا توجد مقاعد كافية. السيارة {plate} فيها {available} مقاعد متاحة فقط والمطلوب {requested}"),
+    ("error.destination_not_enough_seats", "Pas assez de places disponibles pour {requested} place(s) vers cette destination", "لا توجد مقاعد كافية لـ {requested} مقعد نحو هذه الوجهة"),
+    ("error.queue_insert_failed", "Insertion dans la file échouée: {error}", "تعذرت الإضافة إلى الطابور: {error}"),
+    ("error.booking_not_found", "Réservation introuvable: {id}", "الحجز غير موجود: {id}"),
+    ("error.cancel_vehicle_gone", "Accès refusé! Le véhicule n'est plus dans la file d'attente. Vous ne pouvez pas annuler cette réservation.", "تم رفض العملية! السيارة لم تعد في الطابور. لا يمكنك إلغاء هذا الحجز."),
+    ("error.no_booking_by_staff", "Aucune réservation trouvée pour cette destination par ce membre du personnel", "لا يوجد حجز لهذه الوجهة من طرف هذا العون"),
+    ("error.authorization_exists", "Autorisation déjà existante pour cette station", "الترخيص موجود مسبقا لهذه المحطة"),
+    ("error.invalid_date", "Date invalide: {date}", "تاريخ غير صالح: {date}"),
+    ("error.invalid_start_date", "Date de début invalide: {date}", "تاريخ بداية غير صالح: {date}"),
+    ("error.invalid_end_date", "Date de fin invalide: {date}", "تاريخ نهاية غير صالح: {date}"),
+    ("error.end_before_start", "La date de fin doit être après la date de début", "يجب أن يكون تاريخ النهاية بعد تاريخ البداية"),
+    ("error.invalid_trip_period", "Période invalide: {period} (jour, semaine ou mois)", "فترة غير صالحة: {period} (يوم أو أسبوع أو شهر)"),
+    ("error.end_precedes_start", "La date de fin précède la date de début", "تاريخ النهاية يسبق تاريخ البداية"),
+    ("error.invalid_pass_type", "Type de pass invalide: {name}", "نوع تصريح غير صالح: {name}"),
+    ("error.invalid_payment_method", "Mode de paiement invalide: {name}", "طريقة دفع غير صالحة: {name}"),
+    ("error.invalid_language", "Langue invalide: {name}", "لغة غير صالحة: {name}"),
+    ("error.invalid_phone", "Numéro de mobile invalide: {phone} (8 chiffres commençant par 2, 3, 4, 5 ou 9, ex. +216 98 123 456)", "رقم جوال غير صالح: {phone} (8 أرقام تبدأ بـ 2 أو 3 أو 4 أو 5 أو 9، مثال +216 98 123 456)"),
+    ("error.cancel_after_exit", "Annulation impossible: le pass de sortie de ce véhicule est déjà imprimé", "لا يمكن الإلغاء: تم طبع إذن خروج هذه السيارة"),
+    ("error.destination_suspended", "{destination} est suspendue: {reason}", "{destination} معلقة: {reason}"),
+    ("error.destination_suspended_until", "{destination} est suspendue jusqu'à {until}: {reason}", "{destination} معلقة حتى {until}: {reason}"),
+    ("error.suspension_reason_required", "Indiquer la raison de la suspension", "يجب ذكر سبب التعليق"),
+    ("error.suspension_until_past", "La fin de la suspension doit être dans le futur", "يجب أن تكون نهاية التعليق في المستقبل"),
+    ("error.suspension_not_found", "Aucune suspension en cours pour cette destination", "لا يوجد تعليق جار لهذه الوجهة"),
+    ("error.invalid_announcement_settings", "Annonces: un modèle et un point de sonorisation ou une commande de lecture sont requis", "الإعلانات: يلزم نموذج وعنوان مكبر الصوت أو أمر تشغيل"),
+    ("error.no_announcement", "Aucune annonce à rejouer", "لا يوجد إعلان لإعادته"),
+    ("error.message_empty", "Le message est vide", "الرسالة فارغة"),
+    ("error.message_too_long", "Le message dépasse {max} caractères", "الرسالة تتجاوز {max} حرفا"),
+    ("error.message_not_found", "Message introuvable ou déjà lu", "الرسالة غير موجودة أو تم الاطلاع عليها"),
+    ("error.terminal_unknown", "Ce poste n'est pas encore identifié", "لم يتم تعريف هذا المركز بعد"),
+    ("error.terminal_not_found", "Poste introuvable: {id}", "المركز غير موجود: {id}"),
+    ("error.queue_stale", "La file a changé sur un autre poste ({count} véhicule(s)); réessayer sur la file à jour", "تغير الطابور في مركز آخر ({count} سيارة)؛ أعد المحاولة على الطابور المحدث"),
+    ("error.invalid_timestamp", "Horodatage invalide: {value} (attendu AAAA-MM-JJ HH:MM)", "توقيت غير صالح: {value} (الصيغة YYYY-MM-DD HH:MM)"),
+    ("error.search_too_short", "Saisir au moins {count} caractères", "أدخل {count} أحرف على الأقل"),
+    ("error.queue_already_closed", "La file du {date} est déjà fermée", "تم إغلاق طابور {date} مسبقا"),
+    ("error.loading_timeout_too_long", "Le délai de chargement ne peut dépasser {max} minutes", "لا يمكن أن تتجاوز مدة التحميل {max} دقيقة"),
+    ("error.print_copies_invalid", "Copies pour {kind}: entre 1 et {max}", "عدد النسخ لـ {kind}: بين 1 و {max}"),
+    ("error.dispatch_queue_empty", "Aucun véhicule dans la file de cette destination", "لا توجد سيارات في طابور هذه الوجهة"),
+    ("error.dispatch_nothing_sold", "Aucun véhicule de cette destination n'a de places vendues", "لا توجد سيارة بمقاعد مباعة في هذه الوجهة"),
+    ("error.vehicle_not_loading", "Ce véhicule n'est pas en chargement", "هذه السيارة ليست في طور التحميل"),
+    ("error.invalid_cancellation_policy", "Règles d'annulation invalides: délai et frais doivent être positifs, remboursement des absents entre 0 et 100 %", "قواعد إلغاء غير صالحة: يجب أن تكون المهلة والمعلوم موجبة، واسترجاع الغائبين بين 0 و 100 %"),
+    ("error.invalid_reprint_policy", "Règles de réimpression invalides: les frais doivent être positifs", "قواعد إعادة الطبع غير صالحة: يجب أن يكون المعلوم موجبا"),
+    ("error.reprint_limit_reached", "Réimpression refusée: ce ticket a déjà été réimprimé {max} fois", "رفض إعادة الطبع: أعيد طبع هذه التذكرة {max} مرات"),
+    ("error.nothing_to_reprint", "Aucun ticket de ce type à réimprimer", "لا توجد تذكرة من هذا النوع لإعادة طبعها"),
+    ("error.ticket_kind_invalid", "Type de ticket inconnu: {kind}", "نوع تذكرة غير معروف: {kind}"),
+    ("error.ticket_kind_not_numbered", "Ce type de ticket ne porte pas de numéro à annuler", "هذا النوع من التذاكر لا يحمل رقما للإلغاء"),
+    ("error.ticket_number_required", "Le numéro du ticket est obligatoire", "رقم التذكرة إجباري"),
+    ("error.void_reason_invalid", "Le motif d'annulation est obligatoire ({max} caractères au plus)", "سبب الإلغاء إجباري ({max} حرفا على الأكثر)"),
+    ("error.self_check_in_disabled", "L'enregistrement en libre-service est désactivé, présentez-vous au guichet", "التسجيل الذاتي معطل، توجه إلى الشباك"),
+    ("error.driver_pin_incorrect", "Matricule ou code PIN incorrect", "رقم اللوحة أو الرمز السري غير صحيح"),
+    ("error.driver_pin_locked", "Trop de tentatives pour ce véhicule, réessayez dans {minutes} min", "محاولات كثيرة لهذه السيارة، أعد المحاولة بعد {minutes} دقيقة"),
+    ("error.self_check_in_day_pass_required", "Pas de pass du jour valide, achetez-le au guichet", "لا توجد تذكرة يومية صالحة، اشترها من الشباك"),
+    ("error.gate_printer_invalid", "Adresse de l'imprimante du portail invalide", "عنوان طابعة البوابة غير صالح"),
+    ("error.anpr_low_confidence", "Lecture {plate} trop incertaine ({confidence} < {min})", "قراءة {plate} غير مؤكدة ({confidence} < {min})"),
+    ("error.anpr_plate_unreadable", "Matricule illisible", "رقم اللوحة غير مقروء"),
+    ("error.anpr_plate_unknown", "Aucun véhicule ne correspond à la lecture {plate}", "لا توجد سيارة مطابقة للقراءة {plate}"),
+    ("error.anpr_already_queued", "Le véhicule {plate} est déjà dans la file", "السيارة {plate} موجودة في الطابور"),
+    ("error.anpr_no_destination", "Le véhicule {plate} n'a aucune destination autorisée", "السيارة {plate} ليس لها أي وجهة مرخصة"),
+    ("error.anpr_entry_not_pending", "Cette entrée n'attend plus de confirmation", "هذا الدخول لم يعد في انتظار التأكيد"),
+    ("error.sign_off_required", "Autorisation d'un superviseur requise", "يلزم ترخيص من المشرف"),
+    ("error.approval_not_usable", "Demande d'autorisation introuvable, non approuvée, déjà utilisée ou de plus de {minutes} min", "طلب الترخيص غير موجود أو غير مقبول أو مستعمل أو أقدم من {minutes} دقيقة"),
+    ("error.approval_subject_required", "Précisez l'objet de la demande", "حدد موضوع الطلب"),
+    ("error.approval_not_found", "Demande d'autorisation introuvable", "طلب الترخيص غير موجود"),
+    ("error.approval_not_pending", "Cette demande a déjà été traitée", "تمت معالجة هذا الطلب مسبقا"),
+    ("error.invalid_approval_policy", "Le seuil de remplissage doit être entre 0 et 100 %", "يجب أن تكون عتبة الامتلاء بين 0 و 100 %"),
+    ("error.dispatch_below_threshold", "{sold}/{total} places vendues, sous le seuil de {min} %: autorisation d'un superviseur requise", "{sold}/{total} مقاعد مباعة، دون عتبة {min} %: يلزم ترخيص من المشرف"),
+    ("error.business_hours_invalid", "L'heure d'ouverture doit précéder l'heure de fermeture", "يجب أن تسبق ساعة الفتح ساعة الإغلاق"),
+    ("error.station_closed", "Station fermée : les ventes reprennent à {opens} (fermeture à {closes}). Un superviseur peut autoriser la vente hors horaires.", "المحطة مغلقة: تستأنف المبيعات على الساعة {opens} (الإغلاق على الساعة {closes}). يمكن للمشرف السماح بالبيع خارج أوقات العمل."),
+    ("error.training_blocked", "Indisponible en mode formation", "غير متاح في وضع التدريب"),
+    ("error.training_not_seeded", "Préparez d'abord les données de formation", "قم بإعداد بيانات التدريب أولا"),
+    ("error.route_alias_too_long", "Un alias compte au plus {max} caractères", "لا يتجاوز الاسم البديل {max} حرفا"),
+    ("error.too_many_route_aliases", "Au plus {max} alias par destination", "{max} أسماء بديلة على الأكثر لكل وجهة"),
+    ("error.vehicle_capacity_invalid", "La capacité doit être entre 1 et {max} places", "يجب أن تكون السعة بين 1 و {max} مقاعد"),
+    ("error.vehicle_capacity_below_booked", "{booked} places déjà vendues sur ce véhicule: la capacité ne peut pas descendre à {capacity}", "{booked} مقاعد مباعة مسبقا في هذه السيارة: لا يمكن خفض السعة إلى {capacity}"),
+    ("error.vehicle_capacity_fills_entry", "{booked} places déjà vendues: à cette capacité le véhicule serait complet sans bon de sortie; terminez le voyage avec capacité partielle", "{booked} مقاعد مباعة مسبقا: بهذه السعة تمتلئ السيارة دون وصل خروج؛ أنهِ الرحلة بسعة جزئية"),
+    ("error.no_default_destination", "Aucune destination par défaut pour {plate}: choisissez la destination", "لا توجد وجهة افتراضية للسيارة {plate}: اختر الوجهة"),
+    ("error.ticket_not_escrowed", "Ce ticket n'est plus en attente d'impression", "هذه التذكرة لم تعد في انتظار الطباعة"),
+    ("error.day_pass_not_found", "Pass journalier introuvable: {id}", "التصريح اليومي غير موجود: {id}"),
+    ("error.refund_amount_invalid", "Le montant à rembourser doit être positif", "يجب أن يكون المبلغ المسترجع موجبا"),
+    ("error.refund_exceeds_paid", "Remboursement refusé: il ne reste que {refundable} TND à rembourser sur {reference}", "تم رفض الاسترجاع: لم يتبق سوى {refundable} TND للاسترجاع على {reference}"),
+    ("error.staff_not_found", "Employé introuvable: {id}", "الموظف غير موجود: {id}"),
+    ("error.pin_incorrect", "Code PIN incorrect", "الرمز السري غير صحيح"),
+    ("error.pin_change_not_allowed", "Saisissez le code PIN actuel ou celui d'un superviseur", "أدخل الرمز السري الحالي أو رمز المشرف"),
+    ("error.pin_rejected", "Ce code PIN ne peut pas être utilisé, choisissez-en un autre", "لا يمكن استعمال هذا الرمز السري، اختر رمزا آخر"),
+    ("error.session_invalid", "Session non reconnue, reconnectez-vous", "لم يتم التعرف على الجلسة، أعد تسجيل الدخول"),
+    ("error.booking_price_changed", "Les places ont changé pendant le paiement, le paiement par carte a été annulé; réessayez", "تغيرت المقاعد أثناء الدفع، تم إلغاء الدفع بالبطاقة؛ أعد المحاولة"),
+    ("error.kiosk_needs_card_terminal", "La borne nécessite un terminal de paiement configuré (EFT_ENDPOINT)", "تتطلب المحطة الذاتية جهاز دفع مهيأ (EFT_ENDPOINT)"),
+    ("error.seats_out_of_range", "Le nombre de places doit être entre 1 et {max}", "يجب أن يكون عدد المقاعد بين 1 و {max}"),
+    ("error.price_invalid", "Le prix doit être un nombre positif", "يجب أن يكون السعر رقما موجبا"),
+    ("error.retention_too_short", "{table} doit être conservé au moins {min} jours", "يجب الاحتفاظ بـ {table} {min} يوما على الأقل"),
+    ("error.printed_ticket_not_found", "Ticket introuvable dans l'historique d'impression", "التذكرة غير موجودة في سجل الطباعة"),
+    ("error.ticket_already_voided", "Le numéro {number} est déjà annulé", "الرقم {number} ملغى مسبقا"),
+    ("error.no_show_already", "La réservation {code} est déjà marquée absente", "الحجز {code} مسجل غائبا من قبل"),
+    ("error.no_show_not_paid", "Seule une réservation payée peut être marquée absente (statut {status})", "لا يمكن تسجيل الغياب إلا لحجز مدفوع (الحالة {status})"),
+    ("error.customer_name_required", "Le nom du client est obligatoire", "اسم الحريف إجباري"),
+    ("error.customer_not_found", "Client introuvable", "الحريف غير موجود"),
+    ("error.settlement_amount_positive", "Le montant du règlement doit être supérieur à 0", "يجب أن يكون مبلغ التسوية أكبر من 0"),
+    ("error.seats_positive", "Le nombre de places doit être supérieur à 0", "يجب أن يكون عدد المقاعد أكبر من 0"),
+    ("error.reservation_not_found", "Réservation introuvable", "الحجز غير موجود"),
+    ("error.reservation_not_pending", "Cette réservation n'est plus en attente", "هذا الحجز لم يعد في الانتظار"),
+    ("error.reservation_not_pending_status", "Cette réservation n'est plus en attente (statut {status})", "هذا الحجز لم يعد في الانتظار (الحالة {status})"),
+    ("error.waitlist_not_waiting", "Cette inscription n'est plus en attente", "هذا التسجيل لم يعد في الانتظار"),
+    ("error.waitlist_entry_not_found", "Inscription introuvable", "التسجيل غير موجود"),
+    ("error.trip_stats_days_invalid", "Nombre de jours: entre 1 et {max}", "عدد الأيام: بين 1 و {max}"),
+    ("error.gps_position_invalid", "Position GPS invalide: {latitude}, {longitude}", "موقع GPS غير صالح: {latitude}، {longitude}"),
+    ("error.gps_vehicle_required", "La position doit indiquer le véhicule (id ou plaque)", "يجب أن يحدد الموقع السيارة (المعرف أو اللوحة)"),
+    ("error.gps_vehicle_unknown", "Véhicule inconnu: {vehicle}", "سيارة غير معروفة: {vehicle}"),
+    ("error.gps_time_ahead", "L'heure de la position est dans le futur", "توقيت الموقع في المستقبل"),
+    ("error.annotation_label_required", "Le libellé de la note est obligatoire", "نص الملاحظة إجباري"),
+    ("error.annotation_label_too_long", "Le libellé de la note ne peut dépasser {max} caractères", "لا يمكن أن يتجاوز نص الملاحظة {max} حرفا"),
+    ("error.annotation_not_found", "Note introuvable", "الملاحظة غير موجودة"),
+    ("error.invalid_weekday", "Jour invalide: {weekday} (1 lundi à 7 dimanche)", "يوم غير صالح: {weekday} (1 الإثنين إلى 7 الأحد)"),
+    ("error.demand_weeks_invalid", "Nombre de semaines: entre 1 et {max}", "عدد الأسابيع: بين 1 و {max}"),
+    ("error.destination_not_found", "Destination introuvable", "الوجهة غير موجودة"),
+    ("error.transfer_no_booked_seats", "Aucun siège réservé à transférer depuis ce véhicule", "لا توجد مقاعد محجوزة لنقلها من هذه السيارة"),
+    ("error.transfer_target_invalid", "Véhicule cible introuvable ou invalide", "السيارة المستهدفة غير موجودة أو غير صالحة"),
+    ("error.transfer_other_sub_route", "Le véhicule cible n'appartient pas à la même sous-route", "السيارة المستهدفة لا تنتمي إلى نفس الخط الفرعي"),
+    ("error.transfer_no_vehicle_in_sub_route", "Aucun autre véhicule disponible dans cette sous-route pour transférer les sièges", "لا توجد سيارة أخرى متاحة في هذا الخط الفرعي لنقل المقاعد"),
+    ("error.transfer_no_vehicle", "Aucun autre véhicule disponible sans sous-route pour transférer les sièges", "لا توجد سيارة أخرى متاحة دون خط فرعي لنقل المقاعد"),
+    ("error.transfer_not_enough_seats", "Le véhicule cible n'a que {available} sièges disponibles, mais {required} sièges doivent être transférés", "السيارة المستهدفة فيها {available} مقاعد متاحة فقط ويجب نقل {required} مقاعد"),
+    ("error.transfer_seats_invalid", "Le nombre de sièges à transférer doit être positif", "يجب أن يكون عدد المقاعد المنقولة موجبا"),
+    ("error.transfer_same_vehicle", "Le véhicule cible est le véhicule source", "السيارة المستهدفة هي نفسها السيارة المصدر"),
+    ("error.transfer_source_invalid", "Véhicule source introuvable dans la file", "السيارة المصدر غير موجودة في الطابور"),
+    ("error.transfer_not_enough_booked", "Le véhicule source n'a que {booked} sièges réservés, {requested} demandés", "السيارة المصدر فيها {booked} مقاعد محجوزة فقط، والمطلوب {requested}"),
+    ("error.transfer_seats_split", "Impossible de transférer exactement {seats} sièges sans diviser une réservation", "لا يمكن نقل {seats} مقاعد بالضبط دون تقسيم حجز"),
+    ("error.waitlist_not_waiting_status", "Cette inscription n'est plus en attente (statut {status})", "هذا التسجيل لم يعد في الانتظار (الحالة {status})"),
+    ("error.supervisor_required", "Réservé aux superviseurs", "مخصص للمشرفين"),
+    ("error.admin_required", "Réservé aux administrateurs", "مخصص للمسؤولين"),
+    ("error.escpos_invalid_base64", "Séquence ESC/POS invalide: base64 attendu", "تسلسل ESC/POS غير صالح: يجب أن يكون base64"),
+    ("error.escpos_empty", "Séquence ESC/POS vide", "تسلسل ESC/POS فارغ"),
+    ("error.escpos_too_large", "Séquence ESC/POS trop longue ({size} octets, maximum {max})", "تسلسل ESC/POS طويل جدا ({size} بايت، الحد الأقصى {max})"),
+    ("error.escpos_macro_name", "Nom de macro obligatoire, {max} caractères au plus", "اسم الماكرو إجباري، {max} حرفا على الأكثر"),
+    ("error.escpos_macro_not_found", "Macro introuvable: {name}", "الماكرو غير موجود: {name}"),
+    ("error.firewall_invalid_name", "Nom de règle pare-feu invalide: {name}", "اسم قاعدة جدار الحماية غير صالح: {name}"),
+    ("error.firewall_invalid_path", "Chemin de l'application invalide: {path}", "مسار التطبيق غير صالح: {path}"),
+    ("error.firewall_unsupported", "Règles pare-feu gérées sous Windows uniquement", "قواعد جدار الحماية متاحة على ويندوز فقط"),
+    ("error.firewall_declined", "Autorisation administrateur refusée: règles pare-feu de {name} inchangées", "تم رفض صلاحية المسؤول: لم تتغير قواعد جدار الحماية لـ {name}"),
+    ("error.firewall_failed", "Échec de la mise à jour du pare-feu: {error}", "فشل تحديث جدار الحماية: {error}"),
+    ("error.startup_failed", "Échec du démarrage automatique ({method}): {error}", "فشل التشغيل التلقائي ({method}): {error}"),
+    ("error.no_local_ip", "Aucune adresse IPv4 locale trouvée", "لم يتم العثور على عنوان IPv4 محلي"),
+    ("error.station_network_invalid", "Réseau de la station invalide: {network} (trois nombres, ex. 192.168.192)", "شبكة المحطة غير صالحة: {network} (ثلاثة أرقام، مثال 192.168.192)"),
+    ("error.interface_pattern_invalid", "Motif d'interface trop long: {pattern} ({max} caractères au plus)", "نمط الواجهة طويل جدا: {pattern} ({max} حرفا على الأكثر)"),
+    ("error.proxy_denied", "Requête non autorisée vers le serveur local: {method} {endpoint}", "طلب غير مسموح به إلى الخادم المحلي: {method} {endpoint}"),
+    ("error.proxy_rule_invalid", "Règle d'accès invalide: {prefix} ({methods})", "قاعدة وصول غير صالحة: {prefix} ({methods})"),
+    ("error.pricing_rule_label_required", "Le libellé de la règle est obligatoire", "اسم القاعدة إجباري"),
+    ("error.pricing_rule_value", "Indiquez soit un montant, soit un pourcentage entre 0 et 100", "حدد مبلغا أو نسبة بين 0 و 100"),
+    ("error.pricing_rule_not_applicable", "Règle tarifaire inactive ou non applicable à cette destination: {id}", "قاعدة التسعير غير نشطة أو لا تنطبق على هذه الوجهة: {id}"),
+    ("error.child_seats_exceed", "{children} places enfant pour {seats} places réservées", "{children} مقاعد أطفال مقابل {seats} مقاعد محجوزة"),
+    ("error.expense_amount_positive", "Le montant de la dépense doit être supérieur à 0", "يجب أن يكون مبلغ المصروف أكبر من 0"),
+    ("error.expense_category_required", "La catégorie de la dépense est obligatoire", "صنف المصروف إجباري"),
+    ("error.unknown_denomination", "Billet ou pièce inconnu: {amount} TND", "ورقة أو قطعة نقدية غير معروفة: {amount} TND"),
+    ("error.cash_count_negative", "Le nombre de billets ou de pièces ne peut pas être négatif", "لا يمكن أن يكون عدد الأوراق أو القطع سالبا"),
+    ("error.invalid_override_reason", "Motif de dérogation invalide: {code}", "سبب الاستثناء غير صالح: {code}"),
+    ("error.override_note_required", "Une note expliquant la dérogation est obligatoire", "ملاحظة تشرح الاستثناء إجبارية"),
+    ("error.invalid_board_schedule", "Programme invalide: au moins 5 minutes entre deux impressions et une fin après le début", "برنامج غير صالح: 5 دقائق على الأقل بين طباعتين ونهاية بعد البداية"),
+    ("error.invalid_roll_length", "Longueur de rouleau invalide: {length} m", "طول لفافة الورق غير صالح: {length} م"),
+    ("error.unknown_secret", "Secret inconnu: {name}", "سر غير معروف: {name}"),
+    ("error.secret_store_unavailable", "Coffre de mots de passe du système indisponible: {error}", "خزنة كلمات السر في النظام غير متاحة: {error}"),
+    ("error.variance_threshold_negative", "Le seuil d'écart ne peut pas être négatif", "لا يمكن أن يكون حد الفارق سالبا"),
+    ("error.pin_locked", "Trop de tentatives, réessayez dans {seconds} s", "محاولات كثيرة، أعد المحاولة بعد {seconds} ثانية"),
+    ("error.window_command_denied", "Commande non autorisée dans cette fenêtre", "أمر غير مسموح به في هذه النافذة"),
+    ("error.rate_limited", "Trop de requêtes, veuillez patienter un instant", "طلبات كثيرة، يرجى الانتظار قليلا"),
+    ("error.kiosk_card_only", "La borne accepte uniquement le paiement par carte", "المحطة الذاتية تقبل الدفع بالبطاقة فقط"),
+    // Anomaly alerts
+    ("anomaly.repeated_cancellations", "{subject}: {count} annulations en moins d'une heure", "{subject}: {count} إلغاءات في أقل من ساعة"),
+    ("anomaly.bookings_removed_after_exit", "{subject}: réservations retirées après le pass de sortie ({before} places, {after} maintenant)", "{subject}: حجوزات حذفت بعد إذن الخروج ({before} مقاعد، {after} الآن)"),
+    ("anomaly.repeated_free_entries", "{subject}: {count} tickets d'entrée à 0 TND en moins d'une heure", "{subject}: {count} تذاكر دخول بـ 0 دينار في أقل من ساعة"),
+    // Command confirmations
+    ("message.ticket_reprinted", "Duplicata n°{number} envoyé à l'imprimante", "أرسل النظير رقم {number} إلى الطابعة"),
+    ("message.ticket_replaced", "Numéro {voided} annulé: ticket réimprimé sous le n°{number}", "ألغي الرقم {voided}: أعيد طبع التذكرة تحت الرقم {number}"),
+    ("message.ticket_reprinted_fee", "Duplicata n°{number} envoyé à l'imprimante, frais à encaisser: {fee} TND", "أرسل النظير رقم {number} إلى الطابعة، المعلوم المستحق: {fee} TND"),
+    ("message.booking_no_show", "Réservation {code} marquée absente: {seats} place(s) remise(s) en vente sur {plate}, {refund} TND remboursés", "تم تسجيل غياب الحجز {code}: {seats} مقعد(مقاعد) أعيدت للبيع في {plate}، واسترجاع {refund} TND"),
+    ("message.booking_no_show_departed", "Réservation {code} marquée absente ({plate} déjà parti), {refund} TND remboursés", "تم تسجيل غياب الحجز {code} ({plate} غادرت)، واسترجاع {refund} TND"),
+    ("message.booking_cancelled", "Réservation {code} annulée complètement pour {destination} (véhicule {plate})", "تم إلغاء الحجز {code} بالكامل إلى {destination} (السيارة {plate})"),
+    ("message.seat_cancelled", "1 place annulée de la réservation {code} pour {destination} (véhicule {plate})", "تم إلغاء مقعد واحد من الحجز {code} إلى {destination} (السيارة {plate})"),
+    ("message.vehicle_created", "Véhicule {plate} créé avec succès (ID: {id})", "تمت إضافة السيارة {plate} بنجاح (المعرف: {id})"),
+    ("message.phone_shared", "Ce numéro est aussi celui de: {plates}", "هذا الرقم مسجل أيضا لـ: {plates}"),
+    ("message.authorization_created", "Autorisation créée pour la station {station}", "تم إنشاء الترخيص للمحطة {station}"),
+    ("message.vehicle_banned", "Véhicule banni avec succès", "تم حظر السيارة بنجاح"),
+    ("message.vehicle_queued", "Véhicule {plate} ajouté à la file d'attente pour {destination}", "تمت إضافة السيارة {plate} إلى طابور {destination}"),
+    ("message.vehicle_dequeued", "Véhicule {plate} retiré de la file d'attente", "تم سحب السيارة {plate} من الطابور"),
+    ("message.position_updated", "Position mise à jour avec succès", "تم تحديث الترتيب بنجاح"),
+    ("message.seats_moved", "{count} sièges transférés de {from} vers {to}", "تم نقل {count} مقاعد من {from} إلى {to}"),
+    ("message.seats_transferred", "{count} sièges transférés. Véhicule {plate} conservé dans la file.", "تم نقل {count} مقاعد. السيارة {plate} باقية في الطابور."),
+    ("message.vehicle_stalled", "{plate} ({destination}) charge depuis {minutes} min avec {booked}/{total} places réservées", "{plate} ({destination}) في التحميل منذ {minutes} دقيقة مع {booked}/{total} مقاعد محجوزة"),
+    ("message.stalled_vehicle_released", "{count} sièges transférés au véhicule suivant. {plate} renvoyé en fin de file.", "تم نقل {count} مقاعد إلى السيارة التالية. أعيدت {plate} إلى آخر الطابور."),
+    ("message.destination_dispatched", "{count} véhicule(s) partis pour {destination}: {seats} places, {amount} TND encaissés. {left} véhicule(s) vide(s) restent en file.", "انطلقت {count} سيارة نحو {destination}: {seats} مقاعد، {amount} TND محصلة. بقيت {left} سيارة فارغة في الطابور."),
+    ("message.vehicle_removed_unbooked", "Véhicule {plate} retiré de la file (aucune réservation)", "تم سحب السيارة {plate} من الطابور (لا يوجد حجز)"),
+    ("message.vehicle_emergency_removed", "Véhicule {plate} supprimé d'urgence - {count} réservations annulées - Remboursement: {refund} TND", "تم سحب السيارة {plate} بشكل عاجل - {count} حجوزات ملغاة - الاسترجاع: {refund} TND"),
+    ("message.pass_already_valid", "Un pass valide existe déjà pour {plate} aujourd'hui ({id})", "يوجد تصريح صالح للسيارة {plate} اليوم ({id})"),
+    ("message.day_pass_printed", "Pass journalier imprimé avec succès pour {plate}", "تمت طباعة التصريح اليومي للسيارة {plate} بنجاح"),
+    ("message.pass_purchased", "Pass {pass} acheté avec succès pour {plate} ({amount} TND)", "تم شراء التصريح {pass} للسيارة {plate} بنجاح ({amount} TND)"),
+    ("message.firewall_rule_added", "Règles pare-feu de {name} ajoutées", "تمت إضافة قواعد جدار الحماية لـ {name}"),
+    ("message.firewall_rule_present", "Règles pare-feu de {name} déjà en place", "قواعد جدار الحماية لـ {name} موجودة بالفعل"),
+    ("message.firewall_rule_removed", "Règles pare-feu de {name} supprimées", "تم حذف قواعد جدار الحماية لـ {name}"),
+    ("message.firewall_rule_absent", "Aucune règle pare-feu pour {name}", "لا توجد قواعد جدار الحماية لـ {name}"),
+    ("message.startup_enabled", "Démarrage automatique activé ({method})", "تم تفعيل التشغيل التلقائي ({method})"),
+    ("message.startup_already_enabled", "Démarrage automatique déjà activé ({method})", "التشغيل التلقائي مفعل بالفعل ({method})"),
+    ("message.startup_disabled", "Démarrage automatique désactivé", "تم إيقاف التشغيل التلقائي"),
+    ("message.startup_not_enabled", "Démarrage automatique non activé", "التشغيل التلقائي غير مفعل"),
+    ("message.station_open", "Station ouverte · fermeture à {closes}", "المحطة مفتوحة · الإغلاق على الساعة {closes}"),
+    ("message.station_closed", "Station fermée · ouverture à {opens}", "المحطة مغلقة · الفتح على الساعة {opens}"),
+];
+
+/// Catalog text for `key` in `lang`; the key itself when missing so a typo shows up on screen
+pub fn lookup(key: &str, lang: Lang) -> &str {
+    match CATALOG.iter().find(|(k, _, _)| *k == key) {
+        Some((_, fr, ar)) => match lang {
+            Lang::Fr => fr,
+            Lang::Ar => ar,
+        },
+        None => key,
+    }
+}
+
+fn format_args_into(template: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut text = template.to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// Current station wording for tickets
+pub fn ticket_language() -> TicketLanguage {
+    SETTINGS.read().map(|s| *s).unwrap_or_default()
+}
+
+/// Message in the station language
+pub fn t(key: &str) -> String {
+    lookup(key, ticket_language().lang).to_string()
+}
+
+/// Message in the station language with named arguments
+pub fn tf(key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    format_args_into(lookup(key, ticket_language().lang), args)
+}
+
+fn to_dto(settings: &TicketLanguage) -> LanguageSettingsDto {
+    LanguageSettingsDto {
+        language: settings.lang.as_str().to_string(),
+        bilingual_tickets: settings.bilingual,
+    }
+}
+
+/// Read the station language from the database into the cache
+pub async fn load() -> Result<TicketLanguage, String> {
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let lang = crate::db::settings::get(&client, "language").await?.and_then(|v| Lang::from_name(&v)).unwrap_or(Lang::Fr);
+    let bilingual = crate::db::settings::get(&client, "bilingual_tickets").await?.map(|v| v == "true").unwrap_or(false);
+    let settings = TicketLanguage { lang, bilingual };
+    if let Ok(mut cached) = SETTINGS.write() {
+        *cached = settings;
+    }
+    Ok(settings)
+}
+
+/// Re-read so a change made on another terminal of the station shows up
+#[tauri::command]
+pub async fn get_language_settings() -> Result<LanguageSettingsDto, String> {
+    Ok(to_dto(&load().await?))
+}
+
+#[tauri::command]
+pub async fn set_language_settings(language: String, bilingual_tickets: bool) -> Result<LanguageSettingsDto, String> {
+    let lang = Lang::from_name(&language).ok_or_else(|| tf("error.invalid_language", &[("name", &language)]))?;
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    crate::db::settings::set(&client, "language", lang.as_str()).await?;
+    crate::db::settings::set(&client, "bilingual_tickets", if bilingual_tickets { "true" } else { "false" }).await?;
+    println!("🌐 [I18N] Station language set to {} (bilingual tickets: {})", lang.as_str(), bilingual_tickets);
+    let settings = TicketLanguage { lang, bilingual: bilingual_tickets };
+    if let Ok(mut cached) = SETTINGS.write() {
+        *cached = settings;
+    }
+    Ok(to_dto(&settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_key_is_translated_once() {
+        for (i, (key, fr, ar)) in CATALOG.iter().enumerate() {
+            assert!(!fr.is_empty() && !ar.is_empty(), "{} is missing a translation", key);
+            assert!(CATALOG[i + 1..].iter().all(|(other, _, _)| other != key), "{} is listed twice", key);
+            // Both languages take the same arguments
+            let args = |text: &str| text.matches('{').count();
+            assert_eq!(args(fr), args(ar), "{} has different arguments", key);
+        }
+    }
+
+    #[test]
+    fn ticket_text_in_one_or_both_languages() {
+        let fr = TicketLanguage::default();
+        let ar = TicketLanguage { lang: Lang::Ar, bilingual: false };
+        let both = TicketLanguage { lang: Lang::Fr, bilingual: true };
+        assert_eq!(fr.label("ticket.plate"), "Plaque:");
+        assert_eq!(ar.label("ticket.plate"), "اللوحة:");
+        assert_eq!(both.label("ticket.plate"), "Plaque / اللوحة:");
+        assert_eq!(both.text_with("ticket.seat_count", &[("count", &8)]), "8 places / 8 مقاعد");
+        assert_eq!(lookup("ticket.unknown", Lang::Ar), "ticket.unknown");
+        assert_eq!(format_args_into(lookup("error.vehicle_inactive", Lang::Fr), &[("plate", &"123 TU 4567")]), "Véhicule inactif: 123 TU 4567");
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{GlobalShortcutManager, Manager, WindowBuilder, WindowEvent, WindowUrl};
+
+use crate::audit;
+use crate::auth;
+use crate::db;
+use crate::eft::EftConfig;
+use crate::i18n;
+use crate::shortcuts;
+use crate::window_layout;
+use crate::DB_POOL;
+
+// Self-service terminal in the hall: a full-screen window showing only destinations and seat
+// purchase, and the drivers' self check-in (self_check_in.rs). The middleware limits it to
+// KIOSK_COMMANDS, and the tray, the global shortcuts and the cashier window stay out of reach
+// until a supervisor ends kiosk mode.
+
+pub const KIOSK_WINDOW: &str = "kiosk";
+
+static KIOSK_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+// Everything the kiosk page needs, and nothing more
+pub const KIOSK_COMMANDS: &[&str] = &[
+    "get_app_name",
+    "get_app_version",
+    "db_get_available_booking_destinations",
+    "db_get_available_seats_for_destination",
+    "db_create_queue_booking",
+    "kiosk_print_tickets",
+    "kiosk_self_check_in",
+    "stop_kiosk_mode",
+];
+
+// Nobody at the kiosk can hand over cash or charge an account
+const KIOSK_PAYMENT_METHOD: &str = "CARD";
+
+// Name printed on the tickets where the cashier's would be
+const KIOSK_STAFF_NAME: &str = "Borne";
+
+pub fn is_active() -> bool {
+    KIOSK_ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Bookings from the kiosk window are paid by card only, on a terminal this app drives
+pub fn check_booking(command: &str, payload: &serde_json::Value) -> Result<(), String> {
+    if command != "db_create_queue_booking" {
+        return Ok(());
+    }
+    check_payment(payload, EftConfig::from_env().is_some())
+}
+
+// Without a terminal on EFT_ENDPOINT a card booking is recorded as paid on the cashier's word,
+// and at the kiosk there is no cashier
+fn check_payment(payload: &serde_json::Value, has_terminal: bool) -> Result<(), String> {
+    if !has_terminal {
+        return Err(i18n::t("error.kiosk_needs_card_terminal"));
+    }
+    let method = payload.get("paymentMethod").and_then(|v| v.as_str()).unwrap_or("");
+    let has_split = payload.get("payments").is_some_and(|v| !v.is_null());
+    let has_customer = payload.get("customerId").is_some_and(|v| !v.is_null());
+    if !method.eq_ignore_ascii_case(KIOSK_PAYMENT_METHOD) || has_split || has_customer {
+        return Err(i18n::t("error.kiosk_card_only"));
+    }
+    Ok(())
+}
+
+/// Print the tickets of bookings the kiosk just made, read back from the database. Print only:
+/// the kiosk window records bookings through db_create_queue_booking and nothing else.
+#[tauri::command]
+pub async fn kiosk_print_tickets(booking_ids: Vec<String>) -> Result<usize, String> {
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let tickets = db::booking::tickets(&client, &booking_ids).await?;
+    if tickets.is_empty() {
+        return Ok(0);
+    }
+    crate::PRINTER_SERVICE.print_booking_tickets(&tickets, Some(KIOSK_STAFF_NAME.to_string())).await?;
+    Ok(tickets.len())
+}
+
+/// Open the kiosk window over the cashier window and lock the terminal down
+#[tauri::command]
+pub async fn start_kiosk_mode(app_handle: tauri::AppHandle) -> Result<(), String> {
+    if EftConfig::from_env().is_none() {
+        return Err(i18n::t("error.kiosk_needs_card_terminal"));
+    }
+    if app_handle.get_window(KIOSK_WINDOW).is_none() {
+        let window = WindowBuilder::new(&app_handle, KIOSK_WINDOW, WindowUrl::App("index.html#/kiosk".into()))
+            .title("Borne de réservation")
+            .fullscreen(true)
+            .decorations(false)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .resizable(false)
+            .build()
+            .map_err(|e| e.to_string())?;
+        // The hall display it was last put on
+        window_layout::restore_and_track(&window);
+        // Only stop_kiosk_mode closes it
+        let kiosk = window.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::CloseRequested { api, .. } = event {
+                if is_active() {
+                    api.prevent_close();
+                    let _ = kiosk.set_focus();
+                }
+            }
+        });
+    }
+
+    KIOSK_ACTIVE.store(true, Ordering::SeqCst);
+    if let Err(e) = app_handle.global_shortcut_manager().unregister_all() {
+        println!("⚠️ [KIOSK] Failed to release global shortcuts: {}", e);
+    }
+    if let Some(main) = app_handle.get_window("main") {
+        let _ = main.hide();
+    }
+    if let Some(kiosk) = app_handle.get_window(KIOSK_WINDOW) {
+        let _ = kiosk.set_focus();
+    }
+
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    audit::record(&client, "KIOSK_STARTED", auth::active_staff_id().as_deref(), serde_json::json!({})).await?;
+    println!("🏧 [KIOSK] Kiosk mode started");
+    Ok(())
+}
+
+/// Close the kiosk and bring the cashier window back; needs a supervisor's PIN
+#[tauri::command]
+pub async fn stop_kiosk_mode(app_handle: tauri::AppHandle, pin: String) -> Result<(), String> {
+    let supervisor = auth::verify_supervisor_pin(&pin).await?;
+
+    KIOSK_ACTIVE.store(false, Ordering::SeqCst);
+    if let Some(kiosk) = app_handle.get_window(KIOSK_WINDOW) {
+        let _ = kiosk.close();
+    }
+    if let Err(e) = shortcuts::register(&app_handle, &shortcuts::load()) {
+        println!("⚠️ [KIOSK] Failed to restore global shortcuts: {}", e);
+    }
+    if let Some(main) = app_handle.get_window("main") {
+        let _ = main.show();
+        let _ = main.set_focus();
+    }
+
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    audit::record(&client, "KIOSK_STOPPED", Some(&supervisor.id), serde_json::json!({})).await?;
+    println!("🏧 [KIOSK] Kiosk mode ended by {} {}", supervisor.firstName, supervisor.lastName);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn kiosk_bookings_are_card_only() {
+        let card = json!({ "destinationId": "d1", "seatsRequested": 2, "paymentMethod": "CARD" });
+        assert!(check_payment(&card, true).is_ok());
+        assert!(check_booking("db_get_available_booking_destinations", &json!({})).is_ok());
+
+        let cash = json!({ "destinationId": "d1", "seatsRequested": 2, "paymentMethod": "CASH" });
+        assert!(check_payment(&cash, true).is_err());
+        let on_account = json!({ "paymentMethod": "CARD", "customerId": "c1" });
+        assert!(check_payment(&on_account, true).is_err());
+    }
+
+    #[test]
+    fn the_kiosk_prints_without_recording_bookings() {
+        // print_booking_ticket inserts a booking for a ticket carrying a queue id
+        assert!(!KIOSK_COMMANDS.contains(&"print_booking_ticket"));
+        assert!(KIOSK_COMMANDS.contains(&"kiosk_print_tickets"));
+    }
+
+    #[test]
+    fn kiosk_card_bookings_need_a_terminal() {
+        let card = json!({ "destinationId": "d1", "seatsRequested": 2, "paymentMethod": "CARD" });
+        assert_eq!(check_payment(&card, false), Err(i18n::t("error.kiosk_needs_card_terminal")));
+    }
+}
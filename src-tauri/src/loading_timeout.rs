@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::db;
+use crate::db::loading_timeouts::LoadingEntry;
+use crate::i18n;
+use crate::time::{self, TunisTime};
+
+// Vehicles stuck LOADING: a vehicle that took a few bookings and then sat half full (driver gone,
+// breakdown) holds those passengers while the vehicles behind it wait. Past the destination's
+// timeout the watch tells supervisors, once per loading spell, and db_release_stalled_vehicle
+// moves its bookings to the next vehicle with the seat transfer and sends it to the back of the
+// line. The timeouts are the loading_timeout setting (JSON in station_settings).
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+const MAX_TIMEOUT_MINUTES: u32 = 24 * 60;
+pub const VEHICLE_STALLED_EVENT: &str = "vehicle-stalled";
+
+// Loading spells already sent to the UI since the app started
+static REPORTED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LoadingTimeoutPolicy {
+    pub enabled: bool,
+    pub default_minutes: u32,
+    /// Minutes by destination id, overriding the default; 0 never flags the destination
+    pub destinations: HashMap<String, u32>,
+}
+
+impl Default for LoadingTimeoutPolicy {
+    fn default() -> Self {
+        LoadingTimeoutPolicy { enabled: true, default_minutes: 45, destinations: HashMap::new() }
+    }
+}
+
+impl LoadingTimeoutPolicy {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let too_long = std::iter::once(&self.default_minutes).chain(self.destinations.values()).any(|m| *m > MAX_TIMEOUT_MINUTES);
+        if too_long {
+            return Err(i18n::tf("error.loading_timeout_too_long", &[("max", &MAX_TIMEOUT_MINUTES)]));
+        }
+        Ok(())
+    }
+
+    /// Minutes a vehicle of the destination may load before it is flagged; None never flags it
+    pub fn minutes_for(&self, destination_id: &str) -> Option<u32> {
+        if !self.enabled {
+            return None;
+        }
+        let minutes = self.destinations.get(destination_id).copied().unwrap_or(self.default_minutes);
+        (minutes > 0).then_some(minutes)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StalledVehicleDto {
+    pub queue_id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub destination_name: String,
+    pub sub_route_name: Option<String>,
+    pub queue_position: i32,
+    pub booked_seats: i32,
+    pub total_seats: i32,
+    pub loading_since: String,
+    pub minutes_loading: i64,
+    pub timeout_minutes: u32,
+    pub message: String,
+}
+
+impl StalledVehicleDto {
+    fn key(&self) -> String {
+        format!("{}|{}", self.queue_id, self.loading_since)
+    }
+}
+
+/// The entries loading for longer than their destination allows at `now`
+pub fn stalled(policy: &LoadingTimeoutPolicy, entries: Vec<LoadingEntry>, now: DateTime<Utc>) -> Vec<StalledVehicleDto> {
+    entries
+        .into_iter()
+        .filter_map(|e| {
+            let timeout_minutes = policy.minutes_for(&e.destination_id)?;
+            let minutes_loading = (now - e.loading_since).num_minutes();
+            if minutes_loading < timeout_minutes as i64 {
+                return None;
+            }
+            let message = i18n::tf(
+                "message.vehicle_stalled",
+                &[("plate", &e.license_plate), ("destination", &e.destination_name), ("minutes", &minutes_loading), ("booked", &e.booked_seats), ("total", &e.total_seats)],
+            );
+            Some(StalledVehicleDto {
+                queue_id: e.queue_id,
+                license_plate: e.license_plate,
+                destination_id: e.destination_id,
+                destination_name: e.destination_name,
+                sub_route_name: e.sub_route_name,
+                queue_position: e.queue_position,
+                booked_seats: e.booked_seats,
+                total_seats: e.total_seats,
+                loading_since: TunisTime(e.loading_since.with_timezone(&time::TZ)).fmt_dto(),
+                minutes_loading,
+                timeout_minutes,
+                message,
+            })
+        })
+        .collect()
+}
+
+pub async fn stalled_now(client: &impl db::GenericClient) -> Result<Vec<StalledVehicleDto>, String> {
+    let policy = db::loading_timeouts::policy(client).await?;
+    let entries = db::loading_timeouts::loading(client).await?;
+    Ok(stalled(&policy, entries, crate::clock::now()))
+}
+
+/// Stalled vehicles not reported yet, remembered as reported
+async fn check(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let client = db::pool::background_read().await?;
+    let vehicles = stalled_now(&client).await?;
+    let fresh: Vec<StalledVehicleDto> = match REPORTED.lock() {
+        Ok(mut reported) => vehicles.into_iter().filter(|v| reported.insert(v.key())).collect(),
+        Err(e) => return Err(e.to_string()),
+    };
+    for vehicle in &fresh {
+        println!("⏳ [LOADING] {}", vehicle.message);
+        let _ = app_handle.emit_all(VEHICLE_STALLED_EVENT, vehicle);
+    }
+    Ok(())
+}
+
+pub async fn run_loading_watch(app_handle: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if let Err(e) = check(&app_handle).await {
+            println!("⚠️ [LOADING] Check failed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry(queue_id: &str, destination_id: &str, loading_since: DateTime<Utc>) -> LoadingEntry {
+        LoadingEntry {
+            queue_id: queue_id.to_string(),
+            license_plate: format!("{} TUN 1", queue_id),
+            destination_id: destination_id.to_string(),
+            destination_name: destination_id.to_string(),
+            sub_route_name: None,
+            queue_position: 1,
+            booked_seats: 3,
+            total_seats: 8,
+            loading_since,
+        }
+    }
+
+    #[test]
+    fn vehicles_are_flagged_past_their_destination_timeout() {
+        let now = DateTime::<Utc>::from_timestamp(1_760_000_000, 0).unwrap();
+        let ago = |minutes| now - Duration::minutes(minutes);
+        let policy = LoadingTimeoutPolicy {
+            default_minutes: 30,
+            destinations: HashMap::from([("tunis".to_string(), 60), ("kairouan".to_string(), 0)]),
+            ..Default::default()
+        };
+        let entries = vec![
+            entry("a", "sousse", ago(31)),
+            entry("b", "sousse", ago(29)),
+            entry("c", "tunis", ago(45)),
+            entry("d", "tunis", ago(60)),
+            entry("e", "kairouan", ago(600)),
+        ];
+        let flagged = stalled(&policy, entries, now);
+        assert_eq!(flagged.iter().map(|v| (v.queue_id.as_str(), v.timeout_minutes)).collect::<Vec<_>>(), vec![("a", 30), ("d", 60)]);
+        assert_eq!(flagged[0].minutes_loading, 31);
+
+        assert_eq!(LoadingTimeoutPolicy { enabled: false, ..policy.clone() }.minutes_for("sousse"), None);
+        assert_eq!(LoadingTimeoutPolicy::parse(Some(r#"{"defaultMinutes":20}"#)).minutes_for("sousse"), Some(20));
+        assert!(LoadingTimeoutPolicy { default_minutes: 24 * 60 + 1, ..policy }.validate().is_err());
+    }
+}
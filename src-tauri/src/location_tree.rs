@@ -0,0 +1,59 @@
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::db;
+use crate::db::locations::GovernorateNode;
+use crate::DB_POOL;
+
+// The governorate → delegation → station tree of the booking filters, read once and kept until
+// the routes change on any terminal (route_changes NOTIFY, migration 020). Like the day-pass
+// cache, it is not kept while the cache listener is not connected.
+
+static CACHE: Lazy<Mutex<Cached>> = Lazy::new(|| Mutex::new(Cached::default()));
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default)]
+struct Cached {
+    tree: Option<Vec<GovernorateNode>>,
+    // Bumped by every invalidation, so a tree read while the routes changed is not kept
+    generation: u64,
+}
+
+fn cache() -> std::sync::MutexGuard<'static, Cached> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The whole tree, from the cache when it is still current
+pub async fn get() -> Result<Vec<GovernorateNode>, String> {
+    let listening = LISTENING.load(Ordering::SeqCst);
+    let generation = {
+        let cached = cache();
+        match &cached.tree {
+            Some(tree) if listening => return Ok(tree.clone()),
+            _ => cached.generation,
+        }
+    };
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let tree = db::locations::build_tree(db::locations::active_routes(&client).await?);
+    if listening {
+        let mut cached = cache();
+        if cached.generation == generation {
+            cached.tree = Some(tree.clone());
+        }
+    }
+    Ok(tree)
+}
+
+/// The routes changed on some terminal
+pub fn invalidate() {
+    let mut cached = cache();
+    cached.tree = None;
+    cached.generation += 1;
+}
+
+/// Called by the cache listener as its connection comes and goes
+pub fn set_listening(on: bool) {
+    invalidate();
+    LISTENING.store(on, Ordering::SeqCst);
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+// What a maintenance command changes, row by row, so a supervisor can review it before applying.
+// With dry_run the command runs in full inside its transaction and `finish` rolls it back: the
+// diff is exactly what applying would write, not a separate estimate that could drift from it.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChangeAction {
+    Insert,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RowChange {
+    pub table: String,
+    pub id: String,
+    pub label: String, // what the cashier knows the row by, usually a plate
+    pub action: ChangeAction,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+impl RowChange {
+    pub fn new(table: &str, id: &str, label: &str, action: ChangeAction, before: serde_json::Value, after: serde_json::Value) -> Self {
+        RowChange { table: table.to_string(), id: id.to_string(), label: label.to_string(), action, before, after }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceDiff {
+    pub dry_run: bool,
+    pub changes: Vec<RowChange>,
+}
+
+impl MaintenanceDiff {
+    /// Commit the transaction the changes were made in, or roll it back on a dry run
+    pub async fn finish(self, tx: deadpool_postgres::Transaction<'_>) -> Result<Self, String> {
+        if self.dry_run {
+            tx.rollback().await.map_err(|e| e.to_string())?;
+        } else {
+            tx.commit().await.map_err(|e| e.to_string())?;
+        }
+        Ok(self)
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::realtime::RealtimeService;
+use crate::websocket_realtime::WebSocketRealtimeServer;
+use crate::DB_POOL;
+
+// Prometheus exporter for fleet monitoring. Off unless METRICS_PORT is set; it then serves
+// GET /metrics on 127.0.0.1 only, for the monitoring agent running on the station PC.
+
+static BOOKINGS_TOTAL: AtomicU64 = AtomicU64::new(0);
+static TICKETS_PRINTED_TOTAL: AtomicU64 = AtomicU64::new(0);
+static PRINT_FAILURES_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+pub fn record_bookings(count: usize) {
+    BOOKINGS_TOTAL.fetch_add(count as u64, Ordering::Relaxed);
+}
+
+pub fn record_ticket_printed() {
+    TICKETS_PRINTED_TOTAL.fetch_add(1, Ordering::Relaxed);
+}
+
+// A print job given up on after its retries
+pub fn record_print_failure() {
+    PRINT_FAILURES_TOTAL.fetch_add(1, Ordering::Relaxed);
+}
+
+pub struct Snapshot {
+    pub bookings_total: u64,
+    pub tickets_printed_total: u64,
+    pub print_failures_total: u64,
+    pub db_pool_in_use: usize,
+    pub db_pool_max: usize,
+    pub realtime_db_connected: bool,
+    pub realtime_websocket_running: bool,
+    pub websocket_clients: usize,
+}
+
+async fn snapshot() -> Snapshot {
+    let pool = DB_POOL.status();
+    let websocket = WebSocketRealtimeServer::get_instance();
+    let websocket_clients = websocket.clients.read().await.len();
+    Snapshot {
+        bookings_total: BOOKINGS_TOTAL.load(Ordering::Relaxed),
+        tickets_printed_total: TICKETS_PRINTED_TOTAL.load(Ordering::Relaxed),
+        print_failures_total: PRINT_FAILURES_TOTAL.load(Ordering::Relaxed),
+        db_pool_in_use: pool.size.saturating_sub(pool.available),
+        db_pool_max: pool.max_size,
+        realtime_db_connected: RealtimeService::get_instance().is_listening(),
+        realtime_websocket_running: websocket.is_running.load(Ordering::Relaxed),
+        websocket_clients,
+    }
+}
+
+/// Prometheus text exposition format
+pub fn render(snapshot: &Snapshot) -> String {
+    let mut out = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, u64)]| {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let _ = writeln!(out, "{}{} {}", name, labels, value);
+        }
+    };
+    metric("bookings_total", "counter", "Bookings created since start", &[("", snapshot.bookings_total)]);
+    metric("tickets_printed_total", "counter", "Print jobs sent to the printer since start", &[("", snapshot.tickets_printed_total)]);
+    metric("print_failures_total", "counter", "Print jobs abandoned after retrying", &[("", snapshot.print_failures_total)]);
+    metric("db_pool_in_use", "gauge", "Database connections checked out", &[("", snapshot.db_pool_in_use as u64)]);
+    metric("db_pool_max", "gauge", "Database pool size limit", &[("", snapshot.db_pool_max as u64)]);
+    metric(
+        "realtime_connected",
+        "gauge",
+        "1 when the realtime channel is up",
+        &[
+            ("{channel=\"database\"}", snapshot.realtime_db_connected as u64),
+            ("{channel=\"websocket\"}", snapshot.realtime_websocket_running as u64),
+        ],
+    );
+    metric("websocket_clients", "gauge", "Terminals connected to this station's websocket server", &[("", snapshot.websocket_clients as u64)]);
+    out
+}
+
+async fn respond(mut stream: tokio::net::TcpStream) -> std::io::Result<()> {
+    let mut request = [0u8; 1024];
+    let read = stream.read(&mut request).await?;
+    let request_line = String::from_utf8_lossy(&request[..read]);
+    let response = if request_line.starts_with("GET /metrics ") || request_line.starts_with("GET / ") {
+        let body = render(&snapshot().await);
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Serve /metrics on 127.0.0.1:METRICS_PORT; does nothing when the setting is absent
+pub async fn run_exporter() {
+    let port = match std::env::var("METRICS_PORT").ok().and_then(|v| v.trim().parse::<u16>().ok()) {
+        Some(port) => port,
+        None => return,
+    };
+    let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("❌ [METRICS] Cannot listen on 127.0.0.1:{}: {}", port, e);
+            return;
+        }
+    };
+    println!("📈 [METRICS] Serving http://127.0.0.1:{}/metrics", port);
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = respond(stream).await {
+                        println!("⚠️ [METRICS] {}", e);
+                    }
+                });
+            }
+            Err(e) => println!("⚠️ [METRICS] Accept failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_prometheus_text() {
+        let text = render(&Snapshot {
+            bookings_total: 42,
+            tickets_printed_total: 40,
+            print_failures_total: 2,
+            db_pool_in_use: 3,
+            db_pool_max: 16,
+            realtime_db_connected: true,
+            realtime_websocket_running: false,
+            websocket_clients: 0,
+        });
+        assert!(text.contains("# TYPE bookings_total counter\nbookings_total 42\n"));
+        assert!(text.contains("db_pool_in_use 3\n"));
+        assert!(text.contains("realtime_connected{channel=\"database\"} 1\n"));
+        assert!(text.contains("realtime_connected{channel=\"websocket\"} 0\n"));
+    }
+}
@@ -0,0 +1,375 @@
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{Invoke, Runtime};
+
+use crate::audit;
+use crate::auth;
+use crate::business_hours;
+use crate::i18n;
+use crate::kiosk;
+use crate::training;
+use crate::DB_POOL;
+
+// Runs before every Tauri command: per-window allow-lists, argument validation, business hours,
+// the commands training mode refuses, per-command rate limits and an audit entry for every
+// command but pure lookups. Rejected calls never reach the command; the commands keep their
+// own checks all the same.
+
+// Window allowed to call every command
+const MAIN_WINDOW: &str = "main";
+
+// Commands a secondary window may call. A label ending in '*' matches by prefix; a window
+// missing from this table may not call anything.
+struct WindowCommands {
+    label: &'static str,
+    commands: &'static [&'static str],
+}
+
+// Vehicle details opened from the queue (see open_vehicle_window): lookups only
+const VEHICLE_WINDOW_COMMANDS: &[&str] = &[
+    "db_get_vehicle_profile",
+    "db_get_vehicle_queue_status",
+    "db_get_vehicle_authorized_destinations",
+    "db_get_vehicle_activity_72h",
+    "db_get_vehicle_daily_report",
+    "db_has_recently_purchased_day_pass",
+];
+
+// Departure board on a hall display: queue state only
+const BOARD_WINDOW_COMMANDS: &[&str] = &[
+    "get_app_name",
+    "get_app_version",
+    "db_get_queue_summaries",
+    "db_get_queue_by_destination",
+    "db_get_destination_suspensions",
+    "db_get_available_booking_destinations",
+    "get_websocket_realtime_status",
+];
+
+const WINDOW_COMMANDS: &[WindowCommands] = &[
+    WindowCommands { label: kiosk::KIOSK_WINDOW, commands: kiosk::KIOSK_COMMANDS },
+    WindowCommands { label: "vehicle-*", commands: VEHICLE_WINDOW_COMMANDS },
+    WindowCommands { label: "board", commands: BOARD_WINDOW_COMMANDS },
+];
+
+struct RateLimit {
+    command: &'static str,
+    max_calls: usize,
+    window: Duration,
+}
+
+const RATE_LIMITS: &[RateLimit] = &[
+    RateLimit { command: "db_create_queue_booking", max_calls: 3, window: Duration::from_secs(1) },
+    RateLimit { command: "db_create_vehicle_specific_booking", max_calls: 3, window: Duration::from_secs(1) },
+    RateLimit { command: "db_cancel_seat_from_destination", max_calls: 3, window: Duration::from_secs(1) },
+    RateLimit { command: "db_cancel_queue_booking", max_calls: 3, window: Duration::from_secs(1) },
+    RateLimit { command: "print_booking_ticket", max_calls: 3, window: Duration::from_secs(1) },
+    RateLimit { command: "kiosk_print_tickets", max_calls: 3, window: Duration::from_secs(1) },
+    RateLimit { command: "db_purchase_day_pass", max_calls: 3, window: Duration::from_secs(1) },
+    RateLimit { command: "db_enter_queue", max_calls: 5, window: Duration::from_secs(1) },
+    RateLimit { command: "db_quick_enter_queue", max_calls: 5, window: Duration::from_secs(1) },
+    RateLimit { command: "auth_switch_user", max_calls: 5, window: Duration::from_secs(10) },
+    RateLimit { command: "auth_set_pin", max_calls: 3, window: Duration::from_secs(10) },
+    RateLimit { command: "kiosk_self_check_in", max_calls: 3, window: Duration::from_secs(10) },
+    RateLimit { command: "print_diagnostic_page", max_calls: 1, window: Duration::from_secs(5) },
+    RateLimit { command: "send_terminal_message", max_calls: 3, window: Duration::from_secs(5) },
+    RateLimit { command: "db_request_approval", max_calls: 3, window: Duration::from_secs(5) },
+    RateLimit { command: "send_raw_escpos", max_calls: 2, window: Duration::from_secs(5) },
+    RateLimit { command: "run_escpos_macro", max_calls: 2, window: Duration::from_secs(5) },
+    RateLimit { command: "db_send_escrowed_ticket_sms", max_calls: 3, window: Duration::from_secs(5) },
+];
+
+// Every call is written to the audit log with its (redacted) arguments, except lookups that
+// change nothing: these prefixes and commands. A new command is audited unless listed here.
+const UNAUDITED_PREFIXES: &[&str] = &[
+    "get_",
+    "list_",
+    "check_",
+    "db_get_",
+    "db_has_",
+    "db_list_",
+    "db_search_",
+    "db_export_",
+    "db_preview_",
+    "db_suggest_",
+];
+
+const UNAUDITED_COMMANDS: &[&str] = &[
+    "greet",
+    "db_health",
+    "db_global_search",
+    "db_replay_queue_state",
+    "auth_get_active_staff",
+    "render_ticket_preview",
+    "debug_printer_status",
+    "test_printer_connection",
+    "test_printer_connection_by_id",
+    "test_printer_connection_manual",
+    "test_direct_tcp_connection",
+    // Forwarded to the local node, which keeps its own log
+    "proxy_localnode",
+];
+
+// Largest seat count a single booking call may ask for
+const MAX_SEATS_PER_CALL: i64 = 100;
+
+// Arguments never copied into the audit log: any key containing one of these, whatever its case
+// (pin, currentPin, supervisorPin, sessionToken...)
+const REDACTED_ARGS: &[&str] = &["pin", "password", "token", "secret"];
+
+// Keys that carry the acting staff member, in order of preference
+const ACTOR_ARGS: &[&str] = &["createdBy", "staffId", "updatedBy"];
+
+static CALL_HISTORY: Lazy<Mutex<HashMap<&'static str, VecDeque<Instant>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Wrap the generated command handler so every invoke goes through `before_command`
+pub fn wrap<R, H>(handler: H) -> impl Fn(Invoke<R>) + Send + Sync + 'static
+where
+    R: Runtime,
+    H: Fn(Invoke<R>) + Send + Sync + 'static,
+{
+    move |invoke: Invoke<R>| {
+        let command = invoke.message.command().to_string();
+        let window_label = invoke.message.window().label().to_string();
+        let checked = check_window(&window_label, &command, invoke.message.payload())
+            .and_then(|()| before_command(&command, invoke.message.payload()));
+        if let Err(e) = checked {
+            println!("⛔ [MIDDLEWARE] {} rejected: {}", command, e);
+            invoke.resolver.reject(e);
+            return;
+        }
+        handler(invoke)
+    }
+}
+
+fn check_window(window_label: &str, command: &str, payload: &serde_json::Value) -> Result<(), String> {
+    if window_label == MAIN_WINDOW {
+        return Ok(());
+    }
+    let allowed = WINDOW_COMMANDS
+        .iter()
+        .find(|window| match window.label.strip_suffix('*') {
+            Some(prefix) => window_label.starts_with(prefix),
+            None => window_label == window.label,
+        })
+        .is_some_and(|window| window.commands.contains(&command));
+    if !allowed {
+        return Err(i18n::t("error.window_command_denied"));
+    }
+    if window_label == kiosk::KIOSK_WINDOW {
+        kiosk::check_booking(command, payload)?;
+    }
+    Ok(())
+}
+
+fn before_command(command: &str, payload: &serde_json::Value) -> Result<(), String> {
+    validate_args(payload)?;
+    business_hours::check(command)?;
+    training::check(command)?;
+    check_rate_limit(command)?;
+    if is_audited(command) {
+        spawn_audit_entry(command, payload);
+    }
+    Ok(())
+}
+
+fn is_audited(command: &str) -> bool {
+    !UNAUDITED_COMMANDS.contains(&command) && !UNAUDITED_PREFIXES.iter().any(|prefix| command.starts_with(prefix))
+}
+
+// Validation hooks keyed by argument name, shared by every command taking that argument
+fn validate_args(payload: &serde_json::Value) -> Result<(), String> {
+    let args = match payload.as_object() {
+        Some(args) => args,
+        None => return Ok(()),
+    };
+    for (key, value) in args {
+        match key.as_str() {
+            "seatsRequested" => {
+                let seats = value.as_i64().unwrap_or(0);
+                if !(1..=MAX_SEATS_PER_CALL).contains(&seats) {
+                    return Err(i18n::tf("error.seats_out_of_range", &[("max", &MAX_SEATS_PER_CALL)]));
+                }
+            }
+            "licensePlate" => {
+                if let Some(plate) = value.as_str() {
+                    validate_plate(plate)?;
+                }
+            }
+            "licensePlates" => {
+                for plate in value.as_array().into_iter().flatten().filter_map(|v| v.as_str()) {
+                    validate_plate(plate)?;
+                }
+            }
+            "price" | "creditLimit" => {
+                if let Some(price) = value.as_f64() {
+                    if !price.is_finite() || price < 0.0 {
+                        return Err(i18n::t("error.price_invalid"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn validate_plate(plate: &str) -> Result<(), String> {
+    let plate = plate.trim();
+    if plate.is_empty() {
+        return Err("La plaque d'immatriculation est obligatoire".to_string());
+    }
+    if plate.chars().count() > 20 {
+        return Err(format!("Plaque d'immatriculation invalide: {}", plate));
+    }
+    Ok(())
+}
+
+fn check_rate_limit(command: &str) -> Result<(), String> {
+    let limit = match RATE_LIMITS.iter().find(|limit| limit.command == command) {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let now = Instant::now();
+    let mut history = CALL_HISTORY.lock().map_err(|e| e.to_string())?;
+    let calls = history.entry(limit.command).or_default();
+    while calls.front().is_some_and(|t| now.duration_since(*t) >= limit.window) {
+        calls.pop_front();
+    }
+    if calls.len() >= limit.max_calls {
+        return Err(i18n::t("error.rate_limited"));
+    }
+    calls.push_back(now);
+    Ok(())
+}
+
+// Nested too: a supervisor override carries the supervisor's PIN
+fn redact(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(args) => {
+            for (key, arg) in args.iter_mut() {
+                if is_redacted(key) {
+                    *arg = serde_json::Value::String("***".to_string());
+                } else {
+                    redact(arg);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn is_redacted(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    REDACTED_ARGS.iter().any(|word| key.contains(word))
+}
+
+fn spawn_audit_entry(command: &str, payload: &serde_json::Value) {
+    let mut details = payload.clone();
+    redact(&mut details);
+    let actor = ACTOR_ARGS
+        .iter()
+        .find_map(|key| payload.get(*key).and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+        .or_else(auth::active_staff_id);
+    let event_type = format!("COMMAND:{}", command);
+
+    tauri::async_runtime::spawn(async move {
+        let client = match DB_POOL.get().await {
+            Ok(client) => client,
+            Err(e) => {
+                println!("⚠️ [MIDDLEWARE] Audit skipped for {}: {}", event_type, e);
+                return;
+            }
+        };
+        if let Err(e) = audit::record(&client, &event_type, actor.as_deref(), details).await {
+            println!("⚠️ [MIDDLEWARE] {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(validate_args(&serde_json::json!({"destinationId": "d1", "seatsRequested": 2})).is_ok());
+        assert!(validate_args(&serde_json::json!({"seatsRequested": 0})).is_err());
+        assert!(validate_args(&serde_json::json!({"licensePlate": "  "})).is_err());
+        assert!(validate_args(&serde_json::json!({"licensePlates": ["123 TU 4567", ""]})).is_err());
+        assert!(validate_args(&serde_json::json!({"price": -1.0})).is_err());
+        assert!(validate_args(&serde_json::json!(null)).is_ok());
+    }
+
+    #[test]
+    fn seat_counts_outside_the_range_say_so() {
+        let error = validate_args(&serde_json::json!({"seatsRequested": 101})).unwrap_err();
+        assert_eq!(error, i18n::tf("error.seats_out_of_range", &[("max", &MAX_SEATS_PER_CALL)]));
+        assert!(validate_args(&serde_json::json!({"seatsRequested": 100})).is_ok());
+    }
+
+    #[test]
+    fn every_command_but_lookups_is_audited() {
+        assert!(is_audited("db_create_queue_booking"));
+        assert!(is_audited("db_update_vehicle_capacity"));
+        assert!(is_audited("print_booking_ticket"));
+        assert!(!is_audited("db_get_queue_summaries"));
+        assert!(!is_audited("db_health"));
+        assert!(!is_audited("proxy_localnode"));
+    }
+
+    #[test]
+    fn audit_details_hide_pins_at_any_depth() {
+        let mut details = serde_json::json!({
+            "licensePlate": "123 TU 4567",
+            "supervisorOverride": {"pin": "1234", "reasonCode": "OTHER", "note": "Panne"}
+        });
+        redact(&mut details);
+        assert_eq!(details["supervisorOverride"]["pin"], "***");
+        assert_eq!(details["supervisorOverride"]["note"], "Panne");
+        assert_eq!(details["licensePlate"], "123 TU 4567");
+    }
+
+    #[test]
+    fn audit_details_hide_every_pin_of_a_pin_change() {
+        // auth_set_pin's arguments as the frontend sends them
+        let mut details = serde_json::json!({
+            "staffId": "s1",
+            "pin": "1111",
+            "currentPin": "2222",
+            "supervisorPin": "3333"
+        });
+        redact(&mut details);
+        assert_eq!(details, serde_json::json!({"staffId": "s1", "pin": "***", "currentPin": "***", "supervisorPin": "***"}));
+
+        let mut details = serde_json::json!({"sessionToken": "abc", "approvals": [{"PIN": "4444"}]});
+        redact(&mut details);
+        assert_eq!(details, serde_json::json!({"sessionToken": "***", "approvals": [{"PIN": "***"}]}));
+    }
+
+    #[test]
+    fn rate_limit_applies_per_command() {
+        assert!(check_rate_limit("print_diagnostic_page").is_ok());
+        assert!(check_rate_limit("print_diagnostic_page").is_err());
+        assert!(check_rate_limit("db_health").is_ok());
+        assert!(check_rate_limit("db_health").is_ok());
+    }
+
+    #[test]
+    fn windows_only_call_their_own_commands() {
+        let none = serde_json::json!({});
+        assert!(check_window("main", "db_cancel_queue_booking", &none).is_ok());
+        assert!(check_window("vehicle-123 TU 4567", "db_get_vehicle_activity_72h", &none).is_ok());
+        assert!(check_window("vehicle-123 TU 4567", "db_cancel_queue_booking", &none).is_err());
+        assert!(check_window("board", "db_get_queue_summaries", &none).is_ok());
+        assert!(check_window("board", "db_set_pass_price", &none).is_err());
+        assert!(check_window("kiosk", "db_cancel_queue_booking", &none).is_err());
+        assert!(check_window("kiosk", "db_create_queue_booking", &serde_json::json!({"paymentMethod": "CASH"})).is_err());
+        assert!(check_window("unknown", "get_app_name", &none).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use deadpool_postgres::Pool;
+
+// Schema changes applied at startup, in order, each exactly once per database.
+// The SQL lives in scripts/migrations so it can also be run by hand with psql.
+const MIGRATIONS: &[(&str, &str)] = &[
+    (
+        "001_day_pass_unique_per_day",
+        include_str!("../../scripts/migrations/001_day_pass_unique_per_day.sql"),
+    ),
+    (
+        "002_pass_types",
+        include_str!("../../scripts/migrations/002_pass_types.sql"),
+    ),
+    (
+        "003_system_staff",
+        include_str!("../../scripts/migrations/003_system_staff.sql"),
+    ),
+    (
+        "004_staff_pins_audit_log",
+        include_str!("../../scripts/migrations/004_staff_pins_audit_log.sql"),
+    ),
+    (
+        "005_booking_requests",
+        include_str!("../../scripts/migrations/005_booking_requests.sql"),
+    ),
+    (
+        "006_payment_methods",
+        include_str!("../../scripts/migrations/006_payment_methods.sql"),
+    ),
+    (
+        "007_card_authorizations",
+        include_str!("../../scripts/migrations/007_card_authorizations.sql"),
+    ),
+    (
+        "008_customers",
+        include_str!("../../scripts/migrations/008_customers.sql"),
+    ),
+    (
+        "009_standing_reservations",
+        include_str!("../../scripts/migrations/009_standing_reservations.sql"),
+    ),
+    (
+        "010_waitlist",
+        include_str!("../../scripts/migrations/010_waitlist.sql"),
+    ),
+    (
+        "011_station_settings",
+        include_str!("../../scripts/migrations/011_station_settings.sql"),
+    ),
+    (
+        "012_pricing_rules",
+        include_str!("../../scripts/migrations/012_pricing_rules.sql"),
+    ),
+    (
+        "013_exit_pass_sales",
+        include_str!("../../scripts/migrations/013_exit_pass_sales.sql"),
+    ),
+    (
+        "014_driver_settlements",
+        include_str!("../../scripts/migrations/014_driver_settlements.sql"),
+    ),
+    (
+        "015_expenses",
+        include_str!("../../scripts/migrations/015_expenses.sql"),
+    ),
+    (
+        "016_cash_counts",
+        include_str!("../../scripts/migrations/016_cash_counts.sql"),
+    ),
+    (
+        "017_supervisor_overrides",
+        include_str!("../../scripts/migrations/017_supervisor_overrides.sql"),
+    ),
+    (
+        "018_terminal_identity",
+        include_str!("../../scripts/migrations/018_terminal_identity.sql"),
+    ),
+    (
+        "019_day_pass_cache_notify",
+        include_str!("../../scripts/migrations/019_day_pass_cache_notify.sql"),
+    ),
+    (
+        "020_route_changes_notify",
+        include_str!("../../scripts/migrations/020_route_changes_notify.sql"),
+    ),
+    (
+        "021_cancellation_fees",
+        include_str!("../../scripts/migrations/021_cancellation_fees.sql"),
+    ),
+    (
+        "022_queue_closings",
+        include_str!("../../scripts/migrations/022_queue_closings.sql"),
+    ),
+    (
+        "023_queue_events",
+        include_str!("../../scripts/migrations/023_queue_events.sql"),
+    ),
+    (
+        "024_queue_versions",
+        include_str!("../../scripts/migrations/024_queue_versions.sql"),
+    ),
+    (
+        "025_terminal_messages",
+        include_str!("../../scripts/migrations/025_terminal_messages.sql"),
+    ),
+    (
+        "026_destination_suspensions",
+        include_str!("../../scripts/migrations/026_destination_suspensions.sql"),
+    ),
+    (
+        "027_loading_since",
+        include_str!("../../scripts/migrations/027_loading_since.sql"),
+    ),
+    (
+        "028_escpos_macros",
+        include_str!("../../scripts/migrations/028_escpos_macros.sql"),
+    ),
+    (
+        "029_staff_names_notify",
+        include_str!("../../scripts/migrations/029_staff_names_notify.sql"),
+    ),
+    (
+        "030_calendar_annotations",
+        include_str!("../../scripts/migrations/030_calendar_annotations.sql"),
+    ),
+    (
+        "031_vehicle_positions",
+        include_str!("../../scripts/migrations/031_vehicle_positions.sql"),
+    ),
+    (
+        "032_booking_no_shows",
+        include_str!("../../scripts/migrations/032_booking_no_shows.sql"),
+    ),
+    (
+        "033_ticket_reprints",
+        include_str!("../../scripts/migrations/033_ticket_reprints.sql"),
+    ),
+    (
+        "034_voided_ticket_numbers",
+        include_str!("../../scripts/migrations/034_voided_ticket_numbers.sql"),
+    ),
+    (
+        "035_printed_tickets",
+        include_str!("../../scripts/migrations/035_printed_tickets.sql"),
+    ),
+    (
+        "036_driver_pins",
+        include_str!("../../scripts/migrations/036_driver_pins.sql"),
+    ),
+    (
+        "037_approval_requests",
+        include_str!("../../scripts/migrations/037_approval_requests.sql"),
+    ),
+    (
+        "038_queue_seat_changes_notify",
+        include_str!("../../scripts/migrations/038_queue_seat_changes_notify.sql"),
+    ),
+    (
+        "039_route_aliases",
+        include_str!("../../scripts/migrations/039_route_aliases.sql"),
+    ),
+    (
+        "040_ticket_escrow",
+        include_str!("../../scripts/migrations/040_ticket_escrow.sql"),
+    ),
+    (
+        "041_refunds",
+        include_str!("../../scripts/migrations/041_refunds.sql"),
+    ),
+    (
+        "042_settlements_outlive_exit_passes",
+        include_str!("../../scripts/migrations/042_settlements_outlive_exit_passes.sql"),
+    ),
+];
+
+// Serializes terminals starting at the same time against the same database
+const MIGRATION_LOCK_KEY: i64 = 0x4E514C49; // "NQLI"
+
+pub async fn run_migrations(pool: &Pool) -> Result<usize, String> {
+    let mut client = pool.get().await.map_err(|e| e.to_string())?;
+
+    client.batch_execute(
+        "CREATE TABLE IF NOT EXISTS app_schema_migrations (
+            id TEXT PRIMARY KEY,
+            applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        )"
+    ).await.map_err(|e| e.to_string())?;
+
+    let mut applied = 0;
+    for (id, sql) in MIGRATIONS {
+        let tx = client.transaction().await.map_err(|e| e.to_string())?;
+        tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK_KEY]).await.map_err(|e| e.to_string())?;
+
+        let done = tx.query_opt("SELECT 1 FROM app_schema_migrations WHERE id = $1", &[id])
+            .await.map_err(|e| e.to_string())?;
+        if done.is_some() {
+            tx.rollback().await.map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        println!("🗄️ [MIGRATION] Applying {}", id);
+        tx.batch_execute(sql).await.map_err(|e| format!("Migration {} failed: {}", id, e))?;
+        tx.execute("INSERT INTO app_schema_migrations (id) VALUES ($1)", &[id]).await.map_err(|e| e.to_string())?;
+        tx.commit().await.map_err(|e| e.to_string())?;
+        applied += 1;
+    }
+
+    Ok(applied)
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::fs;
+use std::path::PathBuf;
+use once_cell::sync::Lazy;
+
+use crate::printer::PrinterConfig;
+use crate::encoding;
+
+// Global simulated printer, used when PRINTER_MODE=simulate
+static MOCK_TRANSPORT: Lazy<Arc<MockTransport>> = Lazy::new(|| {
+    Arc::new(MockTransport::new(Some(MockTransport::default_preview_dir())))
+});
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SimulatedPrintJob {
+    pub id: String,
+    pub printer_id: String,
+    pub bytes: Vec<u8>,
+    pub text: String,
+    pub preview_path: Option<String>,
+    pub created_at: String,
+}
+
+/// One rendered line of a ticket, with the formatting active when it ended
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PreviewLine {
+    pub text: String,
+    pub align: u8, // 0 left, 1 center, 2 right
+    pub bold: bool,
+}
+
+/// Printer transport that never touches hardware: it decodes the ESC/POS
+/// stream into a text/HTML preview and keeps every job in memory.
+pub struct MockTransport {
+    jobs: Mutex<Vec<SimulatedPrintJob>>,
+    preview_dir: Option<PathBuf>,
+}
+
+impl MockTransport {
+    pub fn new(preview_dir: Option<PathBuf>) -> Self {
+        Self {
+            jobs: Mutex::new(Vec::new()),
+            preview_dir,
+        }
+    }
+
+    pub fn get_instance() -> Arc<Self> {
+        MOCK_TRANSPORT.clone()
+    }
+
+    fn default_preview_dir() -> PathBuf {
+        if let Ok(exe_path) = std::env::current_exe() {
+            if let Some(exe_dir) = exe_path.parent() {
+                return exe_dir.join("print_previews");
+            }
+        }
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join("print_previews")
+    }
+
+    /// Record a job and write its preview files instead of printing.
+    /// `bytes` is the UTF-8 job; the recorded bytes are encoded as the printer would receive them.
+    pub fn send(&self, config: &PrinterConfig, bytes: &[u8]) -> Result<String, String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let lines = Self::decode(bytes);
+        let text = Self::render_text(&lines, config.width as usize);
+
+        let preview_path = match &self.preview_dir {
+            Some(dir) => {
+                fs::create_dir_all(dir).map_err(|e| format!("Failed to create preview directory: {}", e))?;
+                let base = dir.join(format!("{}_{}", chrono::Local::now().format("%Y%m%d_%H%M%S"), &id[..8]));
+                let txt_path = base.with_extension("txt");
+                fs::write(&txt_path, &text).map_err(|e| format!("Failed to write text preview: {}", e))?;
+                fs::write(base.with_extension("html"), Self::render_html(&lines, config.width as usize))
+                    .map_err(|e| format!("Failed to write HTML preview: {}", e))?;
+                Some(txt_path.to_string_lossy().to_string())
+            }
+            None => None,
+        };
+
+        println!("🧪 [SIMULATE] Job {} ({} bytes) for printer {} -> {:?}", id, bytes.len(), config.id, preview_path);
+
+        let job = SimulatedPrintJob {
+            id: id.clone(),
+            printer_id: config.id.clone(),
+            bytes: encoding::encode_job(bytes, config.output_code_page()),
+            text,
+            preview_path,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+        self.jobs.lock().map_err(|e| e.to_string())?.push(job);
+
+        Ok(format!("Print job {} simulated successfully", id))
+    }
+
+    pub fn jobs(&self) -> Result<Vec<SimulatedPrintJob>, String> {
+        Ok(self.jobs.lock().map_err(|e| e.to_string())?.clone())
+    }
+
+    pub fn clear(&self) -> Result<(), String> {
+        self.jobs.lock().map_err(|e| e.to_string())?.clear();
+        Ok(())
+    }
+
+    /// Decode the subset of ESC/POS emitted by the ticket builders into lines
+    pub fn decode(bytes: &[u8]) -> Vec<PreviewLine> {
+        let mut lines = Vec::new();
+        let mut current: Vec<u8> = Vec::new();
+        let mut align = 0u8;
+        let mut bold = false;
+        let mut i = 0;
+
+        let flush = |current: &mut Vec<u8>, lines: &mut Vec<PreviewLine>, align: u8, bold: bool| {
+            lines.push(PreviewLine {
+                text: String::from_utf8_lossy(current).to_string(),
+                align,
+                bold,
+            });
+            current.clear();
+        };
+
+        while i < bytes.len() {
+            let b = bytes[i];
+            let arg = bytes.get(i + 2).copied().unwrap_or(0);
+            match (b, bytes.get(i + 1).copied()) {
+                (0x1B, Some(0x40)) => {
+                    align = 0;
+                    bold = false;
+                    i += 2;
+                }
+                (0x1B, Some(0x61)) => {
+                    align = arg.min(2);
+                    i += 3;
+                }
+                (0x1B, Some(0x45)) => {
+                    bold = arg & 0x01 == 1;
+                    i += 3;
+                }
+                (0x1B, Some(0x2D)) | (0x1B, Some(0x4D)) | (0x1B, Some(0x74)) | (0x1D, Some(0x21)) | (0x10, Some(0x04)) => {
+                    i += 3;
+                }
+                (0x1B, Some(0x70)) => {
+                    i += 5;
+                }
+                (0x1D, Some(0x56)) => {
+                    if !current.is_empty() {
+                        flush(&mut current, &mut lines, align, bold);
+                    }
+                    lines.push(PreviewLine { text: "--- coupe ---".to_string(), align: 1, bold: false });
+                    i += 3;
+                }
+                (b'\n', _) => {
+                    flush(&mut current, &mut lines, align, bold);
+                    i += 1;
+                }
+                _ => {
+                    current.push(b);
+                    i += 1;
+                }
+            }
+        }
+        if !current.is_empty() {
+            flush(&mut current, &mut lines, align, bold);
+        }
+        lines
+    }
+
+    /// Monospace rendering padded to the paper width
+    pub fn render_text(lines: &[PreviewLine], width: usize) -> String {
+        let mut out = String::new();
+        for line in lines {
+            let len = line.text.chars().count();
+            let pad = width.saturating_sub(len);
+            let left = match line.align {
+                1 => pad / 2,
+                2 => pad,
+                _ => 0,
+            };
+            out.push_str(&" ".repeat(left));
+            out.push_str(&line.text);
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn render_html(lines: &[PreviewLine], width: usize) -> String {
+        let mut body = String::new();
+        for line in lines {
+            let align = match line.align {
+                1 => "center",
+                2 => "right",
+                _ => "left",
+            };
+            let text = line.text
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            let text = if text.is_empty() { "&nbsp;".to_string() } else { text };
+            let weight = if line.bold { "bold" } else { "normal" };
+            body.push_str(&format!(
+                "<div style=\"text-align:{};font-weight:{}\">{}</div>\n",
+                align, weight, text
+            ));
+        }
+        format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Aperçu ticket</title></head>\n<body><pre style=\"font-family:monospace;width:{}ch;white-space:pre;border:1px dashed #999;padding:8px\">\n{}</pre></body></html>\n",
+            width, body
+        )
+    }
+}
+
+// Tauri commands for inspecting simulated jobs
+#[tauri::command]
+pub async fn get_simulated_print_jobs() -> Result<Vec<SimulatedPrintJob>, String> {
+    MockTransport::get_instance().jobs()
+}
+
+#[tauri::command]
+pub async fn clear_simulated_print_jobs() -> Result<(), String> {
+    MockTransport::get_instance().clear()
+}
@@ -0,0 +1,195 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use tokio_postgres::types::{FromSql, Type};
+
+// Amounts in Tunisian dinars, held as whole millimes (1 TND = 1000 millimes) so sums of fares
+// never drift the way f64 totals did (12.600000000000001). The rounding rules:
+// - an amount coming in as dinars (JSON number, DOUBLE PRECISION column) is rounded to the
+//   nearest millime, halves away from zero;
+// - seat totals, fees and sums are exact, no rounding happens in between;
+// - a share of an amount (portion) is rounded the same way, and callers take the rest as the
+//   difference so the pieces always add back up to the whole.
+// Columns stay DOUBLE PRECISION: rows are read through FromSql and written with to_tnd(),
+// which is the closest f64 to the millime value. JSON carries the same dinar number as before.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+/// Station fee added to the fare of every booked seat
+pub const SERVICE_FEE_PER_SEAT: Money = Money::from_millimes(200);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_millimes(millimes: i64) -> Self {
+        Money(millimes)
+    }
+
+    /// Dinars to the nearest millime, halves away from zero
+    pub fn from_tnd(tnd: f64) -> Self {
+        if !tnd.is_finite() {
+            return Money::ZERO;
+        }
+        Money((tnd * 1000.0).round() as i64)
+    }
+
+    pub fn millimes(self) -> i64 {
+        self.0
+    }
+
+    pub fn to_tnd(self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    /// `part / whole` of this amount to the nearest millime, halves away from zero
+    pub fn portion(self, part: i64, whole: i64) -> Money {
+        if whole == 0 {
+            return Money::ZERO;
+        }
+        let scaled = self.0 as i128 * part as i128;
+        let whole = whole as i128;
+        let rounded = (scaled.abs() * 2 + whole.abs()) / (whole.abs() * 2);
+        let negative = (scaled < 0) != (whole < 0);
+        Money(if negative { -rounded } else { rounded } as i64)
+    }
+}
+
+/// Three decimals, as printed on tickets and reports: 12.600
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let millimes = self.0.unsigned_abs();
+        let text = format!("{}{}.{:03}", sign, millimes / 1000, millimes % 1000);
+        f.pad(&text)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+// Price per seat times a seat count
+impl Mul<i32> for Money {
+    type Output = Money;
+    fn mul(self, count: i32) -> Money {
+        Money(self.0 * count as i64)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        Money(iter.map(|m| m.0).sum())
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        Money(iter.map(|m| m.0).sum())
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_tnd())
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer).map(Money::from_tnd)
+    }
+}
+
+impl<'a> FromSql<'a> for Money {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *ty {
+            Type::FLOAT4 => f32::from_sql(ty, raw).map(|v| Money::from_tnd(v as f64)),
+            _ => f64::from_sql(ty, raw).map(Money::from_tnd),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::FLOAT8 | Type::FLOAT4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_do_not_drift() {
+        // 3 × 4.000 + 3 × 0.200 was 12.600000000000001 in f64
+        let total = Money::from_tnd(4.0) * 3 + SERVICE_FEE_PER_SEAT * 3;
+        assert_eq!(total, Money::from_millimes(12_600));
+        assert_eq!(total.to_tnd(), 12.6);
+        let tenths: Money = (0..10).map(|_| Money::from_tnd(0.1)).sum();
+        assert_eq!(tenths, Money::from_tnd(1.0));
+    }
+
+    #[test]
+    fn dinars_round_to_the_nearest_millime() {
+        assert_eq!(Money::from_tnd(2.0004).millimes(), 2000);
+        assert_eq!(Money::from_tnd(2.0006).millimes(), 2001);
+        assert_eq!(Money::from_tnd(0.0125 * 100.0).millimes(), 1250);
+        assert_eq!(Money::from_tnd(-1.2346).millimes(), -1235);
+        assert_eq!(Money::from_tnd(f64::NAN), Money::ZERO);
+    }
+
+    #[test]
+    fn portions_add_back_up_to_the_whole() {
+        let total = Money::from_tnd(12.5);
+        let kept = total.portion(2, 3);
+        assert_eq!(kept.millimes(), 8333);
+        assert_eq!(kept + (total - kept), total);
+        // Halves go away from zero
+        assert_eq!(Money::from_millimes(5).portion(1, 2).millimes(), 3);
+        assert_eq!(Money::from_millimes(-5).portion(1, 2).millimes(), -3);
+        assert_eq!(total.portion(1, 0), Money::ZERO);
+    }
+
+    #[test]
+    fn prints_three_decimals_and_serializes_as_dinars() {
+        assert_eq!(Money::from_millimes(12_600).to_string(), "12.600");
+        assert_eq!(Money::from_millimes(-200).to_string(), "-0.200");
+        assert_eq!(format!("{:>8}", Money::from_millimes(2000)), "   2.000");
+        assert_eq!(serde_json::to_string(&Money::from_millimes(12_600)).unwrap(), "12.6");
+        assert_eq!(serde_json::from_str::<Money>("4.2").unwrap(), Money::from_millimes(4200));
+    }
+}
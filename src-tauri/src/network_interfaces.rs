@@ -0,0 +1,225 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::net::Ipv4Addr;
+use std::sync::RwLock;
+
+use crate::db;
+use crate::i18n;
+use crate::platform::{self, InterfaceAddress};
+use crate::DB_POOL;
+
+// Which network interfaces the station uses to find the local node and its printers. Cashier PCs
+// often carry virtual machine, container or VPN adapters whose addresses would send discovery to
+// the wrong network. The rules are the network_interfaces setting (JSON in station_settings),
+// cached here: name patterns to use or skip, and the station network preferred over any other.
+
+const POLICY_KEY: &str = "network_interfaces";
+const MAX_PATTERN_LEN: usize = 32;
+
+static RULES: Lazy<RwLock<InterfaceRules>> = Lazy::new(|| RwLock::new(InterfaceRules::default()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InterfaceRules {
+    /// Name patterns of the interfaces to use ("enp*"); empty uses every one not excluded
+    pub include: Vec<String>,
+    /// Name patterns never used, checked after `include`
+    pub exclude: Vec<String>,
+    /// First three octets of the station network ("192.168.192"), preferred over any other
+    pub station_network: String,
+}
+
+impl Default for InterfaceRules {
+    fn default() -> Self {
+        let exclude = ["docker*", "br-*", "veth*", "virbr*", "vboxnet*", "vmnet*", "*VirtualBox*", "*VMware*", "vEthernet*", "tun*", "tap*", "wg*", "utun*"];
+        InterfaceRules {
+            include: Vec::new(),
+            exclude: exclude.iter().map(|p| p.to_string()).collect(),
+            station_network: "192.168.192".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInterfaceDto {
+    pub name: String,
+    pub address: String,
+    pub prefix_len: Option<u8>,
+    pub loopback: bool,
+    /// Allowed by the rules and not loopback or link-local
+    pub used: bool,
+    pub station_network: bool,
+    /// The address the station is known by
+    pub selected: bool,
+}
+
+/// `*` stands for any run of characters; case is ignored, as Windows adapter names vary
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if parts.len() == 1 {
+        return name == first;
+    }
+    if !name.starts_with(first) || !name[first.len()..].ends_with(last) {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+fn station_octets(network: &str) -> Option<[u8; 3]> {
+    let octets: Vec<u8> = network.split('.').map(|o| o.parse().ok()).collect::<Option<_>>()?;
+    octets.try_into().ok()
+}
+
+impl InterfaceRules {
+    /// The stored setting, or the defaults when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    /// Checked rules, patterns trimmed and empty ones dropped
+    pub fn validate(&self) -> Result<Self, String> {
+        if station_octets(self.station_network.trim()).is_none() {
+            return Err(i18n::tf("error.station_network_invalid", &[("network", &self.station_network)]));
+        }
+        let clean = |patterns: &[String]| -> Result<Vec<String>, String> {
+            let patterns: Vec<String> = patterns.iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect();
+            match patterns.iter().find(|p| p.len() > MAX_PATTERN_LEN) {
+                Some(p) => Err(i18n::tf("error.interface_pattern_invalid", &[("pattern", p), ("max", &MAX_PATTERN_LEN)])),
+                None => Ok(patterns),
+            }
+        };
+        Ok(InterfaceRules {
+            include: clean(&self.include)?,
+            exclude: clean(&self.exclude)?,
+            station_network: self.station_network.trim().to_string(),
+        })
+    }
+
+    pub fn allows(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| matches(p, name))) && !self.exclude.iter().any(|p| matches(p, name))
+    }
+
+    pub fn on_station_network(&self, address: &Ipv4Addr) -> bool {
+        station_octets(&self.station_network).is_some_and(|octets| address.octets()[..3] == octets)
+    }
+
+    fn usable(&self, interface: &InterfaceAddress) -> bool {
+        let ip = &interface.address;
+        !ip.is_loopback() && !ip.is_link_local() && !ip.is_unspecified() && self.allows(&interface.name)
+    }
+
+    /// The address other machines reach this station on: one on the station network first, then
+    /// the one the default route leaves from, then any other used interface. The routed address
+    /// alone counts only when the interfaces could not be listed.
+    pub fn pick(&self, interfaces: &[InterfaceAddress], routed: Option<Ipv4Addr>) -> Option<Ipv4Addr> {
+        let used: Vec<Ipv4Addr> = interfaces.iter().filter(|i| self.usable(i)).map(|i| i.address).collect();
+        if interfaces.is_empty() {
+            return routed.filter(|ip| !ip.is_loopback() && !ip.is_unspecified());
+        }
+        used.iter()
+            .copied()
+            .find(|ip| self.on_station_network(ip))
+            .or(routed.filter(|ip| used.contains(ip)))
+            .or_else(|| used.first().copied())
+    }
+
+    pub fn classify(&self, interfaces: &[InterfaceAddress], routed: Option<Ipv4Addr>) -> Vec<NetworkInterfaceDto> {
+        let selected = self.pick(interfaces, routed);
+        interfaces
+            .iter()
+            .map(|i| NetworkInterfaceDto {
+                name: i.name.clone(),
+                address: i.address.to_string(),
+                prefix_len: i.prefix_len,
+                loopback: i.address.is_loopback(),
+                used: self.usable(i),
+                station_network: self.on_station_network(&i.address),
+                selected: selected == Some(i.address),
+            })
+            .collect()
+    }
+}
+
+pub fn rules() -> InterfaceRules {
+    RULES.read().map(|r| r.clone()).unwrap_or_default()
+}
+
+/// Every IPv4 interface of this PC, as the rules see it
+pub fn list() -> Vec<NetworkInterfaceDto> {
+    rules().classify(&platform::interfaces(), platform::routed_ipv4())
+}
+
+pub fn local_ipv4() -> Result<Ipv4Addr, String> {
+    rules().pick(&platform::interfaces(), platform::routed_ipv4()).ok_or_else(|| i18n::t("error.no_local_ip"))
+}
+
+fn cache(rules: &InterfaceRules) {
+    if let Ok(mut cached) = RULES.write() {
+        *cached = rules.clone();
+    }
+}
+
+/// Read the rules from the database into the cache
+pub async fn load() -> Result<InterfaceRules, String> {
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let rules = InterfaceRules::parse(db::settings::get(&client, POLICY_KEY).await?.as_deref());
+    cache(&rules);
+    Ok(rules)
+}
+
+pub async fn save(client: &impl db::GenericClient, rules: &InterfaceRules) -> Result<InterfaceRules, String> {
+    let rules = rules.validate()?;
+    db::settings::set(client, POLICY_KEY, &serde_json::to_string(&rules).map_err(|e| e.to_string())?).await?;
+    cache(&rules);
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interface(name: &str, address: [u8; 4]) -> InterfaceAddress {
+        InterfaceAddress { name: name.to_string(), address: Ipv4Addr::from(address), prefix_len: Some(24) }
+    }
+
+    #[test]
+    fn rules_pick_the_station_address_among_used_interfaces() {
+        let interfaces = vec![
+            interface("lo", [127, 0, 0, 1]),
+            interface("docker0", [192, 168, 192, 1]),
+            interface("wlp2s0", [10, 0, 0, 7]),
+            interface("enp4s0", [192, 168, 192, 100]),
+        ];
+        let rules = InterfaceRules::default();
+        let routed = Some(Ipv4Addr::new(10, 0, 0, 7));
+        assert_eq!(rules.pick(&interfaces, routed), Some(Ipv4Addr::new(192, 168, 192, 100)));
+        let listed = rules.classify(&interfaces, routed);
+        assert_eq!(listed.iter().map(|i| i.used).collect::<Vec<_>>(), vec![false, false, true, true]);
+        assert!(listed[3].selected && listed[3].station_network);
+
+        // Off the station network: the routed address, only when it is a used interface's
+        assert_eq!(rules.pick(&interfaces[..3], routed), routed);
+        assert_eq!(rules.pick(&interfaces[..3], Some(Ipv4Addr::new(172, 17, 0, 2))), routed);
+        let wired_only = InterfaceRules { include: vec!["en*".to_string()], ..Default::default() };
+        assert_eq!(wired_only.pick(&interfaces[..3], routed), None);
+        assert_eq!(rules.pick(&[], routed), routed);
+
+        assert!(matches("*VirtualBox*", "VirtualBox Host-Only Network"));
+        assert!(matches("br-*", "br-5f2c"));
+        assert!(!matches("tun*", "ethernet-tun"));
+        assert!(InterfaceRules { station_network: "192.168".to_string(), ..Default::default() }.validate().is_err());
+        let cleaned = InterfaceRules { include: vec![" enp* ".to_string(), "".to_string()], ..Default::default() }.validate().unwrap();
+        assert_eq!(cleaned.include, vec!["enp*"]);
+    }
+}
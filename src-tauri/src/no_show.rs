@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db;
+use crate::i18n;
+use crate::money::Money;
+use crate::refunds;
+
+// A passenger who paid and never boarded. Marking the booking a no-show voids it (its seats stop
+// counting as sold, so the driver's settlement leaves them out) and tags it in booking_no_shows.
+// While the vehicle is still in the queue without an exit pass, the seats go back on sale and
+// the waitlist is offered them; once it has left they are gone with it. The passenger gets back
+// the no-show share of the cancellation policy. The daily report counts no-shows by destination.
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoShowDto {
+    pub booking_id: String,
+    pub verification_code: String,
+    pub destination_id: Option<String>,
+    pub destination_name: String,
+    pub license_plate: String,
+    pub seats: i32,
+    pub amount: Money,
+    pub refunded: Money,
+    /// Whether the seats went back on sale on the vehicle
+    pub seat_freed: bool,
+    pub message: String,
+}
+
+/// Mark the booking a no-show, in the caller's transaction
+pub async fn mark(client: &impl db::GenericClient, booking_id: &str, created_by: &Option<String>) -> Result<NoShowDto, String> {
+    let details = db::booking::find(client, booking_id)
+        .await?
+        .ok_or_else(|| i18n::tf("error.booking_not_found", &[("id", &booking_id)]))?;
+    let state = db::no_shows::state(client, booking_id)
+        .await?
+        .ok_or_else(|| i18n::tf("error.booking_not_found", &[("id", &booking_id)]))?;
+    if state.no_show {
+        return Err(i18n::tf("error.no_show_already", &[("code", &details.verification_code)]));
+    }
+    if state.payment_status != "PAID" {
+        return Err(i18n::tf("error.no_show_not_paid", &[("status", &state.payment_status)]));
+    }
+    let booking = &details.booking;
+    let still_queued = db::queue::destination_of(client, &booking.queue_id).await?.is_some();
+    let seat_freed = still_queued && !db::passes::exit_pass_printed(client, &booking.queue_id).await?;
+    let refunded = db::cancellations::policy(client).await?.no_show_refund(booking.total_amount);
+    let refunded = refunds::cap_booking_refund(client, &booking.id, booking.total_amount, refunded).await?;
+
+    db::booking::void(client, booking, refunded).await?;
+    if seat_freed {
+        db::queue::release_seats(client, &booking.queue_id, booking.seats_booked).await?;
+    }
+    db::no_shows::record(client, &details, &state.destination_id, refunded, seat_freed, created_by).await?;
+
+    let message = if seat_freed {
+        i18n::tf(
+            "message.booking_no_show",
+            &[("code", &details.verification_code), ("seats", &booking.seats_booked), ("plate", &details.license_plate), ("refund", &refunded)],
+        )
+    } else {
+        i18n::tf("message.booking_no_show_departed", &[("code", &details.verification_code), ("plate", &details.license_plate), ("refund", &refunded)])
+    };
+    Ok(NoShowDto {
+        booking_id: booking.id.clone(),
+        verification_code: details.verification_code.clone(),
+        destination_id: state.destination_id,
+        destination_name: details.destination_name.clone(),
+        license_plate: details.license_plate.clone(),
+        seats: booking.seats_booked,
+        amount: booking.total_amount,
+        refunded,
+        seat_freed,
+        message,
+    })
+}
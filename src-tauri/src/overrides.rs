@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use crate::auth;
+use crate::i18n;
+use crate::printer::StaffInfo;
+
+// Supervisor overrides: actions a cashier may not take alone. Refunding the bookings of a
+// vehicle pulled from the queue, changing a pass price, ending a trip below the fill threshold
+// cancelling a booking after its vehicle left and selling after hours need a supervisor PIN, a
+// reason code and a note, or a request approved from the supervisor's inbox (approvals.rs).
+// db::overrides::record_override keeps all three with what was overridden, in the same
+// transaction as the action, for the head office audit.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OverrideAction {
+    /// Paid bookings cancelled and handed back
+    Refund,
+    /// A price changed outside the route tariff
+    PriceOverride,
+    /// A trip ended with fewer seats sold than the approval policy asks for
+    DispatchBelowThreshold,
+    /// A booking cancelled after its vehicle's exit pass was printed
+    VoidAfterWindow,
+    /// A terminal selling outside business hours
+    AfterHours,
+}
+
+impl OverrideAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverrideAction::Refund => "REFUND",
+            OverrideAction::PriceOverride => "PRICE_OVERRIDE",
+            OverrideAction::DispatchBelowThreshold => "DISPATCH_BELOW_THRESHOLD",
+            OverrideAction::VoidAfterWindow => "VOID_AFTER_WINDOW",
+            OverrideAction::AfterHours => "AFTER_HOURS",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OverrideReason {
+    CustomerRequest,
+    VehicleBreakdown,
+    CashierError,
+    TariffChange,
+    Other,
+}
+
+impl OverrideReason {
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().replace('-', "_").as_str() {
+            "CUSTOMER_REQUEST" => Some(OverrideReason::CustomerRequest),
+            "VEHICLE_BREAKDOWN" => Some(OverrideReason::VehicleBreakdown),
+            "CASHIER_ERROR" => Some(OverrideReason::CashierError),
+            "TARIFF_CHANGE" => Some(OverrideReason::TariffChange),
+            "OTHER" => Some(OverrideReason::Other),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverrideReason::CustomerRequest => "CUSTOMER_REQUEST",
+            OverrideReason::VehicleBreakdown => "VEHICLE_BREAKDOWN",
+            OverrideReason::CashierError => "CASHIER_ERROR",
+            OverrideReason::TariffChange => "TARIFF_CHANGE",
+            OverrideReason::Other => "OTHER",
+        }
+    }
+}
+
+/// Sent by the frontend with an overridden action
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SupervisorOverride {
+    pub pin: String,
+    pub reason_code: String,
+    pub note: String,
+}
+
+/// A checked reason, authorized by the supervisor owning the PIN
+#[derive(Debug, Clone)]
+pub struct AuthorizedOverride {
+    pub supervisor: StaffInfo,
+    pub reason: OverrideReason,
+    pub note: String,
+}
+
+/// Reason code and note, both required
+pub fn check_reason(reason_code: &str, note: &str) -> Result<(OverrideReason, String), String> {
+    let reason = OverrideReason::from_name(reason_code).ok_or_else(|| i18n::tf("error.invalid_override_reason", &[("code", &reason_code)]))?;
+    let note = note.trim();
+    if note.is_empty() {
+        return Err(i18n::t("error.override_note_required"));
+    }
+    Ok((reason, note.to_string()))
+}
+
+impl SupervisorOverride {
+    pub fn reason(&self) -> Result<(OverrideReason, String), String> {
+        check_reason(&self.reason_code, &self.note)
+    }
+
+    /// The reason is checked first so a missing note does not count as a wrong PIN
+    pub async fn authorize(&self) -> Result<AuthorizedOverride, String> {
+        let (reason, note) = self.reason()?;
+        let supervisor = auth::verify_supervisor_pin(&self.pin).await?;
+        Ok(AuthorizedOverride { supervisor, reason, note })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(reason_code: &str, note: &str) -> SupervisorOverride {
+        SupervisorOverride { pin: "1234".to_string(), reason_code: reason_code.to_string(), note: note.to_string() }
+    }
+
+    #[test]
+    fn reason_code_and_note_are_required() {
+        assert_eq!(
+            request("vehicle-breakdown", "  Panne moteur ").reason().unwrap(),
+            (OverrideReason::VehicleBreakdown, "Panne moteur".to_string())
+        );
+        assert!(request("", "Panne moteur").reason().is_err());
+        assert!(request("BECAUSE", "Panne moteur").reason().is_err());
+        assert!(request("OTHER", "   ").reason().is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PaymentMethod {
+    Cash,
+    Card,
+    EWallet,
+    OnAccount,
+}
+
+impl PaymentMethod {
+    pub const ALL: [PaymentMethod; 4] = [PaymentMethod::Cash, PaymentMethod::Card, PaymentMethod::EWallet, PaymentMethod::OnAccount];
+
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().replace('-', "_").as_str() {
+            "CASH" | "ESPECES" | "ESPÈCES" => Some(PaymentMethod::Cash),
+            "CARD" | "CARTE" => Some(PaymentMethod::Card),
+            "E_WALLET" | "EWALLET" | "WALLET" => Some(PaymentMethod::EWallet),
+            "ON_ACCOUNT" | "ACCOUNT" | "COMPTE" => Some(PaymentMethod::OnAccount),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "CASH",
+            PaymentMethod::Card => "CARD",
+            PaymentMethod::EWallet => "E_WALLET",
+            PaymentMethod::OnAccount => "ON_ACCOUNT",
+        }
+    }
+
+    // Printed on tickets and reports
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "Espèces",
+            PaymentMethod::Card => "Carte",
+            PaymentMethod::EWallet => "E-wallet",
+            PaymentMethod::OnAccount => "Sur compte",
+        }
+    }
+}
+
+// bookings.payment_method for a booking paid with more than one method
+pub const SPLIT_METHOD: &str = "SPLIT";
+
+// Printable name for a stored payment_method, including SPLIT and unknown legacy values
+pub fn method_label(value: &str) -> String {
+    match PaymentMethod::from_name(value) {
+        Some(method) => method.label().to_string(),
+        None if value == SPLIT_METHOD => "Mixte".to_string(),
+        None => value.to_string(),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPart {
+    pub method: PaymentMethod,
+    pub amount: Money,
+}
+
+// How the customer pays for one booking call, before the total is known
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaymentPlan {
+    Single(PaymentMethod),
+    Split(Vec<PaymentPart>),
+}
+
+impl PaymentPlan {
+    /// Checks the requested method (default CASH) or split against what the station accepts
+    pub fn resolve(payment_method: Option<&str>, payments: Option<Vec<PaymentPart>>, accepted: &[PaymentMethod]) -> Result<Self, String> {
+        let check = |method: PaymentMethod| {
+            if accepted.contains(&method) {
+                Ok(())
+            } else {
+                Err(format!("Mode de paiement non accepté dans cette station: {}", method.label()))
+            }
+        };
+
+        match payments {
+            Some(parts) if !parts.is_empty() => {
+                for part in &parts {
+                    check(part.method)?;
+                    if !part.amount.is_positive() {
+                        return Err(format!("Montant invalide pour {}: {}", part.method.label(), part.amount));
+                    }
+                }
+                if parts.len() == 1 {
+                    return Ok(PaymentPlan::Single(parts[0].method));
+                }
+                Ok(PaymentPlan::Split(parts))
+            }
+            _ => {
+                let method = match payment_method {
+                    Some(name) => PaymentMethod::from_name(name).ok_or_else(|| format!("Mode de paiement invalide: {}", name))?,
+                    None => PaymentMethod::Cash,
+                };
+                check(method)?;
+                Ok(PaymentPlan::Single(method))
+            }
+        }
+    }
+
+    /// Value stored in bookings.payment_method
+    pub fn stored_method(&self) -> &'static str {
+        match self {
+            PaymentPlan::Single(method) => method.as_str(),
+            PaymentPlan::Split(_) => SPLIT_METHOD,
+        }
+    }
+
+    /// The parts paying `total`; a split has to add up to it exactly
+    pub fn parts(&self, total: Money) -> Result<Vec<PaymentPart>, String> {
+        match self {
+            PaymentPlan::Single(method) => Ok(vec![PaymentPart { method: *method, amount: total }]),
+            PaymentPlan::Split(parts) => {
+                let paid: Money = parts.iter().map(|p| p.amount).sum();
+                if paid != total {
+                    return Err(format!("Le paiement mixte ({} TND) ne correspond pas au total ({} TND)", paid, total));
+                }
+                Ok(parts.clone())
+            }
+        }
+    }
+}
+
+/// Spread the parts over bookings of `amounts`, filling each booking in turn in the order the
+/// parts were given. Parts must add up to the sum of `amounts`.
+pub fn allocate(parts: &[PaymentPart], amounts: &[Money]) -> Vec<Vec<PaymentPart>> {
+    let mut remaining: Vec<PaymentPart> = parts.to_vec();
+    let mut next = 0;
+    amounts
+        .iter()
+        .map(|&amount| {
+            let mut due = amount;
+            let mut allocated = Vec::new();
+            while due.is_positive() && next < remaining.len() {
+                let part = &mut remaining[next];
+                let take = part.amount.min(due);
+                if take.is_positive() {
+                    allocated.push(PaymentPart { method: part.method, amount: take });
+                }
+                part.amount -= take;
+                due -= take;
+                if !part.amount.is_positive() {
+                    next += 1;
+                }
+            }
+            allocated
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: &[PaymentMethod] = &PaymentMethod::ALL;
+
+    fn part(method: PaymentMethod, amount: f64) -> PaymentPart {
+        PaymentPart { method, amount: Money::from_tnd(amount) }
+    }
+
+    #[test]
+    fn defaults_to_cash_and_rejects_methods_the_station_does_not_take() {
+        assert_eq!(PaymentPlan::resolve(None, None, &[PaymentMethod::Cash]), Ok(PaymentPlan::Single(PaymentMethod::Cash)));
+        assert_eq!(PaymentPlan::resolve(Some("carte"), None, ALL), Ok(PaymentPlan::Single(PaymentMethod::Card)));
+        assert!(PaymentPlan::resolve(Some("card"), None, &[PaymentMethod::Cash]).is_err());
+        assert!(PaymentPlan::resolve(Some("cheque"), None, ALL).is_err());
+
+        let split = vec![part(PaymentMethod::Cash, 5.0), part(PaymentMethod::EWallet, 5.4)];
+        assert!(PaymentPlan::resolve(None, Some(split), &[PaymentMethod::Cash, PaymentMethod::Card]).is_err());
+    }
+
+    #[test]
+    fn split_has_to_cover_the_total() {
+        let plan = PaymentPlan::resolve(None, Some(vec![part(PaymentMethod::Cash, 5.0), part(PaymentMethod::Card, 5.4)]), ALL).unwrap();
+        assert_eq!(plan.stored_method(), SPLIT_METHOD);
+        assert!(plan.parts(Money::from_tnd(10.4)).is_ok());
+        assert!(plan.parts(Money::from_tnd(10.6)).is_err());
+        // 5.0 + 5.4 is exactly 10.4 in millimes, 10.401 is not
+        assert!(plan.parts(Money::from_tnd(10.401)).is_err());
+    }
+
+    #[test]
+    fn allocation_fills_bookings_in_order() {
+        let parts = [part(PaymentMethod::Cash, 6.0), part(PaymentMethod::Card, 4.4)];
+        let allocated = allocate(&parts, &[Money::from_tnd(5.2), Money::from_tnd(5.2)]);
+        assert_eq!(allocated[0], vec![part(PaymentMethod::Cash, 5.2)]);
+        assert_eq!(allocated[1], vec![part(PaymentMethod::Cash, 0.8), part(PaymentMethod::Card, 4.4)]);
+    }
+}
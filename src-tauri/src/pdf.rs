@@ -0,0 +1,111 @@
+// Minimal PDF writer for archived tickets: one page of monospace text (Courier, WinAnsi
+// encoding) cut to the ticket's size, like a strip of the paper roll. Characters WinAnsi lacks
+// (Arabic on bilingual tickets) come out as '?'.
+
+const FONT_SIZE: f64 = 9.0;
+const LEADING: f64 = 11.0;
+const MARGIN: f64 = 18.0;
+// Courier glyphs are 600/1000 of the font size wide
+const CHAR_WIDTH: f64 = 0.6;
+
+fn win_ansi(c: char) -> u8 {
+    match c as u32 {
+        code @ (0x20..=0x7E | 0xA0..=0xFF) => code as u8,
+        0x20AC => 0x80,
+        0x2018 => 0x91,
+        0x2019 => 0x92,
+        0x201C => 0x93,
+        0x201D => 0x94,
+        0x2013 => 0x96,
+        0x2014 => 0x97,
+        _ => b'?',
+    }
+}
+
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut out = vec![b'('];
+    for byte in text.chars().map(win_ansi) {
+        if matches!(byte, b'(' | b')' | b'\\') {
+            out.push(b'\\');
+        }
+        out.push(byte);
+    }
+    out.push(b')');
+    out
+}
+
+/// A one-page PDF of `text`, at least `columns` characters wide
+pub fn text_page(text: &str, columns: usize) -> Vec<u8> {
+    let lines: Vec<&str> = text.lines().collect();
+    let columns = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0).max(columns);
+    let width = columns as f64 * CHAR_WIDTH * FONT_SIZE + 2.0 * MARGIN;
+    let height = lines.len().max(1) as f64 * LEADING + 2.0 * MARGIN;
+
+    // Each ' operator moves down one line, then shows the string
+    let mut content = format!("BT /F1 {:.0} Tf {:.0} TL {:.2} {:.2} Td\n", FONT_SIZE, LEADING, MARGIN, height - MARGIN).into_bytes();
+    for line in &lines {
+        content.extend(pdf_string(line.trim_end()));
+        content.extend_from_slice(b" '\n");
+    }
+    content.extend_from_slice(b"ET\n");
+
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] /Resources << /Font << /F1 4 0 R >> >> /Contents 5 0 R >>",
+            width, height
+        )
+        .into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_vec(),
+    ];
+    let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+    stream.extend(content);
+    stream.extend_from_slice(b"endstream");
+    objects.push(stream);
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let xref = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref).into_bytes());
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|w| w == needle)
+    }
+
+    #[test]
+    fn tickets_become_one_courier_page_with_a_valid_xref() {
+        let pdf = text_page("   ENTRÉE (file 3)\nC:\\ 5 TND\nمحطة", 32);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(find(&pdf, b"(   ENTR\xC9E \\(file 3\\)) '").is_some());
+        assert!(find(&pdf, b"(C:\\\\ 5 TND) '").is_some());
+        assert!(find(&pdf, b"(????) '").is_some());
+        // 32 columns wide, 3 lines high
+        assert!(find(&pdf, b"/MediaBox [0 0 208.80 69.00]").is_some());
+
+        // The xref points at each object
+        let startxref = find(&pdf, b"startxref\n").unwrap() + b"startxref\n".len();
+        let xref: usize = String::from_utf8_lossy(&pdf[startxref..]).lines().next().unwrap().parse().unwrap();
+        assert!(pdf[xref..].starts_with(b"xref\n0 6\n"));
+        let first = xref + b"xref\n0 6\n".len() + 20;
+        let offset: usize = String::from_utf8_lossy(&pdf[first..first + 10]).parse().unwrap();
+        assert!(pdf[offset..].starts_with(b"1 0 obj\n"));
+    }
+}
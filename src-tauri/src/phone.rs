@@ -0,0 +1,56 @@
+// Drivers' phone numbers, kept in the E.164 form the SMS gateway expects (+216 and 8 digits).
+// Tunisian mobiles start with 2, 3, 4, 5 or 9; landlines (7) cannot receive texts.
+
+const COUNTRY_CODE: &str = "216";
+const MOBILE_PREFIXES: &[char] = &['2', '3', '4', '5', '9'];
+
+/// `+216XXXXXXXX` from what a cashier typed: 8 digits, optionally after +216, 00216 or 216,
+/// with spaces, dots, dashes or parentheses anywhere
+pub fn normalize_mobile(input: &str) -> Result<String, String> {
+    let invalid = || crate::i18n::tf("error.invalid_phone", &[("phone", &input.trim())]);
+    let trimmed = input.trim();
+    let (plus, rest) = match trimmed.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    if rest.chars().any(|c| !(c.is_ascii_digit() || matches!(c, ' ' | '.' | '-' | '(' | ')'))) {
+        return Err(invalid());
+    }
+    let digits: String = rest.chars().filter(|c| c.is_ascii_digit()).collect();
+    let national = if plus {
+        digits.strip_prefix(COUNTRY_CODE)
+    } else if digits.len() == 8 {
+        Some(digits.as_str())
+    } else {
+        digits.strip_prefix("00216").or_else(|| digits.strip_prefix(COUNTRY_CODE))
+    };
+    match national {
+        Some(n) if n.len() == 8 && n.starts_with(MOBILE_PREFIXES) => Ok(format!("+{}{}", COUNTRY_CODE, n)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Normalized number, None for a blank field
+pub fn normalize_optional(input: Option<String>) -> Result<Option<String>, String> {
+    match input.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(phone) => normalize_mobile(phone).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tunisian_mobiles_are_stored_in_e164() {
+        for input in ["98 123 456", "+216 98 123 456", "0021698123456", "216-98-123-456", "+216 98.123.456"] {
+            assert_eq!(normalize_mobile(input).unwrap(), "+21698123456", "{}", input);
+        }
+        assert_eq!(normalize_mobile("22 333 444").unwrap(), "+21622333444");
+        for input in ["71 123 456", "9812345", "+33 6 12 34 56 78", "98 12a 456", "", "+98123456"] {
+            assert!(normalize_mobile(input).is_err(), "{}", input);
+        }
+        assert_eq!(normalize_optional(Some("  ".to_string())).unwrap(), None);
+    }
+}
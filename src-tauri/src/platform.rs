@@ -0,0 +1,474 @@
+// What differs between Windows, Linux and macOS: starting with the session, listing the network
+// interfaces and the system-wide environment the printer settings come from. Each OS has its own
+// `imp` module, picked at build time: the registry Run key on Windows, a systemd user unit on
+// Linux, a launchd agent on macOS. The functions above them return the same shapes everywhere,
+// so the commands and the UI never branch on the OS.
+
+use serde::Serialize;
+use std::net::{Ipv4Addr, UdpSocket};
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use crate::i18n;
+use crate::network_interfaces::{self, NetworkInterfaceDto};
+
+const APP_NAME: &str = "Nqlix";
+
+/// An IPv4 address of a network interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceAddress {
+    pub name: String,
+    pub address: Ipv4Addr,
+    pub prefix_len: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupStatus {
+    pub os: &'static str,
+    /// How the app is started with the session: registry-run, systemd-user or launchd
+    pub method: &'static str,
+    pub enabled: bool,
+    /// Whether this call changed anything
+    pub changed: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkReport {
+    pub title: String,
+    pub command: String,
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInfo {
+    pub os: &'static str,
+    pub interfaces: Vec<NetworkInterfaceDto>,
+    pub local_ip: Option<String>,
+    pub network_prefix: Option<String>,
+    pub reports: Vec<NetworkReport>,
+    pub error: Option<String>,
+}
+
+/// Run a helper program without a console window
+fn run(program: &str, args: &[&str]) -> Result<Output, String> {
+    let mut command = Command::new(program);
+    command.args(args);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command.output().map_err(|e| format!("{}: {}", program, e))
+}
+
+fn succeeded(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = run(program, args)?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} {}: {}", program, args.join(" "), String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+fn current_exe() -> Result<PathBuf, String> {
+    std::env::current_exe().map_err(|e| i18n::tf("error.startup_failed", &[("method", &imp::STARTUP_METHOD), ("error", &e)]))
+}
+
+fn startup(enabled: bool, changed: bool) -> StartupStatus {
+    let key = match (enabled, changed) {
+        (true, true) => "message.startup_enabled",
+        (true, false) => "message.startup_already_enabled",
+        (false, true) => "message.startup_disabled",
+        (false, false) => "message.startup_not_enabled",
+    };
+    StartupStatus {
+        os: std::env::consts::OS,
+        method: imp::STARTUP_METHOD,
+        enabled,
+        changed,
+        message: i18n::tf(key, &[("method", &imp::STARTUP_METHOD)]),
+    }
+}
+
+fn startup_error(error: String) -> String {
+    i18n::tf("error.startup_failed", &[("method", &imp::STARTUP_METHOD), ("error", &error)])
+}
+
+pub fn startup_status() -> Result<StartupStatus, String> {
+    let enabled = imp::startup_enabled(&current_exe()?).map_err(startup_error)?;
+    Ok(startup(enabled, false))
+}
+
+/// Start the app with the session; nothing changes when it already is
+pub fn enable_startup() -> Result<StartupStatus, String> {
+    let exe = current_exe()?;
+    if imp::startup_enabled(&exe).map_err(startup_error)? {
+        return Ok(startup(true, false));
+    }
+    imp::enable_startup(&exe).map_err(startup_error)?;
+    Ok(startup(true, true))
+}
+
+pub fn disable_startup() -> Result<StartupStatus, String> {
+    let exe = current_exe()?;
+    if !imp::startup_enabled(&exe).map_err(startup_error)? {
+        return Ok(startup(false, false));
+    }
+    imp::disable_startup().map_err(startup_error)?;
+    Ok(startup(false, true))
+}
+
+// Connecting a UDP socket sends nothing: it only asks the OS which address the route uses
+pub fn routed_ipv4() -> Option<Ipv4Addr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    match socket.local_addr().ok()?.ip() {
+        std::net::IpAddr::V4(ip) => Some(ip),
+        std::net::IpAddr::V6(_) => None,
+    }
+}
+
+/// IPv4 addresses of every interface, from the OS's own tool; empty when it cannot be run
+pub fn interfaces() -> Vec<InterfaceAddress> {
+    imp::interfaces()
+}
+
+/// Interfaces, the station address and the OS's own view of the routes and interfaces
+pub fn network_info() -> NetworkInfo {
+    let interfaces = network_interfaces::list();
+    let local_ip: Option<Ipv4Addr> = interfaces.iter().find(|i| i.selected).and_then(|i| i.address.parse().ok());
+    let reports = imp::NETWORK_REPORTS
+        .iter()
+        .filter_map(|(title, program, args)| {
+            let output = run(program, args).ok().filter(|o| o.status.success())?;
+            Some(NetworkReport {
+                title: title.to_string(),
+                command: format!("{} {}", program, args.join(" ")).trim().to_string(),
+                output: String::from_utf8_lossy(&output.stdout).to_string(),
+            })
+        })
+        .collect();
+    NetworkInfo {
+        os: std::env::consts::OS,
+        interfaces,
+        local_ip: local_ip.map(|ip| ip.to_string()),
+        network_prefix: local_ip.map(|ip| {
+            let o = ip.octets();
+            format!("{}.{}.{}", o[0], o[1], o[2])
+        }),
+        reports,
+        error: local_ip.is_none().then(|| i18n::t("error.no_local_ip")),
+    }
+}
+
+/// A system-wide environment variable as currently saved, so a value changed after the app
+/// started is seen without a restart
+pub fn system_env(key: &str) -> Option<String> {
+    imp::system_env(key).filter(|v| !v.is_empty())
+}
+
+/// Interfaces in `ip -4 -o addr show` output: "2: enp4s0    inet 192.168.192.100/24 brd ..."
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn ip_addr_interfaces(output: &str) -> Vec<InterfaceAddress> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let at = words.iter().position(|w| *w == "inet")?;
+            let (address, prefix_len) = words.get(at + 1)?.split_once('/').unwrap_or((words[at + 1], ""));
+            Some(InterfaceAddress {
+                name: words.get(at.checked_sub(1)?)?.trim_end_matches(':').to_string(),
+                address: address.parse().ok()?,
+                prefix_len: prefix_len.parse().ok(),
+            })
+        })
+        .collect()
+}
+
+/// Interfaces in `ifconfig` output: an "en0: flags=..." line, then "\tinet 10.0.0.9 netmask 0xffffff00"
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn ifconfig_interfaces(output: &str) -> Vec<InterfaceAddress> {
+    let mut name = "";
+    let mut interfaces = Vec::new();
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            name = line.split(':').next().unwrap_or_default();
+            continue;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.first() != Some(&"inet") {
+            continue;
+        }
+        let Some(address) = words.get(1).and_then(|a| a.parse().ok()) else { continue };
+        let netmask = words.iter().position(|w| *w == "netmask").and_then(|at| words.get(at + 1));
+        let prefix_len = netmask.and_then(|m| u32::from_str_radix(m.trim_start_matches("0x"), 16).ok()).map(|m| m.count_ones() as u8);
+        interfaces.push(InterfaceAddress { name: name.to_string(), address, prefix_len });
+    }
+    interfaces
+}
+
+/// Interfaces in `ipconfig` output, whatever the Windows language: an adapter title line
+/// ("Ethernet adapter Ethernet:"), then its IPv4 address line and the subnet mask after it
+#[cfg_attr(not(windows), allow(dead_code))]
+fn ipconfig_interfaces(output: &str) -> Vec<InterfaceAddress> {
+    let value = |line: &str| -> Option<Ipv4Addr> {
+        let value = line.rsplit(':').next()?.trim();
+        value.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect::<String>().parse().ok()
+    };
+    let mut name = String::new();
+    let mut interfaces: Vec<InterfaceAddress> = Vec::new();
+    let mut awaiting_mask = false;
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            name = line.trim().trim_end_matches(':').trim().to_string();
+            awaiting_mask = false;
+        } else if line.contains("IPv4") {
+            if let Some(address) = value(line) {
+                interfaces.push(InterfaceAddress { name: name.clone(), address, prefix_len: None });
+                awaiting_mask = true;
+            }
+        } else if awaiting_mask {
+            if let Some(mask) = value(line).filter(|m| m.octets()[0] == 255) {
+                if let Some(last) = interfaces.last_mut() {
+                    last.prefix_len = Some(u32::from(mask).count_ones() as u8);
+                }
+                awaiting_mask = false;
+            }
+        }
+    }
+    interfaces
+}
+
+/// `KEY=value` or `export KEY="value"` from a shell environment file
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn env_file_value(content: &str, key: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        let (k, value) = line.strip_prefix("export ").unwrap_or(line).split_once('=')?;
+        if k.trim() != key {
+            return None;
+        }
+        let value = value.trim();
+        let value = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value);
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// The data of `key` in `reg query` output
+#[cfg_attr(not(windows), allow(dead_code))]
+fn reg_value(output: &str, key: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        let name = words.next()?;
+        let kind = words.next()?;
+        if !name.eq_ignore_ascii_case(key) || !kind.starts_with("REG_") {
+            return None;
+        }
+        let data = line[line.find(kind)? + kind.len()..].trim();
+        Some(data.to_string())
+    })
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::*;
+    use std::path::Path;
+
+    pub const STARTUP_METHOD: &str = "registry-run";
+    pub const NETWORK_REPORTS: &[(&str, &str, &[&str])] = &[("Routes", "route", &["print", "-4"]), ("Interfaces", "ipconfig", &["/all"])];
+
+    const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+    const USER_ENV_KEY: &str = r"HKCU\Environment";
+    const MACHINE_ENV_KEY: &str = r"HKLM\SYSTEM\CurrentControlSet\Control\Session Manager\Environment";
+
+    fn query(key: &str, value: &str) -> Option<String> {
+        let output = run("reg", &["query", key, "/v", value]).ok().filter(|o| o.status.success())?;
+        reg_value(&String::from_utf8_lossy(&output.stdout), value)
+    }
+
+    pub fn startup_enabled(_exe: &Path) -> Result<bool, String> {
+        Ok(query(RUN_KEY, APP_NAME).is_some())
+    }
+
+    pub fn enable_startup(exe: &Path) -> Result<(), String> {
+        let command = format!("\"{}\"", exe.display());
+        succeeded("reg", &["add", RUN_KEY, "/v", APP_NAME, "/t", "REG_SZ", "/d", &command, "/f"])
+    }
+
+    pub fn disable_startup() -> Result<(), String> {
+        succeeded("reg", &["delete", RUN_KEY, "/v", APP_NAME, "/f"])
+    }
+
+    pub fn interfaces() -> Vec<InterfaceAddress> {
+        run("ipconfig", &[]).map(|o| ipconfig_interfaces(&String::from_utf8_lossy(&o.stdout))).unwrap_or_default()
+    }
+
+    // The user's value wins over the machine's, as in a new session
+    pub fn system_env(key: &str) -> Option<String> {
+        query(USER_ENV_KEY, key).or_else(|| query(MACHINE_ENV_KEY, key))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    pub const STARTUP_METHOD: &str = "systemd-user";
+    pub const NETWORK_REPORTS: &[(&str, &str, &[&str])] = &[("Routes", "ip", &["route", "get", "8.8.8.8"]), ("Interfaces", "ip", &["addr", "show"])];
+
+    const UNIT: &str = "nqlix.service";
+
+    fn config_dir() -> Result<PathBuf, String> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .ok_or_else(|| "HOME is not set".to_string())
+    }
+
+    fn unit_path() -> Result<PathBuf, String> {
+        Ok(config_dir()?.join("systemd").join("user").join(UNIT))
+    }
+
+    // The XDG autostart entry earlier versions wrote; it would start a second instance
+    fn remove_desktop_entry() {
+        if let Ok(dir) = config_dir() {
+            let _ = fs::remove_file(dir.join("autostart").join(format!("{}.desktop", APP_NAME)));
+        }
+    }
+
+    fn unit(exe: &Path) -> String {
+        format!(
+            "[Unit]\nDescription={}\nPartOf=graphical-session.target\nAfter=graphical-session.target\n\n[Service]\nExecStart=\"{}\"\nRestart=on-failure\n\n[Install]\nWantedBy=graphical-session.target\n",
+            APP_NAME,
+            exe.display()
+        )
+    }
+
+    pub fn startup_enabled(_exe: &Path) -> Result<bool, String> {
+        Ok(unit_path()?.exists() && succeeded("systemctl", &["--user", "is-enabled", "--quiet", UNIT]).is_ok())
+    }
+
+    pub fn enable_startup(exe: &Path) -> Result<(), String> {
+        let path = unit_path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(&path, unit(exe)).map_err(|e| e.to_string())?;
+        succeeded("systemctl", &["--user", "daemon-reload"])?;
+        succeeded("systemctl", &["--user", "enable", UNIT])?;
+        remove_desktop_entry();
+        Ok(())
+    }
+
+    pub fn disable_startup() -> Result<(), String> {
+        succeeded("systemctl", &["--user", "disable", UNIT])?;
+        let _ = fs::remove_file(unit_path()?);
+        let _ = succeeded("systemctl", &["--user", "daemon-reload"]);
+        remove_desktop_entry();
+        Ok(())
+    }
+
+    pub fn interfaces() -> Vec<InterfaceAddress> {
+        run("ip", &["-4", "-o", "addr", "show"]).map(|o| ip_addr_interfaces(&String::from_utf8_lossy(&o.stdout))).unwrap_or_default()
+    }
+
+    pub fn system_env(key: &str) -> Option<String> {
+        ["/etc/environment", "/etc/profile.d/printer-env.sh"]
+            .iter()
+            .find_map(|path| env_file_value(&fs::read_to_string(path).ok()?, key))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    pub const STARTUP_METHOD: &str = "launchd";
+    pub const NETWORK_REPORTS: &[(&str, &str, &[&str])] = &[("Routes", "route", &["-n", "get", "8.8.8.8"]), ("Interfaces", "ifconfig", &[])];
+
+    const LABEL: &str = "com.nqlix.app";
+
+    fn agents_dir() -> Result<PathBuf, String> {
+        std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join("Library").join("LaunchAgents"))
+            .ok_or_else(|| "HOME is not set".to_string())
+    }
+
+    fn xml_escape(value: &str) -> String {
+        value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+    }
+
+    // launchd reads the agent at the next login; loading it now would start a second instance
+    fn agent(exe: &Path) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n<plist version=\"1.0\">\n<dict>\n  <key>Label</key>\n  <string>{}</string>\n  <key>ProgramArguments</key>\n  <array>\n    <string>{}</string>\n  </array>\n  <key>RunAtLoad</key>\n  <true/>\n</dict>\n</plist>\n",
+            LABEL,
+            xml_escape(&exe.display().to_string())
+        )
+    }
+
+    pub fn startup_enabled(_exe: &Path) -> Result<bool, String> {
+        Ok(agents_dir()?.join(format!("{}.plist", LABEL)).exists())
+    }
+
+    pub fn enable_startup(exe: &Path) -> Result<(), String> {
+        let dir = agents_dir()?;
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        fs::write(dir.join(format!("{}.plist", LABEL)), agent(exe)).map_err(|e| e.to_string())?;
+        // The agent earlier versions wrote under the app name
+        let _ = fs::remove_file(dir.join(format!("{}.plist", APP_NAME)));
+        Ok(())
+    }
+
+    pub fn disable_startup() -> Result<(), String> {
+        let dir = agents_dir()?;
+        fs::remove_file(dir.join(format!("{}.plist", LABEL))).map_err(|e| e.to_string())?;
+        let _ = fs::remove_file(dir.join(format!("{}.plist", APP_NAME)));
+        Ok(())
+    }
+
+    pub fn interfaces() -> Vec<InterfaceAddress> {
+        run("ifconfig", &[]).map(|o| ifconfig_interfaces(&String::from_utf8_lossy(&o.stdout))).unwrap_or_default()
+    }
+
+    pub fn system_env(key: &str) -> Option<String> {
+        let output = run("launchctl", &["getenv", key]).ok().filter(|o| o.status.success())?;
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn os_tool_output_lists_interfaces_and_environment() {
+        let ip = "1: lo    inet 127.0.0.1/8 scope host lo\n2: enp4s0    inet 192.168.192.100/24 brd 192.168.192.255 scope global enp4s0";
+        let mac = "lo0: flags=8049<UP,LOOPBACK> mtu 16384\n\tinet 127.0.0.1 netmask 0xff000000\nen0: flags=8863<UP> mtu 1500\n\tinet6 fe80::1 prefixlen 64\n\tinet 10.0.0.9 netmask 0xffffff00 broadcast 10.0.0.255";
+        let ipconfig = "Carte Ethernet Ethernet :\r\n\r\n   Adresse IPv4. . . . . . . . . . . . . .: 192.168.192.21(préféré)\r\n   Masque de sous-réseau. . . . . . . . . : 255.255.255.0\r\n\r\nWireless LAN adapter Wi-Fi:\r\n\r\n   IPv4 Address. . . . . . . . . . . : 10.0.0.8\r\n";
+        let interface = |name: &str, address: [u8; 4], prefix_len| InterfaceAddress { name: name.to_string(), address: Ipv4Addr::from(address), prefix_len };
+        assert_eq!(ip_addr_interfaces(ip), vec![interface("lo", [127, 0, 0, 1], Some(8)), interface("enp4s0", [192, 168, 192, 100], Some(24))]);
+        assert_eq!(ifconfig_interfaces(mac), vec![interface("lo0", [127, 0, 0, 1], Some(8)), interface("en0", [10, 0, 0, 9], Some(24))]);
+        assert_eq!(ipconfig_interfaces(ipconfig), vec![interface("Carte Ethernet Ethernet", [192, 168, 192, 21], Some(24)), interface("Wireless LAN adapter Wi-Fi", [10, 0, 0, 8], None)]);
+
+        assert_eq!(env_file_value("# PRINTER_IP=x\nPRINTER_IP=\"192.168.192.10\"", "PRINTER_IP").as_deref(), Some("192.168.192.10"));
+        assert_eq!(env_file_value("export PRINTER_PORT=9100", "PRINTER_PORT").as_deref(), Some("9100"));
+        let reg = "\r\nHKEY_CURRENT_USER\\Environment\r\n    PRINTER_NAME    REG_SZ    Imprimante Thermique\r\n";
+        assert_eq!(reg_value(reg, "printer_name").as_deref(), Some("Imprimante Thermique"));
+    }
+}
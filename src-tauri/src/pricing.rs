@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+use crate::money::{Money, SERVICE_FEE_PER_SEAT};
+
+// Fare modifiers on top of the route base price, configured by supervisors in pricing_rules:
+// reduced fares for children and supplements for luggage. A rule is either a fixed amount or a
+// percent of the base price, per child seat or per bag, for one destination or for all of them.
+// The cashier picks rules and quantities when booking; each booking carries its modifier lines
+// so tickets and reports can itemize them.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PricingRuleType {
+    /// Reduction per child seat, never below a free seat
+    ChildFare,
+    /// Supplement per bag, not tied to a seat
+    Luggage,
+}
+
+impl PricingRuleType {
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().replace('-', "_").as_str() {
+            "CHILD_FARE" | "CHILD" | "ENFANT" => Some(PricingRuleType::ChildFare),
+            "LUGGAGE" | "BAGGAGE" | "BAGAGE" => Some(PricingRuleType::Luggage),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PricingRuleType::ChildFare => "CHILD_FARE",
+            PricingRuleType::Luggage => "LUGGAGE",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingRule {
+    #[serde(default)]
+    pub id: String,
+    pub rule_type: PricingRuleType,
+    /// Printed on the ticket next to the quantity
+    pub label: String,
+    pub amount: Option<Money>,
+    pub percent: Option<f64>,
+    /// None for every destination
+    pub destination_id: Option<String>,
+    pub is_active: bool,
+}
+
+impl PricingRule {
+    pub fn applies_to(&self, destination_id: &str) -> bool {
+        self.is_active && self.destination_id.as_deref().map_or(true, |d| d == destination_id)
+    }
+
+    /// Change to the fare for one unit at `base_price`: negative for a child seat, positive for a bag
+    pub fn unit_amount(&self, base_price: Money) -> Money {
+        let magnitude = match (self.amount, self.percent) {
+            (Some(amount), _) => amount,
+            // percent to two decimals, as basis points of the base price
+            (None, Some(percent)) => base_price.portion((percent * 100.0).round() as i64, 10_000),
+            (None, None) => Money::ZERO,
+        };
+        match self.rule_type {
+            PricingRuleType::ChildFare => -magnitude.min(base_price),
+            PricingRuleType::Luggage => magnitude,
+        }
+    }
+
+    /// Checks a rule before it is saved
+    pub fn validate(&self) -> Result<(), String> {
+        if self.label.trim().is_empty() {
+            return Err(i18n::t("error.pricing_rule_label_required"));
+        }
+        match (self.amount, self.percent) {
+            (Some(amount), None) if amount.is_positive() => Ok(()),
+            (None, Some(percent)) if percent > 0.0 && percent <= 100.0 => Ok(()),
+            _ => Err(i18n::t("error.pricing_rule_value")),
+        }
+    }
+}
+
+/// What the cashier asks for on a booking call: `quantity` child seats or bags under `rule_id`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FareSelection {
+    pub rule_id: String,
+    pub quantity: i32,
+}
+
+/// One modifier line of a booking, as stored and printed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FareModifier {
+    pub rule_id: String,
+    pub rule_type: PricingRuleType,
+    pub label: String,
+    pub quantity: i32,
+    pub amount: Money,
+}
+
+pub fn total(modifiers: &[FareModifier]) -> Money {
+    modifiers.iter().map(|m| m.amount).sum()
+}
+
+// The selections of one booking call, checked against the rules, handed out to the bookings
+// the call makes as seats are taken from each vehicle
+#[derive(Debug, Clone, Default)]
+pub struct FareRequest {
+    items: Vec<(PricingRule, i32)>,
+}
+
+impl FareRequest {
+    /// Every selected rule has to be active for `destination_id`; child seats cannot exceed `seats`
+    pub fn resolve(rules: &[PricingRule], destination_id: &str, seats: i32, selections: Option<Vec<FareSelection>>) -> Result<Self, String> {
+        let mut items: Vec<(PricingRule, i32)> = Vec::new();
+        for selection in selections.unwrap_or_default() {
+            if selection.quantity <= 0 {
+                continue;
+            }
+            let rule = rules
+                .iter()
+                .find(|r| r.id == selection.rule_id && r.applies_to(destination_id))
+                .ok_or_else(|| i18n::tf("error.pricing_rule_not_applicable", &[("id", &selection.rule_id)]))?;
+            match items.iter_mut().find(|(r, _)| r.id == rule.id) {
+                Some((_, quantity)) => *quantity += selection.quantity,
+                None => items.push((rule.clone(), selection.quantity)),
+            }
+        }
+        let child_seats: i32 = items.iter().filter(|(r, _)| r.rule_type == PricingRuleType::ChildFare).map(|(_, q)| q).sum();
+        if child_seats > seats {
+            return Err(i18n::tf("error.child_seats_exceed", &[("children", &child_seats), ("seats", &seats)]));
+        }
+        Ok(FareRequest { items })
+    }
+
+    /// Modifiers for a booking of `seats` at `base_price`: child fares take up to `seats` of what
+    /// is left, luggage all goes on the first booking
+    pub fn take(&mut self, seats: i32, base_price: Money) -> Vec<FareModifier> {
+        let mut modifiers = Vec::new();
+        let mut seats_left = seats;
+        for (rule, remaining) in self.items.iter_mut() {
+            let quantity = match rule.rule_type {
+                PricingRuleType::ChildFare => (*remaining).min(seats_left),
+                PricingRuleType::Luggage => *remaining,
+            };
+            if quantity <= 0 {
+                continue;
+            }
+            if rule.rule_type == PricingRuleType::ChildFare {
+                seats_left -= quantity;
+            }
+            *remaining -= quantity;
+            modifiers.push(FareModifier {
+                rule_id: rule.id.clone(),
+                rule_type: rule.rule_type,
+                label: rule.label.clone(),
+                quantity,
+                amount: rule.unit_amount(base_price) * quantity,
+            });
+        }
+        modifiers
+    }
+
+    /// What the call costs against vehicles given as (available seats, base price) in queue
+    /// order, allocated the way the booking commands do: the first vehicle that can take every
+    /// seat, else seats spread down the queue. None when the vehicles do not have the seats.
+    pub fn quote(&self, vehicles: &[(i32, Money)], seats: i32) -> Option<Money> {
+        let mut fares = self.clone();
+        let mut price = |take: i32, base_price: Money| base_price * take + SERVICE_FEE_PER_SEAT * take + total(&fares.take(take, base_price));
+        if let Some(&(_, base_price)) = vehicles.iter().find(|(available, _)| *available >= seats) {
+            return Some(price(seats, base_price));
+        }
+        let mut remaining = seats;
+        let mut amount = Money::ZERO;
+        for &(available, base_price) in vehicles {
+            let take = remaining.min(available);
+            if take <= 0 {
+                continue;
+            }
+            amount += price(take, base_price);
+            remaining -= take;
+        }
+        (remaining <= 0).then_some(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, rule_type: PricingRuleType, amount: Option<f64>, percent: Option<f64>, destination_id: Option<&str>) -> PricingRule {
+        PricingRule {
+            id: id.to_string(),
+            rule_type,
+            label: id.to_string(),
+            amount: amount.map(Money::from_tnd),
+            percent,
+            destination_id: destination_id.map(str::to_string),
+            is_active: true,
+        }
+    }
+
+    fn select(rule_id: &str, quantity: i32) -> FareSelection {
+        FareSelection { rule_id: rule_id.to_string(), quantity }
+    }
+
+    #[test]
+    fn unit_amounts_from_fixed_and_percent_rules() {
+        let base = Money::from_tnd(5.5);
+        assert_eq!(rule("child", PricingRuleType::ChildFare, None, Some(50.0), None).unit_amount(base), Money::from_tnd(-2.75));
+        assert_eq!(rule("child", PricingRuleType::ChildFare, Some(9.0), None, None).unit_amount(base), -base);
+        assert_eq!(rule("bag", PricingRuleType::Luggage, Some(1.0), None, None).unit_amount(base), Money::from_tnd(1.0));
+        assert_eq!(rule("bag", PricingRuleType::Luggage, None, Some(12.5), None).unit_amount(base), Money::from_millimes(688));
+    }
+
+    #[test]
+    fn rules_must_apply_to_the_destination() {
+        let rules = vec![
+            rule("child", PricingRuleType::ChildFare, None, Some(50.0), None),
+            rule("sfax-bag", PricingRuleType::Luggage, Some(1.0), None, Some("sfax")),
+        ];
+        assert!(FareRequest::resolve(&rules, "sfax", 2, Some(vec![select("sfax-bag", 1)])).is_ok());
+        assert!(FareRequest::resolve(&rules, "sousse", 2, Some(vec![select("sfax-bag", 1)])).is_err());
+        assert!(FareRequest::resolve(&rules, "sousse", 2, Some(vec![select("missing", 1)])).is_err());
+
+        let mut inactive = rules.clone();
+        inactive[0].is_active = false;
+        assert!(FareRequest::resolve(&inactive, "sousse", 2, Some(vec![select("child", 1)])).is_err());
+    }
+
+    #[test]
+    fn child_seats_cannot_exceed_the_booking() {
+        let rules = vec![rule("child", PricingRuleType::ChildFare, None, Some(50.0), None)];
+        assert!(FareRequest::resolve(&rules, "sousse", 2, Some(vec![select("child", 1), select("child", 2)])).is_err());
+        assert!(FareRequest::resolve(&rules, "sousse", 3, Some(vec![select("child", 1), select("child", 2)])).is_ok());
+    }
+
+    #[test]
+    fn modifiers_follow_the_seats_across_vehicles() {
+        let rules = vec![
+            rule("child", PricingRuleType::ChildFare, None, Some(50.0), None),
+            rule("bag", PricingRuleType::Luggage, Some(1.0), None, None),
+        ];
+        let base = Money::from_tnd(4.0);
+        let mut request = FareRequest::resolve(&rules, "sousse", 5, Some(vec![select("child", 3), select("bag", 2)])).unwrap();
+
+        let first = request.take(2, base);
+        assert_eq!(first.len(), 2);
+        assert_eq!((first[0].quantity, first[0].amount), (2, Money::from_tnd(-4.0)));
+        assert_eq!((first[1].quantity, first[1].amount), (2, Money::from_tnd(2.0)));
+        assert_eq!(total(&first), Money::from_tnd(-2.0));
+
+        let second = request.take(3, base);
+        assert_eq!(second.len(), 1);
+        assert_eq!((second[0].rule_type, second[0].quantity), (PricingRuleType::ChildFare, 1));
+        assert!(request.take(1, base).is_empty());
+    }
+
+    #[test]
+    fn quotes_match_the_booking_allocation() {
+        let rules = vec![rule("child", PricingRuleType::ChildFare, None, Some(50.0), None)];
+        let fares = FareRequest::resolve(&rules, "dest", 3, Some(vec![select("child", 1)])).unwrap();
+        let full = Money::from_tnd(5.0) + SERVICE_FEE_PER_SEAT;
+
+        // One vehicle takes the whole call even when an earlier one has some seats
+        let vehicles = [(2, Money::from_tnd(4.0)), (5, Money::from_tnd(5.0))];
+        assert_eq!(fares.quote(&vehicles, 3), Some(full * 3 - Money::from_tnd(2.5)));
+
+        // Otherwise the seats are spread down the queue and the child fare goes with the first
+        let vehicles = [(2, Money::from_tnd(4.0)), (2, Money::from_tnd(5.0))];
+        let first = (Money::from_tnd(4.0) + SERVICE_FEE_PER_SEAT) * 2 - Money::from_tnd(2.0);
+        assert_eq!(fares.quote(&vehicles, 3), Some(first + full));
+
+        assert_eq!(fares.quote(&[(1, Money::from_tnd(5.0))], 3), None);
+    }
+}
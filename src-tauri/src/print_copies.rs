@@ -0,0 +1,93 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::db;
+use crate::i18n;
+use crate::printer::PrintJobType;
+use crate::DB_POOL;
+
+// Copies printed of each ticket type, e.g. two exit passes, one for the driver and one for the
+// accounting archive. The counts are the print_copies setting (JSON in station_settings), cached
+// here for the print queue processor, which prints copy 2 onwards under a "COPIE 2/2" line.
+
+const POLICY_KEY: &str = "print_copies";
+pub const MAX_COPIES: u8 = 5;
+
+static COPIES: Lazy<RwLock<PrintCopies>> = Lazy::new(|| RwLock::new(PrintCopies::default()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrintCopies {
+    /// Copies by job type ("ExitPassTicket"); a type not listed prints once
+    pub copies: HashMap<String, u8>,
+}
+
+impl PrintCopies {
+    /// The stored setting, or one copy of everything when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    /// Checked counts, keyed by job type name whatever kind was given ("exit_pass")
+    pub fn validate(&self) -> Result<Self, String> {
+        let mut copies = HashMap::new();
+        for (kind, count) in &self.copies {
+            let job_type = PrintJobType::from_kind(kind).filter(|_| (1..=MAX_COPIES).contains(count));
+            let job_type = job_type.ok_or_else(|| i18n::tf("error.print_copies_invalid", &[("kind", kind), ("max", &MAX_COPIES)]))?;
+            copies.insert(format!("{:?}", job_type), *count);
+        }
+        Ok(PrintCopies { copies })
+    }
+
+    pub fn copies_for(&self, job_type: &PrintJobType) -> u8 {
+        self.copies.get(&format!("{:?}", job_type)).copied().unwrap_or(1).max(1)
+    }
+}
+
+/// Copies to print of a job of this type
+pub fn copies_for(job_type: &PrintJobType) -> u8 {
+    COPIES.read().map(|c| c.copies_for(job_type)).unwrap_or(1)
+}
+
+fn cache(copies: &PrintCopies) {
+    if let Ok(mut cached) = COPIES.write() {
+        *cached = copies.clone();
+    }
+}
+
+/// Read the copy counts from the database into the cache
+pub async fn load() -> Result<PrintCopies, String> {
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let copies = PrintCopies::parse(db::settings::get(&client, POLICY_KEY).await?.as_deref());
+    cache(&copies);
+    Ok(copies)
+}
+
+pub async fn save(client: &impl db::GenericClient, copies: &PrintCopies) -> Result<PrintCopies, String> {
+    let copies = copies.validate()?;
+    db::settings::set(client, POLICY_KEY, &serde_json::to_string(&copies).map_err(|e| e.to_string())?).await?;
+    cache(&copies);
+    Ok(copies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_are_counted_by_job_type() {
+        let copies = PrintCopies::parse(Some(r#"{"copies":{"exit_pass":2,"talon":1}}"#)).validate().unwrap();
+        assert_eq!(copies.copies_for(&PrintJobType::ExitPassTicket), 2);
+        assert_eq!(copies.copies_for(&PrintJobType::Talon), 1);
+        assert_eq!(copies.copies_for(&PrintJobType::BookingTicket), 1);
+        assert_eq!(copies.copies.get("ExitPassTicket"), Some(&2));
+
+        let invalid = |kind: &str, count| PrintCopies { copies: HashMap::from([(kind.to_string(), count)]) }.validate();
+        assert!(invalid("exit_pass", 0).is_err());
+        assert!(invalid("exit_pass", MAX_COPIES + 1).is_err());
+        assert!(invalid("ticket", 2).is_err());
+        assert_eq!(PrintCopies::parse(Some("not json")), PrintCopies::default());
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+use crate::printer::PrintJobType;
+
+// When the print queue holds jobs back instead of sending them. A printer in maintenance
+// (paper jam, roll change, cleaning) keeps every job until it is switched back on, or until
+// PRINTER_MAINTENANCE_MINUTES have passed so a forgotten toggle cannot stall the station.
+// During quiet hours reports wait and all print together when the quiet hours end. Tickets
+// are never held by quiet hours. The quiet-hours schedule is kept in print_schedule.json.
+
+pub const DEFAULT_MAINTENANCE_MINUTES: u64 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    pub enabled: bool,
+    pub start: NaiveTime,
+    pub end: NaiveTime, // held reports print at this time
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        QuietHours {
+            enabled: false,
+            start: NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        }
+    }
+}
+
+impl QuietHours {
+    /// Whether `time` (Tunis) falls in the quiet hours; a window may run past midnight
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if !self.enabled || self.start == self.end {
+            return false;
+        }
+        if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HoldReason {
+    Maintenance,
+    QuietHours,
+}
+
+/// Reports can wait for the end of the quiet hours; tickets and slips are handed out on the spot
+pub fn is_deferrable(job_type: &PrintJobType) -> bool {
+    matches!(job_type, PrintJobType::DayPassSummary)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrinterMaintenance {
+    pub printer_id: String,
+    pub until: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintScheduleDto {
+    pub quiet_hours: QuietHours,
+    pub maintenance: Vec<PrinterMaintenance>,
+    pub held_jobs: usize,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    quiet_hours: QuietHours,
+    maintenance: HashMap<String, DateTime<Utc>>, // printer id -> automatic resume
+    held_jobs: usize,
+}
+
+impl State {
+    fn hold_reason(&self, job_type: &PrintJobType, printer_id: &str, now: DateTime<Utc>) -> Option<HoldReason> {
+        if self.maintenance.get(printer_id).is_some_and(|until| now < *until) {
+            return Some(HoldReason::Maintenance);
+        }
+        let local = now.with_timezone(&crate::time::TZ).time();
+        (is_deferrable(job_type) && self.quiet_hours.contains(local)).then_some(HoldReason::QuietHours)
+    }
+}
+
+/// Shared by the printer service and the queue processor; `changed` wakes the processor so
+/// held jobs print as soon as a printer is back
+pub struct PrintSchedule {
+    state: Mutex<State>,
+    path: PathBuf,
+    maintenance_minutes: i64,
+    pub changed: Notify,
+}
+
+impl PrintSchedule {
+    pub fn load(path: PathBuf, maintenance_minutes: u64) -> Self {
+        let quiet_hours = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        PrintSchedule {
+            state: Mutex::new(State { quiet_hours, ..Default::default() }),
+            path,
+            maintenance_minutes: maintenance_minutes.max(1) as i64,
+            changed: Notify::new(),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn hold_reason(&self, job_type: &PrintJobType, printer_id: &str) -> Option<HoldReason> {
+        self.state().hold_reason(job_type, printer_id, crate::clock::now())
+    }
+
+    pub fn set_maintenance(&self, printer_id: &str, on: bool) {
+        {
+            let mut state = self.state();
+            if on {
+                let until = crate::clock::now() + chrono::Duration::minutes(self.maintenance_minutes);
+                state.maintenance.insert(printer_id.to_string(), until);
+            } else {
+                state.maintenance.remove(printer_id);
+            }
+        }
+        println!("🛠️ [QUEUE] Printer {} maintenance {}", printer_id, if on { "on" } else { "off" });
+        self.changed.notify_one();
+    }
+
+    pub fn set_quiet_hours(&self, quiet_hours: QuietHours) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(&quiet_hours).map_err(|e| e.to_string())?;
+        fs::write(&self.path, content).map_err(|e| format!("Failed to save {:?}: {}", self.path, e))?;
+        self.state().quiet_hours = quiet_hours;
+        self.changed.notify_one();
+        Ok(())
+    }
+
+    pub fn set_held_jobs(&self, count: usize) {
+        self.state().held_jobs = count;
+    }
+
+    pub fn snapshot(&self) -> PrintScheduleDto {
+        let mut state = self.state();
+        let now = crate::clock::now();
+        state.maintenance.retain(|_, until| *until > now);
+        let mut maintenance: Vec<PrinterMaintenance> = state
+            .maintenance
+            .iter()
+            .map(|(printer_id, until)| PrinterMaintenance { printer_id: printer_id.clone(), until: *until })
+            .collect();
+        maintenance.sort_by(|a, b| a.printer_id.cmp(&b.printer_id));
+        PrintScheduleDto { quiet_hours: state.quiet_hours, maintenance, held_jobs: state.held_jobs }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_may_run_past_midnight() {
+        let night = QuietHours { enabled: true, start: at(22, 0), end: at(6, 30) };
+        assert!(night.contains(at(23, 15)));
+        assert!(night.contains(at(6, 29)));
+        assert!(!night.contains(at(6, 30)));
+        assert!(!night.contains(at(12, 0)));
+        let morning = QuietHours { enabled: true, start: at(6, 0), end: at(9, 0) };
+        assert!(morning.contains(at(6, 0)) && !morning.contains(at(9, 0)));
+        assert!(!QuietHours { enabled: false, ..morning }.contains(at(7, 0)));
+    }
+
+    #[test]
+    fn maintenance_holds_everything_until_it_expires_and_quiet_hours_only_reports() {
+        let now = Utc::now();
+        let mut state = State::default();
+        state.maintenance.insert("p1".to_string(), now + chrono::Duration::minutes(5));
+        assert_eq!(state.hold_reason(&PrintJobType::BookingTicket, "p1", now), Some(HoldReason::Maintenance));
+        assert_eq!(state.hold_reason(&PrintJobType::BookingTicket, "p2", now), None);
+        assert_eq!(state.hold_reason(&PrintJobType::BookingTicket, "p1", now + chrono::Duration::minutes(6)), None);
+
+        state.quiet_hours = QuietHours { enabled: true, start: at(0, 0), end: at(23, 59) };
+        let local_noon = crate::time::from_tunis_local(crate::time::tunis_today().and_time(at(12, 0))).with_timezone(&Utc);
+        assert_eq!(state.hold_reason(&PrintJobType::DayPassSummary, "p2", local_noon), Some(HoldReason::QuietHours));
+        assert_eq!(state.hold_reason(&PrintJobType::ExitTicket, "p2", local_noon), None);
+    }
+}
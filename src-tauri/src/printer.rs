@@ -1,13 +1,182 @@
     use serde::{Deserialize, Serialize};
-use std::process::Command;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use reqwest::Client;
 use std::time::Duration;
 use std::fs;
 use std::path::PathBuf;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch, Mutex, RwLock};
 use tokio::task;
 use std::collections::VecDeque;
+use crate::spooler;
+use crate::encoding::{self, CodePage};
+use crate::i18n::TicketLanguage;
+use crate::mock_transport::{MockTransport, PreviewLine};
+use crate::print_schedule::{HoldReason, PrintSchedule, PrintScheduleDto, QuietHours};
+use crate::money::Money;
+use crate::payments::method_label;
+use crate::tickets::{BookingTicket, CancellationSlip, CashVarianceSlip, DayPassStatus, DayPassTicket, EntryTicket, ExitPassTicket, ExpenseVoucher, QueueBoard, SettlementSlip, TicketPayload};
+
+/// How ESC/POS bytes reach the printer
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PrinterBackend {
+    #[default]
+    Network, // Raw TCP socket (ip:port)
+    Spooler, // Windows driver queue (system_name), RAW datatype
+}
+
+impl PrinterBackend {
+    fn from_env_value(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "spooler" | "windows" | "system" => PrinterBackend::Spooler,
+            _ => PrinterBackend::Network,
+        }
+    }
+}
+
+/// How the paper is cut at the end of a ticket
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CutType {
+    Full,    // GS V 0
+    Partial, // GS V 1
+}
+
+/// Paper roll characteristics the ticket builders adapt to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct PaperProfile {
+    pub chars_per_line: u8,
+    pub feed_lines: u8, // blank lines fed before the cut
+    pub cut: CutType,
+}
+
+impl PaperProfile {
+    /// 80mm roll (TM-T20 family), Font A
+    pub fn mm80() -> Self {
+        Self { chars_per_line: 48, feed_lines: 3, cut: CutType::Full }
+    }
+
+    /// 58mm roll (mobile printers), Font A
+    pub fn mm58() -> Self {
+        Self { chars_per_line: 32, feed_lines: 4, cut: CutType::Partial }
+    }
+
+    fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "80" | "80mm" => Some(Self::mm80()),
+            "58" | "58mm" => Some(Self::mm58()),
+            _ => None,
+        }
+    }
+
+    /// Profile for configs saved before paper profiles existed, inferred from `width`
+    fn from_width(width: u8) -> Self {
+        if width <= 32 {
+            Self { chars_per_line: width, ..Self::mm58() }
+        } else {
+            Self { chars_per_line: width, ..Self::mm80() }
+        }
+    }
+}
+
+/// Control sequences and limits of a printer model, picked by `PrinterConfig.model`
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelProfile {
+    pub model: &'static str,
+    #[serde(skip)]
+    aliases: &'static [&'static str],
+    pub init: &'static [u8],
+    pub full_cut: &'static [u8],  // empty when the model has no cutter
+    pub partial_cut: &'static [u8],
+    pub drawer_kick: &'static [u8], // empty when the model has no drawer port
+    pub code_pages: &'static [CodePage],
+    pub max_width: u8,
+}
+
+const MODEL_PROFILES: &[ModelProfile] = &[
+    // Epson, 80mm; ESC p pulses of 160 ms
+    ModelProfile {
+        model: "TM-T20X",
+        aliases: &["tm-t20", "tm-t20ii", "tm-t20iii", "epson"],
+        init: &[0x1B, 0x40],
+        full_cut: &[0x1D, 0x56, 0x00],
+        partial_cut: &[0x1D, 0x56, 0x01],
+        drawer_kick: &[0x1B, 0x70, 0x00, 0x50, 0x50],
+        code_pages: &[CodePage::Cp437, CodePage::Cp850, CodePage::Cp858],
+        max_width: 48,
+    },
+    // Rongta, 80mm; GS V 65/66 feed to the blade before cutting, the drawer wants shorter pulses
+    ModelProfile {
+        model: "RP-326",
+        aliases: &["rp326", "rongta"],
+        init: &[0x1B, 0x40],
+        full_cut: &[0x1D, 0x56, 0x41, 0x00],
+        partial_cut: &[0x1D, 0x56, 0x42, 0x00],
+        drawer_kick: &[0x1B, 0x70, 0x00, 0x19, 0xFA],
+        code_pages: &[CodePage::Cp437, CodePage::Cp850, CodePage::Cp858],
+        max_width: 48,
+    },
+    // Unbranded 58mm printers: no cutter, no drawer port, no Euro code page
+    ModelProfile {
+        model: "GENERIC-58",
+        aliases: &["58mm", "pos-58", "generic 58mm"],
+        init: &[0x1B, 0x40],
+        full_cut: &[],
+        partial_cut: &[],
+        drawer_kick: &[],
+        code_pages: &[CodePage::Cp437, CodePage::Cp850],
+        max_width: 32,
+    },
+    // Any other 80mm ESC/POS printer
+    ModelProfile {
+        model: "GENERIC-80",
+        aliases: &["80mm", "pos-80", "generic", "generic 80mm"],
+        init: &[0x1B, 0x40],
+        full_cut: &[0x1D, 0x56, 0x00],
+        partial_cut: &[0x1D, 0x56, 0x01],
+        drawer_kick: &[0x1B, 0x70, 0x00, 0x50, 0x50],
+        code_pages: &[CodePage::Cp437, CodePage::Cp850, CodePage::Cp858],
+        max_width: 48,
+    },
+];
+
+impl ModelProfile {
+    pub fn all() -> &'static [ModelProfile] {
+        MODEL_PROFILES
+    }
+
+    /// Profile of a model name or alias, case-insensitive; unknown models get the generic 80mm one
+    pub fn for_model(model: &str) -> &'static ModelProfile {
+        let wanted = model.trim().to_lowercase();
+        MODEL_PROFILES
+            .iter()
+            .find(|p| p.model.to_lowercase() == wanted || p.aliases.contains(&wanted.as_str()))
+            .unwrap_or_else(Self::generic)
+    }
+
+    fn generic() -> &'static ModelProfile {
+        &MODEL_PROFILES[MODEL_PROFILES.len() - 1]
+    }
+
+    pub fn cut_command(&self, cut: CutType) -> &'static [u8] {
+        match cut {
+            CutType::Full => self.full_cut,
+            CutType::Partial if self.partial_cut.is_empty() => self.full_cut,
+            CutType::Partial => self.partial_cut,
+        }
+    }
+
+    /// The configured code page when the model has it, else the model's first
+    pub fn code_page(&self, configured: CodePage) -> CodePage {
+        if self.code_pages.contains(&configured) {
+            configured
+        } else {
+            self.code_pages[0]
+        }
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PrinterConfig {
@@ -20,6 +189,38 @@ pub struct PrinterConfig {
     pub model: String,
     pub enabled: bool,
     pub is_default: bool,
+    #[serde(default)]
+    pub backend: PrinterBackend,
+    #[serde(default)]
+    pub system_name: Option<String>, // Windows printer name, used by the spooler backend
+    #[serde(default)]
+    pub paper: Option<PaperProfile>,
+    #[serde(default)]
+    pub code_page: CodePage,
+    /// Backup printers tried in order when this one fails; their own backups are not used
+    #[serde(default)]
+    pub failover: Vec<PrinterConfig>,
+}
+
+impl PrinterConfig {
+    /// This printer, then its enabled backups: where a queued job is tried, in order
+    pub fn failover_chain(&self) -> Vec<&PrinterConfig> {
+        std::iter::once(self).chain(self.failover.iter().filter(|p| p.enabled)).collect()
+    }
+
+    /// Active paper profile; falls back to one matching `width` when none is set
+    pub fn paper_profile(&self) -> PaperProfile {
+        self.paper.unwrap_or_else(|| PaperProfile::from_width(self.width))
+    }
+
+    pub fn model_profile(&self) -> &'static ModelProfile {
+        ModelProfile::for_model(&self.model)
+    }
+
+    /// Code page the bytes are converted to: the configured one if the model has it
+    pub fn output_code_page(&self) -> CodePage {
+        self.model_profile().code_page(self.code_page)
+    }
 }
 
 
@@ -40,7 +241,15 @@ pub struct PrinterStatus {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Raw status read back from the printer (DLE EOT / GS I), None when not reported
+#[derive(Debug, Default)]
+struct PrinterStatusReport {
+    online: Option<bool>,
+    paper_ok: Option<bool>,
+    firmware: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StaffInfo {
     pub id: String,
     pub cin: String,
@@ -61,6 +270,51 @@ pub enum PrintJobType {
     StandardTicket,
     Receipt,
     QRCode,
+    DayPassSummary,
+    SettlementSlip,
+    ExpenseVoucher,
+    CashVarianceSlip,
+    QueueBoard,
+    CancellationSlip,
+}
+
+impl PrintJobType {
+    /// Accept both the variant name ("BookingTicket") and short kinds ("booking", "day_pass")
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        let normalized: String = kind
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "bookingticket" | "booking" => Some(PrintJobType::BookingTicket),
+            "entryticket" | "entry" => Some(PrintJobType::EntryTicket),
+            "exitticket" | "exit" => Some(PrintJobType::ExitTicket),
+            "daypassticket" | "daypass" => Some(PrintJobType::DayPassTicket),
+            "exitpassticket" | "exitpass" => Some(PrintJobType::ExitPassTicket),
+            "talon" => Some(PrintJobType::Talon),
+            "standardticket" | "standard" => Some(PrintJobType::StandardTicket),
+            "receipt" => Some(PrintJobType::Receipt),
+            "qrcode" | "qr" => Some(PrintJobType::QRCode),
+            "daypasssummary" | "daypassreport" => Some(PrintJobType::DayPassSummary),
+            "settlementslip" | "settlement" => Some(PrintJobType::SettlementSlip),
+            "expensevoucher" | "expense" => Some(PrintJobType::ExpenseVoucher),
+            "cashvarianceslip" | "cashvariance" => Some(PrintJobType::CashVarianceSlip),
+            "queueboard" | "board" => Some(PrintJobType::QueueBoard),
+            "cancellationslip" | "cancellation" => Some(PrintJobType::CancellationSlip),
+            _ => None,
+        }
+    }
+}
+
+/// Ticket rendered by the print pipeline without being sent to the printer
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TicketPreview {
+    pub kind: PrintJobType,
+    pub width: u8, // characters per line, render with a monospace font
+    pub monospace: bool,
+    pub lines: Vec<PreviewLine>,
+    pub text: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -72,6 +326,72 @@ pub struct QueuedPrintJob {
     pub priority: u8, // 0 = highest priority, 255 = lowest
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub retry_count: u8,
+    /// Reprint number of the ticket, printed as a DUPLICATA banner (reprints.rs)
+    #[serde(default)]
+    pub duplicate: Option<u32>,
+    /// Printer the job goes to instead of this terminal's (the self check-in gate printer)
+    #[serde(default)]
+    pub printer: Option<PrinterConfig>,
+    /// The archived ticket this job prints out of the escrow (ticket_escrow.rs)
+    #[serde(default)]
+    pub escrow_id: Option<String>,
+}
+
+impl QueuedPrintJob {
+    pub fn new(job_type: PrintJobType, content: String, staff_name: Option<String>) -> Self {
+        QueuedPrintJob {
+            id: uuid::Uuid::new_v4().to_string(),
+            job_type,
+            content,
+            staff_name,
+            priority: 0,
+            created_at: chrono::Utc::now(),
+            retry_count: 0,
+            duplicate: None,
+            printer: None,
+            escrow_id: None,
+        }
+    }
+}
+
+/// Where a queued job printed and the text that came out, for the ticket archive
+pub struct PrintedJob {
+    pub printer_id: String,
+    pub printer_name: String,
+    pub text: String,
+    pub width: u8,
+}
+
+/// Jobs that print back to back: the processor takes a batch whole, so another cashier's job
+/// never lands between a group's tickets. A single job is a batch of one, and isn't reported.
+struct PrintBatch {
+    id: String,
+    jobs: Vec<QueuedPrintJob>,
+    reported: bool,
+}
+
+impl PrintBatch {
+    // Held as a whole while any of its jobs is held
+    fn hold_reason(&self, schedule: &PrintSchedule, printer_id: &str) -> Option<HoldReason> {
+        self.jobs.iter().find_map(|job| schedule.hold_reason(&job.job_type, printer_id))
+    }
+}
+
+/// How a batch went, once its last job has been tried
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintBatchReport {
+    pub batch_id: String,
+    pub printed: Vec<String>,
+    pub failed: Vec<String>,
+    /// The first failure, for the cashier
+    pub error: Option<String>,
+}
+
+impl PrintBatchReport {
+    pub fn succeeded(&self) -> bool {
+        self.failed.is_empty()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -80,21 +400,359 @@ pub struct PrintQueueStatus {
     pub is_processing: bool,
     pub last_printed_at: Option<chrono::DateTime<chrono::Utc>>,
     pub failed_jobs: usize,
+    pub backlog: usize,
+    pub capacity: usize,
+}
+
+// Jobs waiting for the printer beyond this are refused (PRINTER_QUEUE_CAPACITY overrides it)
+const DEFAULT_PRINT_QUEUE_CAPACITY: u64 = 50;
+
+/// Jobs accepted but not printed yet, pushed to the UI whenever it changes
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PrintBacklog {
+    pub depth: usize,
+    pub capacity: usize,
+    pub full: bool,
+}
+
+// Paper use is estimated from the line feeds sent: one text line is 1/6 inch at the default
+// line spacing. Roll length and the low-paper threshold come from PRINTER_ROLL_LENGTH_M and
+// PRINTER_PAPER_LOW_M; a roll length entered when changing the roll overrides the default.
+const LINE_HEIGHT_MM: f64 = 25.4 / 6.0;
+const DEFAULT_ROLL_LENGTH_M: u64 = 80;
+const DEFAULT_PAPER_LOW_M: u64 = 5;
+const USAGE_FILE: &str = "printer_usage.json";
+const USAGE_DAYS_KEPT: i64 = 31;
+
+const SCHEDULE_FILE: &str = "print_schedule.json";
+// How often held jobs are looked at again, for maintenance that expires and quiet hours that end
+const HOLD_CHECK_SECS: u64 = 30;
+
+/// What was sent to one printer during one Tunis day
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DailyUsage {
+    pub jobs: u64,
+    pub bytes: u64,
+    pub lines: u64,
+    pub cuts: u64,
+}
+
+impl DailyUsage {
+    /// Counts of one job, from the ESC/POS bytes built for it (line feeds and GS V cuts)
+    pub fn of_job(bytes: &[u8]) -> Self {
+        DailyUsage {
+            jobs: 1,
+            bytes: bytes.len() as u64,
+            lines: bytes.iter().filter(|b| **b == b'\n').count() as u64,
+            cuts: bytes.windows(2).filter(|w| *w == [0x1D, 0x56]).count() as u64,
+        }
+    }
+
+    fn add(&mut self, other: &DailyUsage) {
+        self.jobs += other.jobs;
+        self.bytes += other.bytes;
+        self.lines += other.lines;
+        self.cuts += other.cuts;
+    }
+}
+
+/// Usage history and current roll of one printer, kept in printer_usage.json
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrinterUsageRecord {
+    pub roll_length_m: Option<f64>, // None: PRINTER_ROLL_LENGTH_M
+    pub used_mm: f64,
+    pub roll_changed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub low_alerted: bool,
+    pub days: std::collections::BTreeMap<chrono::NaiveDate, DailyUsage>,
+}
+
+impl PrinterUsageRecord {
+    pub fn record(&mut self, day: chrono::NaiveDate, usage: &DailyUsage) {
+        self.days.entry(day).or_default().add(usage);
+        self.used_mm += usage.lines as f64 * LINE_HEIGHT_MM;
+        let oldest = day - chrono::Duration::days(USAGE_DAYS_KEPT);
+        self.days.retain(|d, _| *d > oldest);
+    }
+
+    pub fn stats(&self, printer_id: &str, default_roll_m: f64, low_threshold_m: f64) -> PrinterUsageStats {
+        let roll_length_m = self.roll_length_m.unwrap_or(default_roll_m);
+        let used_m = self.used_mm / 1000.0;
+        let remaining_m = (roll_length_m - used_m).max(0.0);
+        PrinterUsageStats {
+            printer_id: printer_id.to_string(),
+            days: self.days.iter().rev().map(|(date, usage)| DayUsage { date: *date, usage: *usage }).collect(),
+            roll_length_m,
+            used_m,
+            remaining_m,
+            low_threshold_m,
+            paper_low: remaining_m < low_threshold_m,
+            roll_changed_at: self.roll_changed_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DayUsage {
+    pub date: chrono::NaiveDate,
+    #[serde(flatten)]
+    pub usage: DailyUsage,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrinterUsageStats {
+    pub printer_id: String,
+    pub days: Vec<DayUsage>, // most recent first
+    pub roll_length_m: f64,
+    pub used_m: f64,
+    pub remaining_m: f64, // estimate
+    pub low_threshold_m: f64,
+    pub paper_low: bool,
+    pub roll_changed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Usage counters of every printer, saved after each job; a roll running low is announced
+/// once on `paper_alerts` until a new roll is loaded
+pub struct PrinterUsage {
+    records: std::sync::Mutex<std::collections::HashMap<String, PrinterUsageRecord>>,
+    path: PathBuf,
+    default_roll_m: f64,
+    low_threshold_m: f64,
+    paper_alerts: watch::Sender<Option<PrinterUsageStats>>,
+}
+
+impl PrinterUsage {
+    fn load(path: PathBuf, default_roll_m: f64, low_threshold_m: f64) -> Self {
+        let records = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let (paper_alerts, _) = watch::channel(None);
+        PrinterUsage { records: std::sync::Mutex::new(records), path, default_roll_m, low_threshold_m, paper_alerts }
+    }
+
+    fn update<T>(&self, printer_id: &str, f: impl FnOnce(&mut PrinterUsageRecord) -> T) -> Option<T> {
+        let mut records = self.records.lock().ok()?;
+        let result = f(records.entry(printer_id.to_string()).or_default());
+        if let Ok(content) = serde_json::to_string_pretty(&*records) {
+            if let Err(e) = fs::write(&self.path, content) {
+                println!("⚠️ [USAGE] Failed to save {:?}: {}", self.path, e);
+            }
+        }
+        Some(result)
+    }
+
+    /// Count a job the printer accepted
+    pub fn record(&self, printer_id: &str, bytes: &[u8]) {
+        let usage = DailyUsage::of_job(bytes);
+        let (default_roll_m, low_threshold_m) = (self.default_roll_m, self.low_threshold_m);
+        let alert = self.update(printer_id, |record| {
+            record.record(crate::time::tunis_today(), &usage);
+            let stats = record.stats(printer_id, default_roll_m, low_threshold_m);
+            let first_alert = stats.paper_low && !record.low_alerted;
+            record.low_alerted = stats.paper_low;
+            first_alert.then_some(stats)
+        });
+        if let Some(Some(stats)) = alert {
+            println!("📜 [USAGE] Paper low on {}: about {:.1} m left", printer_id, stats.remaining_m);
+            self.paper_alerts.send_replace(Some(stats));
+        }
+    }
+
+    pub fn stats(&self, printer_id: &str) -> PrinterUsageStats {
+        let record = self.records.lock().ok().and_then(|r| r.get(printer_id).cloned()).unwrap_or_default();
+        record.stats(printer_id, self.default_roll_m, self.low_threshold_m)
+    }
+
+    /// A new roll was loaded: start counting its paper from zero
+    pub fn new_roll(&self, printer_id: &str, roll_length_m: Option<f64>) -> Result<PrinterUsageStats, String> {
+        if let Some(length) = roll_length_m {
+            if !length.is_finite() || length <= 0.0 {
+                return Err(crate::i18n::tf("error.invalid_roll_length", &[("length", &length)]));
+            }
+        }
+        self.update(printer_id, |record| {
+            record.roll_length_m = roll_length_m;
+            record.used_mm = 0.0;
+            record.low_alerted = false;
+            record.roll_changed_at = Some(chrono::Utc::now());
+        });
+        Ok(self.stats(printer_id))
+    }
+}
+
+/// Width-aware text layout for fixed-pitch receipt paper (one column per character)
+#[derive(Debug, Clone, Copy)]
+pub struct TextLayout {
+    width: usize,
+    profile: PaperProfile,
+    model: &'static ModelProfile,
+    language: TicketLanguage,
+}
+
+impl TextLayout {
+    /// The paper's columns, no more than the model prints
+    pub fn for_model(profile: PaperProfile, model: &'static ModelProfile) -> Self {
+        let width = profile.chars_per_line.min(model.max_width);
+        Self { width: (width as usize).max(16), profile, model, language: crate::i18n::ticket_language() }
+    }
+
+    pub fn for_printer(config: &PrinterConfig) -> Self {
+        Self::for_model(config.paper_profile(), config.model_profile())
+    }
+
+    /// Same paper in Font B (ESC M 1), which fits a third more columns
+    pub fn condensed(&self) -> Self {
+        Self { width: self.width * 4 / 3, ..*self }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn profile(&self) -> &PaperProfile {
+        &self.profile
+    }
+
+    pub fn model(&self) -> &'static ModelProfile {
+        self.model
+    }
+
+    pub fn language(&self) -> &TicketLanguage {
+        &self.language
+    }
+
+    /// Printed columns of a string; combining marks (accents, Arabic harakat) take none
+    /// and lam-alef prints as a single glyph
+    pub fn display_width(text: &str) -> usize {
+        let mut width = 0;
+        let mut after_lam = false;
+        for c in text.chars() {
+            let cp = c as u32;
+            if matches!(cp, 0x0300..=0x036F | 0x0610..=0x061A | 0x064B..=0x065F | 0x0670 | 0x06D6..=0x06ED) {
+                continue;
+            }
+            if !(after_lam && matches!(c, 'ا' | 'أ' | 'إ' | 'آ')) {
+                width += 1;
+            }
+            after_lam = c == 'ل';
+        }
+        width
+    }
+
+    /// Word-wrap text to the paper width, keeping explicit line breaks and splitting overlong words
+    pub fn wrap(&self, text: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        for raw_line in text.split('\n') {
+            // Lines that already fit are kept verbatim so hand-aligned content survives
+            if Self::display_width(raw_line) <= self.width {
+                lines.push(raw_line.to_string());
+                continue;
+            }
+            let mut current = String::new();
+            for word in raw_line.split_whitespace() {
+                let mut word = word.to_string();
+                while Self::display_width(&word) > self.width {
+                    if !current.is_empty() {
+                        lines.push(std::mem::take(&mut current));
+                    }
+                    let head: String = word.chars().take(self.width).collect();
+                    word = word.chars().skip(self.width).collect();
+                    lines.push(head);
+                }
+                if word.is_empty() {
+                    continue;
+                }
+                if current.is_empty() {
+                    current = word;
+                } else if Self::display_width(&current) + 1 + Self::display_width(&word) <= self.width {
+                    current.push(' ');
+                    current.push_str(&word);
+                } else {
+                    lines.push(std::mem::replace(&mut current, word));
+                }
+            }
+            lines.push(current);
+        }
+        lines
+    }
+
+    pub fn pad_left(&self, text: &str) -> String {
+        let pad = self.width.saturating_sub(Self::display_width(text));
+        format!("{}{}", " ".repeat(pad), text)
+    }
+
+    /// Two-column row: label on the left, value flush right; wraps when both do not fit
+    pub fn row(&self, left: &str, right: &str) -> Vec<String> {
+        let left_width = Self::display_width(left);
+        let right_width = Self::display_width(right);
+        if left_width + 1 + right_width <= self.width {
+            let gap = self.width - left_width - right_width;
+            return vec![format!("{}{}{}", left, " ".repeat(gap), right)];
+        }
+        let mut lines = self.wrap(left);
+        lines.extend(self.wrap(right).iter().map(|line| self.pad_left(line)));
+        lines
+    }
+
+    /// Full-width rule made of `fill`
+    pub fn separator(&self, fill: char) -> String {
+        fill.to_string().repeat(self.width)
+    }
+
+    /// Rule with a centered title, e.g. "----- TARIFICATION -----"
+    pub fn centered_separator(&self, title: &str, fill: char) -> String {
+        let title_width = Self::display_width(title) + 2;
+        if title_width >= self.width {
+            return title.to_string();
+        }
+        let left = (self.width - title_width) / 2;
+        let right = self.width - title_width - left;
+        format!("{} {} {}", fill.to_string().repeat(left), title, fill.to_string().repeat(right))
+    }
+}
+
+fn push_line(data: &mut Vec<u8>, line: &str) {
+    data.extend_from_slice(line.as_bytes());
+    data.push(b'\n');
+}
+
+fn push_lines(data: &mut Vec<u8>, lines: &[String]) {
+    for line in lines {
+        push_line(data, line);
+    }
+}
+
+// Placeholder for ticket fields left empty
+fn dash(value: &str) -> &str {
+    if value.is_empty() { "-" } else { value }
 }
 
+/// Cheap handle to the printer: clones share the same configuration, caches and queue.
+/// Locks are tokio locks and are never held across network I/O.
 #[derive(Clone)]
 pub struct PrinterService {
-    printer_config: Arc<Mutex<PrinterConfig>>,
+    printer_config: Arc<RwLock<PrinterConfig>>,
     node_script_path: String,
     // Cache last printed payloads for reprint functionality
-    last_booking_payload: Arc<Mutex<Option<String>>>,
-    last_entry_payload: Arc<Mutex<Option<String>>>,
-    last_exit_payload: Arc<Mutex<Option<String>>>,
-    last_day_pass_payload: Arc<Mutex<Option<String>>>,
+    last_booking_payload: Arc<RwLock<Option<String>>>,
+    last_entry_payload: Arc<RwLock<Option<String>>>,
+    last_exit_payload: Arc<RwLock<Option<String>>>,
+    last_day_pass_payload: Arc<RwLock<Option<String>>>,
+    last_exit_pass_payload: Arc<RwLock<Option<String>>>,
     // Print queue system
     print_queue: Arc<Mutex<VecDeque<QueuedPrintJob>>>,
-    print_queue_sender: Arc<Mutex<Option<mpsc::UnboundedSender<QueuedPrintJob>>>>,
+    print_queue_sender: Arc<Mutex<Option<mpsc::Sender<PrintBatch>>>>,
     queue_status: Arc<Mutex<PrintQueueStatus>>,
+    queue_capacity: usize,
+    backlog_depth: Arc<AtomicUsize>,
+    backlog_events: Arc<watch::Sender<PrintBacklog>>,
+    batch_reports: Arc<watch::Sender<Option<PrintBatchReport>>>,
+    usage: Arc<PrinterUsage>,
+    schedule: Arc<PrintSchedule>,
 }
 
 impl PrinterService {
@@ -119,13 +777,13 @@ impl PrinterService {
     }
 
     /// Save printer configuration to file
-    fn save_config_to_file(&self) -> Result<(), String> {
-        let config = self.printer_config.lock().map_err(|e| e.to_string())?;
+    async fn save_config_to_file(&self) -> Result<(), String> {
+        let config = self.printer_config.read().await.clone();
         let config_path = Self::get_config_path();
         
         println!("💾 [CONFIG] Saving printer config to: {:?}", config_path);
         
-        let config_json = serde_json::to_string_pretty(&*config)
+        let config_json = serde_json::to_string_pretty(&config)
             .map_err(|e| format!("Failed to serialize config: {}", e))?;
         
         fs::write(&config_path, config_json)
@@ -136,7 +794,7 @@ impl PrinterService {
     }
 
     /// Load printer configuration from file
-    fn load_config_from_file(&self) -> Result<(), String> {
+    fn load_config_from_file() -> Result<Option<PrinterConfig>, String> {
         let config_path = Self::get_config_path();
         
         println!("📂 [CONFIG] Loading printer config from: {:?}", config_path);
@@ -144,7 +802,7 @@ impl PrinterService {
         
         if !config_path.exists() {
             println!("⚠️ [CONFIG] Config file does not exist, using default configuration");
-            return Ok(());
+            return Ok(None);
         }
         
         let config_content = fs::read_to_string(&config_path)
@@ -157,11 +815,8 @@ impl PrinterService {
         
         println!("📂 [CONFIG] Parsed config: IP={}, Port={}", loaded_config.ip, loaded_config.port);
         
-        let mut config = self.printer_config.lock().map_err(|e| e.to_string())?;
-        *config = loaded_config;
-        
-        println!("✅ [CONFIG] Printer configuration loaded successfully: {}:{}", config.ip, config.port);
-        Ok(())
+        println!("✅ [CONFIG] Printer configuration loaded successfully: {}:{}", loaded_config.ip, loaded_config.port);
+        Ok(Some(loaded_config))
     }
 
     // Node-based temporary scripts no longer used
@@ -211,48 +866,9 @@ impl PrinterService {
 
     // Node execution is no longer used
 
+    // Saved system values (registry, /etc/environment, launchctl) so changes apply without restart
     fn read_env_from_system(key: &str) -> Option<String> {
-        // Linux: read from system files first to reflect latest changes without restart
-        #[cfg(target_os = "linux")]
-        {
-            if let Ok(content) = fs::read_to_string("/etc/environment") {
-                for line in content.lines() {
-                    let line = line.trim();
-                    if line.is_empty() || line.starts_with('#') { continue; }
-                    // Accept KEY="value" or KEY=value
-                    if let Some((k, vraw)) = line.split_once('=') {
-                        if k.trim() == key {
-                            let mut v = vraw.trim().to_string();
-                            if v.starts_with('"') && v.ends_with('"') && v.len() >= 2 {
-                                v = v[1..v.len()-1].to_string();
-                            }
-                            if !v.is_empty() { return Some(v); }
-                        }
-                    }
-                }
-            }
-
-            // Try profile.d script format: export KEY="value"
-            if let Ok(content) = fs::read_to_string("/etc/profile.d/printer-env.sh") {
-                for line in content.lines() {
-                    let line = line.trim();
-                    if !line.starts_with("export ") { continue; }
-                    let rest = &line[7..];
-                    if let Some((k, vraw)) = rest.split_once('=') {
-                        if k.trim() == key {
-                            let mut v = vraw.trim().to_string();
-                            if v.starts_with('"') && v.ends_with('"') && v.len() >= 2 {
-                                v = v[1..v.len()-1].to_string();
-                            }
-                            if !v.is_empty() { return Some(v); }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Fallback: none
-        None
+        crate::platform::system_env(key)
     }
 
     fn read_u16_from_env(key: &str, default_val: u16) -> u16 {
@@ -273,6 +889,15 @@ impl PrinterService {
             .unwrap_or(default_val)
     }
 
+    /// PRINTER_MODE=simulate routes every job to the in-memory MockTransport
+    pub fn simulation_enabled() -> bool {
+        std::env::var("PRINTER_MODE")
+            .ok()
+            .or_else(|| Self::read_env_from_system("PRINTER_MODE"))
+            .map(|mode| mode.trim().eq_ignore_ascii_case("simulate"))
+            .unwrap_or(false)
+    }
+
     pub fn debug_env_snapshot(&self) -> std::collections::HashMap<String, String> {
         let mut map = std::collections::HashMap::new();
         let keys = [
@@ -282,6 +907,12 @@ impl PrinterService {
             "PRINTER_WIDTH",
             "PRINTER_TIMEOUT",
             "PRINTER_MODEL",
+            "PRINTER_BACKEND",
+            "PRINTER_SYSTEM_NAME",
+            "PRINTER_MODE",
+            "PRINTER_PAPER",
+            "PRINTER_CODE_PAGE",
+            "PRINTER_QUEUE_CAPACITY",
         ];
         for k in keys.iter() {
             if let Some(v) = Self::read_env_from_system(k) {
@@ -302,7 +933,7 @@ impl PrinterService {
         let printer_timeout = 10000; // Increased timeout for better reliability
         let printer_model = "TM-T20X".to_string();
 
-        let printer_config = PrinterConfig {
+        let mut printer_config = PrinterConfig {
             id: "printer1".to_string(),
             name: printer_name,
             ip: printer_ip,
@@ -312,45 +943,79 @@ impl PrinterService {
             model: printer_model,
             enabled: true,
             is_default: true,
+            backend: PrinterBackend::Network,
+            system_name: None,
+            paper: None,
+            code_page: CodePage::default(),
+            failover: Vec::new(),
         };
 
         println!("🔧 [CONFIG] Created default config: IP={}, Port={}", printer_config.ip, printer_config.port);
 
+        let queue_capacity = Self::read_u64_from_env("PRINTER_QUEUE_CAPACITY", DEFAULT_PRINT_QUEUE_CAPACITY).max(1) as usize;
+
         // Initialize print queue status
         let queue_status = PrintQueueStatus {
             queue_length: 0,
             is_processing: false,
             last_printed_at: None,
             failed_jobs: 0,
+            backlog: 0,
+            capacity: queue_capacity,
         };
+        let (backlog_events, _) = watch::channel(PrintBacklog {
+            depth: 0,
+            capacity: queue_capacity,
+            full: false,
+        });
 
-        let service = Self {
-            printer_config: Arc::new(Mutex::new(printer_config)),
-            node_script_path: "scripts/printer.js".to_string(),
-            last_booking_payload: Arc::new(Mutex::new(None)),
-            last_entry_payload: Arc::new(Mutex::new(None)),
-            last_exit_payload: Arc::new(Mutex::new(None)),
-            last_day_pass_payload: Arc::new(Mutex::new(None)),
-            print_queue: Arc::new(Mutex::new(VecDeque::new())),
-            print_queue_sender: Arc::new(Mutex::new(None)),
-            queue_status: Arc::new(Mutex::new(queue_status)),
-        };
+        let (batch_reports, _) = watch::channel(None);
+
+        let usage = PrinterUsage::load(
+            Self::get_config_path().with_file_name(USAGE_FILE),
+            Self::read_u64_from_env("PRINTER_ROLL_LENGTH_M", DEFAULT_ROLL_LENGTH_M) as f64,
+            Self::read_u64_from_env("PRINTER_PAPER_LOW_M", DEFAULT_PAPER_LOW_M) as f64,
+        );
+        let schedule = PrintSchedule::load(
+            Self::get_config_path().with_file_name(SCHEDULE_FILE),
+            Self::read_u64_from_env("PRINTER_MAINTENANCE_MINUTES", crate::print_schedule::DEFAULT_MAINTENANCE_MINUTES),
+        );
 
         // Try to load configuration from file
         println!("📂 [CONFIG] Attempting to load configuration from file...");
-        if let Err(e) = service.load_config_from_file() {
-            println!("⚠️ [CONFIG] Failed to load config from file: {}. Using default configuration.", e);
-        } else {
-            println!("✅ [CONFIG] Configuration loaded successfully from file");
+        match Self::load_config_from_file() {
+            Ok(Some(loaded_config)) => {
+                printer_config = loaded_config;
+                println!("✅ [CONFIG] Configuration loaded successfully from file");
+            }
+            Ok(None) => {}
+            Err(e) => println!("⚠️ [CONFIG] Failed to load config from file: {}. Using default configuration.", e),
         }
 
         // Note: Print queue processor will be started later when runtime is available
         // service.start_print_queue_processor();
 
-        service
+        Self {
+            printer_config: Arc::new(RwLock::new(printer_config)),
+            node_script_path: "scripts/printer.js".to_string(),
+            last_booking_payload: Arc::new(RwLock::new(None)),
+            last_entry_payload: Arc::new(RwLock::new(None)),
+            last_exit_payload: Arc::new(RwLock::new(None)),
+            last_day_pass_payload: Arc::new(RwLock::new(None)),
+            last_exit_pass_payload: Arc::new(RwLock::new(None)),
+            print_queue: Arc::new(Mutex::new(VecDeque::new())),
+            print_queue_sender: Arc::new(Mutex::new(None)),
+            queue_status: Arc::new(Mutex::new(queue_status)),
+            queue_capacity,
+            backlog_depth: Arc::new(AtomicUsize::new(0)),
+            backlog_events: Arc::new(backlog_events),
+            batch_reports: Arc::new(batch_reports),
+            usage: Arc::new(usage),
+            schedule: Arc::new(schedule),
+        }
     }
 
-    pub fn reload_config_from_env(&self) -> Result<(), String> {
+    pub async fn reload_config_from_env(&self) -> Result<(), String> {
         // Reload configuration from system-level environment sources
         let printer_ip = Self::read_env_from_system("PRINTER_IP").unwrap_or_else(|| "192.168.192.10".to_string());
         let printer_port = Self::read_u16_from_env("PRINTER_PORT", 9100);
@@ -358,6 +1023,14 @@ impl PrinterService {
         let printer_width = Self::read_u8_from_env("PRINTER_WIDTH", 48);
         let printer_timeout = Self::read_u64_from_env("PRINTER_TIMEOUT", 5000);
         let printer_model = Self::read_env_from_system("PRINTER_MODEL").unwrap_or_else(|| "TM-T20X".to_string());
+        let printer_backend = Self::read_env_from_system("PRINTER_BACKEND")
+            .map(|v| PrinterBackend::from_env_value(&v))
+            .unwrap_or_default();
+        let printer_system_name = Self::read_env_from_system("PRINTER_SYSTEM_NAME");
+        let printer_paper = Self::read_env_from_system("PRINTER_PAPER").and_then(|v| PaperProfile::from_name(&v));
+        let printer_code_page = Self::read_env_from_system("PRINTER_CODE_PAGE")
+            .and_then(|v| CodePage::from_name(&v))
+            .unwrap_or_default();
 
         let new_config = PrinterConfig {
             id: "printer1".to_string(),
@@ -369,20 +1042,31 @@ impl PrinterService {
             model: printer_model,
             enabled: true,
             is_default: true,
+            backend: printer_backend,
+            system_name: printer_system_name,
+            paper: printer_paper,
+            code_page: printer_code_page,
+            failover: Vec::new(),
         };
 
-        let mut config = self.printer_config.lock().map_err(|e| e.to_string())?;
-        *config = new_config;
+        *self.printer_config.write().await = new_config;
         Ok(())
     }
 
-    pub fn get_all_printers(&self) -> Result<Vec<PrinterConfig>, String> {
-        let config = self.printer_config.lock().map_err(|e| e.to_string())?;
+    /// Names of printers installed on this machine (Windows spooler)
+    pub async fn list_system_printers(&self) -> Result<Vec<String>, String> {
+        task::spawn_blocking(spooler::list_printers)
+            .await
+            .map_err(|e| format!("Spooler task failed: {}", e))?
+    }
+
+    pub async fn get_all_printers(&self) -> Result<Vec<PrinterConfig>, String> {
+        let config = self.printer_config.read().await;
         Ok(vec![config.clone()])
     }
 
-    pub fn get_printer_by_id(&self, id: &str) -> Result<Option<PrinterConfig>, String> {
-        let config = self.printer_config.lock().map_err(|e| e.to_string())?;
+    pub async fn get_printer_by_id(&self, id: &str) -> Result<Option<PrinterConfig>, String> {
+        let config = self.printer_config.read().await;
         if config.id == id {
             Ok(Some(config.clone()))
         } else {
@@ -390,19 +1074,19 @@ impl PrinterService {
         }
     }
 
-    pub fn get_default_printer(&self) -> Result<Option<PrinterConfig>, String> {
-        let config = self.printer_config.lock().map_err(|e| e.to_string())?;
+    pub async fn get_default_printer(&self) -> Result<Option<PrinterConfig>, String> {
+        let config = self.printer_config.read().await;
         Ok(Some(config.clone()))
     }
 
-    pub fn get_current_printer(&self) -> Result<Option<PrinterConfig>, String> {
-        let config = self.printer_config.lock().map_err(|e| e.to_string())?;
+    pub async fn get_current_printer(&self) -> Result<Option<PrinterConfig>, String> {
+        let config = self.printer_config.read().await;
         println!("🔍 [DEBUG] get_current_printer returning: IP={}, Port={}", config.ip, config.port);
         Ok(Some(config.clone()))
     }
 
-    pub fn set_current_printer(&self, printer_id: &str) -> Result<(), String> {
-        let config = self.printer_config.lock().map_err(|e| e.to_string())?;
+    pub async fn set_current_printer(&self, printer_id: &str) -> Result<(), String> {
+        let config = self.printer_config.read().await;
         if config.id == printer_id {
             // Printer is already set as current
             Ok(())
@@ -411,8 +1095,8 @@ impl PrinterService {
         }
     }
 
-    pub fn update_printer_config(&self, printer_id: &str, new_config: PrinterConfig) -> Result<(), String> {
-        let mut config = self.printer_config.lock().map_err(|e| e.to_string())?;
+    pub async fn update_printer_config(&self, printer_id: &str, new_config: PrinterConfig) -> Result<(), String> {
+        let mut config = self.printer_config.write().await;
         if config.id == printer_id {
             *config = new_config;
             Ok(())
@@ -433,11 +1117,8 @@ impl PrinterService {
 
     /// Test the printer connection and set as default if working
     pub async fn auto_set_default_printer(&self) -> Result<(), String> {
-        // Clone the config to avoid holding the lock across await
-        let config = {
-            let config_guard = self.printer_config.lock().map_err(|e| e.to_string())?;
-            config_guard.clone()
-        };
+        // Clone the config to avoid holding the lock across the connection test
+        let config = self.printer_config.read().await.clone();
         
         if !config.enabled {
             println!("⚠️ Printer is disabled, skipping auto-setup");
@@ -493,105 +1174,239 @@ impl PrinterService {
     }
 
     pub async fn test_connection(&self) -> Result<PrinterStatus, String> {
-        let printer = self.get_current_printer()?;
+        let printer = self.get_current_printer().await?;
         let printer = printer.ok_or("No printer selected")?;
-        
-        // Test connection by trying to connect to the printer
-        let test_result = self.execute_print_job_with_printer(&printer, PrintJob {
-            content: "CONNECTION TEST".to_string(),
-            align: Some("center".to_string()),
-            bold: Some(true),
-            underline: None,
-            size: Some("normal".to_string()),
-            cut: Some(false),
-            open_cash_drawer: Some(false),
-        }).await;
-
-        match test_result {
-            Ok(_) => Ok(PrinterStatus {
-                connected: true,
-                error: None,
-            }),
-            Err(e) => Ok(PrinterStatus {
-                connected: false,
-                error: Some(e),
-            }),
-        }
+        Ok(Self::check_connectivity(&printer).await)
     }
 
     pub async fn test_printer_connection(&self, printer_id: &str) -> Result<PrinterStatus, String> {
-        let printer = self.get_printer_by_id(printer_id)?;
+        let printer = self.get_printer_by_id(printer_id).await?;
         let printer = printer.ok_or(format!("Printer with ID '{}' not found", printer_id))?;
-        
-        // Test connection by trying to connect to the printer
-        let test_result = self.execute_print_job_with_printer(&printer, PrintJob {
-            content: "CONNECTION TEST".to_string(),
-            align: Some("center".to_string()),
-            bold: Some(true),
-            underline: None,
-            size: Some("normal".to_string()),
-            cut: Some(false),
-            open_cash_drawer: Some(false),
-        }).await;
+        Ok(Self::check_connectivity(&printer).await)
+    }
 
-        match test_result {
-            Ok(_) => Ok(PrinterStatus {
-                connected: true,
-                error: None,
-            }),
-            Err(e) => Ok(PrinterStatus {
-                connected: false,
-                error: Some(e),
-            }),
+    /// Check that the printer is reachable and ready without printing anything
+    async fn check_connectivity(printer: &PrinterConfig) -> PrinterStatus {
+        match Self::query_printer_status(printer, false).await {
+            Ok(report) => {
+                let error = if report.online == Some(false) {
+                    Some("Imprimante hors ligne (capot ouvert ou erreur)".to_string())
+                } else if report.paper_ok == Some(false) {
+                    Some("Papier épuisé".to_string())
+                } else {
+                    None
+                };
+                PrinterStatus { connected: true, error }
+            }
+            Err(e) => PrinterStatus { connected: false, error: Some(e) },
         }
     }
 
-    pub async fn test_connection_manual(&self, ip: &str, port: u16) -> Result<PrinterStatus, String> {
-        // Create a temporary printer config for testing
-        let test_printer = PrinterConfig {
-            id: "test_printer".to_string(),
-            name: "Test Printer".to_string(),
-            ip: ip.to_string(),
-            port,
-            width: 48,
-            timeout: 5000,
-            model: "TM-T20X".to_string(),
-            enabled: true,
-            is_default: false,
-        };
-        
-        // Build a small ESC/POS test and send via TCP
-        let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]); // init
-        data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
-        data.extend_from_slice(&[0x1B, 0x45, 0x01]); // bold
-        data.extend_from_slice(b"TEST IMPRIMANTE\n");
-        data.extend_from_slice(&[0x1B, 0x45, 0x00]); // bold off
-        data.extend_from_slice(&[0x1B, 0x61, 0x00]); // left
-        data.extend_from_slice(format!("IP: {}\n", test_printer.ip).as_bytes());
-        data.extend_from_slice(format!("Port: {}\nStatus: OK\n", test_printer.port).as_bytes());
-        data.extend_from_slice(b"\n\n\n"); // Feed paper before cut
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]); // cut
-
-        match self.send_tcp_bytes(&test_printer, &data).await {
-            Ok(_) => Ok(PrinterStatus { connected: true, error: None }),
-            Err(e) => Ok(PrinterStatus { connected: false, error: Some(e) }),
+    async fn query_printer_status(printer: &PrinterConfig, include_firmware: bool) -> Result<PrinterStatusReport, String> {
+        if Self::simulation_enabled() {
+            return Ok(PrinterStatusReport {
+                online: Some(true),
+                paper_ok: Some(true),
+                firmware: Some("SIMULATION".to_string()),
+            });
+        }
+        match printer.backend {
+            PrinterBackend::Network => Self::query_printer_status_tcp(printer, include_firmware).await,
+            PrinterBackend::Spooler => {
+                // The spooler does not expose real-time status, only check the queue exists
+                let name = printer.system_name.clone().unwrap_or_default();
+                let installed = task::spawn_blocking(spooler::list_printers)
+                    .await
+                    .map_err(|e| format!("Spooler task failed: {}", e))??;
+                if installed.iter().any(|p| p.eq_ignore_ascii_case(name.trim())) {
+                    Ok(PrinterStatusReport::default())
+                } else {
+                    Err(format!("Printer '{}' is not installed on this machine", name))
+                }
+            }
         }
     }
 
-    pub fn update_config_manual(&self, ip: &str, port: u16, enabled: bool) -> Result<(), String> {
-        println!("🔧 [CONFIG] update_config_manual called with: IP={}, Port={}, Enabled={}", ip, port, enabled);
+    async fn query_printer_status_tcp(printer: &PrinterConfig, include_firmware: bool) -> Result<PrinterStatusReport, String> {
+        use tokio::net::TcpStream;
+        use tokio::io::AsyncWriteExt;
 
-        // Basic IPv4 validation
-        let ip_trimmed = ip.trim();
-        let octets: Vec<&str> = ip_trimmed.split('.').collect();
-        if octets.len() != 4 {
-            return Err("Invalid IP address format".to_string());
+        let addr = format!("{}:{}", printer.ip, printer.port);
+        let connect_timeout = Duration::from_millis(printer.timeout.max(1000));
+        let mut stream = tokio::time::timeout(connect_timeout, TcpStream::connect(&addr))
+            .await
+            .map_err(|_| format!("Connection to {} timed out", addr))?
+            .map_err(|e| format!("Failed to connect to printer at {}: {}", addr, e))?;
+
+        let mut report = PrinterStatusReport::default();
+
+        // DLE EOT 1: printer status, bit 3 set when offline
+        if let Some(b) = Self::read_status_response(&mut stream, &[0x10, 0x04, 0x01], 1).await {
+            report.online = Some(b[0] & 0x08 == 0);
         }
-        for part in octets {
-            if part.is_empty() { return Err("Invalid IP address format".to_string()); }
-            let parsed = part.parse::<u8>().map_err(|_| "Invalid IP address octet".to_string())?;
-            // parsed used to ensure 0-255 range
+        // DLE EOT 4: roll paper sensor, bits 5-6 set when paper is out
+        if let Some(b) = Self::read_status_response(&mut stream, &[0x10, 0x04, 0x04], 1).await {
+            report.paper_ok = Some(b[0] & 0x60 == 0);
+        }
+        // GS I 65: firmware version, answered as 0x5F <text> NUL
+        if include_firmware {
+            if let Some(b) = Self::read_status_response(&mut stream, &[0x1D, 0x49, 0x41], 64).await {
+                let text: Vec<u8> = b.into_iter()
+                    .skip_while(|c| *c == 0x5F)
+                    .take_while(|c| *c != 0x00)
+                    .collect();
+                let text = String::from_utf8_lossy(&text).trim().to_string();
+                if !text.is_empty() {
+                    report.firmware = Some(text);
+                }
+            }
+        }
+
+        let _ = stream.shutdown().await;
+        println!("🖨️ [STATUS] {} -> online={:?}, paper_ok={:?}, firmware={:?}", addr, report.online, report.paper_ok, report.firmware);
+        Ok(report)
+    }
+
+    /// Send a status query and wait briefly for the answer; many interfaces never reply
+    async fn read_status_response(stream: &mut tokio::net::TcpStream, query: &[u8], max_len: usize) -> Option<Vec<u8>> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        stream.write_all(query).await.ok()?;
+        let mut buf = vec![0u8; max_len];
+        let mut read = 0;
+        while read < max_len {
+            match tokio::time::timeout(Duration::from_millis(1500), stream.read(&mut buf[read..])).await {
+                Ok(Ok(0)) | Err(_) => break,
+                Ok(Ok(n)) => {
+                    read += n;
+                    // Single byte answers and NUL-terminated blocks are complete
+                    if max_len == 1 || buf[..read].contains(&0x00) {
+                        break;
+                    }
+                }
+                Ok(Err(_)) => break,
+            }
+        }
+        if read == 0 {
+            return None;
+        }
+        buf.truncate(read);
+        Some(buf)
+    }
+
+    /// Print a page with the current configuration, firmware status and a character-set sample
+    pub async fn print_diagnostic_page(&self) -> Result<String, String> {
+        let printer = self.get_current_printer().await?;
+        let printer = printer.ok_or("No printer selected")?;
+        let report = Self::query_printer_status(&printer, true).await?;
+        let data = Self::build_diagnostic_page(&printer, &report);
+        self.send_counted(&printer, &data).await
+    }
+
+    fn build_diagnostic_page(printer: &PrinterConfig, report: &PrinterStatusReport) -> Vec<u8> {
+        fn flag(value: Option<bool>) -> &'static str {
+            match value {
+                Some(true) => "OK",
+                Some(false) => "ERREUR",
+                None => "Non disponible",
+            }
+        }
+
+        let layout = TextLayout::for_printer(printer);
+        let mut data: Vec<u8> = Vec::new();
+        data.extend_from_slice(layout.model().init);
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]); // bold on
+        data.extend_from_slice(b"PAGE DE DIAGNOSTIC\n");
+        data.extend_from_slice(&[0x1B, 0x45, 0x00]); // bold off
+        push_line(&mut data, &layout.separator('='));
+
+        // Configuration
+        data.extend_from_slice(&[0x1B, 0x61, 0x00]); // left
+        data.extend_from_slice(format!("Imprimante: {}\n", printer.name).as_bytes());
+        data.extend_from_slice(format!("Modèle: {} (profil {})\n", printer.model, layout.model().model).as_bytes());
+        match printer.backend {
+            PrinterBackend::Network => {
+                data.extend_from_slice(b"Connexion: Reseau (TCP)\n");
+                data.extend_from_slice(format!("Adresse: {}:{}\n", printer.ip, printer.port).as_bytes());
+            }
+            PrinterBackend::Spooler => {
+                data.extend_from_slice(b"Connexion: Spouleur Windows\n");
+                data.extend_from_slice(format!("Nom système: {}\n", printer.system_name.clone().unwrap_or_default()).as_bytes());
+            }
+        }
+        let profile = layout.profile();
+        data.extend_from_slice(format!("Largeur: {} caractères\n", profile.chars_per_line).as_bytes());
+        data.extend_from_slice(format!("Coupe: {}\n", if profile.cut == CutType::Full { "complète" } else { "partielle" }).as_bytes());
+        data.extend_from_slice(format!("Délai: {} ms\n", printer.timeout).as_bytes());
+        data.extend_from_slice(format!("Page de code: {}\n", printer.output_code_page().label()).as_bytes());
+        push_line(&mut data, &layout.separator('='));
+
+        // Firmware status
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]);
+        data.extend_from_slice(b"ETAT IMPRIMANTE\n");
+        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
+        data.extend_from_slice(format!("En ligne: {}\n", flag(report.online)).as_bytes());
+        data.extend_from_slice(format!("Papier: {}\n", flag(report.paper_ok)).as_bytes());
+        data.extend_from_slice(format!("Firmware: {}\n", report.firmware.clone().unwrap_or_else(|| "Non disponible".to_string())).as_bytes());
+        push_line(&mut data, &layout.separator('='));
+
+        // Character set sample
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]);
+        data.extend_from_slice(b"JEU DE CARACTERES\n");
+        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
+        let ascii: Vec<u8> = (0x20u8..=0x7E).collect();
+        for line in ascii.chunks(layout.width()) {
+            data.extend_from_slice(line);
+            data.extend_from_slice(b"\n");
+        }
+        data.extend_from_slice("Accents: é è ê à â ç ù û ô î ï\n".as_bytes());
+        push_line(&mut data, &layout.separator('='));
+
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
+        let date = crate::time::ticket_ts_now();
+        data.extend_from_slice(format!("Date: {}\n", date).as_bytes());
+        Self::push_feed_and_cut(&mut data, &layout);
+        data
+    }
+
+    pub async fn test_connection_manual(&self, ip: &str, port: u16) -> Result<PrinterStatus, String> {
+        // Create a temporary printer config for testing
+        let test_printer = PrinterConfig {
+            id: "test_printer".to_string(),
+            name: "Test Printer".to_string(),
+            ip: ip.to_string(),
+            port,
+            width: 48,
+            timeout: 5000,
+            model: "TM-T20X".to_string(),
+            enabled: true,
+            is_default: false,
+            backend: PrinterBackend::Network,
+            system_name: None,
+            paper: None,
+            code_page: CodePage::default(),
+            failover: Vec::new(),
+        };
+        
+        // Reachable and ready, without printing: settings call this every time they open;
+        // print_diagnostic_page is the one that prints
+        Ok(Self::check_connectivity(&test_printer).await)
+    }
+
+    pub async fn update_config_manual(&self, ip: &str, port: u16, enabled: bool) -> Result<(), String> {
+        println!("🔧 [CONFIG] update_config_manual called with: IP={}, Port={}, Enabled={}", ip, port, enabled);
+
+        // Basic IPv4 validation
+        let ip_trimmed = ip.trim();
+        let octets: Vec<&str> = ip_trimmed.split('.').collect();
+        if octets.len() != 4 {
+            return Err("Invalid IP address format".to_string());
+        }
+        for part in octets {
+            if part.is_empty() { return Err("Invalid IP address format".to_string()); }
+            let parsed = part.parse::<u8>().map_err(|_| "Invalid IP address octet".to_string())?;
+            // parsed used to ensure 0-255 range
             let _ = parsed;
         }
 
@@ -600,7 +1415,7 @@ impl PrinterService {
             return Err("Invalid port (must be between 1 and 65535)".to_string());
         }
 
-        let mut config = self.printer_config.lock().map_err(|e| e.to_string())?;
+        let mut config = self.printer_config.write().await;
         config.ip = ip_trimmed.to_string();
         config.port = port;
         config.enabled = enabled;
@@ -609,45 +1424,45 @@ impl PrinterService {
 
         // Save the updated configuration to file
         drop(config); // Release the lock before calling save_config_to_file
-        self.save_config_to_file()?;
+        self.save_config_to_file().await?;
 
         println!("✅ [CONFIG] Configuration updated and saved successfully");
         Ok(())
     }
 
     /// Update printer configuration with full config object
-    pub fn update_printer_config_full(&self, new_config: PrinterConfig) -> Result<(), String> {
-        let mut config = self.printer_config.lock().map_err(|e| e.to_string())?;
+    pub async fn update_printer_config_full(&self, new_config: PrinterConfig) -> Result<(), String> {
+        let mut config = self.printer_config.write().await;
         *config = new_config;
         
         // Save the updated configuration to file
         drop(config); // Release the lock before calling save_config_to_file
-        self.save_config_to_file()?;
+        self.save_config_to_file().await?;
         
         Ok(())
     }
 
     /// Save current configuration to file (public method)
-    pub fn save_config(&self) -> Result<(), String> {
-        self.save_config_to_file()
+    pub async fn save_config(&self) -> Result<(), String> {
+        self.save_config_to_file().await
     }
 
     pub async fn execute_print_job(&self, job: PrintJob) -> Result<String, String> {
-        let printer = self.get_current_printer()?;
+        let printer = self.get_current_printer().await?;
         let printer = printer.ok_or("No printer selected")?;
         self.execute_print_job_with_printer(&printer, job).await
     }
         
     pub async fn execute_print_job_with_printer(&self, printer: &PrinterConfig, job: PrintJob) -> Result<String, String> {
-        let bytes = Self::build_escpos_from_job(&job);
-        self.send_tcp_bytes(printer, &bytes).await
+        let bytes = Self::build_escpos_from_job(&job, printer.model_profile());
+        self.send_counted(printer, &bytes).await
     }
 
     // Build minimal ESC/POS bytes for a simple text job
-    fn build_escpos_from_job(job: &PrintJob) -> Vec<u8> {
+    fn build_escpos_from_job(job: &PrintJob, model: &ModelProfile) -> Vec<u8> {
         let mut data: Vec<u8> = Vec::new();
         // Initialize
-        data.extend_from_slice(&[0x1B, 0x40]); // ESC @
+        data.extend_from_slice(model.init);
 
         // Alignment
         if let Some(align) = &job.align {
@@ -689,14 +1504,14 @@ impl PrinterService {
             if cut {
                 // Feed and cut
                 data.extend_from_slice(b"\n\n\n");
-                data.extend_from_slice(&[0x1D, 0x56, 0x00]); // GS V 0 (full cut)
+                data.extend_from_slice(model.cut_command(CutType::Full));
             }
         }
 
         if let Some(open) = job.open_cash_drawer {
             if open {
-                // Kick cash drawer: ESC p m t1 t2
-                data.extend_from_slice(&[0x1B, 0x70, 0x00, 0x50, 0x50]);
+                // Kick cash drawer: ESC p m t1 t2 on most models
+                data.extend_from_slice(model.drawer_kick);
             }
         }
 
@@ -705,18 +1520,35 @@ impl PrinterService {
 
     /// Send raw ESC/POS bytes over TCP to the configured printer
     async fn send_tcp_bytes(&self, printer: &PrinterConfig, bytes: &[u8]) -> Result<String, String> {
+        if Self::simulation_enabled() {
+            return MockTransport::get_instance().send(printer, bytes);
+        }
         use tokio::net::TcpStream;
         use tokio::io::AsyncWriteExt;
+        let bytes = encoding::encode_job(bytes, printer.output_code_page());
         let addr = format!("{}:{}", printer.ip, printer.port);
         let mut stream = TcpStream::connect(&addr)
             .await
             .map_err(|e| format!("Failed to connect to printer at {}: {}", addr, e))?;
-        stream.write_all(bytes)
+        stream.write_all(&bytes)
             .await
             .map_err(|e| format!("Failed to send print data: {}", e))?;
+        self.usage.record(&printer.id, &bytes);
         Ok("Print job completed successfully".to_string())
     }
 
+    /// Send to the printer and count the job in its usage
+    async fn send_counted(&self, printer: &PrinterConfig, bytes: &[u8]) -> Result<String, String> {
+        let mut data = Vec::new();
+        if crate::training::is_active() {
+            Self::push_training_banner(&mut data, &TextLayout::for_printer(printer));
+        }
+        data.extend_from_slice(bytes);
+        let result = Self::send_bytes_direct(printer, &data).await?;
+        self.usage.record(&printer.id, &data);
+        Ok(result)
+    }
+
     // Removed JS command generators; printing uses raw ESC/POS bytes
 
     pub async fn print_ticket(&self, content: String) -> Result<String, String> {
@@ -764,61 +1596,55 @@ impl PrinterService {
     }
 
     pub async fn print_with_logo(&self, content: String, _logo_path: String) -> Result<String, String> {
-        let printer = self.get_current_printer()?;
+        let printer = self.get_current_printer().await?;
         let printer = printer.ok_or("No printer selected")?;
+        let layout = TextLayout::for_printer(&printer);
         let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
-        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
-        data.extend_from_slice(&[0x1B, 0x45, 0x01]);
-        data.extend_from_slice(b"STE Dhraiff Services Transport\n");
-        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
-        data.extend_from_slice(b"================================\n");
-        data.extend_from_slice(&[0x1B, 0x61, 0x00]);
-        data.extend_from_slice(content.as_bytes());
-        data.extend_from_slice(b"\n");
-        data.extend_from_slice(b"================================\n");
+        Self::push_ticket_header(&mut data, &layout, None);
+        push_lines(&mut data, &layout.wrap(&content));
+        push_line(&mut data, &layout.separator('='));
         data.extend_from_slice(&[0x1B, 0x61, 0x01]);
-        let date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S");
-        data.extend_from_slice(format!("Date: {}\n", date).as_bytes());
-        data.extend_from_slice(b"\n\n\n"); // Feed paper before cut
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        self.send_tcp_bytes(&printer, &data).await
+        let date = crate::time::ticket_ts_now();
+        push_line(&mut data, &format!("Date: {}", date));
+        Self::push_feed_and_cut(&mut data, &layout); // Feed paper before cut
+        self.send_counted(&printer, &data).await
     }
 
     pub async fn print_standard_ticket(&self, content: String) -> Result<String, String> {
-        let printer = self.get_current_printer()?;
+        let printer = self.get_current_printer().await?;
         let printer = printer.ok_or("No printer selected")?;
-        let mut data: Vec<u8> = Vec::new();
-        // Init
-        data.extend_from_slice(&[0x1B, 0x40]);
-        // Header
-        data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
-        data.extend_from_slice(&[0x1B, 0x45, 0x01]); // bold on
-        data.extend_from_slice(b"STE Dhraiff Services Transport\n");
-        data.extend_from_slice(&[0x1B, 0x45, 0x00]); // bold off
-        data.extend_from_slice(b"================================\n");
-        // Content
-        data.extend_from_slice(&[0x1B, 0x61, 0x00]); // left
-        data.extend_from_slice(content.as_bytes());
-        data.extend_from_slice(b"\n");
-        // Footer
-        data.extend_from_slice(b"================================\n");
-        data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
-        let date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S");
-        data.extend_from_slice(format!("Date: {}\nMerci de votre confiance!\n", date).as_bytes());
-        data.extend_from_slice(b"\n\n\n"); // Feed paper before cut
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]); // cut
-        self.send_tcp_bytes(&printer, &data).await
+        let date = crate::time::ticket_ts_now();
+        let data = Self::build_standard_ticket_bytes(&content, &date, &TextLayout::for_printer(&printer));
+        self.send_counted(&printer, &data).await
     }
 
-    pub async fn print_booking_ticket(&self, ticket_data: String, staff_name: Option<String>) -> Result<String, String> {
+    pub async fn print_booking_ticket(&self, ticket: &BookingTicket, staff_name: Option<String>) -> Result<String, String> {
+        let payload = ticket.to_payload();
         // Cache latest payload for reprint functionality
-        if let Ok(mut cache) = self.last_booking_payload.lock() {
-            *cache = Some(ticket_data.clone());
-        }
+        *self.last_booking_payload.write().await = Some(payload.clone());
         
         // Queue the print job instead of printing directly
-        self.queue_print_job(PrintJobType::BookingTicket, ticket_data, staff_name, 0).await
+        self.queue_print_job(PrintJobType::BookingTicket, payload, staff_name, 0).await
+    }
+
+    /// A booking ticket and its talon, printed together
+    pub async fn print_booking_ticket_with_talon(&self, ticket: &BookingTicket, talon_data: String, staff_name: Option<String>) -> Result<String, String> {
+        let payload = ticket.to_payload();
+        *self.last_booking_payload.write().await = Some(payload.clone());
+        self.queue_print_batch(vec![
+            QueuedPrintJob::new(PrintJobType::BookingTicket, payload, staff_name.clone()),
+            QueuedPrintJob::new(PrintJobType::Talon, talon_data, staff_name),
+        ])
+        .await
+    }
+
+    /// Several booking tickets in one batch, such as the replacements for moved bookings
+    pub async fn print_booking_tickets(&self, tickets: &[BookingTicket], staff_name: Option<String>) -> Result<String, String> {
+        let jobs = tickets
+            .iter()
+            .map(|ticket| QueuedPrintJob::new(PrintJobType::BookingTicket, ticket.to_payload(), staff_name.clone()))
+            .collect();
+        self.queue_print_batch(jobs).await
     }
 
     pub async fn print_talon(&self, talon_data: String, staff_name: Option<String>) -> Result<String, String> {
@@ -826,637 +1652,1069 @@ impl PrinterService {
         self.queue_print_job(PrintJobType::Talon, talon_data, staff_name, 0).await
     }
 
-    pub async fn print_entry_ticket(&self, ticket_data: String, staff_name: Option<String>) -> Result<String, String> {
+    pub async fn print_entry_ticket(&self, ticket: &EntryTicket, staff_name: Option<String>) -> Result<String, String> {
+        let payload = ticket.to_payload();
         // Cache latest payload for reprint functionality
-        if let Ok(mut cache) = self.last_entry_payload.lock() {
-            *cache = Some(ticket_data.clone());
-        }
+        *self.last_entry_payload.write().await = Some(payload.clone());
         
         // Queue the print job instead of printing directly
-        self.queue_print_job(PrintJobType::EntryTicket, ticket_data, staff_name, 0).await
+        self.queue_print_job(PrintJobType::EntryTicket, payload, staff_name, 0).await
+    }
+
+    /// Queue an entry ticket on `printer` rather than this terminal's printer
+    pub async fn print_entry_ticket_at(&self, ticket: &EntryTicket, staff_name: Option<String>, printer: PrinterConfig) -> Result<String, String> {
+        let payload = ticket.to_payload();
+        *self.last_entry_payload.write().await = Some(payload.clone());
+        let job = QueuedPrintJob { printer: Some(printer), ..QueuedPrintJob::new(PrintJobType::EntryTicket, payload, staff_name) };
+        let job_id = job.id.clone();
+        self.submit(PrintBatch { id: job_id.clone(), jobs: vec![job], reported: false }).await?;
+        Ok(job_id)
     }
 
     pub async fn print_exit_ticket(&self, ticket_data: String, staff_name: Option<String>) -> Result<String, String> {
         // Cache latest payload for reprint functionality
-        if let Ok(mut cache) = self.last_exit_payload.lock() {
-            *cache = Some(ticket_data.clone());
-        }
+        *self.last_exit_payload.write().await = Some(ticket_data.clone());
         
         // Queue the print job instead of printing directly
         self.queue_print_job(PrintJobType::ExitTicket, ticket_data, staff_name, 0).await
     }
 
-    // Reprint functions using cached payloads
-    pub async fn reprint_booking_ticket(&self) -> Result<String, String> {
-        let payload_opt = self
-            .last_booking_payload
-            .lock()
-            .map_err(|e| e.to_string())?
-            .clone();
-        match payload_opt {
-            Some(payload) => self.print_booking_ticket(payload, None).await,
-            None => Err("No previous booking ticket to reprint".to_string()),
+    fn reprint_cache(&self, job_type: &PrintJobType) -> Option<&Arc<RwLock<Option<String>>>> {
+        match job_type {
+            PrintJobType::BookingTicket => Some(&self.last_booking_payload),
+            PrintJobType::EntryTicket => Some(&self.last_entry_payload),
+            PrintJobType::ExitTicket => Some(&self.last_exit_payload),
+            PrintJobType::DayPassTicket => Some(&self.last_day_pass_payload),
+            PrintJobType::ExitPassTicket => Some(&self.last_exit_pass_payload),
+            _ => None,
         }
     }
 
-    pub async fn reprint_entry_ticket(&self) -> Result<String, String> {
-        let payload_opt = self
-            .last_entry_payload
-            .lock()
-            .map_err(|e| e.to_string())?
-            .clone();
-        match payload_opt {
-            Some(payload) => self.print_entry_ticket(payload, None).await,
-            None => Err("No previous entry ticket to reprint".to_string()),
-        }
+    /// The last payload printed of a ticket kind that can be reprinted
+    pub async fn last_payload(&self, job_type: &PrintJobType) -> Option<String> {
+        self.reprint_cache(job_type)?.read().await.clone()
     }
 
-    pub async fn reprint_exit_ticket(&self) -> Result<String, String> {
-        let payload_opt = self
-            .last_exit_payload
-            .lock()
-            .map_err(|e| e.to_string())?
-            .clone();
-        match payload_opt {
-            Some(payload) => self.print_exit_ticket(payload, None).await,
-            None => Err("No previous exit ticket to reprint".to_string()),
+    /// Queue a spoiled ticket again under a fresh number; later reprints duplicate this one
+    pub async fn queue_replacement(&self, job_type: PrintJobType, payload: String) -> Result<String, String> {
+        if let Some(cache) = self.reprint_cache(&job_type) {
+            *cache.write().await = Some(payload.clone());
         }
+        self.queue_print_job(job_type, payload, None, 0).await
     }
 
-    pub async fn print_day_pass_ticket(&self, ticket_data: String, staff_name: Option<String>) -> Result<String, String> {
+    /// Queue a reprint under the "DUPLICATA n°`number`" banner
+    pub async fn queue_duplicate(&self, job_type: PrintJobType, payload: String, number: u32) -> Result<String, String> {
+        let job = QueuedPrintJob { duplicate: Some(number), ..QueuedPrintJob::new(job_type, payload, None) };
+        let job_id = job.id.clone();
+        self.submit(PrintBatch { id: job_id.clone(), jobs: vec![job], reported: false }).await?;
+        Ok(job_id)
+    }
+
+    pub async fn print_day_pass_ticket(&self, ticket: &DayPassTicket, staff_name: Option<String>) -> Result<String, String> {
+        let payload = ticket.to_payload();
         // Cache latest payload for reprint functionality
-        if let Ok(mut cache) = self.last_day_pass_payload.lock() {
-            *cache = Some(ticket_data.clone());
-        }
+        *self.last_day_pass_payload.write().await = Some(payload.clone());
         
         // Queue the print job instead of printing directly
-        self.queue_print_job(PrintJobType::DayPassTicket, ticket_data, staff_name, 0).await
+        self.queue_print_job(PrintJobType::DayPassTicket, payload, staff_name, 0).await
     }
 
-    pub async fn print_exit_pass_ticket(&self, ticket_data: String, staff_name: Option<String>) -> Result<String, String> {
+    pub async fn print_exit_pass_ticket(&self, ticket: &ExitPassTicket, staff_name: Option<String>) -> Result<String, String> {
+        let payload = ticket.to_payload();
+        // Cache latest payload for reprint functionality
+        *self.last_exit_pass_payload.write().await = Some(payload.clone());
+        
         // Queue the print job instead of printing directly
-        self.queue_print_job(PrintJobType::ExitPassTicket, ticket_data, staff_name, 0).await
+        self.queue_print_job(PrintJobType::ExitPassTicket, payload, staff_name, 0).await
+    }
+
+    /// An exit pass and the driver's settlement slip, printed together
+    pub async fn print_exit_pass_with_settlement(&self, ticket: &ExitPassTicket, slip: &SettlementSlip, staff_name: Option<String>) -> Result<String, String> {
+        let payload = ticket.to_payload();
+        *self.last_exit_pass_payload.write().await = Some(payload.clone());
+        self.queue_print_batch(vec![
+            QueuedPrintJob::new(PrintJobType::ExitPassTicket, payload, staff_name.clone()),
+            QueuedPrintJob::new(PrintJobType::SettlementSlip, slip.to_payload(), staff_name),
+        ])
+        .await
+    }
+
+    /// Several departures as one batch, each exit pass followed by its settlement slip
+    pub async fn print_exit_passes_with_settlements(&self, departures: &[(ExitPassTicket, SettlementSlip)], staff_name: Option<String>) -> Result<String, String> {
+        let mut jobs = Vec::with_capacity(departures.len() * 2);
+        for (ticket, slip) in departures {
+            let payload = ticket.to_payload();
+            *self.last_exit_pass_payload.write().await = Some(payload.clone());
+            jobs.push(QueuedPrintJob::new(PrintJobType::ExitPassTicket, payload, staff_name.clone()));
+            jobs.push(QueuedPrintJob::new(PrintJobType::SettlementSlip, slip.to_payload(), staff_name.clone()));
+        }
+        self.queue_print_batch(jobs).await
     }
 
-    pub async fn reprint_day_pass_ticket(&self) -> Result<String, String> {
-        let payload_opt = self
-            .last_day_pass_payload
-            .lock()
-            .map_err(|e| e.to_string())?
-            .clone();
-        match payload_opt {
-            Some(payload) => self.print_day_pass_ticket(payload, None).await,
-            None => Err("No previous day pass ticket to reprint".to_string()),
-        }
+    pub async fn print_day_pass_summary(&self, report_data: String, staff_name: Option<String>) -> Result<String, String> {
+        self.queue_print_job(PrintJobType::DayPassSummary, report_data, staff_name, 0).await
     }
 
-    // Direct TCP printing method for Windows (using PowerShell script)
-    pub async fn print_direct_tcp(&self, printer_id: &str, content: &str) -> Result<String, String> {
-        let config = self.get_printer_by_id(printer_id)?
-            .ok_or_else(|| format!("Printer with ID {} not found", printer_id))?;
+    pub async fn print_expense_voucher(&self, voucher: &ExpenseVoucher, staff_name: Option<String>) -> Result<String, String> {
+        self.queue_print_job(PrintJobType::ExpenseVoucher, voucher.to_payload(), staff_name, 0).await
+    }
 
-        println!("🖨️ [DIRECT TCP] Printing to {} ({}:{})", config.name, config.ip, config.port);
-        println!("🖨️ [DIRECT TCP] Content: {}", content);
+    pub async fn print_queue_board(&self, board: &QueueBoard, staff_name: Option<String>) -> Result<String, String> {
+        self.queue_print_job(PrintJobType::QueueBoard, board.to_payload(), staff_name, 0).await
+    }
 
-        // Use PowerShell script for reliable printing
-        let script_path = "scripts/simple-print.ps1";
-        let output = Command::new("powershell")
-            .args(&[
-                "-ExecutionPolicy", "Bypass",
-                "-File", script_path,
-                "-PrinterIP", &config.ip,
-                "-PrinterPort", &config.port.to_string(),
-                "-Content", content
-            ])
-            .output()
-            .map_err(|e| format!("Failed to execute PowerShell script: {}", e))?;
-
-        if output.status.success() {
-            let result = String::from_utf8_lossy(&output.stdout);
-            println!("🖨️ [DIRECT TCP] Print successful: {}", result);
-            Ok(result.to_string())
-        } else {
-            let error = String::from_utf8_lossy(&output.stderr);
-            println!("🖨️ [DIRECT TCP] Print failed: {}", error);
-            Err(format!("PowerShell print failed: {}", error))
-        }
+    pub async fn print_cash_variance_slip(&self, slip: &CashVarianceSlip, staff_name: Option<String>) -> Result<String, String> {
+        self.queue_print_job(PrintJobType::CashVarianceSlip, slip.to_payload(), staff_name, 0).await
     }
 
-    async fn send_tcp_print(&self, printer: &PrinterConfig, content: &str) -> Result<String, String> {
-        use tokio::net::TcpStream;
-        use tokio::io::AsyncWriteExt;
+    pub async fn print_cancellation_slip(&self, slip: &CancellationSlip, staff_name: Option<String>) -> Result<String, String> {
+        self.queue_print_job(PrintJobType::CancellationSlip, slip.to_payload(), staff_name, 0).await
+    }
 
-        // Connect to printer
-        let addr = format!("{}:{}", printer.ip, printer.port);
-        let mut stream = TcpStream::connect(&addr)
-            .await
-            .map_err(|e| format!("Failed to connect to printer: {}", e))?;
+    // Direct TCP printing: raw content wrapped with init/feed/cut, sent over the socket
+    pub async fn print_direct_tcp(&self, printer_id: &str, content: &str) -> Result<String, String> {
+        let config = self.get_printer_by_id(printer_id).await?
+            .ok_or_else(|| format!("Printer with ID {} not found", printer_id))?;
 
-        println!("🖨️ [DIRECT TCP] Connected to printer at {}", addr);
+        println!("🖨️ [DIRECT TCP] Printing to {} ({}:{})", config.name, config.ip, config.port);
+        println!("🖨️ [DIRECT TCP] Content: {}", content);
 
-        // Prepare content with ESC/POS commands
+        let model = config.model_profile();
         let mut print_data = Vec::new();
-        
-        // ESC/POS initialization
-        print_data.extend_from_slice(&[0x1B, 0x40]); // ESC @ - Initialize printer
-        
-        // Add content
+        print_data.extend_from_slice(model.init);
         print_data.extend_from_slice(content.as_bytes());
-        
-        // Add line feeds and cut
         print_data.extend_from_slice(b"\n\n\n\n\n"); // Feed paper
-        print_data.extend_from_slice(&[0x1D, 0x56, 0x00]); // Cut paper
+        print_data.extend_from_slice(model.cut_command(CutType::Full));
 
-        // Send data
-        stream.write_all(&print_data)
-            .await
-            .map_err(|e| format!("Failed to send print data: {}", e))?;
+        match self.send_tcp_bytes(&config, &print_data).await {
+            Ok(result) => {
+                println!("🖨️ [DIRECT TCP] Print successful: {}", result);
+                Ok(result)
+            }
+            Err(e) => {
+                println!("🖨️ [DIRECT TCP] Print failed: {}", e);
+                Err(e)
+            }
+        }
+    }
 
-        println!("🖨️ [DIRECT TCP] Data sent successfully");
-        Ok("Print job completed successfully".to_string())
+    /// Bytes sent as given, for calibration sequences: no queue, no code page conversion
+    pub async fn send_raw(&self, printer_id: &str, bytes: &[u8]) -> Result<String, String> {
+        let config = self.get_printer_by_id(printer_id).await?
+            .ok_or_else(|| format!("Printer with ID {} not found", printer_id))?;
+        println!("🖨️ [RAW] Sending {} bytes to {} ({}:{})", bytes.len(), config.name, config.ip, config.port);
+        let result = if Self::simulation_enabled() {
+            MockTransport::get_instance().send(&config, bytes)
+        } else {
+            match config.backend {
+                PrinterBackend::Network => Self::send_tcp_bytes_direct(&config, bytes).await,
+                PrinterBackend::Spooler => Self::send_spooler_bytes_direct(&config, bytes).await,
+            }
+        }?;
+        self.usage.record(&config.id, bytes);
+        Ok(result)
     }
 
-    // Test direct TCP connection using PowerShell
+    // Test direct TCP connection by opening (and immediately closing) a socket to the printer
     pub async fn test_direct_tcp_connection(&self, printer_id: &str) -> Result<String, String> {
-        let config = self.get_printer_by_id(printer_id)?
+        use tokio::net::TcpStream;
+
+        let config = self.get_printer_by_id(printer_id).await?
             .ok_or_else(|| format!("Printer with ID {} not found", printer_id))?;
 
         println!("🔍 [DIRECT TCP] Testing connection to {} ({}:{})", config.name, config.ip, config.port);
 
-        // Use PowerShell to test connection
-        let test_script = format!(
-            r#"
-try {{
-    $tcp = New-Object System.Net.Sockets.TcpClient
-    $tcp.Connect('{}', {})
-    if ($tcp.Connected) {{
-        Write-Host "✅ Connection successful to {}:{}" -ForegroundColor Green
-        $tcp.Close()
-        exit 0
-    }} else {{
-        Write-Host "❌ Connection failed to {}:{}" -ForegroundColor Red
-        exit 1
-    }}
-}} catch {{
-    Write-Host "❌ Connection error: $($_.Exception.Message)" -ForegroundColor Red
-    exit 1
-}}
-"#,
-            config.ip, config.port, config.ip, config.port, config.ip, config.port
-        );
-
-        let output = Command::new("powershell")
-            .args(&["-ExecutionPolicy", "Bypass", "-Command", &test_script])
-            .output()
-            .map_err(|e| format!("Failed to execute PowerShell test: {}", e))?;
-
-        if output.status.success() {
-            let result = String::from_utf8_lossy(&output.stdout);
-            println!("🔍 [DIRECT TCP] Test successful: {}", result);
-            Ok(format!("Connection successful to {}:{}", config.ip, config.port))
-        } else {
-            let error = String::from_utf8_lossy(&output.stderr);
-            println!("🔍 [DIRECT TCP] Test failed: {}", error);
-            Err(format!("Connection test failed: {}", error))
+        let addr = format!("{}:{}", config.ip, config.port);
+        let connect_timeout = Duration::from_millis(config.timeout.max(1000));
+        match tokio::time::timeout(connect_timeout, TcpStream::connect(&addr)).await {
+            Ok(Ok(_stream)) => {
+                println!("🔍 [DIRECT TCP] Test successful: {}", addr);
+                Ok(format!("Connection successful to {}:{}", config.ip, config.port))
+            }
+            Ok(Err(e)) => {
+                println!("🔍 [DIRECT TCP] Test failed: {}", e);
+                Err(format!("Connection test failed: {}", e))
+            }
+            Err(_) => {
+                println!("🔍 [DIRECT TCP] Test timed out after {}ms", connect_timeout.as_millis());
+                Err(format!("Connection test failed: timed out after {}ms", connect_timeout.as_millis()))
+            }
         }
     }
 
     // Print Queue Management Methods
-    pub fn start_print_queue_processor(&self) {
-        let (tx, mut rx) = mpsc::unbounded_channel::<QueuedPrintJob>();
+    pub async fn start_print_queue_processor(&self) {
+        let (tx, mut rx) = mpsc::channel::<PrintBatch>(self.queue_capacity);
         
         // Store the sender for adding jobs to the queue
-        if let Ok(mut sender_guard) = self.print_queue_sender.lock() {
-            *sender_guard = Some(tx);
-        }
+        *self.print_queue_sender.lock().await = Some(tx);
 
         // Clone the necessary data for the processor task
         let printer_config = self.printer_config.clone();
         let queue_status = self.queue_status.clone();
         let print_queue = self.print_queue.clone();
+        let queue_capacity = self.queue_capacity;
+        let backlog_depth = self.backlog_depth.clone();
+        let backlog_events = self.backlog_events.clone();
+        let batch_reports = self.batch_reports.clone();
+        let usage = self.usage.clone();
+        let schedule = self.schedule.clone();
 
         // Start the queue processor task
         task::spawn(async move {
             println!("🖨️ [QUEUE] Print queue processor started");
+            // Batches kept back by maintenance or quiet hours, oldest first; they stay in the backlog
+            let mut held: VecDeque<PrintBatch> = VecDeque::new();
             
             loop {
-                // Wait for a job to be added to the queue
-                if let Some(job) = rx.recv().await {
-                    println!("🖨️ [QUEUE] Processing job: {} ({:?})", job.id, job.job_type);
-                    
-                    // Update queue status
-                    if let Ok(mut status) = queue_status.lock() {
-                        status.is_processing = true;
+                let printer_id = printer_config.read().await.id.clone();
+                let released = held
+                    .iter()
+                    .position(|batch| batch.hold_reason(&schedule, &printer_id).is_none())
+                    .and_then(|i| held.remove(i));
+                let next = match released {
+                    Some(batch) => Some(batch),
+                    // Wait for a job to be added to the queue, or for held jobs to become printable
+                    None => tokio::select! {
+                        batch = rx.recv() => batch,
+                        _ = schedule.changed.notified() => continue,
+                        _ = tokio::time::sleep(Duration::from_secs(HOLD_CHECK_SECS)), if !held.is_empty() => continue,
+                    },
+                };
+                if let Some(mut batch) = next {
+                    if let Some(reason) = batch.hold_reason(&schedule, &printer_id) {
+                        println!("⏸️ [QUEUE] Holding batch {} ({} jobs): {:?}", batch.id, batch.jobs.len(), reason);
+                        held.push_back(batch);
+                        schedule.set_held_jobs(held.iter().map(|b| b.jobs.len()).sum());
+                        continue;
                     }
+                    schedule.set_held_jobs(held.iter().map(|b| b.jobs.len()).sum());
+                    let mut report = PrintBatchReport { batch_id: batch.id.clone(), printed: Vec::new(), failed: Vec::new(), error: None };
 
-                    // Add job to the queue
-                    if let Ok(mut queue) = print_queue.lock() {
-                        queue.push_back(job.clone());
-                    }
+                    // The batch's jobs go out back to back, nothing else is taken from the channel meanwhile
+                    for job in std::mem::take(&mut batch.jobs) {
+                        println!("🖨️ [QUEUE] Processing job: {} ({:?})", job.id, job.job_type);
+                    
+                        // Update queue status
+                        queue_status.lock().await.is_processing = true;
 
-                    // Process the job
-                    let result = Self::process_print_job(&job, &printer_config).await;
+                        // Add job to the queue
+                        print_queue.lock().await.push_back(job.clone());
+
+                        // Process the job
+                        let result = Self::process_print_job(&job, &printer_config, &usage).await;
                     
-                    match result {
-                        Ok(_) => {
-                            println!("✅ [QUEUE] Job {} completed successfully", job.id);
-                            // Update last printed time
-                            if let Ok(mut status) = queue_status.lock() {
-                                status.last_printed_at = Some(chrono::Utc::now());
+                        match result {
+                            Ok(printed) => {
+                                println!("✅ [QUEUE] Job {} completed successfully on {}", job.id, printed.printer_name);
+                                crate::ticket_archive::archive(&job, printed);
+                                crate::metrics::record_ticket_printed();
+                                // Update last printed time
+                                queue_status.lock().await.last_printed_at = Some(chrono::Utc::now());
+                                report.printed.push(job.id.clone());
                             }
-                        }
-                        Err(e) => {
-                            println!("❌ [QUEUE] Job {} failed: {}", job.id, e);
-                            // Increment retry count and potentially requeue
-                            if job.retry_count < 3 {
-                                println!("🔄 [QUEUE] Retrying job {} (attempt {})", job.id, job.retry_count + 1);
-                                let mut retry_job = job.clone();
-                                retry_job.retry_count += 1;
-                                // Requeue the job
-                                if let Ok(mut queue) = print_queue.lock() {
-                                    queue.push_front(retry_job); // Add to front for retry
-                                }
-                            } else {
-                                println!("💀 [QUEUE] Job {} failed permanently after 3 retries", job.id);
-                                if let Ok(mut status) = queue_status.lock() {
-                                    status.failed_jobs += 1;
+                            Err(e) => {
+                                println!("❌ [QUEUE] Job {} failed: {}", job.id, e);
+                                report.failed.push(job.id.clone());
+                                report.error.get_or_insert(e);
+                                // Increment retry count and potentially requeue
+                                if job.retry_count < 3 {
+                                    println!("🔄 [QUEUE] Retrying job {} (attempt {})", job.id, job.retry_count + 1);
+                                    let mut retry_job = job.clone();
+                                    retry_job.retry_count += 1;
+                                    // Requeue the job
+                                    print_queue.lock().await.push_front(retry_job); // Add to front for retry
+                                } else {
+                                    println!("💀 [QUEUE] Job {} failed permanently after 3 retries", job.id);
+                                    crate::metrics::record_print_failure();
+                                    queue_status.lock().await.failed_jobs += 1;
                                 }
                             }
                         }
-                    }
 
-                    // Remove completed job from queue
-                    if let Ok(mut queue) = print_queue.lock() {
-                        queue.pop_front();
-                    }
+                        // Remove completed job from queue
+                        let queue_length = {
+                            let mut queue = print_queue.lock().await;
+                            queue.pop_front();
+                            queue.len()
+                        };
+
+                        // Update queue status
+                        {
+                            let mut status = queue_status.lock().await;
+                            status.is_processing = false;
+                            status.queue_length = queue_length;
+                        }
+
+                        backlog_depth.fetch_sub(1, Ordering::SeqCst);
+                        Self::publish_backlog(&backlog_depth, queue_capacity, &backlog_events);
 
-                    // Update queue status
-                    if let Ok(mut status) = queue_status.lock() {
-                        status.is_processing = false;
-                        status.queue_length = print_queue.lock().map(|q| q.len()).unwrap_or(0);
+                        // Small delay between jobs to prevent overwhelming the printer
+                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
                     }
 
-                    // Small delay between jobs to prevent overwhelming the printer
-                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                    if !report.succeeded() {
+                        println!("❌ [QUEUE] Batch {}: {} printed, {} failed", report.batch_id, report.printed.len(), report.failed.len());
+                    }
+                    if batch.reported {
+                        batch_reports.send_replace(Some(report));
+                    }
+                } else {
+                    break; // the sender was replaced by a restarted processor
                 }
             }
         });
     }
 
-    async fn process_print_job(job: &QueuedPrintJob, printer_config: &Arc<Mutex<PrinterConfig>>) -> Result<String, String> {
-        let config = printer_config.lock().map_err(|e| e.to_string())?.clone();
-        
-        match job.job_type {
-            PrintJobType::BookingTicket => {
-                Self::print_booking_ticket_direct(&job.content, job.staff_name.clone(), &config).await
-            }
-            PrintJobType::EntryTicket => {
-                Self::print_entry_ticket_direct(&job.content, job.staff_name.clone(), &config).await
-            }
-            PrintJobType::ExitTicket => {
-                Self::print_exit_ticket_direct(&job.content, job.staff_name.clone(), &config).await
-            }
-            PrintJobType::DayPassTicket => {
-                Self::print_day_pass_ticket_direct(&job.content, job.staff_name.clone(), &config).await
-            }
-            PrintJobType::ExitPassTicket => {
-                Self::print_exit_pass_ticket_direct(&job.content, job.staff_name.clone(), &config).await
+    fn publish_backlog(depth: &AtomicUsize, capacity: usize, events: &watch::Sender<PrintBacklog>) {
+        let depth = depth.load(Ordering::SeqCst);
+        events.send_replace(PrintBacklog {
+            depth,
+            capacity,
+            full: depth >= capacity,
+        });
+    }
+
+    /// Reports of finished batches, for forwarding to the frontend
+    pub fn subscribe_batch_reports(&self) -> watch::Receiver<Option<PrintBatchReport>> {
+        self.batch_reports.subscribe()
+    }
+
+    /// Backlog depth updates, for forwarding to the frontend
+    pub fn subscribe_backlog(&self) -> watch::Receiver<PrintBacklog> {
+        self.backlog_events.subscribe()
+    }
+
+    // A job goes to the first printer of the failover chain that takes it; with backups
+    // configured, each ticket names the device that printed it
+    async fn process_print_job(job: &QueuedPrintJob, printer_config: &Arc<RwLock<PrinterConfig>>, usage: &PrinterUsage) -> Result<PrintedJob, String> {
+        let config = match &job.printer {
+            Some(printer) => printer.clone(),
+            None => printer_config.read().await.clone(),
+        };
+        let chain = config.failover_chain();
+        let tagged = chain.len() > 1;
+        let mut errors = Vec::new();
+        for printer in chain {
+            match Self::print_job_on(job, printer, tagged, usage).await {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    println!("⚠️ [QUEUE] Job {} failed on {}: {}", job.id, printer.name, e);
+                    errors.push(format!("{}: {}", printer.name, e));
+                }
             }
-            PrintJobType::Talon => {
-                Self::print_talon_direct(&job.content, job.staff_name.clone(), &config).await
+        }
+        Err(errors.join("; "))
+    }
+
+    // The archived text is the first copy's
+    async fn print_job_on(job: &QueuedPrintJob, printer: &PrinterConfig, tagged: bool, usage: &PrinterUsage) -> Result<PrintedJob, String> {
+        let printed_at = crate::time::ticket_ts_now();
+        let copies = crate::print_copies::copies_for(&job.job_type);
+        let layout = TextLayout::for_printer(printer);
+        let mut text = None;
+        for copy in 1..=copies {
+            let mut data = Vec::new();
+            if crate::training::is_active() {
+                Self::push_training_banner(&mut data, &layout);
             }
-            PrintJobType::StandardTicket => {
-                Self::print_standard_ticket_direct(&job.content, &config).await
+            if tagged {
+                Self::push_device_banner(&mut data, &layout, &printer.name);
             }
-            PrintJobType::Receipt => {
-                Self::print_receipt_direct(&job.content, &config).await
+            if let Some(number) = job.duplicate {
+                Self::push_duplicate_banner(&mut data, &layout, number);
             }
-            PrintJobType::QRCode => {
-                Self::print_qr_code_direct(&job.content, &config).await
+            if copy > 1 {
+                Self::push_copy_banner(&mut data, &layout, copy, copies);
             }
+            data.extend(Self::build_job_bytes(&job.job_type, &job.content, job.staff_name.clone(), &printed_at, printer));
+            Self::send_bytes_direct(printer, &data).await?;
+            usage.record(&printer.id, &data);
+            text.get_or_insert_with(|| MockTransport::render_text(&MockTransport::decode(&data), printer.width as usize));
+        }
+        Ok(PrintedJob {
+            printer_id: printer.id.clone(),
+            printer_name: printer.name.clone(),
+            text: text.unwrap_or_default(),
+            width: printer.width,
+        })
+    }
+
+    /// The text a job would print on `printer`, for a ticket kept instead of printed (ticket_escrow.rs)
+    pub(crate) fn render_job_text(job: &QueuedPrintJob, printer: &PrinterConfig) -> String {
+        let layout = TextLayout::for_printer(printer);
+        let mut data = Vec::new();
+        if crate::training::is_active() {
+            Self::push_training_banner(&mut data, &layout);
+        }
+        if let Some(number) = job.duplicate {
+            Self::push_duplicate_banner(&mut data, &layout, number);
+        }
+        data.extend(Self::build_job_bytes(&job.job_type, &job.content, job.staff_name.clone(), &crate::time::ticket_ts_now(), printer));
+        MockTransport::render_text(&MockTransport::decode(&data), printer.width as usize)
+    }
+
+    /// Run the same formatting as the queue processor and return the lines instead of printing
+    pub async fn render_ticket_preview(&self, kind: &str, payload: &str, staff_name: Option<String>) -> Result<TicketPreview, String> {
+        let job_type = PrintJobType::from_kind(kind).ok_or(format!("Unknown ticket kind '{}'", kind))?;
+        let config = self.printer_config.read().await.clone();
+        let width = config.width;
+        let printed_at = crate::time::ticket_ts_now();
+
+        let data = Self::build_job_bytes(&job_type, payload, staff_name, &printed_at, &config);
+        let lines = MockTransport::decode(&data);
+        let text = MockTransport::render_text(&lines, width as usize);
+
+        Ok(TicketPreview {
+            kind: job_type,
+            width,
+            monospace: true,
+            lines,
+            text,
+        })
+    }
+
+    /// Build the ESC/POS bytes for a queued job; `printed_at` is the footer timestamp
+    pub(crate) fn build_job_bytes(job_type: &PrintJobType, content: &str, staff_name: Option<String>, printed_at: &str, config: &PrinterConfig) -> Vec<u8> {
+        let layout = TextLayout::for_printer(config);
+        match job_type {
+            // Typed tickets: a payload that does not parse prints with placeholders, as before
+            PrintJobType::BookingTicket => Self::build_booking_ticket_bytes(&BookingTicket::from_payload(content), staff_name, printed_at, &layout),
+            PrintJobType::EntryTicket => Self::build_entry_ticket_bytes(&EntryTicket::from_payload(content).unwrap_or_default(), staff_name, &layout),
+            PrintJobType::ExitTicket => Self::build_exit_ticket_bytes(content, staff_name, printed_at, &layout),
+            PrintJobType::DayPassTicket => Self::build_day_pass_ticket_bytes(&DayPassTicket::from_payload(content).unwrap_or_default(), staff_name, &layout),
+            PrintJobType::ExitPassTicket => Self::build_exit_pass_ticket_bytes(&ExitPassTicket::from_payload(content).unwrap_or_default(), staff_name, printed_at, &layout),
+            PrintJobType::Talon => Self::build_talon_bytes(content, staff_name, printed_at, &layout),
+            PrintJobType::StandardTicket => Self::build_standard_ticket_bytes(content, printed_at, &layout),
+            PrintJobType::Receipt => Self::build_receipt_bytes(content, &layout),
+            PrintJobType::QRCode => Self::build_qr_code_bytes(content, &layout),
+            PrintJobType::DayPassSummary => Self::build_day_pass_summary_bytes(content, staff_name, printed_at, &layout),
+            PrintJobType::SettlementSlip => Self::build_settlement_slip_bytes(&SettlementSlip::from_payload(content).unwrap_or_default(), staff_name, printed_at, &layout),
+            PrintJobType::ExpenseVoucher => Self::build_expense_voucher_bytes(&ExpenseVoucher::from_payload(content).unwrap_or_default(), staff_name, printed_at, &layout),
+            PrintJobType::CashVarianceSlip => Self::build_cash_variance_slip_bytes(&CashVarianceSlip::from_payload(content).unwrap_or_default(), staff_name, printed_at, &layout),
+            PrintJobType::QueueBoard => Self::build_queue_board_bytes(&QueueBoard::from_payload(content).unwrap_or_default(), printed_at, &layout),
+            PrintJobType::CancellationSlip => Self::build_cancellation_slip_bytes(&CancellationSlip::from_payload(content).unwrap_or_default(), staff_name, printed_at, &layout),
         }
     }
 
-    // Direct printing methods (without queue)
-    async fn print_booking_ticket_direct(content: &str, staff_name: Option<String>, config: &PrinterConfig) -> Result<String, String> {
-        let staff_footer = if let Some(name) = staff_name {
-            format!("Émis par: {}", name)
-        } else {
-            if let Ok(parsed_data) = serde_json::from_str::<serde_json::Value>(content) {
-                if let Some(staff_name_from_data) = parsed_data.get("staffName").and_then(|v| v.as_str()) {
-                    format!("Émis par: {}", staff_name_from_data)
-                } else {
-                    "Émis par: Staff".to_string()
-                }
-            } else {
-                "Émis par: Staff".to_string()
-            }
-        };
+    // Footer line for typed tickets: explicit staff name, else the one on the ticket
+    fn ticket_staff_footer(lang: &TicketLanguage, staff_name: Option<String>, ticket_staff: &Option<String>) -> String {
+        format!("{} {}", lang.label("ticket.issued_by"), staff_name.or_else(|| ticket_staff.clone()).unwrap_or_else(|| "Staff".to_string()))
+    }
 
-        let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
+    // Footer line: explicit staff name, else "staffName" from the JSON payload
+    fn staff_footer(lang: &TicketLanguage, staff_name: Option<String>, content: &str) -> String {
+        let name = staff_name.unwrap_or_else(|| {
+            serde_json::from_str::<serde_json::Value>(content)
+                .ok()
+                .and_then(|parsed| parsed.get("staffName").and_then(|v| v.as_str()).map(|v| v.to_string()))
+                .unwrap_or_else(|| "Staff".to_string())
+        });
+        format!("{} {}", lang.label("ticket.issued_by"), name)
+    }
+
+    // Ticket byte builders (without queue)
+    const COMPANY_NAME: &str = "STE Dhraiff Services Transport";
+
+    // Shared centered, bold company header followed by the ticket title
+    fn push_ticket_header(data: &mut Vec<u8>, layout: &TextLayout, title: Option<&str>) {
+        data.extend_from_slice(layout.model().init);
         data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
         data.extend_from_slice(&[0x1B, 0x45, 0x01]); // bold
-        data.extend_from_slice(b"STE Dhraiff Services Transport\n");
+        push_lines(data, &layout.wrap(Self::COMPANY_NAME));
         data.extend_from_slice(&[0x1B, 0x45, 0x00]);
-        data.extend_from_slice(b"RESERVATION\n");
-        data.extend_from_slice(b"================================\n");
+        if let Some(title) = title {
+            push_lines(data, &layout.wrap(title));
+        }
+        push_line(data, &layout.separator('='));
         data.extend_from_slice(&[0x1B, 0x61, 0x00]); // left
-        data.extend_from_slice(content.as_bytes());
-        data.extend_from_slice(b"\n");
-        data.extend_from_slice(b"================================\n");
-        data.extend_from_slice(&[0x1B, 0x61, 0x02]); // right
-        data.extend_from_slice(format!("{}\n", staff_footer).as_bytes());
+    }
+
+    // Above copy 2 onwards, so a duplicate can't pass for the original
+    fn push_copy_banner(data: &mut Vec<u8>, layout: &TextLayout, copy: u8, copies: u8) {
+        data.extend_from_slice(layout.model().init);
         data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
-        let date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S");
-        data.extend_from_slice(format!("Date: {}\n", date).as_bytes());
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]); // bold
+        push_lines(data, &layout.wrap(&layout.language().text_with("ticket.copy", &[("copy", &copy), ("total", &copies)])));
+        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
+        data.extend_from_slice(&[0x1B, 0x61, 0x00]); // left
     }
 
-    async fn print_entry_ticket_direct(content: &str, staff_name: Option<String>, config: &PrinterConfig) -> Result<String, String> {
-        let staff_footer = if let Some(name) = staff_name {
-            format!("Émis par: {}", name)
-        } else {
-            if let Ok(parsed_data) = serde_json::from_str::<serde_json::Value>(content) {
-                if let Some(staff_name_from_data) = parsed_data.get("staffName").and_then(|v| v.as_str()) {
-                    format!("Émis par: {}", staff_name_from_data)
-                } else {
-                    "Émis par: Staff".to_string()
-                }
-            } else {
-                "Émis par: Staff".to_string()
-            }
-        };
+    // Names the printer a job went to when a failover chain is set, so a backup's tickets stand out
+    fn push_device_banner(data: &mut Vec<u8>, layout: &TextLayout, printer_name: &str) {
+        data.extend_from_slice(layout.model().init);
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
+        push_lines(data, &layout.wrap(&layout.language().text_with("ticket.printed_on", &[("printer", &printer_name)])));
+        data.extend_from_slice(&[0x1B, 0x61, 0x00]); // left
+    }
 
-        let v: serde_json::Value = serde_json::from_str(content).unwrap_or(serde_json::json!({}));
-        let license_plate = v.get("licensePlate").and_then(|x| x.as_str()).unwrap_or("-");
-        let queue_position = v.get("queuePosition").and_then(|x| x.as_i64()).unwrap_or(0);
-        let destination_name = v.get("destinationName").and_then(|x| x.as_str()).unwrap_or("-");
-        let entry_time = v.get("entryTime").and_then(|x| x.as_str()).unwrap_or("-");
-        let day_pass_status = v.get("dayPassStatus").and_then(|x| x.as_str()).unwrap_or("NONE");
-        let day_pass_purchase = v.get("dayPassPurchaseDate").and_then(|x| x.as_str()).unwrap_or("-");
-        let ticket_number = v.get("ticketNumber").and_then(|x| x.as_str()).unwrap_or("");
+    // Above every copy of a reprint, numbered per ticket
+    fn push_duplicate_banner(data: &mut Vec<u8>, layout: &TextLayout, number: u32) {
+        data.extend_from_slice(layout.model().init);
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]); // bold
+        push_lines(data, &layout.wrap(&layout.language().text_with("ticket.duplicate", &[("number", &number)])));
+        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
+        data.extend_from_slice(&[0x1B, 0x61, 0x00]); // left
+    }
 
-        let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
+    // Above every ticket printed in training mode, so none can be used
+    fn push_training_banner(data: &mut Vec<u8>, layout: &TextLayout) {
+        data.extend_from_slice(layout.model().init);
         data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
-        data.extend_from_slice(&[0x1B, 0x45, 0x01]);
-        data.extend_from_slice(b"STE Dhraiff Services Transport\n");
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]); // bold
+        push_lines(data, &layout.wrap(&layout.language().text("ticket.training")));
         data.extend_from_slice(&[0x1B, 0x45, 0x00]);
-        data.extend_from_slice(b"TICKET D'ENTREE\n");
-        data.extend_from_slice(b"================================\n");
         data.extend_from_slice(&[0x1B, 0x61, 0x00]); // left
-        data.extend_from_slice(b"VEHICULE:\n");
-        data.extend_from_slice(format!("Plaque: {}\n", license_plate).as_bytes());
-        data.extend_from_slice(format!("Position: {}\n\n", queue_position).as_bytes());
-        data.extend_from_slice(b"DESTINATION:\n");
-        data.extend_from_slice(format!("Station: {}\n\n", destination_name).as_bytes());
-        data.extend_from_slice(b"HEURE D'ENTREE:\n");
-        data.extend_from_slice(format!("{}\n\n", entry_time).as_bytes());
-        data.extend_from_slice(b"TARIFICATION:\n");
-        match day_pass_status {
-            "VALID" => {
-                data.extend_from_slice(b"Pass journalier: VALIDE\n");
-                data.extend_from_slice(format!("Achat le: {}\nMONTANT: 0.00 TND\n\n", day_pass_purchase).as_bytes());
+    }
+
+    // Feed the profile's blank lines so the text clears the cutter, then cut
+    fn push_feed_and_cut(data: &mut Vec<u8>, layout: &TextLayout) {
+        let profile = layout.profile();
+        data.extend(std::iter::repeat(b'\n').take(profile.feed_lines as usize));
+        data.extend_from_slice(layout.model().cut_command(profile.cut));
+    }
+
+    fn build_booking_ticket_bytes(ticket: &BookingTicket, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::ticket_staff_footer(lang, staff_name, &ticket.staff_name);
+
+        let mut data: Vec<u8> = Vec::new();
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.booking").as_str()));
+        if let Some(previous) = &ticket.previous_license_plate {
+            push_line(&mut data, &layout.centered_separator(&lang.text("ticket.vehicle_change"), '*'));
+            push_lines(&mut data, &layout.row(&lang.label("ticket.previous_plate"), previous));
+        }
+        if ticket.text.is_empty() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.code"), dash(&ticket.verification_code)));
+            push_lines(&mut data, &layout.row(&lang.label("ticket.plate"), dash(&ticket.license_plate)));
+            push_lines(&mut data, &layout.row(&lang.label("ticket.destination"), dash(&ticket.destination_name)));
+            push_lines(&mut data, &layout.row(&lang.label("ticket.seats"), &ticket.seats_booked.to_string()));
+            for modifier in &ticket.fare_modifiers {
+                let sign = if modifier.amount.is_positive() { "+" } else { "" };
+                let label = format!("{} x{}:", modifier.label, modifier.quantity);
+                push_lines(&mut data, &layout.row(&label, &format!("{}{} TND", sign, modifier.amount)));
+            }
+            push_lines(&mut data, &layout.row(&lang.label("ticket.amount"), &format!("{} TND", ticket.total_amount)));
+            if !ticket.payment_method.is_empty() {
+                push_lines(&mut data, &layout.row(&lang.label("ticket.payment"), &method_label(&ticket.payment_method)));
+            }
+        } else {
+            push_lines(&mut data, &layout.wrap(&ticket.text));
+        }
+        push_line(&mut data, &layout.separator('='));
+        data.extend_from_slice(&[0x1B, 0x61, 0x02]); // right
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]); // center
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
+    }
+
+    fn build_entry_ticket_bytes(ticket: &EntryTicket, staff_name: Option<String>, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::ticket_staff_footer(lang, staff_name, &ticket.staff_name);
+
+        let license_plate = dash(&ticket.license_plate);
+        let queue_position = ticket.queue_position;
+        let destination_name = dash(&ticket.destination_name);
+        let entry_time = dash(&ticket.entry_time);
+        let day_pass_purchase = ticket.day_pass_purchase_date.as_deref().unwrap_or("-");
+        let ticket_number = ticket.ticket_number.as_str();
+
+        let mut data: Vec<u8> = Vec::new();
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.entry").as_str()));
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.vehicle"), '-'));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.plate"), license_plate));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.position"), &queue_position.to_string()));
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.destination"), '-'));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.station"), destination_name));
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.entry_time"), '-'));
+        push_lines(&mut data, &layout.wrap(entry_time));
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.pricing"), '-'));
+        match ticket.day_pass_status {
+            DayPassStatus::Valid => {
+                push_lines(&mut data, &layout.row(&lang.label("ticket.day_pass_field"), &lang.text("ticket.valid")));
+                push_lines(&mut data, &layout.row(&lang.label("ticket.purchased_on"), day_pass_purchase));
+                push_lines(&mut data, &layout.row(&lang.label("ticket.amount_due"), "0.000 TND"));
             }
-            "PURCHASED" => {
-                data.extend_from_slice(b"Pass journalier: ACHETE\n");
-                data.extend_from_slice(format!("Achat le: {}\nMONTANT: 2.00 TND\n\n", day_pass_purchase).as_bytes());
+            DayPassStatus::Purchased => {
+                push_lines(&mut data, &layout.row(&lang.label("ticket.day_pass_field"), &lang.text("ticket.purchased")));
+                push_lines(&mut data, &layout.row(&lang.label("ticket.purchased_on"), day_pass_purchase));
+                push_lines(&mut data, &layout.row(&lang.label("ticket.amount_due"), "2.000 TND"));
             }
-            _ => {
-                data.extend_from_slice(b"Pass journalier: NON VALIDE\nMONTANT: 2.00 TND\n\n");
+            DayPassStatus::None => {
+                push_lines(&mut data, &layout.row(&lang.label("ticket.day_pass_field"), &lang.text("ticket.not_valid")));
+                push_lines(&mut data, &layout.row(&lang.label("ticket.amount_due"), "2.000 TND"));
             }
         }
         if !ticket_number.is_empty() {
-            data.extend_from_slice(format!("N° Ticket: {}\n", ticket_number).as_bytes());
+            push_lines(&mut data, &layout.row(&lang.label("ticket.ticket_number"), ticket_number));
         }
-        data.extend_from_slice(b"================================\n");
+        push_line(&mut data, &layout.separator('='));
         data.extend_from_slice(&[0x1B, 0x61, 0x02]); // right
-        data.extend_from_slice(format!("{}\n", staff_footer).as_bytes());
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
     }
 
-    async fn print_exit_ticket_direct(content: &str, staff_name: Option<String>, config: &PrinterConfig) -> Result<String, String> {
-        let staff_footer = if let Some(name) = staff_name {
-            format!("Émis par: {}", name)
-        } else {
-            "Émis par: Staff".to_string()
-        };
+    fn build_exit_ticket_bytes(content: &str, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = format!("{} {}", lang.label("ticket.issued_by"), staff_name.unwrap_or_else(|| "Staff".to_string()));
 
         let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.exit").as_str()));
+        push_lines(&mut data, &layout.wrap(content));
+        push_line(&mut data, &layout.separator('='));
         data.extend_from_slice(&[0x1B, 0x61, 0x01]);
-        data.extend_from_slice(&[0x1B, 0x45, 0x01]);
-        data.extend_from_slice(b"STE Dhraiff Services Transport\n");
-        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
-        data.extend_from_slice(b"TICKET DE SORTIE\n");
-        data.extend_from_slice(b"================================\n");
-        data.extend_from_slice(&[0x1B, 0x61, 0x00]);
-        data.extend_from_slice(content.as_bytes());
-        data.extend_from_slice(b"\n");
-        data.extend_from_slice(b"================================\n");
-        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
-        let date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S");
-        data.extend_from_slice(format!("Date: {}\nMerci!\n", date).as_bytes());
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
+        push_line(&mut data, &lang.text("ticket.thanks"));
         data.extend_from_slice(&[0x1B, 0x61, 0x02]);
-        data.extend_from_slice(format!("{}\n", staff_footer).as_bytes());
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
     }
 
-    async fn print_day_pass_ticket_direct(content: &str, staff_name: Option<String>, config: &PrinterConfig) -> Result<String, String> {
-        let staff_footer = if let Some(name) = staff_name {
-            format!("Émis par: {}", name)
-        } else {
-            if let Ok(parsed_data) = serde_json::from_str::<serde_json::Value>(content) {
-                if let Some(staff_name_from_data) = parsed_data.get("staffName").and_then(|v| v.as_str()) {
-                    format!("Émis par: {}", staff_name_from_data)
-                } else {
-                    "Émis par: Staff".to_string()
-                }
-            } else {
-                "Émis par: Staff".to_string()
-            }
+    fn build_day_pass_ticket_bytes(ticket: &DayPassTicket, staff_name: Option<String>, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::ticket_staff_footer(lang, staff_name, &ticket.staff_name);
+
+        let license_plate = dash(&ticket.license_plate);
+        let purchase_date = dash(&ticket.purchase_date);
+        let valid_for = dash(&ticket.valid_for);
+        let valid_from = ticket.valid_from.as_deref();
+        let valid_until = ticket.valid_until.as_deref();
+        let destination = dash(&ticket.destination_name);
+        let amount = ticket.amount;
+        let (title, pass_label) = match ticket.pass_type.as_str() {
+            "WEEK" => ("ticket.week_pass", "ticket.week_pass_field"),
+            "MONTH" => ("ticket.month_pass", "ticket.month_pass_field"),
+            _ => ("ticket.day_pass", "ticket.day_pass_field"),
         };
 
+        let mut data: Vec<u8> = Vec::new();
+        Self::push_ticket_header(&mut data, layout, Some(lang.text(title).as_str()));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.plate"), license_plate));
+        push_lines(&mut data, &layout.row(&lang.label(pass_label), &lang.text("ticket.purchased")));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.amount"), &format!("{} TND", amount)));
+        if !ticket.payment_method.is_empty() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.payment"), &method_label(&ticket.payment_method)));
+        }
+        push_lines(&mut data, &layout.row(&lang.label("ticket.purchase_date"), purchase_date));
+        match (valid_from, valid_until) {
+            (Some(from), Some(until)) if from != until => {
+                push_lines(&mut data, &layout.row(&lang.label("ticket.valid_from"), from));
+                push_lines(&mut data, &layout.row(&lang.label("ticket.valid_until"), until));
+            }
+            _ => push_lines(&mut data, &layout.row(&lang.label("ticket.valid_for"), valid_for)),
+        }
+        push_lines(&mut data, &layout.row(&lang.label("ticket.destination"), destination));
+        push_line(&mut data, &layout.separator('='));
+        data.extend_from_slice(&[0x1B, 0x61, 0x02]);
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
+    }
+
+    // Evening closeout: passes sold per staff member and per tariff, then cancellations and net
+    fn build_day_pass_summary_bytes(content: &str, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::staff_footer(lang, staff_name, content);
+
         let v: serde_json::Value = serde_json::from_str(content).unwrap_or(serde_json::json!({}));
-        let license_plate = v.get("licensePlate").and_then(|x| x.as_str()).unwrap_or("-");
-        let amount = v.get("amount").and_then(|x| x.as_f64()).unwrap_or(0.0);
-        let purchase_date = v.get("purchaseDate").and_then(|x| x.as_str()).unwrap_or("-");
-        let valid_for = v.get("validFor").and_then(|x| x.as_str()).unwrap_or("-");
-        let destination = v.get("destinationName").and_then(|x| x.as_str()).unwrap_or("-");
+        let date = v.get("from").and_then(|x| x.as_str()).unwrap_or("-");
+        let lines = v.get("lines").and_then(|x| x.as_array()).cloned().unwrap_or_default();
+
+        // (label, count, amount) in report order
+        let mut by_staff: Vec<(String, i64, Money)> = Vec::new();
+        let mut by_tariff: Vec<(String, i64, Money)> = Vec::new();
+        let mut by_method: Vec<(String, i64, Money)> = Vec::new();
+        let mut total_count = 0;
+        let mut total_amount = Money::ZERO;
+        let mut refunded_count = 0;
+        let mut refunded_amount = Money::ZERO;
+        let money = |value: Option<&serde_json::Value>| Money::from_tnd(value.and_then(|x| x.as_f64()).unwrap_or(0.0));
+        for line in &lines {
+            let staff = line.get("staffName").and_then(|x| x.as_str()).unwrap_or("Staff").to_string();
+            let pass_type = line.get("passType").and_then(|x| x.as_str()).unwrap_or("DAY");
+            let price = money(line.get("price"));
+            let count = line.get("count").and_then(|x| x.as_i64()).unwrap_or(0);
+            let amount = money(line.get("amount"));
+            let tariff = format!("{} {} TND", pass_type, price);
+            let method = method_label(line.get("paymentMethod").and_then(|x| x.as_str()).unwrap_or("CASH"));
+
+            for (bucket, key) in [(&mut by_staff, staff), (&mut by_tariff, tariff), (&mut by_method, method)] {
+                match bucket.iter_mut().find(|(label, _, _)| *label == key) {
+                    Some(entry) => {
+                        entry.1 += count;
+                        entry.2 += amount;
+                    }
+                    None => bucket.push((key, count, amount)),
+                }
+            }
+            total_count += count;
+            total_amount += amount;
+            refunded_count += line.get("refundedCount").and_then(|x| x.as_i64()).unwrap_or(0);
+            refunded_amount += money(line.get("refundedAmount"));
+        }
 
         let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
-        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.day_pass_summary").as_str()));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.day"), date));
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.by_staff"), '-'));
+        if by_staff.is_empty() {
+            push_lines(&mut data, &layout.wrap(&lang.text("ticket.no_pass_sold")));
+        }
+        for (staff, count, amount) in &by_staff {
+            push_lines(&mut data, &layout.row(staff, &format!("{} / {} TND", count, amount)));
+        }
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.by_tariff"), '-'));
+        for (tariff, count, _) in &by_tariff {
+            push_lines(&mut data, &layout.row(tariff, &format!("x{}", count)));
+        }
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.by_payment"), '-'));
+        for (method, count, amount) in &by_method {
+            push_lines(&mut data, &layout.row(method, &format!("{} / {} TND", count, amount)));
+        }
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.total"), '-'));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.passes_sold"), &total_count.to_string()));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.gross_amount"), &format!("{} TND", total_amount)));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.cancelled"), &format!("{} / {} TND", refunded_count, refunded_amount)));
         data.extend_from_slice(&[0x1B, 0x45, 0x01]);
-        data.extend_from_slice(b"STE Dhraiff Services Transport\n");
+        push_lines(&mut data, &layout.row(&lang.label("ticket.net"), &format!("{} TND", total_amount - refunded_amount)));
         data.extend_from_slice(&[0x1B, 0x45, 0x00]);
-        data.extend_from_slice(b"PASS JOURNALIER\n");
-        data.extend_from_slice(b"================================\n");
-        data.extend_from_slice(&[0x1B, 0x61, 0x00]);
-        data.extend_from_slice(format!("Plaque: {}\n", license_plate).as_bytes());
-        data.extend_from_slice(b"Pass journalier: ACHETE\n");
-        data.extend_from_slice(format!("Montant: 2.00 TND\nDate d'achat: {}\n", purchase_date).as_bytes());
-        data.extend_from_slice(format!("Valide pour: {}\nDestination: {}\n", valid_for, destination).as_bytes());
-        data.extend_from_slice(b"================================\n");
+        push_line(&mut data, &layout.separator('='));
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
         data.extend_from_slice(&[0x1B, 0x61, 0x02]);
-        data.extend_from_slice(format!("{}\n", staff_footer).as_bytes());
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
     }
 
-    async fn print_exit_pass_ticket_direct(content: &str, staff_name: Option<String>, config: &PrinterConfig) -> Result<String, String> {
-        let staff_footer = if let Some(name) = staff_name {
-            format!("Émis par: {}", name)
+    fn build_exit_pass_ticket_bytes(ticket: &ExitPassTicket, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::ticket_staff_footer(lang, staff_name, &ticket.staff_name);
+
+        let license_plate = if ticket.license_plate.is_empty() { "N/A" } else { ticket.license_plate.as_str() };
+        let vehicle_capacity = ticket.vehicle_capacity;
+        let exit_time = ticket.exit_time.as_str();
+        let station_name = if ticket.station_name.is_empty() { "N/A" } else { ticket.station_name.as_str() };
+        let base_price = ticket.base_price;
+        let total_price = ticket.total_price;
+        let previous = ticket.previous();
+
+        let mut data: Vec<u8> = Vec::new();
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.exit_pass").as_str()));
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.current_vehicle"), '-'));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.plate"), license_plate));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.capacity"), &lang.text_with("ticket.seat_count", &[("count", &vehicle_capacity)])));
+        if !exit_time.is_empty() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.exit_time"), exit_time));
+        }
+        if let Some(trip_number) = ticket.trip_number {
+            push_lines(&mut data, &layout.wrap(&lang.text_with("ticket.trip_of_day", &[("number", &trip_number)])));
+        }
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.previous_vehicle"), '-'));
+        if let Some(previous) = &previous {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.plate"), &previous.license_plate));
+            push_lines(&mut data, &layout.row(&lang.label("ticket.exit_time"), &previous.exit_time));
         } else {
-            if let Ok(parsed_data) = serde_json::from_str::<serde_json::Value>(content) {
-                if let Some(staff_name_from_data) = parsed_data.get("staffName").and_then(|v| v.as_str()) {
-                    format!("Émis par: {}", staff_name_from_data)
-                } else {
-                    "Émis par: Staff".to_string()
+            push_lines(&mut data, &layout.wrap(&lang.text("ticket.no_previous_vehicle")));
+        }
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.destination"), '-'));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.station"), station_name));
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.pricing"), '-'));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.seat_price"), &format!("{} TND", base_price)));
+        match ticket.booked_seats {
+            // Totals from the bookings on the queue entry: what was sold and what the driver gets
+            Some(seats_sold) => {
+                push_lines(&mut data, &layout.row(&lang.label("ticket.seats_sold"), &format!("{}/{}", seats_sold, vehicle_capacity)));
+                if let Some(collected) = ticket.amount_collected {
+                    push_lines(&mut data, &layout.row(&lang.label("ticket.amount_collected"), &format!("{} TND", collected)));
                 }
-            } else {
-                "Émis par: Staff".to_string()
+                push_lines(&mut data, &layout.row(&lang.label("ticket.driver_due"), &format!("{} TND", total_price)));
             }
-        };
+            None => {
+                push_lines(&mut data, &layout.row(&lang.label("ticket.vehicle_capacity"), &lang.text_with("ticket.seat_count", &[("count", &vehicle_capacity)])));
+                push_lines(&mut data, &layout.row(&lang.label("ticket.total_due"), &format!("{} TND", total_price)));
+            }
+        }
+        push_line(&mut data, &layout.separator('='));
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
+        data.extend_from_slice(&[0x1B, 0x61, 0x02]);
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
 
-        let v: serde_json::Value = serde_json::from_str(content).unwrap_or(serde_json::json!({}));
-        let license_plate = v.get("licensePlate").and_then(|x| x.as_str()).unwrap_or("N/A");
-        let vehicle_capacity = v.get("vehicleCapacity").and_then(|x| x.as_i64()).unwrap_or(8);
-        let exit_time = v.get("exitTime").and_then(|x| x.as_str()).unwrap_or("");
-        let station_name = v.get("stationName").and_then(|x| x.as_str()).unwrap_or("N/A");
-        let base_price = v.get("basePrice").and_then(|x| x.as_f64()).unwrap_or(0.0);
-        let total_price = v.get("totalPrice").and_then(|x| x.as_f64()).unwrap_or(0.0);
-        let prev_plate = v.get("previousVehicle").and_then(|pv| pv.get("licensePlate")).and_then(|x| x.as_str());
-        let prev_exit = v.get("previousVehicle").and_then(|pv| pv.get("exitTime")).and_then(|x| x.as_str());
+        data
+    }
+
+    // Driver's copy of a departure: gross, what the station keeps, and the net paid out
+    fn build_settlement_slip_bytes(slip: &SettlementSlip, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::ticket_staff_footer(lang, staff_name, &slip.staff_name);
+        let settlement = &slip.settlement;
 
         let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
-        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.settlement_slip").as_str()));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.plate"), if slip.license_plate.is_empty() { "N/A" } else { &slip.license_plate }));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.station"), if slip.destination_name.is_empty() { "N/A" } else { &slip.destination_name }));
+        if !slip.exit_time.is_empty() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.exit_time"), &slip.exit_time));
+        }
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.settlement"), '-'));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.seats_sold"), &settlement.seats_sold.to_string()));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.amount_collected"), &format!("{} TND", settlement.gross)));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.commission"), &format!("-{} TND", settlement.commission)));
+        if settlement.day_pass_fee.is_positive() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.day_pass_field"), &format!("-{} TND", settlement.day_pass_fee)));
+        }
         data.extend_from_slice(&[0x1B, 0x45, 0x01]);
-        data.extend_from_slice(b"STE Dhraiff Services Transport\n");
+        push_lines(&mut data, &layout.row(&lang.label("ticket.net_to_driver"), &format!("{} TND", settlement.net_to_driver)));
         data.extend_from_slice(&[0x1B, 0x45, 0x00]);
-        data.extend_from_slice(b"PASS DE SORTIE\n");
-        data.extend_from_slice(b"================================\n");
-        data.extend_from_slice(&[0x1B, 0x61, 0x00]);
-        data.extend_from_slice(b"VEHICULE ACTUEL:\n");
-        data.extend_from_slice(format!("Plaque: {}\n", license_plate).as_bytes());
-        data.extend_from_slice(format!("Capacite: {} places\n", vehicle_capacity).as_bytes());
-        if !exit_time.is_empty() { data.extend_from_slice(format!("Heure de sortie: {}\n", exit_time).as_bytes()); }
-        data.extend_from_slice(b"\n");
-        data.extend_from_slice(b"VEHICULE PRECEDENT:\n");
-        if let (Some(pp), Some(pe)) = (prev_plate, prev_exit) {
-            data.extend_from_slice(format!("Plaque: {}\nHeure de sortie: {}\n", pp, pe).as_bytes());
-        } else {
-            data.extend_from_slice(b"Aucun vehicule precedent aujourd'hui\n");
-        }
-        data.extend_from_slice(b"\nDESTINATION:\n");
-        data.extend_from_slice(format!("Station: {}\n\n", station_name).as_bytes());
-        data.extend_from_slice(b"TARIFICATION:\n");
-        data.extend_from_slice(format!("Prix par place: {:.2} TND\n", base_price).as_bytes());
-        data.extend_from_slice(format!("Capacite vehicule: {} places\n", vehicle_capacity).as_bytes());
-        data.extend_from_slice(format!("TOTAL A RECEVOIR: {:.2} TND\n", total_price).as_bytes());
-        data.extend_from_slice(b"================================\n");
+        push_line(&mut data, &layout.separator('='));
         data.extend_from_slice(&[0x1B, 0x61, 0x01]);
-        let date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S");
-        data.extend_from_slice(format!("Date: {}\n", date).as_bytes());
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
         data.extend_from_slice(&[0x1B, 0x61, 0x02]);
-        data.extend_from_slice(format!("{}\n", staff_footer).as_bytes());
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
     }
 
-    async fn print_talon_direct(content: &str, staff_name: Option<String>, config: &PrinterConfig) -> Result<String, String> {
-        let staff_footer = if let Some(name) = staff_name {
-            format!("Émis par: {}", name)
-        } else {
-            if let Ok(parsed_data) = serde_json::from_str::<serde_json::Value>(content) {
-                if let Some(staff_name_from_data) = parsed_data.get("staffName").and_then(|v| v.as_str()) {
-                    format!("Émis par: {}", staff_name_from_data)
-                } else {
-                    "Émis par: Staff".to_string()
-                }
-            } else {
-                "Émis par: Staff".to_string()
-            }
-        };
+    // Kept in the till in place of the cash paid out, with room for the receiver's signature
+    fn build_expense_voucher_bytes(voucher: &ExpenseVoucher, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::ticket_staff_footer(lang, staff_name, &voucher.staff_name);
 
         let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
-        data.extend_from_slice(&[0x1B, 0x61, 0x00]);
-        data.extend_from_slice(content.as_bytes());
-        data.extend_from_slice(b"\n");
-        data.extend_from_slice(b"================================\n");
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.expense_voucher").as_str()));
+        if !voucher.ticket_number.is_empty() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.ticket_number"), &voucher.ticket_number));
+        }
+        push_lines(&mut data, &layout.row(&lang.label("ticket.category"), &voucher.category));
+        if let Some(note) = voucher.note.as_deref().filter(|n| !n.trim().is_empty()) {
+            push_lines(&mut data, &layout.wrap(&format!("{} {}", lang.label("ticket.reason"), note)));
+        }
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]);
+        push_lines(&mut data, &layout.row(&lang.label("ticket.amount_due"), &format!("{} TND", voucher.amount)));
+        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
+        push_line(&mut data, "");
+        push_line(&mut data, &format!("{} ____________", lang.label("ticket.received_by")));
+        push_line(&mut data, &layout.separator('='));
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
         data.extend_from_slice(&[0x1B, 0x61, 0x02]);
-        data.extend_from_slice(format!("{}\n", staff_footer).as_bytes());
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
+    }
+
+    // Count that missed the expected cash: the notes counted, the difference, two signatures
+    fn build_cash_variance_slip_bytes(slip: &CashVarianceSlip, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::ticket_staff_footer(lang, staff_name, &Some(slip.cashier_name.clone()).filter(|n| !n.is_empty()));
+        let variance = if slip.variance.is_positive() { format!("+{}", slip.variance) } else { slip.variance.to_string() };
+
+        let mut data: Vec<u8> = Vec::new();
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.cash_variance_slip").as_str()));
+        if !slip.ticket_number.is_empty() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.ticket_number"), &slip.ticket_number));
+        }
+        push_lines(&mut data, &layout.row(&lang.label("ticket.cashier"), &slip.cashier_name));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.day"), &slip.date));
+        push_line(&mut data, &layout.centered_separator(&lang.text("ticket.section.cash_count"), '-'));
+        for line in &slip.denominations {
+            push_lines(&mut data, &layout.row(&format!("{} x {}", line.denomination, line.count), &format!("{} TND", line.denomination * line.count)));
+        }
+        push_line(&mut data, &layout.separator('-'));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.counted"), &format!("{} TND", slip.counted)));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.expected"), &format!("{} TND", slip.expected)));
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]);
+        push_lines(&mut data, &layout.row(&lang.label("ticket.variance"), &format!("{} TND", variance)));
+        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
+        push_lines(&mut data, &layout.row(&lang.label("ticket.threshold"), &format!("{} TND", slip.threshold)));
+        push_line(&mut data, "");
+        push_line(&mut data, &format!("{} ____________", lang.label("ticket.cashier")));
+        push_line(&mut data, "");
+        push_line(&mut data, &format!("{} ____________", lang.label("ticket.supervisor")));
+        push_line(&mut data, &layout.separator('='));
         data.extend_from_slice(&[0x1B, 0x61, 0x01]);
-        let date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S");
-        data.extend_from_slice(format!("Date: {}\n", date).as_bytes());
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
+        data.extend_from_slice(&[0x1B, 0x61, 0x02]);
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
     }
 
-    async fn print_standard_ticket_direct(content: &str, config: &PrinterConfig) -> Result<String, String> {
+    // Seats a customer gave back: what they cost, the late-cancellation fee, what is handed back
+    fn build_cancellation_slip_bytes(slip: &CancellationSlip, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::ticket_staff_footer(lang, staff_name, &None);
+
         let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.cancellation_slip").as_str()));
+        if !slip.ticket_number.is_empty() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.ticket_number"), &slip.ticket_number));
+        }
+        push_lines(&mut data, &layout.row(&lang.label("ticket.code"), &slip.verification_code));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.station"), if slip.destination_name.is_empty() { "N/A" } else { &slip.destination_name }));
+        if !slip.license_plate.is_empty() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.plate"), &slip.license_plate));
+        }
+        push_lines(&mut data, &layout.row(&lang.label("ticket.seats_cancelled"), &slip.seats.to_string()));
+        push_lines(&mut data, &layout.row(&lang.label("ticket.amount"), &format!("{} TND", slip.refunded)));
+        if slip.fee.is_positive() {
+            push_lines(&mut data, &layout.row(&lang.label("ticket.cancellation_fee"), &format!("-{} TND", slip.fee)));
+        }
+        data.extend_from_slice(&[0x1B, 0x45, 0x01]);
+        push_lines(&mut data, &layout.row(&lang.label("ticket.to_refund"), &format!("{} TND", slip.net_refund())));
+        data.extend_from_slice(&[0x1B, 0x45, 0x00]);
+        push_line(&mut data, &layout.separator('='));
         data.extend_from_slice(&[0x1B, 0x61, 0x01]);
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
+        data.extend_from_slice(&[0x1B, 0x61, 0x02]);
+        push_lines(&mut data, &layout.wrap(&staff_footer));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
+    }
+
+    // Queue of one destination as a table in Font B, for the lane entrance
+    fn build_queue_board_bytes(board: &QueueBoard, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let wide = layout.condensed();
+        // Position, plate and seats are fixed columns; the status takes what is left
+        let columns = |position: &str, plate: &str, seats: &str, status: &str| {
+            let cell = |text: &str, width: usize| {
+                let text: String = text.chars().take(width).collect();
+                format!("{}{}", text, " ".repeat(width.saturating_sub(TextLayout::display_width(&text))))
+            };
+            let status_width = wide.width().saturating_sub(4 + 16 + 8);
+            format!("{}{}{}{}", cell(position, 4), cell(plate, 16), cell(seats, 8), cell(status, status_width)).trim_end().to_string()
+        };
+
+        let mut data: Vec<u8> = Vec::new();
+        Self::push_ticket_header(&mut data, layout, Some(lang.text("ticket.queue_board").as_str()));
         data.extend_from_slice(&[0x1B, 0x45, 0x01]);
-        data.extend_from_slice(b"STE Dhraiff Services Transport\n");
+        push_lines(&mut data, &layout.row(&lang.label("ticket.destination"), &board.destination_name));
         data.extend_from_slice(&[0x1B, 0x45, 0x00]);
-        data.extend_from_slice(b"================================\n");
+        data.extend_from_slice(&[0x1B, 0x4D, 0x01]); // Font B
+        push_line(&mut data, &wide.separator('-'));
+        push_line(&mut data, &columns("#", &lang.text("ticket.plate"), &lang.text("ticket.seats"), &lang.text("ticket.status")));
+        push_line(&mut data, &wide.separator('-'));
+        if board.vehicles.is_empty() {
+            push_lines(&mut data, &wide.wrap(&lang.text("ticket.queue_empty")));
+        }
+        for vehicle in &board.vehicles {
+            let seats = format!("{}/{}", vehicle.available_seats, vehicle.total_seats);
+            let status = match vehicle.status.as_str() {
+                "WAITING" | "LOADING" | "READY" => lang.text(&format!("ticket.queue_status.{}", vehicle.status.to_lowercase())),
+                other => other.to_string(),
+            };
+            push_line(&mut data, &columns(&vehicle.position.to_string(), &vehicle.license_plate, &seats, &status));
+        }
+        push_line(&mut data, &wide.separator('='));
+        data.extend_from_slice(&[0x1B, 0x4D, 0x00]); // Font A
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
+    }
+
+    fn build_talon_bytes(content: &str, staff_name: Option<String>, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let staff_footer = Self::staff_footer(lang, staff_name, content);
+
+        let mut data: Vec<u8> = Vec::new();
+        data.extend_from_slice(layout.model().init);
         data.extend_from_slice(&[0x1B, 0x61, 0x00]);
-        data.extend_from_slice(content.as_bytes());
-        data.extend_from_slice(b"\n");
-        data.extend_from_slice(b"================================\n");
+        push_lines(&mut data, &layout.wrap(content));
+        push_line(&mut data, &layout.separator('='));
+        data.extend_from_slice(&[0x1B, 0x61, 0x02]);
+        push_lines(&mut data, &layout.wrap(&staff_footer));
         data.extend_from_slice(&[0x1B, 0x61, 0x01]);
-        let date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S");
-        data.extend_from_slice(format!("Date: {}\nMerci de votre confiance!\n", date).as_bytes());
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
+    }
+
+    fn build_standard_ticket_bytes(content: &str, printed_at: &str, layout: &TextLayout) -> Vec<u8> {
+        let lang = layout.language();
+        let mut data: Vec<u8> = Vec::new();
+        Self::push_ticket_header(&mut data, layout, None);
+        push_lines(&mut data, &layout.wrap(content));
+        push_line(&mut data, &layout.separator('='));
+        data.extend_from_slice(&[0x1B, 0x61, 0x01]);
+        push_line(&mut data, &format!("{} {}", lang.label("ticket.date"), printed_at));
+        push_line(&mut data, &lang.text("ticket.thanks_for_trust"));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
     }
 
-    async fn print_receipt_direct(content: &str, config: &PrinterConfig) -> Result<String, String> {
+    fn build_receipt_bytes(content: &str, layout: &TextLayout) -> Vec<u8> {
         let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
+        data.extend_from_slice(layout.model().init);
         data.extend_from_slice(&[0x1B, 0x61, 0x00]);
-        data.extend_from_slice(content.as_bytes());
-        data.extend_from_slice(b"\n");
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        push_lines(&mut data, &layout.wrap(content));
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
     }
 
-    async fn print_qr_code_direct(content: &str, config: &PrinterConfig) -> Result<String, String> {
-        let qr_content = format!("QR DATA:\n{}", content);
+    fn build_qr_code_bytes(content: &str, layout: &TextLayout) -> Vec<u8> {
         let mut data: Vec<u8> = Vec::new();
-        data.extend_from_slice(&[0x1B, 0x40]);
+        data.extend_from_slice(layout.model().init);
         data.extend_from_slice(&[0x1B, 0x61, 0x01]);
         data.extend_from_slice(&[0x1B, 0x45, 0x01]);
-        data.extend_from_slice(qr_content.as_bytes());
-        data.extend_from_slice(b"\n");
+        push_line(&mut data, "QR DATA:");
+        push_lines(&mut data, &layout.wrap(content));
         data.extend_from_slice(&[0x1B, 0x45, 0x00]);
-        data.extend_from_slice(b"\n\n\n");
-        data.extend_from_slice(&[0x1D, 0x56, 0x00]);
-        
-        Self::send_tcp_bytes_direct(config, &data).await
+        Self::push_feed_and_cut(&mut data, layout);
+
+        data
+    }
+
+    /// Route raw ESC/POS bytes to the backend selected in the printer config
+    async fn send_bytes_direct(config: &PrinterConfig, bytes: &[u8]) -> Result<String, String> {
+        if Self::simulation_enabled() {
+            return MockTransport::get_instance().send(config, bytes);
+        }
+        // Builders work in UTF-8; convert to the printer's code page on the way out
+        let bytes = encoding::encode_job(bytes, config.output_code_page());
+        match config.backend {
+            PrinterBackend::Network => Self::send_tcp_bytes_direct(config, &bytes).await,
+            PrinterBackend::Spooler => Self::send_spooler_bytes_direct(config, &bytes).await,
+        }
+    }
+
+    async fn send_spooler_bytes_direct(config: &PrinterConfig, bytes: &[u8]) -> Result<String, String> {
+        let printer_name = config
+            .system_name
+            .as_ref()
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+            .ok_or_else(|| "No system printer name configured for spooler backend".to_string())?;
+
+        println!("🖨️ [SPOOLER] Sending {} bytes to '{}'", bytes.len(), printer_name);
+        let data = bytes.to_vec();
+        task::spawn_blocking(move || spooler::send_raw(&printer_name, "Nqlix ticket", &data))
+            .await
+            .map_err(|e| format!("Spooler task failed: {}", e))??;
+
+        Ok("Print job completed successfully".to_string())
     }
 
     async fn send_tcp_bytes_direct(config: &PrinterConfig, bytes: &[u8]) -> Result<String, String> {
@@ -1477,43 +2735,974 @@ try {{
 
     // Public methods for adding jobs to the queue
     pub async fn queue_print_job(&self, job_type: PrintJobType, content: String, staff_name: Option<String>, priority: u8) -> Result<String, String> {
-        let job_id = uuid::Uuid::new_v4().to_string();
-        let job = QueuedPrintJob {
-            id: job_id.clone(),
-            job_type,
-            content,
-            staff_name,
-            priority,
-            created_at: chrono::Utc::now(),
-            retry_count: 0,
-        };
-
-        // Send job to the queue processor
-        if let Ok(sender_guard) = self.print_queue_sender.lock() {
-            if let Some(sender) = sender_guard.as_ref() {
-                sender.send(job)
-                    .map_err(|e| format!("Failed to queue print job: {}", e))?;
-                
-                println!("📋 [QUEUE] Job {} queued successfully", job_id);
-                Ok(format!("Print job {} queued successfully", job_id))
-            } else {
-                Err("Print queue processor not initialized".to_string())
+        let job = QueuedPrintJob { priority, ..QueuedPrintJob::new(job_type, content, staff_name) };
+        let job_id = job.id.clone();
+        self.submit(PrintBatch { id: job_id.clone(), jobs: vec![job], reported: false }).await?;
+        println!("📋 [QUEUE] Job {} queued successfully", job_id);
+        Ok(format!("Print job {} queued successfully", job_id))
+    }
+
+    /// Queue jobs that must come out together (a ticket and its talon, an exit pass and its
+    /// settlement slip): they print in order with no other job in between. The whole batch is
+    /// refused when the backlog can't take all of it. Once its last job has been tried, the
+    /// batch's report goes out on `subscribe_batch_reports`. Returns the batch id.
+    pub async fn queue_print_batch(&self, jobs: Vec<QueuedPrintJob>) -> Result<String, String> {
+        if jobs.is_empty() {
+            return Err("Empty print batch".to_string());
+        }
+        let batch_id = uuid::Uuid::new_v4().to_string();
+        let count = jobs.len();
+        self.submit(PrintBatch { id: batch_id.clone(), jobs, reported: true }).await?;
+        println!("📋 [QUEUE] Batch {} of {} jobs queued successfully", batch_id, count);
+        Ok(batch_id)
+    }
+
+    async fn submit(&self, batch: PrintBatch) -> Result<(), String> {
+        // Without a printer, new tickets are archived instead and nothing waits on the printer;
+        // the gate printer of the self check-in still prints its own
+        if crate::ticket_escrow::is_active() && batch.jobs.iter().all(|job| job.escrow_id.is_none() && job.printer.is_none()) {
+            if batch.reported {
+                let printed = batch.jobs.iter().map(|job| job.id.clone()).collect();
+                self.batch_reports.send_replace(Some(PrintBatchReport { batch_id: batch.id.clone(), printed, failed: Vec::new(), error: None }));
             }
-        } else {
-            Err("Failed to access print queue sender".to_string())
+            crate::ticket_escrow::escrow(batch.jobs, self.printer_config.read().await.clone());
+            return Ok(());
         }
+        // Send batch to the queue processor
+        let sender_guard = self.print_queue_sender.lock().await;
+        let sender = sender_guard.as_ref().ok_or("Print queue processor not initialized")?;
+
+        // Count the jobs before sending so the processor never decrements below zero
+        let count = batch.jobs.len();
+        let capacity = self.queue_capacity;
+        if self.backlog_depth.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |depth| (depth + count <= capacity).then_some(depth + count)).is_err() {
+            println!("🚫 [QUEUE] Backlog full, batch {} refused", batch.id);
+            return Err(format!("Printer backlog full ({} jobs waiting), check the printer", capacity));
+        }
+        let result = match sender.try_send(batch) {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::TrySendError::Full(batch)) => {
+                self.backlog_depth.fetch_sub(count, Ordering::SeqCst);
+                println!("🚫 [QUEUE] Backlog full, batch {} refused", batch.id);
+                Err(format!("Printer backlog full ({} jobs waiting), check the printer", capacity))
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                self.backlog_depth.fetch_sub(count, Ordering::SeqCst);
+                Err("Failed to queue print job: print queue processor stopped".to_string())
+            }
+        };
+        Self::publish_backlog(&self.backlog_depth, self.queue_capacity, &self.backlog_events);
+        result
     }
 
-    pub fn get_print_queue_status(&self) -> Result<PrintQueueStatus, String> {
-        let mut status = self.queue_status.lock().map_err(|e| e.to_string())?;
-        status.queue_length = self.print_queue.lock().map(|q| q.len()).unwrap_or(0);
+    pub async fn get_print_queue_status(&self) -> Result<PrintQueueStatus, String> {
+        let queue_length = self.print_queue.lock().await.len();
+        let mut status = self.queue_status.lock().await;
+        status.queue_length = queue_length;
+        status.backlog = self.backlog_depth.load(Ordering::SeqCst);
+        status.capacity = self.queue_capacity;
         Ok(status.clone())
     }
 
-    pub fn get_print_queue_length(&self) -> Result<usize, String> {
-        Ok(self.print_queue.lock().map_err(|e| e.to_string())?.len())
+    pub async fn get_print_queue_length(&self) -> Result<usize, String> {
+        Ok(self.print_queue.lock().await.len())
+    }
+
+    /// Usage of a printer, the current one when `printer_id` is None
+    pub async fn get_usage_stats(&self, printer_id: Option<String>) -> Result<PrinterUsageStats, String> {
+        let printer_id = match printer_id {
+            Some(id) => id,
+            None => self.get_current_printer().await?.ok_or("No printer selected")?.id,
+        };
+        Ok(self.usage.stats(&printer_id))
+    }
+
+    pub async fn new_paper_roll(&self, printer_id: Option<String>, roll_length_m: Option<f64>) -> Result<PrinterUsageStats, String> {
+        let printer_id = match printer_id {
+            Some(id) => id,
+            None => self.get_current_printer().await?.ok_or("No printer selected")?.id,
+        };
+        self.usage.new_roll(&printer_id, roll_length_m)
+    }
+
+    /// Hold this printer's jobs (on) or print them now (off); maintenance ends by itself after
+    /// PRINTER_MAINTENANCE_MINUTES
+    pub fn set_maintenance(&self, printer_id: &str, on: bool) -> PrintScheduleDto {
+        self.schedule.set_maintenance(printer_id, on);
+        self.schedule.snapshot()
+    }
+
+    pub fn set_quiet_hours(&self, quiet_hours: QuietHours) -> Result<PrintScheduleDto, String> {
+        self.schedule.set_quiet_hours(quiet_hours)?;
+        Ok(self.schedule.snapshot())
+    }
+
+    pub fn get_print_schedule(&self) -> PrintScheduleDto {
+        self.schedule.snapshot()
+    }
+
+    /// Printers whose roll just ran under the low-paper threshold, for forwarding to the frontend
+    pub fn subscribe_paper_alerts(&self) -> watch::Receiver<Option<PrinterUsageStats>> {
+        self.usage.paper_alerts.subscribe()
     }
 }
 
 // Clone implementation is now derived automatically
-// Clone implementation is now derived automatically
\ No newline at end of file
+// Clone implementation is now derived automatically
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRINTED_AT: &str = "14/10/2026 09:30:00";
+
+    fn build(job_type: PrintJobType, content: &str, staff_name: Option<&str>) -> Vec<u8> {
+        PrinterService::build_job_bytes(&job_type, content, staff_name.map(|s| s.to_string()), PRINTED_AT, &test_config())
+    }
+
+    fn test_config() -> PrinterConfig {
+        PrinterConfig {
+            id: "printer1".to_string(),
+            name: "Imprimante Thermique".to_string(),
+            ip: "127.0.0.1".to_string(),
+            port: 9100,
+            width: 32,
+            timeout: 1000,
+            model: "TM-T20X".to_string(),
+            enabled: true,
+            is_default: true,
+            backend: PrinterBackend::Network,
+            system_name: None,
+            paper: Some(PaperProfile { chars_per_line: 32, feed_lines: 3, cut: CutType::Full }),
+            code_page: CodePage::Cp858,
+            failover: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn booking_ticket_bytes() {
+        let actual = build(PrintJobType::BookingTicket, "Destination: Tunis\nPlaces: 2", Some("Ali Ben Salah"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"RESERVATION\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"Destination: Tunis\nPlaces: 2\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Ali Ben Salah\n".as_bytes(),
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn booking_ticket_itemizes_fare_modifiers() {
+        let content = r#"{"verificationCode":"V-1","licensePlate":"123 TU 4567","destinationName":"Sousse","seatsBooked":2,"totalAmount":8.4,"paymentMethod":"CASH",
+            "fareModifiers":[{"ruleId":"r1","ruleType":"CHILD_FARE","label":"Enfant","quantity":1,"amount":-2.0},{"ruleId":"r2","ruleType":"LUGGAGE","label":"Bagage","quantity":2,"amount":2.0}]}"#;
+        let actual = build(PrintJobType::BookingTicket, content, Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"RESERVATION\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"Code:                        V-1\n",
+            b"Plaque:              123 TU 4567\n",
+            b"Destination:              Sousse\n",
+            b"Places:                        2\n",
+            b"Enfant x1:            -2.000 TND\n",
+            b"Bagage x2:            +2.000 TND\n",
+            b"Montant:               8.400 TND\n",
+            "Paiement:                Espèces\n".as_bytes(),
+            b"================================\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn replacement_booking_ticket_names_the_previous_vehicle() {
+        let content = r#"{"verificationCode":"V-1","licensePlate":"200 TU 1","destinationName":"Sousse","seatsBooked":1,"totalAmount":4.2,"previousLicensePlate":"123 TU 4567"}"#;
+        let actual = build(PrintJobType::BookingTicket, content, Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"RESERVATION\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            "**** CHANGEMENT DE VÉHICULE ****\n".as_bytes(),
+            "Ancien véhicule:     123 TU 4567\n".as_bytes(),
+            b"Code:                        V-1\n",
+            b"Plaque:                 200 TU 1\n",
+            b"Destination:              Sousse\n",
+            b"Places:                        1\n",
+            b"Montant:               4.200 TND\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn entry_ticket_bytes_use_payload_staff_name() {
+        let content = r#"{"licensePlate":"123 TU 4567","queuePosition":3,"destinationName":"Jemmal","entryTime":"09:15","dayPassStatus":"VALID","dayPassPurchaseDate":"14/10/2026 07:00","ticketNumber":"E-001","staffName":"Sami"}"#;
+        let actual = build(PrintJobType::EntryTicket, content, None);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"TICKET D'ENTREE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"----------- VEHICULE -----------\n",
+            b"Plaque:              123 TU 4567\n",
+            b"Position:                      3\n",
+            b"--------- DESTINATION ----------\n",
+            b"Station:                  Jemmal\n",
+            b"-------- HEURE D'ENTREE --------\n",
+            b"09:15\n",
+            b"--------- TARIFICATION ---------\n",
+            b"Pass journalier:          VALIDE\n",
+            b"Achat le:       14/10/2026 07:00\n",
+            b"MONTANT:               0.000 TND\n",
+            "N° Ticket:                 E-001\n".as_bytes(),
+            b"================================\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn entry_ticket_bytes_without_day_pass() {
+        let content = r#"{"licensePlate":"99 TU 1","queuePosition":1,"destinationName":"Moknine","entryTime":"10:00"}"#;
+        let actual = build(PrintJobType::EntryTicket, content, Some("Nour"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"TICKET D'ENTREE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"----------- VEHICULE -----------\n",
+            b"Plaque:                  99 TU 1\n",
+            b"Position:                      1\n",
+            b"--------- DESTINATION ----------\n",
+            b"Station:                 Moknine\n",
+            b"-------- HEURE D'ENTREE --------\n",
+            b"10:00\n",
+            b"--------- TARIFICATION ---------\n",
+            b"Pass journalier:      NON VALIDE\n",
+            b"MONTANT:               2.000 TND\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Nour\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn exit_ticket_bytes() {
+        let actual = build(PrintJobType::ExitTicket, "Plaque: 123 TU 4567", None);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"TICKET DE SORTIE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"Plaque: 123 TU 4567\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\nMerci!\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Staff\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn day_pass_ticket_bytes() {
+        let content = r#"{"licensePlate":"123 TU 4567","amount":2.0,"purchaseDate":"14/10/2026 07:00","validFor":"14/10/2026","destinationName":"Ksar Hellal"}"#;
+        let actual = build(PrintJobType::DayPassTicket, content, Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"PASS JOURNALIER\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"Plaque:              123 TU 4567\n",
+            b"Pass journalier:          ACHETE\n",
+            b"Montant:               2.000 TND\n",
+            b"Date d'achat:   14/10/2026 07:00\n",
+            b"Valide pour:          14/10/2026\n",
+            b"Destination:         Ksar Hellal\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn weekly_pass_ticket_shows_type_and_range() {
+        let content = r#"{"licensePlate":"123 TU 4567","amount":"12","passType":"WEEK","purchaseDate":"14/10/2026 07:00","validFrom":"14/10/2026","validUntil":"20/10/2026","destinationName":"Ksar Hellal"}"#;
+        let actual = build(PrintJobType::DayPassTicket, content, Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"PASS HEBDOMADAIRE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"Plaque:              123 TU 4567\n",
+            b"Pass hebdomadaire:        ACHETE\n",
+            b"Montant:              12.000 TND\n",
+            b"Date d'achat:   14/10/2026 07:00\n",
+            b"Valide du:            14/10/2026\n",
+            b"Valide au:            20/10/2026\n",
+            b"Destination:         Ksar Hellal\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn day_pass_summary_groups_by_staff_tariff_and_payment() {
+        let content = r#"{"from":"2026-10-14","to":"2026-10-14","lines":[
+            {"date":"2026-10-14","staffName":"Sami","passType":"DAY","price":2.0,"count":10,"amount":20.0,"refundedCount":1,"refundedAmount":2.0},
+            {"date":"2026-10-14","staffName":"Sami","passType":"WEEK","price":12.0,"count":1,"amount":12.0,"refundedCount":0,"refundedAmount":0.0},
+            {"date":"2026-10-14","staffName":"Ali","passType":"DAY","paymentMethod":"CARD","price":2.0,"count":4,"amount":8.0,"refundedCount":0,"refundedAmount":0.0}
+        ]}"#;
+        let actual = build(PrintJobType::DayPassSummary, content, Some("Chef"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"RESUME PASS JOURNALIERS\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"Journee:              2026-10-14\n",
+            b"---------- PAR AGENT -----------\n",
+            b"Sami             11 / 32.000 TND\n",
+            b"Ali                4 / 8.000 TND\n",
+            b"---------- PAR TARIF -----------\n",
+            b"DAY 2.000 TND                x14\n",
+            b"WEEK 12.000 TND               x1\n",
+            b"--------- PAR PAIEMENT ---------\n",
+            "Espèces          11 / 32.000 TND\n".as_bytes(),
+            b"Carte              4 / 8.000 TND\n",
+            b"------------ TOTAL -------------\n",
+            b"Pass vendus:                  15\n",
+            b"Montant brut:         40.000 TND\n",
+            b"Annules:           1 / 2.000 TND\n",
+            &[0x1B, 0x45, 0x01],
+            b"NET:                  38.000 TND\n",
+            &[0x1B, 0x45, 0x00],
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Chef\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn exit_pass_ticket_bytes() {
+        let content = r#"{"licensePlate":"123 TU 4567","vehicleCapacity":8,"exitTime":"11:42","stationName":"Jemmal","basePrice":2.5,"totalPrice":20.0,"previousVehicle":{"licensePlate":"55 TU 900","exitTime":"11:10"}}"#;
+        let actual = build(PrintJobType::ExitPassTicket, content, Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"PASS DE SORTIE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"------- VEHICULE ACTUEL --------\n",
+            b"Plaque:              123 TU 4567\n",
+            b"Capacite:               8 places\n",
+            b"Heure de sortie:           11:42\n",
+            b"------ VEHICULE PRECEDENT ------\n",
+            b"Plaque:                55 TU 900\n",
+            b"Heure de sortie:           11:10\n",
+            b"--------- DESTINATION ----------\n",
+            b"Station:                  Jemmal\n",
+            b"--------- TARIFICATION ---------\n",
+            b"Prix par place:        2.500 TND\n",
+            b"Capacite vehicule:      8 places\n",
+            b"TOTAL A RECEVOIR:     20.000 TND\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn exit_pass_ticket_bytes_with_sales() {
+        let content = r#"{"licensePlate":"123 TU 4567","vehicleCapacity":8,"exitTime":"11:42","stationName":"Jemmal","basePrice":2.5,"totalPrice":10.2,"bookedSeats":5,"amountCollected":13.2,"tripNumber":3}"#;
+        let actual = build(PrintJobType::ExitPassTicket, content, Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"PASS DE SORTIE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"------- VEHICULE ACTUEL --------\n",
+            b"Plaque:              123 TU 4567\n",
+            b"Capacite:               8 places\n",
+            b"Heure de sortie:           11:42\n",
+            "Voyage n° 3 du jour\n".as_bytes(),
+            b"------ VEHICULE PRECEDENT ------\n",
+            b"Aucun vehicule precedent\n",
+            b"aujourd'hui\n",
+            b"--------- DESTINATION ----------\n",
+            b"Station:                  Jemmal\n",
+            b"--------- TARIFICATION ---------\n",
+            b"Prix par place:        2.500 TND\n",
+            b"Places vendues:              5/8\n",
+            b"Montant encaisse:     13.200 TND\n",
+            b"DU AU CHAUFFEUR:      10.200 TND\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn settlement_slip_bytes() {
+        let content = r#"{"licensePlate":"123 TU 4567","destinationName":"Jemmal","exitTime":"11:42","seatsSold":8,"gross":33.6,"commission":1.6,"dayPassFee":2.0,"netToDriver":30.0}"#;
+        let actual = build(PrintJobType::SettlementSlip, content, Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"DECOMPTE CHAUFFEUR\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"Plaque:              123 TU 4567\n",
+            b"Station:                  Jemmal\n",
+            b"Heure de sortie:           11:42\n",
+            b"---------- REGLEMENT -----------\n",
+            b"Places vendues:                8\n",
+            b"Montant encaisse:     33.600 TND\n",
+            b"Commission station:   -1.600 TND\n",
+            b"Pass journalier:      -2.000 TND\n",
+            &[0x1B, 0x45, 0x01],
+            b"NET CHAUFFEUR:        30.000 TND\n",
+            &[0x1B, 0x45, 0x00],
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn cancellation_slip_bytes() {
+        let content = r#"{"ticketNumber":"ANN-A1B2C3","verificationCode":"A1B2C3","destinationName":"Jemmal","licensePlate":"123 TU 4567","seats":2,"refunded":8.4,"fee":1.0}"#;
+        let actual = build(PrintJobType::CancellationSlip, content, Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"ANNULATION\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            "N° Ticket:            ANN-A1B2C3\n".as_bytes(),
+            b"Code:                     A1B2C3\n",
+            b"Station:                  Jemmal\n",
+            b"Plaque:              123 TU 4567\n",
+            b"Places annulees:               2\n",
+            b"Montant:               8.400 TND\n",
+            b"Frais d'annulation:   -1.000 TND\n",
+            &[0x1B, 0x45, 0x01],
+            b"A REMBOURSER:          7.400 TND\n",
+            &[0x1B, 0x45, 0x00],
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn cash_variance_slip_bytes() {
+        let content = r#"{"ticketNumber":"ECART-1A2B3C4D","cashierName":"Sami","date":"2026-10-14","denominations":[{"denomination":20,"count":2},{"denomination":0.5,"count":3}],"counted":41.5,"expected":45,"variance":-3.5,"threshold":2}"#;
+        let actual = build(PrintJobType::CashVarianceSlip, content, None);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"ECART DE CAISSE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            "N° Ticket:        ECART-1A2B3C4D\n".as_bytes(),
+            b"Caissier:                   Sami\n",
+            b"Journee:              2026-10-14\n",
+            b"----------- COMPTAGE -----------\n",
+            b"20.000 x 2            40.000 TND\n",
+            b"0.500 x 3              1.500 TND\n",
+            b"--------------------------------\n",
+            b"Compte:               41.500 TND\n",
+            b"Attendu:              45.000 TND\n",
+            &[0x1B, 0x45, 0x01],
+            b"ECART:                -3.500 TND\n",
+            &[0x1B, 0x45, 0x00],
+            b"Seuil:                 2.000 TND\n",
+            b"\n",
+            b"Caissier: ____________\n",
+            b"\n",
+            b"Superviseur: ____________\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn queue_board_bytes() {
+        let content = r#"{"destinationName":"Jemmal","vehicles":[{"position":1,"licensePlate":"123 TU 4567","availableSeats":3,"totalSeats":8,"status":"READY"},{"position":2,"licensePlate":"55 TU 900","availableSeats":8,"totalSeats":8,"status":"WAITING"}]}"#;
+        let actual = build(PrintJobType::QueueBoard, content, None);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"FILE D'ATTENTE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            &[0x1B, 0x45, 0x01],
+            b"Destination:              Jemmal\n",
+            &[0x1B, 0x45, 0x00],
+            &[0x1B, 0x4D, 0x01],
+            b"------------------------------------------\n",
+            b"#   Plaque          Places  Statut\n",
+            b"------------------------------------------\n",
+            b"1   123 TU 4567     3/8     Pret\n",
+            b"2   55 TU 900       8/8     En attente\n",
+            b"==========================================\n",
+            &[0x1B, 0x4D, 0x00],
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn expense_voucher_bytes() {
+        let content = r#"{"ticketNumber":"DEP-1A2B3C4D","amount":4.5,"category":"Nettoyage","note":"Produits","staffName":"Chef"}"#;
+        let actual = build(PrintJobType::ExpenseVoucher, content, None);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"BON DE DEPENSE\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            "N° Ticket:          DEP-1A2B3C4D\n".as_bytes(),
+            b"Categorie:             Nettoyage\n",
+            b"Motif: Produits\n",
+            &[0x1B, 0x45, 0x01],
+            b"MONTANT:               4.500 TND\n",
+            &[0x1B, 0x45, 0x00],
+            b"\n",
+            b"Recu par: ____________\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Chef\n".as_bytes(),
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn talon_bytes() {
+        let actual = build(PrintJobType::Talon, "TALON\nPlaces: 1", Some("Sami"));
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x00],
+            b"TALON\nPlaces: 1\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x02],
+            "Émis par: Sami\n".as_bytes(),
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\n",
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn standard_ticket_bytes() {
+        let actual = build(PrintJobType::StandardTicket, "Bonjour", None);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"STE Dhraiff Services Transport\n",
+            &[0x1B, 0x45, 0x00],
+            b"================================\n",
+            &[0x1B, 0x61, 0x00],
+            b"Bonjour\n",
+            b"================================\n",
+            &[0x1B, 0x61, 0x01],
+            b"Date: 14/10/2026 09:30:00\nMerci de votre confiance!\n",
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn receipt_bytes() {
+        let actual = build(PrintJobType::Receipt, "Total: 5.00 TND", None);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x00],
+            b"Total: 5.00 TND\n",
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn qr_code_bytes() {
+        let actual = build(PrintJobType::QRCode, "BK-42", None);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x01],
+            &[0x1B, 0x45, 0x01],
+            b"QR DATA:\nBK-42\n",
+            &[0x1B, 0x45, 0x00],
+            b"\n\n\n",
+            &[0x1D, 0x56, 0x00],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn mock_transport_records_jobs_and_renders_preview() {
+        let transport = MockTransport::new(None);
+        let data = build(PrintJobType::Receipt, "Total: 5.00 TND", None);
+
+        transport.send(&test_config(), &data).unwrap();
+
+        let jobs = transport.jobs().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].bytes, encoding::encode_job(&data, CodePage::Cp858));
+        assert_eq!(jobs[0].printer_id, "printer1");
+        assert!(jobs[0].preview_path.is_none());
+        assert_eq!(jobs[0].text, "Total: 5.00 TND\n\n\n\n         --- coupe ---\n");
+
+        transport.clear().unwrap();
+        assert!(transport.jobs().unwrap().is_empty());
+    }
+
+    #[test]
+    fn mock_transport_decodes_alignment_and_bold() {
+        let data = build(PrintJobType::StandardTicket, "Bonjour", None);
+        let lines = MockTransport::decode(&data);
+        assert_eq!(lines[0].text, "STE Dhraiff Services Transport");
+        assert_eq!(lines[0].align, 1);
+        assert!(lines[0].bold);
+        assert_eq!(lines[2].text, "Bonjour");
+        assert_eq!(lines[2].align, 0);
+        assert!(!lines[2].bold);
+    }
+
+    #[tokio::test]
+    async fn ticket_preview_matches_print_pipeline() {
+        let service = PrinterService::new();
+        let preview = service
+            .render_ticket_preview("receipt", "Total: 5.00 TND", None)
+            .await
+            .unwrap();
+        assert!(matches!(preview.kind, PrintJobType::Receipt));
+        assert!(preview.monospace);
+        assert_eq!(preview.lines[0].text, "Total: 5.00 TND");
+        assert_eq!(preview.lines.last().unwrap().text, "--- coupe ---");
+        assert!(service.render_ticket_preview("unknown", "", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn refuses_jobs_beyond_backlog_capacity() {
+        let mut service = PrinterService::new();
+        service.queue_capacity = 2;
+        let (tx, _rx) = mpsc::channel(service.queue_capacity);
+        *service.print_queue_sender.lock().await = Some(tx);
+        let backlog = service.subscribe_backlog();
+
+        for _ in 0..2 {
+            assert!(service.queue_print_job(PrintJobType::Receipt, "x".to_string(), None, 0).await.is_ok());
+        }
+        let refused = service.queue_print_job(PrintJobType::Receipt, "x".to_string(), None, 0).await;
+        assert!(refused.unwrap_err().contains("backlog full"));
+
+        let snapshot = backlog.borrow().clone();
+        assert_eq!(snapshot.depth, 2);
+        assert!(snapshot.full);
+        assert_eq!(service.get_print_queue_status().await.unwrap().backlog, 2);
+    }
+
+    #[tokio::test]
+    async fn batches_travel_whole_and_are_refused_whole() {
+        let mut service = PrinterService::new();
+        service.queue_capacity = 3;
+        let (tx, mut rx) = mpsc::channel(service.queue_capacity);
+        *service.print_queue_sender.lock().await = Some(tx);
+
+        let job = |job_type, content: &str| QueuedPrintJob::new(job_type, content.to_string(), None);
+        let batch_id = service
+            .queue_print_batch(vec![job(PrintJobType::BookingTicket, "ticket"), job(PrintJobType::Talon, "talon")])
+            .await
+            .unwrap();
+        let batch = rx.try_recv().unwrap();
+        assert_eq!(batch.id, batch_id);
+        assert_eq!(batch.jobs.iter().map(|j| j.content.as_str()).collect::<Vec<_>>(), vec!["ticket", "talon"]);
+
+        // 2 jobs still count against the backlog: a batch of 2 more doesn't fit, none of it is queued
+        let refused = service.queue_print_batch(vec![job(PrintJobType::BookingTicket, "a"), job(PrintJobType::BookingTicket, "b")]).await;
+        assert!(refused.unwrap_err().contains("backlog full"));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(service.get_print_queue_status().await.unwrap().backlog, 2);
+        assert!(service.queue_print_batch(Vec::new()).await.is_err());
+    }
+
+    #[test]
+    fn model_profiles_drive_cut_width_and_code_page() {
+        assert_eq!(ModelProfile::for_model("tm-t20iii").model, "TM-T20X");
+        assert_eq!(ModelProfile::for_model(" Rongta ").model, "RP-326");
+        assert_eq!(ModelProfile::for_model("XP-80C").model, "GENERIC-80");
+
+        let mut config = test_config();
+        config.model = "RP-326".to_string();
+        config.paper = Some(PaperProfile::mm80());
+        let bytes = PrinterService::build_job_bytes(&PrintJobType::Receipt, "Total", None, "", &config);
+        assert!(bytes.ends_with(&[0x1D, 0x56, 0x41, 0x00]));
+
+        // A 58mm printer without a cutter or the Euro code page
+        config.model = "58mm".to_string();
+        let layout = TextLayout::for_printer(&config);
+        assert_eq!(layout.width(), 32);
+        assert!(PrinterService::build_job_bytes(&PrintJobType::Receipt, "Total", None, "", &config).ends_with(b"\n"));
+        config.code_page = CodePage::Cp858;
+        assert_eq!(config.output_code_page(), CodePage::Cp437);
+    }
+
+    #[test]
+    fn print_job_type_from_kind() {
+        assert!(matches!(PrintJobType::from_kind("day_pass"), Some(PrintJobType::DayPassTicket)));
+        assert!(matches!(PrintJobType::from_kind("ExitPassTicket"), Some(PrintJobType::ExitPassTicket)));
+        assert!(matches!(PrintJobType::from_kind("qr-code"), Some(PrintJobType::QRCode)));
+        assert!(PrintJobType::from_kind("ticket").is_none());
+    }
+
+    #[test]
+    fn layout_wraps_long_text_to_paper_width() {
+        let layout = TextLayout::for_model(PaperProfile::from_width(20), ModelProfile::generic());
+        assert_eq!(layout.wrap("Station: Ksar Hellal Monastir Centre"), vec![
+            "Station: Ksar Hellal".to_string(),
+            "Monastir Centre".to_string(),
+        ]);
+        assert_eq!(layout.wrap("ABCDEFGHIJKLMNOPQRSTUVWXY"), vec![
+            "ABCDEFGHIJKLMNOPQRST".to_string(),
+            "UVWXY".to_string(),
+        ]);
+        // Short lines keep their spacing and explicit breaks
+        assert_eq!(layout.wrap("A  B\nC"), vec!["A  B".to_string(), "C".to_string()]);
+    }
+
+    #[test]
+    fn layout_rows_and_separators() {
+        let layout = TextLayout::for_model(PaperProfile::from_width(20), ModelProfile::generic());
+        assert_eq!(layout.row("Total:", "5.00 TND"), vec!["Total:      5.00 TND".to_string()]);
+        assert_eq!(layout.row("Destination finale:", "Ksar Hellal"), vec![
+            "Destination finale:".to_string(),
+            "         Ksar Hellal".to_string(),
+        ]);
+        assert_eq!(layout.separator('='), "=".repeat(20));
+        assert_eq!(layout.centered_separator("PASS", '-'), "------- PASS -------");
+        // Combining marks do not take a column, lam-alef is one glyph
+        assert_eq!(TextLayout::display_width("e\u{0301}te\u{0301}"), 3);
+        assert_eq!(TextLayout::display_width("سلام"), 3);
+    }
+
+    #[test]
+    fn bilingual_tickets_print_both_languages() {
+        let both = TicketLanguage { lang: crate::i18n::Lang::Fr, bilingual: true };
+        let layout = TextLayout { language: both, ..TextLayout::for_printer(&test_config()) };
+        let data = PrinterService::build_exit_ticket_bytes("Ligne Tunis", Some("Sami".to_string()), PRINTED_AT, &layout);
+        let text = String::from_utf8(data).unwrap();
+        assert!(text.contains("TICKET DE SORTIE / تذكرة خروج\n"));
+        assert!(text.contains("Date / التاريخ: 14/10/2026"));
+        assert!(text.contains("Émis par / أصدرها: Sami\n"));
+
+        let arabic = TextLayout { language: TicketLanguage { lang: crate::i18n::Lang::Ar, bilingual: false }, ..layout };
+        let text = String::from_utf8(PrinterService::build_exit_ticket_bytes("", None, PRINTED_AT, &arabic)).unwrap();
+        assert!(text.contains("تذكرة خروج\n"));
+        assert!(!text.contains("TICKET DE SORTIE"));
+    }
+
+    #[test]
+    fn paper_profile_drives_feed_and_cut() {
+        let mut config = test_config();
+        config.paper = Some(PaperProfile::mm58());
+        let actual = PrinterService::build_job_bytes(&PrintJobType::Receipt, "Total: 5.00 TND", None, PRINTED_AT, &config);
+        let expected: Vec<u8> = [
+            &[0x1B, 0x40][..],
+            &[0x1B, 0x61, 0x00],
+            b"Total: 5.00 TND\n",
+            b"\n\n\n\n",
+            &[0x1D, 0x56, 0x01],
+        ].concat();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn paper_profile_defaults_from_width() {
+        let mut config = test_config();
+        config.paper = None;
+        config.width = 48;
+        assert_eq!(config.paper_profile(), PaperProfile::mm80());
+        config.width = 32;
+        assert_eq!(config.paper_profile(), PaperProfile::mm58());
+        assert_eq!(TextLayout::for_printer(&config).width(), 32);
+    }
+
+    #[test]
+    fn usage_counts_lines_and_cuts_and_estimates_the_roll() {
+        let bytes: Vec<u8> = [&[0x1B, 0x40][..], b"A\nB\n\n\n", &[0x1D, 0x56, 0x01]].concat();
+        let job = DailyUsage::of_job(&bytes);
+        assert_eq!(job, DailyUsage { jobs: 1, bytes: 11, lines: 4, cuts: 1 });
+
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let mut record = PrinterUsageRecord { roll_length_m: Some(0.06), ..Default::default() };
+        record.record(day, &job);
+        record.record(day, &job);
+        let stats = record.stats("p1", 80.0, 0.02);
+        assert_eq!(stats.days, vec![DayUsage { date: day, usage: DailyUsage { jobs: 2, bytes: 22, lines: 8, cuts: 2 } }]);
+        assert!((stats.used_m - 8.0 * LINE_HEIGHT_MM / 1000.0).abs() < 1e-9);
+        assert!(!stats.paper_low);
+        for _ in 0..2 {
+            record.record(day, &job);
+        }
+        assert!(record.stats("p1", 80.0, 0.02).paper_low);
+
+        record.record(day + chrono::Duration::days(USAGE_DAYS_KEPT), &job);
+        assert_eq!(record.days.len(), 1);
+    }
+
+    #[test]
+    fn failover_chain_tries_enabled_backups_in_order() {
+        let backup = |id: &str, enabled: bool| PrinterConfig { id: id.to_string(), name: format!("Secours {}", id), enabled, ..test_config() };
+        let mut config = test_config();
+        assert_eq!(config.failover_chain().len(), 1);
+        config.failover = vec![backup("b1", false), PrinterConfig { failover: vec![backup("nested", true)], ..backup("b2", true) }];
+        let ids: Vec<&str> = config.failover_chain().iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["printer1", "b2"]);
+
+        // A config saved before failover existed has no backups
+        let mut saved = serde_json::to_value(test_config()).unwrap();
+        saved.as_object_mut().unwrap().remove("failover");
+        assert!(serde_json::from_value::<PrinterConfig>(saved).unwrap().failover.is_empty());
+
+        let mut data = Vec::new();
+        PrinterService::push_device_banner(&mut data, &TextLayout::for_printer(&config), "Secours b2");
+        assert!(String::from_utf8_lossy(&data).contains("Imprimé sur Secours b2"));
+    }
+}
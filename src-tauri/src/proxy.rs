@@ -0,0 +1,215 @@
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Requests from the UI to the local node, relayed by the backend. One client keeps its
+// connections to the node open between calls. Without a server URL the node found by the LAN
+// scan is used, remembered for a few minutes instead of rescanning on every call and forgotten
+// when it stops answering. Callers get the status and headers with the body. Endpoints off the
+// proxy allowlist are refused before anything is sent.
+
+const FALLBACK_SERVER: &str = "http://192.168.192.100:3001";
+const SERVER_TTL: Duration = Duration::from_secs(5 * 60);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+const MIN_TIMEOUT_MS: u64 = 500;
+const MAX_TIMEOUT_MS: u64 = 60_000;
+const MAX_RETRIES: u8 = 3;
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .pool_max_idle_per_host(8)
+        .build()
+        .unwrap_or_default()
+});
+
+static SERVER: Lazy<Mutex<Option<CachedServer>>> = Lazy::new(|| Mutex::new(None));
+
+struct CachedServer {
+    url: String,
+    found_at: Instant,
+}
+
+impl CachedServer {
+    fn fresh(&self, now: Instant) -> bool {
+        now.duration_since(self.found_at) < SERVER_TTL
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProxyOptions {
+    /// Whole request, connection included; 10 s when not given
+    pub timeout_ms: Option<u64>,
+    /// Further attempts after a connection failure or timeout, for GET, PUT and DELETE only:
+    /// a POST sent twice could book twice
+    pub retries: Option<u8>,
+}
+
+impl ProxyOptions {
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).clamp(MIN_TIMEOUT_MS, MAX_TIMEOUT_MS))
+    }
+
+    fn attempts(&self, method: &Method) -> u8 {
+        let idempotent = matches!(*method, Method::GET | Method::PUT | Method::DELETE);
+        if idempotent {
+            1 + self.retries.unwrap_or(0).min(MAX_RETRIES)
+        } else {
+            1
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyResponse {
+    pub status: u16,
+    pub ok: bool,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub url: String,
+    pub attempts: u8,
+    pub duration_ms: u64,
+}
+
+fn method(name: &str) -> Result<Method, String> {
+    match name.to_uppercase().as_str() {
+        "GET" => Ok(Method::GET),
+        "POST" => Ok(Method::POST),
+        "PUT" => Ok(Method::PUT),
+        "PATCH" => Ok(Method::PATCH),
+        "DELETE" => Ok(Method::DELETE),
+        _ => Err(format!("Unsupported method: {}", name)),
+    }
+}
+
+fn header_map(headers: Option<HashMap<String, String>>) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    map.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    for (k, v) in headers.unwrap_or_default() {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(k.as_bytes()), HeaderValue::from_str(&v)) {
+            map.insert(name, value);
+        }
+    }
+    map
+}
+
+/// The node found by the last scan while fresh, or a new scan
+async fn discovered_server() -> String {
+    if let Ok(cached) = SERVER.lock() {
+        if let Some(server) = cached.as_ref().filter(|s| s.fresh(Instant::now())) {
+            return server.url.clone();
+        }
+    }
+    let url = match crate::discover_local_servers().await {
+        Ok(result) => result.servers.first().map(|s| s.url.clone()),
+        Err(_) => None,
+    };
+    match url {
+        Some(url) => {
+            if let Ok(mut cached) = SERVER.lock() {
+                *cached = Some(CachedServer { url: url.clone(), found_at: Instant::now() });
+            }
+            url
+        }
+        None => FALLBACK_SERVER.to_string(),
+    }
+}
+
+fn forget_server(url: &str) {
+    if let Ok(mut cached) = SERVER.lock() {
+        if cached.as_ref().is_some_and(|s| s.url == url) {
+            *cached = None;
+        }
+    }
+}
+
+pub async fn request(
+    method_name: &str,
+    endpoint: &str,
+    body: Option<String>,
+    server_url: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    options: ProxyOptions,
+) -> Result<ProxyResponse, String> {
+    let method = method(method_name)?;
+    crate::proxy_allowlist::check(method.as_str(), endpoint, server_url.as_deref()).await?;
+    let discovered = server_url.is_none();
+    let base_url = match server_url {
+        Some(url) => url,
+        None => discovered_server().await,
+    };
+    let url = format!("{}{}", base_url, endpoint);
+    let headers = header_map(headers);
+    let attempts = options.attempts(&method);
+    let started = Instant::now();
+
+    let mut attempt = 0;
+    let response = loop {
+        attempt += 1;
+        let mut req = CLIENT.request(method.clone(), &url).headers(headers.clone()).timeout(options.timeout());
+        if let Some(body) = &body {
+            req = req.body(body.clone());
+        }
+        match req.send().await {
+            Ok(response) => break response,
+            Err(e) if attempt < attempts && (e.is_connect() || e.is_timeout()) => {
+                tokio::time::sleep(RETRY_DELAY * attempt as u32).await;
+            }
+            Err(e) => {
+                if discovered && (e.is_connect() || e.is_timeout()) {
+                    forget_server(&base_url);
+                }
+                return Err(e.to_string());
+            }
+        }
+    };
+
+    let status = response.status();
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    Ok(ProxyResponse {
+        status: status.as_u16(),
+        ok: status.is_success(),
+        headers,
+        body,
+        url,
+        attempts: attempt,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_bound_timeouts_and_retry_only_idempotent_calls() {
+        let options = ProxyOptions { timeout_ms: Some(50), retries: Some(9) };
+        assert_eq!(options.timeout(), Duration::from_millis(MIN_TIMEOUT_MS));
+        assert_eq!(options.attempts(&Method::GET), 1 + MAX_RETRIES);
+        assert_eq!(options.attempts(&Method::POST), 1);
+        assert_eq!(options.attempts(&Method::PATCH), 1);
+        assert_eq!(ProxyOptions::default().timeout(), Duration::from_millis(DEFAULT_TIMEOUT_MS));
+        assert_eq!(ProxyOptions::default().attempts(&Method::DELETE), 1);
+        assert_eq!(method("get"), Ok(Method::GET));
+        assert!(method("TRACE").is_err());
+
+        let found_at = Instant::now();
+        let server = CachedServer { url: FALLBACK_SERVER.to_string(), found_at };
+        assert!(server.fresh(found_at + Duration::from_secs(60)));
+        assert!(!server.fresh(found_at + SERVER_TTL));
+    }
+}
@@ -0,0 +1,178 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::RwLock;
+
+use crate::audit;
+use crate::db;
+use crate::i18n;
+use crate::DB_POOL;
+
+// Endpoints of the local node the UI may reach through proxy_localnode: path prefixes, each with
+// the methods allowed under it. Anything else is refused before it leaves the PC and recorded in
+// the audit log. The list is the proxy_allowlist setting (JSON in station_settings), cached here;
+// the default covers what the app itself calls. Admins only may change it.
+
+const POLICY_KEY: &str = "proxy_allowlist";
+pub const DENIED_EVENT: &str = "PROXY_REQUEST_DENIED";
+const METHODS: [&str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+static ALLOWLIST: Lazy<RwLock<ProxyAllowlist>> = Lazy::new(|| RwLock::new(ProxyAllowlist::default()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AllowRule {
+    /// Matched whole path segments at a time: "/api/queue" allows "/api/queue/exit", not "/api/queue-booking"
+    pub prefix: String,
+    pub methods: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProxyAllowlist {
+    /// Off lets every request through, as before the list existed
+    pub enabled: bool,
+    pub rules: Vec<AllowRule>,
+}
+
+impl Default for ProxyAllowlist {
+    fn default() -> Self {
+        let rule = |prefix: &str, methods: &[&str]| AllowRule { prefix: prefix.to_string(), methods: methods.iter().map(|m| m.to_string()).collect() };
+        ProxyAllowlist {
+            enabled: true,
+            rules: vec![
+                rule("/health", &["GET"]),
+                rule("/api/auth", &["GET", "POST"]),
+                rule("/api/public", &["GET"]),
+                rule("/api/queue", &["GET", "POST", "PUT"]),
+                rule("/api/queue-booking", &["GET", "POST", "PUT", "DELETE"]),
+                rule("/api/cash-booking", &["POST"]),
+                rule("/api/overnight-queue", &["GET", "POST"]),
+                rule("/api/driver-tickets", &["GET"]),
+                rule("/api/dashboard", &["GET"]),
+                rule("/api/station", &["GET", "PUT"]),
+                rule("/api/routes", &["GET", "POST", "PUT", "DELETE"]),
+                rule("/api/vehicles", &["GET", "POST", "PUT", "DELETE"]),
+                rule("/api/staff", &["GET", "POST", "PUT", "PATCH", "DELETE"]),
+                rule("/api/server", &["GET"]),
+                rule("/api/websocket", &["GET", "POST"]),
+            ],
+        }
+    }
+}
+
+/// The path of an endpoint without its query, lowercased as the node's router ignores case;
+/// None when it could climb out of a prefix ("..", backslashes, encoded dots or slashes)
+fn normalized_path(endpoint: &str) -> Option<String> {
+    let path = endpoint.split(['?', '#']).next().unwrap_or_default().to_lowercase();
+    let escapes = path.contains("..") || path.contains('\\') || path.contains("%2e") || path.contains("%2f") || path.contains("%5c");
+    (path.starts_with('/') && !path.contains("//") && !escapes).then_some(path)
+}
+
+impl AllowRule {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        let prefix = self.prefix.trim_end_matches('/').to_lowercase();
+        let under = path == prefix || path.strip_prefix(&prefix).is_some_and(|rest| rest.starts_with('/'));
+        under && self.methods.iter().any(|m| m.eq_ignore_ascii_case(method))
+    }
+}
+
+impl ProxyAllowlist {
+    /// The stored setting, or the default list when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    /// Checked list, prefixes trimmed and methods uppercased
+    pub fn validate(&self) -> Result<Self, String> {
+        let mut rules = Vec::new();
+        for rule in &self.rules {
+            let prefix = rule.prefix.trim().to_string();
+            let methods: Vec<String> = rule.methods.iter().map(|m| m.trim().to_uppercase()).collect();
+            let valid = normalized_path(&prefix).is_some() && !prefix.contains('?') && !methods.is_empty() && methods.iter().all(|m| METHODS.contains(&m.as_str()));
+            if !valid {
+                return Err(i18n::tf("error.proxy_rule_invalid", &[("prefix", &rule.prefix), ("methods", &rule.methods.join(", "))]));
+            }
+            rules.push(AllowRule { prefix, methods });
+        }
+        Ok(ProxyAllowlist { enabled: self.enabled, rules })
+    }
+
+    pub fn allows(&self, method: &str, endpoint: &str) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        match normalized_path(endpoint) {
+            Some(path) => self.rules.iter().any(|rule| rule.matches(method, &path)),
+            None => false,
+        }
+    }
+}
+
+/// Refuse a request off the list, recording the attempt
+pub async fn check(method: &str, endpoint: &str, server_url: Option<&str>) -> Result<(), String> {
+    let allowed = ALLOWLIST.read().map(|list| list.allows(method, endpoint)).unwrap_or(false);
+    if allowed {
+        return Ok(());
+    }
+    println!("🚫 [PROXY] Denied {} {}", method, endpoint);
+    let details = json!({ "method": method, "endpoint": endpoint, "serverUrl": server_url });
+    match DB_POOL.get().await {
+        Ok(client) => {
+            if let Err(e) = audit::record(&client, DENIED_EVENT, crate::auth::active_staff_id().as_deref(), details).await {
+                println!("⚠️ {}", e);
+            }
+        }
+        Err(e) => println!("⚠️ [PROXY] Denied request not audited: {}", e),
+    }
+    Err(i18n::tf("error.proxy_denied", &[("method", &method), ("endpoint", &endpoint)]))
+}
+
+fn cache(list: &ProxyAllowlist) {
+    if let Ok(mut cached) = ALLOWLIST.write() {
+        *cached = list.clone();
+    }
+}
+
+/// Read the list from the database into the cache
+pub async fn load() -> Result<ProxyAllowlist, String> {
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let list = ProxyAllowlist::parse(db::settings::get(&client, POLICY_KEY).await?.as_deref());
+    cache(&list);
+    Ok(list)
+}
+
+pub async fn save(client: &impl db::GenericClient, list: &ProxyAllowlist) -> Result<ProxyAllowlist, String> {
+    let list = list.validate()?;
+    db::settings::set(client, POLICY_KEY, &serde_json::to_string(&list).map_err(|e| e.to_string())?).await?;
+    cache(&list);
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_listed_prefixes_and_methods_go_through() {
+        let list = ProxyAllowlist::default();
+        assert!(list.allows("GET", "/api/queue/dest-1"));
+        assert!(list.allows("post", "/api/queue-booking/book"));
+        assert!(list.allows("GET", "/api/staff/s1/transactions?date=2026-10-15"));
+        assert!(list.allows("GET", "/health"));
+        assert!(!list.allows("DELETE", "/api/queue/dest-1"));
+        assert!(!list.allows("GET", "/api/queueing"));
+        assert!(!list.allows("GET", "/admin/shutdown"));
+        assert!(!list.allows("GET", "/api/public/../staff"));
+        assert!(!list.allows("GET", "/api/public/%2e%2e/staff"));
+        assert!(!list.allows("GET", "api/public"));
+        assert!(list.allows("GET", "/API/Public/destinations"));
+        assert!(ProxyAllowlist { enabled: false, rules: Vec::new() }.allows("DELETE", "/anything"));
+
+        let rule = |prefix: &str, method: &str| ProxyAllowlist { enabled: true, rules: vec![AllowRule { prefix: prefix.to_string(), methods: vec![method.to_string()] }] }.validate();
+        assert_eq!(rule(" /api/routes/ ", "get").unwrap().rules[0], AllowRule { prefix: "/api/routes/".to_string(), methods: vec!["GET".to_string()] });
+        assert!(rule("/api/routes", "TRACE").is_err());
+        assert!(rule("api/routes", "GET").is_err());
+        assert!(rule("/api/../admin", "GET").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::db;
+use crate::tickets::QueueBoard;
+use crate::PRINTER_SERVICE;
+
+// Paper list of the queued vehicles of a destination, pinned at the lane entrance. Printed on
+// demand, or by this terminal every `every_minutes` between `from` and `until` (Tunis time) for
+// the destinations chosen in queue_board.json.
+
+const SCHEDULE_FILE: &str = "queue_board.json";
+const CHECK_INTERVAL_SECS: u64 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QueueBoardSchedule {
+    pub enabled: bool,
+    pub destination_ids: Vec<String>,
+    pub every_minutes: u32,
+    pub from: NaiveTime,
+    pub until: NaiveTime,
+}
+
+impl Default for QueueBoardSchedule {
+    fn default() -> Self {
+        QueueBoardSchedule {
+            enabled: false,
+            destination_ids: Vec::new(),
+            every_minutes: 60,
+            from: NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+            until: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        }
+    }
+}
+
+impl QueueBoardSchedule {
+    /// Latest print time at or before `time`, None outside from..until
+    pub fn slot(&self, time: NaiveTime) -> Option<NaiveTime> {
+        if !self.enabled || self.every_minutes == 0 || time < self.from || time >= self.until {
+            return None;
+        }
+        let since_from = (time.num_seconds_from_midnight() - self.from.num_seconds_from_midnight()) / 60;
+        let minutes = since_from - since_from % self.every_minutes;
+        Some(self.from + chrono::Duration::minutes(minutes as i64))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.every_minutes < 5 || self.from >= self.until {
+            return Err(crate::i18n::t("error.invalid_board_schedule"));
+        }
+        Ok(())
+    }
+}
+
+fn schedule_path(app_handle: &tauri::AppHandle) -> PathBuf {
+    app_handle
+        .path_resolver()
+        .app_config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(SCHEDULE_FILE)
+}
+
+pub fn load(app_handle: &tauri::AppHandle) -> QueueBoardSchedule {
+    fs::read_to_string(schedule_path(app_handle))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(app_handle: &tauri::AppHandle, schedule: &QueueBoardSchedule) -> Result<(), String> {
+    let path = schedule_path(app_handle);
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let content = serde_json::to_string_pretty(schedule).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Impossible d'enregistrer {:?}: {}", path, e))
+}
+
+/// Queue the board of one destination on the current printer
+pub async fn print(destination_id: &str, staff_name: Option<String>) -> Result<String, String> {
+    let client = db::pool::read().await?;
+    let queue = db::queue::by_destination(&client, destination_id).await?;
+    let destination_name = match queue.first() {
+        Some(item) => item.destination_name.clone(),
+        None => destination_id.to_string(),
+    };
+    PRINTER_SERVICE.print_queue_board(&QueueBoard::new(&destination_name, &queue), staff_name).await
+}
+
+/// Print the scheduled boards at each slot; a slot already under way at startup is skipped
+pub async fn run_queue_board_scheduler(app_handle: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    let mut last: Option<(NaiveDate, NaiveTime)> = None;
+    let mut started = false;
+    loop {
+        interval.tick().await;
+        let schedule = load(&app_handle);
+        let now = crate::time::tunis_now().naive_local();
+        let current = schedule.slot(now.time()).map(|slot| (now.date(), slot));
+        let due = started && current.is_some() && current != last;
+        last = current.or(last);
+        started = true;
+        if !due {
+            continue;
+        }
+        for destination_id in &schedule.destination_ids {
+            if let Err(e) = print(destination_id, None).await {
+                println!("⚠️ [BOARD] Queue board for {} not printed: {}", destination_id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn slots_step_from_the_start_of_the_day_window() {
+        let schedule = QueueBoardSchedule { enabled: true, every_minutes: 45, from: at(6, 30), ..Default::default() };
+        assert_eq!(schedule.slot(at(6, 29)), None);
+        assert_eq!(schedule.slot(at(6, 30)), Some(at(6, 30)));
+        assert_eq!(schedule.slot(at(7, 14)), Some(at(6, 30)));
+        assert_eq!(schedule.slot(at(7, 15)), Some(at(7, 15)));
+        assert_eq!(schedule.slot(at(20, 0)), None);
+        assert_eq!(QueueBoardSchedule { enabled: false, ..schedule.clone() }.slot(at(7, 15)), None);
+        assert!(QueueBoardSchedule { every_minutes: 2, ..schedule }.validate().is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::Manager;
+
+use crate::db;
+use crate::db::queue_closings::{ClosingVehicleDto, QueueClosingDto};
+use crate::i18n;
+use crate::maintenance::{ChangeAction, MaintenanceDiff, RowChange};
+use crate::time;
+use crate::DB_POOL;
+
+// What happens to the vehicles still WAITING when the station closes. At `closing_time` (Tunis)
+// they are either purged from the queue, or carried over to the next day where they keep their
+// positions ahead of the morning's arrivals. Every terminal runs the check; the first to claim a
+// day in queue_closings does the work. A closing missed because no terminal was on runs at the
+// next start, on the vehicles that entered before that closing time only. The policy is the
+// queue_closing setting (JSON in station_settings); while business hours are on
+// (business_hours.rs), their closing time replaces the policy's. A supervisor may also close
+// today's queue early with `close_now`, dry run first.
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+pub const QUEUE_CLOSED_EVENT: &str = "queue-closed";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClosingMode {
+    Purge,
+    CarryOver,
+}
+
+impl ClosingMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClosingMode::Purge => "PURGE",
+            ClosingMode::CarryOver => "CARRY_OVER",
+        }
+    }
+
+    pub fn parse(mode: &str) -> Self {
+        if mode == "PURGE" { ClosingMode::Purge } else { ClosingMode::CarryOver }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QueueClosingPolicy {
+    pub enabled: bool,
+    pub mode: ClosingMode,
+    pub closing_time: NaiveTime,
+}
+
+impl Default for QueueClosingPolicy {
+    fn default() -> Self {
+        QueueClosingPolicy { enabled: true, mode: ClosingMode::CarryOver, closing_time: NaiveTime::from_hms_opt(23, 0, 0).unwrap() }
+    }
+}
+
+impl QueueClosingPolicy {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    /// Latest service day whose closing time has passed at `now` (Tunis)
+    pub fn due_day(&self, now: NaiveDateTime) -> NaiveDate {
+        if now.time() >= self.closing_time {
+            now.date()
+        } else {
+            now.date().pred_opt().unwrap()
+        }
+    }
+
+    pub fn closes_at(&self, day: NaiveDate) -> DateTime<Utc> {
+        time::from_tunis_local(day.and_time(self.closing_time)).with_timezone(&Utc)
+    }
+}
+
+/// The closing policy in force: the station closes the queue at its business hours' closing time
+pub async fn policy(client: &impl db::GenericClient) -> Result<QueueClosingPolicy, String> {
+    let mut policy = db::queue_closings::policy(client).await?;
+    let hours = db::business_hours::hours(client).await?;
+    if hours.enabled {
+        policy.closing_time = hours.closes_at;
+    }
+    Ok(policy)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CarryOverPreview {
+    pub policy: QueueClosingPolicy,
+    pub service_date: NaiveDate,
+    pub closes_at: String,
+    pub vehicles: Vec<ClosingVehicleDto>,
+}
+
+/// What the next closing would do to the queue as it stands
+pub async fn preview(client: &impl db::GenericClient) -> Result<CarryOverPreview, String> {
+    let policy = policy(client).await?;
+    let mut day = policy.due_day(time::tunis_now().naive_local());
+    if db::queue_closings::is_closed(client, day).await? {
+        day = day.succ_opt().unwrap();
+    }
+    let closes_at = policy.closes_at(day);
+    let vehicles = db::queue_closings::waiting_before(client, closes_at).await?;
+    Ok(CarryOverPreview {
+        policy,
+        service_date: day,
+        closes_at: time::TunisTime(closes_at.with_timezone(&time::TZ)).fmt_dto(),
+        vehicles: vehicles.into_iter().map(|e| e.vehicle).collect(),
+    })
+}
+
+/// Close `day` in `tx`: record the vehicles waiting since before `closes_at` and, on a purge,
+/// take them out of the queue. None when another terminal closed the day first.
+async fn close(tx: &impl db::GenericClient, day: NaiveDate, mode: ClosingMode, closes_at: DateTime<Utc>) -> Result<Option<Vec<RowChange>>, String> {
+    if !db::queue_closings::claim(tx, day, mode).await? {
+        return Ok(None);
+    }
+    let entries = db::queue_closings::waiting_before(tx, closes_at).await?;
+    let mut changes = vec![RowChange::new("queue_closings", &day.to_string(), &day.to_string(), ChangeAction::Insert, json!(null), json!({ "mode": mode }))];
+    changes.extend(db::queue_closings::record_vehicles(tx, day, &entries).await?);
+    if mode == ClosingMode::Purge {
+        changes.extend(db::queue_closings::purge(tx, &entries).await?);
+    }
+    Ok(Some(changes))
+}
+
+/// Close the latest due day unless a terminal already has; the closing made, if any
+async fn close_due() -> Result<Option<QueueClosingDto>, String> {
+    let mut client = db::pool::background().await?;
+    let policy = policy(&client).await?;
+    let day = policy.due_day(time::tunis_now().naive_local());
+    if !policy.enabled || db::queue_closings::is_closed(&client, day).await? {
+        return Ok(None);
+    }
+    let tx = client.build_transaction().start().await.map_err(|e| e.to_string())?;
+    if close(&tx, day, policy.mode, policy.closes_at(day)).await?.is_none() {
+        return Ok(None);
+    }
+    let closing = db::queue_closings::for_day(&tx, day).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(closing)
+}
+
+/// Close today's queue now with the station's mode, ahead of the closing time
+pub async fn close_now(dry_run: bool) -> Result<MaintenanceDiff, String> {
+    let mut client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let policy = db::queue_closings::policy(&client).await?;
+    let day = time::tunis_today();
+    let tx = client.build_transaction().start().await.map_err(|e| e.to_string())?;
+    let Some(changes) = close(&tx, day, policy.mode, crate::clock::now()).await? else {
+        return Err(i18n::tf("error.queue_already_closed", &[("date", &day)]));
+    };
+    MaintenanceDiff { dry_run, changes }.finish(tx).await
+}
+
+pub async fn run_queue_closing(app_handle: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        match close_due().await {
+            Ok(Some(closing)) => {
+                println!(
+                    "🌙 [CLOSING] Queue of {} closed: {} waiting vehicle(s) {}",
+                    closing.service_date,
+                    closing.vehicles.len(),
+                    if closing.mode == ClosingMode::Purge { "purged" } else { "carried over" }
+                );
+                let _ = app_handle.emit_all(QUEUE_CLOSED_EVENT, &closing);
+            }
+            Ok(None) => {}
+            Err(e) => println!("⚠️ [CLOSING] Queue closing failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, h: u32, m: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn the_due_day_turns_over_at_closing_time() {
+        let policy = QueueClosingPolicy { closing_time: NaiveTime::from_hms_opt(22, 30, 0).unwrap(), ..Default::default() };
+        assert_eq!(policy.due_day(at(15, 22, 29)), at(14, 0, 0).date());
+        assert_eq!(policy.due_day(at(15, 22, 30)), at(15, 0, 0).date());
+        assert_eq!(policy.due_day(at(16, 6, 0)), at(15, 0, 0).date());
+
+        let stored = QueueClosingPolicy::parse(Some(r#"{"mode":"PURGE","closingTime":"21:00:00"}"#));
+        assert_eq!((stored.mode, stored.enabled), (ClosingMode::Purge, true));
+        assert_eq!(QueueClosingPolicy::parse(Some("{")), QueueClosingPolicy::default());
+        assert_eq!(ClosingMode::parse(ClosingMode::CarryOver.as_str()), ClosingMode::CarryOver);
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::vehicles::AuthorizedDestinationDto;
+use crate::i18n;
+
+// Queue entry in one step: most vehicles always load for the same destination, so
+// db_quick_enter_queue puts a plate in the queue of its default destination (the vehicle's
+// default_destination_id, else the authorization marked default), which must still be one of its
+// authorized destinations. It then prints like db_enter_queue. With the quick_entry setting on,
+// the queue screen's add-vehicle search does this as soon as a plate is scanned or typed and
+// Enter pressed; otherwise the cashier still picks the destination.
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuickEntryPolicy {
+    pub enabled: bool,
+}
+
+impl QuickEntryPolicy {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickEntryDto {
+    pub queue_id: String,
+    pub license_plate: String,
+    pub destination_id: String,
+    pub destination_name: String,
+}
+
+/// The vehicle's default destination among those it is authorized for
+pub fn default_destination(license_plate: &str, default_id: Option<&str>, authorized: Vec<AuthorizedDestinationDto>) -> Result<AuthorizedDestinationDto, String> {
+    match default_id.map(str::trim).filter(|id| !id.is_empty()) {
+        Some(id) => authorized
+            .into_iter()
+            .find(|d| d.station_id == id)
+            .ok_or_else(|| i18n::tf("error.vehicle_not_authorized", &[("plate", &license_plate), ("destination", &id)])),
+        None => authorized
+            .into_iter()
+            .find(|d| d.is_default)
+            .ok_or_else(|| i18n::tf("error.no_default_destination", &[("plate", &license_plate)])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money::Money;
+
+    fn authorized(id: &str, is_default: bool) -> AuthorizedDestinationDto {
+        AuthorizedDestinationDto { station_id: id.into(), station_name: id.to_uppercase(), base_price: Money::ZERO, is_default, priority: 1 }
+    }
+
+    #[test]
+    fn the_default_must_still_be_authorized() {
+        let list = || vec![authorized("sousse", false), authorized("monastir", true)];
+        assert_eq!(default_destination("123 TU 4567", Some("sousse"), list()).unwrap().station_id, "sousse");
+        assert_eq!(default_destination("123 TU 4567", None, list()).unwrap().station_id, "monastir");
+        assert_eq!(default_destination("123 TU 4567", Some(" "), list()).unwrap().station_id, "monastir");
+        assert!(default_destination("123 TU 4567", Some("sfax"), list()).is_err());
+        assert!(default_destination("123 TU 4567", None, vec![authorized("sousse", false)]).is_err());
+
+        assert!(!QuickEntryPolicy::parse(None).enabled);
+        assert!(QuickEntryPolicy::parse(Some(r#"{"enabled":true}"#)).enabled);
+    }
+}
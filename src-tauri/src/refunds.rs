@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db;
+use crate::db::refunds::RefundDto;
+use crate::i18n;
+use crate::money::Money;
+
+// Refunds handed back at the counter against a booking or a day pass (migration 041), with a
+// supervisor's sign-off as a REFUND override. What a booking already gave back through
+// cancellations and no-shows (bookings.refund_amount) counts with them, and the row is locked
+// while a refund is recorded, so two refunds of the same ticket can't both go through and
+// nothing is refunded beyond what was paid. Cancellations, no-shows and emergency removals
+// give back no more than what the refunds left. A day pass refunded in full stops being valid.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RefundTarget {
+    Booking,
+    DayPass,
+}
+
+/// What a booking or day pass was paid and has given back so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct Refundable {
+    /// Verification code of the booking, id of the day pass
+    pub reference: String,
+    pub original: Money,
+    pub refunded: Money,
+}
+
+impl Refundable {
+    pub fn remaining(&self) -> Money {
+        (self.original - self.refunded).max(Money::ZERO)
+    }
+
+    /// Refuse an amount that isn't positive or goes beyond what is left to refund
+    pub fn check(&self, amount: Money) -> Result<(), String> {
+        if !amount.is_positive() {
+            return Err(i18n::t("error.refund_amount_invalid"));
+        }
+        if amount > self.remaining() {
+            return Err(i18n::tf("error.refund_exceeds_paid", &[("reference", &self.reference), ("refundable", &self.remaining())]));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundSummaryDto {
+    pub target: RefundTarget,
+    pub id: String,
+    pub reference: String,
+    pub original_amount: Money,
+    /// Cancellations, no-shows and the refunds below
+    pub refunded_amount: Money,
+    pub refundable_amount: Money,
+    pub refunds: Vec<RefundDto>,
+}
+
+async fn refundable(client: &impl db::GenericClient, target: RefundTarget, id: &str, lock: bool) -> Result<Refundable, String> {
+    let refundable = match target {
+        RefundTarget::Booking => db::refunds::booking(client, id, lock).await?,
+        RefundTarget::DayPass => db::refunds::day_pass(client, id, lock).await?,
+    };
+    refundable.ok_or_else(|| match target {
+        RefundTarget::Booking => i18n::tf("error.booking_not_found", &[("id", &id)]),
+        RefundTarget::DayPass => i18n::tf("error.day_pass_not_found", &[("id", &id)]),
+    })
+}
+
+/// A booking or day pass with what it was paid, what it gave back and its refunds, oldest first
+pub async fn summary(client: &impl db::GenericClient, target: RefundTarget, id: &str) -> Result<RefundSummaryDto, String> {
+    let refundable = refundable(client, target, id, false).await?;
+    Ok(RefundSummaryDto {
+        target,
+        id: id.to_string(),
+        refundable_amount: refundable.remaining(),
+        reference: refundable.reference,
+        original_amount: refundable.original,
+        refunded_amount: refundable.refunded,
+        refunds: db::refunds::for_target(client, target, id).await?,
+    })
+}
+
+/// Check and record a refund of `amount`, in the caller's transaction
+pub async fn record(
+    client: &impl db::GenericClient,
+    target: RefundTarget,
+    id: &str,
+    amount: Money,
+    authorized: &crate::overrides::AuthorizedOverride,
+    created_by: &Option<String>,
+) -> Result<(), String> {
+    let refundable = refundable(client, target, id, true).await?;
+    refundable.check(amount)?;
+    db::refunds::insert(client, target, id, &refundable.reference, amount, authorized, created_by).await?;
+    if target == RefundTarget::DayPass && amount == refundable.remaining() {
+        db::refunds::deactivate_day_pass(client, id).await?;
+    }
+    Ok(())
+}
+
+/// What a cancellation or no-show of a paid booking may still give back of `refund`, after the
+/// refunds recorded against it
+pub async fn cap_booking_refund(client: &impl db::GenericClient, booking_id: &str, total_amount: Money, refund: Money) -> Result<Money, String> {
+    let recorded = db::refunds::recorded_for_booking(client, booking_id).await?;
+    Ok(refund.min((total_amount - recorded).max(Money::ZERO)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refunds_never_exceed_what_was_paid() {
+        // Three seats at 3 TND, one cancelled and given back
+        let booking = Refundable { reference: "ABC-123".into(), original: Money::from_tnd(9.0), refunded: Money::from_tnd(3.0) };
+        assert_eq!(booking.remaining(), Money::from_tnd(6.0));
+        assert!(booking.check(Money::from_tnd(6.0)).is_ok());
+        assert!(booking.check(Money::from_tnd(6.001)).is_err());
+        assert!(booking.check(Money::ZERO).is_err());
+        assert!(booking.check(Money::from_tnd(-1.0)).is_err());
+
+        let refunded = Refundable { refunded: Money::from_tnd(9.5), ..booking };
+        assert_eq!(refunded.remaining(), Money::ZERO);
+        assert!(refunded.check(Money::from_tnd(0.1)).is_err());
+    }
+}
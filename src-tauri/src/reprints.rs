@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db;
+use crate::db::voided_tickets::VoidedTicketDto;
+use crate::i18n;
+use crate::money::Money;
+use crate::printer::PrintJobType;
+use crate::tickets::{BookingTicket, DayPassTicket, EntryTicket, ExitPassTicket, TicketPayload};
+use crate::{DB_POOL, PRINTER_SERVICE};
+
+// Reprints of the last ticket of each kind, from the printer's reprint cache. Each reprint is
+// numbered per ticket in ticket_reprints and prints under a "DUPLICATA n°X" banner, so it can't
+// pass for the original. The reprint_policy setting (JSON in station_settings) caps the reprints
+// a ticket gets and may charge a fee on each; the fee is kept on the reprint's row for the till.
+// The buttons and the keyboard shortcuts both come through here.
+//
+// A numbered ticket (entry, exit pass, day pass) spoiled at the printer has its number voided
+// with `void_number`; a number is never printed twice, so the next reprint of that ticket is
+// not a duplicate but a replacement under a fresh number, which later reprints then duplicate.
+
+const MAX_VOID_REASON_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReprintKind {
+    Booking,
+    Entry,
+    Exit,
+    ExitPass,
+    DayPass,
+}
+
+impl ReprintKind {
+    pub fn job_type(self) -> PrintJobType {
+        match self {
+            ReprintKind::Booking => PrintJobType::BookingTicket,
+            ReprintKind::Entry => PrintJobType::EntryTicket,
+            ReprintKind::Exit => PrintJobType::ExitTicket,
+            ReprintKind::ExitPass => PrintJobType::ExitPassTicket,
+            ReprintKind::DayPass => PrintJobType::DayPassTicket,
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind.trim().to_ascii_uppercase().as_str() {
+            "BOOKING" => Some(ReprintKind::Booking),
+            "ENTRY" => Some(ReprintKind::Entry),
+            "EXIT" => Some(ReprintKind::Exit),
+            "EXIT_PASS" => Some(ReprintKind::ExitPass),
+            "DAY_PASS" => Some(ReprintKind::DayPass),
+            _ => None,
+        }
+    }
+
+    /// Tickets printed with a number of their own; the others carry the booking code or none
+    pub fn numbered(self) -> bool {
+        matches!(self, ReprintKind::Entry | ReprintKind::ExitPass | ReprintKind::DayPass)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReprintKind::Booking => "BOOKING",
+            ReprintKind::Entry => "ENTRY",
+            ReprintKind::Exit => "EXIT",
+            ReprintKind::ExitPass => "EXIT_PASS",
+            ReprintKind::DayPass => "DAY_PASS",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReprintPolicy {
+    /// Reprints allowed per ticket; None for no limit
+    pub max_reprints: Option<u32>,
+    /// Charged on every reprint; zero for free reprints
+    pub fee: Money,
+}
+
+impl Default for ReprintPolicy {
+    fn default() -> Self {
+        ReprintPolicy { max_reprints: None, fee: Money::ZERO }
+    }
+}
+
+impl ReprintPolicy {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.fee < Money::ZERO {
+            return Err(i18n::t("error.invalid_reprint_policy"));
+        }
+        Ok(())
+    }
+
+    /// Number of the next reprint of a ticket already reprinted `done` times
+    pub fn next_number(&self, done: u32) -> Result<u32, String> {
+        match self.max_reprints {
+            Some(max) if done >= max => Err(i18n::tf("error.reprint_limit_reached", &[("max", &max)])),
+            _ => Ok(done + 1),
+        }
+    }
+}
+
+/// The ticket a cached payload stands for: its number or code, else a digest of the payload
+/// (the exit ticket is plain text, and older booking screens send text too)
+pub fn ticket_reference(kind: ReprintKind, payload: &str) -> String {
+    let reference = match kind {
+        ReprintKind::Booking => BookingTicket::from_payload(payload).verification_code,
+        ReprintKind::Entry => EntryTicket::from_payload(payload).map(|t| t.ticket_number).unwrap_or_default(),
+        ReprintKind::ExitPass => ExitPassTicket::from_payload(payload).map(|t| t.ticket_number).unwrap_or_default(),
+        ReprintKind::DayPass => DayPassTicket::from_payload(payload).map(|t| t.ticket_number).unwrap_or_default(),
+        ReprintKind::Exit => String::new(),
+    };
+    if !reference.trim().is_empty() {
+        return reference;
+    }
+    // FNV-1a: stable across builds, unlike the std hasher
+    let digest = payload.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+    format!("#{:016x}", digest)
+}
+
+/// A new number in the style of `voided`: its prefix up to the first digit, then `millis`
+pub fn fresh_number(voided: &str, millis: i64) -> String {
+    let prefix: String = voided.chars().take_while(|c| !c.is_ascii_digit()).collect();
+    format!("{}{}", prefix, millis)
+}
+
+fn renumber(kind: ReprintKind, payload: &str, number: &str) -> Result<String, String> {
+    Ok(match kind {
+        ReprintKind::Entry => {
+            let mut ticket = EntryTicket::from_payload(payload)?;
+            ticket.ticket_number = number.to_string();
+            ticket.to_payload()
+        }
+        ReprintKind::ExitPass => {
+            let mut ticket = ExitPassTicket::from_payload(payload)?;
+            ticket.ticket_number = number.to_string();
+            ticket.to_payload()
+        }
+        ReprintKind::DayPass => {
+            let mut ticket = DayPassTicket::from_payload(payload)?;
+            ticket.ticket_number = number.to_string();
+            ticket.to_payload()
+        }
+        ReprintKind::Booking | ReprintKind::Exit => payload.to_string(),
+    })
+}
+
+/// Void a spoiled ticket's number so it is never printed again
+pub async fn void_number(client: &impl db::GenericClient, kind: &str, number: &str, reason: &str, staff_id: &Option<String>) -> Result<VoidedTicketDto, String> {
+    let kind = ReprintKind::parse(kind).ok_or_else(|| i18n::tf("error.ticket_kind_invalid", &[("kind", &kind)]))?;
+    if !kind.numbered() {
+        return Err(i18n::t("error.ticket_kind_not_numbered"));
+    }
+    let number = number.trim();
+    let reason = reason.trim();
+    if number.is_empty() {
+        return Err(i18n::t("error.ticket_number_required"));
+    }
+    if reason.is_empty() || reason.chars().count() > MAX_VOID_REASON_CHARS {
+        return Err(i18n::tf("error.void_reason_invalid", &[("max", &MAX_VOID_REASON_CHARS)]));
+    }
+    db::voided_tickets::insert(client, kind.as_str(), number, reason, staff_id)
+        .await?
+        .ok_or_else(|| i18n::tf("error.ticket_already_voided", &[("number", &number)]))
+}
+
+/// Reprint the last ticket of `kind` if the policy allows it; the message names the duplicate
+/// number and any fee to collect. A ticket whose number was voided is replaced instead.
+pub async fn reprint(kind: ReprintKind, staff_id: Option<String>) -> Result<String, String> {
+    let payload = PRINTER_SERVICE.last_payload(&kind.job_type()).await.ok_or_else(|| i18n::t("error.nothing_to_reprint"))?;
+    let reference = ticket_reference(kind, &payload);
+
+    let mut client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let tx = client.build_transaction().start().await.map_err(|e| e.to_string())?;
+    if kind.numbered() && db::voided_tickets::awaiting_replacement(&tx, kind.as_str(), &reference).await? {
+        let number = fresh_number(&reference, chrono::Utc::now().timestamp_millis());
+        let payload = renumber(kind, &payload, &number)?;
+        db::voided_tickets::set_replacement(&tx, kind.as_str(), &reference, &number).await?;
+        tx.commit().await.map_err(|e| e.to_string())?;
+        PRINTER_SERVICE.queue_replacement(kind.job_type(), payload).await?;
+        return Ok(i18n::tf("message.ticket_replaced", &[("voided", &reference), ("number", &number)]));
+    }
+    let done = db::reprints::count_locked(&tx, kind.as_str(), &reference).await?;
+    let policy = db::reprints::policy(&tx).await?;
+    let number = policy.next_number(done)?;
+    db::reprints::record(&tx, kind.as_str(), &reference, number, policy.fee, &staff_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    PRINTER_SERVICE.queue_duplicate(kind.job_type(), payload, number).await?;
+    Ok(if policy.fee.is_positive() {
+        i18n::tf("message.ticket_reprinted_fee", &[("number", &number), ("fee", &policy.fee)])
+    } else {
+        i18n::tf("message.ticket_reprinted", &[("number", &number)])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reprints_are_numbered_per_ticket_up_to_the_limit() {
+        let unlimited = ReprintPolicy::default();
+        assert_eq!(unlimited.next_number(0), Ok(1));
+        assert_eq!(unlimited.next_number(41), Ok(42));
+        let capped = ReprintPolicy { max_reprints: Some(2), fee: Money::from_tnd(0.5) };
+        assert_eq!(capped.next_number(1), Ok(2));
+        assert!(capped.next_number(2).is_err());
+        assert!(ReprintPolicy::parse(Some(r#"{"maxReprints":0}"#)).next_number(0).is_err());
+        assert!(ReprintPolicy { fee: Money::from_tnd(-1.0), ..capped }.validate().is_err());
+
+        assert_eq!(ticket_reference(ReprintKind::ExitPass, r#"{"ticketNumber":"EXIT-1","licensePlate":"1 TU 1"}"#), "EXIT-1");
+        let exit = ticket_reference(ReprintKind::Exit, "Sortie 1 TU 1");
+        assert_eq!(exit, ticket_reference(ReprintKind::Exit, "Sortie 1 TU 1"));
+        assert_ne!(exit, ticket_reference(ReprintKind::Exit, "Sortie 2 TU 2"));
+    }
+
+    #[test]
+    fn a_voided_number_is_replaced_by_a_fresh_one_of_the_same_style() {
+        assert_eq!(fresh_number("ENTRY-1760000000000", 1760000000123), "ENTRY-1760000000123");
+        assert_eq!(fresh_number("DP00000000", 1760000000123), "DP1760000000123");
+        assert_eq!(ReprintKind::parse("exit_pass"), Some(ReprintKind::ExitPass));
+        assert!(!ReprintKind::Booking.numbered());
+
+        let payload = r#"{"ticketNumber":"DAYPASS-1","licensePlate":"1 TU 1","amount":2.0}"#;
+        let renumbered = renumber(ReprintKind::DayPass, payload, "DAYPASS-2").unwrap();
+        assert_eq!(ticket_reference(ReprintKind::DayPass, &renumbered), "DAYPASS-2");
+        assert_eq!(DayPassTicket::from_payload(&renumbered).unwrap().license_plate, "1 TU 1");
+    }
+}
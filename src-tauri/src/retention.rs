@@ -0,0 +1,210 @@
+use chrono::{DateTime, Duration, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::db;
+use crate::i18n;
+use crate::time;
+use crate::DB_POOL;
+
+// Old rows of the tables that only grow: exit passes, the audit log, the queue journal and the
+// print archive. Each table listed in the retention_policy setting (JSON in station_settings)
+// keeps that many days; a table not listed is kept forever, as before. Every terminal checks each
+// night after `runAt` (Tunis) and the first to claim the day purges; a supervisor may also run
+// it at once, dry run first. Each run logs the rows purged per table. Driver settlements outlive
+// the exit passes they were written with; tickets held unprinted in escrow are never purged.
+
+const CHECK_INTERVAL_SECS: u64 = 10 * 60;
+const MIN_RETENTION_DAYS: u32 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionTable {
+    ExitPasses,
+    AuditLog,
+    QueueEvents,
+    PrintedTickets,
+}
+
+impl RetentionTable {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RetentionTable::ExitPasses => "exit_passes",
+            RetentionTable::AuditLog => "audit_log",
+            RetentionTable::QueueEvents => "queue_events",
+            RetentionTable::PrintedTickets => "printed_tickets",
+        }
+    }
+
+    /// The time a row's age is counted from
+    pub fn column(self) -> &'static str {
+        match self {
+            RetentionTable::ExitPasses => "current_exit_time",
+            RetentionTable::AuditLog => "created_at",
+            RetentionTable::QueueEvents => "occurred_at",
+            RetentionTable::PrintedTickets => "printed_at",
+        }
+    }
+
+    /// Rows never purged however old: an escrowed ticket (ticket_escrow.rs) has not reached its
+    /// customer yet
+    pub fn kept(self) -> &'static str {
+        match self {
+            RetentionTable::PrintedTickets => "status = 'ESCROWED'",
+            RetentionTable::ExitPasses | RetentionTable::AuditLog | RetentionTable::QueueEvents => "false",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionPolicy {
+    pub enabled: bool,
+    /// Tunis time after which the nightly cleanup runs
+    pub run_at: NaiveTime,
+    /// Days kept by table; a table not listed is kept forever
+    pub days: BTreeMap<RetentionTable, u32>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy { enabled: true, run_at: NaiveTime::from_hms_opt(3, 0, 0).unwrap(), days: BTreeMap::new() }
+    }
+}
+
+impl RetentionPolicy {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some((table, _)) = self.days.iter().find(|(_, days)| **days < MIN_RETENTION_DAYS) {
+            return Err(i18n::tf("error.retention_too_short", &[("table", &table.as_str()), ("min", &MIN_RETENTION_DAYS)]));
+        }
+        Ok(())
+    }
+
+    /// Whether the nightly cleanup is due at `now` (Tunis)
+    pub fn due(&self, now: NaiveDateTime) -> bool {
+        self.enabled && !self.days.is_empty() && now.time() >= self.run_at
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgedTableDto {
+    pub table: RetentionTable,
+    pub days: u32,
+    /// Rows older than this were purged, or would be on a dry run
+    pub before: String,
+    pub rows: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub tables: Vec<PurgedTableDto>,
+    pub total_rows: u64,
+}
+
+impl RetentionReport {
+    /// One log line: the rows purged per table
+    pub fn summary(&self) -> String {
+        let tables: Vec<String> = self.tables.iter().map(|t| format!("{} {}", t.table.as_str(), t.rows)).collect();
+        format!("{} row(s){}{}", self.total_rows, if tables.is_empty() { "" } else { ": " }, tables.join(", "))
+    }
+}
+
+/// Rows of a table kept `days` days are purged when older than this
+pub fn cutoff(now: DateTime<Utc>, days: u32) -> DateTime<Utc> {
+    now - Duration::days(days as i64)
+}
+
+async fn purge(client: &impl db::GenericClient, policy: &RetentionPolicy, dry_run: bool) -> Result<RetentionReport, String> {
+    let now = crate::clock::now();
+    let mut tables = Vec::new();
+    for (table, days) in &policy.days {
+        let before = cutoff(now, *days);
+        let rows = if dry_run {
+            db::retention::count_before(client, *table, before).await?
+        } else {
+            db::retention::delete_before(client, *table, before).await?
+        };
+        tables.push(PurgedTableDto { table: *table, days: *days, before: time::TunisTime(before.with_timezone(&time::TZ)).fmt_dto(), rows });
+    }
+    let total_rows = tables.iter().map(|t| t.rows).sum();
+    Ok(RetentionReport { dry_run, tables, total_rows })
+}
+
+/// Purge now with the station's policy, or only count on a dry run
+pub async fn run_now(dry_run: bool) -> Result<RetentionReport, String> {
+    let mut client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let policy = db::retention::policy(&client).await?;
+    let tx = client.build_transaction().start().await.map_err(|e| e.to_string())?;
+    let report = purge(&tx, &policy, dry_run).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    println!("🧹 [RETENTION] {} {}", if dry_run { "Dry run, would purge" } else { "Purged" }, report.summary());
+    Ok(report)
+}
+
+/// Tonight's cleanup unless it is not due or a terminal already ran it
+async fn run_due() -> Result<Option<RetentionReport>, String> {
+    let mut client = db::pool::background().await?;
+    let policy = db::retention::policy(&client).await?;
+    let now = time::tunis_now().naive_local();
+    if !policy.due(now) {
+        return Ok(None);
+    }
+    let tx = client.build_transaction().start().await.map_err(|e| e.to_string())?;
+    if !db::retention::claim_day(&tx, now.date()).await? {
+        return Ok(None);
+    }
+    let report = purge(&tx, &policy, false).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(Some(report))
+}
+
+pub async fn run_retention_scheduler() {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        match run_due().await {
+            Ok(Some(report)) => println!("🧹 [RETENTION] Nightly cleanup purged {}", report.summary()),
+            Ok(None) => {}
+            Err(e) => println!("⚠️ [RETENTION] Nightly cleanup failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn tables_keep_their_days_and_the_cleanup_runs_at_night() {
+        let policy = RetentionPolicy::parse(Some(r#"{"days":{"audit_log":365,"printed_tickets":90}}"#));
+        assert_eq!(policy.days.keys().copied().collect::<Vec<_>>(), vec![RetentionTable::AuditLog, RetentionTable::PrintedTickets]);
+        assert!(policy.validate().is_ok());
+        assert!(RetentionPolicy { days: BTreeMap::from([(RetentionTable::ExitPasses, 7)]), ..Default::default() }.validate().is_err());
+        // An unknown table makes the setting unreadable: nothing is purged
+        assert_eq!(RetentionPolicy::parse(Some(r#"{"days":{"staff":30}}"#)), RetentionPolicy::default());
+
+        let night = |h, m| NaiveDate::from_ymd_opt(2026, 10, 15).unwrap().and_hms_opt(h, m, 0).unwrap();
+        assert!(!policy.due(night(2, 59)));
+        assert!(policy.due(night(3, 0)));
+        assert!(!RetentionPolicy::default().due(night(4, 0)));
+
+        let now = DateTime::<Utc>::from_timestamp(1_760_000_000, 0).unwrap();
+        assert_eq!(now - cutoff(now, 90), Duration::days(90));
+
+        let report = RetentionReport {
+            dry_run: false,
+            tables: vec![PurgedTableDto { table: RetentionTable::AuditLog, days: 365, before: String::new(), rows: 12 }],
+            total_rows: 12,
+        };
+        assert_eq!(report.summary(), "12 row(s): audit_log 12");
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::audit;
+use crate::db;
+use crate::i18n;
+
+// Seats moved by the cashier from one vehicle to another of the same destination and sub-route,
+// as many as they choose. Bookings move whole, so a customer's ticket never ends up split over
+// two vehicles: the seat count must be made of whole bookings, the latest taken first. Both
+// vehicles' free seats and statuses follow.
+//
+// After any transfer (this one, the transfer to the head of the line, the release of a stalled
+// vehicle) the customers hold tickets naming the old plate: `reprint_moved` queues replacement
+// tickets marked as a vehicle change and records each reprint in the audit log.
+
+pub const TICKET_REPRINT_EVENT: &str = "BOOKING_TICKET_REPRINTED";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatTransferDto {
+    pub seats: i32,
+    pub booking_ids: Vec<String>,
+    pub from_license_plate: String,
+    pub to_license_plate: String,
+    pub tickets_reprinted: usize,
+    pub message: String,
+}
+
+/// Bookings (id, seats), latest first, adding up to exactly `seats`, preferring the latest ones;
+/// None when no set of whole bookings does
+pub fn pick_bookings(bookings: &[(String, i32)], seats: i32) -> Option<Vec<String>> {
+    if seats <= 0 {
+        return None;
+    }
+    let target = seats as usize;
+    // via[s]: the booking that first completed a sum of s, on top of a sum reached before it
+    let mut via: Vec<Option<usize>> = vec![None; target + 1];
+    for (i, (_, n)) in bookings.iter().enumerate() {
+        let n = *n as usize;
+        if n == 0 || n > target {
+            continue;
+        }
+        for sum in (n..=target).rev() {
+            if via[sum].is_none() && (sum == n || via[sum - n].is_some()) {
+                via[sum] = Some(i);
+            }
+        }
+    }
+    let mut picked = Vec::new();
+    let mut sum = target;
+    while sum > 0 {
+        let i = via[sum]?;
+        picked.push(bookings[i].0.clone());
+        sum -= bookings[i].1 as usize;
+    }
+    Some(picked)
+}
+
+/// Move `seats` from one queue entry to the other in `tx`, checking both
+pub async fn transfer(tx: &impl db::GenericClient, source_queue_id: &str, target_queue_id: &str, seats: i32) -> Result<SeatTransferDto, String> {
+    if seats <= 0 {
+        return Err(i18n::t("error.transfer_seats_invalid"));
+    }
+    if source_queue_id == target_queue_id {
+        return Err(i18n::t("error.transfer_same_vehicle"));
+    }
+    let entries = db::queue::lock_entries(tx, &[source_queue_id, target_queue_id]).await?;
+    let source = entries
+        .iter()
+        .find(|e| e.id == source_queue_id)
+        .ok_or_else(|| i18n::t("error.transfer_source_invalid"))?;
+    let target = entries
+        .iter()
+        .find(|e| e.id == target_queue_id && e.destination_id == source.destination_id)
+        .ok_or_else(|| i18n::t("error.transfer_target_invalid"))?;
+    if target.sub_route != source.sub_route {
+        return Err(i18n::t("error.transfer_other_sub_route"));
+    }
+    let booked = source.total_seats - source.available_seats;
+    if booked < seats {
+        return Err(i18n::tf("error.transfer_not_enough_booked", &[("booked", &booked), ("requested", &seats)]));
+    }
+    if target.available_seats < seats {
+        return Err(i18n::tf("error.transfer_not_enough_seats", &[("available", &target.available_seats), ("required", &seats)]));
+    }
+    let bookings = db::booking::on_queue(tx, source_queue_id).await?;
+    let booking_ids = pick_bookings(&bookings, seats).ok_or_else(|| i18n::tf("error.transfer_seats_split", &[("seats", &seats)]))?;
+
+    db::booking::move_to_queue(tx, &booking_ids, target_queue_id).await?;
+    db::queue::release_seats(tx, source_queue_id, seats).await?;
+    db::queue::hold_seats(tx, target_queue_id, seats).await?;
+    Ok(SeatTransferDto {
+        seats,
+        booking_ids,
+        from_license_plate: source.license_plate.clone(),
+        to_license_plate: target.license_plate.clone(),
+        tickets_reprinted: 0,
+        message: i18n::tf("message.seats_moved", &[("count", &seats), ("from", &source.license_plate), ("to", &target.license_plate)]),
+    })
+}
+
+/// Queue a replacement ticket for each moved booking, now on its new vehicle, and audit it.
+/// Runs after the transfer is committed: a failure is logged and leaves the transfer in place.
+/// Returns the number of tickets queued.
+pub async fn reprint_moved(client: &deadpool_postgres::Client, booking_ids: &[String], previous_license_plate: &str) -> usize {
+    let tickets = match db::booking::tickets(client, booking_ids).await {
+        Ok(tickets) => tickets,
+        Err(e) => {
+            println!("❌ Failed to load moved bookings for reprint: {}", e);
+            return 0;
+        }
+    };
+    let tickets: Vec<_> = tickets
+        .into_iter()
+        .map(|mut ticket| {
+            ticket.previous_license_plate = Some(previous_license_plate.to_string());
+            ticket
+        })
+        .collect();
+    if tickets.is_empty() {
+        return 0;
+    }
+    // One batch, so the cashier hands out the whole stack at once
+    if let Err(e) = crate::PRINTER_SERVICE.print_booking_tickets(&tickets, None).await {
+        println!("❌ Failed to reprint moved booking tickets: {}", e);
+        return 0;
+    }
+    let actor = crate::auth::active_staff_id();
+    for ticket in &tickets {
+        let details = json!({
+            "verificationCode": ticket.verification_code,
+            "queueId": ticket.queue_id,
+            "licensePlate": ticket.license_plate,
+            "previousLicensePlate": previous_license_plate,
+            "seats": ticket.seats_booked,
+        });
+        if let Err(e) = audit::record(client, TICKET_REPRINT_EVENT, actor.as_deref(), details).await {
+            println!("⚠️ {}", e);
+        }
+    }
+    tickets.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookings(seats: &[i32]) -> Vec<(String, i32)> {
+        seats.iter().enumerate().map(|(i, n)| (format!("b{}", i), *n)).collect()
+    }
+
+    #[test]
+    fn whole_bookings_make_up_the_seats_latest_first() {
+        let list = bookings(&[2, 1, 3, 1]);
+        assert_eq!(pick_bookings(&list, 3), Some(vec!["b1".to_string(), "b0".to_string()]));
+        assert_eq!(pick_bookings(&list, 1), Some(vec!["b1".to_string()]));
+        let mut all = pick_bookings(&list, 7).unwrap();
+        all.sort();
+        assert_eq!(all, vec!["b0", "b1", "b2", "b3"]);
+        // Only a 3-seat booking: 2 seats would split it
+        assert_eq!(pick_bookings(&bookings(&[3]), 2), None);
+        assert_eq!(pick_bookings(&list, 8), None);
+        assert_eq!(pick_bookings(&list, 0), None);
+    }
+}
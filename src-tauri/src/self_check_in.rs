@@ -0,0 +1,252 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::anomalies;
+use crate::audit;
+use crate::auth;
+use crate::db;
+use crate::db::passes::DayPassDto;
+use crate::i18n;
+use crate::printer::PrinterConfig;
+use crate::tickets::{DayPassStatus, EntryTicket};
+use crate::{DB_POOL, PRINTER_SERVICE};
+
+// Self check-in at the kiosk: the driver types the plate and their PIN, and the vehicle joins the
+// queue through the same path as the counter's (enter_queue), its entry ticket coming out of the
+// gate printer. Nobody is there to sell a day pass, so a vehicle without today's pass is sent to
+// the counter. The PIN is an Argon2 hash on the vehicle (migration 036), set by a supervisor;
+// wrong PINs lock the plate out for a while, so a plate can't be tried PIN after PIN.
+
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+const LOCKOUT: Duration = Duration::from_secs(5 * 60);
+const KIOSK_STAFF_NAME: &str = "Borne";
+pub const SELF_CHECK_IN_EVENT: &str = "SELF_CHECK_IN";
+
+// Failed attempts per plate since its last success, and when it is unlocked again
+type Attempts = HashMap<String, (u32, Option<Instant>)>;
+static FAILED_ATTEMPTS: Lazy<Mutex<Attempts>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SelfCheckInPolicy {
+    pub enabled: bool,
+    /// Gate printer address; None prints on the kiosk terminal's own printer
+    pub gate_printer_ip: Option<String>,
+    pub gate_printer_port: u16,
+}
+
+impl Default for SelfCheckInPolicy {
+    fn default() -> Self {
+        SelfCheckInPolicy { enabled: false, gate_printer_ip: None, gate_printer_port: 9100 }
+    }
+}
+
+impl SelfCheckInPolicy {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let blank_ip = self.gate_printer_ip.as_deref().is_some_and(|ip| ip.trim().is_empty());
+        if blank_ip || self.gate_printer_port == 0 {
+            return Err(i18n::t("error.gate_printer_invalid"));
+        }
+        Ok(())
+    }
+
+    /// The gate printer: the terminal's printer model and paper at the gate's address
+    pub fn gate_printer(&self, base: &PrinterConfig) -> Option<PrinterConfig> {
+        let ip = self.gate_printer_ip.as_deref()?.trim();
+        Some(PrinterConfig {
+            id: format!("{}-gate", base.id),
+            name: format!("Portail ({})", ip),
+            ip: ip.to_string(),
+            port: self.gate_printer_port,
+            failover: Vec::new(),
+            ..base.clone()
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfCheckInDto {
+    pub queue_id: String,
+    pub license_plate: String,
+    pub destination_name: String,
+    pub queue_position: i32,
+    pub printer_name: String,
+}
+
+fn check_lockout(plate: &str, now: Instant) -> Result<(), String> {
+    let mut attempts = FAILED_ATTEMPTS.lock().map_err(|e| e.to_string())?;
+    if let Some(&(_, Some(until))) = attempts.get(plate) {
+        if now < until {
+            let minutes = (until.saturating_duration_since(now).as_secs() + 59) / 60;
+            return Err(i18n::tf("error.driver_pin_locked", &[("minutes", &minutes)]));
+        }
+        attempts.remove(plate);
+    }
+    Ok(())
+}
+
+fn register_failure(plate: &str, now: Instant) {
+    if let Ok(mut attempts) = FAILED_ATTEMPTS.lock() {
+        let entry = attempts.entry(plate.to_string()).or_insert((0, None));
+        entry.0 += 1;
+        if entry.0 >= MAX_FAILED_ATTEMPTS {
+            entry.1 = Some(now + LOCKOUT);
+        }
+    }
+}
+
+fn clear_failures(plate: &str) {
+    if let Ok(mut attempts) = FAILED_ATTEMPTS.lock() {
+        attempts.remove(plate);
+    }
+}
+
+/// Check the driver's PIN for the plate; an unknown plate fails the same way as a wrong PIN
+async fn verify_driver(client: &impl db::GenericClient, plate: &str, pin: &str) -> Result<(), String> {
+    check_lockout(plate, Instant::now())?;
+    let pin_hash = db::self_check_in::driver_pin_hash(client, plate).await?;
+    let pin = pin.to_string();
+    let valid = match pin_hash {
+        Some(hash) => tokio::task::spawn_blocking(move || auth::verify_pin(&pin, &hash)).await.map_err(|e| e.to_string())?,
+        None => false,
+    };
+    if !valid {
+        register_failure(plate, Instant::now());
+        return Err(i18n::t("error.driver_pin_incorrect"));
+    }
+    clear_failures(plate);
+    Ok(())
+}
+
+/// Set or clear (None) the vehicle's driver PIN
+pub async fn set_driver_pin(client: &impl db::GenericClient, license_plate: &str, pin: Option<String>) -> Result<(), String> {
+    let pin_hash = match pin {
+        Some(pin) => {
+            auth::validate_pin(&pin)?;
+            Some(tokio::task::spawn_blocking(move || auth::hash_pin(&pin)).await.map_err(|e| e.to_string())??)
+        }
+        None => None,
+    };
+    if db::self_check_in::set_driver_pin_hash(client, license_plate, pin_hash.as_deref()).await? == 0 {
+        return Err(i18n::tf("error.vehicle_not_found_plate", &[("plate", &license_plate)]));
+    }
+    clear_failures(license_plate);
+    Ok(())
+}
+
+/// The entry ticket of a vehicle checked in on today's pass, so free like the counter's
+pub fn entry_ticket(license_plate: &str, destination_name: &str, queue_position: i32, pass: &DayPassDto) -> EntryTicket {
+    let purchased = chrono::NaiveDateTime::parse_from_str(&pass.purchase_date, "%Y-%m-%dT%H:%M:%S%.3fZ")
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| pass.purchase_date.clone());
+    EntryTicket::new(license_plate, destination_name, queue_position)
+        .with_day_pass(DayPassStatus::Valid, Some(purchased))
+        .with_staff(Some(KIOSK_STAFF_NAME.to_string()), Some(crate::SYSTEM_STAFF_ID.to_string()))
+}
+
+/// Put the driver's vehicle in the queue for `destination_id`, else its default destination
+pub async fn check_in(license_plate: &str, pin: &str, destination_id: Option<String>) -> Result<SelfCheckInDto, String> {
+    let plate = license_plate.trim();
+    let (policy, destination, pass) = {
+        let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+        let policy = db::self_check_in::policy(&client).await?;
+        if !policy.enabled {
+            return Err(i18n::t("error.self_check_in_disabled"));
+        }
+        verify_driver(&client, plate, pin).await?;
+        let authorized = db::vehicles::authorized_destinations(&client, plate).await?;
+        // Listed default first, then by priority
+        let destination = match destination_id.as_deref() {
+            Some(id) => authorized.into_iter().find(|d| d.station_id == id),
+            None => authorized.into_iter().next(),
+        }
+        .ok_or_else(|| i18n::tf("error.vehicle_not_authorized", &[("plate", &plate), ("destination", &destination_id.as_deref().unwrap_or("-"))]))?;
+        let pass = db::passes::today_pass_for_plate(&client, plate)
+            .await?
+            .ok_or_else(|| i18n::t("error.self_check_in_day_pass_required"))?;
+        (policy, destination, pass)
+    };
+
+    let entered = crate::enter_queue(plate, &destination.station_id, Some(destination.station_name.clone()), None, None, db::queue::WAITING).await?;
+    crate::spawn_waitlist_promotion(destination.station_id.clone());
+
+    let ticket = entry_ticket(plate, &entered.destination_name, entered.position, &pass);
+    let base = PRINTER_SERVICE.get_current_printer().await?.ok_or_else(|| i18n::t("error.gate_printer_invalid"))?;
+    let printer_name = match policy.gate_printer(&base) {
+        Some(gate) => {
+            let name = gate.name.clone();
+            PRINTER_SERVICE.print_entry_ticket_at(&ticket, Some(KIOSK_STAFF_NAME.to_string()), gate).await?;
+            name
+        }
+        None => {
+            PRINTER_SERVICE.print_entry_ticket(&ticket, Some(KIOSK_STAFF_NAME.to_string())).await?;
+            base.name
+        }
+    };
+
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    let details = serde_json::json!({ "licensePlate": plate, "destinationName": entered.destination_name });
+    // Free entries are watched for repeats (anomalies.rs), whoever let the vehicle in
+    audit::record(&client, anomalies::FREE_ENTRY_EVENT, None, details.clone()).await?;
+    audit::record(&client, SELF_CHECK_IN_EVENT, None, details).await?;
+    println!("🏧 [SELF CHECK-IN] {} entered the queue for {} at position {}", plate, entered.destination_name, entered.position);
+
+    Ok(SelfCheckInDto {
+        queue_id: entered.queue_id,
+        license_plate: plate.to_string(),
+        destination_name: entered.destination_name,
+        queue_position: entered.position,
+        printer_name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_pins_lock_the_plate_and_the_gate_printer_keeps_the_model() {
+        let start = Instant::now();
+        for _ in 0..MAX_FAILED_ATTEMPTS - 1 {
+            register_failure("TEST 1", start);
+        }
+        assert!(check_lockout("TEST 1", start).is_ok());
+        register_failure("TEST 1", start);
+        assert!(check_lockout("TEST 1", start).is_err());
+        assert!(check_lockout("TEST 2", start).is_ok());
+        assert!(check_lockout("TEST 1", start + LOCKOUT).is_ok());
+
+        let policy = SelfCheckInPolicy::parse(Some(r#"{"enabled":true,"gatePrinterIp":"192.168.1.50"}"#));
+        assert_eq!(policy.gate_printer_port, 9100);
+        assert!(policy.validate().is_ok());
+        let base = PrinterConfig {
+            id: "printer1".to_string(),
+            name: "Imprimante Thermique".to_string(),
+            ip: "127.0.0.1".to_string(),
+            port: 9100,
+            width: 58,
+            timeout: 1000,
+            model: "TM-T20X".to_string(),
+            enabled: true,
+            is_default: true,
+            backend: Default::default(),
+            system_name: None,
+            paper: None,
+            code_page: Default::default(),
+            failover: Vec::new(),
+        };
+        let gate = policy.gate_printer(&base).unwrap();
+        assert_eq!((gate.ip.as_str(), gate.port, gate.width), ("192.168.1.50", 9100, 58));
+        assert!(SelfCheckInPolicy::default().gate_printer(&base).is_none());
+        assert!(SelfCheckInPolicy { gate_printer_ip: Some(" ".to_string()), ..policy }.validate().is_err());
+    }
+}
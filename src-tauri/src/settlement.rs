@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::booking::SoldSeats;
+use crate::money::Money;
+
+// What a departure settles between the station and the driver, one settlement per exit pass.
+// The cashiers collected the fares and the service fee of every seat sold on the queue entry;
+// the station keeps the service fees as its commission and, on the vehicle's first exit of the
+// day, the day pass. The rest is paid to the driver with the exit pass.
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settlement {
+    pub seats_sold: i32,
+    /// Everything collected for the seats sold
+    pub gross: Money,
+    /// Service fees kept by the station
+    pub commission: Money,
+    /// Day pass held back on the first exit of the day
+    pub day_pass_fee: Money,
+    pub net_to_driver: Money,
+}
+
+impl Settlement {
+    pub fn for_departure(sold: &SoldSeats, day_pass_fee: Money) -> Self {
+        let fares = sold.due_to_driver();
+        Settlement {
+            seats_sold: sold.seats,
+            gross: sold.collected,
+            commission: sold.collected - fares,
+            day_pass_fee,
+            net_to_driver: fares - day_pass_fee,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gross_splits_into_commission_day_pass_and_net() {
+        let sold = SoldSeats { seats: 8, collected: Money::from_tnd(8.0 * 4.2) };
+        let settlement = Settlement::for_departure(&sold, Money::from_tnd(2.0));
+        assert_eq!(settlement.commission, Money::from_tnd(1.6));
+        assert_eq!(settlement.net_to_driver, Money::from_tnd(30.0));
+        assert_eq!(settlement.commission + settlement.day_pass_fee + settlement.net_to_driver, settlement.gross);
+
+        let later = Settlement::for_departure(&sold, Money::ZERO);
+        assert_eq!(later.net_to_driver, Money::from_tnd(32.0));
+    }
+}
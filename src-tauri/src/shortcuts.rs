@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{CustomMenuItem, GlobalShortcutManager, Manager, SystemTrayMenu, SystemTrayMenuItem};
+
+use crate::business_hours;
+use crate::kiosk;
+use crate::reprints::{self, ReprintKind};
+
+// Global shortcuts and tray quick actions, kept in shortcuts.json next to the executable
+// (like printer_config.json) so each station can move keys that clash with its other software.
+
+const SETTINGS_FILE: &str = "shortcuts.json";
+
+// Tray menu ids of quick actions are this prefix followed by the action name
+pub const TRAY_ACTION_PREFIX: &str = "action:";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ShortcutAction {
+    ToggleFullscreen,
+    ToggleWindow,
+    ReprintBookingTicket,
+    ReprintEntryTicket,
+    ReprintExitTicket,
+    ReprintExitPass,
+    ReprintDayPass,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 7] = [
+        ShortcutAction::ToggleFullscreen,
+        ShortcutAction::ToggleWindow,
+        ShortcutAction::ReprintBookingTicket,
+        ShortcutAction::ReprintEntryTicket,
+        ShortcutAction::ReprintExitTicket,
+        ShortcutAction::ReprintExitPass,
+        ShortcutAction::ReprintDayPass,
+    ];
+
+    pub fn from_name(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleFullscreen => "TOGGLE_FULLSCREEN",
+            ShortcutAction::ToggleWindow => "TOGGLE_WINDOW",
+            ShortcutAction::ReprintBookingTicket => "REPRINT_BOOKING_TICKET",
+            ShortcutAction::ReprintEntryTicket => "REPRINT_ENTRY_TICKET",
+            ShortcutAction::ReprintExitTicket => "REPRINT_EXIT_TICKET",
+            ShortcutAction::ReprintExitPass => "REPRINT_EXIT_PASS",
+            ShortcutAction::ReprintDayPass => "REPRINT_DAY_PASS",
+        }
+    }
+
+    // Shown in the tray menu and the settings page
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleFullscreen => "Basculer plein écran",
+            ShortcutAction::ToggleWindow => "Afficher / masquer",
+            ShortcutAction::ReprintBookingTicket => "Réimprimer le dernier ticket",
+            ShortcutAction::ReprintEntryTicket => "Réimprimer la dernière entrée",
+            ShortcutAction::ReprintExitTicket => "Réimprimer la dernière sortie",
+            ShortcutAction::ReprintExitPass => "Réimprimer la dernière autorisation de sortie",
+            ShortcutAction::ReprintDayPass => "Réimprimer le dernier pass",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBinding {
+    pub action: ShortcutAction,
+    pub accelerator: String, // Tauri accelerator, e.g. "CommandOrControl+Shift+H"
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShortcutSettings {
+    pub shortcuts: Vec<ShortcutBinding>,
+    pub tray_actions: Vec<ShortcutAction>,
+}
+
+impl Default for ShortcutSettings {
+    fn default() -> Self {
+        ShortcutSettings {
+            shortcuts: vec![
+                ShortcutBinding { action: ShortcutAction::ToggleFullscreen, accelerator: "F11".to_string() },
+                ShortcutBinding { action: ShortcutAction::ToggleWindow, accelerator: "CommandOrControl+Shift+H".to_string() },
+            ],
+            tray_actions: Vec::new(),
+        }
+    }
+}
+
+impl ShortcutSettings {
+    /// Bind `action` to `accelerator`, or unbind it when None. One key runs one action.
+    pub fn bind(&mut self, action: ShortcutAction, accelerator: Option<&str>) -> Result<(), String> {
+        self.shortcuts.retain(|binding| binding.action != action);
+        let accelerator = match accelerator.map(str::trim).filter(|a| !a.is_empty()) {
+            Some(accelerator) => accelerator,
+            None => return Ok(()),
+        };
+        if let Some(other) = self.shortcuts.iter().find(|b| b.accelerator.eq_ignore_ascii_case(accelerator)) {
+            return Err(format!("{} est déjà utilisé pour « {} »", accelerator, other.action.label()));
+        }
+        self.shortcuts.push(ShortcutBinding { action, accelerator: accelerator.to_string() });
+        Ok(())
+    }
+}
+
+fn settings_path() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(SETTINGS_FILE)))
+        .unwrap_or_else(|| PathBuf::from(SETTINGS_FILE))
+}
+
+/// Saved settings, or the defaults when the file is missing or unreadable
+pub fn load() -> ShortcutSettings {
+    let path = settings_path();
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            println!("⚠️ [SHORTCUTS] Ignoring {:?}: {}", path, e);
+            ShortcutSettings::default()
+        }),
+        Err(_) => ShortcutSettings::default(),
+    }
+}
+
+fn save(settings: &ShortcutSettings) -> Result<(), String> {
+    let path = settings_path();
+    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Impossible d'enregistrer {:?}: {}", path, e))
+}
+
+/// Run a shortcut or tray quick action against the cashier window
+pub fn run_action(app_handle: &tauri::AppHandle, action: ShortcutAction) {
+    let window = match app_handle.get_window("main") {
+        Some(window) => window,
+        None => return,
+    };
+    match action {
+        ShortcutAction::ToggleFullscreen => {
+            if let Ok(is_fullscreen) = window.is_fullscreen() {
+                let _ = window.set_fullscreen(!is_fullscreen);
+            }
+        }
+        ShortcutAction::ToggleWindow => {
+            if window.is_visible().unwrap_or(false) {
+                let _ = window.hide();
+            } else {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        reprint => {
+            tauri::async_runtime::spawn(async move {
+                let kind = match reprint {
+                    ShortcutAction::ReprintBookingTicket => ReprintKind::Booking,
+                    ShortcutAction::ReprintEntryTicket => ReprintKind::Entry,
+                    ShortcutAction::ReprintExitTicket => ReprintKind::Exit,
+                    ShortcutAction::ReprintExitPass => ReprintKind::ExitPass,
+                    ShortcutAction::ReprintDayPass => ReprintKind::DayPass,
+                    _ => return,
+                };
+                let result = reprints::reprint(kind, None).await;
+                match result {
+                    Ok(_) => println!("🖨️ [SHORTCUTS] {}", reprint.label()),
+                    Err(e) => println!("⚠️ [SHORTCUTS] {} failed: {}", reprint.label(), e),
+                }
+            });
+        }
+    }
+}
+
+/// (Re)register the configured global shortcuts; nothing is registered while in kiosk mode
+pub fn register(app_handle: &tauri::AppHandle, settings: &ShortcutSettings) -> Result<(), String> {
+    let mut shortcut_manager = app_handle.global_shortcut_manager();
+    shortcut_manager.unregister_all().map_err(|e| e.to_string())?;
+    if kiosk::is_active() {
+        return Ok(());
+    }
+    for binding in &settings.shortcuts {
+        let handle = app_handle.clone();
+        let action = binding.action;
+        shortcut_manager
+            .register(&binding.accelerator, move || run_action(&handle, action))
+            .map_err(|e| format!("Raccourci {} refusé: {}", binding.accelerator, e))?;
+    }
+    Ok(())
+}
+
+/// Tray menu with the configured quick actions above the fixed entries
+pub fn tray_menu(settings: &ShortcutSettings) -> SystemTrayMenu {
+    // OPEN/CLOSED, kept up to date by business_hours.rs
+    let mut menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new(business_hours::TRAY_STATUS_ITEM, business_hours::tray_title()).disabled())
+        .add_native_item(SystemTrayMenuItem::Separator);
+    for action in &settings.tray_actions {
+        menu = menu.add_item(CustomMenuItem::new(format!("{}{}", TRAY_ACTION_PREFIX, action.as_str()), action.label()));
+    }
+    if !settings.tray_actions.is_empty() {
+        menu = menu.add_native_item(SystemTrayMenuItem::Separator);
+    }
+    menu.add_item(CustomMenuItem::new("show".to_string(), "Afficher"))
+        .add_item(CustomMenuItem::new("hide".to_string(), "Masquer"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("fullscreen".to_string(), "Basculer plein écran"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("startup".to_string(), "Démarrage automatique"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("quit".to_string(), "Quitter"))
+}
+
+#[tauri::command]
+pub async fn get_shortcuts() -> Result<ShortcutSettings, String> {
+    Ok(load())
+}
+
+/// Change the key of one action (None removes it) and apply it right away
+#[tauri::command]
+pub async fn set_shortcut(app_handle: tauri::AppHandle, action: String, accelerator: Option<String>) -> Result<ShortcutSettings, String> {
+    let action = ShortcutAction::from_name(&action).ok_or_else(|| format!("Action inconnue: {}", action))?;
+    let previous = load();
+    let mut settings = previous.clone();
+    settings.bind(action, accelerator.as_deref())?;
+    if let Err(e) = register(&app_handle, &settings) {
+        // Keep the station usable with the keys it had
+        let _ = register(&app_handle, &previous);
+        return Err(e);
+    }
+    save(&settings)?;
+    println!("⌨️ [SHORTCUTS] {} -> {:?}", action.as_str(), accelerator);
+    Ok(settings)
+}
+
+/// Replace the quick actions listed at the top of the tray menu
+#[tauri::command]
+pub async fn set_tray_actions(app_handle: tauri::AppHandle, actions: Vec<String>) -> Result<ShortcutSettings, String> {
+    let mut tray_actions = Vec::new();
+    for name in &actions {
+        let action = ShortcutAction::from_name(name).ok_or_else(|| format!("Action inconnue: {}", name))?;
+        if !tray_actions.contains(&action) {
+            tray_actions.push(action);
+        }
+    }
+    let mut settings = load();
+    settings.tray_actions = tray_actions;
+    save(&settings)?;
+    app_handle.tray_handle().set_menu(tray_menu(&settings)).map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_keep_the_historical_keys_and_missing_fields_fall_back() {
+        let defaults = ShortcutSettings::default();
+        assert_eq!(defaults.shortcuts[0].accelerator, "F11");
+        assert_eq!(defaults.shortcuts[1].action, ShortcutAction::ToggleWindow);
+
+        let saved: ShortcutSettings = serde_json::from_str(r#"{"trayActions":["REPRINT_EXIT_PASS"]}"#).unwrap();
+        assert_eq!(saved.tray_actions, vec![ShortcutAction::ReprintExitPass]);
+        assert_eq!(saved.shortcuts, defaults.shortcuts);
+    }
+
+    #[test]
+    fn a_key_runs_one_action() {
+        let mut settings = ShortcutSettings::default();
+        assert!(settings.bind(ShortcutAction::ReprintExitPass, Some("f11")).is_err());
+        settings.bind(ShortcutAction::ToggleFullscreen, Some("F10")).unwrap();
+        settings.bind(ShortcutAction::ReprintExitPass, Some("F11")).unwrap();
+        assert_eq!(settings.shortcuts.len(), 3);
+
+        settings.bind(ShortcutAction::ToggleWindow, None).unwrap();
+        assert!(settings.shortcuts.iter().all(|b| b.action != ShortcutAction::ToggleWindow));
+        assert_eq!(ShortcutAction::from_name("reprint_exit_pass"), Some(ShortcutAction::ReprintExitPass));
+    }
+}
@@ -0,0 +1,36 @@
+// Outgoing SMS through an HTTP gateway. SMS_GATEWAY_URL receives a JSON POST
+// {"to": "+216...", "message": "..."}, with SMS_GATEWAY_TOKEN (credential store, or the environment)
+// sent as a bearer token when set.
+// Without a gateway, or in training mode, texts are only logged.
+use std::time::Duration;
+
+use crate::secrets;
+use crate::training;
+
+pub async fn send(phone_number: &str, message: &str) -> Result<(), String> {
+    if training::is_active() {
+        println!("🎓 [SMS] Training mode, not sending to {}: {}", phone_number, message);
+        return Ok(());
+    }
+    let url = match std::env::var("SMS_GATEWAY_URL").ok().filter(|v| !v.trim().is_empty()) {
+        Some(url) => url,
+        None => {
+            println!("📵 [SMS] No gateway configured, not sending to {}: {}", phone_number, message);
+            return Ok(());
+        }
+    };
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.post(url.trim()).json(&serde_json::json!({ "to": phone_number, "message": message }));
+    if let Some(token) = secrets::lookup(secrets::SMS_GATEWAY_TOKEN).or_else(|| std::env::var("SMS_GATEWAY_TOKEN").ok()) {
+        request = request.bearer_auth(token.trim());
+    }
+    let response = request.send().await.map_err(|e| format!("Passerelle SMS injoignable: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Passerelle SMS: HTTP {}", response.status()));
+    }
+    println!("📱 [SMS] Sent to {}", phone_number);
+    Ok(())
+}
@@ -0,0 +1,191 @@
+// Windows print spooler backend.
+// Sends raw ESC/POS bytes to a printer installed through its Windows driver
+// (e.g. "EPSON TM-T20") using the RAW datatype, so the driver passes the bytes
+// through untouched instead of rendering them.
+
+#[cfg(windows)]
+#[allow(non_snake_case, non_camel_case_types, clippy::upper_case_acronyms)]
+mod ffi {
+    use std::ffi::c_void;
+
+    pub type HANDLE = *mut c_void;
+    pub type BOOL = i32;
+
+    #[repr(C)]
+    pub struct DOC_INFO_1W {
+        pub pDocName: *const u16,
+        pub pOutputFile: *const u16,
+        pub pDatatype: *const u16,
+    }
+
+    #[repr(C)]
+    pub struct PRINTER_INFO_4W {
+        pub pPrinterName: *mut u16,
+        pub pServerName: *mut u16,
+        pub Attributes: u32,
+    }
+
+    pub const PRINTER_ENUM_LOCAL: u32 = 0x0000_0002;
+    pub const PRINTER_ENUM_CONNECTIONS: u32 = 0x0000_0004;
+
+    #[link(name = "winspool")]
+    extern "system" {
+        pub fn OpenPrinterW(pPrinterName: *const u16, phPrinter: *mut HANDLE, pDefault: *const c_void) -> BOOL;
+        pub fn ClosePrinter(hPrinter: HANDLE) -> BOOL;
+        pub fn StartDocPrinterW(hPrinter: HANDLE, Level: u32, pDocInfo: *const DOC_INFO_1W) -> u32;
+        pub fn EndDocPrinter(hPrinter: HANDLE) -> BOOL;
+        pub fn StartPagePrinter(hPrinter: HANDLE) -> BOOL;
+        pub fn EndPagePrinter(hPrinter: HANDLE) -> BOOL;
+        pub fn WritePrinter(hPrinter: HANDLE, pBuf: *const c_void, cbBuf: u32, pcWritten: *mut u32) -> BOOL;
+        pub fn EnumPrintersW(
+            Flags: u32,
+            Name: *const u16,
+            Level: u32,
+            pPrinterEnum: *mut u8,
+            cbBuf: u32,
+            pcbNeeded: *mut u32,
+            pcReturned: *mut u32,
+        ) -> BOOL;
+    }
+}
+
+#[cfg(windows)]
+fn to_wide(value: &str) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+    std::ffi::OsStr::new(value)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+#[cfg(windows)]
+unsafe fn from_wide_ptr(ptr: *const u16) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+    String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len))
+}
+
+/// Send raw bytes to a named Windows printer as a single RAW document
+#[cfg(windows)]
+pub fn send_raw(printer_name: &str, document_name: &str, bytes: &[u8]) -> Result<(), String> {
+    use ffi::*;
+    use std::ptr;
+
+    let printer_name_w = to_wide(printer_name);
+    let document_name_w = to_wide(document_name);
+    let datatype_w = to_wide("RAW");
+
+    unsafe {
+        let mut handle: HANDLE = ptr::null_mut();
+        if OpenPrinterW(printer_name_w.as_ptr(), &mut handle, ptr::null()) == 0 {
+            return Err(format!(
+                "Failed to open printer '{}': {}",
+                printer_name,
+                std::io::Error::last_os_error()
+            ));
+        }
+
+        let doc_info = DOC_INFO_1W {
+            pDocName: document_name_w.as_ptr(),
+            pOutputFile: ptr::null(),
+            pDatatype: datatype_w.as_ptr(),
+        };
+
+        let result = (|| {
+            if StartDocPrinterW(handle, 1, &doc_info) == 0 {
+                return Err(format!("Failed to start print document: {}", std::io::Error::last_os_error()));
+            }
+            if StartPagePrinter(handle) == 0 {
+                let err = std::io::Error::last_os_error();
+                EndDocPrinter(handle);
+                return Err(format!("Failed to start print page: {}", err));
+            }
+
+            let mut written: u32 = 0;
+            let ok = WritePrinter(
+                handle,
+                bytes.as_ptr() as *const std::ffi::c_void,
+                bytes.len() as u32,
+                &mut written,
+            );
+            let write_err = std::io::Error::last_os_error();
+
+            EndPagePrinter(handle);
+            EndDocPrinter(handle);
+
+            if ok == 0 {
+                return Err(format!("Failed to send print data: {}", write_err));
+            }
+            if written as usize != bytes.len() {
+                return Err(format!(
+                    "Incomplete print data sent to spooler ({} of {} bytes)",
+                    written,
+                    bytes.len()
+                ));
+            }
+            Ok(())
+        })();
+
+        ClosePrinter(handle);
+        result
+    }
+}
+
+#[cfg(not(windows))]
+pub fn send_raw(printer_name: &str, _document_name: &str, _bytes: &[u8]) -> Result<(), String> {
+    Err(format!(
+        "Spooler printing to '{}' is only available on Windows",
+        printer_name
+    ))
+}
+
+/// List the names of local and connected printers known to the Windows spooler
+#[cfg(windows)]
+pub fn list_printers() -> Result<Vec<String>, String> {
+    use ffi::*;
+    use std::ptr;
+
+    let flags = PRINTER_ENUM_LOCAL | PRINTER_ENUM_CONNECTIONS;
+    let mut needed: u32 = 0;
+    let mut returned: u32 = 0;
+
+    unsafe {
+        // First call only reports the buffer size required
+        EnumPrintersW(flags, ptr::null(), 4, ptr::null_mut(), 0, &mut needed, &mut returned);
+        if needed == 0 {
+            return Ok(Vec::new());
+        }
+
+        // u64 backing storage keeps the PRINTER_INFO_4W records properly aligned
+        let mut buffer: Vec<u64> = vec![0; (needed as usize + 7) / 8];
+        if EnumPrintersW(
+            flags,
+            ptr::null(),
+            4,
+            buffer.as_mut_ptr() as *mut u8,
+            (buffer.len() * 8) as u32,
+            &mut needed,
+            &mut returned,
+        ) == 0
+        {
+            return Err(format!("Failed to enumerate printers: {}", std::io::Error::last_os_error()));
+        }
+
+        let infos = std::slice::from_raw_parts(buffer.as_ptr() as *const PRINTER_INFO_4W, returned as usize);
+        Ok(infos
+            .iter()
+            .map(|info| from_wide_ptr(info.pPrinterName))
+            .filter(|name| !name.is_empty())
+            .collect())
+    }
+}
+
+#[cfg(not(windows))]
+pub fn list_printers() -> Result<Vec<String>, String> {
+    Err("System printer enumeration is only available on Windows".to_string())
+}
@@ -0,0 +1,117 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::db;
+use crate::db::staff::StaffName;
+
+// Staff names for tickets and receipts, read several times in a booking, an end of trip or a
+// day pass. Cached per staff id, unknown ids included, for as long as the app runs. The
+// staff_changes NOTIFY (migration 029) drops an id whenever its name changes on any terminal.
+// While the listener (cache_listener.rs) is not connected nothing is cached, since changes made
+// on other terminals would go unseen.
+
+static CACHE: Lazy<Mutex<StaffNameCache>> = Lazy::new(|| Mutex::new(StaffNameCache::default()));
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default)]
+pub struct StaffNameCache {
+    names: HashMap<String, Option<StaffName>>,
+    // Bumped by every invalidation, so a read that raced with a change is not stored
+    generation: u64,
+}
+
+impl StaffNameCache {
+    /// The cached name (None inside when the id is unknown), or the generation to store a read with
+    pub fn lookup(&self, staff_id: &str) -> Result<Option<StaffName>, u64> {
+        self.names.get(staff_id).cloned().ok_or(self.generation)
+    }
+
+    pub fn store(&mut self, generation: u64, staff_id: &str, name: Option<StaffName>) {
+        if self.generation == generation {
+            self.names.insert(staff_id.to_string(), name);
+        }
+    }
+
+    pub fn invalidate(&mut self, staff_id: &str) {
+        self.names.remove(staff_id);
+        self.generation += 1;
+    }
+
+    /// Empty the cache; the number of names dropped
+    pub fn clear(&mut self) -> usize {
+        let dropped = self.names.len();
+        self.names.clear();
+        self.generation += 1;
+        dropped
+    }
+}
+
+fn cache() -> std::sync::MutexGuard<'static, StaffNameCache> {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Name of a staff member, from the cache where possible; None when the id is unknown
+pub async fn get(client: &impl db::GenericClient, staff_id: &str) -> Result<Option<StaffName>, String> {
+    if !LISTENING.load(Ordering::SeqCst) {
+        return db::staff::name(client, staff_id).await;
+    }
+    let generation = match cache().lookup(staff_id) {
+        Ok(name) => return Ok(name),
+        Err(generation) => generation,
+    };
+    let name = db::staff::name(client, staff_id).await?;
+    cache().store(generation, staff_id, name.clone());
+    Ok(name)
+}
+
+/// "First Last" of a staff member; None when the id is unknown
+pub async fn display(client: &impl db::GenericClient, staff_id: &str) -> Result<Option<String>, String> {
+    Ok(get(client, staff_id).await?.map(|name| name.full()))
+}
+
+/// Called by the cache listener as its connection comes and goes; the cache starts empty either way
+pub fn set_listening(on: bool) {
+    cache().clear();
+    LISTENING.store(on, Ordering::SeqCst);
+}
+
+/// The name of `staff_id` changed on some terminal
+pub fn invalidate(staff_id: &str) {
+    cache().invalidate(staff_id);
+}
+
+/// Drop every cached name, so the next lookups read the database
+pub fn refresh() -> usize {
+    cache().clear()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(first: &str) -> Option<StaffName> {
+        Some(StaffName { first_name: first.to_string(), last_name: "Ben Ali".to_string() })
+    }
+
+    #[test]
+    fn names_are_kept_until_changed_and_racing_reads_are_dropped() {
+        let mut cache = StaffNameCache::default();
+        let generation = cache.lookup("s1").unwrap_err();
+        cache.store(generation, "s1", name("Sami"));
+        cache.store(generation, "ghost", None);
+        assert_eq!(cache.lookup("s1"), Ok(name("Sami")));
+        assert_eq!(cache.lookup("ghost"), Ok(None));
+        assert_eq!(name("Sami").unwrap().full(), "Sami Ben Ali");
+
+        // A read started before the rename must not put the old name back
+        let generation = cache.lookup("s2").unwrap_err();
+        cache.invalidate("s1");
+        cache.store(generation, "s2", name("Ines"));
+        assert!(cache.lookup("s1").is_err());
+        assert!(cache.lookup("s2").is_err());
+        assert_eq!(cache.clear(), 1);
+        assert!(cache.lookup("ghost").is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+// Identity of this cashier PC. An id is generated on first run and kept in terminal.json in the
+// app config dir; every booking, pass and audit row written from here carries it, and the
+// terminal registers itself in the terminals table at startup so head office can name it.
+
+const TERMINAL_FILE: &str = "terminal.json";
+
+static TERMINAL: OnceCell<TerminalInfo> = OnceCell::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalInfo {
+    pub terminal_id: String,
+    pub name: String,
+    pub hostname: String,
+    pub provisioned_at: DateTime<Utc>,
+}
+
+impl TerminalInfo {
+    pub fn new(hostname: &str) -> Self {
+        let terminal_id = uuid::Uuid::new_v4().to_string();
+        let name = match hostname {
+            "" => format!("Caisse {}", &terminal_id[..8]),
+            host => host.to_string(),
+        };
+        TerminalInfo { terminal_id, name, hostname: hostname.to_string(), provisioned_at: Utc::now() }
+    }
+}
+
+/// Saved identity, or a new one when the file is missing or unreadable
+pub fn load_or_new(saved: Option<&str>, hostname: &str) -> (TerminalInfo, bool) {
+    match saved.and_then(|content| serde_json::from_str::<TerminalInfo>(content).ok()) {
+        Some(info) if !info.terminal_id.is_empty() => (info, false),
+        _ => (TerminalInfo::new(hostname), true),
+    }
+}
+
+fn hostname() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn terminal_path(app_handle: &tauri::AppHandle) -> PathBuf {
+    app_handle
+        .path_resolver()
+        .app_config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(TERMINAL_FILE)
+}
+
+/// Load this terminal's identity, generating and saving it on first run
+pub fn provision(app_handle: &tauri::AppHandle) -> &'static TerminalInfo {
+    TERMINAL.get_or_init(|| {
+        let path = terminal_path(app_handle);
+        let (info, generated) = load_or_new(fs::read_to_string(&path).ok().as_deref(), &hostname());
+        if generated {
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            match serde_json::to_string_pretty(&info).map_err(|e| e.to_string()).and_then(|c| fs::write(&path, c).map_err(|e| e.to_string())) {
+                Ok(()) => println!("🖥️ New terminal {} ({})", info.terminal_id, info.name),
+                Err(e) => println!("⚠️ Terminal id {} could not be saved to {:?}: {}", info.terminal_id, path, e),
+            }
+        }
+        info
+    })
+}
+
+/// Id stamped on the rows this terminal writes; None until provisioned
+pub fn id() -> Option<&'static str> {
+    TERMINAL.get().map(|info| info.terminal_id.as_str())
+}
+
+pub fn info() -> Option<&'static TerminalInfo> {
+    TERMINAL.get()
+}
+
+#[tauri::command]
+pub async fn get_terminal_info(app_handle: tauri::AppHandle) -> Result<TerminalInfo, String> {
+    Ok(provision(&app_handle).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_identity_is_kept_and_a_new_one_is_named_after_the_host() {
+        let first = TerminalInfo::new("CAISSE-2");
+        assert_eq!(first.name, "CAISSE-2");
+        let saved = serde_json::to_string(&first).unwrap();
+        assert_eq!(load_or_new(Some(&saved), "OTHER"), (first.clone(), false));
+
+        let (fresh, generated) = load_or_new(Some("{not json"), "");
+        assert!(generated);
+        assert_ne!(fresh.terminal_id, first.terminal_id);
+        assert!(fresh.name.starts_with("Caisse "));
+        assert!(load_or_new(None, "CAISSE-3").1);
+    }
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::db;
+use crate::db::terminal_messages::TerminalMessageDto;
+use crate::i18n;
+use crate::terminal;
+use crate::DB_POOL;
+
+// Short messages between terminals, e.g. a supervisor telling booth 2 to stop selling Sfax.
+// A message is a row in terminal_messages (migration 025); its trigger notifies the
+// terminal_messages channel, which cache_listener hands to `notified`. The receiving terminal
+// shows it until a staff member acknowledges it, and the sender then hears about the
+// acknowledgment. A terminal whose LISTEN connection was down reads its inbox with
+// db_get_terminal_messages instead.
+
+pub const MESSAGE_EVENT: &str = "terminal-message";
+pub const ACKNOWLEDGED_EVENT: &str = "terminal-message-acknowledged";
+pub const MAX_MESSAGE_CHARS: usize = 280;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MessagePriority {
+    Normal,
+    Urgent,
+}
+
+impl MessagePriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessagePriority::Normal => "NORMAL",
+            MessagePriority::Urgent => "URGENT",
+        }
+    }
+
+    pub fn parse(priority: &str) -> Self {
+        if priority == "URGENT" { MessagePriority::Urgent } else { MessagePriority::Normal }
+    }
+}
+
+// Payload of the terminal_messages notification
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageNotice {
+    id: String,
+    from_terminal_id: String,
+    target_terminal_id: String,
+    acknowledged: bool,
+}
+
+/// The text as sent: trimmed, not empty and at most MAX_MESSAGE_CHARS characters
+pub fn validate_text(text: &str) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(i18n::t("error.message_empty"));
+    }
+    if text.chars().count() > MAX_MESSAGE_CHARS {
+        return Err(i18n::tf("error.message_too_long", &[("max", &MAX_MESSAGE_CHARS)]));
+    }
+    Ok(text.to_string())
+}
+
+// The UI event a notice means for `terminal_id`: a new message for the receiver, the
+// acknowledgment for the sender, nothing for the other terminals
+fn event_for(notice: &MessageNotice, terminal_id: &str) -> Option<&'static str> {
+    match notice.acknowledged {
+        false if notice.target_terminal_id == terminal_id => Some(MESSAGE_EVENT),
+        true if notice.from_terminal_id == terminal_id => Some(ACKNOWLEDGED_EVENT),
+        _ => None,
+    }
+}
+
+/// Send `text` from this terminal to `target_terminal_id`
+pub async fn send(target_terminal_id: &str, text: &str, priority: MessagePriority, sent_by: &Option<String>) -> Result<TerminalMessageDto, String> {
+    let text = validate_text(text)?;
+    let from = terminal::id().ok_or_else(|| i18n::t("error.terminal_unknown"))?;
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    if !db::terminal_messages::terminal_exists(&client, target_terminal_id).await? {
+        return Err(i18n::tf("error.terminal_not_found", &[("id", &target_terminal_id)]));
+    }
+    let id = db::terminal_messages::insert(&client, from, target_terminal_id, sent_by, &text, priority).await?;
+    db::terminal_messages::find(&client, &id).await?.ok_or_else(|| i18n::t("error.message_not_found"))
+}
+
+/// Acknowledge a message received by this terminal
+pub async fn acknowledge(message_id: &str, staff_id: &Option<String>) -> Result<TerminalMessageDto, String> {
+    let terminal_id = terminal::id().ok_or_else(|| i18n::t("error.terminal_unknown"))?;
+    let client = DB_POOL.get().await.map_err(|e| e.to_string())?;
+    if !db::terminal_messages::acknowledge(&client, message_id, terminal_id, staff_id).await? {
+        return Err(i18n::t("error.message_not_found"));
+    }
+    db::terminal_messages::find(&client, message_id).await?.ok_or_else(|| i18n::t("error.message_not_found"))
+}
+
+/// Tell the UI about a message sent to this terminal, or acknowledged by the terminal it was sent to
+pub fn notified(app_handle: &tauri::AppHandle, payload: &str) {
+    let Ok(notice) = serde_json::from_str::<MessageNotice>(payload) else {
+        return;
+    };
+    let Some(event) = terminal::id().and_then(|id| event_for(&notice, id)) else {
+        return;
+    };
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        // From the primary: a replica may not have the row yet
+        let message = match DB_POOL.get().await {
+            Ok(client) => db::terminal_messages::find(&client, &notice.id).await,
+            Err(e) => Err(e.to_string()),
+        };
+        match message {
+            Ok(Some(message)) => {
+                let _ = app_handle.emit_all(event, &message);
+            }
+            Ok(None) => {}
+            Err(e) => println!("⚠️ [MESSAGES] Message {} not read: {}", notice.id, e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notices_reach_the_receiver_then_the_sender() {
+        let mut notice = MessageNotice { id: "m".into(), from_terminal_id: "office".into(), target_terminal_id: "booth-2".into(), acknowledged: false };
+        assert_eq!(event_for(&notice, "booth-2"), Some(MESSAGE_EVENT));
+        assert_eq!(event_for(&notice, "office"), None);
+        notice.acknowledged = true;
+        assert_eq!(event_for(&notice, "office"), Some(ACKNOWLEDGED_EVENT));
+        assert_eq!(event_for(&notice, "booth-2"), None);
+        assert_eq!(event_for(&notice, "booth-3"), None);
+
+        assert_eq!(validate_text("  Stop Sfax  ").unwrap(), "Stop Sfax");
+        assert!(validate_text("   ").is_err());
+        assert!(validate_text(&"x".repeat(MAX_MESSAGE_CHARS + 1)).is_err());
+    }
+}
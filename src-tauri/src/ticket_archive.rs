@@ -0,0 +1,169 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+
+use crate::db;
+use crate::db::printed_tickets::{NewPrintedTicket, PrintedTicketSearch};
+use crate::i18n;
+use crate::printer::{PrintJobType, PrintedJob, QueuedPrintJob};
+use crate::time;
+use crate::DB_POOL;
+
+// The print history for customer disputes: every job the print queue printed is archived in
+// printed_tickets with the text as it came out, the device that printed it and the payload it
+// was built from. Archiving runs after the job printed and never holds up the queue; a ticket
+// the database missed is only logged. Tickets are searched by type, number, cashier, printer or
+// any text on them, and any of them can be exported again as a PDF. Tickets issued without a
+// printer are archived unprinted (ticket_escrow.rs) and marked printed when they come out.
+
+const DEFAULT_DAYS: i64 = 30;
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_LIMIT: u32 = 500;
+
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrintedTicketFilters {
+    /// A print job kind, as PrintJobType::from_kind reads it ("entry", "day_pass")
+    pub ticket_type: Option<String>,
+    pub ticket_number: Option<String>,
+    pub staff_name: Option<String>,
+    pub printer: Option<String>,
+    /// Anywhere in the printed text: a plate, a destination
+    pub text: Option<String>,
+    /// YYYY-MM-DD, Tunis days; `to` defaults to today and `from` to 30 days before it
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub limit: Option<u32>,
+}
+
+fn criterion(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+fn parse_day(value: &Option<String>, key: &str) -> Result<Option<NaiveDate>, String> {
+    match criterion(value) {
+        Some(day) => NaiveDate::parse_from_str(&day, "%Y-%m-%d").map(Some).map_err(|_| i18n::tf(key, &[("date", &day)])),
+        None => Ok(None),
+    }
+}
+
+impl PrintedTicketFilters {
+    pub fn resolve(&self, today: NaiveDate) -> Result<PrintedTicketSearch, String> {
+        let to = parse_day(&self.to, "error.invalid_end_date")?.unwrap_or(today);
+        let from = parse_day(&self.from, "error.invalid_start_date")?.unwrap_or(to - Duration::days(DEFAULT_DAYS));
+        if to < from {
+            return Err(i18n::t("error.end_before_start"));
+        }
+        let ticket_type = match criterion(&self.ticket_type) {
+            Some(kind) => Some(job_type_name(
+                &PrintJobType::from_kind(&kind).ok_or_else(|| i18n::tf("error.ticket_kind_invalid", &[("kind", &kind)]))?,
+            )),
+            None => None,
+        };
+        Ok(PrintedTicketSearch {
+            ticket_type,
+            ticket_number: criterion(&self.ticket_number),
+            staff_name: criterion(&self.staff_name),
+            printer: criterion(&self.printer),
+            text: criterion(&self.text),
+            from: time::tunis_day_bounds(from).0,
+            to: time::tunis_day_bounds(to).1,
+            limit: self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as i64,
+        })
+    }
+}
+
+fn job_type_name(job_type: &PrintJobType) -> String {
+    format!("{:?}", job_type)
+}
+
+fn payload_field(content: &str, keys: &[&str]) -> Option<String> {
+    let parsed: serde_json::Value = serde_json::from_str(content).ok()?;
+    keys.iter()
+        .filter_map(|key| parsed.get(key).and_then(|v| v.as_str()))
+        .map(str::trim)
+        .find(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// The number a ticket payload carries: its ticket number, else a booking's verification code
+pub fn ticket_number(content: &str) -> Option<String> {
+    payload_field(content, &["ticketNumber", "dayPassNumber", "verificationCode"])
+}
+
+/// Cashier on the ticket, from the job or its payload
+pub fn staff_name(job: &QueuedPrintJob) -> Option<String> {
+    job.staff_name.clone().or_else(|| payload_field(&job.content, &["staffName"]))
+}
+
+/// Archive a printed job in the background; an escrowed ticket is marked printed instead
+pub fn archive(job: &QueuedPrintJob, printed: PrintedJob) {
+    let job = job.clone();
+    tokio::spawn(async move {
+        if let Some(escrow_id) = &job.escrow_id {
+            let marked = match DB_POOL.get().await {
+                Ok(client) => db::printed_tickets::mark_printed(&client, escrow_id, &printed.printer_id, &printed.printer_name).await,
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = marked {
+                println!("⚠️ [ARCHIVE] Escrowed ticket {} not marked printed: {}", escrow_id, e);
+            }
+            return;
+        }
+        let staff_name = staff_name(&job);
+        let number = ticket_number(&job.content);
+        let ticket = NewPrintedTicket {
+            job_id: &job.id,
+            ticket_type: &job_type_name(&job.job_type),
+            ticket_number: number.as_deref(),
+            staff_name: staff_name.as_deref(),
+            printer_id: &printed.printer_id,
+            printer_name: &printed.printer_name,
+            duplicate_number: job.duplicate.map(|n| n as i32),
+            payload: &job.content,
+            rendered_text: &printed.text,
+            width: printed.width as i16,
+            status: db::printed_tickets::PRINTED,
+        };
+        let stored = match DB_POOL.get().await {
+            Ok(client) => db::printed_tickets::insert(&client, &ticket).await,
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = stored {
+            println!("⚠️ [ARCHIVE] Printed job {} not archived: {}", job.id, e);
+        }
+    });
+}
+
+/// An archived ticket as a PDF, base64 encoded for the frontend to save
+pub async fn pdf(client: &impl db::GenericClient, id: &str) -> Result<String, String> {
+    let ticket = db::printed_tickets::get(client, id).await?.ok_or_else(|| i18n::t("error.printed_ticket_not_found"))?;
+    Ok(STANDARD.encode(crate::pdf::text_page(&ticket.rendered_text, ticket.width.max(0) as usize)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_default_to_the_last_month_and_name_job_types() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let search = PrintedTicketFilters { ticket_type: Some("day_pass".into()), staff_name: Some("  ".into()), ..Default::default() }
+            .resolve(today)
+            .unwrap();
+        assert_eq!(search.ticket_type.as_deref(), Some("DayPassTicket"));
+        assert_eq!(search.staff_name, None);
+        assert_eq!(search.from, time::tunis_day_bounds(NaiveDate::from_ymd_opt(2026, 9, 15).unwrap()).0);
+        assert_eq!(search.to, time::tunis_day_bounds(today).1);
+        assert_eq!(search.limit, DEFAULT_LIMIT as i64);
+
+        let backwards = PrintedTicketFilters { from: Some("2026-10-16".into()), to: Some("2026-10-15".into()), ..Default::default() };
+        assert!(backwards.resolve(today).is_err());
+        assert!(PrintedTicketFilters { ticket_type: Some("menu".into()), ..Default::default() }.resolve(today).is_err());
+        assert_eq!(PrintedTicketFilters { limit: Some(10_000), ..Default::default() }.resolve(today).unwrap().limit, MAX_LIMIT as i64);
+
+        assert_eq!(ticket_number(r#"{"ticketNumber":"ENTRY-1","licensePlate":"1 TU 1"}"#).as_deref(), Some("ENTRY-1"));
+        assert_eq!(ticket_number(r#"{"verificationCode":"AB12","ticketNumber":""}"#).as_deref(), Some("AB12"));
+        assert_eq!(ticket_number("Sortie 1 TU 1"), None);
+    }
+}
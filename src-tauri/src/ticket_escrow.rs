@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::db;
+use crate::db::printed_tickets::{NewPrintedTicket, PrintedTicketDto};
+use crate::i18n;
+use crate::phone;
+use crate::printer::{PrintJobType, PrinterConfig, PrinterService, QueuedPrintJob};
+use crate::sms;
+use crate::ticket_archive;
+use crate::{DB_POOL, PRINTER_SERVICE};
+
+// Stops that run without a printer. With the no_printer_mode setting on, every job the print
+// queue is given is rendered as it would have printed, with the ticket number its payload
+// carries, and archived in printed_tickets as ESCROWED instead (migration 040): the booking that
+// asked for it does not wait on a printer. Escrowed tickets are printed later in bulk, through
+// the print queue as usual (db_print_escrowed_tickets), or texted to the customer
+// (db_send_escrowed_ticket_sms); either way they leave the escrow. Each terminal reads the
+// setting every minute, since the print queue can't wait on the database.
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+/// Tickets sent to the printer per db_print_escrowed_tickets, so the backlog can take them
+pub const RELEASE_LIMIT: i64 = 20;
+/// Escrowed tickets listed at most
+pub const LIST_LIMIT: i64 = 500;
+const ESCROW_PRINTER_ID: &str = "escrow";
+const ESCROW_PRINTER_NAME: &str = "Sans imprimante";
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NoPrinterMode {
+    pub enabled: bool,
+}
+
+impl NoPrinterMode {
+    /// The stored setting, or the default when missing or unreadable
+    pub fn parse(value: Option<&str>) -> Self {
+        value.and_then(|v| serde_json::from_str(v).ok()).unwrap_or_default()
+    }
+}
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Use the mode on this terminal right away; the others pick it up within a minute
+pub fn apply(mode: &NoPrinterMode) {
+    if ACTIVE.swap(mode.enabled, Ordering::SeqCst) != mode.enabled {
+        println!("🧾 [ESCROW] No-printer mode {}", if mode.enabled { "on" } else { "off" });
+    }
+}
+
+/// Keep the mode up to date
+pub async fn run() {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let mode = match db::pool::background_read().await {
+            Ok(client) => db::ticket_escrow::mode(&client).await,
+            Err(e) => Err(e),
+        };
+        match mode {
+            Ok(mode) => apply(&mode),
+            Err(e) => println!("⚠️ [ESCROW] No-printer mode not read: {}", e),
+        }
+    }
+}
+
+/// Archive the jobs as escrowed tickets, in the background
+pub fn escrow(jobs: Vec<QueuedPrintJob>, printer: PrinterConfig) {
+    tokio::spawn(async move {
+        let client = match DB_POOL.get().await {
+            Ok(client) => client,
+            Err(e) => {
+                println!("⚠️ [ESCROW] {} ticket(s) not escrowed: {}", jobs.len(), e);
+                return;
+            }
+        };
+        for job in &jobs {
+            let text = PrinterService::render_job_text(job, &printer);
+            let number = ticket_archive::ticket_number(&job.content);
+            let staff_name = ticket_archive::staff_name(job);
+            let ticket = NewPrintedTicket {
+                job_id: &job.id,
+                ticket_type: &format!("{:?}", job.job_type),
+                ticket_number: number.as_deref(),
+                staff_name: staff_name.as_deref(),
+                printer_id: ESCROW_PRINTER_ID,
+                printer_name: ESCROW_PRINTER_NAME,
+                duplicate_number: job.duplicate.map(|n| n as i32),
+                payload: &job.content,
+                rendered_text: &text,
+                width: printer.width as i16,
+                status: db::printed_tickets::ESCROWED,
+            };
+            match db::printed_tickets::insert(&client, &ticket).await {
+                Ok(_) => println!("🧾 [ESCROW] {:?} {} kept for later", job.job_type, number.as_deref().unwrap_or(&job.id)),
+                Err(e) => println!("⚠️ [ESCROW] Job {} not escrowed: {}", job.id, e),
+            }
+        }
+    });
+}
+
+/// The print job that brings an escrowed ticket out, None for a type the queue doesn't know
+pub fn release_job(ticket: &PrintedTicketDto) -> Option<QueuedPrintJob> {
+    let job_type = PrintJobType::from_kind(&ticket.ticket_type)?;
+    Some(QueuedPrintJob {
+        duplicate: ticket.duplicate_number.map(|n| n.max(0) as u32),
+        escrow_id: Some(ticket.id.clone()),
+        ..QueuedPrintJob::new(job_type, ticket.payload.clone(), ticket.staff_name.clone())
+    })
+}
+
+/// Send the oldest escrowed tickets, or those of `ids`, to the printer; how many were queued.
+/// They stay escrowed until each one prints, so a failed print can be released again.
+pub async fn release(client: &impl db::GenericClient, ids: Option<&[String]>) -> Result<usize, String> {
+    let tickets = db::printed_tickets::escrowed(client, ids, RELEASE_LIMIT).await?;
+    let jobs: Vec<QueuedPrintJob> = tickets.iter().filter_map(release_job).collect();
+    if jobs.is_empty() {
+        return Ok(0);
+    }
+    let count = jobs.len();
+    PRINTER_SERVICE.queue_print_batch(jobs).await?;
+    Ok(count)
+}
+
+/// A rendered ticket as an SMS: its lines without the padding and the rules between sections
+pub fn sms_text(rendered: &str) -> String {
+    rendered
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| line.chars().any(char::is_alphanumeric))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Text an escrowed ticket to the customer and take it out of the escrow
+pub async fn send_sms(client: &impl db::GenericClient, id: &str, phone_number: &str) -> Result<(), String> {
+    let phone_number = phone::normalize_mobile(phone_number)?;
+    let ticket = db::printed_tickets::get(client, id).await?.ok_or_else(|| i18n::t("error.printed_ticket_not_found"))?;
+    if ticket.status != db::printed_tickets::ESCROWED {
+        return Err(i18n::t("error.ticket_not_escrowed"));
+    }
+    sms::send(&phone_number, &sms_text(&ticket.rendered_text)).await?;
+    db::printed_tickets::mark_sent(client, id, &phone_number).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escrowed_tickets_print_or_text_as_issued() {
+        let ticket = PrintedTicketDto {
+            id: "t1".into(),
+            job_id: "j1".into(),
+            ticket_type: "DayPassTicket".into(),
+            ticket_number: Some("DAYPASS-1".into()),
+            staff_name: Some("Sami".into()),
+            printer_id: ESCROW_PRINTER_ID.into(),
+            printer_name: ESCROW_PRINTER_NAME.into(),
+            duplicate_number: None,
+            payload: r#"{"ticketNumber":"DAYPASS-1"}"#.into(),
+            rendered_text: "      WASLA      \n--------------------\nN° Ticket   DAYPASS-1\n\n".into(),
+            width: 32,
+            status: db::printed_tickets::ESCROWED.into(),
+            delivered_at: None,
+            delivered_to: None,
+            printed_at: "2026-10-15T08:00:00+01:00".into(),
+        };
+        let job = release_job(&ticket).unwrap();
+        assert!(matches!(job.job_type, PrintJobType::DayPassTicket));
+        assert_eq!(job.escrow_id.as_deref(), Some("t1"));
+        assert_eq!(job.content, ticket.payload);
+        assert!(release_job(&PrintedTicketDto { ticket_type: "Menu".into(), ..ticket.clone() }).is_none());
+
+        assert_eq!(sms_text(&ticket.rendered_text), "WASLA\nN° Ticket DAYPASS-1");
+        assert!(!NoPrinterMode::parse(Some("oops")).enabled);
+    }
+}
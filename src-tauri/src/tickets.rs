@@ -0,0 +1,550 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use tokio_postgres::Row;
+
+use crate::cash_count::DenominationCount;
+use crate::db::booking::BookingDetails;
+use crate::db::cash_counts::CashCountDto;
+use crate::db::expenses::ExpenseDto;
+use crate::db::queue::QueueItemDto;
+use crate::money::Money;
+use crate::pricing::FareModifier;
+use crate::settlement::Settlement;
+
+// Typed payloads for the tickets the station prints. Commands build them from DB rows,
+// the printer renders them; in between they travel as camelCase JSON through the print
+// queue, the reprint cache and the preview. Aliases accept the field names older
+// frontend formatters still send.
+
+// amount arrives as a number or as a string depending on the caller
+fn number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        Number(f64),
+        Text(String),
+    }
+    Ok(match Option::<Amount>::deserialize(deserializer)? {
+        Some(Amount::Number(n)) => Money::from_tnd(n),
+        Some(Amount::Text(s)) => Money::from_tnd(s.trim().parse().unwrap_or(0.0)),
+        None => Money::ZERO,
+    })
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PreviousVehicle {
+    pub license_plate: String,
+    pub exit_time: String,
+}
+
+impl PreviousVehicle {
+    /// From an exit_passes row selected with `license_plate` and `current_exit_time`
+    pub fn from_exit_pass_row(row: &Row) -> Self {
+        PreviousVehicle {
+            license_plate: row.get("license_plate"),
+            exit_time: row.get::<_, crate::time::TunisTime>("current_exit_time").fmt_ticket(),
+        }
+    }
+}
+
+// The frontend sends the previous plate as a bare string next to `previousExitTime`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum PreviousVehicleField {
+    Vehicle(PreviousVehicle),
+    Plate(String),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BookingTicket {
+    /// Pre-formatted body from the booking screen; when empty the fields below are printed
+    pub text: String,
+    pub verification_code: String,
+    pub queue_id: String,
+    pub license_plate: String,
+    pub destination_name: String,
+    pub queue_position: i32,
+    pub seats_booked: i32,
+    #[serde(deserialize_with = "number_or_string")]
+    pub total_amount: Money,
+    /// Child fares and luggage supplements included in the total
+    pub fare_modifiers: Vec<FareModifier>,
+    /// CASH, CARD, E_WALLET, ON_ACCOUNT or SPLIT; empty on tickets from before payment methods
+    pub payment_method: String,
+    pub staff_name: Option<String>,
+    pub staff_id: Option<String>,
+    /// Plate the booking was on before a seat transfer; the ticket then replaces the one printed
+    pub previous_license_plate: Option<String>,
+}
+
+impl BookingTicket {
+    /// JSON payload, or the plain text body the booking screen formats itself
+    pub fn from_payload(payload: &str) -> Self {
+        serde_json::from_str(payload).unwrap_or_else(|_| BookingTicket {
+            text: payload.to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DayPassStatus {
+    /// Already holds a pass covering today: the entry is free
+    Valid,
+    /// Pass bought together with this entry
+    Purchased,
+    #[default]
+    #[serde(other)]
+    None,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EntryTicket {
+    pub ticket_number: String,
+    pub license_plate: String,
+    pub destination_name: String,
+    pub queue_position: i64,
+    pub entry_time: String,
+    pub day_pass_status: DayPassStatus,
+    pub day_pass_purchase_date: Option<String>,
+    pub staff_name: Option<String>,
+    pub staff_id: Option<String>,
+}
+
+impl EntryTicket {
+    /// Entry into the queue at `queue_position`, stamped now (Tunis time)
+    pub fn new(license_plate: &str, destination_name: &str, queue_position: i32) -> Self {
+        EntryTicket {
+            ticket_number: format!("ENTRY-{}", chrono::Utc::now().timestamp_millis()),
+            license_plate: license_plate.to_string(),
+            destination_name: destination_name.to_string(),
+            queue_position: queue_position as i64,
+            entry_time: crate::time::tunis_now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_day_pass(mut self, status: DayPassStatus, purchase_date: Option<String>) -> Self {
+        self.day_pass_status = status;
+        self.day_pass_purchase_date = purchase_date;
+        self
+    }
+
+    pub fn with_staff(mut self, staff_name: Option<String>, staff_id: Option<String>) -> Self {
+        self.staff_name = staff_name;
+        self.staff_id = staff_id;
+        self
+    }
+
+    pub fn from_payload(payload: &str) -> Result<Self, String> {
+        serde_json::from_str(payload).map_err(|e| format!("Invalid entry ticket: {}", e))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DayPassTicket {
+    #[serde(alias = "dayPassNumber")]
+    pub ticket_number: String,
+    pub license_plate: String,
+    pub destination_name: String,
+    #[serde(deserialize_with = "number_or_string")]
+    pub amount: Money,
+    /// DAY, WEEK or MONTH
+    pub pass_type: String,
+    pub purchase_date: String,
+    pub valid_for: String,
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+    pub payment_method: String,
+    pub staff_name: Option<String>,
+    pub staff_id: Option<String>,
+}
+
+impl DayPassTicket {
+    /// A pass of `pass_type` valid from `valid_from` to `valid_until` (Tunis dates), bought now
+    pub fn new(license_plate: &str, destination_name: &str, pass_type: &str, amount: Money, valid_from: chrono::NaiveDateTime, valid_until: chrono::NaiveDateTime) -> Self {
+        let now = crate::time::tunis_now();
+        DayPassTicket {
+            ticket_number: format!("DAYPASS-{}", chrono::Utc::now().timestamp_millis()),
+            license_plate: license_plate.to_string(),
+            destination_name: destination_name.to_string(),
+            amount,
+            pass_type: pass_type.to_string(),
+            purchase_date: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            valid_for: now.format("%Y-%m-%d").to_string(),
+            valid_from: Some(valid_from.format("%d/%m/%Y").to_string()),
+            valid_until: Some(valid_until.format("%d/%m/%Y").to_string()),
+            payment_method: String::new(),
+            staff_name: None,
+            staff_id: None,
+        }
+    }
+
+    pub fn with_payment_method(mut self, payment_method: &str) -> Self {
+        self.payment_method = payment_method.to_string();
+        self
+    }
+
+    pub fn with_staff(mut self, staff_name: Option<String>, staff_id: Option<String>) -> Self {
+        self.staff_name = staff_name;
+        self.staff_id = staff_id;
+        self
+    }
+
+    pub fn from_payload(payload: &str) -> Result<Self, String> {
+        serde_json::from_str(payload).map_err(|e| format!("Invalid day pass ticket: {}", e))
+    }
+}
+
+fn default_vehicle_capacity() -> i64 {
+    8
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitPassTicket {
+    #[serde(default, alias = "exitPassNumber")]
+    pub ticket_number: String,
+    #[serde(default)]
+    pub license_plate: String,
+    #[serde(default, alias = "destinationName")]
+    pub station_name: String,
+    #[serde(default)]
+    pub exit_time: String,
+    #[serde(default = "default_vehicle_capacity")]
+    pub vehicle_capacity: i64,
+    #[serde(default, alias = "basePricePerSeat", deserialize_with = "number_or_string")]
+    pub base_price: Money,
+    #[serde(default, alias = "totalBasePrice", deserialize_with = "number_or_string")]
+    pub total_price: Money,
+    /// Seats actually sold on the queue entry; when set, `total_price` is what is due to the driver
+    #[serde(default)]
+    pub booked_seats: Option<i64>,
+    #[serde(default)]
+    pub amount_collected: Option<Money>,
+    #[serde(default)]
+    previous_vehicle: Option<PreviousVehicleField>,
+    #[serde(default, skip_serializing)]
+    previous_exit_time: Option<String>,
+    #[serde(default)]
+    pub staff_name: Option<String>,
+    #[serde(default)]
+    pub staff_id: Option<String>,
+    /// Running count of the vehicle's departures today, this one included
+    #[serde(default)]
+    pub trip_number: Option<i64>,
+}
+
+impl Default for ExitPassTicket {
+    fn default() -> Self {
+        ExitPassTicket {
+            ticket_number: String::new(),
+            license_plate: String::new(),
+            station_name: String::new(),
+            exit_time: String::new(),
+            vehicle_capacity: default_vehicle_capacity(),
+            base_price: Money::ZERO,
+            total_price: Money::ZERO,
+            booked_seats: None,
+            amount_collected: None,
+            previous_vehicle: None,
+            previous_exit_time: None,
+            staff_name: None,
+            staff_id: None,
+            trip_number: None,
+        }
+    }
+}
+
+impl ExitPassTicket {
+    /// Departure of a vehicle carrying `vehicle_capacity` seats at `base_price` each, stamped now
+    pub fn new(license_plate: &str, station_name: &str, vehicle_capacity: i64, base_price: Money, total_price: Money) -> Self {
+        ExitPassTicket {
+            ticket_number: format!("EXIT-{}", chrono::Utc::now().timestamp_millis()),
+            license_plate: license_plate.to_string(),
+            station_name: station_name.to_string(),
+            exit_time: crate::clock::now().to_rfc3339(),
+            vehicle_capacity,
+            base_price,
+            total_price,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_previous(mut self, previous: Option<PreviousVehicle>) -> Self {
+        self.previous_vehicle = previous.map(PreviousVehicleField::Vehicle);
+        self.previous_exit_time = None;
+        self
+    }
+
+    /// Seats sold and the amount collected for them, printed above what is due to the driver
+    pub fn with_sales(mut self, booked_seats: i64, amount_collected: Money) -> Self {
+        self.booked_seats = Some(booked_seats);
+        self.amount_collected = Some(amount_collected);
+        self
+    }
+
+    pub fn with_staff(mut self, staff_name: Option<String>, staff_id: Option<String>) -> Self {
+        self.staff_name = staff_name;
+        self.staff_id = staff_id;
+        self
+    }
+
+    pub fn with_trip_number(mut self, trip_number: i64) -> Self {
+        self.trip_number = Some(trip_number);
+        self
+    }
+
+    /// Vehicle that left this destination before this one today, when known
+    pub fn previous(&self) -> Option<PreviousVehicle> {
+        match &self.previous_vehicle {
+            Some(PreviousVehicleField::Vehicle(vehicle)) => Some(vehicle.clone()),
+            Some(PreviousVehicleField::Plate(plate)) => self.previous_exit_time.as_ref().map(|exit_time| PreviousVehicle {
+                license_plate: plate.clone(),
+                exit_time: exit_time.clone(),
+            }),
+            None => None,
+        }
+    }
+
+    pub fn from_payload(payload: &str) -> Result<Self, String> {
+        let mut ticket: ExitPassTicket = serde_json::from_str(payload).map_err(|e| format!("Invalid exit pass ticket: {}", e))?;
+        // Normalise the string form so the queued payload carries one shape
+        let previous = ticket.previous();
+        ticket = ticket.with_previous(previous);
+        Ok(ticket)
+    }
+}
+
+/// What the driver is paid for a departure, handed over with the exit pass
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SettlementSlip {
+    pub ticket_number: String,
+    pub license_plate: String,
+    pub destination_name: String,
+    pub exit_time: String,
+    #[serde(flatten)]
+    pub settlement: Settlement,
+    pub staff_name: Option<String>,
+}
+
+impl SettlementSlip {
+    pub fn new(exit_pass: &ExitPassTicket, settlement: Settlement) -> Self {
+        SettlementSlip {
+            ticket_number: exit_pass.ticket_number.replacen("EXIT", "SETTLE", 1),
+            license_plate: exit_pass.license_plate.clone(),
+            destination_name: exit_pass.station_name.clone(),
+            exit_time: exit_pass.exit_time.clone(),
+            settlement,
+            staff_name: exit_pass.staff_name.clone(),
+        }
+    }
+
+    pub fn from_payload(payload: &str) -> Result<Self, String> {
+        serde_json::from_str(payload).map_err(|e| format!("Invalid settlement slip: {}", e))
+    }
+}
+
+/// Petty cash taken from the till, signed by whoever received it
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExpenseVoucher {
+    pub ticket_number: String,
+    pub amount: Money,
+    pub category: String,
+    pub note: Option<String>,
+    pub created_at: String,
+    pub staff_name: Option<String>,
+}
+
+impl ExpenseVoucher {
+    pub fn new(expense: &ExpenseDto) -> Self {
+        ExpenseVoucher {
+            ticket_number: format!("DEP-{}", expense.id.split('-').next().unwrap_or_default().to_uppercase()),
+            amount: expense.amount,
+            category: expense.category.clone(),
+            note: expense.note.clone(),
+            created_at: expense.created_at.clone(),
+            staff_name: Some(expense.staff_name.clone()),
+        }
+    }
+
+    pub fn from_payload(payload: &str) -> Result<Self, String> {
+        serde_json::from_str(payload).map_err(|e| format!("Invalid expense voucher: {}", e))
+    }
+}
+
+/// Drawer count that missed the expected cash by more than the threshold, signed by the
+/// cashier and the supervisor
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CashVarianceSlip {
+    pub ticket_number: String,
+    pub cashier_name: String,
+    pub date: String,
+    pub denominations: Vec<DenominationCount>,
+    pub counted: Money,
+    pub expected: Money,
+    pub variance: Money,
+    pub threshold: Money,
+    pub created_at: String,
+}
+
+impl CashVarianceSlip {
+    pub fn new(count: &CashCountDto) -> Self {
+        CashVarianceSlip {
+            ticket_number: format!("ECART-{}", count.id.split('-').next().unwrap_or_default().to_uppercase()),
+            cashier_name: count.staff_name.clone(),
+            date: count.date.clone(),
+            denominations: count.denominations.clone(),
+            counted: count.counted,
+            expected: count.expected,
+            variance: count.variance,
+            threshold: count.threshold,
+            created_at: count.created_at.clone(),
+        }
+    }
+
+    pub fn from_payload(payload: &str) -> Result<Self, String> {
+        serde_json::from_str(payload).map_err(|e| format!("Invalid cash variance slip: {}", e))
+    }
+}
+
+/// Seats given back by a customer: what they cost, the fee kept under the cancellation policy
+/// and what is handed back
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CancellationSlip {
+    pub ticket_number: String,
+    pub verification_code: String,
+    pub destination_name: String,
+    pub license_plate: String,
+    pub seats: i32,
+    pub refunded: Money,
+    pub fee: Money,
+    pub cancelled_at: String,
+}
+
+impl CancellationSlip {
+    pub fn new(details: &BookingDetails, seats: i32, refunded: Money, fee: Money) -> Self {
+        CancellationSlip {
+            ticket_number: format!("ANN-{}", details.verification_code),
+            verification_code: details.verification_code.clone(),
+            destination_name: details.destination_name.clone(),
+            license_plate: details.license_plate.clone(),
+            seats,
+            refunded,
+            fee,
+            cancelled_at: crate::time::ticket_ts_now(),
+        }
+    }
+
+    /// Handed back to the customer
+    pub fn net_refund(&self) -> Money {
+        self.refunded - self.fee
+    }
+
+    pub fn from_payload(payload: &str) -> Result<Self, String> {
+        serde_json::from_str(payload).map_err(|e| format!("Invalid cancellation slip: {}", e))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QueueBoardLine {
+    pub position: i32,
+    pub license_plate: String,
+    pub available_seats: i32,
+    pub total_seats: i32,
+    pub status: String,
+}
+
+/// Ordered queue of one destination, pinned at the lane entrance
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QueueBoard {
+    pub destination_name: String,
+    pub vehicles: Vec<QueueBoardLine>,
+}
+
+impl QueueBoard {
+    /// From the rows of db::queue::by_destination, already in queue order
+    pub fn new(destination_name: &str, queue: &[QueueItemDto]) -> Self {
+        QueueBoard {
+            destination_name: destination_name.to_string(),
+            vehicles: queue
+                .iter()
+                .map(|item| QueueBoardLine {
+                    position: item.queue_position,
+                    license_plate: item.license_plate.clone(),
+                    available_seats: item.available_seats,
+                    total_seats: item.total_seats,
+                    status: item.status.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn from_payload(payload: &str) -> Result<Self, String> {
+        serde_json::from_str(payload).map_err(|e| format!("Invalid queue board: {}", e))
+    }
+}
+
+pub trait TicketPayload: Serialize {
+    fn to_payload(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+impl TicketPayload for BookingTicket {}
+impl TicketPayload for EntryTicket {}
+impl TicketPayload for DayPassTicket {}
+impl TicketPayload for ExitPassTicket {}
+impl TicketPayload for SettlementSlip {}
+impl TicketPayload for ExpenseVoucher {}
+impl TicketPayload for CashVarianceSlip {}
+impl TicketPayload for CancellationSlip {}
+impl TicketPayload for QueueBoard {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_pass_accepts_frontend_field_names() {
+        let payload = r#"{"exitPassNumber":"EXIT1234","licensePlate":"123 TU 4567","destinationName":"Jemmal","exitTime":"11:42","previousVehicle":"55 TU 900","previousExitTime":"11:10","vehicleCapacity":8,"basePricePerSeat":2.5,"totalSeats":8,"totalBasePrice":20,"staffName":"Sami"}"#;
+        let ticket = ExitPassTicket::from_payload(payload).unwrap();
+        assert_eq!(ticket.ticket_number, "EXIT1234");
+        assert_eq!(ticket.station_name, "Jemmal");
+        assert_eq!(ticket.vehicle_capacity, 8);
+        assert_eq!(ticket.base_price, Money::from_millimes(2500));
+        assert_eq!(ticket.total_price, Money::from_millimes(20_000));
+        assert_eq!(ticket.previous(), Some(PreviousVehicle { license_plate: "55 TU 900".to_string(), exit_time: "11:10".to_string() }));
+
+        // Round trip through the queue keeps the previous vehicle
+        let queued = ExitPassTicket::from_payload(&ticket.to_payload()).unwrap();
+        assert_eq!(queued, ticket);
+    }
+
+    #[test]
+    fn day_pass_amount_as_string_and_unknown_status() {
+        let ticket = DayPassTicket::from_payload(r#"{"dayPassNumber":"DP1","licensePlate":"1 TU 1","amount":"12.00","passType":"WEEK"}"#).unwrap();
+        assert_eq!(ticket.ticket_number, "DP1");
+        assert_eq!(ticket.amount, Money::from_millimes(12_000));
+
+        let entry = EntryTicket::from_payload(r#"{"licensePlate":"1 TU 1","dayPassStatus":"EXPIRED"}"#).unwrap();
+        assert_eq!(entry.day_pass_status, DayPassStatus::None);
+    }
+
+    #[test]
+    fn booking_ticket_falls_back_to_plain_text() {
+        let ticket = BookingTicket::from_payload("Destination: Tunis\nPlaces: 2");
+        assert_eq!(ticket.text, "Destination: Tunis\nPlaces: 2");
+        assert!(ticket.verification_code.is_empty());
+    }
+}
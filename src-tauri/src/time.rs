@@ -0,0 +1,140 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::error::Error;
+use tokio_postgres::types::{FromSql, Type};
+
+// Every date and time the station shows, prints or filters on is Africa/Tunis. Columns are
+// TIMESTAMPTZ: queries compare them with UTC instants bound from here (never with
+// `AT TIME ZONE` on both sides) and rows are read through `TunisTime`, so the conversion
+// happens in one place whatever the database session time zone is.
+
+pub const TZ: Tz = chrono_tz::Africa::Tunis;
+
+/// Now in Tunis, on database time while the PC clock is drifting (see clock.rs)
+pub fn tunis_now() -> DateTime<Tz> {
+    crate::clock::now().with_timezone(&TZ)
+}
+
+pub fn tunis_today() -> NaiveDate {
+    tunis_now().date_naive()
+}
+
+/// A Tunis wall-clock time as an instant
+pub fn from_tunis_local(local: NaiveDateTime) -> DateTime<Tz> {
+    TZ.from_local_datetime(&local).earliest().unwrap_or_else(|| TZ.from_utc_datetime(&local))
+}
+
+/// An instant given as RFC 3339, or as Tunis wall-clock time ("2026-10-15 09:30[:00]", with a
+/// space or a T)
+pub fn parse_instant(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|local| from_tunis_local(local).with_timezone(&Utc))
+}
+
+/// Start of `day` and start of the next day in Tunis, as UTC bounds for `col >= $a AND col < $b`
+pub fn tunis_day_bounds(day: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = from_tunis_local(day.and_hms_opt(0, 0, 0).unwrap());
+    let end = from_tunis_local((day + chrono::Duration::days(1)).and_hms_opt(0, 0, 0).unwrap());
+    (start.with_timezone(&Utc), end.with_timezone(&Utc))
+}
+
+/// Bounds of today in Tunis
+pub fn today_bounds() -> (DateTime<Utc>, DateTime<Utc>) {
+    tunis_day_bounds(tunis_today())
+}
+
+/// Date and time as printed on tickets
+pub fn fmt_ticket_ts(at: &DateTime<Tz>) -> String {
+    at.format("%d/%m/%Y %H:%M:%S").to_string()
+}
+
+/// Ticket timestamp for right now
+pub fn ticket_ts_now() -> String {
+    fmt_ticket_ts(&tunis_now())
+}
+
+/// A timestamp column read in Tunis time. Accepts TIMESTAMPTZ as stored and plain TIMESTAMP,
+/// which is taken to be UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TunisTime(pub DateTime<Tz>);
+
+impl TunisTime {
+    pub fn local(&self) -> NaiveDateTime {
+        self.0.naive_local()
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.0.date_naive()
+    }
+
+    pub fn fmt_ticket(&self) -> String {
+        fmt_ticket_ts(&self.0)
+    }
+
+    /// Wall-clock time, e.g. 2025-03-10T14:05:00
+    pub fn fmt_iso(&self) -> String {
+        self.0.format("%Y-%m-%dT%H:%M:%S").to_string()
+    }
+
+    /// Wall-clock time with a trailing Z, the shape the frontend DTOs have always carried
+    pub fn fmt_dto(&self) -> String {
+        self.0.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+    }
+}
+
+impl<'a> FromSql<'a> for TunisTime {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let utc = if *ty == Type::TIMESTAMPTZ {
+            DateTime::<Utc>::from_sql(ty, raw)?
+        } else {
+            Utc.from_utc_datetime(&NaiveDateTime::from_sql(ty, raw)?)
+        };
+        Ok(TunisTime(utc.with_timezone(&TZ)))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::TIMESTAMPTZ || *ty == Type::TIMESTAMP
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_bounds_are_tunis_midnights() {
+        let (start, end) = tunis_day_bounds(NaiveDate::from_ymd_opt(2025, 3, 10).unwrap());
+        assert_eq!(start.to_rfc3339(), "2025-03-09T23:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2025-03-10T23:00:00+00:00");
+    }
+
+    #[test]
+    fn instants_are_rfc3339_or_tunis_wall_clock() {
+        let expected = Utc.with_ymd_and_hms(2026, 10, 15, 8, 30, 0).unwrap();
+        assert_eq!(parse_instant("2026-10-15T08:30:00Z"), Some(expected));
+        assert_eq!(parse_instant("2026-10-15 09:30"), Some(expected));
+        assert_eq!(parse_instant("2026-10-15T09:30:00"), Some(expected));
+        assert_eq!(parse_instant("15/10/2026 09:30"), None);
+    }
+
+    #[test]
+    fn timestamps_read_the_same_from_either_column_type() {
+        // Both types travel as microseconds since 2000-01-01
+        let instant = Utc.with_ymd_and_hms(2025, 3, 10, 23, 30, 0).unwrap();
+        let epoch = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let raw = (instant - epoch).num_microseconds().unwrap().to_be_bytes();
+
+        let from_tz = TunisTime::from_sql(&Type::TIMESTAMPTZ, &raw).unwrap();
+        let from_naive = TunisTime::from_sql(&Type::TIMESTAMP, &raw).unwrap();
+        assert_eq!(from_tz, from_naive);
+        // 23:30 UTC is already the next day in Tunis
+        assert_eq!(from_tz.date(), NaiveDate::from_ymd_opt(2025, 3, 11).unwrap());
+        assert_eq!(from_tz.fmt_ticket(), "11/03/2025 00:30:00");
+    }
+}
@@ -0,0 +1,108 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::Manager;
+
+use crate::{day_pass_cache, destination_summary, location_tree, staff_names};
+use crate::i18n;
+
+// Training mode: new cashiers practise on a copy of the station kept in the `training` schema
+// (db::training::seed copies every table, with the staff, routes, vehicles and settings in
+// them). While it is on, every connection this terminal gets from the primary has its
+// search_path on that schema (db::pool), reads skip the replicas, each ticket is headed
+// "FORMATION – NON VALABLE" (printer.rs) and the commands that send data off the terminal are
+// refused (`check`), as are SMS. Background jobs keep to the real data (db::pool::background)
+// and every cache is emptied on the switch. It is local to the terminal and off again after a
+// restart, so a terminal left in training comes back on the real data.
+
+pub const SCHEMA: &str = "training";
+pub const TRAINING_EVENT: &str = "training-mode-changed";
+
+// Commands that sync with the local node or export data
+const BLOCKED_COMMANDS: &[&str] = &[
+    "proxy_localnode",
+    "start_realtime_listening",
+    "start_websocket_realtime_listening",
+    "broadcast_custom_event",
+    "upload_crash_report",
+    "db_export_customer_statement",
+    "db_export_demand_profile",
+    "db_export_printed_ticket_pdf",
+];
+
+struct State {
+    active: bool,
+    // Connections opened before this were set up for the other mode
+    switched_at: Option<Instant>,
+}
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(|| Mutex::new(State { active: false, switched_at: None }));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrainingStatusDto {
+    pub active: bool,
+    pub seeded: bool,
+}
+
+pub fn is_active() -> bool {
+    STATE.lock().map(|state| state.active).unwrap_or(false)
+}
+
+/// Whether a pooled connection opened at `created` still has this mode's search_path
+pub fn is_current(created: Instant) -> bool {
+    STATE.lock().map(|state| state.switched_at.map_or(true, |at| created >= at)).unwrap_or(true)
+}
+
+/// What a new connection runs before it is handed out, if anything
+pub fn session_setup() -> Option<String> {
+    is_active().then(|| format!("SET search_path TO {}, public", SCHEMA))
+}
+
+fn check_with(active: bool, command: &str) -> Result<(), String> {
+    if active && BLOCKED_COMMANDS.contains(&command) {
+        return Err(i18n::t("error.training_blocked"));
+    }
+    Ok(())
+}
+
+/// Refuse the sync and export commands while training
+pub fn check(command: &str) -> Result<(), String> {
+    check_with(is_active(), command)
+}
+
+/// Turn training mode on or off for this terminal
+pub fn switch(app_handle: &tauri::AppHandle, active: bool) {
+    {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        if state.active == active {
+            return;
+        }
+        state.active = active;
+        state.switched_at = Some(Instant::now());
+    }
+    // Everything cached was read from the other schema
+    destination_summary::clear();
+    day_pass_cache::clear();
+    staff_names::refresh();
+    location_tree::invalidate();
+    let _ = app_handle.emit_all(TRAINING_EVENT, active);
+    println!("🎓 [TRAINING] Training mode {}", if active { "on" } else { "off" });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_and_exports_wait_for_the_end_of_training() {
+        assert!(check_with(true, "proxy_localnode").is_err());
+        assert!(check_with(true, "db_export_demand_profile").is_err());
+        assert!(check_with(true, "db_create_queue_booking").is_ok());
+        assert!(check_with(false, "proxy_localnode").is_ok());
+
+        assert!(is_current(Instant::now()));
+        assert!(session_setup().is_none());
+    }
+}
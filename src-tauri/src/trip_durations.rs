@@ -0,0 +1,114 @@
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::db;
+use crate::db::trips::RoundTrip;
+use crate::i18n;
+
+// How long a vehicle takes to come back: from its exit pass to its next queue entry, per
+// destination, over the last few days. Longer than MAX_ROUND_TRIP_HOURS is not a round trip (the
+// vehicle stopped for the night, or went elsewhere) and is left out. The median feeds the
+// estimated return times of the GPS positions (gps.rs).
+
+pub const DEFAULT_DAYS: u32 = 30;
+const MAX_DAYS: u32 = 365;
+const MAX_ROUND_TRIP_HOURS: i32 = 12;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TripDurationStatsDto {
+    pub destination_id: String,
+    pub destination_name: String,
+    pub days: u32,
+    pub trips: usize,
+    /// Minutes, None when no round trip was seen
+    pub median_minutes: Option<f64>,
+    pub average_minutes: Option<f64>,
+    /// Nine trips in ten were back within this
+    pub p90_minutes: Option<f64>,
+    pub shortest_minutes: Option<f64>,
+    pub longest_minutes: Option<f64>,
+}
+
+pub fn check_days(days: Option<u32>) -> Result<u32, String> {
+    match days.unwrap_or(DEFAULT_DAYS) {
+        d @ 1..=MAX_DAYS => Ok(d),
+        _ => Err(i18n::tf("error.trip_stats_days_invalid", &[("max", &MAX_DAYS)])),
+    }
+}
+
+/// Linear interpolation between the closest ranks, as percentile_cont does; `sorted` not empty
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = fraction * (sorted.len() - 1) as f64;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+}
+
+fn round1(minutes: f64) -> f64 {
+    (minutes * 10.0).round() / 10.0
+}
+
+pub fn stats(destination_id: &str, destination_name: &str, days: u32, mut minutes: Vec<f64>) -> TripDurationStatsDto {
+    minutes.sort_by(|a, b| a.total_cmp(b));
+    let measured = !minutes.is_empty();
+    TripDurationStatsDto {
+        destination_id: destination_id.to_string(),
+        destination_name: destination_name.to_string(),
+        days,
+        trips: minutes.len(),
+        median_minutes: measured.then(|| round1(percentile(&minutes, 0.5))),
+        average_minutes: measured.then(|| round1(minutes.iter().sum::<f64>() / minutes.len() as f64)),
+        p90_minutes: measured.then(|| round1(percentile(&minutes, 0.9))),
+        shortest_minutes: minutes.first().copied().map(round1),
+        longest_minutes: minutes.last().copied().map(round1),
+    }
+}
+
+fn since(days: u32) -> chrono::DateTime<chrono::Utc> {
+    crate::clock::now() - Duration::days(days as i64)
+}
+
+pub async fn for_destination(client: &impl db::GenericClient, destination_id: &str, days: Option<u32>) -> Result<TripDurationStatsDto, String> {
+    let days = check_days(days)?;
+    let destination_name = db::queue::destination_route(client, destination_id)
+        .await?
+        .map(|r| r.station_name)
+        .ok_or_else(|| i18n::t("error.destination_not_found"))?;
+    let trips = db::trips::round_trips(client, Some(destination_id), since(days), MAX_ROUND_TRIP_HOURS).await?;
+    Ok(stats(destination_id, &destination_name, days, trips.into_iter().map(|t| t.minutes).collect()))
+}
+
+/// Median round trip in minutes by destination id, over the default days
+pub async fn typical_minutes(client: &impl db::GenericClient) -> Result<HashMap<String, f64>, String> {
+    let trips = db::trips::round_trips(client, None, since(DEFAULT_DAYS), MAX_ROUND_TRIP_HOURS).await?;
+    let mut by_destination: HashMap<String, Vec<f64>> = HashMap::new();
+    for RoundTrip { destination_id, minutes } in trips {
+        by_destination.entry(destination_id).or_default().push(minutes);
+    }
+    Ok(by_destination
+        .into_iter()
+        .filter_map(|(id, minutes)| stats(&id, "", DEFAULT_DAYS, minutes).median_minutes.map(|m| (id, m)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_stats_interpolate_like_postgres() {
+        let summary = stats("sousse", "Sousse", 30, vec![100.0, 60.0, 80.0, 90.0]);
+        assert_eq!(summary.trips, 4);
+        assert_eq!(summary.median_minutes, Some(85.0));
+        assert_eq!(summary.average_minutes, Some(82.5));
+        assert_eq!(summary.p90_minutes, Some(97.0));
+        assert_eq!((summary.shortest_minutes, summary.longest_minutes), (Some(60.0), Some(100.0)));
+
+        let none = stats("sousse", "Sousse", 30, vec![]);
+        assert_eq!((none.trips, none.median_minutes, none.average_minutes, none.longest_minutes), (0, None, None, None));
+
+        assert_eq!(check_days(None), Ok(DEFAULT_DAYS));
+        assert!(check_days(Some(0)).is_err());
+    }
+}
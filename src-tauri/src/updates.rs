@@ -0,0 +1,247 @@
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+// Update channel, deferral and maintenance window for the built-in updater. The startup dialog
+// is off (tauri.conf.json): checks go through here and an update found is only installed inside
+// the maintenance window, never while the station is selling tickets.
+
+const SETTINGS_FILE: &str = "update_settings.json";
+const UPDATE_ENDPOINT: &str = "https://tunimove.vercel.app/api/updater/wasla";
+
+// How often the background task looks for an update it may install
+const CHECK_INTERVAL_SECS: u64 = 30 * 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "stable" => Some(UpdateChannel::Stable),
+            "beta" => Some(UpdateChannel::Beta),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Beta => "beta",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UpdateSettings {
+    pub channel: UpdateChannel,
+    pub deferred_until: Option<NaiveDate>, // no install before this day (Tunis)
+    pub window_start: NaiveTime,           // maintenance window, may run past midnight
+    pub window_end: NaiveTime,
+    // 0..100, drawn once per install; sent with every check so the update server can roll a
+    // release out to a share of the stations first
+    pub rollout_bucket: u8,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        UpdateSettings {
+            channel: UpdateChannel::Stable,
+            deferred_until: None,
+            window_start: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+            window_end: NaiveTime::from_hms_opt(5, 0, 0).unwrap(),
+            rollout_bucket: (uuid::Uuid::new_v4().as_u128() % 100) as u8,
+        }
+    }
+}
+
+impl UpdateSettings {
+    pub fn in_maintenance_window(&self, time: NaiveTime) -> bool {
+        if self.window_start <= self.window_end {
+            time >= self.window_start && time < self.window_end
+        } else {
+            time >= self.window_start || time < self.window_end
+        }
+    }
+
+    /// Whether an update may be installed at this Tunis date and time
+    pub fn install_allowed(&self, date: NaiveDate, time: NaiveTime) -> bool {
+        let deferred = self.deferred_until.is_some_and(|until| date < until);
+        !deferred && self.in_maintenance_window(time)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateStatusDto {
+    pub current_version: String,
+    pub channel: UpdateChannel,
+    pub available: bool,
+    pub version: Option<String>,
+    pub notes: Option<String>,
+    pub install_allowed_now: bool,
+    pub settings: UpdateSettings,
+}
+
+fn settings_path(app_handle: &tauri::AppHandle) -> PathBuf {
+    app_handle
+        .path_resolver()
+        .app_config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(SETTINGS_FILE)
+}
+
+/// Saved settings; the first call saves the defaults so the rollout bucket stays put
+pub fn load(app_handle: &tauri::AppHandle) -> UpdateSettings {
+    let path = settings_path(app_handle);
+    if let Some(settings) = fs::read_to_string(&path).ok().and_then(|content| serde_json::from_str(&content).ok()) {
+        return settings;
+    }
+    let settings = UpdateSettings::default();
+    let _ = save(app_handle, &settings);
+    settings
+}
+
+fn save(app_handle: &tauri::AppHandle, settings: &UpdateSettings) -> Result<(), String> {
+    let path = settings_path(app_handle);
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Impossible d'enregistrer {:?}: {}", path, e))
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").map_err(|_| format!("Heure invalide: {} (HH:MM)", value))
+}
+
+async fn check(app_handle: &tauri::AppHandle, settings: &UpdateSettings) -> Result<tauri::updater::UpdateResponse<tauri::Wry>, String> {
+    tauri::updater::builder(app_handle.clone())
+        .endpoints(&[format!("{}?channel={}", UPDATE_ENDPOINT, settings.channel.as_str())])
+        .header("X-Update-Channel", settings.channel.as_str())
+        .map_err(|e| e.to_string())?
+        .header("X-Rollout-Bucket", settings.rollout_bucket.to_string())
+        .map_err(|e| e.to_string())?
+        .check()
+        .await
+        .map_err(|e| format!("Vérification des mises à jour impossible: {}", e))
+}
+
+/// Every CHECK_INTERVAL_SECS, install an available update if the maintenance window allows it
+pub async fn run_update_scheduler(app_handle: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let settings = load(&app_handle);
+        let now = crate::time::tunis_now().naive_local();
+        if !settings.install_allowed(now.date(), now.time()) {
+            continue;
+        }
+        match check(&app_handle, &settings).await {
+            Ok(update) if update.is_update_available() => {
+                println!("⬇️ [UPDATER] Installing {} ({} channel)", update.latest_version(), settings.channel.as_str());
+                if let Err(e) = update.download_and_install().await {
+                    println!("❌ [UPDATER] Install failed: {}", e);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => println!("⚠️ [UPDATER] {}", e),
+        }
+    }
+}
+
+/// Look for an update on the current channel; installing is left to the maintenance window
+#[tauri::command]
+pub async fn check_for_updates(app_handle: tauri::AppHandle) -> Result<UpdateStatusDto, String> {
+    let settings = load(&app_handle);
+    let update = check(&app_handle, &settings).await?;
+    let now = crate::time::tunis_now().naive_local();
+    let available = update.is_update_available();
+    Ok(UpdateStatusDto {
+        current_version: app_handle.package_info().version.to_string(),
+        channel: settings.channel,
+        available,
+        version: available.then(|| update.latest_version().to_string()),
+        notes: if available { update.body().cloned() } else { None },
+        install_allowed_now: settings.install_allowed(now.date(), now.time()),
+        settings,
+    })
+}
+
+#[tauri::command]
+pub async fn get_update_channel(app_handle: tauri::AppHandle) -> Result<UpdateSettings, String> {
+    Ok(load(&app_handle))
+}
+
+#[tauri::command]
+pub async fn set_update_channel(app_handle: tauri::AppHandle, channel: String) -> Result<UpdateSettings, String> {
+    let mut settings = load(&app_handle);
+    settings.channel = UpdateChannel::from_name(&channel).ok_or_else(|| format!("Canal inconnu: {} (stable ou beta)", channel))?;
+    save(&app_handle, &settings)?;
+    println!("🔀 [UPDATER] Channel set to {}", settings.channel.as_str());
+    Ok(settings)
+}
+
+/// Hold updates until `date` (YYYY-MM-DD); None lifts the deferral
+#[tauri::command]
+pub async fn defer_update_until(app_handle: tauri::AppHandle, date: Option<String>) -> Result<UpdateSettings, String> {
+    let mut settings = load(&app_handle);
+    settings.deferred_until = match date.as_deref() {
+        Some(date) => Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("Date invalide: {}", date))?),
+        None => None,
+    };
+    save(&app_handle, &settings)?;
+    Ok(settings)
+}
+
+/// Hours (HH:MM, Tunis time) during which updates may install; the end may be past midnight
+#[tauri::command]
+pub async fn set_update_maintenance_window(app_handle: tauri::AppHandle, start: String, end: String) -> Result<UpdateSettings, String> {
+    let (window_start, window_end) = (parse_time(&start)?, parse_time(&end)?);
+    if window_start == window_end {
+        return Err("La fenêtre de maintenance doit durer au moins une minute".to_string());
+    }
+    let mut settings = load(&app_handle);
+    settings.window_start = window_start;
+    settings.window_end = window_end;
+    save(&app_handle, &settings)?;
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn installs_only_inside_the_window_and_after_the_deferral() {
+        let mut settings = UpdateSettings::default();
+        let day = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        assert!(settings.install_allowed(day, at(2, 30)));
+        assert!(!settings.install_allowed(day, at(9, 0)));
+        assert!(!settings.install_allowed(day, at(5, 0)));
+
+        settings.deferred_until = NaiveDate::from_ymd_opt(2025, 3, 12);
+        assert!(!settings.install_allowed(day, at(2, 30)));
+        assert!(settings.install_allowed(NaiveDate::from_ymd_opt(2025, 3, 12).unwrap(), at(2, 30)));
+    }
+
+    #[test]
+    fn window_may_run_past_midnight() {
+        let settings = UpdateSettings { window_start: at(22, 0), window_end: at(4, 0), ..UpdateSettings::default() };
+        assert!(settings.in_maintenance_window(at(23, 15)));
+        assert!(settings.in_maintenance_window(at(3, 59)));
+        assert!(!settings.in_maintenance_window(at(12, 0)));
+        assert!(settings.rollout_bucket < 100);
+    }
+}
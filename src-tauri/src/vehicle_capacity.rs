@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+
+// Changing the seat count of a vehicle (db_update_vehicle_capacity). A vehicle in the queue has
+// its entry resized in the same transaction: the seats already sold stay sold, the rest become
+// the new free seats, and the capacity cannot go below what was sold. Nor can it come down to
+// exactly what was sold: the entry would be full with no exit pass, and that departure is
+// db_end_trip_with_partial_capacity's job. Each change is recorded in the audit log with the
+// capacity before and after, in the same transaction.
+
+/// The most seats the vehicle form accepts
+pub const MAX_CAPACITY: i32 = 50;
+pub const CAPACITY_CHANGED_EVENT: &str = "VEHICLE_CAPACITY_CHANGED";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VehicleCapacityDto {
+    pub vehicle_id: String,
+    pub license_plate: String,
+    pub previous_capacity: i32,
+    pub capacity: i32,
+    /// The queue entry resized with the vehicle, when it is queued
+    pub queue_id: Option<String>,
+    pub available_seats: Option<i32>,
+}
+
+pub fn validate(capacity: i32) -> Result<(), String> {
+    if !(1..=MAX_CAPACITY).contains(&capacity) {
+        return Err(i18n::tf("error.vehicle_capacity_invalid", &[("max", &MAX_CAPACITY)]));
+    }
+    Ok(())
+}
+
+/// Free seats of a queue entry of `total_seats` once the vehicle carries `capacity`
+pub fn available_after(total_seats: i32, available_seats: i32, capacity: i32) -> Result<i32, String> {
+    let booked = (total_seats - available_seats).max(0);
+    if capacity < booked {
+        return Err(i18n::tf("error.vehicle_capacity_below_booked", &[("booked", &booked), ("capacity", &capacity)]));
+    }
+    if booked > 0 && capacity == booked {
+        return Err(i18n::tf("error.vehicle_capacity_fills_entry", &[("booked", &booked)]));
+    }
+    Ok(capacity - booked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sold_seats_survive_a_capacity_change() {
+        assert_eq!(available_after(8, 8, 10), Ok(10));
+        assert_eq!(available_after(8, 3, 10), Ok(5));
+        assert_eq!(available_after(8, 3, 6), Ok(1));
+        // Down to exactly what was sold would fill the entry without an exit pass
+        assert!(available_after(8, 3, 5).is_err());
+        assert!(available_after(8, 3, 4).is_err());
+
+        assert!(validate(0).is_err());
+        assert!(validate(MAX_CAPACITY + 1).is_err());
+        assert!(validate(8).is_ok());
+    }
+}
@@ -0,0 +1,240 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Manager, PhysicalPosition, PhysicalSize, Position, Size, Window, WindowEvent};
+
+// Where supervisors put each window across monitors, saved in window_layout.json in the app
+// config dir and applied again when the window is created. Positions are kept relative to
+// their monitor so a layout survives the monitors being renumbered or reordered.
+
+const LAYOUT_FILE: &str = "window_layout.json";
+
+// Moving a window fires a burst of events; save once it has stopped moving
+const SAVE_DELAY: Duration = Duration::from_millis(800);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowGeometry {
+    pub monitor: Option<String>,
+    pub x: i32, // from the monitor's top-left corner, physical pixels
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub fullscreen: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorRect {
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+static LAYOUT: Lazy<Mutex<Option<HashMap<String, WindowGeometry>>>> = Lazy::new(|| Mutex::new(None));
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Windows of one kind share a slot: every vehicle window opens where the last one was
+fn layout_key(label: &str) -> &str {
+    if label.starts_with("vehicle-") {
+        "vehicle"
+    } else {
+        label
+    }
+}
+
+/// Desktop position for `saved`, if the monitor it was on is still connected
+pub fn placement(saved: &WindowGeometry, monitors: &[MonitorRect]) -> Option<(i32, i32)> {
+    let monitor = match &saved.monitor {
+        Some(name) => monitors.iter().find(|m| m.name.as_deref() == Some(name.as_str()))?,
+        None => monitors.first()?,
+    };
+    // Keep at least the title bar on that monitor even if its resolution went down
+    let x = saved.x.clamp(0, monitor.width.saturating_sub(100) as i32);
+    let y = saved.y.clamp(0, monitor.height.saturating_sub(50) as i32);
+    Some((monitor.x + x, monitor.y + y))
+}
+
+fn layout_path(app_handle: &tauri::AppHandle) -> PathBuf {
+    app_handle
+        .path_resolver()
+        .app_config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(LAYOUT_FILE)
+}
+
+fn with_layout<T>(app_handle: &tauri::AppHandle, f: impl FnOnce(&mut HashMap<String, WindowGeometry>) -> T) -> Option<T> {
+    let mut guard = LAYOUT.lock().ok()?;
+    let layout = guard.get_or_insert_with(|| {
+        fs::read_to_string(layout_path(app_handle))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    });
+    Some(f(layout))
+}
+
+fn save(app_handle: &tauri::AppHandle) {
+    let content = match with_layout(app_handle, |layout| serde_json::to_string_pretty(layout)) {
+        Some(Ok(content)) => content,
+        _ => return,
+    };
+    let path = layout_path(app_handle);
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Err(e) = fs::write(&path, content) {
+        println!("⚠️ [LAYOUT] Failed to save {:?}: {}", path, e);
+    }
+}
+
+fn schedule_save(app_handle: tauri::AppHandle) {
+    let generation = SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_DELAY).await;
+        if SAVE_GENERATION.load(Ordering::SeqCst) == generation {
+            save(&app_handle);
+        }
+    });
+}
+
+fn monitor_rect(monitor: &tauri::Monitor) -> MonitorRect {
+    MonitorRect {
+        name: monitor.name().cloned(),
+        x: monitor.position().x,
+        y: monitor.position().y,
+        width: monitor.size().width,
+        height: monitor.size().height,
+    }
+}
+
+fn record(window: &Window) {
+    let (position, size) = match (window.outer_position(), window.outer_size()) {
+        (Ok(position), Ok(size)) => (position, size),
+        _ => return,
+    };
+    let monitor = window.current_monitor().ok().flatten().map(|m| monitor_rect(&m));
+    let maximized = window.is_maximized().unwrap_or(false);
+    let fullscreen = window.is_fullscreen().unwrap_or(false);
+    let label = window.label().to_string();
+    let app_handle = window.app_handle();
+    with_layout(&app_handle, |layout| {
+        // A maximized or fullscreen window reports its monitor's bounds; keep the normal size
+        let previous = layout.get(layout_key(&label)).cloned();
+        let (width, height) = match (&previous, maximized || fullscreen) {
+            (Some(previous), true) => (previous.width, previous.height),
+            _ => (size.width, size.height),
+        };
+        let origin = monitor.as_ref().map_or((0, 0), |m| (m.x, m.y));
+        layout.insert(
+            layout_key(&label).to_string(),
+            WindowGeometry {
+                monitor: monitor.as_ref().and_then(|m| m.name.clone()),
+                x: position.x - origin.0,
+                y: position.y - origin.1,
+                width,
+                height,
+                maximized,
+                fullscreen,
+            },
+        );
+    });
+    schedule_save(app_handle);
+}
+
+/// Put `window` back where it was last left and keep tracking it. Returns false when nothing
+/// was saved for it or its monitor is gone, so the caller keeps its default placement.
+pub fn restore_and_track(window: &Window) -> bool {
+    let app_handle = window.app_handle();
+    let saved = with_layout(&app_handle, |layout| layout.get(layout_key(window.label())).cloned()).flatten();
+    let monitors: Vec<MonitorRect> = window.available_monitors().unwrap_or_default().iter().map(monitor_rect).collect();
+
+    let restored = match saved.as_ref().and_then(|saved| placement(saved, &monitors).map(|at| (saved, at))) {
+        Some((saved, (x, y))) => {
+            let _ = window.set_fullscreen(false);
+            let _ = window.set_position(Position::Physical(PhysicalPosition { x, y }));
+            let _ = window.set_size(Size::Physical(PhysicalSize { width: saved.width, height: saved.height }));
+            if saved.fullscreen {
+                let _ = window.set_fullscreen(true);
+            } else if saved.maximized {
+                let _ = window.maximize();
+            }
+            println!("🪟 [LAYOUT] Restored {} on {}", window.label(), saved.monitor.as_deref().unwrap_or("primary monitor"));
+            true
+        }
+        None => false,
+    };
+
+    let tracked = window.clone();
+    window.on_window_event(move |event| {
+        if matches!(event, WindowEvent::Moved(_) | WindowEvent::Resized(_)) {
+            record(&tracked);
+        }
+    });
+    restored
+}
+
+/// Forget every saved placement and bring the cashier window back full screen on the primary monitor
+#[tauri::command]
+pub async fn reset_window_layout(app_handle: tauri::AppHandle) -> Result<(), String> {
+    SAVE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    with_layout(&app_handle, |layout| layout.clear());
+    let path = layout_path(&app_handle);
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Impossible de supprimer {:?}: {}", path, e))?;
+    }
+
+    if let Some(main) = app_handle.get_window("main") {
+        let _ = main.set_fullscreen(false);
+        if let Ok(Some(primary)) = main.primary_monitor() {
+            let _ = main.set_position(Position::Physical(*primary.position()));
+        }
+        let _ = main.set_fullscreen(true);
+    }
+    for (label, window) in app_handle.windows() {
+        if label.starts_with("vehicle-") {
+            let _ = window.center();
+        }
+    }
+    println!("🪟 [LAYOUT] Window layout reset");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, x: i32, width: u32) -> MonitorRect {
+        MonitorRect { name: Some(name.to_string()), x, y: 0, width, height: 1080 }
+    }
+
+    fn geometry(monitor: Option<&str>, x: i32, y: i32) -> WindowGeometry {
+        WindowGeometry { monitor: monitor.map(str::to_string), x, y, width: 980, height: 720, maximized: false, fullscreen: false }
+    }
+
+    #[test]
+    fn placement_follows_the_monitor_wherever_it_now_sits() {
+        let monitors = [monitor("\\\\.\\DISPLAY1", 0, 1920), monitor("\\\\.\\DISPLAY2", 1920, 1920)];
+        assert_eq!(placement(&geometry(Some("\\\\.\\DISPLAY2"), 200, 100), &monitors), Some((2120, 100)));
+
+        // Same monitors, now arranged the other way round
+        let swapped = [monitor("\\\\.\\DISPLAY2", -1920, 1920), monitor("\\\\.\\DISPLAY1", 0, 1920)];
+        assert_eq!(placement(&geometry(Some("\\\\.\\DISPLAY2"), 200, 100), &swapped), Some((-1720, 100)));
+    }
+
+    #[test]
+    fn unplugged_monitor_keeps_the_default_and_off_screen_positions_are_pulled_back() {
+        let monitors = [monitor("\\\\.\\DISPLAY1", 0, 1280)];
+        assert_eq!(placement(&geometry(Some("\\\\.\\DISPLAY3"), 10, 10), &monitors), None);
+        assert_eq!(placement(&geometry(Some("\\\\.\\DISPLAY1"), 1900, -40), &monitors), Some((1180, 0)));
+        assert_eq!(layout_key("vehicle-123 TU 4567"), "vehicle");
+        assert_eq!(layout_key("main"), "main");
+    }
+}
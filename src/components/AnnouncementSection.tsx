@@ -0,0 +1,127 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Megaphone } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { AnnouncementSettings, dbClient, QueueSummaryDto } from '../services/dbClient';
+
+// Departure calls this terminal makes on the PA when it prints an exit pass; supervisors change it
+export const AnnouncementSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [settings, setSettings] = useState<AnnouncementSettings | null>(null);
+  const [command, setCommand] = useState('');
+  const [destinations, setDestinations] = useState<QueueSummaryDto[]>([]);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.getAnnouncementSettings().then(s => {
+      setSettings(s);
+      setCommand(s.playCommand.join(' | '));
+    }).catch(() => setSettings(null));
+    dbClient.getQueueSummaries().then(setDestinations).catch(() => setDestinations([]));
+  }, []);
+
+  if (!settings) return null;
+
+  const toggleDestination = (destinationId: string, checked: boolean) => {
+    const destinationIds = checked
+      ? [...settings.destinationIds, destinationId]
+      : settings.destinationIds.filter(id => id !== destinationId);
+    setSettings({ ...settings, destinationIds });
+  };
+
+  const save = async () => {
+    try {
+      // Arguments are separated by "|" so that one may contain spaces
+      const playCommand = command.split('|').map(a => a.trim()).filter(a => a.length > 0);
+      setSettings(await dbClient.setAnnouncementSettings({ ...settings, playCommand, endpointUrl: settings.endpointUrl || null }, currentStaff?.id));
+      setMessage('Annonces enregistrées');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const replay = async () => {
+    try {
+      const last = await dbClient.replayLastAnnouncement();
+      setMessage(`Rejouée: ${last.text}`);
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Megaphone className="h-5 w-5" />
+          <span>Annonces de départ</span>
+        </CardTitle>
+        <CardDescription>
+          Appel sur la sonorisation à chaque laissez-passer imprimé par ce poste, pour les destinations choisies
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <label className="flex items-center gap-2 text-sm">
+          <input
+            type="checkbox"
+            checked={settings.enabled}
+            disabled={!isSupervisor}
+            onChange={e => setSettings({ ...settings, enabled: e.target.checked })}
+          />
+          Annoncer les départs depuis ce poste
+        </label>
+        <div className="flex flex-wrap gap-3">
+          {destinations.map(d => (
+            <label key={d.destinationId} className="flex items-center gap-1 text-sm">
+              <input
+                type="checkbox"
+                checked={settings.destinationIds.includes(d.destinationId)}
+                disabled={!isSupervisor}
+                onChange={e => toggleDestination(d.destinationId, e.target.checked)}
+              />
+              {d.destinationName}
+            </label>
+          ))}
+        </div>
+        <div>
+          <Label htmlFor="announce-template">Texte ({'{destination}'}, {'{plate}'})</Label>
+          <Input
+            id="announce-template"
+            value={settings.template}
+            disabled={!isSupervisor}
+            onChange={e => setSettings({ ...settings, template: e.target.value })}
+          />
+        </div>
+        <div>
+          <Label htmlFor="announce-endpoint">Adresse de la sonorisation (POST texte)</Label>
+          <Input
+            id="announce-endpoint"
+            placeholder="http://192.168.1.50:8080/announce"
+            value={settings.endpointUrl ?? ''}
+            disabled={!isSupervisor}
+            onChange={e => setSettings({ ...settings, endpointUrl: e.target.value })}
+          />
+        </div>
+        <div>
+          <Label htmlFor="announce-command">Ou commande de lecture, arguments séparés par |</Label>
+          <Input
+            id="announce-command"
+            placeholder="espeak | -v | fr | {text}"
+            value={command}
+            disabled={!isSupervisor}
+            onChange={e => setCommand(e.target.value)}
+          />
+        </div>
+        <div className="flex gap-2">
+          {isSupervisor && <Button variant="outline" onClick={save}>Enregistrer</Button>}
+          <Button variant="outline" onClick={replay}>Rejouer la dernière annonce</Button>
+        </div>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,28 @@
+import React, { useEffect } from 'react';
+import { useAuth } from '../context/AuthProvider';
+import { useNotifications } from '../context/NotificationProvider';
+import { dbClient } from '../services/dbClient';
+
+// Shows supervisors the suspicious patterns the backend watch reports as they show up
+export const AnomalyAlerts: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const { addNotification } = useNotifications();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+
+  useEffect(() => {
+    if (!isSupervisor) return;
+    const unlisten = dbClient.onAnomaly(anomaly => {
+      addNotification({
+        type: 'warning',
+        title: 'Activité suspecte',
+        message: anomaly.message,
+        autoClose: false,
+      });
+    });
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, [isSupervisor, addNotification]);
+
+  return null;
+};
@@ -0,0 +1,62 @@
+import React, { useCallback, useEffect, useState } from 'react';
+import { Camera } from 'lucide-react';
+import { Button } from './ui/button';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, PendingAnprEntry } from '../services/dbClient';
+
+// Vehicles the gate camera put in the queue, until a cashier confirms or rejects them
+export const AnprEntryConfirmations: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const [pending, setPending] = useState<PendingAnprEntry[]>([]);
+  const [error, setError] = useState('');
+
+  const refresh = useCallback(() => {
+    dbClient.getPendingAnprEntries().then(setPending).catch(() => {});
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    const unlisten = dbClient.onAnprEntryPending(entry => {
+      setPending(current => current.some(e => e.queueId === entry.queueId) ? current : [...current, entry]);
+    });
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, [refresh]);
+
+  const decide = async (queueId: string, confirm: boolean) => {
+    setError('');
+    try {
+      if (confirm) await dbClient.confirmAnprEntry(queueId, currentStaff?.id);
+      else await dbClient.rejectAnprEntry(queueId, currentStaff?.id);
+      setPending(current => current.filter(e => e.queueId !== queueId));
+    } catch (e) {
+      setError(String(e));
+      refresh();
+    }
+  };
+
+  if (!currentStaff || pending.length === 0) return null;
+
+  return (
+    <div className="fixed bottom-4 right-4 z-50 w-96 space-y-2">
+      {pending.map(entry => (
+        <div key={entry.queueId} className="rounded-lg border border-amber-400 bg-white p-3 shadow-lg dark:bg-gray-900">
+          <div className="flex items-center gap-2 text-sm font-semibold">
+            <Camera className="h-4 w-4" />
+            Caméra du portail
+            <span className="ml-auto text-xs font-normal text-muted-foreground">{entry.enteredAt.slice(11, 16)}</span>
+          </div>
+          <p className="my-2 text-base">
+            {entry.licensePlate} → {entry.destinationName} (position {entry.queuePosition})
+          </p>
+          <div className="flex gap-2">
+            <Button size="sm" onClick={() => decide(entry.queueId, true)}>Confirmer l'entrée</Button>
+            <Button size="sm" variant="outline" onClick={() => decide(entry.queueId, false)}>Rejeter</Button>
+          </div>
+        </div>
+      ))}
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+    </div>
+  );
+};
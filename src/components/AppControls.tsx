@@ -17,6 +17,15 @@ interface AppControlsProps {
   className?: string;
 }
 
+// Same shape on every OS; method is registry-run, systemd-user or launchd
+interface StartupStatus {
+  os: string;
+  method: string;
+  enabled: boolean;
+  changed: boolean;
+  message: string;
+}
+
 export const AppControls: React.FC<AppControlsProps> = ({ className = "" }) => {
   const [isAutoStartupEnabled, setIsAutoStartupEnabled] = useState<boolean>(false);
   const [isLoading, setIsLoading] = useState(false);
@@ -28,8 +37,8 @@ export const AppControls: React.FC<AppControlsProps> = ({ className = "" }) => {
 
   const checkAutoStartupStatus = async () => {
     try {
-      const enabled = await invoke<boolean>('check_auto_startup');
-      setIsAutoStartupEnabled(enabled);
+      const status = await invoke<StartupStatus>('check_auto_startup');
+      setIsAutoStartupEnabled(status.enabled);
     } catch (error) {
       console.error('Failed to check auto-startup status:', error);
     }
@@ -59,17 +68,9 @@ export const AppControls: React.FC<AppControlsProps> = ({ className = "" }) => {
     try {
       setIsLoading(true);
       
-      if (isAutoStartupEnabled) {
-        const result = await invoke<string>('disable_auto_startup');
-        setIsAutoStartupEnabled(false);
-        toast.success('Démarrage automatique désactivé');
-        console.log(result);
-      } else {
-        const result = await invoke<string>('setup_auto_startup');
-        setIsAutoStartupEnabled(true);
-        toast.success('Démarrage automatique activé');
-        console.log(result);
-      }
+      const status = await invoke<StartupStatus>(isAutoStartupEnabled ? 'disable_auto_startup' : 'setup_auto_startup');
+      setIsAutoStartupEnabled(status.enabled);
+      toast.success(status.message);
     } catch (error) {
       toast.error('Échec de la modification du démarrage automatique');
       console.error('Failed to toggle auto-startup:', error);
@@ -0,0 +1,105 @@
+import React, { useCallback, useEffect, useState } from 'react';
+import { ShieldCheck } from 'lucide-react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { useAuth } from '../context/AuthProvider';
+import { useNotifications } from '../context/NotificationProvider';
+import { ApprovalRequest, dbClient, OverrideAction } from '../services/dbClient';
+
+const ACTION_LABELS: Record<OverrideAction, string> = {
+  REFUND: 'Remboursement',
+  PRICE_OVERRIDE: 'Changement de prix',
+  DISPATCH_BELOW_THRESHOLD: 'Départ sous le seuil',
+  VOID_AFTER_WINDOW: 'Annulation après sortie',
+  AFTER_HOURS: 'Vente hors horaires',
+};
+
+// The supervisors' approval inbox: a badge with the requests cashiers sent from their
+// terminals, opened to approve or reject each with an optional note
+export const ApprovalInbox: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const { addNotification } = useNotifications();
+  const [pending, setPending] = useState<ApprovalRequest[]>([]);
+  const [open, setOpen] = useState(false);
+  const [notes, setNotes] = useState<Record<string, string>>({});
+  const [error, setError] = useState('');
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+
+  const refresh = useCallback(() => {
+    dbClient.listPendingApprovals().then(setPending).catch(() => {});
+  }, []);
+
+  useEffect(() => {
+    if (!isSupervisor) return;
+    refresh();
+    const unlistenRequested = dbClient.onApprovalRequested(request => {
+      setPending(current => current.some(r => r.id === request.id) ? current : [...current, request]);
+      addNotification({
+        type: 'warning',
+        title: `Autorisation demandée · ${ACTION_LABELS[request.action]}`,
+        message: `${request.subject} · ${request.requestedByName ?? request.terminalName ?? ''}`,
+      });
+    });
+    const unlistenClosed = dbClient.onApprovalClosed(request => {
+      setPending(current => current.filter(r => r.id !== request.id));
+    });
+    return () => {
+      unlistenRequested.then(f => f());
+      unlistenClosed.then(f => f());
+    };
+  }, [isSupervisor, refresh, addNotification]);
+
+  const decide = async (id: string, approve: boolean) => {
+    setError('');
+    try {
+      const note = notes[id]?.trim() || undefined;
+      if (approve) {
+        await dbClient.approveApproval(id, note, currentStaff?.id);
+      } else {
+        await dbClient.rejectApproval(id, note, currentStaff?.id);
+      }
+      setPending(current => current.filter(r => r.id !== id));
+    } catch (e) {
+      setError(String(e));
+      refresh();
+    }
+  };
+
+  if (!isSupervisor || pending.length === 0) return null;
+
+  return (
+    <div className="fixed bottom-4 left-4 z-50">
+      {open && (
+        <div className="mb-2 w-96 max-h-[60vh] overflow-y-auto space-y-2 rounded-lg border bg-white dark:bg-gray-900 p-3 shadow-lg">
+          {pending.map(request => (
+            <div key={request.id} className="rounded border p-2">
+              <div className="flex items-center text-sm font-semibold">
+                {ACTION_LABELS[request.action]} · {request.subject}
+                <span className="ml-auto font-normal text-xs text-muted-foreground">{request.createdAt.slice(11, 16)}</span>
+              </div>
+              <p className="text-xs text-muted-foreground">
+                {request.requestedByName ?? '—'}{request.terminalName ? ` · ${request.terminalName}` : ''} · {request.reasonCode}
+              </p>
+              <p className="my-1 text-sm">{request.note}</p>
+              <Input
+                placeholder="Note (facultative)"
+                value={notes[request.id] ?? ''}
+                onChange={e => setNotes(current => ({ ...current, [request.id]: e.target.value }))}
+              />
+              <div className="mt-2 flex gap-2">
+                <Button size="sm" onClick={() => decide(request.id, true)}>Approuver</Button>
+                <Button size="sm" variant="destructive" onClick={() => decide(request.id, false)}>Refuser</Button>
+              </div>
+            </div>
+          ))}
+          {error && <p className="text-sm text-red-600">❌ {error}</p>}
+        </div>
+      )}
+      <Button onClick={() => setOpen(!open)} className="relative rounded-full shadow-lg">
+        <ShieldCheck className="h-5 w-5" />
+        <span className="ml-2">Autorisations</span>
+        <span className="ml-2 rounded-full bg-red-600 px-2 text-xs text-white">{pending.length}</span>
+      </Button>
+    </div>
+  );
+};
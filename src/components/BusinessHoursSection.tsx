@@ -0,0 +1,81 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Clock } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { BusinessHours, dbClient } from '../services/dbClient';
+
+// Opening and closing hours of the station, set by supervisors
+export const BusinessHoursSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [hours, setHours] = useState<BusinessHours | null>(null);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.getBusinessHours().then(setHours).catch(() => setHours(null));
+  }, []);
+
+  if (!hours) return null;
+
+  const save = async () => {
+    try {
+      setHours(await dbClient.setBusinessHours(hours, currentStaff?.id));
+      setMessage('Horaires mis à jour');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Clock className="h-5 w-5" />
+          <span>Horaires d'ouverture</span>
+        </CardTitle>
+        <CardDescription>
+          Hors horaires, la vente et l'entrée en file sont refusées sauf autorisation d'un superviseur.
+          La fermeture de la file s'exécute à l'heure de fermeture.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={hours.enabled}
+            disabled={!isSupervisor}
+            onChange={e => setHours({ ...hours, enabled: e.target.checked })}
+          />
+          <span>Appliquer les horaires</span>
+        </label>
+        <div className="grid grid-cols-2 gap-4">
+          <div>
+            <Label htmlFor="opens-at">Ouverture</Label>
+            <Input
+              id="opens-at"
+              type="time"
+              value={hours.opensAt.slice(0, 5)}
+              disabled={!isSupervisor}
+              onChange={e => setHours({ ...hours, opensAt: `${e.target.value}:00` })}
+            />
+          </div>
+          <div>
+            <Label htmlFor="closes-at">Fermeture</Label>
+            <Input
+              id="closes-at"
+              type="time"
+              value={hours.closesAt.slice(0, 5)}
+              disabled={!isSupervisor}
+              onChange={e => setHours({ ...hours, closesAt: `${e.target.value}:00` })}
+            />
+          </div>
+        </div>
+        {isSupervisor && <Button variant="outline" onClick={save}>Enregistrer</Button>}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,102 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Ban } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, CancellationPolicy, CancellationFee } from '../services/dbClient';
+
+// Station-wide cancellation rules and the fees kept today; supervisors change the rules
+export const CancellationPolicySection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [policy, setPolicy] = useState<CancellationPolicy | null>(null);
+  const [fees, setFees] = useState<CancellationFee[]>([]);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.getCancellationPolicy().then(setPolicy).catch(() => setPolicy(null));
+    dbClient.getCancellationFees().then(setFees).catch(() => setFees([]));
+  }, []);
+
+  if (!policy) return null;
+
+  const save = async () => {
+    try {
+      setPolicy(await dbClient.setCancellationPolicy(policy, currentStaff?.id));
+      setMessage('Règles enregistrées');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const totalFees = fees.reduce((sum, f) => sum + f.fee, 0);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Ban className="h-5 w-5" />
+          <span>Annulations</span>
+        </CardTitle>
+        <CardDescription>
+          Délai d'annulation gratuite et frais retenus par place au-delà
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex gap-4 items-end">
+          <div>
+            <Label htmlFor="cancel-free-minutes">Gratuite pendant (minutes)</Label>
+            <Input
+              id="cancel-free-minutes"
+              type="number"
+              min="0"
+              value={policy.freeMinutes}
+              disabled={!isSupervisor}
+              onChange={e => setPolicy({ ...policy, freeMinutes: Number(e.target.value) })}
+            />
+          </div>
+          <div>
+            <Label htmlFor="cancel-fee">Frais par place (TND)</Label>
+            <Input
+              id="cancel-fee"
+              type="number"
+              min="0"
+              step="0.1"
+              value={policy.feePerSeat}
+              disabled={!isSupervisor}
+              onChange={e => setPolicy({ ...policy, feePerSeat: Number(e.target.value) })}
+            />
+          </div>
+          <div>
+            <Label htmlFor="no-show-refund">Remboursement des absents (%)</Label>
+            <Input
+              id="no-show-refund"
+              type="number"
+              min="0"
+              max="100"
+              value={policy.noShowRefundPercent}
+              disabled={!isSupervisor}
+              onChange={e => setPolicy({ ...policy, noShowRefundPercent: Number(e.target.value) })}
+            />
+          </div>
+          {isSupervisor && <Button variant="outline" onClick={save}>Enregistrer</Button>}
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <input
+            type="checkbox"
+            checked={policy.forbidAfterExit}
+            disabled={!isSupervisor}
+            onChange={e => setPolicy({ ...policy, forbidAfterExit: e.target.checked })}
+          />
+          Refuser l'annulation une fois le bon de sortie imprimé
+        </label>
+        <p className="text-sm text-muted-foreground">
+          Frais retenus aujourd'hui : {totalFees.toFixed(3)} TND ({fees.length} annulation{fees.length > 1 ? 's' : ''})
+        </p>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
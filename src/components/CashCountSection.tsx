@@ -0,0 +1,107 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Coins } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { CashCount, dbClient, ShiftReport } from '../services/dbClient';
+
+// Same list as the backend (cash_count::DENOMINATIONS), largest first
+const DENOMINATIONS = [50, 20, 10, 5, 2, 1, 0.5, 0.2, 0.1, 0.05, 0.02, 0.01];
+
+// Cashier's drawer count at shift close; the threshold is a supervisor setting
+export const CashCountSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [counts, setCounts] = useState<Record<number, string>>({});
+  const [report, setReport] = useState<ShiftReport | null>(null);
+  const [result, setResult] = useState<CashCount | null>(null);
+  const [threshold, setThreshold] = useState('');
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    if (currentStaff?.id) {
+      dbClient.getShiftReport(currentStaff.id).then(setReport).catch(e => setMessage(`❌ ${e}`));
+    }
+    dbClient.getCashVarianceThreshold().then(t => setThreshold(t.toFixed(3))).catch(() => setThreshold(''));
+  }, [currentStaff?.id]);
+
+  const counted = DENOMINATIONS.reduce((sum, d) => sum + d * (parseInt(counts[d] || '0', 10) || 0), 0);
+
+  const record = async () => {
+    if (!currentStaff?.id) return;
+    try {
+      const denominations = DENOMINATIONS
+        .map(denomination => ({ denomination, count: parseInt(counts[denomination] || '0', 10) || 0 }))
+        .filter(line => line.count > 0);
+      const count = await dbClient.recordCashCount(currentStaff.id, denominations);
+      setResult(count);
+      setMessage(count.flagged ? '⚠️ Écart au-delà du seuil, fiche imprimée' : '✅ Comptage enregistré');
+      setCounts({});
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  const saveThreshold = async () => {
+    try {
+      const saved = await dbClient.setCashVarianceThreshold(parseFloat(threshold), currentStaff?.id);
+      setThreshold(saved.toFixed(3));
+      setMessage('✅ Seuil enregistré');
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Coins className="h-5 w-5" />
+          <span>Comptage de caisse</span>
+        </CardTitle>
+        <CardDescription>
+          Billets et pièces en caisse à la fin du service, comparés à la caisse attendue
+          {report ? ` (${report.expectedCash.toFixed(3)} TND)` : ''}.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="grid grid-cols-4 gap-2">
+          {DENOMINATIONS.map(d => (
+            <label key={d} className="flex items-center gap-2 text-sm">
+              <span className="w-14 text-right">{d.toFixed(3)}</span>
+              <Input
+                type="number"
+                min="0"
+                step="1"
+                value={counts[d] ?? ''}
+                onChange={e => setCounts({ ...counts, [d]: e.target.value })}
+              />
+            </label>
+          ))}
+        </div>
+        <div className="text-sm font-semibold">Total compté : {counted.toFixed(3)} TND</div>
+        <Button onClick={record} disabled={!currentStaff?.id}>Enregistrer le comptage</Button>
+        {result && (
+          <div className={`text-sm ${result.flagged ? 'text-red-600' : 'text-muted-foreground'}`}>
+            Compté {result.counted.toFixed(3)} TND · Attendu {result.expected.toFixed(3)} TND · Écart{' '}
+            {result.variance > 0 ? '+' : ''}{result.variance.toFixed(3)} TND
+          </div>
+        )}
+        {report && report.voidedTickets.length > 0 && (
+          <div className="text-sm text-muted-foreground">
+            Numéros annulés : {report.voidedTickets.map(v => `${v.ticketNumber}${v.replacedBy ? ` → ${v.replacedBy}` : ''}`).join(', ')}
+          </div>
+        )}
+        {isSupervisor && (
+          <div className="flex items-center gap-2">
+            <span className="text-sm">Seuil d'écart (TND)</span>
+            <Input className="w-32" type="number" step="0.001" min="0" value={threshold} onChange={e => setThreshold(e.target.value)} />
+            <Button variant="outline" onClick={saveThreshold} disabled={!threshold}>Enregistrer</Button>
+          </div>
+        )}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
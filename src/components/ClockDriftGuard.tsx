@@ -0,0 +1,58 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Clock, RefreshCw } from 'lucide-react';
+import { dbClient, ClockStatusDto } from '../services/dbClient';
+
+// Blocks the screen while the PC clock is off the database clock by more than the threshold.
+// Tickets printed meanwhile carry database time; the fix is to correct the Windows clock.
+export const ClockDriftGuard: React.FC = () => {
+  const [status, setStatus] = useState<ClockStatusDto | null>(null);
+  const [checking, setChecking] = useState(false);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    dbClient.getClockStatus().then(setStatus).catch(() => {});
+    const unlisten = dbClient.onClockDrift(setStatus);
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  const recheck = async () => {
+    setChecking(true);
+    setError('');
+    try {
+      setStatus(await dbClient.checkClockDrift());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setChecking(false);
+    }
+  };
+
+  if (!status?.drifting) return null;
+
+  const minutes = Math.abs(status.driftSeconds) / 60;
+  const direction = status.driftSeconds > 0 ? 'en retard' : 'en avance';
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/70">
+      <div className="max-w-md space-y-4 rounded-lg bg-background p-6 text-center shadow-xl">
+        <Clock className="mx-auto h-12 w-12 text-red-600" />
+        <h2 className="text-xl font-bold">Horloge du poste incorrecte</h2>
+        <p className="text-sm">
+          L'horloge de ce PC est {direction} de {minutes.toFixed(1)} min par rapport au serveur
+          (tolérance {status.thresholdSeconds} s). Corrigez l'heure de Windows puis vérifiez à nouveau.
+        </p>
+        <p className="text-xs text-muted-foreground">
+          En attendant, les tickets sont horodatés avec l'heure du serveur.
+        </p>
+        {error && <p className="text-sm text-red-600">{error}</p>}
+        <Button onClick={recheck} disabled={checking}>
+          <RefreshCw className={`mr-2 h-4 w-4 ${checking ? 'animate-spin' : ''}`} />
+          Vérifier à nouveau
+        </Button>
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,58 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { AlertTriangle, Upload } from 'lucide-react';
+import { dbClient, CrashReportSummaryDto } from '../services/dbClient';
+
+export const CrashReportsSection: React.FC = () => {
+  const [reports, setReports] = useState<CrashReportSummaryDto[]>([]);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.listCrashReports().then(setReports).catch(e => setMessage(String(e)));
+  }, []);
+
+  const upload = async (id: string) => {
+    try {
+      const uploaded = await dbClient.uploadCrashReport(id);
+      setReports(reports.map(r => (r.id === id ? uploaded : r)));
+      setMessage('✅ Rapport envoyé');
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <AlertTriangle className="h-5 w-5" />
+          <span>Rapports d'incident</span>
+        </CardTitle>
+        <CardDescription>
+          Enregistrés sur ce poste à chaque plantage. Rien n'est envoyé sans votre accord.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-2">
+        {reports.length === 0 && <p className="text-sm text-muted-foreground">Aucun incident enregistré</p>}
+        {reports.map(report => (
+          <div key={report.id} className="flex items-center gap-3 rounded border p-2 text-sm">
+            <div className="flex-1">
+              <div className="font-mono text-xs text-muted-foreground">{report.createdAt} · v{report.appVersion}</div>
+              <div>{report.message}</div>
+              {report.location && <div className="font-mono text-xs text-muted-foreground">{report.location}</div>}
+            </div>
+            {report.uploadedAt ? (
+              <span className="text-xs text-muted-foreground">Envoyé le {report.uploadedAt}</span>
+            ) : (
+              <Button variant="outline" size="sm" onClick={() => upload(report.id)}>
+                <Upload className="mr-1 h-4 w-4" /> Envoyer
+              </Button>
+            )}
+          </div>
+        ))}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
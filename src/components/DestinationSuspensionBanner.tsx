@@ -0,0 +1,32 @@
+import React, { useEffect, useState } from 'react';
+import { Ban } from 'lucide-react';
+import { dbClient, DestinationSuspension } from '../services/dbClient';
+
+const REFRESH_MS = 30_000;
+
+// Suspended routes, re-read every 30 s so a suspension that lifted by itself disappears
+export const DestinationSuspensionBanner: React.FC = () => {
+  const [suspensions, setSuspensions] = useState<DestinationSuspension[]>([]);
+
+  useEffect(() => {
+    const load = () => dbClient.getDestinationSuspensions().then(setSuspensions).catch(() => {});
+    load();
+    const id = setInterval(load, REFRESH_MS);
+    return () => clearInterval(id);
+  }, []);
+
+  if (suspensions.length === 0) return null;
+
+  return (
+    <div className="rounded-lg border border-red-300 bg-red-50 p-3 space-y-1">
+      {suspensions.map(s => (
+        <div key={s.id} className="flex items-center gap-2 text-sm text-red-800">
+          <Ban className="h-4 w-4" />
+          <span className="font-semibold">{s.destinationName} suspendue</span>
+          <span>· {s.reason}</span>
+          {s.until && <span>· jusqu'à {s.until.slice(11, 16)}</span>}
+        </div>
+      ))}
+    </div>
+  );
+};
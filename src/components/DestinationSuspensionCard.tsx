@@ -0,0 +1,105 @@
+import React, { useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Select } from './ui/select';
+import { Ban } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, DestinationDto, DestinationSuspension } from '../services/dbClient';
+
+// Suspend a route for weather or roadworks, and lift it early; it lifts by itself at the end time
+export const DestinationSuspensionCard: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const [destinations, setDestinations] = useState<DestinationDto[]>([]);
+  const [suspensions, setSuspensions] = useState<DestinationSuspension[]>([]);
+  const [destinationId, setDestinationId] = useState('');
+  const [reason, setReason] = useState('');
+  const [until, setUntil] = useState('');
+  const [error, setError] = useState('');
+
+  const refresh = () => {
+    dbClient.getDestinationSuspensions().then(setSuspensions).catch(e => setError(String(e)));
+  };
+
+  useEffect(() => {
+    dbClient.getAvailableDestinations().then(list => {
+      setDestinations(list);
+      if (list.length > 0) setDestinationId(id => id || list[0].stationId);
+    }).catch(e => setError(String(e)));
+    refresh();
+  }, []);
+
+  const suspend = async () => {
+    setError('');
+    try {
+      // datetime-local gives Tunis wall-clock time
+      await dbClient.suspendDestination(destinationId, reason, until || undefined, currentStaff?.id);
+      setReason('');
+      setUntil('');
+      refresh();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const lift = async (id: string) => {
+    setError('');
+    try {
+      await dbClient.liftDestinationSuspension(id, currentStaff?.id);
+      refresh();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <Card className="p-4 space-y-3">
+      <div className="text-lg font-semibold flex items-center gap-2">
+        <Ban className="h-5 w-5" />
+        Suspension de destination
+      </div>
+      <div className="flex gap-2 items-end">
+        <div className="w-48">
+          <Select
+            options={destinations.map(d => ({ value: d.stationId, label: d.stationName }))}
+            value={destinationId}
+            onChange={e => setDestinationId(e.target.value)}
+          />
+        </div>
+        <Input placeholder="Raison (météo, travaux…)" value={reason} onChange={e => setReason(e.target.value)} />
+        <div className="w-56">
+          <Input type="datetime-local" value={until} onChange={e => setUntil(e.target.value)} />
+        </div>
+        <Button variant="destructive" onClick={suspend} disabled={!destinationId || !reason.trim()}>Suspendre</Button>
+      </div>
+      <p className="text-xs text-muted-foreground">Sans heure de fin, la suspension dure jusqu'à sa levée</p>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {suspensions.length > 0 && (
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-muted-foreground">
+              <th>Destination</th>
+              <th>Raison</th>
+              <th>Jusqu'à</th>
+              <th>Par</th>
+              <th></th>
+            </tr>
+          </thead>
+          <tbody>
+            {suspensions.map(s => (
+              <tr key={s.id} className="border-t">
+                <td className="font-medium">{s.destinationName}</td>
+                <td>{s.reason}</td>
+                <td>{s.until ? s.until.slice(0, 16).replace('T', ' ') : 'Levée manuelle'}</td>
+                <td>{s.suspendedByName ?? '—'}</td>
+                <td className="text-right">
+                  <Button size="sm" variant="outline" onClick={() => lift(s.destinationId)}>Lever</Button>
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+    </Card>
+  );
+};
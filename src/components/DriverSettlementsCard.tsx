@@ -0,0 +1,67 @@
+import React, { useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Wallet } from 'lucide-react';
+import { dbClient, DriverSettlement } from '../services/dbClient';
+
+// Departures of one vehicle on one day and what each paid the driver
+export const DriverSettlementsCard: React.FC<{ vehicleId: string; date: string }> = ({ vehicleId, date }) => {
+  const [settlements, setSettlements] = useState<DriverSettlement[]>([]);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    setError('');
+    dbClient.getDriverSettlements(vehicleId, date, date).then(setSettlements).catch(e => {
+      setSettlements([]);
+      setError(String(e));
+    });
+  }, [vehicleId, date]);
+
+  const sum = (pick: (s: DriverSettlement) => number) => settlements.reduce((total, s) => total + pick(s), 0);
+
+  return (
+    <Card className="p-6">
+      <h3 className="text-lg font-semibold mb-4 flex items-center gap-2">
+        <Wallet className="h-5 w-5" />
+        Décomptes chauffeur
+      </h3>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {!error && settlements.length === 0 && <p className="text-sm text-muted-foreground">Aucun départ ce jour</p>}
+      {settlements.length > 0 && (
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-muted-foreground">
+              <th>Heure</th>
+              <th>Destination</th>
+              <th className="text-right">Places</th>
+              <th className="text-right">Encaissé</th>
+              <th className="text-right">Commission</th>
+              <th className="text-right">Pass</th>
+              <th className="text-right">Net chauffeur</th>
+            </tr>
+          </thead>
+          <tbody>
+            {settlements.map(s => (
+              <tr key={s.id} className="border-t">
+                <td>{new Date(s.createdAt).toLocaleTimeString('fr-FR', { hour: '2-digit', minute: '2-digit' })}</td>
+                <td>{s.destinationName}</td>
+                <td className="text-right">{s.seatsSold}</td>
+                <td className="text-right">{s.gross.toFixed(3)}</td>
+                <td className="text-right">{s.commission.toFixed(3)}</td>
+                <td className="text-right">{s.dayPassFee.toFixed(3)}</td>
+                <td className="text-right font-semibold">{s.netToDriver.toFixed(3)}</td>
+              </tr>
+            ))}
+            <tr className="border-t font-semibold">
+              <td colSpan={2}>Total</td>
+              <td className="text-right">{sum(s => s.seatsSold)}</td>
+              <td className="text-right">{sum(s => s.gross).toFixed(3)}</td>
+              <td className="text-right">{sum(s => s.commission).toFixed(3)}</td>
+              <td className="text-right">{sum(s => s.dayPassFee).toFixed(3)}</td>
+              <td className="text-right">{sum(s => s.netToDriver).toFixed(3)} TND</td>
+            </tr>
+          </tbody>
+        </table>
+      )}
+    </Card>
+  );
+};
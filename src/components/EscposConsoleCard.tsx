@@ -0,0 +1,107 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Terminal } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { thermalPrinter } from '../services/thermalPrinterService';
+import type { EscposMacro } from '../services/thermalPrinterService';
+
+// "1B 40 1D 56 00" to base64; null when a byte is not two hex digits
+const hexToBase64 = (hex: string): string | null => {
+  const parts = hex.trim().split(/[\s,]+/).filter(Boolean);
+  if (parts.length === 0 || parts.some(p => !/^[0-9a-fA-F]{2}$/.test(p))) return null;
+  return btoa(String.fromCharCode(...parts.map(p => parseInt(p, 16))));
+};
+
+const base64ToHex = (base64: string) =>
+  Array.from(atob(base64), c => c.charCodeAt(0).toString(16).padStart(2, '0').toUpperCase()).join(' ');
+
+// Raw ESC/POS for calibrating the selected printer, and the saved sequences; admins only
+export const EscposConsoleCard: React.FC<{ printerId: string }> = ({ printerId }) => {
+  const { currentStaff } = useAuth();
+  const isAdmin = currentStaff?.role === 'ADMIN';
+  const [hex, setHex] = useState('');
+  const [name, setName] = useState('');
+  const [macros, setMacros] = useState<EscposMacro[]>([]);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    if (!isAdmin) return;
+    thermalPrinter.listEscposMacros().then(setMacros).catch(e => setMessage(String(e)));
+  }, [isAdmin]);
+
+  if (!isAdmin) return null;
+
+  const run = async (action: () => Promise<string>) => {
+    setMessage('');
+    try {
+      setMessage(await action());
+    } catch (e) {
+      setMessage(String(e));
+    }
+  };
+
+  const bytes = () => {
+    const base64 = hexToBase64(hex);
+    if (!base64) throw new Error('Octets hexadécimaux attendus, ex. 1B 40');
+    return base64;
+  };
+
+  const save = () => run(async () => {
+    const saved = await thermalPrinter.saveEscposMacro(name, bytes(), undefined, currentStaff?.id);
+    setMacros(await thermalPrinter.listEscposMacros());
+    return `Macro « ${saved.name} » enregistrée (${saved.size} octets)`;
+  });
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Terminal className="h-5 w-5" />
+          Console ESC/POS
+        </CardTitle>
+        <CardDescription>
+          Octets envoyés tels quels à l'imprimante sélectionnée, chaque envoi est journalisé
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <textarea
+          className="w-full border rounded p-2 font-mono text-sm"
+          rows={3}
+          placeholder="1B 40 1D 56 00"
+          value={hex}
+          onChange={e => setHex(e.target.value)}
+        />
+        <div className="flex gap-2">
+          <Button variant="outline" disabled={!printerId} onClick={() => run(() => thermalPrinter.sendRawEscpos(printerId, bytes(), currentStaff?.id))}>
+            Envoyer
+          </Button>
+          <Input placeholder="Nom de la macro" value={name} onChange={e => setName(e.target.value)} />
+          <Button variant="outline" disabled={!name.trim()} onClick={save}>Enregistrer</Button>
+        </div>
+        {macros.length > 0 && (
+          <table className="w-full text-sm">
+            <tbody>
+              {macros.map(m => (
+                <tr key={m.id} className="border-t">
+                  <td>{m.name}</td>
+                  <td className="font-mono text-xs truncate max-w-xs">{base64ToHex(m.base64Bytes)}</td>
+                  <td className="text-right space-x-2">
+                    <Button size="sm" variant="ghost" onClick={() => { setHex(base64ToHex(m.base64Bytes)); setName(m.name); }}>
+                      Modifier
+                    </Button>
+                    <Button size="sm" variant="outline" disabled={!printerId} onClick={() => run(() => thermalPrinter.runEscposMacro(printerId, m.name, currentStaff?.id))}>
+                      Exécuter
+                    </Button>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,78 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Receipt } from 'lucide-react';
+import { dbClient, Expense } from '../services/dbClient';
+
+const CATEGORIES = ['Nettoyage', 'Rouleaux papier', 'Fournitures', 'Autre'];
+
+const EMPTY_FORM = { amount: '', category: CATEGORIES[0], note: '' };
+
+// Petty cash paid out of the till today; each expense prints a voucher to sign
+export const ExpensesSection: React.FC<{ staffId?: string }> = ({ staffId }) => {
+  const [expenses, setExpenses] = useState<Expense[]>([]);
+  const [form, setForm] = useState(EMPTY_FORM);
+  const [message, setMessage] = useState('');
+
+  const load = () => dbClient.getExpenses().then(setExpenses).catch(e => setMessage(`❌ ${e}`));
+
+  useEffect(() => {
+    load();
+  }, []);
+
+  const add = async () => {
+    try {
+      await dbClient.addExpense(parseFloat(form.amount), form.category, form.note.trim() || null, staffId);
+      setMessage('✅ Dépense enregistrée, bon imprimé');
+      setForm(EMPTY_FORM);
+      await load();
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  const total = expenses.reduce((sum, e) => sum + e.amount, 0);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Receipt className="h-5 w-5" />
+          <span>Dépenses de caisse</span>
+        </CardTitle>
+        <CardDescription>
+          Sorties d'espèces du jour, déduites de la caisse attendue. Total : {total.toFixed(3)} TND
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {expenses.map(expense => (
+          <div key={expense.id} className="flex items-center justify-between text-sm border-b pb-2">
+            <div>
+              <div className="font-medium">{expense.category}</div>
+              <div className="text-muted-foreground">
+                {expense.createdAt} · {expense.staffName}{expense.note ? ` · ${expense.note}` : ''}
+              </div>
+            </div>
+            <span className="font-semibold">{expense.amount.toFixed(3)} TND</span>
+          </div>
+        ))}
+        <div className="grid grid-cols-3 gap-2">
+          <Input type="number" step="0.001" min="0" placeholder="Montant" value={form.amount} onChange={e => setForm({ ...form, amount: e.target.value })} />
+          <select
+            className="border rounded px-2 py-1 text-sm"
+            value={form.category}
+            onChange={e => setForm({ ...form, category: e.target.value })}
+          >
+            {CATEGORIES.map(category => (
+              <option key={category} value={category}>{category}</option>
+            ))}
+          </select>
+          <Input placeholder="Motif (optionnel)" value={form.note} onChange={e => setForm({ ...form, note: e.target.value })} />
+        </div>
+        <Button onClick={add} disabled={!(parseFloat(form.amount) > 0)}>Enregistrer la dépense</Button>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,69 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Minus, Plus } from 'lucide-react';
+import { dbClient, FareSelection, PricingRule } from '../services/dbClient';
+
+interface FareModifierPickerProps {
+  destinationId?: string;
+  seats: number;
+  basePrice: number;
+  value: FareSelection[];
+  onChange: (selections: FareSelection[]) => void;
+  disabled?: boolean;
+}
+
+// Same arithmetic as the backend, for the preview only: the booking reply has the real amounts
+const unitAmount = (rule: PricingRule, basePrice: number) => {
+  const magnitude = rule.amount ?? (basePrice * (rule.percent ?? 0)) / 100;
+  return rule.ruleType === 'CHILD_FARE' ? -Math.min(magnitude, basePrice) : magnitude;
+};
+
+// Child fares and luggage supplements the cashier can add to a booking for this destination
+export const FareModifierPicker: React.FC<FareModifierPickerProps> = ({ destinationId, seats, basePrice, value, onChange, disabled }) => {
+  const [rules, setRules] = useState<PricingRule[]>([]);
+
+  useEffect(() => {
+    if (!destinationId) {
+      setRules([]);
+      return;
+    }
+    dbClient.getPricingRules(destinationId).then(setRules).catch(() => setRules([]));
+  }, [destinationId]);
+
+  if (rules.length === 0) return null;
+
+  const quantityOf = (ruleId: string) => value.find(s => s.ruleId === ruleId)?.quantity ?? 0;
+  const childSeats = rules
+    .filter(r => r.ruleType === 'CHILD_FARE')
+    .reduce((sum, r) => sum + quantityOf(r.id!), 0);
+
+  const change = (rule: PricingRule, delta: number) => {
+    const quantity = Math.max(0, quantityOf(rule.id!) + delta);
+    const others = value.filter(s => s.ruleId !== rule.id);
+    onChange(quantity > 0 ? [...others, { ruleId: rule.id!, quantity }] : others);
+  };
+
+  return (
+    <div className="flex flex-col gap-1">
+      {rules.map(rule => {
+        const quantity = quantityOf(rule.id!);
+        const full = rule.ruleType === 'CHILD_FARE' && childSeats >= seats;
+        return (
+          <div key={rule.id} className="flex items-center gap-2 text-sm">
+            <Button variant="outline" size="sm" className="w-7 h-7 p-0 rounded-full" onClick={() => change(rule, -1)} disabled={disabled || quantity === 0}>
+              <Minus className="w-3 h-3" />
+            </Button>
+            <span className="w-6 text-center font-semibold">{quantity}</span>
+            <Button variant="outline" size="sm" className="w-7 h-7 p-0 rounded-full" onClick={() => change(rule, 1)} disabled={disabled || full}>
+              <Plus className="w-3 h-3" />
+            </Button>
+            <span>{rule.label}</span>
+            {quantity > 0 && (
+              <span className="text-gray-500">{(unitAmount(rule, basePrice) * quantity).toFixed(3)} TND</span>
+            )}
+          </div>
+        );
+      })}
+    </div>
+  );
+};
@@ -0,0 +1,93 @@
+import React, { useState } from 'react';
+import { Card } from './ui/card';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Search } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, SearchHit, SearchKind } from '../services/dbClient';
+
+const KINDS: Record<SearchKind, string> = { BOOKING: 'Billet', EXIT_PASS: 'Sortie', DAY_PASS: 'Pass jour' };
+
+// For phone inquiries: find a ticket or pass from whatever the caller remembers
+export const GlobalSearchCard: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const [query, setQuery] = useState('');
+  const [includeHistory, setIncludeHistory] = useState(false);
+  const [hits, setHits] = useState<SearchHit[] | null>(null);
+  const [error, setError] = useState('');
+  const [message, setMessage] = useState('');
+
+  const runSearch = async () => {
+    setError('');
+    try {
+      setHits(await dbClient.globalSearch(query, includeHistory));
+    } catch (err) {
+      setHits(null);
+      setError(String(err));
+    }
+  };
+
+  const search = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setMessage('');
+    await runSearch();
+  };
+
+  const markNoShow = async (hit: SearchHit) => {
+    if (!confirm(`Marquer le passager du billet ${hit.reference} comme absent ?`)) return;
+    try {
+      setMessage((await dbClient.markNoShow(hit.id, currentStaff?.id)).message);
+      await runSearch();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <Card className="p-6 space-y-4">
+      <form onSubmit={search} className="space-y-2">
+        <div className="flex gap-2">
+          <Input placeholder="Code, plaque, n° de pass ou caissier" value={query} onChange={e => setQuery(e.target.value)} />
+          <Button type="submit" variant="outline">
+            <Search className="h-4 w-4" />
+          </Button>
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <input type="checkbox" checked={includeHistory} onChange={e => setIncludeHistory(e.target.checked)} />
+          Chercher aussi avant aujourd'hui
+        </label>
+      </form>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {message && <p className="text-sm text-green-700">{message}</p>}
+      {hits && hits.length === 0 && <p className="text-sm text-muted-foreground">Aucun résultat</p>}
+      {hits && hits.length > 0 && (
+        <div className="space-y-2">
+          {hits.map(hit => (
+            <div key={`${hit.kind}-${hit.id}`} className="border rounded p-2 text-sm">
+              <div className="flex justify-between">
+                <span className="font-semibold">{KINDS[hit.kind]} {hit.kind === 'BOOKING' ? hit.reference : ''}</span>
+                <span className="text-muted-foreground">{new Date(hit.at).toLocaleString('fr-FR')}</span>
+              </div>
+              <div>
+                <span className="font-mono">{hit.licensePlate}</span>
+                {hit.destinationName && <> → {hit.destinationName}</>}
+                {hit.seats !== null && <> · {hit.seats} place(s)</>}
+                {' '}· {hit.amount.toFixed(3)} TND
+                {hit.refunded > 0 && <> · remboursé {hit.refunded.toFixed(3)} TND</>}
+                {' '}· {hit.status}
+              </div>
+              <div className="flex justify-between items-center text-muted-foreground">
+                <span>{hit.staffName}</span>
+                {hit.kind === 'BOOKING' && hit.status === 'PAID' && (
+                  <Button type="button" variant="outline" size="sm" onClick={() => markNoShow(hit)}>
+                    Absent
+                  </Button>
+                )}
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </Card>
+  );
+};
@@ -0,0 +1,68 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Languages } from 'lucide-react';
+import { dbClient, LanguageSettingsDto, StationLanguage } from '../services/dbClient';
+
+const LANGUAGES: { language: StationLanguage; label: string }[] = [
+  { language: 'fr', label: 'Français' },
+  { language: 'ar', label: 'العربية' },
+];
+
+export const LanguageSection: React.FC = () => {
+  const [settings, setSettings] = useState<LanguageSettingsDto | null>(null);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.getLanguageSettings().then(setSettings).catch(e => setMessage(String(e)));
+  }, []);
+
+  const save = async (language: StationLanguage, bilingualTickets: boolean) => {
+    try {
+      setSettings(await dbClient.setLanguageSettings(language, bilingualTickets));
+      setMessage('✅ Langue enregistrée');
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Languages className="h-5 w-5" />
+          <span>Langue de la station</span>
+        </CardTitle>
+        <CardDescription>
+          Langue des messages et des tickets, pour tous les postes de la station.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {settings && (
+          <>
+            <div className="flex gap-2">
+              {LANGUAGES.map(({ language, label }) => (
+                <Button
+                  key={language}
+                  variant={settings.language === language ? 'default' : 'outline'}
+                  onClick={() => save(language, settings.bilingualTickets)}
+                >
+                  {label}
+                </Button>
+              ))}
+            </div>
+            <label className="flex items-center gap-2 text-sm">
+              <input
+                type="checkbox"
+                checked={settings.bilingualTickets}
+                onChange={e => save(settings.language, e.target.checked)}
+              />
+              Tickets bilingues (français / arabe)
+            </label>
+          </>
+        )}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,136 @@
+import React, { useCallback, useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Hourglass } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, DestinationDto, LoadingTimeoutPolicy, StalledVehicle } from '../services/dbClient';
+
+// Loading timeouts by destination, and the vehicles past theirs with a release button
+export const LoadingTimeoutCard: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const [policy, setPolicy] = useState<LoadingTimeoutPolicy | null>(null);
+  const [destinations, setDestinations] = useState<DestinationDto[]>([]);
+  const [stalled, setStalled] = useState<StalledVehicle[]>([]);
+  const [message, setMessage] = useState('');
+
+  const refresh = useCallback(() => {
+    dbClient.getStalledVehicles().then(setStalled).catch(e => setMessage(String(e)));
+  }, []);
+
+  useEffect(() => {
+    dbClient.getLoadingTimeoutPolicy().then(setPolicy).catch(e => setMessage(String(e)));
+    dbClient.getAvailableDestinations().then(setDestinations).catch(() => setDestinations([]));
+    refresh();
+    const unlisten = dbClient.onVehicleStalled(() => refresh());
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, [refresh]);
+
+  if (!policy) return null;
+
+  // An empty field falls back to the default
+  const setDestinationMinutes = (destinationId: string, value: string) => {
+    const destinations = { ...policy.destinations };
+    if (value === '') delete destinations[destinationId];
+    else destinations[destinationId] = Math.max(0, Number(value));
+    setPolicy({ ...policy, destinations });
+  };
+
+  const save = async () => {
+    try {
+      setPolicy(await dbClient.setLoadingTimeoutPolicy(policy, currentStaff?.id));
+      setMessage('Délais enregistrés');
+      refresh();
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const release = async (vehicle: StalledVehicle) => {
+    try {
+      setMessage(await dbClient.releaseStalledVehicle(vehicle.queueId, currentStaff?.id));
+      refresh();
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card className="p-4 space-y-4">
+      <div className="text-lg font-semibold flex items-center gap-2">
+        <Hourglass className="h-5 w-5" />
+        Chargement trop long
+      </div>
+      <div className="flex gap-4 items-end">
+        <label className="flex items-center gap-2 text-sm">
+          <input type="checkbox" checked={policy.enabled} onChange={e => setPolicy({ ...policy, enabled: e.target.checked })} />
+          Signaler les véhicules bloqués
+        </label>
+        <label className="text-sm">
+          Délai par défaut (min)
+          <div className="w-24">
+            <Input
+              type="number"
+              min={0}
+              value={policy.defaultMinutes}
+              onChange={e => setPolicy({ ...policy, defaultMinutes: Math.max(0, Number(e.target.value)) })}
+            />
+          </div>
+        </label>
+        <Button variant="outline" onClick={save}>Enregistrer</Button>
+      </div>
+      {destinations.length > 0 && (
+        <div className="grid grid-cols-3 gap-2">
+          {destinations.map(d => (
+            <label key={d.stationId} className="flex items-center justify-between gap-2 text-sm">
+              {d.stationName}
+              <div className="w-24">
+                <Input
+                  type="number"
+                  min={0}
+                  placeholder={String(policy.defaultMinutes)}
+                  value={policy.destinations[d.stationId] ?? ''}
+                  onChange={e => setDestinationMinutes(d.stationId, e.target.value)}
+                />
+              </div>
+            </label>
+          ))}
+        </div>
+      )}
+      <p className="text-xs text-muted-foreground">0 : jamais signalé pour cette destination</p>
+      {message && <p className="text-sm">{message}</p>}
+
+      <div>
+        <div className="font-medium mb-1">Véhicules bloqués : {stalled.length}</div>
+        {stalled.length > 0 && (
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="text-left text-muted-foreground">
+                <th>Destination</th>
+                <th>Plaque</th>
+                <th>Places</th>
+                <th>Depuis</th>
+                <th></th>
+              </tr>
+            </thead>
+            <tbody>
+              {stalled.map(v => (
+                <tr key={v.queueId} className="border-t">
+                  <td>{v.destinationName}{v.subRouteName ? ` (${v.subRouteName})` : ''}</td>
+                  <td className="font-mono">{v.licensePlate}</td>
+                  <td>{v.bookedSeats}/{v.totalSeats}</td>
+                  <td>{v.minutesLoading} min</td>
+                  <td className="text-right">
+                    <Button size="sm" variant="outline" onClick={() => release(v)}>Libérer</Button>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+      </div>
+    </Card>
+  );
+};
@@ -0,0 +1,39 @@
+import React from 'react';
+import { MaintenanceDiff } from '../services/dbClient';
+
+const ACTIONS: Record<string, string> = { INSERT: 'Ajout', UPDATE: 'Modification', DELETE: 'Suppression' };
+
+const describe = (value: any) => {
+  if (value === null || value === undefined) return '—';
+  if (typeof value !== 'object') return String(value);
+  return Object.entries(value).map(([key, v]) => `${key}: ${v ?? '—'}`).join(', ');
+};
+
+// Changes of a maintenance command, for review before applying
+export const MaintenanceDiffTable: React.FC<{ diff: MaintenanceDiff }> = ({ diff }) => {
+  if (diff.changes.length === 0) return <p className="text-sm text-muted-foreground">Aucun changement</p>;
+  return (
+    <table className="w-full text-sm">
+      <thead>
+        <tr className="text-left text-muted-foreground">
+          <th>Table</th>
+          <th>Ligne</th>
+          <th>Action</th>
+          <th>Avant</th>
+          <th>Après</th>
+        </tr>
+      </thead>
+      <tbody>
+        {diff.changes.map(change => (
+          <tr key={`${change.table}-${change.id}-${change.action}`} className="border-t align-top">
+            <td className="font-mono">{change.table}</td>
+            <td className="font-mono">{change.label}</td>
+            <td>{ACTIONS[change.action] ?? change.action}</td>
+            <td>{describe(change.before)}</td>
+            <td>{describe(change.after)}</td>
+          </tr>
+        ))}
+      </tbody>
+    </table>
+  );
+};
@@ -0,0 +1,101 @@
+import React, { useCallback, useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Network } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, InterfaceRules, NetworkInterface } from '../services/dbClient';
+
+const patterns = (value: string) => value.split(',').map(p => p.trim()).filter(p => p !== '');
+
+// This PC's interfaces and which one finds the local node and printers; supervisors edit the rules
+export const NetworkInterfacesSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [interfaces, setInterfaces] = useState<NetworkInterface[]>([]);
+  const [rules, setRules] = useState<InterfaceRules | null>(null);
+  const [include, setInclude] = useState('');
+  const [exclude, setExclude] = useState('');
+  const [message, setMessage] = useState('');
+
+  const showRules = (next: InterfaceRules) => {
+    setRules(next);
+    setInclude(next.include.join(', '));
+    setExclude(next.exclude.join(', '));
+  };
+
+  const refresh = useCallback(() => {
+    dbClient.listNetworkInterfaces().then(setInterfaces).catch(e => setMessage(String(e)));
+  }, []);
+
+  useEffect(() => {
+    dbClient.getInterfaceRules().then(showRules).catch(e => setMessage(String(e)));
+    refresh();
+  }, [refresh]);
+
+  const save = async () => {
+    if (!rules) return;
+    try {
+      showRules(await dbClient.setInterfaceRules({ ...rules, include: patterns(include), exclude: patterns(exclude) }, currentStaff?.id));
+      setMessage('Règles enregistrées');
+      refresh();
+    } catch (e) {
+      setMessage(String(e));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Network className="h-5 w-5" />
+          <span>Interfaces réseau</span>
+        </CardTitle>
+        <CardDescription>
+          L'adresse retenue sert à trouver le serveur local et les imprimantes
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-muted-foreground">
+              <th>Interface</th>
+              <th>Adresse</th>
+              <th></th>
+            </tr>
+          </thead>
+          <tbody>
+            {interfaces.map(i => (
+              <tr key={`${i.name}-${i.address}`} className={`border-t ${i.used ? '' : 'text-muted-foreground'}`}>
+                <td>{i.name}</td>
+                <td className="font-mono">{i.address}{i.prefixLen !== null ? `/${i.prefixLen}` : ''}</td>
+                <td className="text-right">
+                  {i.selected ? '✅ Retenue' : i.used ? (i.stationNetwork ? 'Réseau station' : 'Utilisable') : 'Ignorée'}
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+        {interfaces.length === 0 && <p className="text-sm text-muted-foreground">Aucune interface IPv4 trouvée</p>}
+        {isSupervisor && rules && (
+          <div className="grid grid-cols-3 gap-2 items-end text-sm">
+            <label>
+              Réseau de la station
+              <Input value={rules.stationNetwork} onChange={e => setRules({ ...rules, stationNetwork: e.target.value })} />
+            </label>
+            <label>
+              Utiliser seulement (ex. enp*, Ethernet*)
+              <Input value={include} onChange={e => setInclude(e.target.value)} />
+            </label>
+            <label>
+              Ignorer
+              <Input value={exclude} onChange={e => setExclude(e.target.value)} />
+            </label>
+            <Button variant="outline" onClick={save}>Enregistrer</Button>
+          </div>
+        )}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
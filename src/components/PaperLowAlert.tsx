@@ -0,0 +1,24 @@
+import React, { useEffect } from 'react';
+import { useNotifications } from '../context/NotificationProvider';
+import { thermalPrinter } from '../services/thermalPrinterService';
+
+// Tells the cashier to get a new paper roll ready before the printer runs out mid-ticket
+export const PaperLowAlert: React.FC = () => {
+  const { addNotification } = useNotifications();
+
+  useEffect(() => {
+    const unlisten = thermalPrinter.onPaperLow(stats => {
+      addNotification({
+        type: 'warning',
+        title: 'Papier bientôt épuisé',
+        message: `Il reste environ ${stats.remainingM.toFixed(1)} m de papier sur l'imprimante ${stats.printerId}. Préparez un nouveau rouleau.`,
+        autoClose: false,
+      });
+    });
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, [addNotification]);
+
+  return null;
+};
@@ -0,0 +1,115 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Percent } from 'lucide-react';
+import { dbClient, DestinationDto, PricingRule, PricingRuleType } from '../services/dbClient';
+
+const RULE_TYPES: { ruleType: PricingRuleType; label: string }[] = [
+  { ruleType: 'CHILD_FARE', label: 'Tarif enfant (réduction par place)' },
+  { ruleType: 'LUGGAGE', label: 'Supplément bagage (par bagage)' },
+];
+
+const EMPTY_FORM = { ruleType: 'CHILD_FARE' as PricingRuleType, label: '', value: '', unit: 'percent', destinationId: '' };
+
+// Child fares and luggage supplements, edited by supervisors and offered at booking
+export const PricingRulesSection: React.FC<{ staffId?: string }> = ({ staffId }) => {
+  const [rules, setRules] = useState<PricingRule[]>([]);
+  const [destinations, setDestinations] = useState<DestinationDto[]>([]);
+  const [form, setForm] = useState(EMPTY_FORM);
+  const [message, setMessage] = useState('');
+
+  const load = () => dbClient.getPricingRules().then(setRules).catch(e => setMessage(`❌ ${e}`));
+
+  useEffect(() => {
+    load();
+    dbClient.getAvailableDestinations().then(setDestinations).catch(() => setDestinations([]));
+  }, []);
+
+  const save = async (rule: PricingRule) => {
+    try {
+      await dbClient.upsertPricingRule(rule, staffId);
+      setMessage('✅ Règle enregistrée');
+      await load();
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  const create = async () => {
+    const value = parseFloat(form.value);
+    await save({
+      ruleType: form.ruleType,
+      label: form.label,
+      amount: form.unit === 'amount' ? value : null,
+      percent: form.unit === 'percent' ? value : null,
+      destinationId: form.destinationId || null,
+      isActive: true,
+    });
+    setForm(EMPTY_FORM);
+  };
+
+  const destinationName = (id: string | null) =>
+    id ? destinations.find(d => d.stationId === id)?.stationName ?? id : 'Toutes les destinations';
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Percent className="h-5 w-5" />
+          <span>Règles tarifaires</span>
+        </CardTitle>
+        <CardDescription>
+          Réductions enfant et suppléments bagage, en montant fixe ou en pourcentage du prix de base.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {rules.map(rule => (
+          <div key={rule.id} className="flex items-center justify-between text-sm border-b pb-2">
+            <div>
+              <div className="font-medium">{rule.label}</div>
+              <div className="text-muted-foreground">
+                {rule.ruleType === 'CHILD_FARE' ? 'Réduction' : 'Supplément'}{' '}
+                {rule.amount != null ? `${rule.amount.toFixed(3)} TND` : `${rule.percent}%`} · {destinationName(rule.destinationId)}
+              </div>
+            </div>
+            <Button variant={rule.isActive ? 'default' : 'outline'} size="sm" onClick={() => save({ ...rule, isActive: !rule.isActive })}>
+              {rule.isActive ? 'Active' : 'Inactive'}
+            </Button>
+          </div>
+        ))}
+        <div className="grid grid-cols-2 gap-2">
+          <select
+            className="border rounded px-2 py-1 text-sm"
+            value={form.ruleType}
+            onChange={e => setForm({ ...form, ruleType: e.target.value as PricingRuleType })}
+          >
+            {RULE_TYPES.map(({ ruleType, label }) => (
+              <option key={ruleType} value={ruleType}>{label}</option>
+            ))}
+          </select>
+          <Input placeholder="Libellé sur le ticket" value={form.label} onChange={e => setForm({ ...form, label: e.target.value })} />
+          <div className="flex gap-2">
+            <Input type="number" step="0.001" min="0" placeholder="Valeur" value={form.value} onChange={e => setForm({ ...form, value: e.target.value })} />
+            <select className="border rounded px-2 py-1 text-sm" value={form.unit} onChange={e => setForm({ ...form, unit: e.target.value })}>
+              <option value="percent">%</option>
+              <option value="amount">TND</option>
+            </select>
+          </div>
+          <select
+            className="border rounded px-2 py-1 text-sm"
+            value={form.destinationId}
+            onChange={e => setForm({ ...form, destinationId: e.target.value })}
+          >
+            <option value="">Toutes les destinations</option>
+            {destinations.map(d => (
+              <option key={d.stationId} value={d.stationId}>{d.stationName}</option>
+            ))}
+          </select>
+        </div>
+        <Button onClick={create} disabled={!form.label.trim() || !form.value}>Ajouter la règle</Button>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,25 @@
+import React, { useEffect } from 'react';
+import { useNotifications } from '../context/NotificationProvider';
+import { thermalPrinter } from '../services/thermalPrinterService';
+
+// Tells the cashier when part of a ticket group (ticket and talon, exit pass and slip) didn't print
+export const PrintBatchAlert: React.FC = () => {
+  const { addNotification } = useNotifications();
+
+  useEffect(() => {
+    const unlisten = thermalPrinter.onPrintBatchFinished(report => {
+      if (report.failed.length === 0) return;
+      addNotification({
+        type: 'error',
+        title: 'Impression incomplète',
+        message: `${report.failed.length} sur ${report.printed.length + report.failed.length} document(s) non imprimé(s)${report.error ? ` : ${report.error}` : ''}. Réimprimez depuis la dernière opération.`,
+        autoClose: false,
+      });
+    });
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, [addNotification]);
+
+  return null;
+};
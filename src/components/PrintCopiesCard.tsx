@@ -0,0 +1,77 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Copy } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { thermalPrinter } from '../services/thermalPrinterService';
+import type { PrintCopies } from '../services/thermalPrinterService';
+
+const TICKET_TYPES: [string, string][] = [
+  ['ExitPassTicket', 'Pass de sortie'],
+  ['SettlementSlip', 'Décompte chauffeur'],
+  ['BookingTicket', 'Ticket de réservation'],
+  ['Talon', 'Talon'],
+  ['EntryTicket', "Ticket d'entrée"],
+  ['DayPassTicket', 'Pass journalier'],
+  ['ExpenseVoucher', 'Bon de dépense'],
+  ['CashVarianceSlip', 'Écart de caisse'],
+  ['CancellationSlip', "Reçu d'annulation"],
+];
+
+// Copies printed of each ticket type, for the whole station
+export const PrintCopiesCard: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const [copies, setCopies] = useState<PrintCopies | null>(null);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    thermalPrinter.getPrintCopies().then(setCopies).catch(e => setMessage(String(e)));
+  }, []);
+
+  if (!copies) return null;
+
+  const setCount = (type: string, value: string) => {
+    const next = { ...copies.copies };
+    if (value === '' || Number(value) <= 1) delete next[type];
+    else next[type] = Number(value);
+    setCopies({ copies: next });
+  };
+
+  const save = async () => {
+    try {
+      setCopies(await thermalPrinter.setPrintCopies(copies, currentStaff?.id));
+      setMessage('Nombre de copies enregistré');
+    } catch (e) {
+      setMessage(String(e));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Copy className="h-5 w-5" />
+          Copies par type de ticket
+        </CardTitle>
+        <CardDescription>
+          Les copies suivantes sont marquées « COPIE 2/2 »
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="grid grid-cols-3 gap-2">
+          {TICKET_TYPES.map(([type, label]) => (
+            <label key={type} className="flex items-center justify-between gap-2 text-sm">
+              {label}
+              <div className="w-20">
+                <Input type="number" min={1} max={5} value={copies.copies[type] ?? 1} onChange={e => setCount(type, e.target.value)} />
+              </div>
+            </label>
+          ))}
+        </div>
+        <Button variant="outline" onClick={save}>Enregistrer</Button>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,96 @@
+import React, { useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { History } from 'lucide-react';
+import { dbClient, PrintedTicket, PrintedTicketFilters } from '../services/dbClient';
+
+const TYPE_OPTIONS = [
+  { value: '', label: 'Tous les tickets' },
+  { value: 'booking', label: 'Réservation' },
+  { value: 'entry', label: 'Entrée' },
+  { value: 'exit_pass', label: 'Sortie' },
+  { value: 'day_pass', label: 'Pass journalier' },
+];
+
+const savePdf = (ticket: PrintedTicket, base64: string) => {
+  const bytes = Uint8Array.from(atob(base64), c => c.charCodeAt(0));
+  const url = URL.createObjectURL(new Blob([bytes], { type: 'application/pdf' }));
+  const link = document.createElement('a');
+  link.href = url;
+  link.download = `ticket-${ticket.ticketNumber ?? ticket.id}.pdf`;
+  link.click();
+  URL.revokeObjectURL(url);
+};
+
+// Print history for customer disputes: find a printed ticket and get it back as a PDF
+export const PrintHistoryCard: React.FC = () => {
+  const [filters, setFilters] = useState<PrintedTicketFilters>({});
+  const [tickets, setTickets] = useState<PrintedTicket[]>([]);
+  const [selected, setSelected] = useState<PrintedTicket | null>(null);
+  const [error, setError] = useState('');
+
+  const search = async () => {
+    setError('');
+    try {
+      setTickets(await dbClient.searchPrintedTickets(filters));
+      setSelected(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const exportPdf = async (ticket: PrintedTicket) => {
+    try {
+      savePdf(ticket, await dbClient.exportPrintedTicketPdf(ticket.id));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const set = (key: keyof PrintedTicketFilters) => (e: React.ChangeEvent<HTMLInputElement | HTMLSelectElement>) =>
+    setFilters({ ...filters, [key]: e.target.value || undefined });
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <History className="h-5 w-5" />
+          <span>Historique d'impression</span>
+        </CardTitle>
+        <CardDescription>
+          Tickets imprimés, tels qu'ils sont sortis de l'imprimante (30 derniers jours par défaut)
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="grid grid-cols-4 gap-2">
+          <select className="border rounded px-2 py-2 text-sm" value={filters.ticketType ?? ''} onChange={set('ticketType')}>
+            {TYPE_OPTIONS.map(o => <option key={o.value} value={o.value}>{o.label}</option>)}
+          </select>
+          <Input placeholder="Numéro ou code" value={filters.ticketNumber ?? ''} onChange={set('ticketNumber')} />
+          <Input placeholder="Caissier" value={filters.staffName ?? ''} onChange={set('staffName')} />
+          <Input placeholder="Imprimante" value={filters.printer ?? ''} onChange={set('printer')} />
+          <Input placeholder="Texte (plaque, destination)" value={filters.text ?? ''} onChange={set('text')} />
+          <Input type="date" value={filters.from ?? ''} onChange={set('from')} />
+          <Input type="date" value={filters.to ?? ''} onChange={set('to')} />
+          <Button onClick={search}>Rechercher</Button>
+        </div>
+        {error && <p className="text-sm text-red-600">{error}</p>}
+        <div className="max-h-64 overflow-y-auto divide-y text-sm">
+          {tickets.map(t => (
+            <div key={t.id} className="flex items-center justify-between py-1 gap-2">
+              <button className="text-left flex-1" onClick={() => setSelected(t)}>
+                {t.printedAt} · {t.ticketType} {t.ticketNumber ?? ''}
+                {t.duplicateNumber ? ` (duplicata ${t.duplicateNumber})` : ''} · {t.staffName ?? '—'} · {t.printerName}
+              </button>
+              <Button variant="outline" size="sm" onClick={() => exportPdf(t)}>PDF</Button>
+            </div>
+          ))}
+        </div>
+        {selected && (
+          <pre className="bg-muted p-2 text-xs font-mono whitespace-pre overflow-x-auto">{selected.renderedText}</pre>
+        )}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,104 @@
+import React, { useState, useEffect } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Badge } from './ui/badge';
+import { thermalPrinter } from '../services/thermalPrinterService';
+import type { PrintSchedule, QuietHours } from '../services/thermalPrinterService';
+import { Clock, Wrench } from 'lucide-react';
+
+const hhmm = (time: string) => time.slice(0, 5);
+
+// Maintenance mode for the selected printer and the quiet hours during which reports wait
+export const PrintScheduleCard: React.FC<{ printerId: string }> = ({ printerId }) => {
+  const [schedule, setSchedule] = useState<PrintSchedule | null>(null);
+  const [quietHours, setQuietHours] = useState<QuietHours | null>(null);
+  const [error, setError] = useState('');
+
+  const apply = (next: PrintSchedule) => {
+    setSchedule(next);
+    setQuietHours(next.quietHours);
+  };
+
+  useEffect(() => {
+    thermalPrinter.getPrintSchedule().then(apply).catch(e => setError(String(e)));
+  }, []);
+
+  const run = async (action: () => Promise<PrintSchedule>) => {
+    setError('');
+    try {
+      apply(await action());
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  if (!schedule || !quietHours) return null;
+  const maintenance = schedule.maintenance.find(m => m.printerId === printerId);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Wrench className="h-5 w-5" />
+          Maintenance et heures calmes
+        </CardTitle>
+        <CardDescription>
+          Les impressions sont mises en attente au lieu d'échouer, puis imprimées automatiquement
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        {printerId && (
+          <div className="flex items-center gap-4">
+            <Button
+              variant={maintenance ? 'default' : 'outline'}
+              onClick={() => run(() => thermalPrinter.setPrinterMaintenance(printerId, !maintenance))}
+            >
+              {maintenance ? 'Terminer la maintenance' : 'Mettre en maintenance'}
+            </Button>
+            {maintenance && (
+              <span className="text-sm">
+                Reprise automatique à {new Date(maintenance.until).toLocaleTimeString('fr-FR')}
+              </span>
+            )}
+            {schedule.heldJobs > 0 && <Badge variant="secondary">{schedule.heldJobs} en attente</Badge>}
+          </div>
+        )}
+        <label className="flex items-center gap-2 text-sm">
+          <input
+            type="checkbox"
+            checked={quietHours.enabled}
+            onChange={e => setQuietHours({ ...quietHours, enabled: e.target.checked })}
+          />
+          <Clock className="h-4 w-4" />
+          Reporter les rapports pendant les heures calmes
+        </label>
+        <div className="flex gap-4 items-end">
+          <div>
+            <Label htmlFor="quiet-start">Début</Label>
+            <Input
+              id="quiet-start"
+              type="time"
+              value={hhmm(quietHours.start)}
+              onChange={e => setQuietHours({ ...quietHours, start: `${e.target.value}:00` })}
+            />
+          </div>
+          <div>
+            <Label htmlFor="quiet-end">Impression des rapports à</Label>
+            <Input
+              id="quiet-end"
+              type="time"
+              value={hhmm(quietHours.end)}
+              onChange={e => setQuietHours({ ...quietHours, end: `${e.target.value}:00` })}
+            />
+          </div>
+          <Button variant="outline" onClick={() => run(() => thermalPrinter.setPrintQuietHours(quietHours))}>
+            Enregistrer
+          </Button>
+        </div>
+        {error && <p className="text-sm text-red-600">{error}</p>}
+      </CardContent>
+    </Card>
+  );
+};
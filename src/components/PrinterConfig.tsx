@@ -8,10 +8,35 @@ import { Badge } from './ui/badge';
 import { Separator } from './ui/separator';
 import { Select } from './ui/select';
 import { thermalPrinter, PrinterStatus } from '../services/thermalPrinterService';
-import type { PrinterConfig } from '../services/thermalPrinterService';
+import type { PrinterConfig, PrinterModelProfile } from '../services/thermalPrinterService';
 import { getLocalStorage, setLocalStorage } from '../lib/storage';
+import { PrinterUsageCard } from './PrinterUsageCard';
+import { PrintScheduleCard } from './PrintScheduleCard';
+import { PrintCopiesCard } from './PrintCopiesCard';
+import { EscposConsoleCard } from './EscposConsoleCard';
 import { Printer, Wifi, Settings, TestTube, CheckCircle, XCircle, Loader2, Plus, Trash2, Edit } from 'lucide-react';
 
+// Backups share the printer's model and paper; only their address differs
+const parseFailover = (primary: PrinterConfig, value: string): PrinterConfig[] =>
+  value
+    .split(',')
+    .map(entry => entry.trim())
+    .filter(Boolean)
+    .map((entry, i) => {
+      const [ip, port] = entry.split(':');
+      return {
+        ...primary,
+        id: `${primary.id}-backup${i + 1}`,
+        name: `Secours ${i + 1} (${ip})`,
+        ip,
+        port: parseInt(port) || 9100,
+        enabled: true,
+        is_default: false,
+        backend: 'network' as const,
+        failover: [],
+      };
+    });
+
 export const PrinterConfigComponent: React.FC = () => {
   const [printers, setPrinters] = useState<PrinterConfig[]>([]);
   const [currentPrinter, setCurrentPrinterState] = useState<PrinterConfig | null>(null);
@@ -22,9 +47,11 @@ export const PrinterConfigComponent: React.FC = () => {
   const [testing, setTesting] = useState(false);
   const [message, setMessage] = useState<string>('');
   const [testContent, setTestContent] = useState('Test de connexion imprimante');
+  const [models, setModels] = useState<PrinterModelProfile[]>([]);
 
   useEffect(() => {
     loadPrinters();
+    thermalPrinter.getPrinterModels().then(setModels).catch(() => setModels([]));
   }, []);
 
   const loadPrinters = async () => {
@@ -360,6 +387,18 @@ export const PrinterConfigComponent: React.FC = () => {
         </CardContent>
       </Card>
 
+      {/* Paper roll estimate and daily usage */}
+      <PrinterUsageCard printerId={selectedPrinterId} />
+
+      {/* Maintenance mode and quiet hours */}
+      <PrintScheduleCard printerId={selectedPrinterId} />
+
+      {/* Copies of each ticket type */}
+      <PrintCopiesCard />
+
+      {/* Raw ESC/POS for technicians */}
+      <EscposConsoleCard printerId={selectedPrinterId} />
+
       {/* Printer List */}
       <Card>
         <CardHeader>
@@ -452,6 +491,26 @@ export const PrinterConfigComponent: React.FC = () => {
               </div>
             </div>
             
+            <div className="space-y-2">
+              <Label htmlFor="edit-model">Modèle</Label>
+              <select
+                id="edit-model"
+                className="w-full border rounded px-2 py-2 text-sm"
+                value={models.some(m => m.model === editingPrinter.model) ? editingPrinter.model : ''}
+                onChange={(e) => {
+                  const model = models.find(m => m.model === e.target.value);
+                  if (model) setEditingPrinter({ ...editingPrinter, model: model.model, width: Math.min(editingPrinter.width, model.maxWidth) });
+                }}
+              >
+                <option value="" disabled>{editingPrinter.model}</option>
+                {models.map(m => (
+                  <option key={m.model} value={m.model}>
+                    {m.model} - {m.maxWidth} col.{m.fullCut.length === 0 ? ', sans coupe' : ''}
+                  </option>
+                ))}
+              </select>
+            </div>
+
             <div className="grid grid-cols-3 gap-4">
               <div className="space-y-2">
                 <Label htmlFor="edit-port">Port</Label>
@@ -482,6 +541,20 @@ export const PrinterConfigComponent: React.FC = () => {
               </div>
             </div>
 
+            <div className="space-y-2">
+              <Label htmlFor="edit-failover">Imprimantes de secours (IP:port, séparées par des virgules)</Label>
+              <Input
+                id="edit-failover"
+                key={editingPrinter.id}
+                placeholder="192.168.1.51:9100, 192.168.1.52:9100"
+                defaultValue={(editingPrinter.failover ?? []).map(p => `${p.ip}:${p.port}`).join(', ')}
+                onBlur={(e) => setEditingPrinter({ ...editingPrinter, failover: parseFailover(editingPrinter, e.target.value) })}
+              />
+              <p className="text-xs text-muted-foreground">
+                Essayées dans l'ordre si cette imprimante échoue ; chaque ticket indique l'imprimante qui l'a imprimé.
+              </p>
+            </div>
+
             <div className="flex gap-2">
               <Button onClick={updatePrinter} disabled={loading}>
                 {loading && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
@@ -0,0 +1,101 @@
+import React, { useState, useEffect } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Badge } from './ui/badge';
+import { thermalPrinter } from '../services/thermalPrinterService';
+import type { PrinterUsageStats } from '../services/thermalPrinterService';
+import { FileText } from 'lucide-react';
+
+// Paper used by a printer over the last days, and what is left on its roll
+export const PrinterUsageCard: React.FC<{ printerId: string }> = ({ printerId }) => {
+  const [stats, setStats] = useState<PrinterUsageStats | null>(null);
+  const [rollLength, setRollLength] = useState('');
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    if (!printerId) return;
+    thermalPrinter.getPrinterUsageStats(printerId)
+      .then(setStats)
+      .catch(e => setError(String(e)));
+  }, [printerId]);
+
+  const newRoll = async () => {
+    setError('');
+    try {
+      const length = rollLength.trim() ? Number(rollLength) : undefined;
+      setStats(await thermalPrinter.setPrinterPaperRoll(printerId, length));
+      setRollLength('');
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  if (!printerId) return null;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <FileText className="h-5 w-5" />
+          Papier et utilisation
+        </CardTitle>
+        <CardDescription>
+          Estimation à partir des lignes imprimées depuis le dernier changement de rouleau
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        {stats && (
+          <div className="flex items-center gap-2">
+            <span className="text-sm font-medium">
+              Reste environ {stats.remainingM.toFixed(1)} m sur {stats.rollLengthM.toFixed(0)} m
+            </span>
+            {stats.paperLow && <Badge variant="destructive">Papier bas</Badge>}
+          </div>
+        )}
+        <div className="flex gap-4 items-end">
+          <div className="flex-1">
+            <Label htmlFor="roll-length">Longueur du nouveau rouleau (m)</Label>
+            <Input
+              id="roll-length"
+              type="number"
+              min="1"
+              placeholder={stats ? String(stats.rollLengthM) : ''}
+              value={rollLength}
+              onChange={(e) => setRollLength(e.target.value)}
+            />
+          </div>
+          <Button onClick={newRoll} variant="outline">
+            Nouveau rouleau
+          </Button>
+        </div>
+        {stats && stats.days.length > 0 && (
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="text-left text-muted-foreground">
+                <th>Jour</th>
+                <th>Travaux</th>
+                <th>Lignes</th>
+                <th>Coupes</th>
+                <th>Octets</th>
+              </tr>
+            </thead>
+            <tbody>
+              {stats.days.slice(0, 7).map(day => (
+                <tr key={day.date}>
+                  <td>{day.date}</td>
+                  <td>{day.jobs}</td>
+                  <td>{day.lines}</td>
+                  <td>{day.cuts}</td>
+                  <td>{day.bytes}</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+        {error && <p className="text-sm text-red-600">{error}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,73 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { ShieldCheck } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, ProxyAllowlist } from '../services/dbClient';
+
+// One rule per line: "/api/queue GET POST"
+const toText = (list: ProxyAllowlist) => list.rules.map(r => `${r.prefix} ${r.methods.join(' ')}`).join('\n');
+
+const toRules = (text: string) =>
+  text
+    .split('\n')
+    .map(line => line.trim().split(/\s+/))
+    .filter(words => words[0] !== '')
+    .map(([prefix, ...methods]) => ({ prefix, methods }));
+
+// Local node endpoints the app may reach through the proxy; admins only
+export const ProxyAllowlistSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isAdmin = currentStaff?.role === 'ADMIN';
+  const [list, setList] = useState<ProxyAllowlist | null>(null);
+  const [text, setText] = useState('');
+  const [message, setMessage] = useState('');
+
+  const show = (next: ProxyAllowlist) => {
+    setList(next);
+    setText(toText(next));
+  };
+
+  useEffect(() => {
+    if (isAdmin) dbClient.getProxyAllowlist().then(show).catch(e => setMessage(`❌ ${e}`));
+  }, [isAdmin]);
+
+  if (!isAdmin || !list) return null;
+
+  const save = async () => {
+    try {
+      show(await dbClient.setProxyAllowlist({ enabled: list.enabled, rules: toRules(text) }, currentStaff?.id));
+      setMessage('✅ Liste enregistrée');
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <ShieldCheck className="h-5 w-5" />
+          <span>Accès au serveur local</span>
+        </CardTitle>
+        <CardDescription>
+          Chemins et méthodes autorisés, un par ligne. Les autres requêtes sont refusées et notées dans le journal d'audit.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <input type="checkbox" checked={list.enabled} onChange={e => setList({ ...list, enabled: e.target.checked })} />
+          Filtrer les requêtes
+        </label>
+        <textarea
+          className="w-full border rounded px-2 py-2 font-mono text-sm"
+          rows={10}
+          value={text}
+          onChange={e => setText(e.target.value)}
+        />
+        <Button variant="outline" onClick={save}>Enregistrer</Button>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
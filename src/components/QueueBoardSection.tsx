@@ -0,0 +1,116 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { ClipboardList } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, QueueSummaryDto } from '../services/dbClient';
+import { thermalPrinter, QueueBoardSchedule } from '../services/thermalPrinterService';
+
+const hhmm = (time: string) => time.slice(0, 5);
+
+// Queue boards this terminal prints by itself for the lane entrances; supervisors change it
+export const QueueBoardSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [schedule, setSchedule] = useState<QueueBoardSchedule | null>(null);
+  const [destinations, setDestinations] = useState<QueueSummaryDto[]>([]);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    thermalPrinter.getQueueBoardSchedule().then(setSchedule).catch(() => setSchedule(null));
+    dbClient.getQueueSummaries().then(setDestinations).catch(() => setDestinations([]));
+  }, []);
+
+  if (!schedule) return null;
+
+  const toggleDestination = (destinationId: string, checked: boolean) => {
+    const destinationIds = checked
+      ? [...schedule.destinationIds, destinationId]
+      : schedule.destinationIds.filter(id => id !== destinationId);
+    setSchedule({ ...schedule, destinationIds });
+  };
+
+  const save = async () => {
+    try {
+      setSchedule(await thermalPrinter.setQueueBoardSchedule(schedule, currentStaff?.id));
+      setMessage('Programme enregistré');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <ClipboardList className="h-5 w-5" />
+          <span>Tableaux de file</span>
+        </CardTitle>
+        <CardDescription>
+          Impression automatique de la file des destinations choisies, à afficher à l'entrée des quais
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <label className="flex items-center gap-2 text-sm">
+          <input
+            type="checkbox"
+            checked={schedule.enabled}
+            disabled={!isSupervisor}
+            onChange={e => setSchedule({ ...schedule, enabled: e.target.checked })}
+          />
+          Imprimer automatiquement depuis ce poste
+        </label>
+        <div className="flex flex-wrap gap-3">
+          {destinations.map(d => (
+            <label key={d.destinationId} className="flex items-center gap-1 text-sm">
+              <input
+                type="checkbox"
+                checked={schedule.destinationIds.includes(d.destinationId)}
+                disabled={!isSupervisor}
+                onChange={e => toggleDestination(d.destinationId, e.target.checked)}
+              />
+              {d.destinationName}
+            </label>
+          ))}
+        </div>
+        <div className="flex gap-4 items-end">
+          <div>
+            <Label htmlFor="board-every">Toutes les (minutes)</Label>
+            <Input
+              id="board-every"
+              type="number"
+              min="5"
+              value={schedule.everyMinutes}
+              disabled={!isSupervisor}
+              onChange={e => setSchedule({ ...schedule, everyMinutes: Number(e.target.value) })}
+            />
+          </div>
+          <div>
+            <Label htmlFor="board-from">De</Label>
+            <Input
+              id="board-from"
+              type="time"
+              value={hhmm(schedule.from)}
+              disabled={!isSupervisor}
+              onChange={e => setSchedule({ ...schedule, from: `${e.target.value}:00` })}
+            />
+          </div>
+          <div>
+            <Label htmlFor="board-until">À</Label>
+            <Input
+              id="board-until"
+              type="time"
+              value={hhmm(schedule.until)}
+              disabled={!isSupervisor}
+              onChange={e => setSchedule({ ...schedule, until: `${e.target.value}:00` })}
+            />
+          </div>
+          {isSupervisor && <Button variant="outline" onClick={save}>Enregistrer</Button>}
+        </div>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,144 @@
+import React, { useCallback, useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Select } from './ui/select';
+import { Moon } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, CarryOverPreview, ClosingMode, ClosingVehicle, MaintenanceDiff, QueueClosing } from '../services/dbClient';
+import { MaintenanceDiffTable } from './MaintenanceDiffTable';
+
+const MODES = [
+  { value: 'CARRY_OVER', label: 'Reporter au lendemain' },
+  { value: 'PURGE', label: 'Vider la file' },
+];
+
+const VehicleTable: React.FC<{ vehicles: ClosingVehicle[] }> = ({ vehicles }) => (
+  <table className="w-full text-sm">
+    <thead>
+      <tr className="text-left text-muted-foreground">
+        <th>Destination</th>
+        <th>#</th>
+        <th>Plaque</th>
+      </tr>
+    </thead>
+    <tbody>
+      {vehicles.map(v => (
+        <tr key={v.vehicleId} className="border-t">
+          <td>{v.destinationName}{v.subRouteName ? ` (${v.subRouteName})` : ''}</td>
+          <td>{v.queuePosition}</td>
+          <td className="font-mono">{v.licensePlate}</td>
+        </tr>
+      ))}
+    </tbody>
+  </table>
+);
+
+// Closing of the queue: the policy, the vehicles the next closing would touch and the morning report
+export const QueueClosingCard: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const [preview, setPreview] = useState<CarryOverPreview | null>(null);
+  const [report, setReport] = useState<QueueClosing | null>(null);
+  const [message, setMessage] = useState('');
+  const [review, setReview] = useState<MaintenanceDiff | null>(null);
+
+  const refresh = useCallback(() => {
+    dbClient.previewCarryOver().then(setPreview).catch(e => setMessage(String(e)));
+    dbClient.getCarryOverReport().then(setReport).catch(() => setReport(null));
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    const unlisten = dbClient.onQueueClosed(() => refresh());
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, [refresh]);
+
+  if (!preview) return null;
+  const policy = preview.policy;
+  const setPolicy = (changes: Partial<typeof policy>) => setPreview({ ...preview, policy: { ...policy, ...changes } });
+
+  const save = async () => {
+    try {
+      await dbClient.setQueueClosingPolicy(policy, currentStaff?.id);
+      setMessage('Fermeture enregistrée');
+      refresh();
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  // Dry run first; the supervisor applies only what they reviewed
+  const closeNow = async (dryRun: boolean) => {
+    try {
+      const diff = await dbClient.closeQueueNow(dryRun, currentStaff?.id);
+      setReview(dryRun ? diff : null);
+      if (!dryRun) {
+        setMessage(`File fermée : ${diff.changes.length} changement(s)`);
+        refresh();
+      }
+    } catch (error) {
+      setReview(null);
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card className="p-4 space-y-4">
+      <div className="text-lg font-semibold flex items-center gap-2">
+        <Moon className="h-5 w-5" />
+        Fermeture de la file
+      </div>
+      <div className="flex gap-4 items-end">
+        <label className="flex items-center gap-2 text-sm">
+          <input type="checkbox" checked={policy.enabled} onChange={e => setPolicy({ enabled: e.target.checked })} />
+          Fermer chaque soir
+        </label>
+        <div className="w-56">
+          <Select options={MODES} value={policy.mode} onChange={e => setPolicy({ mode: e.target.value as ClosingMode })} />
+        </div>
+        <div className="w-32">
+          <Input
+            type="time"
+            value={policy.closingTime.slice(0, 5)}
+            onChange={e => setPolicy({ closingTime: `${e.target.value}:00` })}
+          />
+        </div>
+        <Button variant="outline" onClick={save}>Enregistrer</Button>
+        <Button variant="outline" onClick={() => closeNow(true)}>Fermer maintenant…</Button>
+      </div>
+      {message && <p className="text-sm">{message}</p>}
+      {review && (
+        <div className="space-y-2 border rounded p-3">
+          <div className="font-medium">Changements prévus</div>
+          <MaintenanceDiffTable diff={review} />
+          <div className="flex gap-2">
+            <Button onClick={() => closeNow(false)}>Appliquer</Button>
+            <Button variant="outline" onClick={() => setReview(null)}>Annuler</Button>
+          </div>
+        </div>
+      )}
+
+      <div>
+        <div className="font-medium mb-1">
+          Fermeture du {preview.serviceDate} : {preview.vehicles.length} véhicule(s) en attente
+        </div>
+        {preview.vehicles.length > 0 && <VehicleTable vehicles={preview.vehicles} />}
+      </div>
+
+      <div>
+        <div className="font-medium mb-1">Rapport du matin</div>
+        {!report && <p className="text-sm text-muted-foreground">La file d'hier n'a pas été fermée</p>}
+        {report && (
+          <>
+            <p className="text-sm text-muted-foreground">
+              {report.vehicles.length} véhicule(s) {report.mode === 'PURGE' ? 'retirés de la file' : 'reportés, positions conservées'} le {report.serviceDate}
+            </p>
+            {report.vehicles.length > 0 && <VehicleTable vehicles={report.vehicles} />}
+          </>
+        )}
+      </div>
+    </Card>
+  );
+};
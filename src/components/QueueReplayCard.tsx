@@ -0,0 +1,110 @@
+import React, { useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Select } from './ui/select';
+import { History } from 'lucide-react';
+import { dbClient, DestinationDto, QueueReplay } from '../services/dbClient';
+
+const KINDS: Record<string, string> = {
+  ENTERED: 'Entrée',
+  BOOKED: 'Réservation',
+  CANCELLED: 'Annulation',
+  MOVED: 'Déplacement',
+  UPDATED: 'Mise à jour',
+  DEPARTED: 'Départ',
+  REMOVED: 'Retrait',
+};
+
+// The queue of a destination as it stood at a given moment, to investigate position complaints
+export const QueueReplayCard: React.FC = () => {
+  const [destinations, setDestinations] = useState<DestinationDto[]>([]);
+  const [destinationId, setDestinationId] = useState('');
+  const [at, setAt] = useState('');
+  const [replay, setReplay] = useState<QueueReplay | null>(null);
+  const [showEvents, setShowEvents] = useState(false);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    dbClient.getAvailableDestinations().then(list => {
+      setDestinations(list);
+      if (list.length > 0) setDestinationId(id => id || list[0].stationId);
+    }).catch(e => setError(String(e)));
+  }, []);
+
+  const load = async () => {
+    setError('');
+    try {
+      // datetime-local gives Tunis wall-clock time without seconds
+      setReplay(await dbClient.replayQueueState(destinationId, at));
+    } catch (e) {
+      setReplay(null);
+      setError(String(e));
+    }
+  };
+
+  return (
+    <Card className="p-4 space-y-3">
+      <div className="text-lg font-semibold flex items-center gap-2">
+        <History className="h-5 w-5" />
+        Historique de la file
+      </div>
+      <div className="flex gap-2 items-end">
+        <div className="w-48">
+          <Select
+            options={destinations.map(d => ({ value: d.stationId, label: d.stationName }))}
+            value={destinationId}
+            onChange={e => setDestinationId(e.target.value)}
+          />
+        </div>
+        <div className="w-56">
+          <Input type="datetime-local" value={at} onChange={e => setAt(e.target.value)} />
+        </div>
+        <Button variant="outline" onClick={load} disabled={!destinationId || !at}>Rejouer</Button>
+      </div>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {replay && (
+        <>
+          {replay.entries.length === 0 && <p className="text-sm text-muted-foreground">File vide à ce moment</p>}
+          {replay.entries.length > 0 && (
+            <table className="w-full text-sm">
+              <thead>
+                <tr className="text-left text-muted-foreground">
+                  <th>#</th>
+                  <th>Plaque</th>
+                  <th>Sous-route</th>
+                  <th>Statut</th>
+                  <th className="text-right">Places libres</th>
+                </tr>
+              </thead>
+              <tbody>
+                {replay.entries.map(entry => (
+                  <tr key={entry.queueId} className="border-t">
+                    <td>{entry.queuePosition}</td>
+                    <td className="font-mono">{entry.licensePlate}</td>
+                    <td>{entry.subRoute ?? ''}</td>
+                    <td>{entry.status}</td>
+                    <td className="text-right">{entry.availableSeats}/{entry.totalSeats}</td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          )}
+          <label className="flex items-center gap-2 text-sm">
+            <input type="checkbox" checked={showEvents} onChange={e => setShowEvents(e.target.checked)} />
+            Afficher les {replay.events.length} événement(s)
+          </label>
+          {showEvents && (
+            <ul className="text-xs font-mono space-y-1 max-h-64 overflow-y-auto">
+              {replay.events.map(event => (
+                <li key={event.seq}>
+                  {event.occurredAt.slice(0, 19).replace('T', ' ')} {KINDS[event.kind] ?? event.kind} {event.licensePlate} → {event.destinationName} #{event.queuePosition} ({event.availableSeats}/{event.totalSeats})
+                </li>
+              ))}
+            </ul>
+          )}
+        </>
+      )}
+    </Card>
+  );
+};
@@ -0,0 +1,51 @@
+import React, { useEffect, useState } from 'react';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { ScanLine } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, QuickEntryPolicy } from '../services/dbClient';
+
+// Scan plate → queue: the add-vehicle panel queues a scanned plate for its default destination
+export const QuickEntrySection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [policy, setPolicy] = useState<QuickEntryPolicy | null>(null);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.getQuickEntryPolicy().then(setPolicy).catch(() => setPolicy(null));
+  }, []);
+
+  if (!policy) return null;
+
+  const toggle = async (enabled: boolean) => {
+    try {
+      setPolicy(await dbClient.setQuickEntryPolicy({ ...policy, enabled }, currentStaff?.id));
+      setMessage(enabled ? 'Entrée rapide activée' : 'Entrée rapide désactivée');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <ScanLine className="h-5 w-5" />
+          <span>Entrée rapide en file</span>
+        </CardTitle>
+        <CardDescription>
+          Dans le panneau d'ajout de véhicule, une plaque scannée ou saisie puis validée par Entrée met le véhicule
+          dans la file de sa destination par défaut, sans autre étape. La destination par défaut doit faire partie
+          de ses destinations autorisées.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <label className="flex items-center gap-2">
+          <input type="checkbox" checked={policy.enabled} disabled={!isSupervisor} onChange={e => toggle(e.target.checked)} />
+          <span>Plaque scannée → file de la destination par défaut</span>
+        </label>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,116 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Copy } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, ReprintPolicy, TicketReprint, VoidedTicket } from '../services/dbClient';
+
+// Reprint limit and fee per ticket, with today's reprints; supervisors change the rules.
+// A spoiled ticket's number is voided here; the next reprint of it prints under a fresh number.
+export const ReprintPolicySection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [policy, setPolicy] = useState<ReprintPolicy | null>(null);
+  const [reprints, setReprints] = useState<TicketReprint[]>([]);
+  const [message, setMessage] = useState('');
+  const [voidKind, setVoidKind] = useState<VoidedTicket['ticketKind']>('ENTRY');
+  const [voidNumber, setVoidNumber] = useState('');
+  const [voidReason, setVoidReason] = useState('');
+
+  useEffect(() => {
+    dbClient.getReprintPolicy().then(setPolicy).catch(() => setPolicy(null));
+    dbClient.getTicketReprints().then(setReprints).catch(() => setReprints([]));
+  }, []);
+
+  if (!policy) return null;
+
+  const save = async () => {
+    try {
+      setPolicy(await dbClient.setReprintPolicy(policy, currentStaff?.id));
+      setMessage('Règles enregistrées');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const voidTicket = async () => {
+    try {
+      const voided = await dbClient.voidTicketNumber(voidKind, voidNumber, voidReason, currentStaff?.id);
+      setMessage(`Numéro ${voided.ticketNumber} annulé : réimprimez le ticket pour lui donner un nouveau numéro`);
+      setVoidNumber('');
+      setVoidReason('');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const totalFees = reprints.reduce((sum, r) => sum + r.fee, 0);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Copy className="h-5 w-5" />
+          <span>Réimpressions</span>
+        </CardTitle>
+        <CardDescription>
+          Nombre de duplicatas par ticket et frais par réimpression
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex gap-4 items-end">
+          <div>
+            <Label htmlFor="reprint-max">Duplicatas par ticket (vide: sans limite)</Label>
+            <Input
+              id="reprint-max"
+              type="number"
+              min="0"
+              value={policy.maxReprints ?? ''}
+              disabled={!isSupervisor}
+              onChange={e => setPolicy({ ...policy, maxReprints: e.target.value === '' ? null : Number(e.target.value) })}
+            />
+          </div>
+          <div>
+            <Label htmlFor="reprint-fee">Frais par réimpression (TND)</Label>
+            <Input
+              id="reprint-fee"
+              type="number"
+              min="0"
+              step="0.1"
+              value={policy.fee}
+              disabled={!isSupervisor}
+              onChange={e => setPolicy({ ...policy, fee: Number(e.target.value) })}
+            />
+          </div>
+          {isSupervisor && <Button variant="outline" onClick={save}>Enregistrer</Button>}
+        </div>
+        <p className="text-sm text-muted-foreground">
+          Réimpressions aujourd'hui : {reprints.length} ({totalFees.toFixed(3)} TND de frais)
+        </p>
+        <div className="flex gap-2 items-end">
+          <div>
+            <Label htmlFor="void-kind">Ticket abîmé</Label>
+            <select
+              id="void-kind"
+              className="border rounded px-2 py-2 text-sm"
+              value={voidKind}
+              onChange={e => setVoidKind(e.target.value as VoidedTicket['ticketKind'])}
+            >
+              <option value="ENTRY">Entrée</option>
+              <option value="EXIT_PASS">Sortie</option>
+              <option value="DAY_PASS">Pass journalier</option>
+            </select>
+          </div>
+          <Input placeholder="Numéro" value={voidNumber} onChange={e => setVoidNumber(e.target.value)} />
+          <Input placeholder="Motif" value={voidReason} onChange={e => setVoidReason(e.target.value)} />
+          <Button variant="outline" onClick={voidTicket} disabled={!voidNumber.trim() || !voidReason.trim()}>
+            Annuler le numéro
+          </Button>
+        </div>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,100 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Eraser } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, RetentionPolicy, RetentionReport, RetentionTable } from '../services/dbClient';
+
+const TABLES: { table: RetentionTable; label: string }[] = [
+  { table: 'exit_passes', label: 'Tickets de sortie' },
+  { table: 'audit_log', label: "Journal d'audit" },
+  { table: 'queue_events', label: 'Journal de la file' },
+  { table: 'printed_tickets', label: "Historique d'impression" },
+];
+
+// Days each growing table keeps, purged every night; supervisors change them and can purge now
+export const RetentionSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [policy, setPolicy] = useState<RetentionPolicy | null>(null);
+  const [report, setReport] = useState<RetentionReport | null>(null);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.getRetentionPolicy().then(setPolicy).catch(() => setPolicy(null));
+  }, []);
+
+  if (!policy) return null;
+
+  const setDays = (table: RetentionTable, value: string) => {
+    const days = { ...policy.days };
+    if (value === '') delete days[table];
+    else days[table] = Number(value);
+    setPolicy({ ...policy, days });
+  };
+
+  const save = async () => {
+    try {
+      setPolicy(await dbClient.setRetentionPolicy(policy, currentStaff?.id));
+      setMessage('Durées de conservation enregistrées');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const run = async (dryRun: boolean) => {
+    try {
+      setReport(await dbClient.runRetentionNow(dryRun, currentStaff?.id));
+      setMessage('');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Eraser className="h-5 w-5" />
+          <span>Conservation des données</span>
+        </CardTitle>
+        <CardDescription>
+          Jours conservés par table (vide : sans limite), purgés chaque nuit après {policy.runAt.slice(0, 5)}
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="grid grid-cols-2 gap-4">
+          {TABLES.map(({ table, label }) => (
+            <div key={table}>
+              <Label htmlFor={`retention-${table}`}>{label}</Label>
+              <Input
+                id={`retention-${table}`}
+                type="number"
+                min="30"
+                value={policy.days[table] ?? ''}
+                disabled={!isSupervisor}
+                onChange={e => setDays(table, e.target.value)}
+              />
+            </div>
+          ))}
+        </div>
+        {isSupervisor && (
+          <div className="flex gap-2">
+            <Button variant="outline" onClick={save}>Enregistrer</Button>
+            <Button variant="outline" onClick={() => run(true)}>Simuler la purge</Button>
+            <Button variant="destructive" onClick={() => run(false)}>Purger maintenant</Button>
+          </div>
+        )}
+        {report && (
+          <p className="text-sm">
+            {report.dryRun ? 'Seraient purgées' : 'Purgées'} : {report.totalRows} ligne(s)
+            {report.tables.map(t => ` · ${TABLES.find(x => x.table === t.table)?.label ?? t.table} ${t.rows}`).join('')}
+          </p>
+        )}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
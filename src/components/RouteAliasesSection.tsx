@@ -0,0 +1,91 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Tags } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, DestinationMatch } from '../services/dbClient';
+
+// Other names cashiers type for a destination, found by the booking screen's search
+export const RouteAliasesSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [query, setQuery] = useState('');
+  const [matches, setMatches] = useState<DestinationMatch[]>([]);
+  const [route, setRoute] = useState<DestinationMatch | null>(null);
+  const [aliases, setAliases] = useState('');
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    if (!query.trim()) {
+      setMatches([]);
+      return;
+    }
+    const timer = setTimeout(() => {
+      dbClient.searchDestinations(query).then(setMatches).catch(() => setMatches([]));
+    }, 200);
+    return () => clearTimeout(timer);
+  }, [query]);
+
+  const choose = async (match: DestinationMatch) => {
+    setRoute(match);
+    setMessage('');
+    try {
+      setAliases((await dbClient.getRouteAliases(match.stationId)).join(', '));
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const save = async () => {
+    if (!route) return;
+    try {
+      const saved = await dbClient.setRouteAliases(route.stationId, aliases.split(','), currentStaff?.id);
+      setAliases(saved.join(', '));
+      setMessage(`Alias de ${route.stationName} enregistrés`);
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Tags className="h-5 w-5" />
+          <span>Alias des destinations</span>
+        </CardTitle>
+        <CardDescription>
+          La recherche de destination ignore les accents et la casse, comprend l'arabe et tolère une faute de frappe.
+          Les alias ajoutent d'autres noms, par exemple « sfax » pour « Station Sfax Ville ».
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <Input placeholder="Rechercher une destination" value={query} onChange={e => setQuery(e.target.value)} />
+        {matches.length > 0 && (
+          <div className="flex flex-wrap gap-2">
+            {matches.map(match => (
+              <Button
+                key={match.stationId}
+                size="sm"
+                variant={route?.stationId === match.stationId ? 'default' : 'outline'}
+                onClick={() => choose(match)}
+              >
+                {match.stationName}{match.matched !== match.stationName ? ` · ${match.matched}` : ''}
+              </Button>
+            ))}
+          </div>
+        )}
+        {route && (
+          <div>
+            <Label htmlFor="route-aliases">Alias de {route.stationName}, séparés par des virgules</Label>
+            <Input id="route-aliases" value={aliases} disabled={!isSupervisor} onChange={e => setAliases(e.target.value)} />
+          </div>
+        )}
+        {route && isSupervisor && <Button variant="outline" onClick={save}>Enregistrer</Button>}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,89 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Dialog, DialogContent, DialogDescription, DialogFooter, DialogHeader, DialogTitle } from './ui/dialog';
+import { dbClient, QueueItemDto, SeatTransfer } from '../services/dbClient';
+
+interface SeatTransferDialogProps {
+  source: QueueItemDto | null;
+  onDone: (transfer: SeatTransfer) => void;
+  onCancel: () => void;
+}
+
+// Seats from one vehicle to another of the same line, chosen by the cashier
+export const SeatTransferDialog: React.FC<SeatTransferDialogProps> = ({ source, onDone, onCancel }) => {
+  const [targets, setTargets] = useState<QueueItemDto[]>([]);
+  const [targetId, setTargetId] = useState('');
+  const [seats, setSeats] = useState(1);
+  const [error, setError] = useState('');
+  const [busy, setBusy] = useState(false);
+  const booked = source ? source.totalSeats - source.availableSeats : 0;
+
+  useEffect(() => {
+    if (!source) return;
+    setError('');
+    setSeats(source.totalSeats - source.availableSeats);
+    dbClient.getQueueByDestination(source.destinationId).then(queue => {
+      const sameLine = queue.filter(q =>
+        q.id !== source.id && (q.subRoute ?? null) === (source.subRoute ?? null) && q.availableSeats > 0
+      );
+      setTargets(sameLine);
+      setTargetId(sameLine[0]?.id ?? '');
+    }).catch(e => setError(String(e)));
+  }, [source]);
+
+  const target = targets.find(t => t.id === targetId);
+
+  const confirm = async () => {
+    if (!source || !target) return;
+    setBusy(true);
+    setError('');
+    try {
+      onDone(await dbClient.transferSeats(source.id, target.id, seats));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <Dialog open={source !== null} onOpenChange={isOpen => !isOpen && onCancel()}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>Transférer des sièges</DialogTitle>
+          {source && (
+            <DialogDescription>
+              {source.licensePlate} : {booked} siège(s) réservé(s). Les tickets transférés sont réimprimés avec la nouvelle plaque.
+            </DialogDescription>
+          )}
+        </DialogHeader>
+        <div className="space-y-3">
+          <select
+            className="w-full border rounded px-2 py-2 text-sm"
+            value={targetId}
+            onChange={e => setTargetId(e.target.value)}
+          >
+            {targets.map(t => (
+              <option key={t.id} value={t.id}>
+                #{t.queuePosition} {t.licensePlate} ({t.availableSeats} libre(s))
+              </option>
+            ))}
+          </select>
+          {targets.length === 0 && <p className="text-sm text-muted-foreground">Aucun autre véhicule avec des places libres sur cette ligne</p>}
+          <Input type="number" min={1} max={booked} value={seats} onChange={e => setSeats(Number(e.target.value))} />
+          {error && <p className="text-sm text-red-600">❌ {error}</p>}
+        </div>
+        <DialogFooter>
+          <Button variant="outline" onClick={onCancel}>Annuler</Button>
+          <Button
+            onClick={confirm}
+            disabled={busy || !target || seats < 1 || seats > booked || seats > (target?.availableSeats ?? 0)}
+          >
+            Transférer
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
@@ -0,0 +1,77 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Key } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, SecretName } from '../services/dbClient';
+
+const SECRETS: { name: SecretName; label: string }[] = [
+  { name: 'DATABASE_PASSWORD', label: 'Mot de passe de la base de données' },
+  { name: 'SMS_GATEWAY_TOKEN', label: 'Jeton de la passerelle SMS' },
+];
+
+// Passwords and tokens kept in the OS credential store instead of .env; supervisors only
+export const SecretsSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [stored, setStored] = useState<Partial<Record<SecretName, boolean>>>({});
+  const [values, setValues] = useState<Partial<Record<SecretName, string>>>({});
+  const [message, setMessage] = useState('');
+
+  const load = () =>
+    Promise.all(SECRETS.map(({ name }) => dbClient.getSecret(name, currentStaff?.id).then(v => [name, v != null] as const)))
+      .then(entries => setStored(Object.fromEntries(entries)))
+      .catch(e => setMessage(`❌ ${e}`));
+
+  useEffect(() => {
+    if (isSupervisor) load();
+  }, [isSupervisor]);
+
+  if (!isSupervisor) return null;
+
+  const save = async (name: SecretName, secret: string) => {
+    try {
+      await dbClient.setSecret(name, secret, currentStaff?.id);
+      setValues({ ...values, [name]: '' });
+      setMessage(secret ? '✅ Enregistré dans le coffre du système' : '✅ Supprimé du coffre du système');
+      await load();
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Key className="h-5 w-5" />
+          <span>Mots de passe et jetons</span>
+        </CardTitle>
+        <CardDescription>
+          Conservés dans le coffre du système (Gestionnaire d'identification Windows). Un nouveau mot de passe de base de données est utilisé au prochain démarrage.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {SECRETS.map(({ name, label }) => (
+          <div key={name} className="space-y-1">
+            <div className="text-sm font-medium">
+              {label} · <span className="text-muted-foreground">{stored[name] ? 'enregistré' : 'absent'}</span>
+            </div>
+            <div className="flex gap-2">
+              <Input
+                type="password"
+                autoComplete="off"
+                value={values[name] ?? ''}
+                onChange={e => setValues({ ...values, [name]: e.target.value })}
+              />
+              <Button onClick={() => save(name, values[name] ?? '')} disabled={!values[name]}>Enregistrer</Button>
+              <Button variant="outline" onClick={() => save(name, '')} disabled={!stored[name]}>Supprimer</Button>
+            </div>
+          </div>
+        ))}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,112 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Key } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, SelfCheckInPolicy } from '../services/dbClient';
+
+// Driver self check-in at the kiosk: supervisors turn it on, name the gate printer and give
+// drivers their PINs
+export const SelfCheckInSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [policy, setPolicy] = useState<SelfCheckInPolicy | null>(null);
+  const [plate, setPlate] = useState('');
+  const [pin, setPin] = useState('');
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.getSelfCheckInPolicy().then(setPolicy).catch(() => setPolicy(null));
+  }, []);
+
+  if (!policy) return null;
+
+  const save = async () => {
+    try {
+      setPolicy(await dbClient.setSelfCheckInPolicy(policy, currentStaff?.id));
+      setMessage('Enregistrement en libre-service mis à jour');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const savePin = async (value: string | null) => {
+    try {
+      await dbClient.setDriverPin(plate, value, currentStaff?.id);
+      setMessage(value ? `Code PIN du chauffeur de ${plate} enregistré` : `Code PIN du chauffeur de ${plate} supprimé`);
+      setPin('');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Key className="h-5 w-5" />
+          <span>Enregistrement des chauffeurs à la borne</span>
+        </CardTitle>
+        <CardDescription>
+          Le chauffeur saisit son matricule et son code PIN ; le ticket d'entrée sort à l'imprimante du portail.
+          Un véhicule sans pass du jour est renvoyé au guichet.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={policy.enabled}
+            disabled={!isSupervisor}
+            onChange={e => setPolicy({ ...policy, enabled: e.target.checked })}
+          />
+          <span>Activer l'enregistrement en libre-service</span>
+        </label>
+        <div className="grid grid-cols-2 gap-4">
+          <div>
+            <Label htmlFor="gate-printer-ip">Imprimante du portail (IP)</Label>
+            <Input
+              id="gate-printer-ip"
+              placeholder="Imprimante de la borne"
+              value={policy.gatePrinterIp ?? ''}
+              disabled={!isSupervisor}
+              onChange={e => setPolicy({ ...policy, gatePrinterIp: e.target.value.trim() || null })}
+            />
+          </div>
+          <div>
+            <Label htmlFor="gate-printer-port">Port</Label>
+            <Input
+              id="gate-printer-port"
+              type="number"
+              value={policy.gatePrinterPort}
+              disabled={!isSupervisor}
+              onChange={e => setPolicy({ ...policy, gatePrinterPort: Number(e.target.value) })}
+            />
+          </div>
+        </div>
+        {isSupervisor && (
+          <>
+            <Button variant="outline" onClick={save}>Enregistrer</Button>
+            <div className="grid grid-cols-3 items-end gap-4">
+              <div>
+                <Label htmlFor="driver-plate">Matricule</Label>
+                <Input id="driver-plate" value={plate} onChange={e => setPlate(e.target.value)} />
+              </div>
+              <div>
+                <Label htmlFor="driver-pin">Code PIN du chauffeur</Label>
+                <Input id="driver-pin" type="password" inputMode="numeric" maxLength={6} value={pin} onChange={e => setPin(e.target.value)} />
+              </div>
+              <div className="flex gap-2">
+                <Button variant="outline" disabled={!plate || !pin} onClick={() => savePin(pin)}>Définir</Button>
+                <Button variant="outline" disabled={!plate} onClick={() => savePin(null)}>Supprimer</Button>
+              </div>
+            </div>
+          </>
+        )}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
@@ -0,0 +1,90 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Keyboard, Save } from 'lucide-react';
+import { dbClient, ShortcutAction, ShortcutSettings } from '../services/dbClient';
+
+const ACTIONS: { action: ShortcutAction; label: string }[] = [
+  { action: 'TOGGLE_FULLSCREEN', label: 'Basculer plein écran' },
+  { action: 'TOGGLE_WINDOW', label: 'Afficher / masquer' },
+  { action: 'REPRINT_BOOKING_TICKET', label: 'Réimprimer le dernier ticket' },
+  { action: 'REPRINT_ENTRY_TICKET', label: 'Réimprimer la dernière entrée' },
+  { action: 'REPRINT_EXIT_TICKET', label: 'Réimprimer la dernière sortie' },
+  { action: 'REPRINT_EXIT_PASS', label: 'Réimprimer la dernière autorisation de sortie' },
+  { action: 'REPRINT_DAY_PASS', label: 'Réimprimer le dernier pass' },
+];
+
+export const ShortcutsSection: React.FC = () => {
+  const [settings, setSettings] = useState<ShortcutSettings | null>(null);
+  const [drafts, setDrafts] = useState<Partial<Record<ShortcutAction, string>>>({});
+  const [message, setMessage] = useState('');
+
+  const apply = (next: ShortcutSettings) => {
+    setSettings(next);
+    setDrafts(Object.fromEntries(next.shortcuts.map(s => [s.action, s.accelerator])));
+  };
+
+  useEffect(() => {
+    dbClient.getShortcuts().then(apply).catch(e => setMessage(String(e)));
+  }, []);
+
+  const saveShortcut = async (action: ShortcutAction) => {
+    try {
+      apply(await dbClient.setShortcut(action, drafts[action]?.trim() || undefined));
+      setMessage('✅ Raccourci enregistré');
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  const toggleTrayAction = async (action: ShortcutAction) => {
+    if (!settings) return;
+    const current = settings.trayActions;
+    const next = current.includes(action) ? current.filter(a => a !== action) : [...current, action];
+    try {
+      apply(await dbClient.setTrayActions(next));
+    } catch (e) {
+      setMessage(`❌ ${e}`);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Keyboard className="h-5 w-5" />
+          <span>Raccourcis et menu de la barre des tâches</span>
+        </CardTitle>
+        <CardDescription>
+          Laissez le champ vide pour désactiver un raccourci (ex. F11, CommandOrControl+Shift+H)
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {ACTIONS.map(({ action, label }) => (
+          <div key={action} className="flex items-center gap-3">
+            <span className="flex-1 text-sm">{label}</span>
+            <Input
+              className="w-64 font-mono"
+              value={drafts[action] ?? ''}
+              onChange={e => setDrafts({ ...drafts, [action]: e.target.value })}
+              placeholder="Aucun"
+            />
+            <Button variant="outline" size="sm" onClick={() => saveShortcut(action)}>
+              <Save className="h-4 w-4" />
+            </Button>
+            <label className="flex items-center gap-1 text-xs text-muted-foreground">
+              <input
+                type="checkbox"
+                checked={settings?.trayActions.includes(action) ?? false}
+                onChange={() => toggleTrayAction(action)}
+              />
+              Menu
+            </label>
+          </div>
+        ))}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
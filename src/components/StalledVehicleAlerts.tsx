@@ -0,0 +1,28 @@
+import React, { useEffect } from 'react';
+import { useAuth } from '../context/AuthProvider';
+import { useNotifications } from '../context/NotificationProvider';
+import { dbClient } from '../services/dbClient';
+
+// Tells supervisors when a vehicle has been loading past its destination's timeout
+export const StalledVehicleAlerts: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const { addNotification } = useNotifications();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+
+  useEffect(() => {
+    if (!isSupervisor) return;
+    const unlisten = dbClient.onVehicleStalled(vehicle => {
+      addNotification({
+        type: 'warning',
+        title: 'Véhicule bloqué en chargement',
+        message: `${vehicle.message}. Libérez-le depuis la gestion des véhicules.`,
+        autoClose: false,
+      });
+    });
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, [isSupervisor, addNotification]);
+
+  return null;
+};
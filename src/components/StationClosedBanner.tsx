@@ -0,0 +1,61 @@
+import React, { useEffect, useState } from 'react';
+import { Moon } from 'lucide-react';
+import { Button } from './ui/button';
+import { SupervisorOverrideDialog } from './SupervisorOverrideDialog';
+import { BusinessHoursStatus, dbClient, SignOff } from '../services/dbClient';
+
+// Shown outside business hours, when booking and queue entry are refused; a supervisor can let
+// this terminal sell for an hour, with their PIN or from the approval inbox
+export const StationClosedBanner: React.FC = () => {
+  const [status, setStatus] = useState<BusinessHoursStatus | null>(null);
+  const [unlocking, setUnlocking] = useState(false);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    dbClient.getBusinessHoursStatus().then(setStatus).catch(() => {});
+    const unlisten = dbClient.onBusinessHoursChanged(setStatus);
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  const unlock = async (signOff: SignOff) => {
+    setUnlocking(false);
+    setError('');
+    try {
+      setStatus(await dbClient.unlockAfterHours(signOff.supervisorOverride, signOff.approvalId));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  if (!status || status.open) return null;
+
+  const hours = `${status.hours.opensAt.slice(0, 5)} – ${status.hours.closesAt.slice(0, 5)}`;
+
+  return (
+    <div className="fixed top-2 left-1/2 z-40 -translate-x-1/2 rounded-lg border border-indigo-300 bg-indigo-50 px-4 py-2 shadow">
+      <div className="flex items-center gap-3 text-sm text-indigo-900">
+        <Moon className="h-4 w-4" />
+        {status.unlockedUntil ? (
+          <span>Station fermée · vente hors horaires autorisée jusqu'à {status.unlockedUntil.slice(11, 16)}</span>
+        ) : (
+          <>
+            <span className="font-semibold">Station fermée</span>
+            <span>· horaires {hours}</span>
+            <Button size="sm" variant="outline" onClick={() => setUnlocking(true)}>Vendre hors horaires</Button>
+          </>
+        )}
+      </div>
+      {error && <p className="text-sm text-red-600">{error}</p>}
+      <SupervisorOverrideDialog
+        open={unlocking}
+        title="Vente hors horaires"
+        description="Autorise ce poste à vendre et à enregistrer des véhicules pendant une heure."
+        approval={status.terminalId ? { action: 'AFTER_HOURS', subject: status.terminalId } : undefined}
+        onConfirm={unlock}
+        onCancel={() => setUnlocking(false)}
+      />
+    </div>
+  );
+};
@@ -0,0 +1,111 @@
+import React, { useEffect, useRef, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Dialog, DialogContent, DialogDescription, DialogFooter, DialogHeader, DialogTitle } from './ui/dialog';
+import { dbClient, OverrideAction, OverrideReasonCode, SignOff } from '../services/dbClient';
+import { useAuth } from '../context/AuthProvider';
+
+const REASONS: { code: OverrideReasonCode; label: string }[] = [
+  { code: 'CUSTOMER_REQUEST', label: 'Demande du client' },
+  { code: 'VEHICLE_BREAKDOWN', label: 'Panne du véhicule' },
+  { code: 'CASHIER_ERROR', label: 'Erreur de caisse' },
+  { code: 'TARIFF_CHANGE', label: 'Changement de tarif' },
+  { code: 'OTHER', label: 'Autre' },
+];
+
+interface SupervisorOverrideDialogProps {
+  open: boolean;
+  title: string;
+  description?: string;
+  // What a remote request would be for; without it only the PIN is offered
+  approval?: { action: OverrideAction; subject: string; details?: Record<string, unknown> };
+  onConfirm: (signOff: SignOff) => void;
+  onCancel: () => void;
+}
+
+// Supervisor PIN, reason code and note for an action a cashier may not take alone, or a request
+// sent to the supervisors' approval inbox, waited for here
+export const SupervisorOverrideDialog: React.FC<SupervisorOverrideDialogProps> = ({ open, title, description, approval, onConfirm, onCancel }) => {
+  const { currentStaff } = useAuth();
+  const [pin, setPin] = useState('');
+  const [reasonCode, setReasonCode] = useState<OverrideReasonCode>(REASONS[0].code);
+  const [note, setNote] = useState('');
+  const [waitingFor, setWaitingFor] = useState<string | null>(null);
+  const [requestError, setRequestError] = useState<string | null>(null);
+  // The decision may come after a re-render; the listener stays put and calls the latest handler
+  const onConfirmRef = useRef(onConfirm);
+  onConfirmRef.current = onConfirm;
+
+  useEffect(() => {
+    if (open) {
+      setPin('');
+      setReasonCode(REASONS[0].code);
+      setNote('');
+      setWaitingFor(null);
+      setRequestError(null);
+    }
+  }, [open]);
+
+  useEffect(() => {
+    if (!waitingFor) return;
+    const unlisten = dbClient.onApprovalDecided(request => {
+      if (request.id !== waitingFor) return;
+      setWaitingFor(null);
+      if (request.status === 'APPROVED') {
+        onConfirmRef.current({ approvalId: request.id });
+      } else {
+        setRequestError(`Refusé par ${request.decidedByName ?? 'le superviseur'}${request.decisionNote ? `: ${request.decisionNote}` : ''}`);
+      }
+    });
+    return () => { unlisten.then(fn => fn()); };
+  }, [waitingFor]);
+
+  const requestRemotely = async () => {
+    if (!approval) return;
+    setRequestError(null);
+    try {
+      const request = await dbClient.requestApproval({ ...approval, reasonCode, note }, currentStaff?.id);
+      setWaitingFor(request.id);
+    } catch (error: any) {
+      setRequestError(typeof error === 'string' ? error : error?.message || 'Demande impossible');
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={isOpen => !isOpen && onCancel()}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>{title}</DialogTitle>
+          {description && <DialogDescription>{description}</DialogDescription>}
+        </DialogHeader>
+        <div className="space-y-3">
+          <select
+            className="w-full border rounded px-2 py-2 text-sm"
+            value={reasonCode}
+            onChange={e => setReasonCode(e.target.value as OverrideReasonCode)}
+            disabled={waitingFor !== null}
+          >
+            {REASONS.map(({ code, label }) => (
+              <option key={code} value={code}>{label}</option>
+            ))}
+          </select>
+          <Input placeholder="Note (obligatoire)" value={note} onChange={e => setNote(e.target.value)} disabled={waitingFor !== null} />
+          <Input type="password" inputMode="numeric" placeholder="Code PIN superviseur" value={pin} onChange={e => setPin(e.target.value)} disabled={waitingFor !== null} />
+          {waitingFor && <p className="text-sm text-muted-foreground">En attente de la décision d'un superviseur…</p>}
+          {requestError && <p className="text-sm text-red-600">{requestError}</p>}
+        </div>
+        <DialogFooter>
+          <Button variant="outline" onClick={onCancel}>Annuler</Button>
+          {approval && (
+            <Button variant="secondary" onClick={requestRemotely} disabled={!note.trim() || waitingFor !== null}>
+              Demander à distance
+            </Button>
+          )}
+          <Button variant="destructive" onClick={() => onConfirm({ supervisorOverride: { pin, reasonCode, note } })} disabled={!pin || !note.trim() || waitingFor !== null}>
+            Autoriser
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
@@ -0,0 +1,106 @@
+import React, { useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Select } from './ui/select';
+import { Send } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, MessagePriority, Terminal, TerminalMessage } from '../services/dbClient';
+
+const MAX_CHARS = 280;
+
+// Send a message to another terminal and follow which of today's were acknowledged
+export const TerminalMessageCard: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const [terminals, setTerminals] = useState<Terminal[]>([]);
+  const [ownId, setOwnId] = useState('');
+  const [target, setTarget] = useState('');
+  const [text, setText] = useState('');
+  const [priority, setPriority] = useState<MessagePriority>('NORMAL');
+  const [sent, setSent] = useState<TerminalMessage[]>([]);
+  const [error, setError] = useState('');
+
+  const refresh = () => {
+    dbClient.getTerminalMessages().then(m => setSent(m.sent)).catch(e => setError(String(e)));
+  };
+
+  useEffect(() => {
+    dbClient.getTerminalInfo().then(info => setOwnId(info.terminalId)).catch(() => {});
+    dbClient.getTerminals().then(setTerminals).catch(e => setError(String(e)));
+    refresh();
+    const unlisten = dbClient.onTerminalMessageAcknowledged(() => refresh());
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  const others = terminals.filter(t => t.id !== ownId);
+
+  const send = async () => {
+    setError('');
+    try {
+      await dbClient.sendTerminalMessage(target, text, priority, currentStaff?.id);
+      setText('');
+      refresh();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <Card className="p-4 space-y-3">
+      <div className="text-lg font-semibold flex items-center gap-2">
+        <Send className="h-5 w-5" />
+        Messages aux postes
+      </div>
+      <div className="flex gap-2 items-end">
+        <div className="w-48">
+          <Select
+            options={[{ value: '', label: 'Poste…' }, ...others.map(t => ({ value: t.id, label: t.name }))]}
+            value={target}
+            onChange={e => setTarget(e.target.value)}
+          />
+        </div>
+        <div className="w-32">
+          <Select
+            options={[{ value: 'NORMAL', label: 'Normal' }, { value: 'URGENT', label: 'Urgent' }]}
+            value={priority}
+            onChange={e => setPriority(e.target.value as MessagePriority)}
+          />
+        </div>
+      </div>
+      <div className="flex gap-2">
+        <Input
+          value={text}
+          maxLength={MAX_CHARS}
+          placeholder="Ex: arrêter la vente pour Sfax, dernier véhicule au départ"
+          onChange={e => setText(e.target.value)}
+        />
+        <Button onClick={send} disabled={!target || !text.trim()}>Envoyer</Button>
+      </div>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {sent.length > 0 && (
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-muted-foreground">
+              <th>Heure</th>
+              <th>Poste</th>
+              <th>Message</th>
+              <th>Lu</th>
+            </tr>
+          </thead>
+          <tbody>
+            {sent.map(m => (
+              <tr key={m.id} className="border-t">
+                <td>{m.sentAt.slice(11, 16)}</td>
+                <td>{m.targetTerminalName}</td>
+                <td className={m.priority === 'URGENT' ? 'font-semibold text-red-600' : ''}>{m.text}</td>
+                <td>{m.acknowledgedAt ? `✅ ${m.acknowledgedAt.slice(11, 16)}${m.acknowledgedByName ? ` · ${m.acknowledgedByName}` : ''}` : '⏳'}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+    </Card>
+  );
+};
@@ -0,0 +1,73 @@
+import React, { useCallback, useEffect, useState } from 'react';
+import { MessageSquare } from 'lucide-react';
+import { Button } from './ui/button';
+import { useAuth } from '../context/AuthProvider';
+import { useNotifications } from '../context/NotificationProvider';
+import { dbClient, TerminalMessage } from '../services/dbClient';
+
+// Messages sent to this terminal, kept on screen until someone acknowledges them; the sender
+// is told when they are
+export const TerminalMessageInbox: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const { addNotification } = useNotifications();
+  const [inbox, setInbox] = useState<TerminalMessage[]>([]);
+  const [error, setError] = useState('');
+
+  const refresh = useCallback(() => {
+    dbClient.getTerminalMessages().then(m => setInbox(m.inbox)).catch(() => {});
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    const unlistenMessage = dbClient.onTerminalMessage(message => {
+      setInbox(current => current.some(m => m.id === message.id) ? current : [...current, message]);
+    });
+    const unlistenAck = dbClient.onTerminalMessageAcknowledged(message => {
+      addNotification({
+        type: 'success',
+        title: `Lu par ${message.targetTerminalName}`,
+        message: `${message.text}${message.acknowledgedByName ? ` · ${message.acknowledgedByName}` : ''}`,
+      });
+    });
+    return () => {
+      unlistenMessage.then(f => f());
+      unlistenAck.then(f => f());
+    };
+  }, [refresh, addNotification]);
+
+  const acknowledge = async (id: string) => {
+    setError('');
+    try {
+      await dbClient.acknowledgeTerminalMessage(id, currentStaff?.id);
+      setInbox(current => current.filter(m => m.id !== id));
+    } catch (e) {
+      setError(String(e));
+      refresh();
+    }
+  };
+
+  if (inbox.length === 0) return null;
+
+  return (
+    <div className="fixed top-4 right-4 z-50 w-96 space-y-2">
+      {inbox.map(message => (
+        <div
+          key={message.id}
+          className={`rounded-lg border p-3 shadow-lg bg-white dark:bg-gray-900 ${
+            message.priority === 'URGENT' ? 'border-red-500 ring-2 ring-red-300' : 'border-blue-300'
+          }`}
+        >
+          <div className="flex items-center gap-2 text-sm font-semibold">
+            <MessageSquare className="h-4 w-4" />
+            {message.priority === 'URGENT' ? 'Urgent · ' : ''}{message.fromTerminalName}
+            {message.sentByName && <span className="font-normal text-muted-foreground">({message.sentByName})</span>}
+            <span className="ml-auto font-normal text-xs text-muted-foreground">{message.sentAt.slice(11, 16)}</span>
+          </div>
+          <p className="my-2 text-base">{message.text}</p>
+          <Button size="sm" onClick={() => acknowledge(message.id)}>Bien reçu</Button>
+        </div>
+      ))}
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+    </div>
+  );
+};
@@ -0,0 +1,44 @@
+import React, { useEffect, useState } from 'react';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Monitor } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, Terminal, TerminalInfo } from '../services/dbClient';
+
+// This PC's terminal id, stamped on its bookings and passes; supervisors also see the others
+export const TerminalSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [info, setInfo] = useState<TerminalInfo | null>(null);
+  const [terminals, setTerminals] = useState<Terminal[]>([]);
+
+  useEffect(() => {
+    dbClient.getTerminalInfo().then(setInfo).catch(() => setInfo(null));
+    if (isSupervisor) {
+      dbClient.getTerminals().then(setTerminals).catch(() => setTerminals([]));
+    }
+  }, [isSupervisor]);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Monitor className="h-5 w-5" />
+          <span>Poste de caisse</span>
+        </CardTitle>
+        <CardDescription>
+          {info ? <>{info.name} · <span className="font-mono">{info.terminalId}</span></> : 'Identifiant indisponible'}
+        </CardDescription>
+      </CardHeader>
+      {isSupervisor && terminals.length > 0 && (
+        <CardContent className="space-y-1 text-sm">
+          {terminals.map(t => (
+            <div key={t.id} className="flex justify-between border-b pb-1">
+              <span className={t.id === info?.terminalId ? 'font-semibold' : ''}>{t.name}</span>
+              <span className="text-muted-foreground">v{t.appVersion} · vu {t.lastSeenAt}</span>
+            </div>
+          ))}
+        </CardContent>
+      )}
+    </Card>
+  );
+};
@@ -0,0 +1,103 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { Inbox } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, NoPrinterMode, PrintedTicket } from '../services/dbClient';
+
+// Printer-less mode: tickets are kept unprinted, then printed in bulk or texted to the customer
+export const TicketEscrowCard: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [mode, setMode] = useState<NoPrinterMode | null>(null);
+  const [tickets, setTickets] = useState<PrintedTicket[]>([]);
+  const [phones, setPhones] = useState<Record<string, string>>({});
+  const [message, setMessage] = useState('');
+
+  const load = async () => {
+    try {
+      setTickets(await dbClient.getEscrowedTickets());
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  useEffect(() => {
+    dbClient.getNoPrinterMode().then(setMode).catch(() => setMode(null));
+    load();
+  }, []);
+
+  if (!mode) return null;
+
+  const toggle = async (enabled: boolean) => {
+    try {
+      setMode(await dbClient.setNoPrinterMode({ ...mode, enabled }, currentStaff?.id));
+      setMessage(enabled ? 'Mode sans imprimante activé' : 'Mode sans imprimante désactivé');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const printAll = async () => {
+    try {
+      const count = await dbClient.printEscrowedTickets();
+      setMessage(count ? `${count} ticket(s) envoyé(s) à l'imprimante` : 'Aucun ticket en attente');
+      await load();
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const sendSms = async (ticket: PrintedTicket) => {
+    try {
+      await dbClient.sendEscrowedTicketSms(ticket.id, phones[ticket.id] ?? '');
+      setMessage(`Ticket ${ticket.ticketNumber ?? ''} envoyé par SMS`);
+      await load();
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Inbox className="h-5 w-5" />
+          <span>Mode sans imprimante</span>
+        </CardTitle>
+        <CardDescription>
+          Les tickets sont émis avec leur numéro et gardés en attente au lieu d'être imprimés ; la vente n'attend
+          pas l'imprimante. Ils sont imprimés plus tard, 20 par 20, ou envoyés au client par SMS.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <label className="flex items-center gap-2">
+          <input type="checkbox" checked={mode.enabled} disabled={!isSupervisor} onChange={e => toggle(e.target.checked)} />
+          <span>Garder les tickets au lieu de les imprimer</span>
+        </label>
+        <div className="flex items-center justify-between">
+          <span className="text-sm">{tickets.length} ticket(s) en attente</span>
+          <Button size="sm" onClick={printAll} disabled={tickets.length === 0}>Imprimer les tickets en attente</Button>
+        </div>
+        <div className="max-h-64 overflow-y-auto divide-y text-sm">
+          {tickets.map(t => (
+            <div key={t.id} className="flex items-center justify-between py-1 gap-2">
+              <span className="flex-1">
+                {t.printedAt} · {t.ticketType} {t.ticketNumber ?? ''} · {t.staffName ?? '—'}
+              </span>
+              <Input
+                className="w-36"
+                placeholder="Téléphone"
+                value={phones[t.id] ?? ''}
+                onChange={e => setPhones({ ...phones, [t.id]: e.target.value })}
+              />
+              <Button variant="outline" size="sm" onClick={() => sendSms(t)} disabled={!phones[t.id]}>SMS</Button>
+            </div>
+          ))}
+        </div>
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
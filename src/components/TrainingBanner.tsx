@@ -0,0 +1,23 @@
+import React, { useEffect, useState } from 'react';
+import { dbClient } from '../services/dbClient';
+
+// A strip across the top of every screen while this terminal is in training mode
+export const TrainingBanner: React.FC = () => {
+  const [active, setActive] = useState(false);
+
+  useEffect(() => {
+    dbClient.getTrainingMode().then(status => setActive(status.active)).catch(() => {});
+    const unlisten = dbClient.onTrainingModeChanged(setActive);
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
+  if (!active) return null;
+
+  return (
+    <div className="pointer-events-none fixed inset-x-0 top-0 z-50 bg-amber-500 py-1 text-center text-sm font-bold text-white">
+      MODE FORMATION – DONNÉES FICTIVES, TICKETS NON VALABLES
+    </div>
+  );
+};
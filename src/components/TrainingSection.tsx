@@ -0,0 +1,77 @@
+import React, { useEffect, useState } from 'react';
+import { Button } from './ui/button';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from './ui/card';
+import { GraduationCap } from 'lucide-react';
+import { useAuth } from '../context/AuthProvider';
+import { dbClient, TrainingStatus } from '../services/dbClient';
+
+// Training mode for new cashiers: supervisors prepare the practice data and switch this
+// terminal over to it
+export const TrainingSection: React.FC = () => {
+  const { currentStaff } = useAuth();
+  const isSupervisor = currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN';
+  const [status, setStatus] = useState<TrainingStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [message, setMessage] = useState('');
+
+  useEffect(() => {
+    dbClient.getTrainingMode().then(setStatus).catch(() => setStatus(null));
+  }, []);
+
+  if (!status) return null;
+
+  const seed = async () => {
+    if (!window.confirm('Réinitialiser les données de formation ? Les ventes d\'entraînement seront effacées.')) return;
+    setBusy(true);
+    try {
+      const seeded = await dbClient.seedTraining(currentStaff?.id);
+      setStatus({ ...status, seeded: true });
+      setMessage(`Données de formation prêtes : ${seeded.tables} tables, ${seeded.rows} lignes copiées`);
+    } catch (error) {
+      setMessage(String(error));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const toggle = async () => {
+    try {
+      setStatus(await dbClient.setTrainingMode(!status.active, currentStaff?.id));
+      setMessage(status.active ? 'Retour aux données réelles' : 'Mode formation activé sur ce poste');
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <GraduationCap className="h-5 w-5" />
+          <span>Mode formation</span>
+        </CardTitle>
+        <CardDescription>
+          Ce poste travaille sur une copie de la station : les tickets portent « FORMATION – NON VALABLE »,
+          la synchronisation, les exports et les SMS sont suspendus. Le mode se désactive au redémarrage.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-sm">
+          {status.active ? 'Mode formation actif' : 'Données réelles'}
+          {!status.seeded && ' · données de formation non préparées'}
+        </p>
+        {isSupervisor && (
+          <div className="flex gap-2">
+            <Button variant="outline" onClick={seed} disabled={busy}>
+              Préparer les données de formation
+            </Button>
+            <Button variant={status.active ? 'default' : 'destructive'} onClick={toggle} disabled={busy || (!status.active && !status.seeded)}>
+              {status.active ? 'Quitter le mode formation' : 'Passer en mode formation'}
+            </Button>
+          </div>
+        )}
+        {message && <p className="text-sm">{message}</p>}
+      </CardContent>
+    </Card>
+  );
+};
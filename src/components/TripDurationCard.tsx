@@ -0,0 +1,92 @@
+import React, { useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Select } from './ui/select';
+import { Timer } from 'lucide-react';
+import { dbClient, DestinationDto, TripDurationStats } from '../services/dbClient';
+
+const DAY_OPTIONS = [
+  { value: '7', label: '7 jours' },
+  { value: '30', label: '30 jours' },
+  { value: '90', label: '90 jours' },
+];
+
+const formatMinutes = (minutes: number | null) => {
+  if (minutes === null) return '—';
+  const rounded = Math.round(minutes);
+  return rounded >= 60 ? `${Math.floor(rounded / 60)} h ${String(rounded % 60).padStart(2, '0')}` : `${rounded} min`;
+};
+
+// How long vehicles take to come back from one destination, for the supervisor dashboard
+export const TripDurationCard: React.FC = () => {
+  const [destinations, setDestinations] = useState<DestinationDto[]>([]);
+  const [destinationId, setDestinationId] = useState('');
+  const [days, setDays] = useState('30');
+  const [stats, setStats] = useState<TripDurationStats | null>(null);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    dbClient.getAvailableDestinations().then(list => {
+      setDestinations(list);
+      if (list.length > 0) setDestinationId(id => id || list[0].stationId);
+    }).catch(e => setError(String(e)));
+  }, []);
+
+  useEffect(() => {
+    if (!destinationId) return;
+    setError('');
+    dbClient.getTripDurationStats(destinationId, Number(days)).then(setStats).catch(e => {
+      setStats(null);
+      setError(String(e));
+    });
+  }, [destinationId, days]);
+
+  const figures: [string, number | null][] = stats
+    ? [
+        ['Médiane', stats.medianMinutes],
+        ['Moyenne', stats.averageMinutes],
+        ['9 sur 10 en', stats.p90Minutes],
+        ['Plus court', stats.shortestMinutes],
+        ['Plus long', stats.longestMinutes],
+      ]
+    : [];
+
+  return (
+    <Card className="p-4">
+      <div className="flex items-center justify-between gap-2 mb-3">
+        <div className="text-lg font-semibold flex items-center gap-2">
+          <Timer className="h-5 w-5" />
+          Durée des allers-retours
+        </div>
+        <div className="flex gap-2">
+          <div className="w-48">
+            <Select
+              options={destinations.map(d => ({ value: d.stationId, label: d.stationName }))}
+              value={destinationId}
+              onChange={e => setDestinationId(e.target.value)}
+            />
+          </div>
+          <div className="w-32">
+            <Select options={DAY_OPTIONS} value={days} onChange={e => setDays(e.target.value)} />
+          </div>
+        </div>
+      </div>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {!error && stats && stats.trips === 0 && <p className="text-sm text-muted-foreground">Aucun retour mesuré sur la période</p>}
+      {stats && stats.trips > 0 && (
+        <>
+          <div className="grid grid-cols-2 md:grid-cols-5 gap-3">
+            {figures.map(([label, minutes]) => (
+              <div key={label}>
+                <p className="text-xs text-muted-foreground">{label}</p>
+                <p className="text-xl font-semibold">{formatMinutes(minutes)}</p>
+              </div>
+            ))}
+          </div>
+          <p className="text-xs text-muted-foreground mt-2">
+            {stats.trips} allers-retours, de la sortie au retour dans la file
+          </p>
+        </>
+      )}
+    </Card>
+  );
+};
@@ -0,0 +1,76 @@
+import React, { useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Select } from './ui/select';
+import { Trophy } from 'lucide-react';
+import { dbClient, DestinationDto, TripLeaderboardEntry, TripPeriod } from '../services/dbClient';
+import { TRIP_PERIODS } from './VehicleTripsCard';
+
+// Vehicles with the most departures to one destination, for the supervisor screen
+export const TripLeaderboardCard: React.FC = () => {
+  const [destinations, setDestinations] = useState<DestinationDto[]>([]);
+  const [destinationId, setDestinationId] = useState('');
+  const [period, setPeriod] = useState<TripPeriod>('day');
+  const [entries, setEntries] = useState<TripLeaderboardEntry[]>([]);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    dbClient.getAvailableDestinations().then(list => {
+      setDestinations(list);
+      if (list.length > 0) setDestinationId(id => id || list[0].stationId);
+    }).catch(e => setError(String(e)));
+  }, []);
+
+  useEffect(() => {
+    if (!destinationId) return;
+    setError('');
+    dbClient.getTripLeaderboard(destinationId, period).then(setEntries).catch(e => {
+      setEntries([]);
+      setError(String(e));
+    });
+  }, [destinationId, period]);
+
+  return (
+    <Card className="p-4">
+      <div className="flex items-center justify-between gap-2 mb-3">
+        <div className="text-lg font-semibold flex items-center gap-2">
+          <Trophy className="h-5 w-5" />
+          Classement des voyages
+        </div>
+        <div className="flex gap-2">
+          <div className="w-48">
+            <Select
+              options={destinations.map(d => ({ value: d.stationId, label: d.stationName }))}
+              value={destinationId}
+              onChange={e => setDestinationId(e.target.value)}
+            />
+          </div>
+          <div className="w-32">
+            <Select options={TRIP_PERIODS} value={period} onChange={e => setPeriod(e.target.value as TripPeriod)} />
+          </div>
+        </div>
+      </div>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {!error && entries.length === 0 && <p className="text-sm text-muted-foreground">Aucun départ sur la période</p>}
+      {entries.length > 0 && (
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-muted-foreground">
+              <th>#</th>
+              <th>Plaque</th>
+              <th className="text-right">Voyages</th>
+            </tr>
+          </thead>
+          <tbody>
+            {entries.map(entry => (
+              <tr key={entry.vehicleId} className="border-t">
+                <td>{entry.rank}</td>
+                <td className="font-mono">{entry.licensePlate}</td>
+                <td className="text-right font-semibold">{entry.trips}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+    </Card>
+  );
+};
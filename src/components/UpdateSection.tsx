@@ -1,9 +1,11 @@
 import React, { useEffect, useState } from 'react';
-import { checkUpdate, onUpdaterEvent } from '@tauri-apps/api/updater';
+import { onUpdaterEvent } from '@tauri-apps/api/updater';
 import { useTauri } from '../context/TauriProvider';
 import { Button } from './ui/button';
 import { Card, CardContent, CardHeader, CardTitle } from './ui/card';
 import { Badge } from './ui/badge';
+import { Input } from './ui/input';
+import { dbClient, UpdateChannel, UpdateSettings } from '../services/dbClient';
 import { Download, RefreshCw, CheckCircle, AlertCircle } from 'lucide-react';
 
 interface UpdateInfo {
@@ -20,6 +22,21 @@ export const UpdateSection: React.FC = () => {
   const [checking, setChecking] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [lastChecked, setLastChecked] = useState<Date | null>(null);
+  const [settings, setSettings] = useState<UpdateSettings | null>(null);
+  const [installAllowedNow, setInstallAllowedNow] = useState<boolean | null>(null);
+
+  useEffect(() => {
+    dbClient.getUpdateSettings().then(setSettings).catch(err => console.error('Failed to load update settings:', err));
+  }, []);
+
+  const saveSettings = async (change: () => Promise<UpdateSettings>) => {
+    try {
+      setError(null);
+      setSettings(await change());
+    } catch (err) {
+      setError(String(err));
+    }
+  };
 
   useEffect(() => {
     const loadAppInfo = async () => {
@@ -92,14 +109,16 @@ export const UpdateSection: React.FC = () => {
       setError(null);
       setUpdateAvailable(null);
 
-      const { shouldUpdate, manifest } = await checkUpdate();
+      const status = await dbClient.checkForUpdates();
+      setSettings(status.settings);
+      setInstallAllowedNow(status.installAllowedNow);
       
-      if (shouldUpdate && manifest) {
-        console.log(`Update available: ${manifest.version}, ${manifest.date}, ${manifest.body}`);
+      if (status.available && status.version) {
+        console.log(`Update available: ${status.version} (${status.channel})`);
         setUpdateAvailable({
-          version: manifest.version,
-          date: manifest.date,
-          body: manifest.body
+          version: status.version,
+          date: '',
+          body: status.notes || ''
         });
       } else {
         console.log('No updates available');
@@ -156,7 +175,11 @@ export const UpdateSection: React.FC = () => {
             </div>
             <div className="space-y-1 text-sm">
               <div><strong>Version:</strong> {updateAvailable.version}</div>
-              <div><strong>Date:</strong> {updateAvailable.date}</div>
+              <div>
+                {installAllowedNow
+                  ? 'Installation en cours dans la fenêtre de maintenance'
+                  : `Sera installée entre ${settings?.windowStart.slice(0, 5)} et ${settings?.windowEnd.slice(0, 5)}`}
+              </div>
               {updateAvailable.body && (
                 <div><strong>What's new:</strong> {updateAvailable.body}</div>
               )}
@@ -164,6 +187,50 @@ export const UpdateSection: React.FC = () => {
           </div>
         )}
 
+        {/* Channel, deferral and maintenance window */}
+        {settings && (
+          <div className="space-y-2">
+            <div className="flex items-center justify-between">
+              <span className="text-sm font-medium">Canal:</span>
+              <select
+                className="rounded border bg-background px-2 py-1 text-sm"
+                value={settings.channel}
+                onChange={e => saveSettings(() => dbClient.setUpdateChannel(e.target.value as UpdateChannel))}
+              >
+                <option value="stable">Stable</option>
+                <option value="beta">Bêta</option>
+              </select>
+            </div>
+            <div className="flex items-center justify-between gap-2">
+              <span className="text-sm font-medium">Reporter jusqu'au:</span>
+              <Input
+                type="date"
+                className="w-44"
+                value={settings.deferredUntil || ''}
+                onChange={e => saveSettings(() => dbClient.deferUpdateUntil(e.target.value || undefined))}
+              />
+            </div>
+            <div className="flex items-center justify-between gap-2">
+              <span className="text-sm font-medium">Fenêtre de maintenance:</span>
+              <div className="flex items-center gap-1">
+                <Input
+                  type="time"
+                  className="w-28"
+                  defaultValue={settings.windowStart.slice(0, 5)}
+                  onBlur={e => saveSettings(() => dbClient.setUpdateMaintenanceWindow(e.target.value, settings.windowEnd.slice(0, 5)))}
+                />
+                <span>–</span>
+                <Input
+                  type="time"
+                  className="w-28"
+                  defaultValue={settings.windowEnd.slice(0, 5)}
+                  onBlur={e => saveSettings(() => dbClient.setUpdateMaintenanceWindow(settings.windowStart.slice(0, 5), e.target.value))}
+                />
+              </div>
+            </div>
+          </div>
+        )}
+
         {/* Error Display */}
         {error && (
           <div className="p-3 bg-red-50 dark:bg-red-950/20 rounded-lg border border-red-200 dark:border-red-800">
@@ -0,0 +1,77 @@
+import React, { useCallback, useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Button } from './ui/button';
+import { MapPin } from 'lucide-react';
+import { dbClient, VehiclePosition } from '../services/dbClient';
+
+const REFRESH_MS = 60_000;
+
+const clock = (iso: string | null) => (iso ? new Date(iso).toLocaleTimeString('fr-FR', { hour: '2-digit', minute: '2-digit' }) : '—');
+
+// Last GPS positions and when the vehicles out on a trip should be back, to plan the queue
+export const VehiclePositionsCard: React.FC = () => {
+  const [positions, setPositions] = useState<VehiclePosition[]>([]);
+  const [error, setError] = useState('');
+
+  const refresh = useCallback(() => {
+    dbClient.getVehiclePositions().then(list => {
+      setPositions(list);
+      setError('');
+    }).catch(e => setError(String(e)));
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    const timer = setInterval(refresh, REFRESH_MS);
+    return () => clearInterval(timer);
+  }, [refresh]);
+
+  return (
+    <Card className="p-4">
+      <div className="flex items-center justify-between gap-2 mb-3">
+        <div className="text-lg font-semibold flex items-center gap-2">
+          <MapPin className="h-5 w-5" />
+          Positions GPS
+        </div>
+        <Button size="sm" variant="outline" onClick={refresh}>Actualiser</Button>
+      </div>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {!error && positions.length === 0 && <p className="text-sm text-muted-foreground">Aucune position reçue</p>}
+      {positions.length > 0 && (
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-muted-foreground">
+              <th>Plaque</th>
+              <th>Dernière position</th>
+              <th>Dernier départ</th>
+              <th>Retour estimé</th>
+            </tr>
+          </thead>
+          <tbody>
+            {positions.map(p => (
+              <tr key={p.vehicleId} className="border-t">
+                <td className="font-mono">{p.licensePlate}</td>
+                <td>
+                  <a
+                    className="underline"
+                    href={`https://www.openstreetmap.org/?mlat=${p.latitude}&mlon=${p.longitude}#map=14/${p.latitude}/${p.longitude}`}
+                    target="_blank"
+                    rel="noreferrer"
+                  >
+                    {p.latitude.toFixed(4)}, {p.longitude.toFixed(4)}
+                  </a>
+                  <span className="text-muted-foreground"> il y a {p.minutesSinceReport} min</span>
+                </td>
+                <td>{p.lastExitAt ? `${clock(p.lastExitAt)} → ${p.lastDestinationName ?? ''}` : '—'}</td>
+                <td className={p.overdue ? 'text-red-600 font-medium' : ''}>
+                  {p.inQueue ? 'En file' : clock(p.estimatedReturnAt)}
+                  {p.overdue && ' (en retard)'}
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+    </Card>
+  );
+};
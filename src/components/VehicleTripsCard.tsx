@@ -0,0 +1,60 @@
+import React, { useEffect, useState } from 'react';
+import { Card } from './ui/card';
+import { Select } from './ui/select';
+import { Route } from 'lucide-react';
+import { dbClient, TripPeriod, VehicleTripCounts } from '../services/dbClient';
+
+export const TRIP_PERIODS: { value: TripPeriod; label: string }[] = [
+  { value: 'day', label: 'Jour' },
+  { value: 'week', label: 'Semaine' },
+  { value: 'month', label: 'Mois' },
+];
+
+// Departures of one vehicle over the day, week or month holding `date`, per destination
+export const VehicleTripsCard: React.FC<{ vehicleId: string; date: string }> = ({ vehicleId, date }) => {
+  const [period, setPeriod] = useState<TripPeriod>('month');
+  const [counts, setCounts] = useState<VehicleTripCounts | null>(null);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    setError('');
+    dbClient.getVehicleTripCounts(vehicleId, period, date).then(setCounts).catch(e => {
+      setCounts(null);
+      setError(String(e));
+    });
+  }, [vehicleId, period, date]);
+
+  return (
+    <Card className="p-6">
+      <div className="flex items-center justify-between mb-4">
+        <h3 className="text-lg font-semibold flex items-center gap-2">
+          <Route className="h-5 w-5" />
+          Voyages
+        </h3>
+        <div className="w-40">
+          <Select options={TRIP_PERIODS} value={period} onChange={e => setPeriod(e.target.value as TripPeriod)} />
+        </div>
+      </div>
+      {error && <p className="text-sm text-red-600">❌ {error}</p>}
+      {counts && (
+        <>
+          <p className="text-sm text-muted-foreground mb-2">
+            Du {counts.from} au {counts.to}: <span className="font-semibold text-foreground">{counts.trips} voyage(s)</span>
+          </p>
+          {counts.byDestination.length > 0 && (
+            <table className="w-full text-sm">
+              <tbody>
+                {counts.byDestination.map(d => (
+                  <tr key={d.destinationId} className="border-t">
+                    <td>{d.destinationName}</td>
+                    <td className="text-right">{d.trips}</td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          )}
+        </>
+      )}
+    </Card>
+  );
+};
@@ -1,4 +1,5 @@
 import React, { createContext, useState, useContext, useEffect, ReactNode } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
 import api from "../lib/api";
 import SessionManager from "../lib/sessionManager";
 
@@ -18,6 +19,7 @@ interface AuthContextInterface {
   selectedRoute: string | null;
   login: (cin: string, route?: string) => Promise<any>;
   logout: () => Promise<void>;
+  switchUser: (pin: string) => Promise<Staff>;
   isLoading: boolean;
   restoreSession: () => Promise<boolean>;
 }
@@ -28,6 +30,7 @@ const AuthContext = createContext<AuthContextInterface>({
   selectedRoute: null,
   login: async () => ({}),
   logout: async () => {},
+  switchUser: async () => { throw new Error("AuthProvider missing"); },
   isLoading: true,
   restoreSession: async () => false,
 });
@@ -53,6 +56,7 @@ export const AuthProvider = ({ children }: { children: ReactNode }) => {
       if (validationResult.isValid && validationResult.session) {
         console.log('✅ Session restored successfully');
         setCurrentStaff(validationResult.session.staff);
+        invoke('auth_set_active_staff', { token: validationResult.session.token }).catch(() => {});
         setSelectedRoute(validationResult.session.selectedRoute || null);
         setIsAuthenticated(true);
         return true;
@@ -102,6 +106,7 @@ export const AuthProvider = ({ children }: { children: ReactNode }) => {
         console.log('✅ Login successful, saving session...');
         setIsAuthenticated(true);
         setCurrentStaff(response.staff);
+        invoke('auth_set_active_staff', { token: response.token }).catch(() => {});
         setSelectedRoute(route || null);
         
         // Save session with route information
@@ -138,9 +143,23 @@ export const AuthProvider = ({ children }: { children: ReactNode }) => {
       setCurrentStaff(null);
       setSelectedRoute(null);
       sessionManager.clearSession();
+      invoke('auth_set_active_staff', { token: null }).catch(() => {});
     }
   };
 
+  /**
+   * Hand the terminal over to another cashier with their PIN, keeping the session open
+   */
+  const switchUser = async (pin: string): Promise<Staff> => {
+    const staff = await invoke<Staff>('auth_switch_user', { pin });
+    setCurrentStaff(staff);
+    const session = sessionManager.getCurrentSession();
+    if (session) {
+      sessionManager.saveSession({ ...session, staff });
+    }
+    return staff;
+  };
+
   return (
     <AuthContext.Provider value={{
       isAuthenticated,
@@ -148,6 +167,7 @@ export const AuthProvider = ({ children }: { children: ReactNode }) => {
       selectedRoute,
       login,
       logout,
+      switchUser,
       isLoading,
       restoreSession
     }}>
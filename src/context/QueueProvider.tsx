@@ -18,6 +18,7 @@ interface QueueItem {
   status?: string;
   subRoute?: string | null;
   subRouteName?: string | null;
+  version?: number; // concurrency token sent back with a reorder
   vehicle: {
     licensePlate: string;
     driver: {
@@ -222,6 +223,7 @@ export const QueueProvider: React.FC<QueueProviderProps> = ({ children }) => {
                 status: it.status,
                 subRoute: (it as any).subRoute ?? null,
                 subRouteName: (it as any).subRouteName ?? null,
+                version: it.version,
                 vehicle: {
                   licensePlate: it.licensePlate,
                   driver: { cin: '' },
@@ -471,6 +473,7 @@ export const QueueProvider: React.FC<QueueProviderProps> = ({ children }) => {
           status: it.status,
           subRoute: (it as any).subRoute ?? null,
           subRouteName: (it as any).subRouteName ?? null,
+          version: it.version,
           vehicle: {
             licensePlate: it.licensePlate,
             driver: { cin: '' },
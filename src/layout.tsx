@@ -26,6 +26,16 @@ import { useSupervisorMode } from "./context/SupervisorModeProvider";
 import { WaslaLogo } from "./components/WaslaLogo";
 import { keyboardShortcuts } from "./services/keyboardShortcuts";
 import KeyboardShortcutsHelp from "./components/KeyboardShortcutsHelp";
+import { ClockDriftGuard } from "./components/ClockDriftGuard";
+import { StationClosedBanner } from "./components/StationClosedBanner";
+import { TrainingBanner } from "./components/TrainingBanner";
+import { AnomalyAlerts } from "./components/AnomalyAlerts";
+import { StalledVehicleAlerts } from "./components/StalledVehicleAlerts";
+import { AnprEntryConfirmations } from "./components/AnprEntryConfirmations";
+import { PaperLowAlert } from "./components/PaperLowAlert";
+import { PrintBatchAlert } from "./components/PrintBatchAlert";
+import { TerminalMessageInbox } from "./components/TerminalMessageInbox";
+import { ApprovalInbox } from "./components/ApprovalInbox";
 
 export default function Layout() {
   const location = useLocation();
@@ -282,6 +292,34 @@ export default function Layout() {
       {/* Keyboard Shortcuts Help */}
       <KeyboardShortcutsHelp />
       
+      {/* Blocks the UI while the PC clock is off the database clock */}
+      <ClockDriftGuard />
+      
+      {/* Outside business hours, with the after-hours sign-off */}
+      <StationClosedBanner />
+      
+      {/* This terminal is on the training data */}
+      <TrainingBanner />
+      
+      {/* Suspicious activity notifications for supervisors */}
+      <AnomalyAlerts />
+      
+      {/* Vehicles stuck loading past their destination's timeout, for supervisors */}
+      <StalledVehicleAlerts />
+      
+      {/* Vehicles queued by the gate camera, waiting for a cashier */}
+      <AnprEntryConfirmations />
+      
+      {/* Low paper warning from the printer usage estimate */}
+      <PaperLowAlert />
+      <PrintBatchAlert />
+      
+      {/* Messages from other terminals, until acknowledged */}
+      <TerminalMessageInbox />
+
+      {/* Sign-offs cashiers asked for from their terminals, for supervisors */}
+      <ApprovalInbox />
+      
       {/*<SocketMonitor />*/}
       
     </div>
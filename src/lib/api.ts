@@ -2,6 +2,17 @@ import { invoke } from '@tauri-apps/api/tauri';
 import { getLocalStorage, setLocalStorage } from './storage';
 import { SERVER_CONFIG } from '../config/server';
 
+// What proxy_localnode returns: the local node's answer with its status and headers
+export interface ProxyResponse {
+  status: number;
+  ok: boolean;
+  headers: Record<string, string>;
+  body: string;
+  url: string;
+  attempts: number;
+  durationMs: number;
+}
+
 // Default API configuration - Using server IP
 const DEFAULT_CONFIG = {
   baseUrl: SERVER_CONFIG.API.BASE_URL,
@@ -283,13 +294,14 @@ class ApiService {
     }
 
     // Call Rust proxy
-    const responseText = await invoke<string>('proxy_localnode', {
+    const proxyResponse = await invoke<ProxyResponse>('proxy_localnode', {
       method,
       endpoint,
       body,
       serverUrl: targetServerUrl,
       headers // <-- pass headers to Rust
     });
+    const responseText = proxyResponse.body;
 
     console.log(`🔍 Proxy Response:`, responseText);
 
@@ -358,12 +370,15 @@ class ApiService {
       console.log('🔍 Checking connection via proxy to /health');
       console.log('🔍 Server URL:', targetServerUrl);
       
-      const responseText = await invoke<string>('proxy_localnode', {
+      const proxyResponse = await invoke<ProxyResponse>('proxy_localnode', {
         method: 'GET',
         endpoint: '/health',
         body: null,
-        serverUrl: targetServerUrl
+        serverUrl: targetServerUrl,
+        // A health check should answer fast; one retry rides out a dropped connection
+        options: { timeoutMs: 3000, retries: 1 }
       });
+      const responseText = proxyResponse.body;
       
       console.log('🔍 Proxy health check response:', responseText);
       
@@ -27,6 +27,7 @@ import PrintVehicleReport from './routes/print-vehicle-report';
 import PrintAllVehiclesReport from './routes/print-all-vehicles-report';
 import DayPassDebug from './routes/day-pass-debug';
 import PrintQueueTest from './routes/print-queue-test';
+import Kiosk from './routes/kiosk';
 import { TauriProvider } from "./context/TauriProvider";
 import { AuthProvider } from "./context/AuthProvider";
 import "./styles.css";
@@ -52,6 +53,19 @@ import { platform } from '@tauri-apps/api/os';
 // Import enhanced API service
 import enhancedApi from './services/enhancedLocalNodeApi';
 
+interface FirewallRuleStatus {
+  appName: string;
+  supported: boolean;
+  inbound: boolean;
+  outbound: boolean;
+}
+
+interface FirewallActionResult {
+  outcome: 'APPLIED' | 'UNCHANGED' | 'DECLINED' | 'FAILED' | 'UNSUPPORTED';
+  status: FirewallRuleStatus;
+  message: string;
+}
+
 function useAddFirewallRule() {
   useEffect(() => {
     async function run() {
@@ -62,12 +76,15 @@ function useAddFirewallRule() {
         // Guess the exe path (you can hardcode if needed)
         // You may want to use @tauri-apps/api/path to get the exe path more robustly
         const exePath = `${await appDir()}Nqlix.exe`;
-        await invoke('add_firewall_rule', {
+        // Checking needs no elevation: the prompt only shows when a rule is missing
+        const status = await invoke<FirewallRuleStatus>('check_firewall_rule', { appName: 'Nqlix' });
+        if (status.inbound && status.outbound) return;
+        const result = await invoke<FirewallActionResult>('add_firewall_rule', {
           exePath,
           appName: 'Nqlix'
         });
-        // Optionally show a notification or log
-        console.log('Firewall rule added (or already exists)');
+        if (result.outcome === 'APPLIED' || result.outcome === 'UNCHANGED') console.log(result.message);
+        else console.warn(`Firewall rule not added (${result.outcome}):`, result.message);
       } catch (e) {
         console.error('Failed to add firewall rule:', e);
       }
@@ -242,7 +259,16 @@ const App: React.FC = () => {
   );
 };
 
+// The kiosk window (start_kiosk_mode) skips init, login and the cashier providers entirely
+const isKioskWindow = window.location.hash.startsWith('#/kiosk');
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
+  isKioskWindow ? (
+    <React.StrictMode>
+      <Kiosk />
+      <Toaster />
+    </React.StrictMode>
+  ) : (
   <React.StrictMode>
     <TauriProvider>
       <InitProvider>
@@ -263,4 +289,5 @@ ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
       </InitProvider>
     </TauriProvider>
   </React.StrictMode>
+  )
 );
@@ -17,6 +17,9 @@ import api from '../lib/api';
 import { dbClient } from '../services/dbClient';
 // Real-time disabled
 import { SystemStatus } from '../components/SystemStatus';
+import { ExpensesSection } from '../components/ExpensesSection';
+import { TripDurationCard } from '../components/TripDurationCard';
+import { DestinationSuspensionBanner } from '../components/DestinationSuspensionBanner';
 
 interface QueueData {
   destinationId: string;
@@ -264,6 +267,9 @@ function StaffDashboard({ selectedRoute }: { selectedRoute: string | null }) {
         </div>
       </div>
 
+      {/* Routes suspended for weather or roadworks */}
+      <DestinationSuspensionBanner />
+
       {/* Stats Cards */}
       <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-4">
         <Card className="p-6 bg-gradient-to-br from-blue-50 to-blue-100 border-blue-200">
@@ -561,6 +567,7 @@ function StaffDashboard({ selectedRoute }: { selectedRoute: string | null }) {
 }
 
 function SupervisorDashboard() {
+  const { currentStaff } = useAuth();
   const { 
     notifyPaymentSuccess, 
     notifyPaymentFailed, 
@@ -717,6 +724,14 @@ function SupervisorDashboard() {
             </div>
           </Card>
         </div>
+
+        <div className="mt-4">
+          <ExpensesSection staffId={currentStaff?.id} />
+        </div>
+
+        <div className="mt-4">
+          <TripDurationCard />
+        </div>
       </div>
 
       {/* Transaction History - Full Height */}
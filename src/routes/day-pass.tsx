@@ -319,10 +319,9 @@ export default function DayPassPage() {
               <Button
                 onClick={async () => {
                   try {
-                    await thermalPrinter.reprintLastDayPass();
-                    setSuccess('Le dernier pass journalier a été réimprimé');
+                    setSuccess(await thermalPrinter.reprintLastDayPass(currentStaff?.id));
                   } catch (error) {
-                    setError('Impossible de réimprimer le dernier pass journalier');
+                    setError(`Impossible de réimprimer le dernier pass journalier: ${error}`);
                   }
                 }}
                 className="px-6 py-2 bg-gradient-to-r from-blue-500 to-purple-600 hover:from-blue-600 hover:to-purple-700 text-white shadow-lg"
@@ -645,10 +644,9 @@ export default function DayPassPage() {
                     variant="outline"
                     onClick={async () => {
                       try {
-                        await thermalPrinter.reprintLastDayPass();
-                        setSuccess('Le dernier pass journalier a été réimprimé');
+                        setSuccess(await thermalPrinter.reprintLastDayPass(currentStaff?.id));
                       } catch (error) {
-                        setError('Impossible de réimprimer le dernier pass journalier');
+                        setError(`Impossible de réimprimer le dernier pass journalier: ${error}`);
                       }
                     }}
                     className="text-blue-600 hover:text-blue-700"
@@ -86,19 +86,19 @@ export default function DriverTicketsPage() {
 
   const reprintLastEntry = async () => {
     try {
-      await thermalPrinter.reprintLastEntry();
-      console.log('✅ Reprinted last entry ticket');
+      alert(await thermalPrinter.reprintLastEntry(currentStaff?.id));
     } catch (error) {
       console.error('❌ Failed to reprint last entry ticket:', error);
+      alert(`❌ ${error}`);
     }
   };
 
   const reprintLastExit = async () => {
     try {
-      await thermalPrinter.reprintLastExit();
-      console.log('✅ Reprinted last exit ticket');
+      alert(await thermalPrinter.reprintLastExit(currentStaff?.id));
     } catch (error) {
       console.error('❌ Failed to reprint last exit ticket:', error);
+      alert(`❌ ${error}`);
     }
   };
 
@@ -0,0 +1,255 @@
+import { useEffect, useState } from "react";
+import { Button } from "../components/ui/button";
+import { Card } from "../components/ui/card";
+import { ArrowLeft, Bus, CheckCircle, CreditCard, Loader2, Lock, MapPin, XCircle } from "lucide-react";
+import { dbClient, BookingError } from "../services/dbClient";
+
+// Self-service terminal in the hall. Runs in its own window (see kiosk.rs) without login:
+// the backend only lets this window list destinations, book with a card, print tickets and check
+// drivers in with their PIN (self_check_in.rs).
+
+interface KioskDestination {
+  destinationId: string;
+  destinationName: string;
+  subRoute?: string | null;
+  subRouteName?: string | null;
+  totalAvailableSeats: number;
+}
+
+type Step = 'destinations' | 'seats' | 'checkin' | 'paying' | 'done' | 'error';
+
+const MAX_KIOSK_SEATS = 4;
+// Back to the destination list when nobody touches the screen
+const IDLE_RESET_MS = 60_000;
+
+export default function Kiosk() {
+  const [step, setStep] = useState<Step>('destinations');
+  const [destinations, setDestinations] = useState<KioskDestination[]>([]);
+  const [selected, setSelected] = useState<KioskDestination | null>(null);
+  const [seats, setSeats] = useState(1);
+  const [message, setMessage] = useState('');
+  const [showUnlock, setShowUnlock] = useState(false);
+  const [pin, setPin] = useState('');
+  const [plate, setPlate] = useState('');
+  const [driverPin, setDriverPin] = useState('');
+
+  const reset = () => {
+    setStep('destinations');
+    setSelected(null);
+    setSeats(1);
+    setMessage('');
+    setShowUnlock(false);
+    setPin('');
+    setPlate('');
+    setDriverPin('');
+  };
+
+  const loadDestinations = async () => {
+    try {
+      const list = await dbClient.getAvailableBookingDestinations();
+      setDestinations((list as KioskDestination[]).filter(d => d.totalAvailableSeats > 0));
+    } catch (error) {
+      console.error('❌ [KIOSK] Failed to load destinations:', error);
+    }
+  };
+
+  useEffect(() => {
+    loadDestinations();
+    const interval = setInterval(loadDestinations, 15_000);
+    return () => clearInterval(interval);
+  }, []);
+
+  useEffect(() => {
+    if (step === 'paying') return;
+    let timer = setTimeout(reset, IDLE_RESET_MS);
+    const restart = () => {
+      clearTimeout(timer);
+      timer = setTimeout(reset, IDLE_RESET_MS);
+    };
+    window.addEventListener('pointerdown', restart);
+    return () => {
+      clearTimeout(timer);
+      window.removeEventListener('pointerdown', restart);
+    };
+  }, [step]);
+
+  const pay = async () => {
+    if (!selected) return;
+    setStep('paying');
+    setMessage('Suivez les instructions sur le terminal de paiement');
+    try {
+      const response = await dbClient.createQueueBooking(selected.destinationId, seats, undefined, crypto.randomUUID(), 'CARD');
+      try {
+        await dbClient.printKioskTickets((response.bookings || []).map((booking: any) => booking.id));
+      } catch (printError) {
+        console.error('❌ [KIOSK] Failed to print tickets:', printError);
+      }
+      setStep('done');
+      setMessage('Paiement accepté, récupérez vos tickets');
+      setTimeout(reset, 10_000);
+    } catch (error) {
+      setStep('error');
+      setMessage((error as BookingError)?.message ?? String(error));
+      setTimeout(reset, 10_000);
+    } finally {
+      loadDestinations();
+    }
+  };
+
+  const checkIn = async () => {
+    setStep('paying');
+    setMessage('Enregistrement en cours');
+    try {
+      const entry = await dbClient.selfCheckIn(plate, driverPin);
+      setStep('done');
+      setMessage(`${entry.licensePlate} en position ${entry.queuePosition} pour ${entry.destinationName}, ticket à l'imprimante ${entry.printerName}`);
+    } catch (error) {
+      setStep('error');
+      setMessage(String(error));
+    }
+    setTimeout(reset, 10_000);
+  };
+
+  const unlock = async () => {
+    try {
+      await dbClient.stopKioskMode(pin);
+    } catch (error) {
+      setMessage(String(error));
+      setPin('');
+    }
+  };
+
+  const maxSeats = Math.min(MAX_KIOSK_SEATS, selected?.totalAvailableSeats ?? 0);
+
+  return (
+    <div className="min-h-screen select-none bg-gray-50 p-8 dark:bg-gray-900">
+      <div className="mb-8 flex items-center justify-between">
+        <h1 className="text-4xl font-bold">Achetez votre place</h1>
+        <button className="p-4 text-gray-300" onClick={() => setShowUnlock(!showUnlock)} aria-label="Superviseur">
+          <Lock className="h-6 w-6" />
+        </button>
+      </div>
+
+      {showUnlock && (
+        <Card className="mx-auto mb-8 max-w-sm p-6">
+          <div className="mb-4 h-12 rounded border text-center text-3xl tracking-widest">{'•'.repeat(pin.length)}</div>
+          <div className="grid grid-cols-3 gap-3">
+            {['1', '2', '3', '4', '5', '6', '7', '8', '9', 'C', '0', 'OK'].map(key => (
+              <Button
+                key={key}
+                className="h-16 text-2xl"
+                variant={key === 'OK' ? 'default' : 'outline'}
+                onClick={() => {
+                  if (key === 'C') setPin('');
+                  else if (key === 'OK') unlock();
+                  else if (pin.length < 6) setPin(pin + key);
+                }}
+              >
+                {key}
+              </Button>
+            ))}
+          </div>
+          {message && <p className="mt-4 text-center text-red-600">{message}</p>}
+        </Card>
+      )}
+
+      {step === 'destinations' && (
+        <div className="mb-6 flex justify-end">
+          <Button className="h-16 px-8 text-xl" variant="outline" onClick={() => setStep('checkin')}>
+            <Bus className="mr-3 h-6 w-6" /> Chauffeurs : entrer dans la file
+          </Button>
+        </div>
+      )}
+
+      {step === 'checkin' && (
+        <div className="mx-auto max-w-md text-center">
+          <h2 className="mb-6 text-3xl font-semibold">Entrée dans la file</h2>
+          <input
+            className="mb-4 h-16 w-full rounded border text-center text-3xl"
+            placeholder="Matricule"
+            value={plate}
+            onChange={e => setPlate(e.target.value.toUpperCase())}
+          />
+          <div className="mb-4 h-12 rounded border text-center text-3xl tracking-widest">{'•'.repeat(driverPin.length)}</div>
+          <div className="mb-8 grid grid-cols-3 gap-3">
+            {['1', '2', '3', '4', '5', '6', '7', '8', '9', 'C', '0', 'OK'].map(key => (
+              <Button
+                key={key}
+                className="h-16 text-2xl"
+                variant={key === 'OK' ? 'default' : 'outline'}
+                disabled={key === 'OK' && (!plate || driverPin.length < 4)}
+                onClick={() => {
+                  if (key === 'C') setDriverPin('');
+                  else if (key === 'OK') checkIn();
+                  else if (driverPin.length < 6) setDriverPin(driverPin + key);
+                }}
+              >
+                {key}
+              </Button>
+            ))}
+          </div>
+          <Button className="h-20 px-10 text-2xl" variant="outline" onClick={reset}>
+            <ArrowLeft className="mr-3 h-8 w-8" /> Retour
+          </Button>
+        </div>
+      )}
+
+      {step === 'destinations' && (
+        <div className="grid grid-cols-2 gap-6 lg:grid-cols-3">
+          {destinations.map(destination => (
+            <button
+              key={`${destination.destinationId}-${destination.subRoute || ''}`}
+              className="rounded-xl border bg-white p-8 text-left shadow-sm active:scale-95 dark:bg-gray-800"
+              onClick={() => {
+                setSelected(destination);
+                setSeats(1);
+                setStep('seats');
+              }}
+            >
+              <MapPin className="mb-3 h-8 w-8 text-blue-600" />
+              <div className="text-3xl font-semibold">{destination.subRouteName || destination.destinationName}</div>
+              <div className="mt-2 text-xl text-gray-500">{destination.totalAvailableSeats} places disponibles</div>
+            </button>
+          ))}
+          {destinations.length === 0 && (
+            <p className="col-span-full text-center text-2xl text-gray-500">Aucune place disponible pour le moment</p>
+          )}
+        </div>
+      )}
+
+      {step === 'seats' && selected && (
+        <div className="mx-auto max-w-2xl text-center">
+          <h2 className="mb-8 text-3xl font-semibold">{selected.subRouteName || selected.destinationName}</h2>
+          <p className="mb-4 text-2xl">Nombre de places</p>
+          <div className="mb-10 flex justify-center gap-4">
+            {Array.from({ length: maxSeats }, (_, i) => i + 1).map(n => (
+              <Button key={n} className="h-24 w-24 text-4xl" variant={seats === n ? 'default' : 'outline'} onClick={() => setSeats(n)}>
+                {n}
+              </Button>
+            ))}
+          </div>
+          <div className="flex justify-center gap-6">
+            <Button className="h-20 px-10 text-2xl" variant="outline" onClick={reset}>
+              <ArrowLeft className="mr-3 h-8 w-8" /> Retour
+            </Button>
+            <Button className="h-20 px-10 text-2xl" onClick={pay}>
+              <CreditCard className="mr-3 h-8 w-8" /> Payer par carte
+            </Button>
+          </div>
+        </div>
+      )}
+
+      {step !== 'destinations' && step !== 'seats' && step !== 'checkin' && (
+        <div className="mt-24 flex flex-col items-center gap-6 text-center">
+          {step === 'paying' && <Loader2 className="h-24 w-24 animate-spin text-blue-600" />}
+          {step === 'done' && <CheckCircle className="h-24 w-24 text-green-600" />}
+          {step === 'error' && <XCircle className="h-24 w-24 text-red-600" />}
+          <p className="text-3xl">{message}</p>
+          {step !== 'paying' && (
+            <Button className="h-20 px-10 text-2xl" onClick={reset}>Terminer</Button>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,4 +1,4 @@
-import React, { useState, useEffect } from 'react';
+import React, { useState, useEffect, useMemo } from 'react';
 import { useAuth } from '../context/AuthProvider';
 import { useInit } from '../context/InitProvider';
 import { useQueue } from '../context/QueueProvider';
@@ -32,7 +32,7 @@ import {
   Keyboard
 } from 'lucide-react';
 import api from '../lib/api';
-import { dbClient, BookingUpdateEvent, QueueUpdateEvent } from '../services/dbClient';
+import { dbClient, BookingUpdateEvent, QueueUpdateEvent, FareModifier, FareSelection, BookingError } from '../services/dbClient';
 import { websocketDbClient } from '../services/websocketRealtimeService';
 import { useMQTT } from '../lib/useMQTT';
 import { usePaymentNotifications } from '../components/NotificationToast';
@@ -41,6 +41,7 @@ import { renderToString } from 'react-dom/server';
 import { thermalPrinter } from '../services/thermalPrinterService';
 import { Settings } from 'lucide-react';
 import ExitPassConfirmationModal from '../components/ExitPassConfirmationModal';
+import { FareModifierPicker } from '../components/FareModifierPicker';
 
 interface Destination {
   destinationId: string;
@@ -64,6 +65,26 @@ interface Government {
   }>;
 }
 
+// Modifier lines for the ticket of seat `seatIndex` (1-based): one child fare per seat from the
+// first ticket on, every supplement on the first ticket
+function seatFareModifiers(modifiers: FareModifier[], seatIndex: number): FareModifier[] {
+  const lines: FareModifier[] = [];
+  let childSeat = 0;
+  for (const modifier of modifiers) {
+    if (modifier.ruleType === 'CHILD_FARE') {
+      for (let i = 0; i < modifier.quantity; i++) {
+        childSeat += 1;
+        if (childSeat === seatIndex) {
+          lines.push({ ...modifier, quantity: 1, amount: modifier.amount / modifier.quantity });
+        }
+      }
+    } else if (seatIndex === 1) {
+      lines.push(modifier);
+    }
+  }
+  return lines;
+}
+
 export default function MainBooking() {
   const { currentStaff, selectedRoute } = useAuth();
   const { systemStatus } = useInit();
@@ -91,9 +112,15 @@ export default function MainBooking() {
   
   // Thermal printer integration removed - keeping console logging
   const [destinations, setDestinations] = useState<Destination[]>([]);
+  // Destination search: matching station ids, best first, or null to show every destination
+  const [destinationQuery, setDestinationQuery] = useState('');
+  const [matchedStationIds, setMatchedStationIds] = useState<string[] | null>(null);
   const [selectedDestination, setSelectedDestination] = useState<Destination | null>(null);
   const [availableSeats, setAvailableSeats] = useState<number>(1);
   const [bookingData, setBookingData] = useState<{ seats: number }>({ seats: 1 });
+  const [fareSelections, setFareSelections] = useState<FareSelection[]>([]);
+  // Pricing rules are per destination
+  useEffect(() => setFareSelections([]), [selectedDestination?.destinationId]);
   const [isProcessing, setIsProcessing] = useState(false);
   const [showSuccess, setShowSuccess] = useState(false);
   const [ticketsPrinted, setTicketsPrinted] = useState<number>(0);
@@ -1041,11 +1068,13 @@ export default function MainBooking() {
   // Fetch available governments and delegations
   const fetchGovernments = async () => {
     try {
-      const response = await api.getAllLocations();
-      if (response.success && response.data) {
-        setGovernments(response.data);
-        console.log(`🏛️ Fetched ${response.data.length} governments`);
-      }
+      const tree = await dbClient.getLocationTree();
+      setGovernments(tree.map(gov => ({
+        name: gov.name,
+        nameAr: gov.nameAr ?? undefined,
+        delegations: gov.delegations.map(del => ({ name: del.name, nameAr: del.nameAr ?? undefined })),
+      })));
+      console.log(`🏛️ Fetched ${tree.length} governments`);
     } catch (error) {
       console.error('❌ Error fetching governments:', error);
     }
@@ -1382,6 +1411,26 @@ export default function MainBooking() {
   // Removed aggressive refresh on user interaction to improve performance
   // Only refresh when necessary (booking operations, filter changes, etc.)
 
+  useEffect(() => {
+    if (!destinationQuery.trim()) {
+      setMatchedStationIds(null);
+      return;
+    }
+    const timer = setTimeout(() => {
+      dbClient.searchDestinations(destinationQuery)
+        .then(matches => setMatchedStationIds(matches.map(m => m.stationId)))
+        .catch(() => setMatchedStationIds(null));
+    }, 200);
+    return () => clearTimeout(timer);
+  }, [destinationQuery]);
+
+  const shownDestinations = useMemo(() => {
+    if (!matchedStationIds) return destinations;
+    return destinations
+      .filter(d => matchedStationIds.includes(d.destinationId))
+      .sort((a, b) => matchedStationIds.indexOf(a.destinationId) - matchedStationIds.indexOf(b.destinationId));
+  }, [destinations, matchedStationIds]);
+
   // Keyboard shortcuts for numberpad
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
@@ -1399,9 +1448,9 @@ export default function MainBooking() {
       // AZERTY shortcuts for destination selection (A, Z, E, R, T, Y, U, I)
       const destinationKeys = ['a', 'z', 'e', 'r', 't', 'y', 'u', 'i'];
       const destinationIndex = destinationKeys.indexOf(event.key.toLowerCase());
-      if (destinationIndex !== -1 && destinations[destinationIndex]) {
+      if (destinationIndex !== -1 && shownDestinations[destinationIndex]) {
         event.preventDefault();
-        handleDestinationSelect(destinations[destinationIndex]);
+        handleDestinationSelect(shownDestinations[destinationIndex]);
       }
 
       // Alt + number for seat selection (1-8)
@@ -1452,7 +1501,7 @@ export default function MainBooking() {
 
     document.addEventListener('keydown', handleKeyDown);
     return () => document.removeEventListener('keydown', handleKeyDown);
-  }, [destinations, availableSeats, selectedDestination, isProcessing]);
+  }, [shownDestinations, availableSeats, selectedDestination, isProcessing]);
 
   const handleDestinationSelect = (destination: Destination | null) => {
     console.log("🎯 Destination selected:", destination);
@@ -1538,29 +1587,11 @@ export default function MainBooking() {
       const talonData = thermalPrinter.formatTalonData(booking);
       console.log('📄 Formatted talon data:', talonData);
       
-      // Print main ticket with thermal printer
-      console.log('🖨️ Calling thermal printer for main ticket...');
+      // Ticket and talon go out as one batch, nothing else prints between them
+      console.log('🖨️ Calling thermal printer for ticket and talon...');
       const staffName = currentStaff ? `${currentStaff.firstName} ${currentStaff.lastName}` : undefined;
-      const mainTicketResult = await thermalPrinter.printBookingTicket(ticketData, staffName);
-      console.log('✅ Main ticket printed:', mainTicketResult);
-      
-      // Small delay to ensure main ticket is fully printed before talon
-      await new Promise(resolve => setTimeout(resolve, 200));
-      
-      // Print talon (detachable stub) with thermal printer
-      console.log('🖨️ Calling thermal printer for talon...');
-      console.log('📄 Talon data to print:', talonData);
-      console.log('👤 Staff name for talon:', staffName);
-      try {
-      const talonResult = await thermalPrinter.printTalon(talonData, staffName);
-        console.log('✅ Talon printed successfully:', talonResult);
-      } catch (talonError) {
-        console.error('❌ Talon printing failed:', talonError);
-        console.error('❌ Talon error details:', talonError instanceof Error ? talonError.message : 'Unknown error');
-        // Don't throw - continue with the booking even if talon fails
-      }
-      
-      console.log('✅ Booking ticket and talon printed successfully with thermal printer');
+      const result = await thermalPrinter.printBookingTicket(ticketData, staffName, talonData);
+      console.log('✅ Booking ticket and talon sent to the thermal printer:', result);
       
     } catch (error) {
       console.error('❌ Thermal printer error:', error);
@@ -1574,10 +1605,10 @@ export default function MainBooking() {
   // Reprint last booking ticket
   async function reprintLastBookingTicket() {
     try {
-      await thermalPrinter.reprintLastBooking();
-      console.log('✅ Reprinted last booking ticket');
+      alert(await thermalPrinter.reprintLastBooking(currentStaff?.id));
     } catch (error) {
       console.error('❌ Failed to reprint last booking ticket:', error);
+      alert(`❌ ${error}`);
     }
   }
 
@@ -1672,10 +1703,10 @@ export default function MainBooking() {
 
           const response = await (async () => {
             try {
-              await dbClient.cancelQueueBooking(booking.id);
+              await dbClient.cancelQueueBooking(booking.id, currentStaff?.id);
               return { success: true } as any;
             } catch (e: any) {
-              return { success: false, message: e?.message } as any;
+              return { success: false, message: typeof e === 'string' ? e : e?.message } as any;
             }
           })();
 
@@ -1847,6 +1878,9 @@ export default function MainBooking() {
       } : null;
     })();
 
+    // One id per submission; a retry of this call is answered with the same booking
+    const requestId = crypto.randomUUID();
+
     try {
       let response;
       
@@ -1856,7 +1890,12 @@ export default function MainBooking() {
         response = await dbClient.createVehicleSpecificBooking(
           selectedVehicle.queueId,
           seatsToBook,
-          currentStaff?.id
+          currentStaff?.id,
+          requestId,
+          undefined,
+          undefined,
+          undefined,
+          fareSelections
         );
       } else {
         // Fallback to general queue booking (books from first available vehicle)
@@ -1864,7 +1903,12 @@ export default function MainBooking() {
         response = await dbClient.createQueueBooking(
           selectedDestination.destinationId,
           seatsToBook,
-          currentStaff?.id
+          currentStaff?.id,
+          requestId,
+          undefined,
+          undefined,
+          undefined,
+          fareSelections
         );
       }
 
@@ -1936,6 +1980,7 @@ export default function MainBooking() {
           
           let successfulPrints = 0;
           const baseBooking = response.bookings[0];
+          const fareModifiers: FareModifier[] = response.bookings.flatMap((b: any) => b.fareModifiers || []);
           
           // Get the correct vehicle information for seat calculation
           const vehicleCapacity = baseBooking.vehicleCapacity || 8;
@@ -1980,13 +2025,15 @@ export default function MainBooking() {
             // Use the first booking as template but modify for individual seat
             const staffName = currentStaff ? `${currentStaff.firstName} ${currentStaff.lastName}` : 'N/A';
             const serviceFee = 0.200; // Fixed 0.200 TND service fee per seat
+            const seatModifiers = seatFareModifiers(fareModifiers, seatNumber);
             const individualSeatBooking = {
               ...baseBooking,
               // Remove seats field and treat as single seat
               seatsBooked: 1,
               baseAmount: basePrice, // Base price for one seat
               serviceFeeAmount: serviceFee, // Service fee for one seat
-              totalAmount: basePrice + serviceFee, // Total price for one seat
+              fareModifiers: seatModifiers, // Child fare / luggage on this ticket
+              totalAmount: basePrice + serviceFee + seatModifiers.reduce((sum, m) => sum + m.amount, 0), // Total price for one seat
               basePrice: basePrice, // Ensure basePrice is also set for one seat
               verificationCode: `${baseBooking.verificationCode}-${seatNumber}`, // Unique code per seat
               seatNumber: actualSeatPosition, // Actual seat position in vehicle
@@ -2063,6 +2110,7 @@ export default function MainBooking() {
           setShowSuccess(false);
           setTicketsPrinted(0);
           setBookingData({ seats: 1 });
+          setFareSelections([]);
           
           // Ensure destination selection is maintained
           if (selectedDestination) {
@@ -2119,7 +2167,7 @@ export default function MainBooking() {
           if (selectedDestination) {
             fetchQueueForDestination(selectedDestination.destinationId);
           }
-        } else if (response.message?.includes('Not enough seats available')) {
+        } else if (response.code === 'NO_SEATS') {
           alert(`⚠️ Insufficient Seats!\n\n${response.message}\n\nPlease select fewer seats or choose a different destination.`);
           
           // Refresh available seats for the current destination
@@ -2142,6 +2190,22 @@ export default function MainBooking() {
       } else if (error?.toString) {
         errorMessage = error.toString();
       }
+
+      // No seats left: offer the waiting list so the customer is called back when seats open up
+      if ((error as BookingError)?.code === 'NO_SEATS' && selectedDestination) {
+        const phone = window.prompt(
+          `⚠️ Places insuffisantes pour ${selectedDestination.destinationName}.\n\nInscrire le client en liste d'attente pour ${seatsToBook} place(s) ?\nTéléphone (optionnel) :`
+        );
+        if (phone !== null) {
+          try {
+            await dbClient.addToWaitlist(selectedDestination.destinationId, seatsToBook, phone || undefined, currentStaff?.id);
+            alert(`✅ Client inscrit en liste d'attente pour ${selectedDestination.destinationName}`);
+          } catch (waitlistError: any) {
+            alert(`❌ Inscription en liste d'attente impossible: ${waitlistError}`);
+          }
+        }
+        return;
+      }
       
       alert(`❌ Network Error!\n\nFailed to process booking: ${errorMessage}\n\nPlease check your connection and try again.`);
     } finally {
@@ -2230,14 +2294,23 @@ export default function MainBooking() {
           <div className="grid grid-cols-1 lg:grid-cols-2 gap-6 h-full">
             {/* Left Side - Destination Selection Grid */}
           <div className="space-y-4">
-              <h2 className="text-xl font-bold">Destinations Disponibles</h2>
+              <div className="flex items-center gap-3">
+                <h2 className="text-xl font-bold">Destinations Disponibles</h2>
+                <Input
+                  className="ml-auto max-w-xs"
+                  placeholder="Rechercher (ex. sfax, صفاقس)"
+                  value={destinationQuery}
+                  onChange={e => setDestinationQuery(e.target.value)}
+                  onKeyDown={e => e.key === 'Escape' && setDestinationQuery('')}
+                />
+              </div>
               <div className={`grid gap-4 ${
-                destinations.length <= 4 ? 'grid-cols-2' :
-                destinations.length <= 6 ? 'grid-cols-3' :
-                destinations.length <= 9 ? 'grid-cols-3' :
+                shownDestinations.length <= 4 ? 'grid-cols-2' :
+                shownDestinations.length <= 6 ? 'grid-cols-3' :
+                shownDestinations.length <= 9 ? 'grid-cols-3' :
                 'grid-cols-4'
               }`}>
-                {destinations.map((destination: Destination, index: number) => (
+                {shownDestinations.map((destination: Destination, index: number) => (
                     <Card
                       key={destination.destinationId}
                     className={`relative cursor-pointer transition-colors duration-200 ${
@@ -2642,6 +2715,16 @@ export default function MainBooking() {
                           </div>
                         </div>
 
+                {/* Child fares and luggage supplements */}
+                <FareModifierPicker
+                  destinationId={selectedDestination?.destinationId}
+                  seats={bookingData.seats}
+                  basePrice={basePrice}
+                  value={fareSelections}
+                  onChange={setFareSelections}
+                  disabled={isProcessing}
+                />
+
                 {/* Total Amount - Center */}
                 <div className="text-center">
                   <div className="text-lg font-semibold text-gray-700 dark:text-gray-300">Montant Total</div>
@@ -131,6 +131,41 @@ export default function PrintAllVehiclesReport() {
         </table>
       </div>
 
+      {/* Petty cash paid out of the till */}
+      {report.expenses.length > 0 && (
+        <div style={{ marginBottom: '20px' }}>
+          <h2 style={{ fontSize: '16px', fontWeight: 'bold', margin: '0 0 15px 0', color: '#333' }}>
+            DÉPENSES DE CAISSE
+          </h2>
+          <table style={{ width: '100%', borderCollapse: 'collapse', border: '1px solid #ddd', fontSize: '10px' }}>
+            <thead>
+              <tr style={{ backgroundColor: '#f5f5f5' }}>
+                <th style={{ border: '1px solid #ddd', padding: '6px', textAlign: 'left' }}>Heure</th>
+                <th style={{ border: '1px solid #ddd', padding: '6px', textAlign: 'left' }}>Catégorie</th>
+                <th style={{ border: '1px solid #ddd', padding: '6px', textAlign: 'left' }}>Motif</th>
+                <th style={{ border: '1px solid #ddd', padding: '6px', textAlign: 'left' }}>Par</th>
+                <th style={{ border: '1px solid #ddd', padding: '6px', textAlign: 'right' }}>Montant</th>
+              </tr>
+            </thead>
+            <tbody>
+              {report.expenses.map(expense => (
+                <tr key={expense.id}>
+                  <td style={{ border: '1px solid #ddd', padding: '6px' }}>{expense.createdAt}</td>
+                  <td style={{ border: '1px solid #ddd', padding: '6px' }}>{expense.category}</td>
+                  <td style={{ border: '1px solid #ddd', padding: '6px' }}>{expense.note ?? ''}</td>
+                  <td style={{ border: '1px solid #ddd', padding: '6px' }}>{expense.staffName}</td>
+                  <td style={{ border: '1px solid #ddd', padding: '6px', textAlign: 'right' }}>{formatTND(expense.amount)}</td>
+                </tr>
+              ))}
+              <tr style={{ fontWeight: 'bold' }}>
+                <td colSpan={4} style={{ border: '1px solid #ddd', padding: '6px' }}>Total des dépenses</td>
+                <td style={{ border: '1px solid #ddd', padding: '6px', textAlign: 'right' }}>{formatTND(report.totalExpenses)}</td>
+              </tr>
+            </tbody>
+          </table>
+        </div>
+      )}
+
       {/* Detailed Trips by Vehicle */}
       <div style={{ marginBottom: '20px' }}>
         <h2 style={{ fontSize: '16px', fontWeight: 'bold', margin: '0 0 15px 0', color: '#333' }}>
@@ -18,10 +18,11 @@ import {
   X,
   MapPin,
   ArrowUp,
+  ChevronUp,
   RotateCcw,
   Printer,
   Ticket,
-  
+  ArrowLeftRight,
 } from "lucide-react";
 import { useState, useEffect, useCallback } from "react";
 import { useQueue } from "../context/QueueProvider";
@@ -37,7 +38,9 @@ import { Dialog, DialogContent, DialogHeader, DialogTitle, DialogFooter } from "
 import { Input } from "../components/ui/input";
 import api from "../lib/api";
 import { useAuth } from "../context/AuthProvider";
-import { dbClient } from "../services/dbClient";
+import { dbClient, SignOff, QueueReorderError } from "../services/dbClient";
+import { SupervisorOverrideDialog } from "../components/SupervisorOverrideDialog";
+import { SeatTransferDialog } from "../components/SeatTransferDialog";
 import React from "react";
 
 // Simple queue item component (no drag and drop)
@@ -50,15 +53,17 @@ interface QueueItemProps {
   onExitQueue: (licensePlate: string) => void;
   onEndTrip: (queueId: string, licensePlate: string, availableSeats: number, totalSeats: number) => void;
   onMoveToFront: (queueId: string, destinationId: string) => void;
+  onMoveUp: (queue: any) => void;
   onRetryExitPass: (queue: any) => void;
   onConfirmExit: (queue: any) => void;
   onEmergencyRemove: (queue: any) => void;
+  onTransferSeats: (queue: any) => void;
   onPrintDayPass: (queue: any) => void;
   hasRecentDayPass: boolean;
   actionLoading: string | null;
 }
 
-function QueueItem({ queue, getStatusColor, formatTime, getBasePriceForDestination, onVehicleClick, onExitQueue, onEndTrip, onMoveToFront, onRetryExitPass, onConfirmExit, onEmergencyRemove, onPrintDayPass, hasRecentDayPass, actionLoading }: QueueItemProps) {
+function QueueItem({ queue, getStatusColor, formatTime, getBasePriceForDestination, onVehicleClick, onExitQueue, onEndTrip, onMoveToFront, onMoveUp, onRetryExitPass, onConfirmExit, onEmergencyRemove, onTransferSeats, onPrintDayPass, hasRecentDayPass, actionLoading }: QueueItemProps) {
   const basePrice = getBasePriceForDestination(queue.destinationName) ?? queue.basePrice;
   const hasBookedSeats = queue.availableSeats < queue.totalSeats;
 
@@ -215,6 +220,22 @@ function QueueItem({ queue, getStatusColor, formatTime, getBasePriceForDestinati
                 })()}
               </div>
             )}
+          {/* Move up one place, swapping with the vehicle ahead */}
+          {queue.status === 'WAITING' && (
+            <Button 
+              variant="outline" 
+              size="sm"
+              className="text-purple-600 border-purple-300 hover:bg-purple-50"
+              onClick={(e) => {
+                e.stopPropagation();
+                onMoveUp(queue);
+              }}
+              disabled={actionLoading === queue.id}
+              title="Avancer d'une place"
+            >
+              <ChevronUp className="h-4 w-4" />
+            </Button>
+          )}
           {/* Move to Front Button - only show if no booked seats */}
           {!hasBookedSeats && (
             <Button 
@@ -257,6 +278,23 @@ function QueueItem({ queue, getStatusColor, formatTime, getBasePriceForDestinati
             </Button>
           )}
 
+          {/* Transfer Seats Button - move some or all booked seats to another vehicle of the line */}
+          {hasBookedSeats && (
+            <Button 
+              variant="outline" 
+              size="sm"
+              className="text-purple-600 border-purple-300 hover:bg-purple-50"
+              onClick={(e) => {
+                e.stopPropagation();
+                onTransferSeats(queue);
+              }}
+              disabled={actionLoading === queue.licensePlate}
+              title="Transférer des sièges vers un autre véhicule"
+            >
+              <ArrowLeftRight className="h-4 w-4" />
+            </Button>
+          )}
+
           {/* Retry Exit Pass Button - only show if vehicle is READY (fully booked) */}
           {queue.status === 'READY' && (
             <Button 
@@ -420,6 +458,12 @@ export default function QueueManagement() {
   const [selectedDestination, setSelectedDestination] = useState<string | null>(null);
   const [lastUpdated, setLastUpdated] = useState<Date | null>(null);
   const [actionLoading, setActionLoading] = useState<string | null>(null);
+  // Vehicle waiting for a supervisor override before its emergency removal
+  const [emergencyQueue, setEmergencyQueue] = useState<any | null>(null);
+  // Trip ending below the approval policy's fill threshold, waiting for a supervisor's sign-off
+  const [endTripSignOff, setEndTripSignOff] = useState<{ queueId: string; licensePlate: string; bookedSeats: number; totalSeats: number } | null>(null);
+  // Vehicle whose booked seats the cashier is moving to another vehicle
+  const [transferQueue, setTransferQueue] = useState<any | null>(null);
   const [vehiclesWithRecentDayPass, setVehiclesWithRecentDayPass] = useState<Set<string>>(new Set());
 
   // Test function to create a day pass for testing (expose to window for debugging)
@@ -527,6 +571,7 @@ export default function QueueManagement() {
   const [selectedVehicle, setSelectedVehicle] = useState<any | null>(null);
   const [search, setSearch] = useState("");
   const [addVehicleError, setAddVehicleError] = useState<string | null>(null);
+  const [quickEntryEnabled, setQuickEntryEnabled] = useState(false);
   const [isInputFocused, setIsInputFocused] = useState(false);
   const [routes, setRoutes] = useState<any[]>([]);
   
@@ -817,10 +862,11 @@ export default function QueueManagement() {
     }
   }, [showAddVehicleModal]);
 
-  // When modal opens, clear error and reset focus state
+  // When modal opens, clear error, reset focus state and check whether scanned plates queue directly
   useEffect(() => {
     if (showAddVehicleModal) {
       setAddVehicleError(null);
+      dbClient.getQuickEntryPolicy().then(policy => setQuickEntryEnabled(policy.enabled)).catch(() => setQuickEntryEnabled(false));
       setIsInputFocused(false);
     }
   }, [showAddVehicleModal]);
@@ -1015,6 +1061,33 @@ export default function QueueManagement() {
     }
   };
 
+  // Scan plate → queue: Enter in the search queues the plate for its default destination
+  const handleQuickEntry = async () => {
+    const normalize = (plate: string) => plate.toUpperCase().replace(/[^A-Z0-9]/g, '');
+    const typed = normalize(search);
+    if (!typed) return;
+    const vehicle = vehicles.find(v => normalize(v.licensePlate || '') === typed);
+    const licensePlate = vehicle?.licensePlate || search.trim();
+    setActionLoading(licensePlate);
+    setAddVehicleError(null);
+    try {
+      const staffId = SessionManager.getInstance().getCurrentSession()?.staff?.id;
+      const entry = await dbClient.quickEnterQueue(licensePlate, staffId);
+      addNotification({
+        type: 'success',
+        title: 'Véhicule ajouté',
+        message: `${entry.licensePlate} ajouté à la file pour ${entry.destinationName}`,
+        duration: 4000
+      });
+      setSearch("");
+      debouncedRefreshQueues();
+    } catch (error: any) {
+      setAddVehicleError(typeof error === 'string' ? error : error?.message || 'Échec de l\'entrée en file');
+    } finally {
+      setActionLoading(null);
+    }
+  };
+
   // Handle day pass purchase (extracted for keyboard shortcuts)
   const handleDayPassPurchase = async () => {
     if (!selectedVehicle) return;
@@ -1446,13 +1519,22 @@ export default function QueueManagement() {
     );
     
     if (!confirmed) return;
-    
+
+    const policy = await dbClient.getApprovalPolicy().catch(() => null);
+    if (policy && bookedSeats * 100 < policy.dispatchMinPercent * totalSeats) {
+      setEndTripSignOff({ queueId, licensePlate, bookedSeats, totalSeats });
+      return;
+    }
+    await finishEndTrip(queueId, licensePlate);
+  };
+
+  const finishEndTrip = async (queueId: string, licensePlate: string, signOff?: SignOff) => {
     setActionLoading(licensePlate);
     try { beginOptimisticSuppression({ licensePlate, durationMs: 2500 }); } catch {}
     
     try {
       const staffId = currentStaff?.id || undefined;
-      const result = await dbClient.endTripWithPartialCapacity(queueId, staffId);
+      const result = await dbClient.endTripWithPartialCapacity(queueId, staffId, signOff?.supervisorOverride, signOff?.approvalId);
       setActionLoading(null);
       
       addNotification({
@@ -1475,6 +1557,79 @@ export default function QueueManagement() {
     }
   };
 
+  // End of service: every vehicle of the line with sold seats leaves, empty ones stay queued
+  const handleDispatchAll = async (destinationId: string, destinationName: string) => {
+    const confirmed = window.confirm(
+      `Faire partir tous les véhicules pour ${destinationName} ?\n\n` +
+      `Cette action va:\n` +
+      `• Imprimer un ticket de sortie pour chaque véhicule ayant des places vendues\n` +
+      `• Retirer ces véhicules de la file d'attente\n` +
+      `• Laisser les véhicules vides dans la file`
+    );
+    if (!confirmed) return;
+
+    setActionLoading(destinationId);
+    try {
+      const summary = await dbClient.dispatchAll(destinationId, currentStaff?.id || undefined);
+      addNotification({
+        type: summary.printError ? 'warning' : 'success',
+        title: 'Départs enregistrés',
+        message: summary.printError ? `${summary.message} Impression: ${summary.printError}` : summary.message,
+        duration: 8000
+      });
+      debouncedRefreshQueues();
+    } catch (error: any) {
+      addNotification({
+        type: 'error',
+        title: 'Échec des départs',
+        message: error?.message || String(error),
+        duration: 5000
+      });
+    } finally {
+      setActionLoading(null);
+    }
+  };
+
+  // Swap a vehicle with the one ahead of it. The versions of every entry read go along, so a
+  // queue another terminal changed in the meantime is refused and reloaded instead of clobbered.
+  const handleMoveUp = async (queue: any, destinationQueues: any[], destinationId?: string) => {
+    if (!destinationId) return;
+    const ordered = [...destinationQueues].sort((a, b) => (a.queuePosition || 0) - (b.queuePosition || 0));
+    const index = ordered.findIndex(q => q.id === queue.id);
+    if (index <= 0) return;
+    const ahead = ordered[index - 1];
+    const expectedVersions = Object.fromEntries(ordered.map(q => [q.id, q.version]));
+
+    setActionLoading(queue.id);
+    try {
+      await dbClient.updateQueuePositions(
+        destinationId,
+        [
+          { queueId: queue.id, position: ahead.queuePosition },
+          { queueId: ahead.id, position: queue.queuePosition },
+        ],
+        expectedVersions
+      );
+      addNotification({
+        type: 'success',
+        title: 'Véhicule avancé',
+        message: `${queue.licensePlate} passe devant ${ahead.licensePlate}`,
+        duration: 3000
+      });
+    } catch (error: any) {
+      const reorderError = error as QueueReorderError;
+      addNotification({
+        type: reorderError?.code === 'STALE_STATE' ? 'warning' : 'error',
+        title: reorderError?.code === 'STALE_STATE' ? 'File modifiée entre-temps' : 'Échec du déplacement',
+        message: reorderError?.message || String(error),
+        duration: 5000
+      });
+    } finally {
+      fetchQueueForDestination(destinationId);
+      setActionLoading(null);
+    }
+  };
+
   const handleMoveToFront = async (queueId: string, destinationId: string) => {
     setActionLoading(queueId);
     
@@ -1607,11 +1762,20 @@ export default function QueueManagement() {
     
     if (!confirmed) return;
     
+    // Refunds need a supervisor: the removal continues from the override dialog
+    setEmergencyQueue(queue);
+  };
+
+  const confirmEmergencyRemove = async (signOff: SignOff) => {
+    const queue = emergencyQueue;
+    setEmergencyQueue(null);
+    if (!queue) return;
+
     setActionLoading(queue.licensePlate);
     
     try {
       // Call emergency removal function
-      const result = await dbClient.emergencyRemoveVehicle(queue.licensePlate);
+      const result = await dbClient.emergencyRemoveVehicle(queue.licensePlate, signOff.supervisorOverride, signOff.approvalId);
       
       addNotification({
         type: 'success',
@@ -1804,11 +1968,22 @@ export default function QueueManagement() {
                   onChange={e => setSearch(e.target.value)}
                   onFocus={() => setIsInputFocused(true)}
                   onBlur={() => setIsInputFocused(false)}
+                  onKeyDown={e => {
+                    if (e.key === 'Enter' && quickEntryEnabled) {
+                      e.preventDefault();
+                      handleQuickEntry();
+                    }
+                  }}
                   className="w-full"
                 />
                 <div className="mt-1 text-xs text-gray-500">
                   Appuyez sur <kbd className="px-1 py-0.5 bg-gray-100 rounded text-xs">Alt</kbd> pour basculer entre la recherche et la sélection
                 </div>
+                {quickEntryEnabled && (
+                  <div className="mt-1 text-xs text-blue-700">
+                    Entrée rapide : scannez la plaque puis <kbd className="px-1 py-0.5 bg-gray-100 rounded text-xs">Entrée</kbd> pour l'ajouter à sa destination par défaut
+                  </div>
+                )}
               </div>
               
               {vehiclesLoading ? (
@@ -2371,18 +2546,18 @@ export default function QueueManagement() {
                   variant="outline"
                   onClick={async () => {
                     try {
-                      await thermalPrinter.reprintLastDayPass();
+                      const message = await thermalPrinter.reprintLastDayPass(currentStaff?.id);
                       addNotification({
                         type: 'success',
                         title: 'Réimpression réussie',
-                        message: 'Le dernier pass journalier a été réimprimé',
+                        message,
                         duration: 3000
                       });
                     } catch (error) {
                       addNotification({
                         type: 'error',
                         title: 'Erreur de réimpression',
-                        message: 'Impossible de réimprimer le dernier pass journalier',
+                        message: String(error),
                         duration: 3000
                       });
                     }
@@ -2613,6 +2788,37 @@ export default function QueueManagement() {
                           return null;
                         })()}
 
+                        {summary && (
+                          <Button
+                            variant="outline"
+                            size="sm"
+                            className="flex items-center gap-1"
+                            title="Imprimer la liste des véhicules pour l'entrée du quai"
+                            onClick={() => {
+                              const staffName = currentStaff ? `${currentStaff.firstName} ${currentStaff.lastName}` : undefined;
+                              thermalPrinter.printQueueBoard(summary.destinationId, staffName).catch(error => {
+                                addNotification({ type: 'error', title: 'Impression', message: String(error) });
+                              });
+                            }}
+                          >
+                            <Printer className="h-4 w-4" />
+                            Tableau
+                          </Button>
+                        )}
+
+                        {summary && (currentStaff?.role === 'SUPERVISOR' || currentStaff?.role === 'ADMIN') && (
+                          <Button
+                            variant="outline"
+                            size="sm"
+                            className="text-orange-700 border-orange-300 hover:bg-orange-50"
+                            title="Fin de service : faire partir tous les véhicules ayant des places vendues"
+                            disabled={actionLoading === summary.destinationId}
+                            onClick={() => handleDispatchAll(summary.destinationId, summary.destinationName)}
+                          >
+                            Tout faire partir
+                          </Button>
+                        )}
+
                         <div className="flex items-center gap-4 text-sm text-gray-600">
                           <span>Véhicules: <span className="font-semibold text-gray-900">{summary?.totalVehicles || 0}</span></span>
                           {isConnected && (
@@ -2690,9 +2896,11 @@ export default function QueueManagement() {
                                             onExitQueue={handleExitQueue}
                                             onEndTrip={handleEndTrip}
                                             onMoveToFront={handleMoveToFront}
+                                            onMoveUp={(q) => handleMoveUp(q, destinationQueues, summary?.destinationId)}
                                             onRetryExitPass={handleRetryExitPass}
                                             onConfirmExit={handleConfirmExit}
                                             onEmergencyRemove={handleEmergencyRemove}
+                                            onTransferSeats={setTransferQueue}
                                             onPrintDayPass={handlePrintDayPass}
                                             hasRecentDayPass={queue.licensePlate ? vehiclesWithRecentDayPass.has(queue.licensePlate) : false}
                                             actionLoading={actionLoading}
@@ -2723,9 +2931,11 @@ export default function QueueManagement() {
                                             onExitQueue={handleExitQueue}
                                             onEndTrip={handleEndTrip}
                                             onMoveToFront={handleMoveToFront}
+                                            onMoveUp={(q) => handleMoveUp(q, destinationQueues, summary?.destinationId)}
                                             onRetryExitPass={handleRetryExitPass}
                                             onConfirmExit={handleConfirmExit}
                                             onEmergencyRemove={handleEmergencyRemove}
+                                            onTransferSeats={setTransferQueue}
                                             onPrintDayPass={handlePrintDayPass}
                                             hasRecentDayPass={queue.licensePlate ? vehiclesWithRecentDayPass.has(queue.licensePlate) : false}
                                             actionLoading={actionLoading}
@@ -2762,9 +2972,11 @@ export default function QueueManagement() {
                                                 onExitQueue={handleExitQueue}
                                                 onEndTrip={handleEndTrip}
                                                 onMoveToFront={handleMoveToFront}
+                                                onMoveUp={(q) => handleMoveUp(q, destinationQueues, summary?.destinationId)}
                                                 onRetryExitPass={handleRetryExitPass}
                                                 onConfirmExit={handleConfirmExit}
                                                 onEmergencyRemove={handleEmergencyRemove}
+                                                onTransferSeats={setTransferQueue}
                                                 onPrintDayPass={handlePrintDayPass}
                                                 hasRecentDayPass={queue.licensePlate ? vehiclesWithRecentDayPass.has(queue.licensePlate) : false}
                                                 actionLoading={actionLoading}
@@ -2806,9 +3018,11 @@ export default function QueueManagement() {
                                           onExitQueue={handleExitQueue}
                                           onEndTrip={handleEndTrip}
                                           onMoveToFront={handleMoveToFront}
+                                          onMoveUp={(q) => handleMoveUp(q, destinationQueues, summary?.destinationId)}
                                           onRetryExitPass={handleRetryExitPass}
                                           onConfirmExit={handleConfirmExit}
                                           onEmergencyRemove={handleEmergencyRemove}
+                                          onTransferSeats={setTransferQueue}
                                           onPrintDayPass={handlePrintDayPass}
                                           hasRecentDayPass={queue.licensePlate ? vehiclesWithRecentDayPass.has(queue.licensePlate) : false}
                                           actionLoading={actionLoading}
@@ -2849,6 +3063,47 @@ export default function QueueManagement() {
         )}
       </div>
 
+      <SeatTransferDialog
+        source={transferQueue}
+        onDone={(transfer) => {
+          setTransferQueue(null);
+          addNotification({
+            type: 'success',
+            title: 'Sièges transférés',
+            message: `${transfer.message} (${transfer.ticketsReprinted} ticket(s) réimprimé(s))`,
+            duration: 4000
+          });
+          debouncedRefreshQueues();
+        }}
+        onCancel={() => setTransferQueue(null)}
+      />
+
+      <SupervisorOverrideDialog
+        open={emergencyQueue !== null}
+        title="Autorisation superviseur"
+        description={emergencyQueue ? `Suppression d'urgence de ${emergencyQueue.licensePlate} et remboursement des réservations` : undefined}
+        approval={emergencyQueue ? { action: 'REFUND', subject: emergencyQueue.licensePlate, details: { destinationName: emergencyQueue.destinationName } } : undefined}
+        onConfirm={confirmEmergencyRemove}
+        onCancel={() => setEmergencyQueue(null)}
+      />
+
+      <SupervisorOverrideDialog
+        open={endTripSignOff !== null}
+        title="Autorisation superviseur"
+        description={endTripSignOff ? `${endTripSignOff.licensePlate} part avec ${endTripSignOff.bookedSeats}/${endTripSignOff.totalSeats} places vendues, sous le seuil de remplissage` : undefined}
+        approval={endTripSignOff ? {
+          action: 'DISPATCH_BELOW_THRESHOLD',
+          subject: endTripSignOff.licensePlate,
+          details: { seatsSold: endTripSignOff.bookedSeats, totalSeats: endTripSignOff.totalSeats },
+        } : undefined}
+        onConfirm={signOff => {
+          const trip = endTripSignOff;
+          setEndTripSignOff(null);
+          if (trip) finishEndTrip(trip.queueId, trip.licensePlate, signOff);
+        }}
+        onCancel={() => setEndTripSignOff(null)}
+      />
+
       {/* Floating Action Button */}
       <div className="fixed bottom-20 right-6 z-50">
         <Button
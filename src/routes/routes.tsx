@@ -1,5 +1,6 @@
 import React from 'react';
 import { RoutesTable } from '../components/RoutesTable';
+import { PricingRulesSection } from '../components/PricingRulesSection';
 import { useAuth } from '../context/AuthProvider';
 import { Card } from '../components/ui/card';
 import { Badge } from '../components/ui/badge';
@@ -34,6 +35,8 @@ export default function RoutesPage() {
 
       {/* Routes Table */}
       <RoutesTable />
+
+      {(isSupervisor || isAdmin) && <PricingRulesSection staffId={currentStaff?.id} />}
     </div>
   );
 } 
\ No newline at end of file
@@ -5,10 +5,32 @@ import { Label } from "../components/ui/label";
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "../components/ui/card";
 import { SystemStatus } from "../components/SystemStatus";
 import { UpdateSection } from "../components/UpdateSection";
+import { ShortcutsSection } from "../components/ShortcutsSection";
+import { CrashReportsSection } from "../components/CrashReportsSection";
+import { LanguageSection } from "../components/LanguageSection";
+import { CashCountSection } from "../components/CashCountSection";
+import { SecretsSection } from "../components/SecretsSection";
+import { ProxyAllowlistSection } from "../components/ProxyAllowlistSection";
+import { TerminalSection } from "../components/TerminalSection";
+import { NetworkInterfacesSection } from "../components/NetworkInterfacesSection";
+import { TerminalMessageCard } from "../components/TerminalMessageCard";
+import { QueueBoardSection } from "../components/QueueBoardSection";
+import { AnnouncementSection } from "../components/AnnouncementSection";
+import { CancellationPolicySection } from "../components/CancellationPolicySection";
+import { ReprintPolicySection } from "../components/ReprintPolicySection";
+import { PrintHistoryCard } from "../components/PrintHistoryCard";
+import { TicketEscrowCard } from "../components/TicketEscrowCard";
+import { RetentionSection } from "../components/RetentionSection";
+import { SelfCheckInSection } from "../components/SelfCheckInSection";
+import { BusinessHoursSection } from "../components/BusinessHoursSection";
+import { TrainingSection } from "../components/TrainingSection";
+import { RouteAliasesSection } from "../components/RouteAliasesSection";
+import { QuickEntrySection } from "../components/QuickEntrySection";
 import AppControls from "../components/AppControls";
 import { Moon, Sun, RefreshCw, Save, TestTube, Activity, Wifi, WifiOff, Clock, Zap, AlertCircle, CheckCircle } from "lucide-react";
 import { useState, useEffect } from "react";
 import { thermalPrinter } from "../services/thermalPrinterService";
+import { dbClient } from "../services/dbClient";
 import { invoke } from "@tauri-apps/api/tauri";
 import { getLocalStorage, setLocalStorage } from "../lib/storage";
 import { useMQTT } from "../lib/useMQTT";
@@ -192,7 +214,9 @@ export default function Settings() {
     setTestingPrinter(true);
     try {
       const status = await thermalPrinter.testConnectionManual(printerIp.trim(), parseInt(printerPort.trim()));
-      if (status.connected) {
+      if (status.connected && status.error) {
+        setPrinterMessage(`⚠️ Imprimante joignable mais pas prête: ${status.error}`);
+      } else if (status.connected) {
         setPrinterMessage(`✅ Test réussi: ${printerIp.trim()}:${printerPort.trim()}`);
       } else {
         setPrinterMessage(`❌ Test échoué: ${status.error || 'Imprimante non accessible'}`);
@@ -446,11 +470,102 @@ export default function Settings() {
           </CardContent>
         </Card>
 
+        {/* Kiosk Section */}
+        <Card>
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <span>Mode borne et fenêtres</span>
+            </CardTitle>
+            <CardDescription>
+              Transforme ce poste en borne libre-service : destinations et achat par carte uniquement. Un code PIN superviseur est demandé pour en sortir. La position des fenêtres sur les écrans est mémorisée.
+            </CardDescription>
+          </CardHeader>
+          <CardContent>
+            <Button
+              variant="outline"
+              onClick={() => dbClient.startKioskMode().catch((e) => alert(String(e)))}
+              className="flex items-center gap-2"
+            >
+              <Zap className="h-4 w-4" />
+              Démarrer le mode borne
+            </Button>
+            <Button
+              variant="outline"
+              onClick={() => dbClient.resetWindowLayout().catch((e) => alert(String(e)))}
+              className="mt-2 flex items-center gap-2"
+            >
+              <RefreshCw className="h-4 w-4" />
+              Réinitialiser la disposition des fenêtres
+            </Button>
+          </CardContent>
+        </Card>
+
+        {/* Cash count at shift close */}
+        <CashCountSection />
+
+        {/* Identity of this cashier PC */}
+        <TerminalSection />
+
+        {/* Interfaces used to find the local node and printers */}
+        <NetworkInterfacesSection />
+
+        {/* Messages to the other terminals */}
+        <TerminalMessageCard />
+
+        {/* Queue boards printed for the lane entrances */}
+        <QueueBoardSection />
+
+        {/* Departure calls on the PA */}
+        <AnnouncementSection />
 
+        {/* Cancellation window and late fees */}
+        <CancellationPolicySection />
+
+        {/* Duplicatas per ticket and reprint fees */}
+        <ReprintPolicySection />
+
+        {/* Printed tickets, searchable and exportable to PDF */}
+        <PrintHistoryCard />
+
+        {/* Tickets issued without a printer, printed later or texted */}
+        <TicketEscrowCard />
+
+        {/* Days kept by the tables that only grow, purged nightly */}
+        <RetentionSection />
+
+        {/* Drivers checking in at the kiosk with their PIN */}
+        <SelfCheckInSection />
+
+        {/* Opening hours; booking and queue entry refused outside them */}
+        <BusinessHoursSection />
+
+        {/* Practice on a copy of the station, for new cashiers */}
+        <TrainingSection />
+
+        {/* Other names of the destinations for the booking search */}
+        <RouteAliasesSection />
+
+        {/* Scanned plates queued for their default destination */}
+        <QuickEntrySection />
+
+        {/* Passwords and tokens kept in the OS credential store */}
+        <SecretsSection />
+
+        {/* Local node endpoints reachable through the proxy */}
+        <ProxyAllowlistSection />
+
+        {/* Station Language */}
+        <LanguageSection />
+
+        {/* Shortcuts Section */}
+        <ShortcutsSection />
 
         {/* Update Section */}
         <UpdateSection />
 
+        {/* Crash Reports */}
+        <CrashReportsSection />
+
         {/* System Status */}
         <SystemStatus />
       </div>
@@ -13,6 +13,12 @@ import { useNavigate } from 'react-router-dom';
 import { Select } from '../components/ui/select';
 import MunicipalityService from '../services/municipalityService';
 import { keyboardShortcuts } from '../services/keyboardShortcuts';
+import { TripLeaderboardCard } from '../components/TripLeaderboardCard';
+import { QueueClosingCard } from '../components/QueueClosingCard';
+import { LoadingTimeoutCard } from '../components/LoadingTimeoutCard';
+import { VehiclePositionsCard } from '../components/VehiclePositionsCard';
+import { QueueReplayCard } from '../components/QueueReplayCard';
+import { DestinationSuspensionCard } from '../components/DestinationSuspensionCard';
 
 interface Vehicle {
   id: string;
@@ -71,6 +77,8 @@ const SupervisorVehicleManagement: React.FC = () => {
   const [editingPhoneVehicleId, setEditingPhoneVehicleId] = useState<string | null>(null);
   const [phoneDraft, setPhoneDraft] = useState('');
   const [isSavingPhone, setIsSavingPhone] = useState(false);
+  const [capacityDraft, setCapacityDraft] = useState<string | null>(null);
+  const [isSavingCapacity, setIsSavingCapacity] = useState(false);
   const [showActiveOnly, setShowActiveOnly] = useState(false);
   const [showAvailableOnly, setShowAvailableOnly] = useState(false);
 
@@ -209,21 +217,42 @@ const SupervisorVehicleManagement: React.FC = () => {
   const savePhone = async (vehicleId: string) => {
     try {
       setIsSavingPhone(true);
-      await dbClient.updateVehiclePhone(vehicleId, phoneDraft.trim() ? phoneDraft.trim() : undefined);
-      setVehicles(prev => prev.map(v => v.id === vehicleId ? { ...v, phoneNumber: phoneDraft.trim() || null } : v));
+      const { phoneNumber, sharedWith } = await dbClient.updateVehiclePhone(vehicleId, phoneDraft.trim() ? phoneDraft.trim() : undefined);
+      setVehicles(prev => prev.map(v => v.id === vehicleId ? { ...v, phoneNumber } : v));
       if (vehicleDetails && vehicleDetails.id === vehicleId) {
-        setVehicleDetails({ ...vehicleDetails, phoneNumber: phoneDraft.trim() || null });
+        setVehicleDetails({ ...vehicleDetails, phoneNumber });
       }
       addNotification({ type: 'success', title: 'Téléphone mis à jour', message: 'Numéro enregistré avec succès.' });
+      if (sharedWith.length > 0) {
+        addNotification({ type: 'warning', title: 'Numéro partagé', message: `Ce numéro est aussi celui de: ${sharedWith.join(', ')}` });
+      }
       setEditingPhoneVehicleId(null);
       setPhoneDraft('');
     } catch (e: any) {
-      addNotification({ type: 'error', title: 'Erreur', message: e?.message || "Échec de la mise à jour du numéro." });
+      addNotification({ type: 'error', title: 'Erreur', message: typeof e === 'string' ? e : e?.message || "Échec de la mise à jour du numéro." });
     } finally {
       setIsSavingPhone(false);
     }
   };
 
+  const saveCapacity = async (vehicleId: string) => {
+    try {
+      setIsSavingCapacity(true);
+      const change = await dbClient.updateVehicleCapacity(vehicleId, Number(capacityDraft), currentStaff?.id);
+      setVehicles(prev => prev.map(v => v.id === vehicleId ? { ...v, capacity: change.capacity } : v));
+      if (vehicleDetails && vehicleDetails.id === vehicleId) {
+        setVehicleDetails({ ...vehicleDetails, capacity: change.capacity });
+      }
+      const queued = change.availableSeats !== null ? ` En file: ${change.availableSeats} places libres.` : '';
+      addNotification({ type: 'success', title: 'Capacité mise à jour', message: `${change.licensePlate}: ${change.previousCapacity} → ${change.capacity} places.${queued}` });
+      setCapacityDraft(null);
+    } catch (e: any) {
+      addNotification({ type: 'error', title: 'Erreur', message: typeof e === 'string' ? e : e?.message || "Échec de la mise à jour de la capacité." });
+    } finally {
+      setIsSavingCapacity(false);
+    }
+  };
+
   // Function to select default destination
   const selectDefaultDestination = (stationId: string) => {
     setForm(prev => ({
@@ -474,6 +503,10 @@ const SupervisorVehicleManagement: React.FC = () => {
         setLicensePlateFirst('');
         setLicensePlateSecond('');
         addNotification({ type: 'success', title: 'Véhicule créé', message: 'Le véhicule a été créé avec succès.' });
+        const sharedPhone = result.match(/\. ([^.]+: .+)$/);
+        if (sharedPhone) {
+          addNotification({ type: 'warning', title: 'Numéro partagé', message: sharedPhone[1] });
+        }
         
         // Optional: Refresh routes to ensure we have the latest data
         fetchRoutes();
@@ -481,7 +514,7 @@ const SupervisorVehicleManagement: React.FC = () => {
         addNotification({ type: 'error', title: 'Erreur', message: 'Impossible de récupérer l\'ID du véhicule créé.' });
       }
     } catch (error: any) {
-      addNotification({ type: 'error', title: 'Erreur', message: error.message || 'Une erreur est survenue.' });
+      addNotification({ type: 'error', title: 'Erreur', message: typeof error === 'string' ? error : error.message || 'Une erreur est survenue.' });
     }
     
     setIsSubmitting(false);
@@ -490,6 +523,7 @@ const SupervisorVehicleManagement: React.FC = () => {
   // Fetch vehicle details by ID (optional: if you want to fetch fresh data)
   const fetchVehicleDetails = async (id: string) => {
     setIsVehicleDetailsLoading(true);
+    setCapacityDraft(null);
     // Use already loaded vehicle data instead of making API call
     const vehicle = vehicles.find(v => v.id === id);
     if (vehicle) {
@@ -703,7 +737,22 @@ const SupervisorVehicleManagement: React.FC = () => {
                   <div className="grid grid-cols-2 gap-3">
                     <div className="p-3 rounded-md bg-zinc-50 dark:bg-zinc-800 border">
                       <div className="text-xs text-muted-foreground">Capacité</div>
-                      <div className="text-lg font-semibold">{vehicleDetails.capacity} places</div>
+                      {capacityDraft !== null ? (
+                        <div className="flex items-center gap-2 mt-1">
+                          <Input value={capacityDraft} onChange={(e) => setCapacityDraft(e.target.value)} type="number" min="1" max="50" className="w-full" />
+                          <Button size="sm" className="bg-green-600 hover:bg-green-700" disabled={isSavingCapacity} onClick={() => saveCapacity(vehicleDetails.id)}>
+                            {isSavingCapacity ? <Loader2 className="h-4 w-4 animate-spin" /> : <Check className="h-4 w-4" />}
+                          </Button>
+                          <Button size="sm" variant="outline" onClick={() => setCapacityDraft(null)}>
+                            <X className="h-4 w-4" />
+                          </Button>
+                        </div>
+                      ) : (
+                        <div className="flex items-center gap-2">
+                          <span className="text-lg font-semibold">{vehicleDetails.capacity} places</span>
+                          <Button size="sm" variant="outline" onClick={() => setCapacityDraft(String(vehicleDetails.capacity))}>Modifier</Button>
+                        </div>
+                      )}
                     </div>
                     <div className="p-3 rounded-md bg-zinc-50 dark:bg-zinc-800 border">
                       <div className="text-xs text-muted-foreground">Téléphone</div>
@@ -798,6 +847,17 @@ const SupervisorVehicleManagement: React.FC = () => {
         </div>
       </div>
 
+      <TripLeaderboardCard />
+
+      <DestinationSuspensionCard />
+
+      <LoadingTimeoutCard />
+
+      <VehiclePositionsCard />
+
+      <QueueClosingCard />
+
+      <QueueReplayCard />
 
       {/* New Vehicle Form Dialog */}
       <Dialog open={showRequestForm} onOpenChange={setShowRequestForm}>
@@ -9,6 +9,8 @@ import { useNotifications } from '../context/NotificationProvider';
 import { dbClient, VehicleDailyReport, AllVehiclesDailyReport } from '../services/dbClient';
 import { Loader2, FileText, Download, Calendar, Car, BarChart3, TrendingUp, Users, MapPin, Clock, DollarSign } from 'lucide-react';
 import { useNavigate } from 'react-router-dom';
+import { DriverSettlementsCard } from '../components/DriverSettlementsCard';
+import { VehicleTripsCard } from '../components/VehicleTripsCard';
 
 const VehicleReports: React.FC = () => {
   const { currentStaff } = useAuth();
@@ -283,6 +285,13 @@ const VehicleReports: React.FC = () => {
         </div>
       </Card>
 
+      {reportType === 'individual' && selectedVehicleId && (
+        <>
+          <DriverSettlementsCard vehicleId={selectedVehicleId} date={selectedDate} />
+          <VehicleTripsCard vehicleId={selectedVehicleId} date={selectedDate} />
+        </>
+      )}
+
       {/* Vehicle Selection Dialog */}
       <Dialog open={showVehicleSelector} onOpenChange={setShowVehicleSelector}>
         <DialogContent className="max-w-2xl">
@@ -13,6 +13,7 @@ import api from '../lib/api';
 import { TicketPrintout } from '../components/TicketPrintout';
 import { renderToString } from 'react-dom/server';
 import { thermalPrinter } from '../services/thermalPrinterService';
+import { GlobalSearchCard } from '../components/GlobalSearchCard';
 
 type VerificationStatus = 'success' | 'not_found' | 'already_verified' | 'error';
 
@@ -327,6 +328,9 @@ export default function VerifyTicket() {
             </div>
           </Card>
         )}
+
+        {/* Lost ticket or pass: search by anything the customer remembers */}
+        <GlobalSearchCard />
       </div>
     </div>
   );
@@ -10,6 +10,236 @@ export interface QueueSummaryDto {
   readyVehicles: number;
   governorate?: string | null;
   delegation?: string | null;
+  suspension?: DestinationSuspension | null;
+}
+
+// Route suspended for weather or roadworks: no queue entry nor booking until it lifts
+export interface DestinationSuspension {
+  id: string;
+  destinationId: string;
+  destinationName: string;
+  reason: string;
+  suspendedAt: string;
+  until: string | null;
+  suspendedByName: string | null;
+}
+
+export type PassType = 'DAY' | 'WEEK' | 'MONTH';
+
+export type PaymentMethod = 'CASH' | 'CARD' | 'E_WALLET' | 'ON_ACCOUNT';
+
+// One leg of a split payment (e.g. cash + card); the legs must add up to the booking total
+export interface PaymentPart {
+  method: PaymentMethod;
+  amount: number;
+}
+
+export type PricingRuleType = 'CHILD_FARE' | 'LUGGAGE';
+
+// Child fare reduction or luggage supplement; either amount or percent (of the base price) is set.
+// destinationId null applies it to every destination.
+export interface PricingRule {
+  id?: string;
+  ruleType: PricingRuleType;
+  label: string;
+  amount: number | null;
+  percent: number | null;
+  destinationId: string | null;
+  isActive: boolean;
+}
+
+// Child seats or bags picked by the cashier for one booking call
+export interface FareSelection {
+  ruleId: string;
+  quantity: number;
+}
+
+// A modifier line on a booking; amount is negative for a reduction
+export interface FareModifier {
+  ruleId: string;
+  ruleType: PricingRuleType;
+  label: string;
+  quantity: number;
+  amount: number;
+}
+
+export type ShortcutAction =
+  | 'TOGGLE_FULLSCREEN'
+  | 'TOGGLE_WINDOW'
+  | 'REPRINT_BOOKING_TICKET'
+  | 'REPRINT_ENTRY_TICKET'
+  | 'REPRINT_EXIT_TICKET'
+  | 'REPRINT_EXIT_PASS'
+  | 'REPRINT_DAY_PASS';
+
+export interface ShortcutSettings {
+  shortcuts: { action: ShortcutAction; accelerator: string }[];
+  trayActions: ShortcutAction[];
+}
+
+export type UpdateChannel = 'stable' | 'beta';
+
+export interface UpdateSettings {
+  channel: UpdateChannel;
+  deferredUntil?: string | null;
+  windowStart: string;
+  windowEnd: string;
+  rolloutBucket: number;
+}
+
+export interface UpdateStatusDto {
+  currentVersion: string;
+  channel: UpdateChannel;
+  available: boolean;
+  version?: string | null;
+  notes?: string | null;
+  installAllowedNow: boolean;
+  settings: UpdateSettings;
+}
+
+export interface ClockStatusDto {
+  driftSeconds: number;
+  thresholdSeconds: number;
+  drifting: boolean;
+  checkedAt: string;
+}
+
+export type StationLanguage = 'fr' | 'ar';
+
+export interface LanguageSettingsDto {
+  language: StationLanguage;
+  bilingualTickets: boolean;
+}
+
+export interface CrashReportSummaryDto {
+  id: string;
+  createdAt: string;
+  appVersion: string;
+  message: string;
+  location?: string | null;
+  uploadedAt?: string | null;
+}
+
+export interface PaymentMethodSettingDto {
+  method: PaymentMethod;
+  label: string;
+  enabled: boolean;
+}
+
+export interface PaymentTotalDto {
+  method: PaymentMethod | 'SPLIT' | string;
+  label: string;
+  count: number;
+  amount: number;
+}
+
+// A company booking on account; balance is what it owes this station
+export interface CustomerDto {
+  id: string;
+  name: string;
+  phoneNumber?: string | null;
+  taxId?: string | null;
+  creditLimit: number;
+  balance: number;
+  isActive: boolean;
+  createdAt: string;
+}
+
+export interface StatementLineDto {
+  date: string;
+  kind: 'BOOKING' | 'PAYMENT';
+  reference: string;
+  destinationName?: string | null;
+  licensePlate?: string | null;
+  seats?: number | null;
+  charge: number;
+  payment: number;
+}
+
+export interface CustomerStatementDto {
+  customer: CustomerDto;
+  month: string;
+  openingBalance: number;
+  totalCharges: number;
+  totalPayments: number;
+  closingBalance: number;
+  lines: StatementLineDto[];
+  annotations: CalendarAnnotation[];
+}
+
+// A note on a service day (holiday, strike); date is YYYY-MM-DD
+export interface CalendarAnnotation {
+  id: string;
+  date: string;
+  label: string;
+  createdByName: string | null;
+  createdAt: string;
+}
+
+// weekdays are ISO: 1 = Monday .. 7 = Sunday
+export interface StandingReservationDto {
+  id: string;
+  label: string;
+  destinationId: string;
+  destinationName: string;
+  seats: number;
+  weekdays: number[];
+  customerId?: string | null;
+  startsOn: string;
+  endsOn?: string | null;
+  isActive: boolean;
+}
+
+// startsOn defaults to today; dates are YYYY-MM-DD
+export interface StandingReservationRequest {
+  label: string;
+  destinationId: string;
+  seats: number;
+  weekdays: number[];
+  customerId?: string;
+  startsOn?: string;
+  endsOn?: string;
+}
+
+export interface ReservationOccurrenceDto {
+  id: string;
+  reservationId: string;
+  label: string;
+  destinationId: string;
+  destinationName: string;
+  customerId?: string | null;
+  serviceDate: string;
+  seats: number;
+  status: 'HELD' | 'BOOKED' | 'SKIPPED' | 'CANCELLED';
+}
+
+export interface WaitlistEntryDto {
+  id: string;
+  destinationId: string;
+  destinationName: string;
+  seats: number;
+  phoneNumber?: string | null;
+  status: 'WAITING' | 'NOTIFIED' | 'BOOKED' | 'CANCELLED' | 'EXPIRED';
+  createdAt: string;
+  notifiedAt?: string | null;
+}
+
+// A vehicle that could be called in, fairest first
+export interface OverflowVehicleDto {
+  vehicleId: string;
+  licensePlate: string;
+  capacity: number;
+  phoneNumber?: string | null;
+  tripsToday: number;
+  lastTripAt?: string | null;
+}
+
+export interface OverflowSuggestionDto {
+  destinationId: string;
+  availableSeats: number;
+  waitingSeats: number;
+  shortfall: number;
+  vehicles: OverflowVehicleDto[];
 }
 
 export interface QueueItemDto {
@@ -24,8 +254,19 @@ export interface QueueItemDto {
   totalSeats: number;
   basePrice: number;
   licensePlate: string;
+  version: number;
 }
 
+// Error of a reorder refused because another terminal changed the queue since it was read
+export type QueueReorderError =
+  | { code: 'STALE_STATE'; message: string; changed: string[]; queue: QueueItemDto[] }
+  | { code: 'FAILED'; message: string };
+
+// Error of a refused booking; NO_SEATS is the one worth offering the waiting list on
+export type BookingError =
+  | { code: 'NO_SEATS'; message: string }
+  | { code: 'FAILED'; message: string };
+
 export interface AuthorizedDestinationDto {
   stationId: string;
   stationName: string;
@@ -39,6 +280,19 @@ export const dbClient = {
     return invoke<QueueSummaryDto[]>('db_get_queue_summaries', { routeFilter });
   },
 
+  // until: Tunis "YYYY-MM-DDTHH:MM" or RFC 3339; without it the suspension lasts until lifted
+  async suspendDestination(destinationId: string, reason: string, until?: string, staffId?: string) {
+    return invoke<DestinationSuspension>('db_suspend_destination', { destinationId, reason, until, staffId });
+  },
+
+  async liftDestinationSuspension(destinationId: string, staffId?: string) {
+    return invoke<void>('db_lift_destination_suspension', { destinationId, staffId });
+  },
+
+  async getDestinationSuspensions() {
+    return invoke<DestinationSuspension[]>('db_get_destination_suspensions');
+  },
+
   async getQueueByDestination(destinationId: string): Promise<QueueItemDto[]> {
     return invoke<QueueItemDto[]>('db_get_queue_by_destination', { destinationId });
   },
@@ -51,6 +305,20 @@ export const dbClient = {
     return invoke<string>('db_enter_queue', { licensePlate, destinationId, destinationName, staffId, subRoute, subRouteName });
   },
 
+  // Queue the vehicle for its default destination, which must still be authorized
+  async quickEnterQueue(licensePlate: string, staffId?: string) {
+    return invoke<QuickEntry>('db_quick_enter_queue', { licensePlate, staffId });
+  },
+
+  async getQuickEntryPolicy() {
+    return invoke<QuickEntryPolicy>('db_get_quick_entry_policy');
+  },
+
+  // Supervisors only
+  async setQuickEntryPolicy(policy: QuickEntryPolicy, staffId?: string) {
+    return invoke<QuickEntryPolicy>('db_set_quick_entry_policy', { policy, staffId });
+  },
+
   async exitQueue(licensePlate: string) {
     return invoke<number>('db_exit_queue', { licensePlate });
   },
@@ -72,16 +340,37 @@ export const dbClient = {
     return invoke<any>('db_get_available_seats_for_destination', { destinationId, subRoute });
   },
 
-  async createQueueBooking(destinationId: string, seatsRequested: number, createdBy?: string) {
-    return invoke<any>('db_create_queue_booking', { destinationId, seatsRequested, createdBy });
+  // requestId: reuse the same value when retrying so the booking is only made once
+  // paymentMethod defaults to CASH; pass `payments` instead for a split payment
+  // customerId is required when any of it is paid ON_ACCOUNT
+  // fareSelections: child fares and luggage supplements, itemized as fareModifiers on each booking
+  // Both booking calls reject with a BookingError
+  async createQueueBooking(destinationId: string, seatsRequested: number, createdBy?: string, requestId?: string, paymentMethod?: PaymentMethod, payments?: PaymentPart[], customerId?: string, fareSelections?: FareSelection[]) {
+    return invoke<any>('db_create_queue_booking', { destinationId, seatsRequested, createdBy, requestId, paymentMethod, payments, customerId, fareSelections });
+  },
+
+  async createVehicleSpecificBooking(queueId: string, seatsRequested: number, createdBy?: string, requestId?: string, paymentMethod?: PaymentMethod, payments?: PaymentPart[], customerId?: string, fareSelections?: FareSelection[]) {
+    return invoke<any>('db_create_vehicle_specific_booking', { queueId, seatsRequested, createdBy, requestId, paymentMethod, payments, customerId, fareSelections });
+  },
+
+  // Subject to the cancellation policy: a late cancellation keeps a fee, none is allowed after the
+  // exit pass without a supervisor's PIN or approved request
+  async cancelQueueBooking(bookingId: string, createdBy?: string, supervisorOverride?: SupervisorOverride, approvalId?: string) {
+    return invoke<void>('db_cancel_queue_booking', { bookingId, createdBy, supervisorOverride, approvalId });
+  },
+
+  // A paid passenger who never boarded; the seats go back on sale while the vehicle is queued
+  async markNoShow(bookingId: string, createdBy?: string) {
+    return invoke<NoShow>('db_mark_no_show', { bookingId, createdBy });
   },
 
-  async createVehicleSpecificBooking(queueId: string, seatsRequested: number, createdBy?: string) {
-    return invoke<any>('db_create_vehicle_specific_booking', { queueId, seatsRequested, createdBy });
+  // Needs a supervisor sign-off; refused beyond what is left of what was paid
+  async recordRefund(target: RefundTarget, id: string, amount: number, createdBy?: string, supervisorOverride?: SupervisorOverride, approvalId?: string) {
+    return invoke<RefundSummary>('db_record_refund', { target, id, amount, createdBy, supervisorOverride, approvalId });
   },
 
-  async cancelQueueBooking(bookingId: string) {
-    return invoke<void>('db_cancel_queue_booking', { bookingId });
+  async getRefundsForBooking(bookingId: string) {
+    return invoke<RefundSummary>('db_get_refunds_for_booking', { bookingId });
   },
 
   async cancelSeatFromDestination(destinationId: string, createdBy?: string) {
@@ -92,6 +381,135 @@ export const dbClient = {
     return invoke<boolean>('db_health');
   },
 
+  // This cashier PC, identified on first run, and every terminal registered at the station
+  async getTerminalInfo() {
+    return invoke<TerminalInfo>('get_terminal_info');
+  },
+
+  async getTerminals() {
+    return invoke<Terminal[]>('db_get_terminals');
+  },
+
+  // IPv4 interfaces of this PC and the rules choosing which ones find the local node and printers
+  async listNetworkInterfaces() {
+    return invoke<NetworkInterface[]>('list_network_interfaces');
+  },
+
+  async getInterfaceRules() {
+    return invoke<InterfaceRules>('get_interface_rules');
+  },
+
+  async setInterfaceRules(rules: InterfaceRules, staffId?: string) {
+    return invoke<InterfaceRules>('set_interface_rules', { rules, staffId });
+  },
+
+  // Local node endpoints reachable through proxy_localnode; admins only may change them
+  async getProxyAllowlist() {
+    return invoke<ProxyAllowlist>('get_proxy_allowlist');
+  },
+
+  async setProxyAllowlist(allowlist: ProxyAllowlist, staffId?: string) {
+    return invoke<ProxyAllowlist>('set_proxy_allowlist', { allowlist, staffId });
+  },
+
+  // Staff names are cached and dropped on rename; this drops them all. Returns the number dropped
+  async refreshStaffCache() {
+    return invoke<number>('refresh_staff_cache');
+  },
+
+  async getAnnouncementSettings() {
+    return invoke<AnnouncementSettings>('get_announcement_settings');
+  },
+
+  async setAnnouncementSettings(settings: AnnouncementSettings, staffId?: string) {
+    return invoke<AnnouncementSettings>('set_announcement_settings', { settings, staffId });
+  },
+
+  async replayLastAnnouncement() {
+    return invoke<Announcement>('replay_last_announcement');
+  },
+
+  // Messages between terminals: sent to one terminal, shown there until acknowledged
+  async sendTerminalMessage(targetTerminal: string, text: string, priority: MessagePriority, staffId?: string) {
+    return invoke<TerminalMessage>('send_terminal_message', { targetTerminal, text, priority, staffId });
+  },
+
+  async acknowledgeTerminalMessage(messageId: string, staffId?: string) {
+    return invoke<TerminalMessage>('acknowledge_terminal_message', { messageId, staffId });
+  },
+
+  async getTerminalMessages() {
+    return invoke<TerminalMessages>('db_get_terminal_messages');
+  },
+
+  onTerminalMessage(callback: (message: TerminalMessage) => void) {
+    return listen<TerminalMessage>('terminal-message', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Fired on the sending terminal when the receiving one acknowledges
+  onTerminalMessageAcknowledged(callback: (message: TerminalMessage) => void) {
+    return listen<TerminalMessage>('terminal-message-acknowledged', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Ask the supervisors for a sign-off instead of their PIN; the id goes with the action once approved
+  async requestApproval(request: NewApprovalRequest, staffId?: string) {
+    return invoke<ApprovalRequest>('db_request_approval', { request, staffId });
+  },
+
+  async listPendingApprovals() {
+    return invoke<ApprovalRequest[]>('db_list_pending_approvals');
+  },
+
+  async getApproval(approvalId: string) {
+    return invoke<ApprovalRequest>('db_get_approval', { approvalId });
+  },
+
+  async approveApproval(approvalId: string, note?: string, staffId?: string) {
+    return invoke<ApprovalRequest>('db_approve_approval', { approvalId, note, staffId });
+  },
+
+  async rejectApproval(approvalId: string, note?: string, staffId?: string) {
+    return invoke<ApprovalRequest>('db_reject_approval', { approvalId, note, staffId });
+  },
+
+  async getApprovalPolicy() {
+    return invoke<ApprovalPolicy>('db_get_approval_policy');
+  },
+
+  async setApprovalPolicy(policy: ApprovalPolicy, staffId?: string) {
+    return invoke<ApprovalPolicy>('db_set_approval_policy', { policy, staffId });
+  },
+
+  // Fired on every terminal when a request is made
+  onApprovalRequested(callback: (request: ApprovalRequest) => void) {
+    return listen<ApprovalRequest>('approval-requested', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Fired on the requesting terminal when a supervisor approves or rejects
+  onApprovalDecided(callback: (request: ApprovalRequest) => void) {
+    return listen<ApprovalRequest>('approval-decided', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Fired on the other terminals, so their inboxes drop the request
+  onApprovalClosed(callback: (request: ApprovalRequest) => void) {
+    return listen<ApprovalRequest>('approval-closed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Primary and read replicas with the result of their last health check
+  async getDatabaseNodes() {
+    return invoke<DatabaseNode[]>('db_get_database_nodes');
+  },
+
   async hasDayPassToday(licensePlate: string): Promise<boolean> {
     return invoke<boolean>('db_has_day_pass_today', { licensePlate });
   },
@@ -117,15 +535,16 @@ export const dbClient = {
     return invoke<any>('db_get_queued_without_day_pass');
   },
 
-  // End trip with partial capacity
-  async endTripWithPartialCapacity(queueId: string, createdBy?: string) {
-    return invoke<string>('db_end_trip_with_partial_capacity', { queueId, createdBy });
+  // End trip with partial capacity; below the approval policy's fill threshold it needs a sign-off
+  async endTripWithPartialCapacity(queueId: string, createdBy?: string, supervisorOverride?: SupervisorOverride, approvalId?: string) {
+    return invoke<string>('db_end_trip_with_partial_capacity', { queueId, createdBy, supervisorOverride, approvalId });
   },
 
   // Queue management
-  async updateQueuePositions(destinationId: string, vehiclePositions: Array<{queueId: string, position: number}>) {
+  // expectedVersions: queue id -> version of every entry read; rejects with a QueueReorderError
+  async updateQueuePositions(destinationId: string, vehiclePositions: Array<{queueId: string, position: number}>, expectedVersions: Record<string, number>) {
     const positions = vehiclePositions.map(vp => [vp.queueId, vp.position] as [string, number]);
-    return invoke<string>('db_update_queue_positions', { destinationId, vehiclePositions: positions });
+    return invoke<string>('db_update_queue_positions', { destinationId, vehiclePositions: positions, expectedVersions });
   },
 
   async moveVehicleToFront(queueId: string, destinationId: string) {
@@ -145,6 +564,11 @@ export const dbClient = {
     return invoke<DestinationDto[]>('db_get_stations_by_governorate', { governorate });
   },
 
+  // Every governorate with its delegations and their stations; cached until the routes change
+  async getLocationTree() {
+    return invoke<GovernorateNode[]>('db_get_location_tree');
+  },
+
   async getSubRoutesForDestination(destinationName: string): Promise<Array<{id: string, name: string}>> {
     // Normalize station names to handle variants like "STATION KSAR HLEL"
     const norm = (destinationName || '').toUpperCase().trim().replace(/^STATION\s+/,'');
@@ -183,144 +607,728 @@ export const dbClient = {
   },
 
   // Day pass operations
-  async purchaseDayPass(licensePlate: string, vehicleId: string, price: number, createdBy?: string) {
-    return invoke<string>('db_purchase_day_pass', { licensePlate, vehicleId, price, createdBy });
+  async purchaseDayPass(licensePlate: string, vehicleId: string, price: number, createdBy?: string, passType?: PassType, paymentMethod?: PaymentMethod) {
+    return invoke<string>('db_purchase_day_pass', { licensePlate, vehicleId, price, createdBy, passType, paymentMethod });
   },
 
   async getDayPassPrice() {
     return invoke<number>('db_get_day_pass_price');
   },
 
-  // Vehicle management functions
-  async createVehicle(licensePlate: string, capacity: number, phoneNumber?: string) {
-    return invoke<string>('db_create_vehicle', { licensePlate, capacity, phoneNumber });
+  async getPassPrices() {
+    return invoke<Record<PassType, number>>('db_get_pass_prices');
   },
-  async updateVehiclePhone(vehicleId: string, phoneNumber?: string) {
-    return invoke<string>('db_update_vehicle_phone', { vehicleId, phoneNumber });
+
+  // Signed off with the supervisor's PIN or an approved request
+  async setPassPrice(passType: PassType, price: number, supervisorOverride?: SupervisorOverride, approvalId?: string) {
+    return invoke<void>('db_set_pass_price', { passType, price, supervisorOverride, approvalId });
   },
-  async getVehicleActivity72h(licensePlate: string) {
-    return invoke<Array<{eventType: string; timestamp: string; destinationName?: string}>>('db_get_vehicle_activity_72h', { licensePlate });
+
+  // Payment methods accepted at this station
+  async getPaymentMethods() {
+    return invoke<PaymentMethodSettingDto[]>('db_get_payment_methods');
   },
 
-  async authorizeVehicleStation(vehicleId: string, stationId: string, stationName: string) {
-    return invoke<string>('db_authorize_vehicle_station', { vehicleId, stationId, stationName });
+  async setPaymentMethodEnabled(method: PaymentMethod, enabled: boolean) {
+    return invoke<void>('db_set_payment_method_enabled', { method, enabled });
   },
 
-  async banVehicle(vehicleId: string) {
-    return invoke<string>('db_ban_vehicle', { vehicleId });
+  // Pricing rules; with a destination, only the active ones a booking there can use
+  async getPricingRules(destinationId?: string) {
+    return invoke<PricingRule[]>('db_get_pricing_rules', { destinationId });
   },
 
-  // Report functions
-  async getVehicleDailyReport(vehicleId: string, date: string) {
-    return invoke<VehicleDailyReport>('db_get_vehicle_daily_report', { vehicleId, date });
+  // Supervisors only; a rule without an id is created
+  async upsertPricingRule(rule: PricingRule, updatedBy?: string) {
+    return invoke<PricingRule>('db_upsert_pricing_rule', { rule, updatedBy });
   },
 
-  async getAllVehiclesDailyReport(date: string) {
-    return invoke<AllVehiclesDailyReport>('db_get_all_vehicles_daily_report', { date });
+  async createCustomer(name: string, phoneNumber?: string, taxId?: string, creditLimit?: number) {
+    return invoke<CustomerDto>('db_create_customer', { name, phoneNumber, taxId, creditLimit });
   },
 
-  // Add new method for transferring seats and removing vehicle
-  async transferSeatsAndRemoveVehicle(licensePlate: string, destinationId: string, targetQueueId?: string) {
-    return invoke<string>('db_transfer_seats_and_remove_vehicle', { licensePlate, destinationId, targetQueueId });
+  async getCustomers() {
+    return invoke<CustomerDto[]>('db_get_customers');
   },
 
-  // Emergency remove vehicle with booked seats (cancel all bookings)
-  async emergencyRemoveVehicle(licensePlate: string) {
-    return invoke<{cancelledBookings: number, totalRefund: number, message: string}>('db_emergency_remove_vehicle', { licensePlate });
+  async setCustomerCreditLimit(customerId: string, creditLimit: number) {
+    return invoke<CustomerDto>('db_set_customer_credit_limit', { customerId, creditLimit });
   },
 
-  // Check if vehicle has a recently purchased day pass (within last 10 minutes)
-  async hasRecentlyPurchasedDayPass(licensePlate: string) {
-    return invoke<boolean>('db_has_recently_purchased_day_pass', { licensePlate });
+  async recordCustomerPayment(customerId: string, amount: number, reference?: string, createdBy?: string) {
+    return invoke<CustomerDto>('db_record_customer_payment', { customerId, amount, reference, createdBy });
   },
 
-  // Print day pass for vehicle in queue
-  async printDayPassForVehicle(licensePlate: string) {
-    return invoke<string>('db_print_day_pass_for_vehicle', { licensePlate });
+  // month: YYYY-MM
+  async getCustomerStatement(customerId: string, month: string) {
+    return invoke<CustomerStatementDto>('db_get_customer_statement', { customerId, month });
   },
 
-  async updateQueueSubroute(queueId: string, subRoute?: string, subRouteName?: string) {
-    return invoke<string>('db_update_queue_subroute', { queueId, subRoute, subRouteName });
+  // CSV text of the same statement
+  async exportCustomerStatement(customerId: string, month: string) {
+    return invoke<string>('db_export_customer_statement', { customerId, month });
   },
 
-  async bulkUpdateSubroute(destinationId: string, subRoute: string, subRouteName: string, onlyEmpty = true) {
-    return invoke<number>('db_bulk_update_subroute', { destinationId, subRoute, subRouteName, onlyEmpty });
+  // Seats sold per 30-minute slot; weekday 1 (Monday) to 7 (Sunday), weeks back defaults to 8
+  async getDemandProfile(destinationId: string, weekday: number, weeks?: number) {
+    return invoke<DemandProfile>('db_get_demand_profile', { destinationId, weekday, weeks });
   },
 
-  async distributeSubroutesEvenly(destinationId: string, leftSub: string, rightSub: string, onlyEmpty = true) {
-    return invoke<number>('db_distribute_subroutes_evenly', { destinationId, leftSub, rightSub, onlyEmpty });
+  // CSV text of the same profile
+  async exportDemandProfile(destinationId: string, weekday: number, weeks?: number) {
+    return invoke<string>('db_export_demand_profile', { destinationId, weekday, weeks });
   },
 
-  // Realtime functionality
-  async startRealtimeListening() {
-    return invoke<void>('start_realtime_listening');
+  // Dates are YYYY-MM-DD; startsOn defaults to today
+  async createStandingReservation(reservation: StandingReservationRequest, createdBy?: string) {
+    return invoke<StandingReservationDto>('db_create_standing_reservation', { reservation, createdBy });
   },
 
-  async stopRealtimeListening() {
-    return invoke<void>('stop_realtime_listening');
+  async getStandingReservations() {
+    return invoke<StandingReservationDto[]>('db_get_standing_reservations');
   },
 
-  async getRealtimeStatus() {
-    return invoke<boolean>('get_realtime_status');
+  async cancelStandingReservation(reservationId: string, createdBy?: string) {
+    return invoke<void>('db_cancel_standing_reservation', { reservationId, createdBy });
   },
 
-  // Event listeners
-  onRealtimeEvent(callback: (event: RealtimeEvent) => void) {
-    return listen<RealtimeEvent>('realtime-event', (event) => {
-      callback(event.payload);
-    });
+  // Today's occurrences when date is omitted
+  async getReservationOccurrences(date?: string) {
+    return invoke<ReservationOccurrenceDto[]>('db_get_reservation_occurrences', { date });
   },
 
-  onBookingUpdate(callback: (event: BookingUpdateEvent) => void) {
-    return listen<BookingUpdateEvent>('booking-update', (event) => {
-      callback(event.payload);
-    });
+  async skipReservationOccurrence(reservationId: string, date: string, createdBy?: string) {
+    return invoke<void>('db_skip_reservation_occurrence', { reservationId, date, createdBy });
   },
 
-  onQueueUpdate(callback: (event: QueueUpdateEvent) => void) {
-    return listen<QueueUpdateEvent>('queue-update', (event) => {
-      callback(event.payload);
-    });
-  }
-};
+  async cancelReservationOccurrence(occurrenceId: string, createdBy?: string) {
+    return invoke<void>('db_cancel_reservation_occurrence', { occurrenceId, createdBy });
+  },
 
-// Report interfaces
-export interface VehicleInfo {
-  id: string;
-  licensePlate: string;
-  capacity: number;
-  isActive: boolean;
-  isAvailable: boolean;
-  isBanned: boolean;
-}
+  async bookReservationOccurrence(occurrenceId: string, createdBy?: string) {
+    return invoke<any>('db_book_reservation_occurrence', { occurrenceId, createdBy });
+  },
 
-export interface TripInfo {
-  id: string;
-  destinationId: string;
-  destinationName: string;
-  queuePosition: number;
-  availableSeats: number;
-  totalSeats: number;
-  basePrice: number;
-  enteredAt: string;
-  createdAt: string;
-}
+  async suggestOverflowVehicles(destinationId: string, limit?: number) {
+    return invoke<OverflowSuggestionDto>('db_suggest_overflow_vehicles', { destinationId, limit });
+  },
 
-export interface DestinationSummary {
-  destinationName: string;
-  tripCount: number;
-  totalSeatsSold: number;
-  totalIncome: number;
-}
+  async addToWaitlist(destinationId: string, seats: number, phoneNumber?: string, createdBy?: string) {
+    return invoke<WaitlistEntryDto>('db_add_to_waitlist', { destinationId, seats, phoneNumber, createdBy });
+  },
 
-export interface VehicleDailyReport {
-  vehicle: VehicleInfo;
+  async getWaitlist(destinationId: string) {
+    return invoke<WaitlistEntryDto[]>('db_get_waitlist', { destinationId });
+  },
+
+  async cancelWaitlistEntry(entryId: string) {
+    return invoke<void>('db_cancel_waitlist_entry', { entryId });
+  },
+
+  // Safe to tap twice: the entry books at most once
+  async bookWaitlistEntry(entryId: string, createdBy?: string, paymentMethod?: PaymentMethod, payments?: PaymentPart[]) {
+    return invoke<any>('db_book_waitlist_entry', { entryId, createdBy, paymentMethod, payments });
+  },
+
+  // Self-service kiosk: opens the locked-down window; only a supervisor PIN closes it
+  async startKioskMode() {
+    return invoke<void>('start_kiosk_mode');
+  },
+
+  async stopKioskMode(pin: string) {
+    return invoke<void>('stop_kiosk_mode', { pin });
+  },
+
+  // Prints the tickets of bookings just made at the kiosk; resolves with how many were queued
+  async printKioskTickets(bookingIds: string[]) {
+    return invoke<number>('kiosk_print_tickets', { bookingIds });
+  },
+
+  // Driver self check-in at the kiosk; without a destination, the vehicle's default one
+  async selfCheckIn(licensePlate: string, pin: string, destinationId?: string) {
+    return invoke<SelfCheckIn>('kiosk_self_check_in', { licensePlate, pin, destinationId });
+  },
+
+  async getSelfCheckInPolicy() {
+    return invoke<SelfCheckInPolicy>('db_get_self_check_in_policy');
+  },
+
+  // Supervisors only
+  async setSelfCheckInPolicy(policy: SelfCheckInPolicy, staffId?: string) {
+    return invoke<SelfCheckInPolicy>('db_set_self_check_in_policy', { policy, staffId });
+  },
+
+  // Supervisors only; null removes the driver's PIN
+  async setDriverPin(licensePlate: string, pin: string | null, staffId?: string) {
+    return invoke<void>('db_set_driver_pin', { licensePlate, pin, staffId });
+  },
+
+  // Global shortcuts and tray quick actions (shortcuts.json next to the executable)
+  async getShortcuts() {
+    return invoke<ShortcutSettings>('get_shortcuts');
+  },
+
+  // accelerator: Tauri syntax such as "CommandOrControl+Shift+H"; omit it to remove the shortcut
+  async setShortcut(action: ShortcutAction, accelerator?: string) {
+    return invoke<ShortcutSettings>('set_shortcut', { action, accelerator });
+  },
+
+  async setTrayActions(actions: ShortcutAction[]) {
+    return invoke<ShortcutSettings>('set_tray_actions', { actions });
+  },
+
+  // Forget where each window was placed across monitors
+  async resetWindowLayout() {
+    return invoke<void>('reset_window_layout');
+  },
+
+  // Updates are found here but only installed inside the maintenance window
+  async checkForUpdates() {
+    return invoke<UpdateStatusDto>('check_for_updates');
+  },
+
+  async getUpdateSettings() {
+    return invoke<UpdateSettings>('get_update_channel');
+  },
+
+  async setUpdateChannel(channel: UpdateChannel) {
+    return invoke<UpdateSettings>('set_update_channel', { channel });
+  },
+
+  // date: YYYY-MM-DD; omit it to lift the deferral
+  async deferUpdateUntil(date?: string) {
+    return invoke<UpdateSettings>('defer_update_until', { date });
+  },
+
+  // start/end: HH:MM Tunis time, the window may run past midnight
+  async setUpdateMaintenanceWindow(start: string, end: string) {
+    return invoke<UpdateSettings>('set_update_maintenance_window', { start, end });
+  },
+
+  // Panic reports saved on this station; uploading is opt-in, one report at a time
+  async listCrashReports() {
+    return invoke<CrashReportSummaryDto[]>('list_crash_reports');
+  },
+
+  async getCrashReport(id: string) {
+    return invoke<any>('get_crash_report', { id });
+  },
+
+  async uploadCrashReport(id: string) {
+    return invoke<CrashReportSummaryDto>('upload_crash_report', { id });
+  },
+
+  // PC clock vs database clock; null until the first check has run
+  async getClockStatus() {
+    return invoke<ClockStatusDto | null>('get_clock_status');
+  },
+
+  async checkClockDrift() {
+    return invoke<ClockStatusDto>('check_clock_drift');
+  },
+
+  onClockDrift(callback: (status: ClockStatusDto) => void) {
+    return listen<ClockStatusDto>('clock-drift', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Suspicious patterns found by the rules in anomalies.rs, for a day (today by default)
+  async getAnomalies(date?: string) {
+    return invoke<Anomaly[]>('db_get_anomalies', { date });
+  },
+
+  // Fired on the terminal that closed the queue for the day
+  onQueueClosed(callback: (closing: QueueClosing) => void) {
+    return listen<QueueClosing>('queue-closed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Vehicles the gate camera queued, waiting for a cashier to confirm them
+  async getPendingAnprEntries() {
+    return invoke<PendingAnprEntry[]>('db_get_pending_anpr_entries');
+  },
+
+  // The entry's seats go on sale and its entry ticket or day pass prints
+  async confirmAnprEntry(queueId: string, staffId?: string) {
+    return invoke<PendingAnprEntry>('db_confirm_anpr_entry', { queueId, staffId });
+  },
+
+  async rejectAnprEntry(queueId: string, staffId?: string) {
+    return invoke<void>('db_reject_anpr_entry', { queueId, staffId });
+  },
+
+  // Fired on the terminal the gate camera posts to, when it queues a vehicle
+  onAnprEntryPending(callback: (entry: PendingAnprEntry) => void) {
+    return listen<PendingAnprEntry>('anpr-entry-pending', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Once per loading spell, when a vehicle passes its destination's loading timeout
+  onVehicleStalled(callback: (vehicle: StalledVehicle) => void) {
+    return listen<StalledVehicle>('vehicle-stalled', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  onAnomaly(callback: (anomaly: Anomaly) => void) {
+    return listen<Anomaly>('anomaly-detected', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Station language for command messages and tickets, shared by every terminal
+  async getLanguageSettings() {
+    return invoke<LanguageSettingsDto>('get_language_settings');
+  },
+
+  async setLanguageSettings(language: StationLanguage, bilingualTickets: boolean) {
+    return invoke<LanguageSettingsDto>('set_language_settings', { language, bilingualTickets });
+  },
+
+  // Vehicle management functions
+  async createVehicle(licensePlate: string, capacity: number, phoneNumber?: string) {
+    return invoke<string>('db_create_vehicle', { licensePlate, capacity, phoneNumber });
+  },
+  // Stored as +216XXXXXXXX; sharedWith lists the other plates registered with the same number
+  async updateVehiclePhone(vehicleId: string, phoneNumber?: string) {
+    return invoke<{ phoneNumber: string | null; sharedWith: string[] }>('db_update_vehicle_phone', { vehicleId, phoneNumber });
+  },
+  // A queued vehicle keeps its sold seats; refused at or below what was sold
+  async updateVehicleCapacity(vehicleId: string, newCapacity: number, staffId?: string) {
+    return invoke<VehicleCapacityChange>('db_update_vehicle_capacity', { vehicleId, newCapacity, staffId });
+  },
+  async getVehicleActivity72h(licensePlate: string) {
+    return invoke<Array<{eventType: string; timestamp: string; destinationName?: string}>>('db_get_vehicle_activity_72h', { licensePlate });
+  },
+  async getVehicleProfile(licensePlate: string) {
+    return invoke<VehicleProfileDto>('db_get_vehicle_profile', { licensePlate });
+  },
+
+  async authorizeVehicleStation(vehicleId: string, stationId: string, stationName: string) {
+    return invoke<string>('db_authorize_vehicle_station', { vehicleId, stationId, stationName });
+  },
+
+  async banVehicle(vehicleId: string) {
+    return invoke<string>('db_ban_vehicle', { vehicleId });
+  },
+
+  // Report functions
+  async getVehicleDailyReport(vehicleId: string, date: string) {
+    return invoke<VehicleDailyReport>('db_get_vehicle_daily_report', { vehicleId, date });
+  },
+
+  async getAllVehiclesDailyReport(date: string) {
+    return invoke<AllVehiclesDailyReport>('db_get_all_vehicles_daily_report', { date });
+  },
+
+  async getDayPassReport(from: string, to: string) {
+    return invoke<DayPassReport>('db_get_day_pass_report', { from, to });
+  },
+
+  // Dates are YYYY-MM-DD
+  async addAnnotation(date: string, label: string, staffId?: string) {
+    return invoke<CalendarAnnotation>('db_add_annotation', { date, label, staffId });
+  },
+
+  async getAnnotations(from: string, to: string) {
+    return invoke<CalendarAnnotation[]>('db_get_annotations', { from, to });
+  },
+
+  async deleteAnnotation(id: string, staffId?: string) {
+    return invoke<void>('db_delete_annotation', { id, staffId });
+  },
+
+  async printDayPassSummary(date: string, staffName?: string) {
+    return invoke<string>('print_day_pass_summary', { date, staffName });
+  },
+
+  // Supervisor overrides between two days (inclusive), for the head office audit
+  async getOverrides(from: string, to: string) {
+    return invoke<SupervisorOverrideRecord[]>('db_get_overrides', { from, to });
+  },
+
+  async getDriverSettlements(vehicleId: string, from: string, to: string) {
+    return invoke<DriverSettlement[]>('db_get_driver_settlements', { vehicleId, from, to });
+  },
+
+  // Departures counted from exit passes over the day, week or month holding `date` (today by default)
+  async getVehicleTripCounts(vehicleId: string, period: TripPeriod, date?: string) {
+    return invoke<VehicleTripCounts>('db_get_vehicle_trip_counts', { vehicleId, period, date });
+  },
+
+  async getTripLeaderboard(destinationId: string, period: TripPeriod, date?: string, limit?: number) {
+    return invoke<TripLeaderboardEntry[]>('db_get_trip_leaderboard', { destinationId, period, date, limit });
+  },
+
+  async getCancellationPolicy() {
+    return invoke<CancellationPolicy>('db_get_cancellation_policy');
+  },
+
+  // Supervisors only
+  async setCancellationPolicy(policy: CancellationPolicy, staffId?: string) {
+    return invoke<CancellationPolicy>('db_set_cancellation_policy', { policy, staffId });
+  },
+
+  // Fees kept on the cancellations of a service day (today by default)
+  async getCancellationFees(date?: string) {
+    return invoke<CancellationFee[]>('db_get_cancellation_fees', { date });
+  },
+
+  async getReprintPolicy() {
+    return invoke<ReprintPolicy>('db_get_reprint_policy');
+  },
+
+  // Supervisors only
+  async setReprintPolicy(policy: ReprintPolicy, staffId?: string) {
+    return invoke<ReprintPolicy>('db_set_reprint_policy', { policy, staffId });
+  },
+
+  // Reprints of the day (today by default) with the fees charged
+  async getTicketReprints(date?: string) {
+    return invoke<TicketReprint[]>('db_get_ticket_reprints', { date });
+  },
+
+  // Void a spoiled ticket's number (ENTRY, EXIT_PASS or DAY_PASS); its next reprint takes a fresh number
+  async voidTicketNumber(kind: VoidedTicket['ticketKind'], number: string, reason: string, staffId?: string) {
+    return invoke<VoidedTicket>('void_ticket_number', { kind, number, reason, staffId });
+  },
+
+  async getRetentionPolicy() {
+    return invoke<RetentionPolicy>('db_get_retention_policy');
+  },
+
+  // Supervisors only
+  async setRetentionPolicy(policy: RetentionPolicy, staffId?: string) {
+    return invoke<RetentionPolicy>('db_set_retention_policy', { policy, staffId });
+  },
+
+  // Supervisors only; a dry run counts the rows without deleting them
+  async runRetentionNow(dryRun: boolean, staffId?: string) {
+    return invoke<RetentionReport>('run_retention_now', { dryRun, staffId });
+  },
+
+  // Printed tickets matching every filter given, latest first (the last 30 days by default)
+  async searchPrintedTickets(filters: PrintedTicketFilters) {
+    return invoke<PrintedTicket[]>('db_search_printed_tickets', { filters });
+  },
+
+  // Base64 PDF of an archived ticket, as it printed
+  async exportPrintedTicketPdf(id: string) {
+    return invoke<string>('db_export_printed_ticket_pdf', { id });
+  },
+
+  async getNoPrinterMode() {
+    return invoke<NoPrinterMode>('db_get_no_printer_mode');
+  },
+
+  // Supervisors only; the terminal switches at once, the others within a minute
+  async setNoPrinterMode(mode: NoPrinterMode, staffId?: string) {
+    return invoke<NoPrinterMode>('db_set_no_printer_mode', { mode, staffId });
+  },
+
+  // Tickets issued without a printer, not yet printed or texted, oldest first
+  async getEscrowedTickets() {
+    return invoke<PrintedTicket[]>('db_get_escrowed_tickets');
+  },
+
+  // Sends the oldest escrowed tickets (or those of `ids`) to the printer, 20 at a time; how many were queued
+  async printEscrowedTickets(ids?: string[]) {
+    return invoke<number>('db_print_escrowed_tickets', { ids });
+  },
+
+  async sendEscrowedTicketSms(id: string, phoneNumber: string) {
+    return invoke<void>('db_send_escrowed_ticket_sms', { id, phoneNumber });
+  },
+
+  // Queue of a destination as it stood at `at` (ISO, or Tunis 'YYYY-MM-DD HH:MM'), rebuilt from the queue journal
+  async replayQueueState(destinationId: string, at: string) {
+    return invoke<QueueReplay>('db_replay_queue_state', { destinationId, atTimestamp: at });
+  },
+
+  // Bookings, exit passes and day passes matching a code, plate, pass id or cashier name; today only unless includeHistory
+  async globalSearch(query: string, includeHistory = false) {
+    return invoke<SearchHit[]>('db_global_search', { query, includeHistory });
+  },
+
+  async getQueueClosingPolicy() {
+    return invoke<QueueClosingPolicy>('db_get_queue_closing_policy');
+  },
+
+  // Supervisors only
+  async setQueueClosingPolicy(policy: QueueClosingPolicy, staffId?: string) {
+    return invoke<QueueClosingPolicy>('db_set_queue_closing_policy', { policy, staffId });
+  },
+
+  // Waiting vehicles the next closing would purge or carry over
+  async previewCarryOver() {
+    return invoke<CarryOverPreview>('db_preview_carry_over');
+  },
+
+  // Supervisors only; with dryRun nothing is written and the diff shows what applying would change
+  async closeQueueNow(dryRun: boolean, staffId?: string) {
+    return invoke<MaintenanceDiff>('db_close_queue_now', { dryRun, staffId });
+  },
+
+  // Closing of the day before `date` (today by default); null when that day was never closed
+  async getCarryOverReport(date?: string) {
+    return invoke<QueueClosing | null>('db_get_carry_over_report', { date });
+  },
+
+  async getBusinessHours() {
+    return invoke<BusinessHours>('db_get_business_hours');
+  },
+
+  // Supervisors only
+  async setBusinessHours(hours: BusinessHours, staffId?: string) {
+    return invoke<BusinessHours>('db_set_business_hours', { hours, staffId });
+  },
+
+  async getBusinessHoursStatus() {
+    return invoke<BusinessHoursStatus>('get_business_hours_status');
+  },
+
+  // Lets this terminal sell after hours for an hour, with a supervisor PIN or an approved AFTER_HOURS request
+  async unlockAfterHours(supervisorOverride?: SupervisorOverride, approvalId?: string) {
+    return invoke<BusinessHoursStatus>('db_unlock_after_hours', { supervisorOverride, approvalId });
+  },
+
+  // Fired when the station opens or closes, or its hours change
+  onBusinessHoursChanged(callback: (status: BusinessHoursStatus) => void) {
+    return listen<BusinessHoursStatus>('business-hours-changed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Destinations by partial, accent-free or Arabic name, or by alias, best match first
+  async searchDestinations(query: string) {
+    return invoke<DestinationMatch[]>('db_search_destinations', { query });
+  },
+
+  async getRouteAliases(stationId: string) {
+    return invoke<string[]>('db_get_route_aliases', { stationId });
+  },
+
+  // Supervisors only; replaces the route's aliases and returns them cleaned up
+  async setRouteAliases(stationId: string, aliases: string[], staffId?: string) {
+    return invoke<string[]>('db_set_route_aliases', { stationId, aliases, staffId });
+  },
+
+  async getTrainingMode() {
+    return invoke<TrainingStatus>('db_get_training_mode');
+  },
+
+  // Supervisors only; rebuilds the training schema from the live data, discarding previous practice
+  async seedTraining(staffId?: string) {
+    return invoke<TrainingSeed>('db_seed_training', { staffId });
+  },
+
+  // Supervisors only; this terminal only, and off again after a restart
+  async setTrainingMode(active: boolean, staffId?: string) {
+    return invoke<TrainingStatus>('db_set_training_mode', { active, staffId });
+  },
+
+  onTrainingModeChanged(callback: (active: boolean) => void) {
+    return listen<boolean>('training-mode-changed', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  async getLoadingTimeoutPolicy() {
+    return invoke<LoadingTimeoutPolicy>('db_get_loading_timeout_policy');
+  },
+
+  // Supervisors only
+  async setLoadingTimeoutPolicy(policy: LoadingTimeoutPolicy, staffId?: string) {
+    return invoke<LoadingTimeoutPolicy>('db_set_loading_timeout_policy', { policy, staffId });
+  },
+
+  // Vehicles loading for longer than their destination's timeout
+  async getStalledVehicles() {
+    return invoke<StalledVehicle[]>('db_get_stalled_vehicles');
+  },
+
+  async getVehiclePositions() {
+    return invoke<VehiclePosition[]>('db_get_vehicle_positions');
+  },
+
+  // Exit pass to next queue entry, over the last `days` days (30 by default)
+  async getTripDurationStats(destinationId: string, days?: number) {
+    return invoke<TripDurationStats>('db_get_trip_duration_stats', { destinationId, days });
+  },
+
+  // Returns false when a later position of the vehicle is already stored
+  async recordVehiclePosition(report: PositionReport) {
+    return invoke<boolean>('db_record_vehicle_position', { report });
+  },
+
+  // Supervisors only; moves the bookings to the next vehicle and sends this one to the back
+  async releaseStalledVehicle(queueId: string, staffId?: string) {
+    return invoke<string>('db_release_stalled_vehicle', { queueId, staffId });
+  },
+
+  async dispatchAll(destinationId: string, staffId?: string) {
+    return invoke<DispatchSummary>('db_dispatch_all', { destinationId, staffId });
+  },
+
+  // Petty cash paid out of the till; supervisors only
+  async addExpense(amount: number, category: string, note: string | null, staffId?: string) {
+    return invoke<Expense>('db_add_expense', { amount, category, note, staffId });
+  },
+
+  async getExpenses(date?: string) {
+    return invoke<Expense[]>('db_get_expenses', { date });
+  },
+
+  async getShiftReport(staffId: string, date?: string) {
+    return invoke<ShiftReport>('db_get_shift_report', { staffId, date });
+  },
+
+  // Drawer count at shift close against the expected cash of the shift report
+  async recordCashCount(staffId: string, denominations: DenominationCount[]) {
+    return invoke<CashCount>('db_record_cash_count', { staffId, denominations });
+  },
+
+  async getCashCounts(date?: string) {
+    return invoke<CashCount[]>('db_get_cash_counts', { date });
+  },
+
+  async getCashVarianceThreshold() {
+    return invoke<number>('db_get_cash_variance_threshold');
+  },
+
+  async setCashVarianceThreshold(threshold: number, staffId?: string) {
+    return invoke<number>('db_set_cash_variance_threshold', { threshold, staffId });
+  },
+
+  // Secrets in the OS credential store, supervisors only; an empty secret removes it
+  async setSecret(name: SecretName, secret: string, staffId?: string) {
+    return invoke<void>('set_secret', { name, secret, staffId });
+  },
+
+  async getSecret(name: SecretName, staffId?: string) {
+    return invoke<string | null>('get_secret', { name, staffId });
+  },
+
+  // Add new method for transferring seats and removing vehicle
+  async transferSeatsAndRemoveVehicle(licensePlate: string, destinationId: string, targetQueueId?: string) {
+    return invoke<string>('db_transfer_seats_and_remove_vehicle', { licensePlate, destinationId, targetQueueId });
+  },
+
+  // Moves whole bookings adding up to `seats` and reprints their tickets with the new plate
+  async transferSeats(sourceQueueId: string, targetQueueId: string, seats: number) {
+    return invoke<SeatTransfer>('db_transfer_seats', { sourceQueueId, targetQueueId, seats });
+  },
+
+  // Emergency remove vehicle with booked seats (cancel all bookings)
+  async emergencyRemoveVehicle(licensePlate: string, supervisorOverride?: SupervisorOverride, approvalId?: string) {
+    return invoke<{cancelledBookings: number, totalRefund: number, message: string}>('db_emergency_remove_vehicle', { licensePlate, supervisorOverride, approvalId });
+  },
+
+  // Check if vehicle has a recently purchased day pass (within last 10 minutes)
+  async hasRecentlyPurchasedDayPass(licensePlate: string) {
+    return invoke<boolean>('db_has_recently_purchased_day_pass', { licensePlate });
+  },
+
+  // Print day pass for vehicle in queue
+  async printDayPassForVehicle(licensePlate: string) {
+    return invoke<string>('db_print_day_pass_for_vehicle', { licensePlate });
+  },
+
+  async updateQueueSubroute(queueId: string, subRoute?: string, subRouteName?: string) {
+    return invoke<string>('db_update_queue_subroute', { queueId, subRoute, subRouteName });
+  },
+
+  async bulkUpdateSubroute(destinationId: string, subRoute: string, subRouteName: string, onlyEmpty = true) {
+    return invoke<number>('db_bulk_update_subroute', { destinationId, subRoute, subRouteName, onlyEmpty });
+  },
+
+  async distributeSubroutesEvenly(destinationId: string, leftSub: string, rightSub: string, onlyEmpty = true) {
+    return invoke<number>('db_distribute_subroutes_evenly', { destinationId, leftSub, rightSub, onlyEmpty });
+  },
+
+  // Realtime functionality
+  async startRealtimeListening() {
+    return invoke<void>('start_realtime_listening');
+  },
+
+  async stopRealtimeListening() {
+    return invoke<void>('stop_realtime_listening');
+  },
+
+  async getRealtimeStatus() {
+    return invoke<boolean>('get_realtime_status');
+  },
+
+  // Event listeners
+  onRealtimeEvent(callback: (event: RealtimeEvent) => void) {
+    return listen<RealtimeEvent>('realtime-event', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  onBookingUpdate(callback: (event: BookingUpdateEvent) => void) {
+    return listen<BookingUpdateEvent>('booking-update', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  onQueueUpdate(callback: (event: QueueUpdateEvent) => void) {
+    return listen<QueueUpdateEvent>('queue-update', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  // Seats opened up for a customer on the waiting list
+  onWaitlistPromoted(callback: (entry: WaitlistEntryDto) => void) {
+    return listen<RealtimeEvent>('realtime-event', (event) => {
+      if (event.payload.event_type === 'waitlist_promoted' && event.payload.data) {
+        callback(event.payload.data as WaitlistEntryDto);
+      }
+    });
+  }
+};
+
+// Report interfaces
+export interface VehicleInfo {
+  id: string;
+  licensePlate: string;
+  capacity: number;
+  isActive: boolean;
+  isAvailable: boolean;
+  isBanned: boolean;
+}
+
+export interface TripInfo {
+  id: string;
+  destinationId: string;
+  destinationName: string;
+  queuePosition: number;
+  availableSeats: number;
+  totalSeats: number;
+  basePrice: number;
+  enteredAt: string;
+  createdAt: string;
+}
+
+export interface DestinationSummary {
+  destinationName: string;
+  tripCount: number;
+  totalSeatsSold: number;
+  totalIncome: number;
+}
+
+export interface VehicleDailyReport {
+  vehicle: VehicleInfo;
   date: string;
   trips: TripInfo[];
   totalTrips: number;
   totalIncome: number;
   totalSeatsSold: number;
   destinations: DestinationSummary[];
+  payments: PaymentTotalDto[];
 }
 
 export interface VehicleReport {
@@ -338,6 +1346,725 @@ export interface AllVehiclesDailyReport {
   totalTrips: number;
   totalIncome: number;
   totalSeatsSold: number;
+  payments: PaymentTotalDto[];
+  expenses: Expense[];
+  totalExpenses: number;
+  noShows: NoShowTotal[];
+}
+
+export interface NoShow {
+  bookingId: string;
+  verificationCode: string;
+  destinationId: string | null;
+  destinationName: string;
+  licensePlate: string;
+  seats: number;
+  amount: number;
+  refunded: number;
+  // False when the vehicle had already left
+  seatFreed: boolean;
+  message: string;
+}
+
+export type RefundTarget = 'BOOKING' | 'DAY_PASS';
+
+export interface Refund {
+  id: string;
+  // Cleared when the booking was deleted by a later cancellation
+  bookingId: string | null;
+  dayPassId: string | null;
+  // Verification code of the booking, id of the day pass
+  reference: string;
+  amount: number;
+  reasonCode: OverrideReasonCode;
+  note: string;
+  supervisorId: string | null;
+  createdBy: string | null;
+  createdAt: string;
+}
+
+export interface RefundSummary {
+  target: RefundTarget;
+  id: string;
+  reference: string;
+  originalAmount: number;
+  // Cancellations, no-shows and the refunds below
+  refundedAmount: number;
+  refundableAmount: number;
+  refunds: Refund[];
+}
+
+// No-shows of one destination over the report's day
+export interface NoShowTotal {
+  destinationId: string | null;
+  destinationName: string;
+  bookings: number;
+  seats: number;
+  amount: number;
+  refunded: number;
+}
+
+export interface Expense {
+  id: string;
+  amount: number;
+  category: string;
+  note: string | null;
+  createdBy: string | null;
+  staffName: string;
+  createdAt: string;
+}
+
+// What one cashier's drawer should hold at the end of the day
+export interface ShiftReport {
+  staffId: string;
+  date: string;
+  payments: PaymentTotalDto[];
+  cashBookings: number;
+  cashDayPasses: number;
+  customerPayments: number;
+  // Refunds handed back at the counter that day
+  cashRefunds: number;
+  expenses: Expense[];
+  totalExpenses: number;
+  expectedCash: number;
+  // Ticket numbers the cashier voided that day
+  voidedTickets: VoidedTicket[];
+}
+
+export type OverrideReasonCode = 'CUSTOMER_REQUEST' | 'VEHICLE_BREAKDOWN' | 'CASHIER_ERROR' | 'TARIFF_CHANGE' | 'OTHER';
+
+// Sent with actions a cashier may not take alone: the supervisor's PIN, a reason code and a note
+export interface SupervisorOverride {
+  pin: string;
+  reasonCode: OverrideReasonCode;
+  note: string;
+}
+
+// How an action was signed off: the PIN typed at the counter or a request approved from the inbox
+export interface SignOff {
+  supervisorOverride?: SupervisorOverride;
+  approvalId?: string;
+}
+
+export type OverrideAction = 'REFUND' | 'PRICE_OVERRIDE' | 'DISPATCH_BELOW_THRESHOLD' | 'VOID_AFTER_WINDOW' | 'AFTER_HOURS';
+
+// A sign-off asked for from a distance: the subject is what the action checks (plate, pass
+// type, booking code)
+export interface NewApprovalRequest {
+  action: OverrideAction;
+  subject: string;
+  reasonCode: OverrideReasonCode;
+  note: string;
+  details?: Record<string, unknown>;
+}
+
+export interface ApprovalRequest {
+  id: string;
+  action: OverrideAction;
+  subject: string;
+  reasonCode: OverrideReasonCode;
+  note: string;
+  details: Record<string, unknown> | null;
+  requestedBy: string | null;
+  requestedByName: string | null;
+  terminalId: string | null;
+  terminalName: string | null;
+  status: 'PENDING' | 'APPROVED' | 'REJECTED' | 'CONSUMED';
+  decidedByName: string | null;
+  decisionNote: string | null;
+  decidedAt: string | null;
+  createdAt: string;
+}
+
+export interface ApprovalPolicy {
+  // Ending a trip with less of its seats sold needs a sign-off; 0 to never ask
+  dispatchMinPercent: number;
+}
+
+export interface SupervisorOverrideRecord {
+  id: string;
+  action: OverrideAction;
+  reasonCode: OverrideReasonCode;
+  note: string;
+  supervisorId: string;
+  supervisorName: string;
+  target: string;
+  details: Record<string, unknown>;
+  createdAt: string;
+}
+
+export interface TerminalInfo {
+  terminalId: string;
+  name: string;
+  hostname: string;
+  provisionedAt: string;
+}
+
+export interface NetworkInterface {
+  name: string;
+  address: string;
+  prefixLen: number | null;
+  loopback: boolean;
+  used: boolean;
+  stationNetwork: boolean;
+  selected: boolean;
+}
+
+export interface InterfaceRules {
+  include: string[];
+  exclude: string[];
+  stationNetwork: string;
+}
+
+export interface ProxyAllowRule {
+  prefix: string;
+  methods: string[];
+}
+
+export interface ProxyAllowlist {
+  enabled: boolean;
+  rules: ProxyAllowRule[];
+}
+
+export interface Terminal {
+  id: string;
+  name: string;
+  hostname: string;
+  appVersion: string;
+  provisionedAt: string;
+  lastSeenAt: string;
+}
+
+// Departure calls on the PA, per terminal (announcements.json)
+export interface AnnouncementSettings {
+  enabled: boolean;
+  destinationIds: string[];
+  template: string;
+  endpointUrl: string | null;
+  playCommand: string[];
+}
+
+export interface Announcement {
+  text: string;
+  licensePlate: string;
+  destinationName: string;
+  announcedAt: string;
+}
+
+export type MessagePriority = 'NORMAL' | 'URGENT';
+
+export interface TerminalMessage {
+  id: string;
+  fromTerminalId: string;
+  fromTerminalName: string;
+  targetTerminalId: string;
+  targetTerminalName: string;
+  sentByName: string | null;
+  text: string;
+  priority: MessagePriority;
+  sentAt: string;
+  acknowledgedAt: string | null;
+  acknowledgedByName: string | null;
+}
+
+export interface TerminalMessages {
+  inbox: TerminalMessage[];
+  sent: TerminalMessage[];
+}
+
+export type SecretName = 'DATABASE_PASSWORD' | 'SMS_GATEWAY_TOKEN';
+
+export interface DatabaseNode {
+  role: 'PRIMARY' | 'REPLICA';
+  address: string;
+  healthy: boolean;
+}
+
+export type AnomalyKind = 'REPEATED_CANCELLATIONS' | 'BOOKINGS_REMOVED_AFTER_EXIT' | 'REPEATED_FREE_ENTRIES';
+
+export interface Anomaly {
+  kind: AnomalyKind;
+  subject: string;
+  count: number;
+  firstAt: string;
+  lastAt: string;
+  message: string;
+}
+
+export interface DenominationCount {
+  denomination: number;
+  count: number;
+}
+
+// variance = counted - expectedCash; flagged when beyond the threshold (a slip was printed)
+export interface CashCount {
+  id: string;
+  staffId: string;
+  staffName: string;
+  date: string;
+  denominations: DenominationCount[];
+  counted: number;
+  expected: number;
+  variance: number;
+  threshold: number;
+  flagged: boolean;
+  createdAt: string;
+}
+
+export interface DayPassReportLine {
+  date: string;
+  staffId: string | null;
+  staffName: string;
+  passType: PassType;
+  paymentMethod: PaymentMethod;
+  price: number;
+  count: number;
+  amount: number;
+  refundedCount: number;
+  refundedAmount: number;
+}
+
+export interface DayPassReport {
+  from: string;
+  to: string;
+  lines: DayPassReportLine[];
+  totalCount: number;
+  totalAmount: number;
+  refundedCount: number;
+  refundedAmount: number;
+  netAmount: number;
+  annotations: CalendarAnnotation[];
+}
+
+// What a departure paid the driver: gross = commission + dayPassFee + netToDriver
+export interface DriverSettlement {
+  id: string;
+  exitPassId: string | null; // null once the exit pass was purged
+  vehicleId: string;
+  licensePlate: string;
+  destinationName: string;
+  seatsSold: number;
+  gross: number;
+  commission: number;
+  dayPassFee: number;
+  netToDriver: number;
+  createdBy: string | null;
+  createdAt: string;
+}
+
+// Free within freeMinutes of booking, then feePerSeat per seat given up, capped at the refund
+export interface CancellationPolicy {
+  freeMinutes: number;
+  feePerSeat: number;
+  forbidAfterExit: boolean;
+  // Share of the fare given back to a passenger marked as a no-show, 0 to 100
+  noShowRefundPercent: number;
+}
+
+export interface ReprintPolicy {
+  // Reprints allowed per ticket; null for no limit
+  maxReprints: number | null;
+  // Charged on every reprint; 0 for free reprints
+  fee: number;
+}
+
+export interface TicketReprint {
+  id: string;
+  ticketKind: 'BOOKING' | 'ENTRY' | 'EXIT' | 'EXIT_PASS' | 'DAY_PASS';
+  ticketRef: string;
+  reprintNumber: number;
+  fee: number;
+  createdBy: string | null;
+  staffName: string;
+  createdAt: string;
+}
+
+export interface PendingAnprEntry {
+  queueId: string;
+  licensePlate: string;
+  destinationId: string;
+  destinationName: string;
+  queuePosition: number;
+  enteredAt: string;
+}
+
+export interface QuickEntryPolicy {
+  // Scanning a plate in the add-vehicle panel queues it for its default destination
+  enabled: boolean;
+}
+
+export interface QuickEntry {
+  queueId: string;
+  licensePlate: string;
+  destinationId: string;
+  destinationName: string;
+}
+
+export interface SelfCheckInPolicy {
+  enabled: boolean;
+  // Gate printer address; null prints on the kiosk's own printer
+  gatePrinterIp: string | null;
+  gatePrinterPort: number;
+}
+
+export interface SelfCheckIn {
+  queueId: string;
+  licensePlate: string;
+  destinationName: string;
+  queuePosition: number;
+  printerName: string;
+}
+
+export type RetentionTable = 'exit_passes' | 'audit_log' | 'queue_events' | 'printed_tickets';
+
+export interface RetentionPolicy {
+  enabled: boolean;
+  // HH:MM:SS, Tunis time after which the nightly cleanup runs
+  runAt: string;
+  // Days kept by table, at least 30; a table not listed is kept forever
+  days: Partial<Record<RetentionTable, number>>;
+}
+
+export interface RetentionReport {
+  dryRun: boolean;
+  tables: { table: RetentionTable; days: number; before: string; rows: number }[];
+  totalRows: number;
+}
+
+export interface PrintedTicketFilters {
+  // 'entry', 'exit_pass', 'day_pass', 'booking'...
+  ticketType?: string;
+  ticketNumber?: string;
+  staffName?: string;
+  printer?: string;
+  // Anywhere on the printed ticket: a plate, a destination
+  text?: string;
+  // YYYY-MM-DD
+  from?: string;
+  to?: string;
+  limit?: number;
+}
+
+export interface PrintedTicket {
+  id: string;
+  jobId: string;
+  ticketType: string;
+  ticketNumber: string | null;
+  staffName: string | null;
+  printerId: string;
+  printerName: string;
+  duplicateNumber: number | null;
+  payload: string;
+  renderedText: string;
+  width: number;
+  status: 'PRINTED' | 'ESCROWED' | 'SENT';
+  // When an escrowed ticket was printed or texted, and the number it went to
+  deliveredAt: string | null;
+  deliveredTo: string | null;
+  // When it printed, or was issued for an escrowed ticket
+  printedAt: string;
+}
+
+// Tickets are kept instead of printed while enabled
+export interface NoPrinterMode {
+  enabled: boolean;
+}
+
+export interface VoidedTicket {
+  id: string;
+  ticketKind: 'ENTRY' | 'EXIT_PASS' | 'DAY_PASS';
+  ticketNumber: string;
+  reason: string;
+  // The number the ticket was reprinted under, once it was
+  replacedBy: string | null;
+  createdBy: string | null;
+  staffName: string;
+  createdAt: string;
+}
+
+export interface CancellationFee {
+  id: string;
+  bookingId: string;
+  verificationCode: string;
+  destinationName: string;
+  licensePlate: string;
+  seats: number;
+  refunded: number;
+  fee: number;
+  createdBy: string | null;
+  staffName: string;
+  createdAt: string;
+}
+
+export type QueueEventKind = 'ENTERED' | 'BOOKED' | 'CANCELLED' | 'MOVED' | 'UPDATED' | 'DEPARTED' | 'REMOVED';
+
+// A vehicle_queue entry as it was right after the change
+export interface QueueEvent {
+  seq: number;
+  queueId: string;
+  kind: QueueEventKind;
+  vehicleId: string;
+  licensePlate: string;
+  destinationId: string;
+  destinationName: string;
+  subRoute: string | null;
+  queuePosition: number;
+  status: string;
+  availableSeats: number;
+  totalSeats: number;
+  occurredAt: string;
+}
+
+export interface QueueReplay {
+  destinationId: string;
+  at: string;
+  entries: QueueEvent[];
+  events: QueueEvent[];
+}
+
+export type SearchKind = 'BOOKING' | 'EXIT_PASS' | 'DAY_PASS';
+
+export interface SearchHit {
+  kind: SearchKind;
+  id: string;
+  reference: string;
+  licensePlate: string;
+  destinationName: string;
+  seats: number | null;
+  amount: number;
+  // Given back by cancellations, no-shows and refunds
+  refunded: number;
+  status: string;
+  staffName: string;
+  at: string;
+  matched: 'verificationCode' | 'licensePlate' | 'id' | 'staffName';
+}
+
+// Row-by-row changes of a maintenance command, returned before (dryRun) or after applying
+export interface RowChange {
+  table: string;
+  id: string;
+  label: string;
+  action: 'INSERT' | 'UPDATE' | 'DELETE';
+  before: any;
+  after: any;
+}
+
+export interface MaintenanceDiff {
+  dryRun: boolean;
+  changes: RowChange[];
+}
+
+export type ClosingMode = 'PURGE' | 'CARRY_OVER';
+
+// closingTime is HH:MM:SS, Tunis time
+export interface QueueClosingPolicy {
+  enabled: boolean;
+  mode: ClosingMode;
+  closingTime: string;
+}
+
+// opensAt and closesAt are HH:MM:SS, Tunis time
+export interface BusinessHours {
+  enabled: boolean;
+  opensAt: string;
+  closesAt: string;
+}
+
+export interface BusinessHoursStatus {
+  hours: BusinessHours;
+  open: boolean;
+  unlockedUntil: string | null;
+  terminalId: string | null;
+}
+
+export interface VehicleCapacityChange {
+  vehicleId: string;
+  licensePlate: string;
+  previousCapacity: number;
+  capacity: number;
+  queueId: string | null;
+  availableSeats: number | null;
+}
+
+export interface DestinationMatch {
+  stationId: string;
+  stationName: string;
+  stationNameAr: string | null;
+  governorate: string | null;
+  delegation: string | null;
+  basePrice: number;
+  matched: string;
+  score: number;
+}
+
+export interface TrainingStatus {
+  active: boolean;
+  seeded: boolean;
+}
+
+export interface TrainingSeed {
+  tables: number;
+  rows: number;
+}
+
+export interface ClosingVehicle {
+  vehicleId: string;
+  licensePlate: string;
+  destinationId: string;
+  destinationName: string;
+  subRouteName: string | null;
+  queuePosition: number;
+  enteredAt: string | null;
+}
+
+export interface CarryOverPreview {
+  policy: QueueClosingPolicy;
+  serviceDate: string;
+  closesAt: string;
+  vehicles: ClosingVehicle[];
+}
+
+export interface QueueClosing {
+  serviceDate: string;
+  mode: ClosingMode;
+  closedAt: string;
+  vehicles: ClosingVehicle[];
+}
+
+export interface SeatTransfer {
+  seats: number;
+  bookingIds: string[];
+  fromLicensePlate: string;
+  toLicensePlate: string;
+  ticketsReprinted: number;
+  message: string;
+}
+
+export interface DemandSlot {
+  // Start of the slot, HH:MM
+  slot: string;
+  seatsSold: number;
+  averageSeats: number;
+  peakSeats: number;
+}
+
+export interface DemandProfile {
+  destinationId: string;
+  destinationName: string;
+  weekday: number;
+  weeks: number;
+  days: string[];
+  slots: DemandSlot[];
+  seatsSold: number;
+  busiestSlot: string | null;
+  annotations: CalendarAnnotation[];
+}
+
+export interface DispatchedVehicle {
+  queueId: string;
+  exitPassId: string;
+  licensePlate: string;
+  seatsSold: number;
+  amountCollected: number;
+  netToDriver: number;
+  tripNumber: number;
+}
+
+export interface DispatchSummary {
+  destinationId: string;
+  destinationName: string;
+  vehicles: DispatchedVehicle[];
+  seatsSold: number;
+  amountCollected: number;
+  netToDriver: number;
+  // Plates left in the queue because nothing was sold on them
+  leftInQueue: string[];
+  printBatchId: string | null;
+  printError: string | null;
+  message: string;
+}
+
+// The vehicle by id or by plate; recordedAt defaults to now
+export interface PositionReport {
+  vehicleId?: string;
+  licensePlate?: string;
+  latitude: number;
+  longitude: number;
+  speedKmh?: number;
+  heading?: number;
+  recordedAt?: string;
+}
+
+export interface VehiclePosition {
+  vehicleId: string;
+  licensePlate: string;
+  latitude: number;
+  longitude: number;
+  speedKmh: number | null;
+  heading: number | null;
+  recordedAt: string;
+  minutesSinceReport: number;
+  inQueue: boolean;
+  lastExitAt: string | null;
+  lastDestinationName: string | null;
+  typicalTripMinutes: number | null;
+  // Last exit pass plus the usual round trip, for a vehicle not back in the queue yet
+  estimatedReturnAt: string | null;
+  overdue: boolean;
+}
+
+// Minutes; null when no round trip was seen
+export interface TripDurationStats {
+  destinationId: string;
+  destinationName: string;
+  days: number;
+  trips: number;
+  medianMinutes: number | null;
+  averageMinutes: number | null;
+  p90Minutes: number | null;
+  shortestMinutes: number | null;
+  longestMinutes: number | null;
+}
+
+export interface LoadingTimeoutPolicy {
+  enabled: boolean;
+  defaultMinutes: number;
+  // Minutes by destination id, overriding the default; 0 never flags the destination
+  destinations: Record<string, number>;
+}
+
+export interface StalledVehicle {
+  queueId: string;
+  licensePlate: string;
+  destinationId: string;
+  destinationName: string;
+  subRouteName?: string | null;
+  queuePosition: number;
+  bookedSeats: number;
+  totalSeats: number;
+  loadingSince: string;
+  minutesLoading: number;
+  timeoutMinutes: number;
+  message: string;
+}
+
+export type TripPeriod = 'day' | 'week' | 'month';
+
+export interface VehicleTripCounts {
+  vehicleId: string;
+  from: string;
+  to: string;
+  trips: number;
+  byDestination: { destinationId: string; destinationName: string; trips: number }[];
+}
+
+// Vehicles sharing a trip count share a rank
+export interface TripLeaderboardEntry {
+  rank: number;
+  vehicleId: string;
+  licensePlate: string;
+  trips: number;
 }
 
 // New TypeScript interfaces for the enhanced queue management
@@ -354,6 +2081,16 @@ export interface VehicleDto {
   createdAt?: string;
 }
 
+export interface GovernorateNode {
+  name: string;
+  nameAr: string | null;
+  delegations: {
+    name: string;
+    nameAr: string | null;
+    stations: { stationId: string; stationName: string; basePrice: number }[];
+  }[];
+}
+
 export interface DestinationDto {
   stationId: string;
   stationName: string;
@@ -373,6 +2110,7 @@ export interface BookingDestinationDto {
   governorateAr?: string | null;
   delegation?: string | null;
   delegationAr?: string | null;
+  suspension?: DestinationSuspension | null;
 }
 
 export interface VehicleQueueStatusDto {
@@ -389,6 +2127,36 @@ export interface VehicleQueueStatusDto {
   enteredAt: string;
 }
 
+export interface CarriedBookingDto {
+  id: string;
+  verificationCode: string;
+  destinationName: string;
+  seatsBooked: number;
+  totalAmount: number;
+  paymentStatus: string;
+  createdAt: string;
+}
+
+// Everything the vehicle window shows, from db_get_vehicle_profile
+export interface VehicleProfileDto {
+  vehicle: VehicleDto;
+  authorizations: AuthorizedDestinationDto[];
+  queueStatus: VehicleQueueStatusDto | null;
+  activity72h: Array<{ eventType: string; timestamp: string; destinationName?: string | null }>;
+  todayDayPass: {
+    id: string;
+    vehicleId: string;
+    licensePlate: string;
+    passType: string;
+    price: number;
+    purchaseDate: string;
+    validFrom: string;
+    validUntil: string;
+    isActive: boolean;
+  } | null;
+  recentBookings: CarriedBookingDto[];
+}
+
 // Realtime event interfaces
 export interface RealtimeEvent {
   event_type: string;
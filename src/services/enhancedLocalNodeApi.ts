@@ -1,6 +1,7 @@
 import { invoke } from '@tauri-apps/api/tauri';
 import { getLocalStorage, setLocalStorage } from '../lib/storage';
 import { SERVER_CONFIG } from '../config/server';
+import type { ProxyResponse } from '../lib/api';
 
 // Enhanced API configuration
 const DEFAULT_CONFIG = {
@@ -427,13 +428,14 @@ class EnhancedLocalNodeApiService {
       }
 
       // Call Rust proxy
-      const responseText = await invoke<string>('proxy_localnode', {
+      const proxyResponse = await invoke<ProxyResponse>('proxy_localnode', {
         method,
         endpoint,
         body,
         serverUrl: targetServerUrl,
         headers
       });
+      const responseText = proxyResponse.body;
 
       console.log(`🔍 Enhanced Proxy Response:`, responseText);
 
@@ -766,12 +768,15 @@ class EnhancedLocalNodeApiService {
       console.log('🔍 Checking enhanced connection via proxy to /health');
       console.log('🔍 Server URL:', targetServerUrl);
       
-      const responseText = await invoke<string>('proxy_localnode', {
+      const proxyResponse = await invoke<ProxyResponse>('proxy_localnode', {
         method: 'GET',
         endpoint: '/health',
         body: null,
-        serverUrl: targetServerUrl
+        serverUrl: targetServerUrl,
+        // A health check should answer fast; one retry rides out a dropped connection
+        options: { timeoutMs: 3000, retries: 1 }
       });
+      const responseText = proxyResponse.body;
       
       const data = JSON.parse(responseText);
       return data.status === 'ok';
@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/tauri';
+import { listen } from '@tauri-apps/api/event';
 import { getLocalStorage } from '../lib/storage';
 
 export interface PrinterConfig {
@@ -11,6 +12,18 @@ export interface PrinterConfig {
   model: string;
   enabled: boolean;
   is_default: boolean;
+  backend?: 'network' | 'spooler';
+  system_name?: string | null;
+  paper?: PaperProfile | null;
+  code_page?: 'cp437' | 'cp850' | 'cp858';
+  // Backup printers tried in order when this one fails
+  failover?: PrinterConfig[];
+}
+
+export interface PaperProfile {
+  chars_per_line: number;
+  feed_lines: number;
+  cut: 'full' | 'partial';
 }
 
 export interface PrintJob {
@@ -33,6 +46,88 @@ export interface PrintQueueStatus {
   is_processing: boolean;
   last_printed_at?: string;
   failed_jobs: number;
+  backlog: number;
+  capacity: number;
+}
+
+export interface PrintBacklog {
+  depth: number;
+  capacity: number;
+  full: boolean;
+}
+
+// How a batch of jobs printed together went (ticket and talon, exit pass and settlement slip)
+export interface PrintBatchReport {
+  batchId: string;
+  printed: string[];
+  failed: string[];
+  error?: string;
+}
+
+export interface PrinterDayUsage {
+  date: string;
+  jobs: number;
+  bytes: number;
+  lines: number;
+  cuts: number;
+}
+
+export interface QuietHours {
+  enabled: boolean;
+  start: string; // HH:MM:SS, Tunis time
+  end: string; // held reports print at this time
+}
+
+export interface PrintSchedule {
+  quietHours: QuietHours;
+  maintenance: { printerId: string; until: string }[];
+  heldJobs: number;
+}
+
+// Control sequences and limits of a printer model; PrinterConfig.model picks one
+export interface PrinterModelProfile {
+  model: string;
+  init: number[];
+  fullCut: number[];
+  partialCut: number[];
+  drawerKick: number[];
+  codePages: string[];
+  maxWidth: number;
+}
+
+// Raw ESC/POS sequence saved by a technician
+export interface EscposMacro {
+  id: string;
+  name: string;
+  description?: string;
+  base64Bytes: string;
+  size: number;
+  updatedByName?: string;
+  updatedAt: string;
+}
+
+// Copies printed of each ticket type, by type name ("ExitPassTicket"); unlisted types print once
+export interface PrintCopies {
+  copies: Record<string, number>;
+}
+
+export interface QueueBoardSchedule {
+  enabled: boolean;
+  destinationIds: string[];
+  everyMinutes: number;
+  from: string; // HH:MM:SS, Tunis time
+  until: string;
+}
+
+export interface PrinterUsageStats {
+  printerId: string;
+  days: PrinterDayUsage[];
+  rollLengthM: number;
+  usedM: number;
+  remainingM: number;
+  lowThresholdM: number;
+  paperLow: boolean;
+  rollChangedAt?: string;
 }
 
 export enum PrintJobType {
@@ -169,6 +264,18 @@ export class ThermalPrinterService {
     throw new Error('Removing printers is not supported. Printer configuration is managed via environment variables.');
   }
 
+  /**
+   * List printers installed through the Windows spooler
+   */
+  async listSystemPrinters(): Promise<string[]> {
+    try {
+      return await invoke<string[]>('list_system_printers');
+    } catch (error) {
+      console.error('Failed to list system printers:', error);
+      throw error;
+    }
+  }
+
   /**
    * Test printer connection
    */
@@ -181,6 +288,18 @@ export class ThermalPrinterService {
     }
   }
 
+  /**
+   * Print a diagnostic page (configuration, firmware status, character set)
+   */
+  async printDiagnosticPage(): Promise<string> {
+    try {
+      return await invoke<string>('print_diagnostic_page');
+    } catch (error) {
+      console.error('Failed to print diagnostic page:', error);
+      throw error;
+    }
+  }
+
   /**
    * Test printer connection with specific IP and port
    */
@@ -553,9 +672,10 @@ Date: ${paymentData.date}
   }
 
   /**
-   * Print booking ticket with thermal printer
+   * Print booking ticket with thermal printer; with talon data, the talon is printed
+   * right after the ticket in the same batch
    */
-  async printBookingTicket(ticketData: string, staffName?: string): Promise<string> {
+  async printBookingTicket(ticketData: string, staffName?: string, talonData?: string): Promise<string> {
     console.log('🖨️ ThermalPrinterService.printBookingTicket called');
     console.log('📄 Ticket data:', ticketData);
     console.log('👤 Staff name:', staffName);
@@ -563,6 +683,7 @@ Date: ${paymentData.date}
       console.log('📡 Calling Tauri command: print_booking_ticket');
       const result = await invoke<string>('print_booking_ticket', { 
         ticketData, 
+        talonData: talonData ?? null,
         staffName: staffName || null 
       });
       console.log('✅ Tauri command result:', result);
@@ -670,37 +791,37 @@ Date: ${paymentData.date}
     }
   }
 
-  // Reprint last tickets (cached in backend)
-  async reprintLastBooking(): Promise<string> {
+  // Reprint last tickets (cached in backend); the reply names the duplicate number and any fee
+  async reprintLastBooking(staffId?: string): Promise<string> {
     try {
-      return await invoke<string>('reprint_booking_ticket');
+      return await invoke<string>('reprint_booking_ticket', { staffId: staffId || null });
     } catch (error) {
       console.error('Failed to reprint last booking ticket:', error);
       throw error;
     }
   }
 
-  async reprintLastEntry(): Promise<string> {
+  async reprintLastEntry(staffId?: string): Promise<string> {
     try {
-      return await invoke<string>('reprint_entry_ticket');
+      return await invoke<string>('reprint_entry_ticket', { staffId: staffId || null });
     } catch (error) {
       console.error('Failed to reprint last entry ticket:', error);
       throw error;
     }
   }
 
-  async reprintLastExit(): Promise<string> {
+  async reprintLastExit(staffId?: string): Promise<string> {
     try {
-      return await invoke<string>('reprint_exit_ticket');
+      return await invoke<string>('reprint_exit_ticket', { staffId: staffId || null });
     } catch (error) {
       console.error('Failed to reprint last exit ticket:', error);
       throw error;
     }
   }
 
-  async reprintLastDayPass(): Promise<string> {
+  async reprintLastDayPass(staffId?: string): Promise<string> {
     try {
-      return await invoke<string>('reprint_day_pass_ticket');
+      return await invoke<string>('reprint_day_pass_ticket', { staffId: staffId || null });
     } catch (error) {
       console.error('Failed to reprint last day pass ticket:', error);
       throw error;
@@ -740,11 +861,27 @@ Date: ${paymentData.date}
     // Price breakdown - always show base price + service fees = total
     const basePrice = booking.basePrice || booking.baseAmount || 0;
     const serviceFee = 0.200; // Fixed 0.200 TND service fee per seat
-    const totalPrice = basePrice + serviceFee;
+    // Child fares and luggage supplements, per ticket
+    const modifiers: { label: string; quantity: number; amount: number }[] = booking.fareModifiers || [];
+    const totalPrice = basePrice + serviceFee + modifiers.reduce((sum, m) => sum + m.amount, 0);
     
     ticketContent += `Prix de base: ${basePrice.toFixed(3)} TND\n`;
     ticketContent += `Frais de service: ${serviceFee.toFixed(3)} TND\n`;
+    for (const modifier of modifiers) {
+      ticketContent += `${modifier.label} x${modifier.quantity}: ${modifier.amount > 0 ? '+' : ''}${modifier.amount.toFixed(3)} TND\n`;
+    }
     ticketContent += `Total: ${totalPrice.toFixed(3)} TND\n`;
+
+    if (booking.paymentMethod) {
+      const paymentLabels: Record<string, string> = {
+        CASH: 'Espèces',
+        CARD: 'Carte',
+        E_WALLET: 'E-wallet',
+        ON_ACCOUNT: 'Sur compte',
+        SPLIT: 'Mixte',
+      };
+      ticketContent += `Paiement: ${paymentLabels[booking.paymentMethod] || booking.paymentMethod}\n`;
+    }
     
     // Booking type - removed as requested
     
@@ -1043,6 +1180,89 @@ Date: ${paymentData.date}
     }
   }
 
+  onBacklogUpdate(callback: (backlog: PrintBacklog) => void) {
+    return listen<PrintBacklog>('print-backlog-update', (event) => {
+      callback(event.payload);
+    });
+  }
+
+  onPrintBatchFinished(callback: (report: PrintBatchReport) => void) {
+    return listen<PrintBatchReport>('print-batch-finished', (event) => {
+      callback(event.payload);
+    });
+  }
+
+  // Paper used per day and the estimated length left on the roll (current printer by default)
+  async getPrinterUsageStats(printerId?: string): Promise<PrinterUsageStats> {
+    return invoke<PrinterUsageStats>('get_printer_usage_stats', { printerId });
+  }
+
+  // Call after loading a new roll; without a length the configured default is assumed
+  async setPrinterPaperRoll(printerId?: string, rollLengthM?: number): Promise<PrinterUsageStats> {
+    return invoke<PrinterUsageStats>('set_printer_paper_roll', { printerId, rollLengthM });
+  }
+
+  // Hold a printer's jobs while it is serviced; they print as soon as it is switched back
+  async setPrinterMaintenance(printerId: string, on: boolean): Promise<PrintSchedule> {
+    return invoke<PrintSchedule>('set_printer_maintenance', { printerId, on });
+  }
+
+  async setPrintQuietHours(quietHours: QuietHours): Promise<PrintSchedule> {
+    return invoke<PrintSchedule>('set_print_quiet_hours', { quietHours });
+  }
+
+  async getPrintSchedule(): Promise<PrintSchedule> {
+    return invoke<PrintSchedule>('get_print_schedule');
+  }
+
+  async getPrinterModels(): Promise<PrinterModelProfile[]> {
+    return invoke<PrinterModelProfile[]>('get_printer_models');
+  }
+
+  // Raw ESC/POS, admins only
+  async sendRawEscpos(printerId: string, base64Bytes: string, staffId?: string): Promise<string> {
+    return invoke<string>('send_raw_escpos', { printerId, base64Bytes, staffId });
+  }
+
+  async listEscposMacros(): Promise<EscposMacro[]> {
+    return invoke<EscposMacro[]>('list_escpos_macros');
+  }
+
+  async saveEscposMacro(name: string, base64Bytes: string, description?: string, staffId?: string): Promise<EscposMacro> {
+    return invoke<EscposMacro>('save_escpos_macro', { name, base64Bytes, description, staffId });
+  }
+
+  async runEscposMacro(printerId: string, name: string, staffId?: string): Promise<string> {
+    return invoke<string>('run_escpos_macro', { printerId, name, staffId });
+  }
+
+  async getPrintCopies(): Promise<PrintCopies> {
+    return invoke<PrintCopies>('get_print_copies');
+  }
+
+  async setPrintCopies(copies: PrintCopies, staffId?: string): Promise<PrintCopies> {
+    return invoke<PrintCopies>('set_print_copies', { copies, staffId });
+  }
+
+  // Ordered queue of a destination, printed wide for the lane entrance
+  async printQueueBoard(destinationId: string, staffName?: string): Promise<string> {
+    return invoke<string>('print_queue_board', { destinationId, staffName });
+  }
+
+  async getQueueBoardSchedule(): Promise<QueueBoardSchedule> {
+    return invoke<QueueBoardSchedule>('get_queue_board_schedule');
+  }
+
+  async setQueueBoardSchedule(schedule: QueueBoardSchedule, staffId?: string): Promise<QueueBoardSchedule> {
+    return invoke<QueueBoardSchedule>('set_queue_board_schedule', { schedule, staffId });
+  }
+
+  onPaperLow(callback: (stats: PrinterUsageStats) => void) {
+    return listen<PrinterUsageStats>('printer-paper-low', (event) => {
+      callback(event.payload);
+    });
+  }
+
   async getPrintQueueLength(): Promise<number> {
     try {
       const length = await invoke<number>('get_print_queue_length');